@@ -0,0 +1,293 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    FnArg,
+    GenericArgument,
+    Ident,
+    ItemTrait,
+    LitStr,
+    Pat,
+    PathArguments,
+    ReturnType,
+    Token,
+    TraitItem,
+    TraitItemFn,
+    Type,
+};
+
+/// The name of the attribute of the [`host_api`](crate::host_api) macro.
+const ATTRIBUTE: &str = "host_api";
+
+/// The arguments of the [`host_api`](crate::host_api) attribute on a trait.
+pub struct Args {
+    /// The import module name of all host functions of the trait.
+    module: Option<LitStr>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut module = None;
+        for (key, value) in parse_fields(input)? {
+            if key != "module" {
+                return Err(syn::Error::new(key.span(), "unknown `host_api` field"));
+            }
+            if module.replace(value).is_some() {
+                return Err(syn::Error::new(key.span(), "duplicate `host_api` field"));
+            }
+        }
+        Ok(Self { module })
+    }
+}
+
+/// Parses comma separated `key = "value"` fields.
+fn parse_fields(input: ParseStream) -> syn::Result<Punctuated<(Ident, LitStr), Token![,]>> {
+    Punctuated::parse_terminated_with(input, |input| {
+        let key = input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        Ok((key, input.parse::<LitStr>()?))
+    })
+}
+
+/// A host function of a trait annotated with [`host_api`](crate::host_api).
+struct HostFunc {
+    /// The name of the trait method.
+    ident: Ident,
+    /// The import name of the host function.
+    name: String,
+    /// The parameters of the host function.
+    params: Vec<(Ident, Type)>,
+    /// The return type of the trait method.
+    output: TokenStream,
+    /// The type returned to the guest if any.
+    guest_result: Option<Type>,
+}
+
+impl HostFunc {
+    /// Creates a [`HostFunc`] from the trait `method` and strips its `host_api` attributes.
+    fn new(method: &mut TraitItemFn) -> syn::Result<Self> {
+        let sig = &method.sig;
+        let ident = sig.ident.clone();
+        if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "host functions must not be generic or async",
+            ));
+        }
+        match sig.inputs.first() {
+            Some(FnArg::Receiver(receiver))
+                if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    sig,
+                    "host functions must take `&mut self` as their first parameter",
+                ))
+            }
+        }
+        let params = sig
+            .inputs
+            .iter()
+            .skip(1)
+            .enumerate()
+            .map(|(index, input)| match input {
+                FnArg::Typed(typed) => {
+                    let ident = match &*typed.pat {
+                        Pat::Ident(pat) => pat.ident.clone(),
+                        _ => format_ident!("a{index}"),
+                    };
+                    Ok((ident, (*typed.ty).clone()))
+                }
+                FnArg::Receiver(receiver) => Err(syn::Error::new_spanned(
+                    receiver,
+                    "unexpected receiver parameter",
+                )),
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        let (output, guest_result) = match &sig.output {
+            ReturnType::Default => (quote!(()), None),
+            ReturnType::Type(_, ty) => (ty.to_token_stream(), guest_result(ty)),
+        };
+        let mut name = ident.to_string();
+        let mut error = None;
+        method.attrs.retain(|attr| {
+            if !attr.path().is_ident(ATTRIBUTE) {
+                return true;
+            }
+            let renamed = attr.parse_args_with(parse_fields).and_then(|fields| {
+                for (key, value) in fields {
+                    if key != "name" {
+                        return Err(syn::Error::new(key.span(), "unknown `host_api` field"));
+                    }
+                    name = value.value();
+                }
+                Ok(())
+            });
+            if let Err(renamed) = renamed {
+                error.get_or_insert(renamed);
+            }
+            false
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+        Ok(Self {
+            ident,
+            name,
+            params,
+            output,
+            guest_result,
+        })
+    }
+
+    /// Generates the definition of the host function within `add_to_linker`.
+    fn generate_define(&self, module: &str, trait_ident: &Ident) -> TokenStream {
+        let Self {
+            ident,
+            name,
+            params,
+            output,
+            ..
+        } = self;
+        let args = params.iter().map(|(ident, _)| ident);
+        let params = params.iter().map(|(ident, ty)| quote!(#ident: #ty));
+        quote! {
+            linker.func_wrap(
+                #module,
+                #name,
+                move |mut caller: ::wasmi::Caller<'_, T>, #( #params ),*| -> #output {
+                    <U as #trait_ident>::#ident(get(caller.data_mut()), #( #args ),*)
+                },
+            )?;
+        }
+    }
+
+    /// Returns the guest-side Rust declaration of the host function.
+    fn guest_extern(&self) -> String {
+        let params = self
+            .params
+            .iter()
+            .map(|(ident, ty)| format!("{ident}: {}", type_string(ty)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let result = match &self.guest_result {
+            Some(ty) => format!(" -> {}", type_string(ty)),
+            None => String::new(),
+        };
+        let ident = self.ident.to_string();
+        let ident = ident.trim_start_matches("r#");
+        let link_name = match self.name != ident {
+            true => format!("    #[link_name = {:?}]\n", self.name),
+            false => String::new(),
+        };
+        format!("{link_name}    pub fn {ident}({params}){result};\n")
+    }
+}
+
+/// Returns the type returned to the guest for the trait method return type `ty`.
+///
+/// This unwraps `Result<T, E>` to `T` and returns `None` for `()`.
+fn guest_result(ty: &Type) -> Option<Type> {
+    let ty = match ty {
+        Type::Path(path) => {
+            let last = path.path.segments.last()?;
+            match (&last.ident, &last.arguments) {
+                (ident, PathArguments::AngleBracketed(args)) if ident == "Result" => {
+                    match args.args.first() {
+                        Some(GenericArgument::Type(ty)) => ty.clone(),
+                        _ => return Some(ty.clone()),
+                    }
+                }
+                _ => ty.clone(),
+            }
+        }
+        ty => ty.clone(),
+    };
+    match &ty {
+        Type::Tuple(tuple) if tuple.elems.is_empty() => None,
+        _ => Some(ty),
+    }
+}
+
+/// Returns the Rust source of `ty` without the spaces inserted by token streams.
+fn type_string(ty: &Type) -> String {
+    ty.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Expands the trait `item` annotated with [`host_api`](crate::host_api) with `args`.
+pub fn expand(args: Args, mut item: ItemTrait) -> syn::Result<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "`host_api` traits must not be generic",
+        ));
+    }
+    let module = args
+        .module
+        .as_ref()
+        .map(LitStr::value)
+        .unwrap_or_else(|| String::from("env"));
+    let mut funcs = Vec::new();
+    for trait_item in &mut item.items {
+        if let TraitItem::Fn(method) = trait_item {
+            funcs.push(HostFunc::new(method)?);
+        }
+    }
+    let trait_ident = &item.ident;
+    let vis = &item.vis;
+    let api = format_ident!("{trait_ident}HostApi");
+    let defines = funcs
+        .iter()
+        .map(|func| func.generate_define(&module, trait_ident));
+    let names = funcs.iter().map(|func| &func.name);
+    let guest_externs = format!(
+        "#[link(wasm_import_module = {module:?})]\nextern \"C\" {{\n{}}}\n",
+        funcs.iter().map(HostFunc::guest_extern).collect::<String>()
+    );
+    let api_docs = format!(" The `{module}` host API defined by the [`{trait_ident}`] trait.");
+    Ok(quote! {
+        #item
+
+        #[doc = #api_docs]
+        #[derive(Debug, Copy, Clone)]
+        #vis struct #api;
+
+        impl #api {
+            /// The import module name of all host functions.
+            pub const MODULE: &'static str = #module;
+
+            /// The import names of all host functions in the order of their definition.
+            pub const NAMES: &'static [&'static str] = &[ #( #names ),* ];
+
+            /// The `extern` block that declares all host functions in a Rust guest.
+            pub const GUEST_EXTERNS: &'static str = #guest_externs;
+
+            /// Defines all host functions in `linker`.
+            ///
+            /// The implementation of the host functions in the host state is accessed via `get`.
+            ///
+            /// # Errors
+            ///
+            /// If any of the host functions is already defined in `linker`.
+            pub fn add_to_linker<T, U>(
+                linker: &mut ::wasmi::Linker<T>,
+                get: fn(&mut T) -> &mut U,
+            ) -> ::core::result::Result<(), ::wasmi::Error>
+            where
+                T: 'static,
+                U: #trait_ident + 'static,
+            {
+                #( #defines )*
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Returns the error for a [`host_api`](crate::host_api) attribute that is not on a trait.
+pub fn not_a_trait() -> syn::Error {
+    syn::Error::new(
+        Span::call_site(),
+        "`host_api` can only be applied to traits",
+    )
+}
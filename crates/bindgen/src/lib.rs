@@ -0,0 +1,853 @@
+//! Generates typed Wasmi bindings for core Wasm modules from WIT.
+//!
+//! The [`bindgen!`] macro reads a WIT world and generates a host trait for its imports
+//! as well as typed call wrappers for its exports. The generated code follows the
+//! canonical ABI as used by core Wasm modules compiled for WIT worlds, e.g. via
+//! `wit-bindgen`, without requiring the component model.
+//!
+//! # Supported Types
+//!
+//! | WIT type                 | Rust type            |
+//! |:-------------------------|:---------------------|
+//! | `bool`                   | `bool`               |
+//! | `s8`, `s16`, `s32`, `s64`| `i8`, `i16`, `i32`, `i64` |
+//! | `u8`, `u16`, `u32`, `u64`| `u8`, `u16`, `u32`, `u64` |
+//! | `f32`, `f64`             | `f32`, `f64`         |
+//! | `char`                   | `char`               |
+//! | `string`                 | `String` or `&str`   |
+//! | `list<u8>`               | `Vec<u8>` or `&[u8]` |
+//!
+//! Functions may have at most one result and at most 16 flattened parameters.
+//! Other types such as records, variants or resources are not supported.
+//!
+//! The [`host_api`] attribute instead derives the `Linker` definitions of a host API
+//! from a Rust trait without any WIT.
+//!
+//! Usually this crate is used via the `wasmi::bindgen!` and `wasmi::host_api` re-exports
+//! of the `bindgen` feature.
+
+mod host_api;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use std::path::PathBuf;
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident,
+    LitStr,
+    Token,
+};
+use wit_parser::{
+    Function,
+    FunctionKind,
+    Resolve,
+    Results,
+    Type,
+    TypeDefKind,
+    World,
+    WorldItem,
+    WorldKey,
+};
+
+/// Generates typed bindings for a WIT world.
+///
+/// The WIT is either read from a file or directory path relative to the
+/// `CARGO_MANIFEST_DIR` or given inline:
+///
+/// ```ignore
+/// wasmi::bindgen!("wit/plugin.wit");
+/// wasmi::bindgen!({ path: "wit", world: "plugin" });
+/// wasmi::bindgen!({
+///     inline: "
+///         package example:plugin;
+///         world plugin {
+///             import log: func(message: string);
+///             export greet: func(name: string) -> string;
+///         }
+///     ",
+/// });
+/// ```
+///
+/// For a world named `plugin` this generates:
+///
+/// - A `PluginImports` trait with one method per imported function.
+/// - A `Plugin` struct with a `call_*` method per exported function.
+/// - `Plugin::add_to_linker` which defines all imports of the world in a `Linker`.
+/// - `Plugin::new` and `Plugin::instantiate` to create a `Plugin` from an instance.
+///
+/// Functions of imported or exported interfaces are prefixed by the interface name.
+#[proc_macro]
+pub fn bindgen(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as Input);
+    match input.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives the `Linker` definitions of a host API from a Rust trait.
+///
+/// Every method of the trait becomes a host function of the import module given
+/// via `module` which defaults to `"env"`. Methods must take `&mut self` followed
+/// by parameters of Wasm compatible types and return a Wasm compatible type, a tuple
+/// thereof or a `Result` with a `wasmi::Error` to trap. Methods may be renamed
+/// for the guest via `#[host_api(name = "...")]`:
+///
+/// ```ignore
+/// #[wasmi::host_api(module = "env")]
+/// pub trait Env {
+///     fn log(&mut self, value: i32);
+///     #[host_api(name = "get_time")]
+///     fn now(&mut self) -> Result<i64, wasmi::Error>;
+/// }
+/// ```
+///
+/// For a trait named `Env` this generates an `EnvHostApi` type with:
+///
+/// - `EnvHostApi::add_to_linker` which defines all host functions in a `Linker`.
+/// - `EnvHostApi::MODULE` and `EnvHostApi::NAMES` with the import names.
+/// - `EnvHostApi::GUEST_EXTERNS` with the `extern` block declaring the host functions in a Rust guest.
+#[proc_macro_attribute]
+pub fn host_api(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(args as host_api::Args);
+    let Ok(item) = syn::parse::<syn::ItemTrait>(item) else {
+        return host_api::not_a_trait().to_compile_error().into();
+    };
+    match host_api::expand(args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The input of the [`bindgen!`] macro.
+struct Input {
+    /// The path to a WIT file or directory relative to `CARGO_MANIFEST_DIR`.
+    path: Option<LitStr>,
+    /// The inline WIT source.
+    inline: Option<LitStr>,
+    /// The name of the world if the WIT package defines multiple worlds.
+    world: Option<LitStr>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Self {
+                path: Some(input.parse()?),
+                inline: None,
+                world: None,
+            });
+        }
+        let content;
+        braced!(content in input);
+        let mut this = Self {
+            path: None,
+            inline: None,
+            world: None,
+        };
+        let fields =
+            Punctuated::<(Ident, LitStr), Token![,]>::parse_terminated_with(&content, |input| {
+                let key = input.parse::<Ident>()?;
+                input.parse::<Token![:]>()?;
+                Ok((key, input.parse::<LitStr>()?))
+            })?;
+        for (key, value) in fields {
+            let field = match key.to_string().as_str() {
+                "path" => &mut this.path,
+                "inline" => &mut this.inline,
+                "world" => &mut this.world,
+                _ => return Err(syn::Error::new(key.span(), "unknown `bindgen!` field")),
+            };
+            if field.replace(value).is_some() {
+                return Err(syn::Error::new(key.span(), "duplicate `bindgen!` field"));
+            }
+        }
+        Ok(this)
+    }
+}
+
+impl Input {
+    /// Resolves the WIT world of the [`Input`] and generates its bindings.
+    fn expand(&self) -> syn::Result<TokenStream> {
+        let mut resolve = Resolve::default();
+        let mut tracked = Vec::new();
+        let package = match (&self.path, &self.inline) {
+            (Some(path), None) => {
+                let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+                let path = PathBuf::from(manifest_dir).join(path.value());
+                if path.is_file() {
+                    tracked.push(path.display().to_string());
+                }
+                resolve.push_path(&path).map(|(package, _)| package)
+            }
+            (None, Some(inline)) => resolve.push_str("inline.wit", &inline.value()),
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "`bindgen!` requires exactly one of `path` or `inline`",
+                ))
+            }
+        }
+        .map_err(|error| syn::Error::new(Span::call_site(), format!("{error:?}")))?;
+        let world = resolve
+            .select_world(package, self.world.as_ref().map(LitStr::value).as_deref())
+            .map_err(|error| syn::Error::new(Span::call_site(), format!("{error:?}")))?;
+        let bindings = Generator::new(&resolve, &resolve.worlds[world])?.generate();
+        Ok(quote! {
+            #( const _: &str = include_str!(#tracked); )*
+            #bindings
+        })
+    }
+}
+
+/// The WIT types supported by the generated bindings.
+#[derive(Debug, Copy, Clone)]
+enum Ty {
+    Bool,
+    S8,
+    U8,
+    S16,
+    U16,
+    S32,
+    U32,
+    S64,
+    U64,
+    F32,
+    F64,
+    Char,
+    String,
+    Bytes,
+}
+
+/// The maximum number of flattened core parameters of the canonical ABI.
+const MAX_FLAT_PARAMS: usize = 16;
+
+impl Ty {
+    /// Converts the WIT `ty` into a [`Ty`] if supported.
+    fn from_wit(resolve: &Resolve, ty: &Type) -> Option<Self> {
+        let ty = match ty {
+            Type::Bool => Self::Bool,
+            Type::S8 => Self::S8,
+            Type::U8 => Self::U8,
+            Type::S16 => Self::S16,
+            Type::U16 => Self::U16,
+            Type::S32 => Self::S32,
+            Type::U32 => Self::U32,
+            Type::S64 => Self::S64,
+            Type::U64 => Self::U64,
+            Type::F32 => Self::F32,
+            Type::F64 => Self::F64,
+            Type::Char => Self::Char,
+            Type::String => Self::String,
+            Type::Id(id) => match &resolve.types[*id].kind {
+                TypeDefKind::List(Type::U8) => Self::Bytes,
+                TypeDefKind::Type(ty) => return Self::from_wit(resolve, ty),
+                _ => return None,
+            },
+        };
+        Some(ty)
+    }
+
+    /// Returns `true` if values of the [`Ty`] are passed via guest memory.
+    fn is_indirect(self) -> bool {
+        matches!(self, Self::String | Self::Bytes)
+    }
+
+    /// Returns the core Wasm types of the flattened [`Ty`].
+    fn flat(self) -> &'static [CoreTy] {
+        match self {
+            Self::Bool
+            | Self::S8
+            | Self::U8
+            | Self::S16
+            | Self::U16
+            | Self::S32
+            | Self::U32
+            | Self::Char => &[CoreTy::I32],
+            Self::S64 | Self::U64 => &[CoreTy::I64],
+            Self::F32 => &[CoreTy::F32],
+            Self::F64 => &[CoreTy::F64],
+            Self::String | Self::Bytes => &[CoreTy::I32, CoreTy::I32],
+        }
+    }
+
+    /// Returns the owned Rust type of the [`Ty`].
+    fn owned(self) -> TokenStream {
+        match self {
+            Self::Bool => quote!(bool),
+            Self::S8 => quote!(i8),
+            Self::U8 => quote!(u8),
+            Self::S16 => quote!(i16),
+            Self::U16 => quote!(u16),
+            Self::S32 => quote!(i32),
+            Self::U32 => quote!(u32),
+            Self::S64 => quote!(i64),
+            Self::U64 => quote!(u64),
+            Self::F32 => quote!(f32),
+            Self::F64 => quote!(f64),
+            Self::Char => quote!(char),
+            Self::String => quote!(::std::string::String),
+            Self::Bytes => quote!(::std::vec::Vec<u8>),
+        }
+    }
+
+    /// Returns the borrowed Rust type of the [`Ty`] used for parameters of exports.
+    fn borrowed(self) -> TokenStream {
+        match self {
+            Self::String => quote!(&str),
+            Self::Bytes => quote!(&[u8]),
+            _ => self.owned(),
+        }
+    }
+
+    /// Returns code that lowers the Rust `value` into its core Wasm values.
+    ///
+    /// Indirect values are copied into guest memory via the `abi` variable and the `ctx` context.
+    fn lower(self, value: &TokenStream, ctx: &TokenStream) -> TokenStream {
+        match self {
+            Self::Bool | Self::S8 | Self::U8 | Self::S16 | Self::U16 => {
+                quote!(i32::from(#value))
+            }
+            Self::S32 | Self::S64 | Self::F32 | Self::F64 => quote!(#value),
+            Self::U32 => quote!(#value as i32),
+            Self::U64 => quote!(#value as i64),
+            Self::Char => quote!(u32::from(#value) as i32),
+            Self::String | Self::Bytes => {
+                let bytes = match self {
+                    Self::String => quote!(#value.as_bytes()),
+                    _ => quote!(&#value[..]),
+                };
+                quote! {{
+                    let slice = abi.write_bytes(&mut #ctx, #bytes)?;
+                    (slice.ptr as i32, slice.len as i32)
+                }}
+            }
+        }
+    }
+
+    /// Returns code that lifts the core Wasm `values` into the owned Rust type.
+    ///
+    /// Indirect values are read from guest memory via the `abi` variable and the `ctx` context.
+    fn lift(self, values: &[Ident], ctx: &TokenStream) -> TokenStream {
+        let value = &values[0];
+        match self {
+            Self::Bool => quote!(#value != 0),
+            Self::S8 => quote!(#value as i8),
+            Self::U8 => quote!(#value as u8),
+            Self::S16 => quote!(#value as i16),
+            Self::U16 => quote!(#value as u16),
+            Self::S32 | Self::S64 | Self::F32 | Self::F64 => quote!(#value),
+            Self::U32 => quote!(#value as u32),
+            Self::U64 => quote!(#value as u64),
+            Self::Char => quote! {
+                char::from_u32(#value as u32).ok_or_else(|| {
+                    ::wasmi::Error::new(format!("invalid char: {}", #value))
+                })?
+            },
+            Self::String | Self::Bytes => {
+                let len = &values[1];
+                let read = match self {
+                    Self::String => quote!(read_string),
+                    _ => quote!(read_bytes),
+                };
+                quote!(abi.#read(&#ctx, ::wasmi::GuestSlice::new(#value, #len))?)
+            }
+        }
+    }
+}
+
+/// Core Wasm value types.
+#[derive(Debug, Copy, Clone)]
+enum CoreTy {
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl CoreTy {
+    /// Returns the Rust type of the [`CoreTy`].
+    fn rust(self) -> TokenStream {
+        match self {
+            Self::I32 => quote!(i32),
+            Self::I64 => quote!(i64),
+            Self::F32 => quote!(f32),
+            Self::F64 => quote!(f64),
+        }
+    }
+}
+
+/// A function of a WIT world with its generated names.
+struct Func<'a> {
+    /// The WIT function.
+    func: &'a Function,
+    /// The core Wasm module name of an imported function.
+    module: String,
+    /// The core Wasm name of the function.
+    name: String,
+    /// The generated Rust name for the function.
+    ident: Ident,
+    /// The parameters of the function.
+    params: Vec<(Ident, Ty)>,
+    /// The optional result of the function.
+    result: Option<Ty>,
+}
+
+impl Func<'_> {
+    /// Returns the flattened core Wasm parameter types of the function.
+    fn flat_params(&self) -> Vec<CoreTy> {
+        self.params
+            .iter()
+            .flat_map(|(_, ty)| ty.flat().iter().copied())
+            .collect()
+    }
+
+    /// Returns `true` if the result is returned via a pointer to guest memory.
+    fn has_indirect_result(&self) -> bool {
+        self.result.is_some_and(Ty::is_indirect)
+    }
+
+    /// Returns `true` if the function accesses guest memory.
+    fn uses_memory(&self) -> bool {
+        self.params.iter().any(|(_, ty)| ty.is_indirect()) || self.has_indirect_result()
+    }
+
+    /// Returns the documentation of the function.
+    fn docs(&self) -> TokenStream {
+        let docs = self.func.docs.contents.as_deref().unwrap_or_default();
+        let docs = docs.lines().map(|line| format!(" {line}"));
+        quote!( #( #[doc = #docs] )* )
+    }
+}
+
+/// Generates the bindings of a WIT world.
+struct Generator<'a> {
+    /// The name of the world.
+    world: &'a str,
+    /// The imported functions of the world.
+    imports: Vec<Func<'a>>,
+    /// The exported functions of the world.
+    exports: Vec<Func<'a>>,
+}
+
+impl<'a> Generator<'a> {
+    /// Collects the imported and exported functions of `world`.
+    fn new(resolve: &'a Resolve, world: &'a World) -> syn::Result<Self> {
+        let imports = Self::collect(resolve, &world.imports)?;
+        let mut exports = Self::collect(resolve, &world.exports)?;
+        for export in &mut exports {
+            // Exported interface functions are named `{interface}#{func}` by the canonical ABI.
+            if export.module != "$root" {
+                export.name = format!("{}#{}", export.module, export.name);
+            }
+        }
+        Ok(Self {
+            world: &world.name,
+            imports,
+            exports,
+        })
+    }
+
+    /// Collects the functions of the world `items`.
+    fn collect(
+        resolve: &'a Resolve,
+        items: impl IntoIterator<Item = (&'a WorldKey, &'a WorldItem)>,
+    ) -> syn::Result<Vec<Func<'a>>> {
+        let mut funcs = Vec::new();
+        for (key, item) in items {
+            match item {
+                WorldItem::Function(func) => {
+                    funcs.push(Self::func(resolve, func, "$root", &func.name, None)?);
+                }
+                WorldItem::Interface { id, .. } => {
+                    let module = resolve.name_world_key(key);
+                    let prefix = match key {
+                        WorldKey::Name(name) => name.as_str(),
+                        WorldKey::Interface(_) => {
+                            resolve.interfaces[*id].name.as_deref().unwrap_or_default()
+                        }
+                    };
+                    for func in resolve.interfaces[*id].functions.values() {
+                        funcs.push(Self::func(
+                            resolve,
+                            func,
+                            &module,
+                            &func.name,
+                            Some(prefix),
+                        )?);
+                    }
+                }
+                WorldItem::Type(_) => {}
+            }
+        }
+        Ok(funcs)
+    }
+
+    /// Creates a [`Func`] for the WIT `func` of `module` with an optional name `prefix`.
+    fn func(
+        resolve: &Resolve,
+        func: &'a Function,
+        module: &str,
+        name: &str,
+        prefix: Option<&str>,
+    ) -> syn::Result<Func<'a>> {
+        let unsupported = |what: &str| {
+            syn::Error::new(
+                Span::call_site(),
+                format!("unsupported {what} in function `{name}` of `{module}`"),
+            )
+        };
+        if func.kind != FunctionKind::Freestanding {
+            return Err(unsupported("function kind"));
+        }
+        let params = func
+            .params
+            .iter()
+            .map(|(name, ty)| {
+                let ty = Ty::from_wit(resolve, ty)
+                    .ok_or_else(|| unsupported(&format!("type of parameter `{name}`")))?;
+                Ok((snake_ident(name), ty))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        let result = match &func.results {
+            Results::Anon(ty) => Some(ty),
+            Results::Named(results) => match &results[..] {
+                [] => None,
+                [(_, ty)] => Some(ty),
+                _ => return Err(unsupported("multiple results")),
+            },
+        };
+        let result = result
+            .map(|ty| Ty::from_wit(resolve, ty).ok_or_else(|| unsupported("result type")))
+            .transpose()?;
+        let ident = match prefix {
+            Some(prefix) => snake_ident(&format!("{prefix}-{name}")),
+            None => snake_ident(name),
+        };
+        let func = Func {
+            func,
+            module: module.into(),
+            name: name.into(),
+            ident,
+            params,
+            result,
+        };
+        if func.flat_params().len() > MAX_FLAT_PARAMS {
+            return Err(unsupported("number of parameters"));
+        }
+        Ok(func)
+    }
+
+    /// Generates the bindings of the world.
+    fn generate(&self) -> TokenStream {
+        let world = camel_ident(self.world);
+        let imports_trait = format_ident!("{world}Imports");
+        let trait_methods = self.imports.iter().map(|func| {
+            let docs = func.docs();
+            let ident = &func.ident;
+            let params = func.params.iter().map(|(name, ty)| {
+                let ty = ty.owned();
+                quote!(#name: #ty)
+            });
+            let result = func.result.map(Ty::owned).unwrap_or_else(|| quote!(()));
+            quote! {
+                #docs
+                fn #ident(&mut self, #( #params ),*) -> ::core::result::Result<#result, ::wasmi::Error>;
+            }
+        });
+        let import_defs = self
+            .imports
+            .iter()
+            .map(|func| self.generate_import(func, &imports_trait));
+        let add_to_linker = (!self.imports.is_empty()).then(|| {
+            quote! {
+                /// Defines all imports of the world in `linker`.
+                ///
+                /// The implementation of the imports in the host state is accessed via `get`.
+                ///
+                /// # Errors
+                ///
+                /// If any of the imports is already defined in `linker`.
+                pub fn add_to_linker<T, U>(
+                    linker: &mut ::wasmi::Linker<T>,
+                    get: fn(&mut T) -> &mut U,
+                ) -> ::core::result::Result<(), ::wasmi::Error>
+                where
+                    T: 'static,
+                    U: #imports_trait + 'static,
+                {
+                    #( #import_defs )*
+                    Ok(())
+                }
+            }
+        });
+        let uses_abi = self.exports.iter().any(Func::uses_memory);
+        let abi_field = uses_abi.then(|| quote!(abi: ::wasmi::GuestAbi,));
+        let abi_init = uses_abi.then(|| quote!(abi: ::wasmi::GuestAbi::new(&ctx, instance)?,));
+        let fields = self.exports.iter().map(|export| {
+            let ident = &export.ident;
+            let post = format_ident!("post_{}", export.ident);
+            let params = export.flat_params().into_iter().map(CoreTy::rust);
+            let result = export_result(export);
+            quote! {
+                #ident: ::wasmi::TypedFunc<( #( #params, )* ), #result>,
+                #post: ::core::option::Option<::wasmi::TypedFunc<#result, ()>>,
+            }
+        });
+        let inits = self.exports.iter().map(|export| {
+            let ident = &export.ident;
+            let post = format_ident!("post_{}", export.ident);
+            let name = &export.name;
+            let post_name = format!("cabi_post_{name}");
+            quote! {
+                #ident: instance.get_typed_func(&ctx, #name)?,
+                #post: match instance.get_func(&ctx, #post_name) {
+                    ::core::option::Option::Some(func) => ::core::option::Option::Some(func.typed(&ctx)?),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                },
+            }
+        });
+        let calls = self
+            .exports
+            .iter()
+            .map(|export| self.generate_export(export));
+        let struct_docs = format!(
+            " Typed bindings for the exports of the `{}` WIT world.",
+            self.world
+        );
+        let trait_docs = format!(
+            " The imports of the `{}` WIT world implemented by the host.",
+            self.world
+        );
+        quote! {
+            #[doc = #trait_docs]
+            pub trait #imports_trait {
+                #( #trait_methods )*
+            }
+
+            #[doc = #struct_docs]
+            #[derive(Debug, Copy, Clone)]
+            pub struct #world {
+                #abi_field
+                #( #fields )*
+            }
+
+            impl #world {
+                #add_to_linker
+
+                /// Creates the bindings for the exports of `instance`.
+                ///
+                /// # Errors
+                ///
+                /// If an export of the world is missing or has an unexpected type.
+                pub fn new(
+                    ctx: impl ::wasmi::AsContext,
+                    instance: &::wasmi::Instance,
+                ) -> ::core::result::Result<Self, ::wasmi::Error> {
+                    let ctx = ctx.as_context();
+                    Ok(Self {
+                        #abi_init
+                        #( #inits )*
+                    })
+                }
+
+                /// Instantiates `module` via `linker` and creates the bindings for its exports.
+                ///
+                /// # Errors
+                ///
+                /// - If instantiation of `module` fails.
+                /// - If an export of the world is missing or has an unexpected type.
+                pub fn instantiate<T>(
+                    mut ctx: impl ::wasmi::AsContextMut<Data = T>,
+                    linker: &::wasmi::Linker<T>,
+                    module: &::wasmi::Module,
+                ) -> ::core::result::Result<(Self, ::wasmi::Instance), ::wasmi::Error> {
+                    let instance = linker.instantiate(&mut ctx, module)?.start(&mut ctx)?;
+                    let bindings = Self::new(&ctx, &instance)?;
+                    Ok((bindings, instance))
+                }
+
+                #( #calls )*
+            }
+        }
+    }
+
+    /// Generates the definition of the imported `func` within `add_to_linker`.
+    fn generate_import(&self, func: &Func, imports_trait: &Ident) -> TokenStream {
+        let module = &func.module;
+        let name = &func.name;
+        let method = &func.ident;
+        let ctx = quote!(caller);
+        let mut core_params = Vec::new();
+        let mut lifts = Vec::new();
+        let mut args = Vec::new();
+        for (index, (_, ty)) in func.params.iter().enumerate() {
+            let values = (0..ty.flat().len())
+                .map(|n| format_ident!("p{index}_{n}"))
+                .collect::<Vec<_>>();
+            for (value, core) in values.iter().zip(ty.flat()) {
+                let core = core.rust();
+                core_params.push(quote!(#value: #core));
+            }
+            let arg = format_ident!("a{index}");
+            let lifted = ty.lift(&values, &ctx);
+            lifts.push(quote!(let #arg = #lifted;));
+            args.push(arg);
+        }
+        let abi = func
+            .uses_memory()
+            .then(|| quote!(let abi = ::wasmi::GuestAbi::from_caller(&caller)?;));
+        let call = quote!(<U as #imports_trait>::#method(get(caller.data_mut()), #( #args ),*)?);
+        let (result_ty, body) = match func.result {
+            None => (quote!(()), quote!(#call;)),
+            Some(ty) if ty.is_indirect() => {
+                core_params.push(quote!(retptr: i32));
+                let lowered = ty.lower(&quote!(result), &ctx);
+                (
+                    quote!(()),
+                    quote! {
+                        let result = #call;
+                        let (ptr, len) = #lowered;
+                        let mut bytes = [0x00_u8; 8];
+                        bytes[..4].copy_from_slice(&ptr.to_le_bytes());
+                        bytes[4..].copy_from_slice(&len.to_le_bytes());
+                        abi.memory().write(&mut caller, retptr as u32 as usize, &bytes)?;
+                    },
+                )
+            }
+            Some(ty) => {
+                let core = ty.flat()[0].rust();
+                let lowered = ty.lower(&quote!(result), &ctx);
+                (
+                    core,
+                    quote! {
+                        let result = #call;
+                        #lowered
+                    },
+                )
+            }
+        };
+        quote! {
+            linker.func_wrap(
+                #module,
+                #name,
+                move |mut caller: ::wasmi::Caller<'_, T>, #( #core_params ),*| -> ::core::result::Result<#result_ty, ::wasmi::Error> {
+                    #abi
+                    #( #lifts )*
+                    Ok({ #body })
+                },
+            )?;
+        }
+    }
+
+    /// Generates the `call_*` method of the exported `func`.
+    fn generate_export(&self, func: &Func) -> TokenStream {
+        let docs = func.docs();
+        let ident = &func.ident;
+        let post = format_ident!("post_{}", func.ident);
+        let method = format_ident!("call_{}", func.ident);
+        let ctx = quote!(ctx);
+        let params = func.params.iter().map(|(name, ty)| {
+            let ty = ty.borrowed();
+            quote!(#name: #ty)
+        });
+        let values = func
+            .params
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (_, ty))| {
+                (0..ty.flat().len()).map(move |n| format_ident!("p{index}_{n}"))
+            })
+            .collect::<Vec<_>>();
+        let lower_params = func.params.iter().enumerate().map(|(index, (name, ty))| {
+            let lowered = ty.lower(&quote!(#name), &ctx);
+            match ty.is_indirect() {
+                true => {
+                    let ptr = format_ident!("p{index}_0");
+                    let len = format_ident!("p{index}_1");
+                    quote!(let (#ptr, #len) = #lowered;)
+                }
+                false => {
+                    let value = format_ident!("p{index}_0");
+                    quote!(let #value = #lowered;)
+                }
+            }
+        });
+        let result_ty = func.result.map(Ty::owned).unwrap_or_else(|| quote!(()));
+        let result = match func.result {
+            None => quote!(()),
+            Some(ty) if ty.is_indirect() => {
+                let ptr = format_ident!("ptr");
+                let len = format_ident!("len");
+                let lifted = ty.lift(&[ptr, len], &ctx);
+                quote! {{
+                    let bytes = abi.read_bytes(&ctx, ::wasmi::GuestSlice { ptr: ret as u32, len: 8 })?;
+                    let ptr = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    let len = i32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+                    #lifted
+                }}
+            }
+            Some(ty) => ty.lift(&[format_ident!("ret")], &ctx),
+        };
+        let abi = func.uses_memory().then(|| quote!(let abi = &self.abi;));
+        quote! {
+            #docs
+            ///
+            /// # Errors
+            ///
+            /// If marshalling the parameters or results fails or if the execution traps.
+            pub fn #method(
+                &self,
+                mut ctx: impl ::wasmi::AsContextMut,
+                #( #params ),*
+            ) -> ::core::result::Result<#result_ty, ::wasmi::Error> {
+                #abi
+                #( #lower_params )*
+                let ret = self.#ident.call(&mut ctx, ( #( #values, )* ))?;
+                let result = #result;
+                if let ::core::option::Option::Some(post) = self.#post {
+                    post.call(&mut ctx, ret)?;
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+/// Returns the core Wasm result type of the exported `func`.
+fn export_result(func: &Func) -> TokenStream {
+    match func.result {
+        None => quote!(()),
+        Some(ty) if ty.is_indirect() => quote!(i32),
+        Some(ty) => ty.flat()[0].rust(),
+    }
+}
+
+/// Converts the WIT `name` into a snake case Rust identifier.
+fn snake_ident(name: &str) -> Ident {
+    let name = name.replace('-', "_");
+    match syn::parse_str::<Ident>(&name) {
+        Ok(ident) => ident,
+        Err(_) => Ident::new_raw(&name, Span::call_site()),
+    }
+}
+
+/// Converts the WIT `name` into a camel case Rust identifier.
+fn camel_ident(name: &str) -> Ident {
+    let name = name
+        .split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+    Ident::new(&name, Span::call_site())
+}
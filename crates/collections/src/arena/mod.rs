@@ -145,6 +145,14 @@ where
         self.entities.get_mut(index.into_usize())
     }
 
+    /// Returns an exclusive reference to the `len` consecutive entities starting at `start` if any.
+    #[inline]
+    pub fn get_many_mut(&mut self, start: Idx, len: usize) -> Option<&mut [T]> {
+        let start = start.into_usize();
+        let end = start.checked_add(len)?;
+        self.entities.get_mut(start..end)
+    }
+
     /// Returns an exclusive reference to the pair of entities at the given indices if any.
     ///
     /// Returns `None` if `fst` and `snd` refer to the same entity.
@@ -0,0 +1,164 @@
+//! Branchless helpers that evaluate secret values in constant time.
+//!
+//! These helpers are used by Wasmi's `hardened-dispatch` crate feature in order to
+//! execute Wasm `select` and integer comparison instructions without secret-dependent
+//! branches or table lookups in Wasmi itself.
+
+use core::hint::black_box;
+
+/// Returns a mask with all bits set if `condition` is `true` and no bits set otherwise.
+///
+/// # Note
+///
+/// The mask is passed through [`black_box`] so that the optimizer does not
+/// turn its users back into branches on `condition`.
+#[inline]
+pub fn mask(condition: bool) -> u64 {
+    black_box(u64::from(condition).wrapping_neg())
+}
+
+/// Returns `lhs` if `condition` is `true` and `rhs` otherwise.
+#[inline]
+pub fn select(condition: bool, lhs: u64, rhs: u64) -> u64 {
+    let mask = mask(condition);
+    (lhs & mask) | (rhs & !mask)
+}
+
+/// Returns the most significant bit of `value` as `bool`.
+#[inline]
+fn msb(value: u64) -> bool {
+    (value >> 63) == 1
+}
+
+/// Comparisons of integers that are evaluated without branches.
+pub trait ConstantTimeCmp: Copy {
+    /// Returns `true` if `self` is equal to `rhs`.
+    fn ct_eq(self, rhs: Self) -> bool;
+
+    /// Returns `true` if `self` is less than `rhs`.
+    fn ct_lt(self, rhs: Self) -> bool;
+
+    /// Returns `true` if `self` is not equal to `rhs`.
+    #[inline]
+    fn ct_ne(self, rhs: Self) -> bool {
+        !self.ct_eq(rhs)
+    }
+
+    /// Returns `true` if `self` is less than or equal to `rhs`.
+    #[inline]
+    fn ct_le(self, rhs: Self) -> bool {
+        !rhs.ct_lt(self)
+    }
+
+    /// Returns `true` if `self` is greater than `rhs`.
+    #[inline]
+    fn ct_gt(self, rhs: Self) -> bool {
+        rhs.ct_lt(self)
+    }
+
+    /// Returns `true` if `self` is greater than or equal to `rhs`.
+    #[inline]
+    fn ct_ge(self, rhs: Self) -> bool {
+        !self.ct_lt(rhs)
+    }
+}
+
+impl ConstantTimeCmp for u64 {
+    #[inline]
+    fn ct_eq(self, rhs: Self) -> bool {
+        let diff = black_box(self ^ rhs);
+        !msb(diff | diff.wrapping_neg())
+    }
+
+    #[inline]
+    fn ct_lt(self, rhs: Self) -> bool {
+        let (lhs, rhs) = (black_box(self), black_box(rhs));
+        msb((!lhs & rhs) | (!(lhs ^ rhs) & lhs.wrapping_sub(rhs)))
+    }
+}
+
+impl ConstantTimeCmp for i64 {
+    #[inline]
+    fn ct_eq(self, rhs: Self) -> bool {
+        (self as u64).ct_eq(rhs as u64)
+    }
+
+    #[inline]
+    fn ct_lt(self, rhs: Self) -> bool {
+        // Flipping the sign bits maps signed to unsigned order.
+        let flip = |value: i64| (value as u64) ^ (1 << 63);
+        flip(self).ct_lt(flip(rhs))
+    }
+}
+
+impl ConstantTimeCmp for u32 {
+    #[inline]
+    fn ct_eq(self, rhs: Self) -> bool {
+        u64::from(self).ct_eq(u64::from(rhs))
+    }
+
+    #[inline]
+    fn ct_lt(self, rhs: Self) -> bool {
+        u64::from(self).ct_lt(u64::from(rhs))
+    }
+}
+
+impl ConstantTimeCmp for i32 {
+    #[inline]
+    fn ct_eq(self, rhs: Self) -> bool {
+        i64::from(self).ct_eq(i64::from(rhs))
+    }
+
+    #[inline]
+    fn ct_lt(self, rhs: Self) -> bool {
+        i64::from(self).ct_lt(i64::from(rhs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_works() {
+        assert_eq!(select(true, 1, 2), 1);
+        assert_eq!(select(false, 1, 2), 2);
+        assert_eq!(select(true, u64::MAX, 0), u64::MAX);
+        assert_eq!(select(false, u64::MAX, 0), 0);
+    }
+
+    #[test]
+    fn cmp_matches_primitive_operators() {
+        let values = [
+            0,
+            1,
+            2,
+            -1,
+            -2,
+            i64::MIN,
+            i64::MIN + 1,
+            i64::MAX,
+            i64::MAX - 1,
+        ];
+        for &lhs in &values {
+            for &rhs in &values {
+                assert_eq!(lhs.ct_eq(rhs), lhs == rhs);
+                assert_eq!(lhs.ct_ne(rhs), lhs != rhs);
+                assert_eq!(lhs.ct_lt(rhs), lhs < rhs);
+                assert_eq!(lhs.ct_le(rhs), lhs <= rhs);
+                assert_eq!(lhs.ct_gt(rhs), lhs > rhs);
+                assert_eq!(lhs.ct_ge(rhs), lhs >= rhs);
+                let (l, r) = (lhs as u64, rhs as u64);
+                assert_eq!(l.ct_lt(r), l < r);
+                assert_eq!(l.ct_ge(r), l >= r);
+                let (l, r) = (lhs as i32, rhs as i32);
+                assert_eq!(l.ct_eq(r), l == r);
+                assert_eq!(l.ct_lt(r), l < r);
+                assert_eq!(l.ct_le(r), l <= r);
+                let (l, r) = (lhs as u32, rhs as u32);
+                assert_eq!(l.ct_lt(r), l < r);
+                assert_eq!(l.ct_gt(r), l > r);
+            }
+        }
+    }
+}
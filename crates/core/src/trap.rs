@@ -216,7 +216,13 @@ impl StdError for Trap {
 /// See [`Trap`] for details.
 ///
 /// [`Trap`]: struct.Trap.html
+///
+/// # Note
+///
+/// New variants may be added in minor releases. Therefore matches on
+/// [`TrapCode`] outside of this crate require a wildcard arm.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TrapCode {
     /// Wasm code executed `unreachable` opcode.
     ///
@@ -291,6 +297,23 @@ pub enum TrapCode {
     /// desire on the part of the embedder to trap the interpreter rather than
     /// merely fail the growth operation.
     GrowthOperationLimited,
+
+    /// This trap is raised when an operator of a disabled Wasm proposal is executed.
+    ///
+    /// Such operators only pass validation if the `wasmi::Config` is set up
+    /// to translate operators of disabled Wasm proposals into traps.
+    UnsupportedOperator,
+
+    /// This trap is raised when an interruptible host call is interrupted.
+    ///
+    /// Host calls are interrupted via the `wasmi::InterruptHandle` of their
+    /// `wasmi::Store` or once the deadline of the `wasmi::Store` has passed.
+    Interrupted,
+
+    /// This trap is raised when a host function call exceeded its time budget.
+    ///
+    /// Time budgets are assigned to host functions via `wasmi::Linker::func_budget`.
+    HostBudgetExceeded,
 }
 
 impl TrapCode {
@@ -313,6 +336,9 @@ impl TrapCode {
             Self::BadSignature => "indirect call type mismatch",
             Self::OutOfFuel => "all fuel consumed by WebAssembly",
             Self::GrowthOperationLimited => "growth operation limited",
+            Self::UnsupportedOperator => "unsupported Wasm operator executed",
+            Self::Interrupted => "interrupted host call",
+            Self::HostBudgetExceeded => "host function exceeded its time budget",
         }
     }
 }
@@ -271,6 +271,16 @@ impl TypedVal {
         #[fallible] fn i64_rem_s(i64, i64) -> Result<i64, TrapCode>;
         #[fallible] fn i64_rem_u(i64, i64) -> Result<i64, TrapCode>;
 
+        fn i32_div_s_sat(i32, i32) -> i32;
+        fn i32_div_u_sat(i32, i32) -> i32;
+        fn i32_rem_s_sat(i32, i32) -> i32;
+        fn i32_rem_u_sat(i32, i32) -> i32;
+
+        fn i64_div_s_sat(i64, i64) -> i64;
+        fn i64_div_u_sat(i64, i64) -> i64;
+        fn i64_rem_s_sat(i64, i64) -> i64;
+        fn i64_rem_u_sat(i64, i64) -> i64;
+
         // Shift & Rotate Instructions
 
         fn i32_shl(i32, i32) -> i32;
@@ -131,6 +131,37 @@ macro_rules! op {
     }};
 }
 
+/// Integer comparisons which are evaluated without branches if `hardened` is enabled.
+#[cfg(not(feature = "hardened"))]
+macro_rules! cmp {
+    ( $operator:tt ) => {
+        op!($operator)
+    };
+}
+
+/// Integer comparisons which are evaluated without branches if `hardened` is enabled.
+#[cfg(feature = "hardened")]
+macro_rules! cmp {
+    (==) => {
+        crate::ct::ConstantTimeCmp::ct_eq
+    };
+    (!=) => {
+        crate::ct::ConstantTimeCmp::ct_ne
+    };
+    (<) => {
+        crate::ct::ConstantTimeCmp::ct_lt
+    };
+    (<=) => {
+        crate::ct::ConstantTimeCmp::ct_le
+    };
+    (>) => {
+        crate::ct::ConstantTimeCmp::ct_gt
+    };
+    (>=) => {
+        crate::ct::ConstantTimeCmp::ct_ge
+    };
+}
+
 /// Calculates the effective address of a linear memory access.
 ///
 /// # Errors
@@ -590,6 +621,62 @@ impl UntypedVal {
         self.try_execute_binary(rhs, <u64 as Integer<u64>>::rem)
     }
 
+    /// Execute `i32.div_s` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero and saturates upon overflow.
+    pub fn i32_div_s_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <i32 as Integer<i32>>::div_sat)
+    }
+
+    /// Execute `i64.div_s` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero and saturates upon overflow.
+    pub fn i64_div_s_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <i64 as Integer<i64>>::div_sat)
+    }
+
+    /// Execute `i32.div_u` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero and saturates upon overflow.
+    pub fn i32_div_u_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <u32 as Integer<u32>>::div_sat)
+    }
+
+    /// Execute `i64.div_u` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero and saturates upon overflow.
+    pub fn i64_div_u_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <u64 as Integer<u64>>::div_sat)
+    }
+
+    /// Execute `i32.rem_s` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero.
+    pub fn i32_rem_s_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <i32 as Integer<i32>>::rem_sat)
+    }
+
+    /// Execute `i64.rem_s` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero.
+    pub fn i64_rem_s_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <i64 as Integer<i64>>::rem_sat)
+    }
+
+    /// Execute `i32.rem_u` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero.
+    pub fn i32_rem_u_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <u32 as Integer<u32>>::rem_sat)
+    }
+
+    /// Execute `i64.rem_u` Wasm operation without trapping.
+    ///
+    /// Returns zero if `rhs` is equal to zero.
+    pub fn i64_rem_u_sat(self, rhs: Self) -> Self {
+        self.execute_binary(rhs, <u64 as Integer<u64>>::rem_sat)
+    }
+
     /// Execute `i32.and` Wasm operation.
     pub fn i32_and(self, rhs: Self) -> Self {
         self.execute_binary::<i32, _>(rhs, op!(&))
@@ -702,22 +789,22 @@ impl UntypedVal {
 
     /// Execute `i32.eqz` Wasm operation.
     pub fn i32_eqz(self) -> Self {
-        self.execute_unary::<i32, bool>(|value| value == 0)
+        self.execute_unary::<i32, bool>(|value| cmp!(==)(value, 0))
     }
 
     /// Execute `i64.eqz` Wasm operation.
     pub fn i64_eqz(self) -> Self {
-        self.execute_unary::<i64, bool>(|value| value == 0)
+        self.execute_unary::<i64, bool>(|value| cmp!(==)(value, 0))
     }
 
     /// Execute `i32.eq` Wasm operation.
     pub fn i32_eq(self, rhs: Self) -> Self {
-        self.execute_binary::<i32, bool>(rhs, op!(==))
+        self.execute_binary::<i32, bool>(rhs, cmp!(==))
     }
 
     /// Execute `i64.eq` Wasm operation.
     pub fn i64_eq(self, rhs: Self) -> Self {
-        self.execute_binary::<i64, bool>(rhs, op!(==))
+        self.execute_binary::<i64, bool>(rhs, cmp!(==))
     }
 
     /// Execute `f32.eq` Wasm operation.
@@ -732,12 +819,12 @@ impl UntypedVal {
 
     /// Execute `i32.ne` Wasm operation.
     pub fn i32_ne(self, rhs: Self) -> Self {
-        self.execute_binary::<i32, bool>(rhs, op!(!=))
+        self.execute_binary::<i32, bool>(rhs, cmp!(!=))
     }
 
     /// Execute `i64.ne` Wasm operation.
     pub fn i64_ne(self, rhs: Self) -> Self {
-        self.execute_binary::<i64, bool>(rhs, op!(!=))
+        self.execute_binary::<i64, bool>(rhs, cmp!(!=))
     }
 
     /// Execute `f32.ne` Wasm operation.
@@ -752,22 +839,22 @@ impl UntypedVal {
 
     /// Execute `i32.lt_s` Wasm operation.
     pub fn i32_lt_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i32, bool>(rhs, op!(<))
+        self.execute_binary::<i32, bool>(rhs, cmp!(<))
     }
 
     /// Execute `i64.lt_s` Wasm operation.
     pub fn i64_lt_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i64, bool>(rhs, op!(<))
+        self.execute_binary::<i64, bool>(rhs, cmp!(<))
     }
 
     /// Execute `i32.lt_u` Wasm operation.
     pub fn i32_lt_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u32, bool>(rhs, op!(<))
+        self.execute_binary::<u32, bool>(rhs, cmp!(<))
     }
 
     /// Execute `i64.lt_u` Wasm operation.
     pub fn i64_lt_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u64, bool>(rhs, op!(<))
+        self.execute_binary::<u64, bool>(rhs, cmp!(<))
     }
 
     /// Execute `f32.lt` Wasm operation.
@@ -782,22 +869,22 @@ impl UntypedVal {
 
     /// Execute `i32.le_s` Wasm operation.
     pub fn i32_le_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i32, bool>(rhs, op!(<=))
+        self.execute_binary::<i32, bool>(rhs, cmp!(<=))
     }
 
     /// Execute `i64.le_s` Wasm operation.
     pub fn i64_le_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i64, bool>(rhs, op!(<=))
+        self.execute_binary::<i64, bool>(rhs, cmp!(<=))
     }
 
     /// Execute `i32.le_u` Wasm operation.
     pub fn i32_le_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u32, bool>(rhs, op!(<=))
+        self.execute_binary::<u32, bool>(rhs, cmp!(<=))
     }
 
     /// Execute `i64.le_u` Wasm operation.
     pub fn i64_le_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u64, bool>(rhs, op!(<=))
+        self.execute_binary::<u64, bool>(rhs, cmp!(<=))
     }
 
     /// Execute `f32.le` Wasm operation.
@@ -812,22 +899,22 @@ impl UntypedVal {
 
     /// Execute `i32.gt_s` Wasm operation.
     pub fn i32_gt_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i32, bool>(rhs, op!(>))
+        self.execute_binary::<i32, bool>(rhs, cmp!(>))
     }
 
     /// Execute `i64.gt_s` Wasm operation.
     pub fn i64_gt_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i64, bool>(rhs, op!(>))
+        self.execute_binary::<i64, bool>(rhs, cmp!(>))
     }
 
     /// Execute `i32.gt_u` Wasm operation.
     pub fn i32_gt_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u32, bool>(rhs, op!(>))
+        self.execute_binary::<u32, bool>(rhs, cmp!(>))
     }
 
     /// Execute `i64.gt_u` Wasm operation.
     pub fn i64_gt_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u64, bool>(rhs, op!(>))
+        self.execute_binary::<u64, bool>(rhs, cmp!(>))
     }
 
     /// Execute `f32.gt` Wasm operation.
@@ -842,22 +929,22 @@ impl UntypedVal {
 
     /// Execute `i32.ge_s` Wasm operation.
     pub fn i32_ge_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i32, bool>(rhs, op!(>=))
+        self.execute_binary::<i32, bool>(rhs, cmp!(>=))
     }
 
     /// Execute `i64.ge_s` Wasm operation.
     pub fn i64_ge_s(self, rhs: Self) -> Self {
-        self.execute_binary::<i64, bool>(rhs, op!(>=))
+        self.execute_binary::<i64, bool>(rhs, cmp!(>=))
     }
 
     /// Execute `i32.ge_u` Wasm operation.
     pub fn i32_ge_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u32, bool>(rhs, op!(>=))
+        self.execute_binary::<u32, bool>(rhs, cmp!(>=))
     }
 
     /// Execute `i64.ge_u` Wasm operation.
     pub fn i64_ge_u(self, rhs: Self) -> Self {
-        self.execute_binary::<u64, bool>(rhs, op!(>=))
+        self.execute_binary::<u64, bool>(rhs, cmp!(>=))
     }
 
     /// Execute `f32.ge` Wasm operation.
@@ -230,6 +230,14 @@ pub trait Integer<T>: ArithmeticOps<T> {
     ///
     /// If `other` is equal to zero.
     fn rem(self, other: T) -> Result<T, TrapCode>;
+    /// Divide two values without trapping.
+    ///
+    /// Returns zero if `other` is equal to zero and saturates upon overflow.
+    fn div_sat(self, other: T) -> T;
+    /// Get division remainder without trapping.
+    ///
+    /// Returns zero if `other` is equal to zero.
+    fn rem_sat(self, other: T) -> T;
 }
 
 /// Float-point value.
@@ -523,6 +531,20 @@ macro_rules! impl_integer {
                 }
                 Ok(self.wrapping_rem(other))
             }
+            #[inline]
+            fn div_sat(self, other: Self) -> Self {
+                if unlikely(other == 0) {
+                    return 0;
+                }
+                self.saturating_div(other)
+            }
+            #[inline]
+            fn rem_sat(self, other: Self) -> Self {
+                if unlikely(other == 0) {
+                    return 0;
+                }
+                self.wrapping_rem(other)
+            }
         }
     };
 }
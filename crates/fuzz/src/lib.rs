@@ -4,6 +4,7 @@ mod error;
 mod module;
 #[cfg(feature = "differential")]
 pub mod oracle;
+mod run;
 mod value;
 
 pub use self::{
@@ -11,5 +12,6 @@ pub use self::{
     crash_inputs::generate_crash_inputs,
     error::{FuzzError, TrapCode},
     module::{FuzzModule, WasmSource, WatSource},
+    run::{run_module, FuzzCall, FuzzConfig, FuzzOutcome},
     value::{FuzzVal, FuzzValType},
 };
@@ -3,10 +3,8 @@ use crate::{
     FuzzError,
     FuzzSmithConfig,
     FuzzVal,
-    FuzzValType,
 };
 use wasmi::{
-    core::ValType,
     Config,
     Engine,
     Instance,
@@ -134,55 +132,3 @@ impl DifferentialOracle for WasmiOracle {
         Some(data)
     }
 }
-
-impl From<FuzzValType> for ValType {
-    fn from(ty: FuzzValType) -> Self {
-        match ty {
-            FuzzValType::I32 => Self::I32,
-            FuzzValType::I64 => Self::I64,
-            FuzzValType::F32 => Self::F32,
-            FuzzValType::F64 => Self::F64,
-            FuzzValType::FuncRef => Self::FuncRef,
-            FuzzValType::ExternRef => Self::ExternRef,
-        }
-    }
-}
-
-impl From<Val> for FuzzVal {
-    fn from(value: Val) -> Self {
-        match value {
-            Val::I32(value) => Self::I32(value),
-            Val::I64(value) => Self::I64(value),
-            Val::F32(value) => Self::F32(value.into()),
-            Val::F64(value) => Self::F64(value.into()),
-            Val::FuncRef(value) => Self::FuncRef {
-                is_null: value.is_null(),
-            },
-            Val::ExternRef(value) => Self::ExternRef {
-                is_null: value.is_null(),
-            },
-        }
-    }
-}
-
-impl From<wasmi::Error> for FuzzError {
-    fn from(error: wasmi::Error) -> Self {
-        use wasmi::core::TrapCode;
-        let Some(trap_code) = error.as_trap_code() else {
-            return FuzzError::Other;
-        };
-        let trap_code = match trap_code {
-            TrapCode::UnreachableCodeReached => crate::TrapCode::UnreachableCodeReached,
-            TrapCode::MemoryOutOfBounds => crate::TrapCode::MemoryOutOfBounds,
-            TrapCode::TableOutOfBounds => crate::TrapCode::TableOutOfBounds,
-            TrapCode::IndirectCallToNull => crate::TrapCode::IndirectCallToNull,
-            TrapCode::IntegerDivisionByZero => crate::TrapCode::IntegerDivisionByZero,
-            TrapCode::IntegerOverflow => crate::TrapCode::IntegerOverflow,
-            TrapCode::BadConversionToInteger => crate::TrapCode::BadConversionToInteger,
-            TrapCode::StackOverflow => crate::TrapCode::StackOverflow,
-            TrapCode::BadSignature => crate::TrapCode::BadSignature,
-            TrapCode::OutOfFuel | TrapCode::GrowthOperationLimited => return FuzzError::Other,
-        };
-        FuzzError::Trap(trap_code)
-    }
-}
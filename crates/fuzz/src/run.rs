@@ -0,0 +1,226 @@
+use crate::{
+    config::{ParsingMode, ValidationMode},
+    FuzzError,
+    FuzzVal,
+    FuzzWasmiConfig,
+};
+use arbitrary::{Arbitrary, Unstructured};
+use wasmi::{
+    Engine,
+    Extern,
+    ExternType,
+    Func,
+    Global,
+    Instance,
+    Memory,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    Table,
+    Val,
+};
+
+/// Configuration of a fuel-bounded run of a Wasm module via [`run_module`].
+#[derive(Debug, Copy, Clone)]
+pub struct FuzzConfig {
+    /// The Wasmi configuration.
+    ///
+    /// # Note
+    ///
+    /// Fuel metering is always enabled for [`run_module`].
+    pub wasmi: FuzzWasmiConfig,
+    /// The fuel available to the instantiation and to each call of an exported function.
+    pub fuel: u64,
+    /// The maximum size of each linear memory in bytes.
+    pub max_memory_bytes: usize,
+    /// The maximum number of elements of each table.
+    pub max_table_elements: u32,
+    /// The maximum number of exported functions that are called.
+    pub max_calls: usize,
+}
+
+impl FuzzConfig {
+    /// The maximum fuel of an arbitrary [`FuzzConfig`].
+    const MAX_FUEL: u64 = 100_000;
+    /// The maximum linear memory size of an arbitrary [`FuzzConfig`] in bytes.
+    const MAX_MEMORY_BYTES: usize = 16 * 0x10000;
+    /// The maximum number of table elements of an arbitrary [`FuzzConfig`].
+    const MAX_TABLE_ELEMENTS: u32 = 10_000;
+    /// The maximum number of calls of an arbitrary [`FuzzConfig`].
+    const MAX_CALLS: usize = 100;
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            wasmi: FuzzWasmiConfig {
+                consume_fuel: true,
+                parsing_mode: ParsingMode::Buffered,
+                validation_mode: ValidationMode::Checked,
+                translation_mode: wasmi::CompilationMode::Eager,
+            },
+            fuel: Self::MAX_FUEL,
+            max_memory_bytes: Self::MAX_MEMORY_BYTES,
+            max_table_elements: Self::MAX_TABLE_ELEMENTS,
+            max_calls: Self::MAX_CALLS,
+        }
+    }
+}
+
+impl Arbitrary<'_> for FuzzConfig {
+    fn arbitrary(u: &mut Unstructured) -> arbitrary::Result<Self> {
+        Ok(Self {
+            wasmi: FuzzWasmiConfig::arbitrary(u)?,
+            fuel: u.int_in_range(0..=Self::MAX_FUEL)?,
+            max_memory_bytes: u.int_in_range(0..=Self::MAX_MEMORY_BYTES)?,
+            max_table_elements: u.int_in_range(0..=Self::MAX_TABLE_ELEMENTS)?,
+            max_calls: u.int_in_range(0..=Self::MAX_CALLS)?,
+        })
+    }
+}
+
+/// The structured outcome of [`run_module`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum FuzzOutcome {
+    /// The Wasm module failed to validate or translate.
+    Invalid,
+    /// The Wasm module failed to instantiate, e.g. due to a trap in its start function.
+    NotInstantiated(FuzzError),
+    /// The exported functions of the instantiated Wasm module have been called.
+    Executed(Vec<FuzzCall>),
+}
+
+/// A call of an exported function by [`run_module`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct FuzzCall {
+    /// The export name of the called function.
+    pub name: Box<str>,
+    /// The results of the call or the reason it failed.
+    pub results: Result<Box<[FuzzVal]>, FuzzError>,
+}
+
+/// Runs the Wasm module `wasm` under the strict fuel and memory caps of `config`.
+///
+/// Instantiates the Wasm module with dummy imports and calls its exported functions
+/// in order with zero parameters until [`FuzzConfig::max_calls`] is reached.
+///
+/// - Imported functions return zero values.
+/// - Imported global variables, memories and tables are zero initialized.
+///
+/// Each call starts with [`FuzzConfig::fuel`] so that a single diverging
+/// function does not prevent the other exported functions from being run.
+pub fn run_module(wasm: &[u8], config: &FuzzConfig) -> FuzzOutcome {
+    let mut engine_config = wasmi::Config::from(config.wasmi);
+    engine_config.consume_fuel(true);
+    let engine = Engine::new(&engine_config);
+    let Some(module) = compile(&engine, wasm, &config.wasmi) else {
+        return FuzzOutcome::Invalid;
+    };
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(config.max_memory_bytes)
+        .table_elements(config.max_table_elements)
+        .build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    let instance = match instantiate(&mut store, &module, config.fuel) {
+        Ok(instance) => instance,
+        Err(error) => return FuzzOutcome::NotInstantiated(FuzzError::from(error)),
+    };
+    let funcs = instance
+        .exports(&store)
+        .filter_map(|export| {
+            let name = Box::from(export.name());
+            export.into_func().map(|func| (name, func))
+        })
+        .take(config.max_calls)
+        .collect::<Vec<(Box<str>, Func)>>();
+    let calls = funcs
+        .into_iter()
+        .map(|(name, func)| FuzzCall {
+            name,
+            results: call(&mut store, func, config.fuel),
+        })
+        .collect();
+    FuzzOutcome::Executed(calls)
+}
+
+/// Compiles the `wasm` module according to `config`.
+///
+/// Returns `None` if `wasm` is invalid.
+fn compile(engine: &Engine, wasm: &[u8], config: &FuzzWasmiConfig) -> Option<Module> {
+    if matches!(config.validation_mode, ValidationMode::Unchecked) {
+        // Translating an invalid Wasm module is undefined behavior.
+        Module::validate(engine, wasm).ok()?;
+        // Safety: we have just checked Wasm validity above.
+        return unsafe { Module::new_unchecked(engine, wasm) }.ok();
+    }
+    match config.parsing_mode {
+        ParsingMode::Buffered => Module::new(engine, wasm).ok(),
+        ParsingMode::Streaming => Module::new_streaming(engine, wasm).ok(),
+    }
+}
+
+/// Instantiates the `module` with dummy imports using `fuel` for its start function.
+fn instantiate(
+    store: &mut Store<StoreLimits>,
+    module: &Module,
+    fuel: u64,
+) -> Result<Instance, wasmi::Error> {
+    store.set_fuel(fuel)?;
+    let externs = module
+        .imports()
+        .map(|import| dummy_extern(store, import.ty().clone()))
+        .collect::<Result<Vec<Extern>, wasmi::Error>>()?;
+    Instance::new(store, module, &externs)
+}
+
+/// Creates a zero initialized dummy [`Extern`] of type `ty`.
+fn dummy_extern(store: &mut Store<StoreLimits>, ty: ExternType) -> Result<Extern, wasmi::Error> {
+    let dummy = match ty {
+        ExternType::Func(ty) => {
+            let results = ty.results().to_vec();
+            let func = Func::new(&mut *store, ty, move |_caller, _params, outputs| {
+                for (output, ty) in outputs.iter_mut().zip(&results) {
+                    *output = Val::default(*ty);
+                }
+                Ok(())
+            });
+            Extern::from(func)
+        }
+        ExternType::Global(ty) => {
+            let global = Global::new(&mut *store, Val::default(ty.content()), ty.mutability());
+            Extern::from(global)
+        }
+        ExternType::Memory(ty) => Extern::from(Memory::new(&mut *store, ty)?),
+        ExternType::Table(ty) => {
+            let table = Table::new(&mut *store, ty, Val::default(ty.element()))?;
+            Extern::from(table)
+        }
+    };
+    Ok(dummy)
+}
+
+/// Calls `func` with zero parameters and `fuel`.
+fn call(
+    store: &mut Store<StoreLimits>,
+    func: Func,
+    fuel: u64,
+) -> Result<Box<[FuzzVal]>, FuzzError> {
+    store.set_fuel(fuel)?;
+    let ty = func.ty(&*store);
+    let params = ty
+        .params()
+        .iter()
+        .copied()
+        .map(Val::default)
+        .collect::<Vec<_>>();
+    let mut results = ty
+        .results()
+        .iter()
+        .copied()
+        .map(Val::default)
+        .collect::<Vec<_>>();
+    func.call(&mut *store, &params, &mut results)?;
+    Ok(results.into_iter().map(FuzzVal::from).collect())
+}
@@ -1,5 +1,5 @@
 use arbitrary::{Arbitrary, Unstructured};
-use wasmi::core::ValType;
+use wasmi::{core::ValType, Val};
 
 /// A Wasm value type supported by the Wasmi fuzzing infrastructure.
 #[derive(Debug, Copy, Clone)]
@@ -90,3 +90,33 @@ impl From<FuzzVal> for wasmi::Val {
         }
     }
 }
+
+impl From<FuzzValType> for ValType {
+    fn from(ty: FuzzValType) -> Self {
+        match ty {
+            FuzzValType::I32 => Self::I32,
+            FuzzValType::I64 => Self::I64,
+            FuzzValType::F32 => Self::F32,
+            FuzzValType::F64 => Self::F64,
+            FuzzValType::FuncRef => Self::FuncRef,
+            FuzzValType::ExternRef => Self::ExternRef,
+        }
+    }
+}
+
+impl From<Val> for FuzzVal {
+    fn from(value: Val) -> Self {
+        match value {
+            Val::I32(value) => Self::I32(value),
+            Val::I64(value) => Self::I64(value),
+            Val::F32(value) => Self::F32(value.into()),
+            Val::F64(value) => Self::F64(value.into()),
+            Val::FuncRef(value) => Self::FuncRef {
+                is_null: value.is_null(),
+            },
+            Val::ExternRef(value) => Self::ExternRef {
+                is_null: value.is_null(),
+            },
+        }
+    }
+}
@@ -136,6 +136,40 @@ macro_rules! define_result {
 }
 for_each_op::for_each_op!(define_result);
 
+macro_rules! define_name {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
+            $(
+                {
+                    $(
+                        @ $result_name:ident: $result_ty:ty,
+                    )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
+    ) => {
+        impl Instruction {
+            /// Returns the name of the variant of `self`, e.g. `"I32Add"`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$name { .. } => stringify!($name),
+                    )*
+                }
+            }
+        }
+    };
+}
+for_each_op::for_each_op!(define_name);
+
 impl Instruction {
     /// Creates a new [`Instruction::ReturnReg2`] for the given [`Reg`] indices.
     pub fn return_reg2_ext(reg0: impl Into<Reg>, reg1: impl Into<Reg>) -> Self {
@@ -151,6 +185,51 @@ impl Instruction {
         Self::return_reg3([reg0.into(), reg1.into(), reg2.into()])
     }
 
+    /// Creates a new [`Instruction::ReturnReg4`] for the given [`Reg`] indices.
+    pub fn return_reg4_ext(
+        reg0: impl Into<Reg>,
+        reg1: impl Into<Reg>,
+        reg2: impl Into<Reg>,
+    ) -> Self {
+        Self::return_reg4([reg0.into(), reg1.into(), reg2.into()])
+    }
+
+    /// Creates a new [`Instruction::ReturnReg5`] for the given [`Reg`] indices.
+    pub fn return_reg5_ext(
+        reg0: impl Into<Reg>,
+        reg1: impl Into<Reg>,
+        reg2: impl Into<Reg>,
+    ) -> Self {
+        Self::return_reg5([reg0.into(), reg1.into(), reg2.into()])
+    }
+
+    /// Creates a new [`Instruction::ReturnReg6`] for the given [`Reg`] indices.
+    pub fn return_reg6_ext(
+        reg0: impl Into<Reg>,
+        reg1: impl Into<Reg>,
+        reg2: impl Into<Reg>,
+    ) -> Self {
+        Self::return_reg6([reg0.into(), reg1.into(), reg2.into()])
+    }
+
+    /// Creates a new [`Instruction::ReturnReg7`] for the given [`Reg`] indices.
+    pub fn return_reg7_ext(
+        reg0: impl Into<Reg>,
+        reg1: impl Into<Reg>,
+        reg2: impl Into<Reg>,
+    ) -> Self {
+        Self::return_reg7([reg0.into(), reg1.into(), reg2.into()])
+    }
+
+    /// Creates a new [`Instruction::ReturnReg8`] for the given [`Reg`] indices.
+    pub fn return_reg8_ext(
+        reg0: impl Into<Reg>,
+        reg1: impl Into<Reg>,
+        reg2: impl Into<Reg>,
+    ) -> Self {
+        Self::return_reg8([reg0.into(), reg1.into(), reg2.into()])
+    }
+
     /// Creates a new [`Instruction::ReturnMany`] for the given [`Reg`] indices.
     pub fn return_many_ext(
         reg0: impl Into<Reg>,
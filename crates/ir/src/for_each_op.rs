@@ -23,6 +23,18 @@ macro_rules! for_each_op {
             ConsumeFuel {
                 block_fuel: BlockFuel
             },
+            /// Instruction generated to count the iterations of its associated Wasm `loop`.
+            ///
+            /// # Note
+            ///
+            /// - These instructions are only generated if loop iteration counting is enabled.
+            /// - The `func` refers to the internal function that contains the `loop`.
+            /// - Followed by an [`Instruction::Const32`] that encodes the index of the `loop`
+            ///   in order of appearance in the function body of `func`.
+            #[snake_name(count_loop_iteration)]
+            CountLoopIteration {
+                func: InternalFunc
+            },
 
             /// A Wasm `return` instruction.
             ///
@@ -65,6 +77,76 @@ macro_rules! for_each_op {
             ///
             /// # Note
             ///
+            /// Returns four values stored in registers.
+            ///
+            /// # Encoding
+            ///
+            /// Must be followed by [`Instruction::Register`] encoding the remaining returned values.
+            #[snake_name(return_reg4)]
+            ReturnReg4 {
+                /// The first three returned values.
+                values: [Reg; 3],
+            },
+            /// A Wasm `return` instruction.
+            ///
+            /// # Note
+            ///
+            /// Returns five values stored in registers.
+            ///
+            /// # Encoding
+            ///
+            /// Must be followed by [`Instruction::Register2`] encoding the remaining returned values.
+            #[snake_name(return_reg5)]
+            ReturnReg5 {
+                /// The first three returned values.
+                values: [Reg; 3],
+            },
+            /// A Wasm `return` instruction.
+            ///
+            /// # Note
+            ///
+            /// Returns six values stored in registers.
+            ///
+            /// # Encoding
+            ///
+            /// Must be followed by [`Instruction::Register3`] encoding the remaining returned values.
+            #[snake_name(return_reg6)]
+            ReturnReg6 {
+                /// The first three returned values.
+                values: [Reg; 3],
+            },
+            /// A Wasm `return` instruction.
+            ///
+            /// # Note
+            ///
+            /// Returns seven values stored in registers.
+            ///
+            /// # Encoding
+            ///
+            /// Must be followed by [`Instruction::Register3`] and [`Instruction::Register`] encoding the remaining returned values.
+            #[snake_name(return_reg7)]
+            ReturnReg7 {
+                /// The first three returned values.
+                values: [Reg; 3],
+            },
+            /// A Wasm `return` instruction.
+            ///
+            /// # Note
+            ///
+            /// Returns eight values stored in registers.
+            ///
+            /// # Encoding
+            ///
+            /// Must be followed by [`Instruction::Register3`] and [`Instruction::Register2`] encoding the remaining returned values.
+            #[snake_name(return_reg8)]
+            ReturnReg8 {
+                /// The first three returned values.
+                values: [Reg; 3],
+            },
+            /// A Wasm `return` instruction.
+            ///
+            /// # Note
+            ///
             /// Returns a single 32-bit constant value.
             #[snake_name(return_imm32)]
             ReturnImm32 {
@@ -1231,6 +1313,51 @@ macro_rules! for_each_op {
                 func: Func,
             },
 
+            /// An instruction provided by an extension of the Wasmi engine.
+            ///
+            /// # Note
+            ///
+            /// Used for extension instructions without parameters.
+            ///
+            /// This is the secondary dispatch range reserved for engine extensions
+            /// that allows downstream forks to add instructions without defining
+            /// new [`Instruction`] variants.
+            #[snake_name(extension_0)]
+            Extension0 {
+                @results: RegSpan,
+                /// The index of the extension within its engine.
+                extension: u8,
+                /// The number of results of the extension instruction.
+                len_results: u8,
+                /// The opcode of the instruction within its extension.
+                opcode: u16,
+            },
+            /// An instruction provided by an extension of the Wasmi engine.
+            ///
+            /// # Note
+            ///
+            /// Used for extension instructions with parameters.
+            ///
+            /// # Encoding (Parameters)
+            ///
+            /// Must be followed by
+            ///
+            /// 1. Zero or more [`Instruction::RegisterList`]
+            /// 2. Followed by one of
+            ///     - [`Instruction::Register`]
+            ///     - [`Instruction::Register2`]
+            ///     - [`Instruction::Register3`]
+            #[snake_name(extension)]
+            Extension {
+                @results: RegSpan,
+                /// The index of the extension within its engine.
+                extension: u8,
+                /// The number of results of the extension instruction.
+                len_results: u8,
+                /// The opcode of the instruction within its extension.
+                opcode: u16,
+            },
+
             /// Wasm `call_indirect` equivalent Wasmi instruction.
             ///
             /// # Note
@@ -3287,6 +3414,66 @@ macro_rules! for_each_op {
                 rhs: Reg,
             },
 
+            /// Non-trapping `i32` signed-division instruction: `r0 = r1 / r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I32DivS`] that never traps.
+            /// - Returns zero if the right-hand side value is zero and saturates upon overflow.
+            #[snake_name(i32_div_s_sat)]
+            I32DivSSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
+            /// Non-trapping `i32` unsigned-division instruction: `r0 = r1 / r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I32DivU`] that never traps.
+            /// - Returns zero if the right-hand side value is zero and saturates upon overflow.
+            #[snake_name(i32_div_u_sat)]
+            I32DivUSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
+            /// Non-trapping `i32` signed-remainder instruction: `r0 = r1 % r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I32RemS`] that never traps.
+            /// - Returns zero if the right-hand side value is zero.
+            #[snake_name(i32_rem_s_sat)]
+            I32RemSSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
+            /// Non-trapping `i32` unsigned-remainder instruction: `r0 = r1 % r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I32RemU`] that never traps.
+            /// - Returns zero if the right-hand side value is zero.
+            #[snake_name(i32_rem_u_sat)]
+            I32RemUSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
             /// `i32` bitwise-and instruction: `r0 = r1 & r2`
             #[snake_name(i32_and)]
             I32And {
@@ -3801,6 +3988,66 @@ macro_rules! for_each_op {
                 rhs: Reg,
             },
 
+            /// Non-trapping `i64` signed-division instruction: `r0 = r1 / r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I64DivS`] that never traps.
+            /// - Returns zero if the right-hand side value is zero and saturates upon overflow.
+            #[snake_name(i64_div_s_sat)]
+            I64DivSSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
+            /// Non-trapping `i64` unsigned-division instruction: `r0 = r1 / r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I64DivU`] that never traps.
+            /// - Returns zero if the right-hand side value is zero and saturates upon overflow.
+            #[snake_name(i64_div_u_sat)]
+            I64DivUSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
+            /// Non-trapping `i64` signed-remainder instruction: `r0 = r1 % r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I64RemS`] that never traps.
+            /// - Returns zero if the right-hand side value is zero.
+            #[snake_name(i64_rem_s_sat)]
+            I64RemSSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
+            /// Non-trapping `i64` unsigned-remainder instruction: `r0 = r1 % r2`
+            ///
+            /// # Note
+            ///
+            /// - Variant of [`Instruction::I64RemU`] that never traps.
+            /// - Returns zero if the right-hand side value is zero.
+            #[snake_name(i64_rem_u_sat)]
+            I64RemUSat {
+                @result: Reg,
+                /// The register holding the left-hand side value.
+                lhs: Reg,
+                /// The register holding the right-hand side value.
+                rhs: Reg,
+            },
+
             /// `i64` bitwise-and instruction: `r0 = r1 & r2`
             #[snake_name(i64_and)]
             I64And {
@@ -0,0 +1,320 @@
+//! Implements JavaScript bindings for the Wasmi WebAssembly interpreter.
+//!
+//! The bindings are generated via [`wasm-bindgen`] and allow to run Wasmi inside of
+//! a JavaScript host such as a web browser by compiling this crate to the
+//! `wasm32-unknown-unknown` target.
+//!
+//! # Value Conversions
+//!
+//! | Wasm type | JavaScript type |
+//! |:---------:|:---------------:|
+//! | `i32`     | `number`        |
+//! | `i64`     | `bigint`        |
+//! | `f32`     | `number`        |
+//! | `f64`     | `number`        |
+//!
+//! Reference types are not supported at the JavaScript boundary.
+//!
+//! # Errors
+//!
+//! All fallible operations report failures as JavaScript `Error` exceptions
+//! instead of panicking which would abort the entire Wasm instance of Wasmi.
+//!
+//! [`wasm-bindgen`]: https://crates.io/crates/wasm-bindgen
+
+use js_sys::{Array, BigInt, Function};
+use wasm_bindgen::prelude::*;
+use wasmi::{core::ValType, Error, FuncType, Val};
+
+/// A Wasmi [`Engine`](wasmi::Engine) for JavaScript hosts.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Engine {
+    inner: wasmi::Engine,
+}
+
+#[wasm_bindgen]
+impl Engine {
+    /// Creates a new [`Engine`] with the default configuration.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// A compiled and validated Wasm or Wat [`Module`](wasmi::Module) for JavaScript hosts.
+#[wasm_bindgen]
+pub struct Module {
+    inner: wasmi::Module,
+}
+
+#[wasm_bindgen]
+impl Module {
+    /// Compiles the Wasm or Wat `wasm` bytes into a [`Module`] for `engine`.
+    ///
+    /// # Errors
+    ///
+    /// If `wasm` is not a valid Wasm or Wat module.
+    #[wasm_bindgen(constructor)]
+    pub fn new(engine: &Engine, wasm: &[u8]) -> Result<Module, JsError> {
+        let inner = wasmi::Module::new(&engine.inner, wasm).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Returns the names of all exports of the [`Module`].
+    pub fn exports(&self) -> Vec<String> {
+        self.inner
+            .exports()
+            .map(|export| export.name().into())
+            .collect()
+    }
+}
+
+/// A Wasmi [`Store`](wasmi::Store) for JavaScript hosts owning all Wasm entities.
+#[wasm_bindgen]
+pub struct Store {
+    inner: wasmi::Store<()>,
+}
+
+#[wasm_bindgen]
+impl Store {
+    /// Creates a new [`Store`] for `engine`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            inner: wasmi::Store::new(&engine.inner, ()),
+        }
+    }
+}
+
+/// A Wasmi [`Linker`](wasmi::Linker) for JavaScript hosts defining host functions.
+#[wasm_bindgen]
+pub struct Linker {
+    inner: wasmi::Linker<()>,
+}
+
+#[wasm_bindgen]
+impl Linker {
+    /// Creates a new [`Linker`] for `engine`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            inner: wasmi::Linker::new(&engine.inner),
+        }
+    }
+
+    /// Defines the JavaScript function `func` as host function `module::name`.
+    ///
+    /// The `params` and `results` are the names of the Wasm value types of the host function, e.g. `"i32"`.
+    ///
+    /// # Note
+    ///
+    /// - `func` is called with one JavaScript value per parameter.
+    /// - `func` must return a single JavaScript value if the host function has a single result
+    ///   and an array of JavaScript values if it has multiple results.
+    ///
+    /// # Errors
+    ///
+    /// - If `params` or `results` contain unsupported Wasm value types.
+    /// - If `module::name` is already defined.
+    pub fn define(
+        &mut self,
+        module: &str,
+        name: &str,
+        params: Vec<String>,
+        results: Vec<String>,
+        func: Function,
+    ) -> Result<(), JsError> {
+        let params = params
+            .iter()
+            .map(|param| parse_val_type(param))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(js_error)?;
+        let results = results
+            .iter()
+            .map(|result| parse_val_type(result))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(js_error)?;
+        let ty = FuncType::new(params, results);
+        let func = JsFunc(func);
+        self.inner
+            .func_new(module, name, ty, move |_caller, params, results| {
+                func.call(params, results)
+            })
+            .map_err(js_error)?;
+        Ok(())
+    }
+
+    /// Instantiates `module` within `store` and runs its `start` function if any.
+    ///
+    /// # Errors
+    ///
+    /// - If an import of `module` is not defined by the [`Linker`].
+    /// - If instantiation or the `start` function of `module` fails.
+    pub fn instantiate(&self, store: &mut Store, module: &Module) -> Result<Instance, JsError> {
+        let inner = self
+            .inner
+            .instantiate(&mut store.inner, &module.inner)
+            .and_then(|pre| pre.start(&mut store.inner))
+            .map_err(js_error)?;
+        Ok(Instance { inner })
+    }
+}
+
+/// A Wasmi [`Instance`](wasmi::Instance) for JavaScript hosts.
+#[wasm_bindgen]
+pub struct Instance {
+    inner: wasmi::Instance,
+}
+
+#[wasm_bindgen]
+impl Instance {
+    /// Calls the exported function `name` with `args` and returns its results.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no exported function `name`.
+    /// - If `args` do not match the parameters of the exported function.
+    /// - If the execution of the exported function traps.
+    pub fn call(&self, store: &mut Store, name: &str, args: &Array) -> Result<Array, JsError> {
+        let Some(func) = self.inner.get_func(&store.inner, name) else {
+            return Err(JsError::new(&format!("missing exported function: {name}")));
+        };
+        let ty = func.ty(&store.inner);
+        if args.length() as usize != ty.params().len() {
+            return Err(JsError::new(&format!(
+                "expected {} arguments but found {}",
+                ty.params().len(),
+                args.length()
+            )));
+        }
+        let params = ty
+            .params()
+            .iter()
+            .zip(args.iter())
+            .map(|(ty, arg)| from_js(&arg, ty))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(js_error)?;
+        let mut results = ty
+            .results()
+            .iter()
+            .copied()
+            .map(Val::default)
+            .collect::<Vec<_>>();
+        func.call(&mut store.inner, &params, &mut results)
+            .map_err(js_error)?;
+        results
+            .iter()
+            .map(to_js)
+            .collect::<Result<Array, _>>()
+            .map_err(js_error)
+    }
+}
+
+/// A JavaScript function used as host function.
+struct JsFunc(Function);
+
+// # Safety
+//
+// JavaScript values are bound to the single thread of their JavaScript host and
+// the `wasm32-unknown-unknown` target does not support spawning other threads.
+unsafe impl Send for JsFunc {}
+
+// # Safety
+//
+// JavaScript values are bound to the single thread of their JavaScript host and
+// the `wasm32-unknown-unknown` target does not support spawning other threads.
+unsafe impl Sync for JsFunc {}
+
+impl JsFunc {
+    /// Calls the JavaScript function with `params` and writes its return value into `results`.
+    ///
+    /// # Errors
+    ///
+    /// - If the JavaScript function throws an exception.
+    /// - If the return value of the JavaScript function does not match `results`.
+    fn call(&self, params: &[Val], results: &mut [Val]) -> Result<(), Error> {
+        let args = params.iter().map(to_js).collect::<Result<Array, _>>()?;
+        let value = self
+            .0
+            .apply(&JsValue::NULL, &args)
+            .map_err(|error| Error::new(format!("host function threw: {error:?}")))?;
+        match results {
+            [] => {}
+            [result] => *result = from_js(&value, &result.ty())?,
+            results => {
+                let Ok(values) = value.dyn_into::<Array>() else {
+                    return Err(Error::new("expected an array of results"));
+                };
+                if values.length() as usize != results.len() {
+                    return Err(Error::new(format!(
+                        "expected {} results but found {}",
+                        results.len(),
+                        values.length()
+                    )));
+                }
+                for (result, value) in results.iter_mut().zip(values.iter()) {
+                    *result = from_js(&value, &result.ty())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Converts the Wasmi [`Error`] into a JavaScript [`JsError`].
+fn js_error(error: impl core::fmt::Display) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+/// Parses the Wasm value type `name`, e.g. `"i32"`.
+///
+/// # Errors
+///
+/// If `name` is not a Wasm value type supported at the JavaScript boundary.
+fn parse_val_type(name: &str) -> Result<ValType, Error> {
+    match name {
+        "i32" => Ok(ValType::I32),
+        "i64" => Ok(ValType::I64),
+        "f32" => Ok(ValType::F32),
+        "f64" => Ok(ValType::F64),
+        _ => Err(Error::new(format!("unsupported value type: {name}"))),
+    }
+}
+
+/// Converts the Wasm `val` into a JavaScript value.
+///
+/// # Errors
+///
+/// If `val` is a reference type.
+fn to_js(val: &Val) -> Result<JsValue, Error> {
+    let value = match val {
+        Val::I32(value) => JsValue::from(*value),
+        Val::I64(value) => JsValue::from(BigInt::from(*value)),
+        Val::F32(value) => JsValue::from(f32::from(*value)),
+        Val::F64(value) => JsValue::from(f64::from(*value)),
+        Val::FuncRef(_) | Val::ExternRef(_) => {
+            return Err(Error::new(
+                "reference types are not supported by JavaScript hosts",
+            ))
+        }
+    };
+    Ok(value)
+}
+
+/// Converts the JavaScript `value` into a Wasm value of type `ty`.
+///
+/// # Errors
+///
+/// If `value` cannot be converted to a Wasm value of type `ty`.
+fn from_js(value: &JsValue, ty: &ValType) -> Result<Val, Error> {
+    let mismatch = || Error::new(format!("expected a JavaScript value of type {ty:?}"));
+    let val = match ty {
+        ValType::I32 => Val::I32(value.as_f64().ok_or_else(mismatch)? as i32),
+        ValType::I64 => Val::I64(i64::try_from(value.clone()).map_err(|_| mismatch())?),
+        ValType::F32 => Val::F32((value.as_f64().ok_or_else(mismatch)? as f32).into()),
+        ValType::F64 => Val::F64(value.as_f64().ok_or_else(mismatch)?.into()),
+        _ => return Err(mismatch()),
+    };
+    Ok(val)
+}
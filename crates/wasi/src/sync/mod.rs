@@ -2,10 +2,12 @@
 //! consumers of this library from having to keep additional dependencies
 //! in sync.
 
+mod sleep;
 pub mod snapshots;
 
 pub use wasi_common::sync::*;
 
+pub use self::sleep::{add_yielding_sleep_to_linker, WasiSleep};
 #[doc(inline)]
 pub use self::snapshots::preview_1::{
     add_wasi_snapshot_preview1_to_linker as add_to_linker,
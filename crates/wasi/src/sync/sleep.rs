@@ -0,0 +1,162 @@
+use crate::{sync::snapshots::preview_1::run_in_dummy_executor, WasmiGuestMemory};
+use std::time::Duration;
+use wasi_common::{
+    snapshots::preview_1::{
+        types,
+        wasi_snapshot_preview1::{self, WasiSnapshotPreview1},
+    },
+    Error,
+};
+use wasmi::{Caller, Extern, Linker};
+use wiggle::GuestPtr;
+
+/// The payload of a WASI guest execution that has been suspended by a sleep.
+///
+/// Retrieve it from a suspended [`ResumableInvocation`] via [`ResumableInvocation::payload_mut`].
+/// Once the [`WasiSleep::duration`] has elapsed, resume the [`ResumableInvocation`] with
+/// `Val::I32(0)` as result of the WASI `poll_oneoff` host function.
+///
+/// [`ResumableInvocation`]: wasmi::ResumableInvocation
+/// [`ResumableInvocation::payload_mut`]: wasmi::ResumableInvocation::payload_mut
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WasiSleep {
+    /// The time the guest wants to sleep.
+    duration: Duration,
+}
+
+impl WasiSleep {
+    /// Returns the time the suspended guest wants to sleep.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// Makes the WASI `poll_oneoff` of the Wasmi [`Linker`] suspend sleeping guests.
+///
+/// Guests sleep by calling `poll_oneoff` with relative clock subscriptions only, e.g. via
+/// `sleep` or `nanosleep` of WASI libc. Instead of blocking the host thread, such calls write
+/// the event of the earliest clock subscription and suspend the execution with a [`WasiSleep`]
+/// payload. Embedders call Wasm functions via [`Func::call_resumable`] and resume them once the
+/// [`WasiSleep::duration`] has elapsed, e.g. from an asynchronous timer, so that sleeping guests
+/// do not occupy a host thread.
+///
+/// # Note
+///
+/// - This shadows the `poll_oneoff` definition of [`add_to_linker`] and therefore must be
+///   called after it. The shadowing configuration of the [`Linker`] is left unchanged.
+/// - All other `poll_oneoff` calls are handled by the blocking implementation of [`add_to_linker`].
+///
+/// [`Func::call_resumable`]: wasmi::Func::call_resumable
+/// [`add_to_linker`]: crate::add_to_linker
+pub fn add_yielding_sleep_to_linker<T, U>(
+    linker: &mut Linker<T>,
+    wasi_ctx: impl Fn(&mut T) -> &mut U + Send + Sync + Copy + 'static,
+) -> Result<(), Error>
+where
+    U: WasiSnapshotPreview1,
+{
+    let allow_shadowing = linker.is_shadowing_allowed();
+    linker.allow_shadowing(true);
+    let result = linker
+        .func_wrap(
+            "wasi_snapshot_preview1",
+            "poll_oneoff",
+            move |mut caller: Caller<'_, T>,
+                  in_: i32,
+                  out: i32,
+                  nsubscriptions: i32,
+                  offset0: i32|
+                  -> Result<i32, wasmi::Error> {
+                let memory = match caller.get_export("memory") {
+                    Some(Extern::Memory(m)) => m,
+                    _ => {
+                        return Err(wasmi::Error::new(String::from(
+                            "missing required WASI memory export",
+                        )))
+                    }
+                };
+                let (memory, ctx) = memory.data_and_store_mut(&mut caller);
+                let ctx = wasi_ctx(ctx);
+                let mut memory = WasmiGuestMemory::Unshared(memory);
+                if let Some(duration) =
+                    prepare_sleep(&mut memory, in_, out, nsubscriptions, offset0)
+                {
+                    if duration.is_zero() {
+                        return Ok(types::Errno::Success as i32);
+                    }
+                    return Err(wasmi::Error::suspend(WasiSleep { duration }));
+                }
+                let result = async {
+                    match wasi_snapshot_preview1::poll_oneoff(
+                        ctx,
+                        &mut memory,
+                        in_,
+                        out,
+                        nsubscriptions,
+                        offset0,
+                    )
+                    .await
+                    {
+                        Ok(r) => Ok(r),
+                        Err(e) => match e.downcast::<wasi_common::I32Exit>() {
+                            Ok(wasi_common::I32Exit(status)) => Err(wasmi::Error::i32_exit(status)),
+                            Err(e) => Err(wasmi::Error::new(e.to_string())),
+                        },
+                    }
+                };
+                run_in_dummy_executor(result)?
+            },
+        )
+        .map(|_| ());
+    linker.allow_shadowing(allow_shadowing);
+    result
+        .map_err(wiggle::anyhow::Error::from)
+        .map_err(Error::trap)
+}
+
+/// Prepares the `poll_oneoff` call for suspension if it only subscribes to relative clocks.
+///
+/// Writes the event of the earliest clock subscription to `out` and the number of events
+/// to `nevents` and returns the time to sleep. Returns `None` if the `poll_oneoff` call
+/// must be handled by the blocking implementation instead.
+fn prepare_sleep(
+    memory: &mut WasmiGuestMemory,
+    in_: i32,
+    out: i32,
+    nsubscriptions: i32,
+    nevents: i32,
+) -> Option<Duration> {
+    let nsubscriptions = u32::try_from(nsubscriptions).ok().filter(|&n| n != 0)?;
+    let subs = GuestPtr::<types::Subscription>::new(in_ as u32).as_array(nsubscriptions);
+    let mut earliest: Option<(types::Userdata, types::Timestamp)> = None;
+    for sub in subs.iter() {
+        let sub = memory.read(sub.ok()?).ok()?;
+        let types::SubscriptionU::Clock(clock) = sub.u else {
+            return None;
+        };
+        if clock
+            .flags
+            .contains(types::Subclockflags::SUBSCRIPTION_CLOCK_ABSTIME)
+        {
+            return None;
+        }
+        if earliest.map_or(true, |(_, timeout)| clock.timeout < timeout) {
+            earliest = Some((sub.userdata, clock.timeout));
+        }
+    }
+    let (userdata, timeout) = earliest?;
+    let event = types::Event {
+        userdata,
+        error: types::Errno::Success,
+        type_: types::Eventtype::Clock,
+        fd_readwrite: types::EventFdReadwrite {
+            nbytes: 0,
+            flags: types::Eventrwflags::empty(),
+        },
+    };
+    memory.write(GuestPtr::new(out as u32), event).ok()?;
+    memory
+        .write(GuestPtr::<types::Size>::new(nevents as u32), 1)
+        .ok()?;
+    Some(Duration::from_nanos(timeout))
+}
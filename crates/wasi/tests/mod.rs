@@ -1 +1,2 @@
 mod wasi_wat;
+mod yielding_sleep;
@@ -0,0 +1,114 @@
+use std::time::Duration;
+use wasi_common::sync::WasiCtxBuilder;
+use wasmi::{Engine, Func, Linker, Memory, Module, ResumableCall, Store, Val};
+use wasmi_wasi::{add_to_linker, add_yielding_sleep_to_linker, WasiCtx, WasiSleep};
+
+/// A Wasm module polling the subscriptions at address 0.
+///
+/// Events are written to address 256 and the number of events to address 512.
+const WAT: &str = r#"
+    (module
+        (import "wasi_snapshot_preview1" "poll_oneoff"
+            (func $poll_oneoff (param i32 i32 i32 i32) (result i32))
+        )
+        (memory (export "memory") 1)
+        (func (export "poll") (param $nsubscriptions i32) (result i32)
+            (call $poll_oneoff
+                (i32.const 0)
+                (i32.const 256)
+                (local.get $nsubscriptions)
+                (i32.const 512)
+            )
+        )
+    )
+"#;
+
+/// The `subclockflags::subscription_clock_abstime` WASI flag.
+const ABSTIME: u16 = 1;
+
+fn setup() -> (Store<WasiCtx>, Func, Memory) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WAT).unwrap();
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    add_yielding_sleep_to_linker(&mut linker, |ctx| ctx).unwrap();
+    assert!(!linker.is_shadowing_allowed());
+    let mut store = Store::new(&engine, WasiCtxBuilder::new().build());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let poll = instance.get_func(&store, "poll").unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    (store, poll, memory)
+}
+
+/// Writes the monotonic clock subscription at `index` to `memory`.
+fn subscribe(store: &mut Store<WasiCtx>, memory: Memory, index: usize, sub: (u64, u64, u16)) {
+    let (userdata, timeout, flags) = sub;
+    let base = index * 48;
+    let data = memory.data_mut(store);
+    data[base..base + 48].fill(0);
+    data[base..base + 8].copy_from_slice(&userdata.to_le_bytes());
+    data[base + 16..base + 20].copy_from_slice(&1_u32.to_le_bytes());
+    data[base + 24..base + 32].copy_from_slice(&timeout.to_le_bytes());
+    data[base + 40..base + 42].copy_from_slice(&flags.to_le_bytes());
+}
+
+/// Returns the number of events and the `userdata` of the first event.
+fn events(store: &Store<WasiCtx>, memory: Memory) -> (u32, u64) {
+    let data = memory.data(store);
+    let nevents = u32::from_le_bytes(data[512..516].try_into().unwrap());
+    let userdata = u64::from_le_bytes(data[256..264].try_into().unwrap());
+    (nevents, userdata)
+}
+
+#[test]
+fn sleep_suspends_execution() {
+    let (mut store, poll, memory) = setup();
+    let second = Duration::from_secs(1).as_nanos() as u64;
+    subscribe(&mut store, memory, 0, (1, 2 * second, 0));
+    subscribe(&mut store, memory, 1, (2, second, 0));
+    let mut results = [Val::I32(-1)];
+    let ResumableCall::Resumable(mut invocation) = poll
+        .call_resumable(&mut store, &[Val::I32(2)], &mut results)
+        .unwrap()
+    else {
+        panic!("expected the sleeping execution to be suspended")
+    };
+    let sleep = invocation.payload_mut::<WasiSleep>().copied().unwrap();
+    assert_eq!(sleep.duration(), Duration::from_secs(1));
+    assert_eq!(events(&store, memory), (1, 2));
+    let resumed = invocation
+        .resume(&mut store, &[Val::I32(0)], &mut results)
+        .unwrap();
+    assert!(matches!(resumed, ResumableCall::Finished));
+    assert_eq!(results[0].i32(), Some(0));
+}
+
+#[test]
+fn zero_sleep_does_not_suspend() {
+    let (mut store, poll, memory) = setup();
+    subscribe(&mut store, memory, 0, (3, 0, 0));
+    let mut results = [Val::I32(-1)];
+    let call = poll
+        .call_resumable(&mut store, &[Val::I32(1)], &mut results)
+        .unwrap();
+    assert!(matches!(call, ResumableCall::Finished));
+    assert_eq!(results[0].i32(), Some(0));
+    assert_eq!(events(&store, memory), (1, 3));
+}
+
+#[test]
+fn absolute_sleep_blocks() {
+    let (mut store, poll, memory) = setup();
+    subscribe(&mut store, memory, 0, (4, 0, ABSTIME));
+    let mut results = [Val::I32(-1)];
+    let call = poll
+        .call_resumable(&mut store, &[Val::I32(1)], &mut results)
+        .unwrap();
+    assert!(matches!(call, ResumableCall::Finished));
+    assert_eq!(results[0].i32(), Some(0));
+    assert_eq!(events(&store, memory), (1, 4));
+}
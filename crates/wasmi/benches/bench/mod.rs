@@ -85,7 +85,23 @@ pub fn wat2wasm(bytes: &[u8]) -> Vec<u8> {
 ///
 /// If the benchmark Wasm file could not be opened, read or parsed.
 pub fn load_instance_from_wat(wasm: &[u8]) -> (wasmi::Store<()>, wasmi::Instance) {
-    let engine = wasmi::Engine::new(&bench_config());
+    load_instance_from_wat_with_config(wasm, &bench_config())
+}
+
+/// Parses the Wasm source from the given `.wat` bytes into a Wasmi module using `config`.
+///
+/// # Note
+///
+/// This includes validation and compilation to Wasmi bytecode.
+///
+/// # Panics
+///
+/// If the benchmark Wasm file could not be opened, read or parsed.
+pub fn load_instance_from_wat_with_config(
+    wasm: &[u8],
+    config: &Config,
+) -> (wasmi::Store<()>, wasmi::Instance) {
+    let engine = wasmi::Engine::new(config);
     let module = wasmi::Module::new(&engine, wasm).unwrap();
     let linker = <wasmi::Linker<()>>::new(&engine);
     let mut store = wasmi::Store::new(&engine, ());
@@ -3,6 +3,7 @@ mod bench;
 use self::bench::{
     load_instance_from_file,
     load_instance_from_wat,
+    load_instance_from_wat_with_config,
     load_module_from_file,
     load_wasm_from_file,
     wat2wasm,
@@ -80,6 +81,7 @@ criterion_group!(
         .warm_up_time(Duration::from_millis(1000));
     targets =
         bench_overhead_call_typed_0,
+        bench_overhead_call_typed_1,
         bench_overhead_call_typed_16,
         bench_overhead_call_untyped_0,
         bench_overhead_call_untyped_16,
@@ -112,6 +114,7 @@ criterion_group! {
         bench_execute_br_table,
         bench_execute_trunc_f2i,
         bench_execute_global_bump,
+        bench_execute_global_bump_many,
         bench_execute_global_const,
         bench_execute_recursive_scan,
         bench_execute_recursive_trap,
@@ -121,6 +124,7 @@ criterion_group! {
         bench_execute_fuse,
         bench_execute_divrem,
         bench_execute_fibonacci,
+        bench_execute_zero_on_free,
         bench_execute_recursive_is_even,
         bench_execute_memory_sum,
         bench_execute_memory_fill,
@@ -922,6 +926,21 @@ fn bench_overhead_call_typed_0(c: &mut Criterion) {
     });
 }
 
+fn bench_overhead_call_typed_1(c: &mut Criterion) {
+    const REPETITIONS: usize = 20_000;
+    c.bench_function("overhead/call/typed/1", |b| {
+        let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/bare_call.wat"));
+        let bare_call = instance
+            .get_typed_func::<i32, i32>(&store, "bare_call/1")
+            .unwrap();
+        b.iter(|| {
+            for n in 0..REPETITIONS as i32 {
+                assert_eq!(bare_call.call(&mut store, n).unwrap(), n);
+            }
+        })
+    });
+}
+
 fn bench_overhead_call_typed_16(c: &mut Criterion) {
     const REPETITIONS: usize = 20_000;
     type InOut = (
@@ -1036,6 +1055,19 @@ fn bench_execute_global_bump(c: &mut Criterion) {
     });
 }
 
+fn bench_execute_global_bump_many(c: &mut Criterion) {
+    const ITERATIONS: i32 = 100_000;
+    c.bench_function("execute/global/bump_many", |b| {
+        let (mut store, instance) =
+            load_instance_from_wat(include_bytes!("wat/global_bump_many.wat"));
+        let run = instance.get_typed_func::<i32, i32>(&store, "bump").unwrap();
+        b.iter(|| {
+            let result = run.call(&mut store, ITERATIONS).unwrap();
+            assert_eq!(result, ITERATIONS);
+        })
+    });
+}
+
 fn bench_execute_global_const(c: &mut Criterion) {
     const ITERATIONS: i32 = 100_000;
     c.bench_function("execute/global/get_const", |b| {
@@ -1311,6 +1343,29 @@ fn bench_execute_fibonacci(c: &mut Criterion) {
     bench_fib("execute/fibonacci/iter", "fibonacci_iter", FIBONACCI_INC_N);
 }
 
+fn bench_execute_zero_on_free(c: &mut Criterion) {
+    const FIBONACCI_REC_N: i64 = 25;
+    const EXPECTED: i64 = 75025;
+    for zero_on_free in [false, true] {
+        let bench_id = match zero_on_free {
+            false => "execute/zero_on_free/disabled",
+            true => "execute/zero_on_free/enabled",
+        };
+        c.bench_function(bench_id, |b| {
+            let mut config = bench_config();
+            config.zero_on_free(zero_on_free);
+            let (mut store, instance) =
+                load_instance_from_wat_with_config(include_bytes!("wat/fibonacci.wat"), &config);
+            let run = instance
+                .get_typed_func::<i64, i64>(&store, "fibonacci_rec")
+                .unwrap();
+            b.iter(|| {
+                assert_eq!(run.call(&mut store, FIBONACCI_REC_N).unwrap(), EXPECTED);
+            });
+        });
+    }
+}
+
 fn bench_execute_memory_sum(c: &mut Criterion) {
     c.bench_function("execute/memory/sum_bytes", |b| {
         let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/memory-sum.wat"));
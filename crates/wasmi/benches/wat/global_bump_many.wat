@@ -0,0 +1,327 @@
+;; Exports a function `bump` that takes an input `n`.
+;; The exported function bumps a global variable `n` times and then returns it.
+;; Unlike `global_bump.wat` the bumped global variable is one of many and has a large index.
+(module
+    (global $g0 (mut i32) (i32.const 0))
+    (global $g1 (mut i32) (i32.const 0))
+    (global $g2 (mut i32) (i32.const 0))
+    (global $g3 (mut i32) (i32.const 0))
+    (global $g4 (mut i32) (i32.const 0))
+    (global $g5 (mut i32) (i32.const 0))
+    (global $g6 (mut i32) (i32.const 0))
+    (global $g7 (mut i32) (i32.const 0))
+    (global $g8 (mut i32) (i32.const 0))
+    (global $g9 (mut i32) (i32.const 0))
+    (global $g10 (mut i32) (i32.const 0))
+    (global $g11 (mut i32) (i32.const 0))
+    (global $g12 (mut i32) (i32.const 0))
+    (global $g13 (mut i32) (i32.const 0))
+    (global $g14 (mut i32) (i32.const 0))
+    (global $g15 (mut i32) (i32.const 0))
+    (global $g16 (mut i32) (i32.const 0))
+    (global $g17 (mut i32) (i32.const 0))
+    (global $g18 (mut i32) (i32.const 0))
+    (global $g19 (mut i32) (i32.const 0))
+    (global $g20 (mut i32) (i32.const 0))
+    (global $g21 (mut i32) (i32.const 0))
+    (global $g22 (mut i32) (i32.const 0))
+    (global $g23 (mut i32) (i32.const 0))
+    (global $g24 (mut i32) (i32.const 0))
+    (global $g25 (mut i32) (i32.const 0))
+    (global $g26 (mut i32) (i32.const 0))
+    (global $g27 (mut i32) (i32.const 0))
+    (global $g28 (mut i32) (i32.const 0))
+    (global $g29 (mut i32) (i32.const 0))
+    (global $g30 (mut i32) (i32.const 0))
+    (global $g31 (mut i32) (i32.const 0))
+    (global $g32 (mut i32) (i32.const 0))
+    (global $g33 (mut i32) (i32.const 0))
+    (global $g34 (mut i32) (i32.const 0))
+    (global $g35 (mut i32) (i32.const 0))
+    (global $g36 (mut i32) (i32.const 0))
+    (global $g37 (mut i32) (i32.const 0))
+    (global $g38 (mut i32) (i32.const 0))
+    (global $g39 (mut i32) (i32.const 0))
+    (global $g40 (mut i32) (i32.const 0))
+    (global $g41 (mut i32) (i32.const 0))
+    (global $g42 (mut i32) (i32.const 0))
+    (global $g43 (mut i32) (i32.const 0))
+    (global $g44 (mut i32) (i32.const 0))
+    (global $g45 (mut i32) (i32.const 0))
+    (global $g46 (mut i32) (i32.const 0))
+    (global $g47 (mut i32) (i32.const 0))
+    (global $g48 (mut i32) (i32.const 0))
+    (global $g49 (mut i32) (i32.const 0))
+    (global $g50 (mut i32) (i32.const 0))
+    (global $g51 (mut i32) (i32.const 0))
+    (global $g52 (mut i32) (i32.const 0))
+    (global $g53 (mut i32) (i32.const 0))
+    (global $g54 (mut i32) (i32.const 0))
+    (global $g55 (mut i32) (i32.const 0))
+    (global $g56 (mut i32) (i32.const 0))
+    (global $g57 (mut i32) (i32.const 0))
+    (global $g58 (mut i32) (i32.const 0))
+    (global $g59 (mut i32) (i32.const 0))
+    (global $g60 (mut i32) (i32.const 0))
+    (global $g61 (mut i32) (i32.const 0))
+    (global $g62 (mut i32) (i32.const 0))
+    (global $g63 (mut i32) (i32.const 0))
+    (global $g64 (mut i32) (i32.const 0))
+    (global $g65 (mut i32) (i32.const 0))
+    (global $g66 (mut i32) (i32.const 0))
+    (global $g67 (mut i32) (i32.const 0))
+    (global $g68 (mut i32) (i32.const 0))
+    (global $g69 (mut i32) (i32.const 0))
+    (global $g70 (mut i32) (i32.const 0))
+    (global $g71 (mut i32) (i32.const 0))
+    (global $g72 (mut i32) (i32.const 0))
+    (global $g73 (mut i32) (i32.const 0))
+    (global $g74 (mut i32) (i32.const 0))
+    (global $g75 (mut i32) (i32.const 0))
+    (global $g76 (mut i32) (i32.const 0))
+    (global $g77 (mut i32) (i32.const 0))
+    (global $g78 (mut i32) (i32.const 0))
+    (global $g79 (mut i32) (i32.const 0))
+    (global $g80 (mut i32) (i32.const 0))
+    (global $g81 (mut i32) (i32.const 0))
+    (global $g82 (mut i32) (i32.const 0))
+    (global $g83 (mut i32) (i32.const 0))
+    (global $g84 (mut i32) (i32.const 0))
+    (global $g85 (mut i32) (i32.const 0))
+    (global $g86 (mut i32) (i32.const 0))
+    (global $g87 (mut i32) (i32.const 0))
+    (global $g88 (mut i32) (i32.const 0))
+    (global $g89 (mut i32) (i32.const 0))
+    (global $g90 (mut i32) (i32.const 0))
+    (global $g91 (mut i32) (i32.const 0))
+    (global $g92 (mut i32) (i32.const 0))
+    (global $g93 (mut i32) (i32.const 0))
+    (global $g94 (mut i32) (i32.const 0))
+    (global $g95 (mut i32) (i32.const 0))
+    (global $g96 (mut i32) (i32.const 0))
+    (global $g97 (mut i32) (i32.const 0))
+    (global $g98 (mut i32) (i32.const 0))
+    (global $g99 (mut i32) (i32.const 0))
+    (global $g100 (mut i32) (i32.const 0))
+    (global $g101 (mut i32) (i32.const 0))
+    (global $g102 (mut i32) (i32.const 0))
+    (global $g103 (mut i32) (i32.const 0))
+    (global $g104 (mut i32) (i32.const 0))
+    (global $g105 (mut i32) (i32.const 0))
+    (global $g106 (mut i32) (i32.const 0))
+    (global $g107 (mut i32) (i32.const 0))
+    (global $g108 (mut i32) (i32.const 0))
+    (global $g109 (mut i32) (i32.const 0))
+    (global $g110 (mut i32) (i32.const 0))
+    (global $g111 (mut i32) (i32.const 0))
+    (global $g112 (mut i32) (i32.const 0))
+    (global $g113 (mut i32) (i32.const 0))
+    (global $g114 (mut i32) (i32.const 0))
+    (global $g115 (mut i32) (i32.const 0))
+    (global $g116 (mut i32) (i32.const 0))
+    (global $g117 (mut i32) (i32.const 0))
+    (global $g118 (mut i32) (i32.const 0))
+    (global $g119 (mut i32) (i32.const 0))
+    (global $g120 (mut i32) (i32.const 0))
+    (global $g121 (mut i32) (i32.const 0))
+    (global $g122 (mut i32) (i32.const 0))
+    (global $g123 (mut i32) (i32.const 0))
+    (global $g124 (mut i32) (i32.const 0))
+    (global $g125 (mut i32) (i32.const 0))
+    (global $g126 (mut i32) (i32.const 0))
+    (global $g127 (mut i32) (i32.const 0))
+    (global $g128 (mut i32) (i32.const 0))
+    (global $g129 (mut i32) (i32.const 0))
+    (global $g130 (mut i32) (i32.const 0))
+    (global $g131 (mut i32) (i32.const 0))
+    (global $g132 (mut i32) (i32.const 0))
+    (global $g133 (mut i32) (i32.const 0))
+    (global $g134 (mut i32) (i32.const 0))
+    (global $g135 (mut i32) (i32.const 0))
+    (global $g136 (mut i32) (i32.const 0))
+    (global $g137 (mut i32) (i32.const 0))
+    (global $g138 (mut i32) (i32.const 0))
+    (global $g139 (mut i32) (i32.const 0))
+    (global $g140 (mut i32) (i32.const 0))
+    (global $g141 (mut i32) (i32.const 0))
+    (global $g142 (mut i32) (i32.const 0))
+    (global $g143 (mut i32) (i32.const 0))
+    (global $g144 (mut i32) (i32.const 0))
+    (global $g145 (mut i32) (i32.const 0))
+    (global $g146 (mut i32) (i32.const 0))
+    (global $g147 (mut i32) (i32.const 0))
+    (global $g148 (mut i32) (i32.const 0))
+    (global $g149 (mut i32) (i32.const 0))
+    (global $g150 (mut i32) (i32.const 0))
+    (global $g151 (mut i32) (i32.const 0))
+    (global $g152 (mut i32) (i32.const 0))
+    (global $g153 (mut i32) (i32.const 0))
+    (global $g154 (mut i32) (i32.const 0))
+    (global $g155 (mut i32) (i32.const 0))
+    (global $g156 (mut i32) (i32.const 0))
+    (global $g157 (mut i32) (i32.const 0))
+    (global $g158 (mut i32) (i32.const 0))
+    (global $g159 (mut i32) (i32.const 0))
+    (global $g160 (mut i32) (i32.const 0))
+    (global $g161 (mut i32) (i32.const 0))
+    (global $g162 (mut i32) (i32.const 0))
+    (global $g163 (mut i32) (i32.const 0))
+    (global $g164 (mut i32) (i32.const 0))
+    (global $g165 (mut i32) (i32.const 0))
+    (global $g166 (mut i32) (i32.const 0))
+    (global $g167 (mut i32) (i32.const 0))
+    (global $g168 (mut i32) (i32.const 0))
+    (global $g169 (mut i32) (i32.const 0))
+    (global $g170 (mut i32) (i32.const 0))
+    (global $g171 (mut i32) (i32.const 0))
+    (global $g172 (mut i32) (i32.const 0))
+    (global $g173 (mut i32) (i32.const 0))
+    (global $g174 (mut i32) (i32.const 0))
+    (global $g175 (mut i32) (i32.const 0))
+    (global $g176 (mut i32) (i32.const 0))
+    (global $g177 (mut i32) (i32.const 0))
+    (global $g178 (mut i32) (i32.const 0))
+    (global $g179 (mut i32) (i32.const 0))
+    (global $g180 (mut i32) (i32.const 0))
+    (global $g181 (mut i32) (i32.const 0))
+    (global $g182 (mut i32) (i32.const 0))
+    (global $g183 (mut i32) (i32.const 0))
+    (global $g184 (mut i32) (i32.const 0))
+    (global $g185 (mut i32) (i32.const 0))
+    (global $g186 (mut i32) (i32.const 0))
+    (global $g187 (mut i32) (i32.const 0))
+    (global $g188 (mut i32) (i32.const 0))
+    (global $g189 (mut i32) (i32.const 0))
+    (global $g190 (mut i32) (i32.const 0))
+    (global $g191 (mut i32) (i32.const 0))
+    (global $g192 (mut i32) (i32.const 0))
+    (global $g193 (mut i32) (i32.const 0))
+    (global $g194 (mut i32) (i32.const 0))
+    (global $g195 (mut i32) (i32.const 0))
+    (global $g196 (mut i32) (i32.const 0))
+    (global $g197 (mut i32) (i32.const 0))
+    (global $g198 (mut i32) (i32.const 0))
+    (global $g199 (mut i32) (i32.const 0))
+    (global $g200 (mut i32) (i32.const 0))
+    (global $g201 (mut i32) (i32.const 0))
+    (global $g202 (mut i32) (i32.const 0))
+    (global $g203 (mut i32) (i32.const 0))
+    (global $g204 (mut i32) (i32.const 0))
+    (global $g205 (mut i32) (i32.const 0))
+    (global $g206 (mut i32) (i32.const 0))
+    (global $g207 (mut i32) (i32.const 0))
+    (global $g208 (mut i32) (i32.const 0))
+    (global $g209 (mut i32) (i32.const 0))
+    (global $g210 (mut i32) (i32.const 0))
+    (global $g211 (mut i32) (i32.const 0))
+    (global $g212 (mut i32) (i32.const 0))
+    (global $g213 (mut i32) (i32.const 0))
+    (global $g214 (mut i32) (i32.const 0))
+    (global $g215 (mut i32) (i32.const 0))
+    (global $g216 (mut i32) (i32.const 0))
+    (global $g217 (mut i32) (i32.const 0))
+    (global $g218 (mut i32) (i32.const 0))
+    (global $g219 (mut i32) (i32.const 0))
+    (global $g220 (mut i32) (i32.const 0))
+    (global $g221 (mut i32) (i32.const 0))
+    (global $g222 (mut i32) (i32.const 0))
+    (global $g223 (mut i32) (i32.const 0))
+    (global $g224 (mut i32) (i32.const 0))
+    (global $g225 (mut i32) (i32.const 0))
+    (global $g226 (mut i32) (i32.const 0))
+    (global $g227 (mut i32) (i32.const 0))
+    (global $g228 (mut i32) (i32.const 0))
+    (global $g229 (mut i32) (i32.const 0))
+    (global $g230 (mut i32) (i32.const 0))
+    (global $g231 (mut i32) (i32.const 0))
+    (global $g232 (mut i32) (i32.const 0))
+    (global $g233 (mut i32) (i32.const 0))
+    (global $g234 (mut i32) (i32.const 0))
+    (global $g235 (mut i32) (i32.const 0))
+    (global $g236 (mut i32) (i32.const 0))
+    (global $g237 (mut i32) (i32.const 0))
+    (global $g238 (mut i32) (i32.const 0))
+    (global $g239 (mut i32) (i32.const 0))
+    (global $g240 (mut i32) (i32.const 0))
+    (global $g241 (mut i32) (i32.const 0))
+    (global $g242 (mut i32) (i32.const 0))
+    (global $g243 (mut i32) (i32.const 0))
+    (global $g244 (mut i32) (i32.const 0))
+    (global $g245 (mut i32) (i32.const 0))
+    (global $g246 (mut i32) (i32.const 0))
+    (global $g247 (mut i32) (i32.const 0))
+    (global $g248 (mut i32) (i32.const 0))
+    (global $g249 (mut i32) (i32.const 0))
+    (global $g250 (mut i32) (i32.const 0))
+    (global $g251 (mut i32) (i32.const 0))
+    (global $g252 (mut i32) (i32.const 0))
+    (global $g253 (mut i32) (i32.const 0))
+    (global $g254 (mut i32) (i32.const 0))
+    (global $g255 (mut i32) (i32.const 0))
+    (global $g256 (mut i32) (i32.const 0))
+    (global $g257 (mut i32) (i32.const 0))
+    (global $g258 (mut i32) (i32.const 0))
+    (global $g259 (mut i32) (i32.const 0))
+    (global $g260 (mut i32) (i32.const 0))
+    (global $g261 (mut i32) (i32.const 0))
+    (global $g262 (mut i32) (i32.const 0))
+    (global $g263 (mut i32) (i32.const 0))
+    (global $g264 (mut i32) (i32.const 0))
+    (global $g265 (mut i32) (i32.const 0))
+    (global $g266 (mut i32) (i32.const 0))
+    (global $g267 (mut i32) (i32.const 0))
+    (global $g268 (mut i32) (i32.const 0))
+    (global $g269 (mut i32) (i32.const 0))
+    (global $g270 (mut i32) (i32.const 0))
+    (global $g271 (mut i32) (i32.const 0))
+    (global $g272 (mut i32) (i32.const 0))
+    (global $g273 (mut i32) (i32.const 0))
+    (global $g274 (mut i32) (i32.const 0))
+    (global $g275 (mut i32) (i32.const 0))
+    (global $g276 (mut i32) (i32.const 0))
+    (global $g277 (mut i32) (i32.const 0))
+    (global $g278 (mut i32) (i32.const 0))
+    (global $g279 (mut i32) (i32.const 0))
+    (global $g280 (mut i32) (i32.const 0))
+    (global $g281 (mut i32) (i32.const 0))
+    (global $g282 (mut i32) (i32.const 0))
+    (global $g283 (mut i32) (i32.const 0))
+    (global $g284 (mut i32) (i32.const 0))
+    (global $g285 (mut i32) (i32.const 0))
+    (global $g286 (mut i32) (i32.const 0))
+    (global $g287 (mut i32) (i32.const 0))
+    (global $g288 (mut i32) (i32.const 0))
+    (global $g289 (mut i32) (i32.const 0))
+    (global $g290 (mut i32) (i32.const 0))
+    (global $g291 (mut i32) (i32.const 0))
+    (global $g292 (mut i32) (i32.const 0))
+    (global $g293 (mut i32) (i32.const 0))
+    (global $g294 (mut i32) (i32.const 0))
+    (global $g295 (mut i32) (i32.const 0))
+    (global $g296 (mut i32) (i32.const 0))
+    (global $g297 (mut i32) (i32.const 0))
+    (global $g298 (mut i32) (i32.const 0))
+    (global $g (mut i32) (i32.const 0))
+    (func $bump (export "bump") (param $n i32) (result i32)
+        (global.set $g (i32.const 0))
+        (block $break
+            (loop $continue
+                (br_if ;; if $g == $n then break
+                    $break
+                    (i32.eq
+                        (global.get $g)
+                        (local.get $n)
+                    )
+                )
+                (global.set $g ;; $g += 1
+                    (i32.add
+                        (global.get $g)
+                        (i32.const 1)
+                    )
+                )
+                (br $continue)
+            )
+        )
+        (return (global.get $g))
+    )
+)
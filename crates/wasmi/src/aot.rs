@@ -0,0 +1,855 @@
+use crate::{
+    core::{TrapCode, UntypedVal},
+    ir::{Const16, Instruction, Reg, ShiftAmount},
+    value::WithType,
+    AsContext,
+    AsContextMut,
+    Error,
+    Func,
+    FuncEntity,
+    FuncType,
+};
+use alloc::{collections::BTreeSet, format, string::String, vec, vec::Vec};
+use core::{
+    fmt::Write as _,
+    num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64},
+};
+
+/// The trap codes that may be returned by emitted functions.
+///
+/// The status returned by an emitted function for a trap code is its position plus one.
+const TRAP_CODES: [TrapCode; 12] = [
+    TrapCode::UnreachableCodeReached,
+    TrapCode::MemoryOutOfBounds,
+    TrapCode::TableOutOfBounds,
+    TrapCode::IndirectCallToNull,
+    TrapCode::IntegerDivisionByZero,
+    TrapCode::IntegerOverflow,
+    TrapCode::BadConversionToInteger,
+    TrapCode::StackOverflow,
+    TrapCode::BadSignature,
+    TrapCode::OutOfFuel,
+    TrapCode::GrowthOperationLimited,
+    TrapCode::UnsupportedOperator,
+];
+
+/// Emits portable C source code from the Wasmi IR of Wasm functions.
+///
+/// This is an experimental escape hatch for platforms where JIT compilation is banned:
+/// embedders emit C code for selected hot functions ahead of time, compile it with their
+/// C toolchain and register the compiled functions as host functions via
+/// [`AotEmitter::host_func`] in place of the original Wasm functions.
+///
+/// # Supported Functions
+///
+/// Only functions that exclusively perform integer computations are supported.
+/// This includes control flow, integer arithmetic, comparisons and bit operations.
+/// Functions that access linear memories, tables or global variables, that call
+/// other functions or that operate on floating point numbers are rejected.
+///
+/// # Emitted Functions
+///
+/// Every emitted function has the C signature `int32_t name(uint64_t *values)`:
+///
+/// - Upon entry `values` holds the parameters of the function.
+/// - Upon success the function returns zero and `values` holds its results.
+/// - Otherwise the function returns a non-zero status that is converted into
+///   the [`TrapCode`] of the trap via [`AotEmitter::trap_code`].
+///
+/// The `values` buffer must be large enough to hold all parameters and results.
+/// Values are encoded as in [`UntypedVal`].
+///
+/// # Note
+///
+/// - The emitted code is not fuel metered and cannot be interrupted.
+/// - The emitted code depends on the Wasmi IR and thus must be re-emitted for new Wasmi versions.
+#[derive(Debug)]
+pub struct AotEmitter {
+    /// The C source code emitted so far.
+    source: String,
+    /// The names of the emitted functions.
+    names: BTreeSet<String>,
+}
+
+impl Default for AotEmitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AotEmitter {
+    /// Creates a new [`AotEmitter`].
+    pub fn new() -> Self {
+        let mut source = String::from(
+            "/* Generated by the Wasmi AOT emitter. Do not edit. */\n\
+            #include <stdint.h>\n",
+        );
+        for (bits, ty) in [(32, "uint32_t"), (64, "uint64_t")] {
+            let top = bits - 1;
+            let _ = write!(
+                source,
+                "\n\
+                static inline {ty} wasmi_shr_s{bits}({ty} x, {ty} n) {{\n    \
+                    n &= {top};\n    \
+                    return (x >> n) | ((x >> {top}) ? ~(({ty})-1 >> n) : 0);\n\
+                }}\n\
+                static inline {ty} wasmi_rotl{bits}({ty} x, {ty} n) {{\n    \
+                    n &= {top};\n    \
+                    return n ? (x << n) | (x >> ({bits} - n)) : x;\n\
+                }}\n\
+                static inline {ty} wasmi_rotr{bits}({ty} x, {ty} n) {{\n    \
+                    n &= {top};\n    \
+                    return n ? (x >> n) | (x << ({bits} - n)) : x;\n\
+                }}\n\
+                static inline {ty} wasmi_clz{bits}({ty} x) {{\n    \
+                    {ty} n = 0;\n    \
+                    if (x == 0) return {bits};\n    \
+                    while (!(x >> {top})) {{ x <<= 1; n++; }}\n    \
+                    return n;\n\
+                }}\n\
+                static inline {ty} wasmi_ctz{bits}({ty} x) {{\n    \
+                    {ty} n = 0;\n    \
+                    if (x == 0) return {bits};\n    \
+                    while (!(x & 1)) {{ x >>= 1; n++; }}\n    \
+                    return n;\n\
+                }}\n\
+                static inline {ty} wasmi_popcnt{bits}({ty} x) {{\n    \
+                    {ty} n = 0;\n    \
+                    while (x) {{ x &= x - 1; n++; }}\n    \
+                    return n;\n\
+                }}\n"
+            );
+        }
+        Self {
+            source,
+            names: BTreeSet::new(),
+        }
+    }
+
+    /// Emits the C function `name` for the Wasm function `func`.
+    ///
+    /// # Errors
+    ///
+    /// - If `name` is not a valid C identifier or has already been emitted.
+    /// - If `func` is a host function.
+    /// - If `func` uses unsupported instructions. Read more in the docs of [`AotEmitter`].
+    /// - If the compilation of `func` fails.
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` does not own `func`.
+    pub fn emit_func(
+        &mut self,
+        ctx: impl AsContext,
+        func: &Func,
+        name: &str,
+    ) -> Result<&mut Self, Error> {
+        let mut chars = name.chars();
+        let is_identifier = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(Error::new(format!("invalid C function name: {name}")));
+        }
+        if self.names.contains(name) {
+            return Err(Error::new(format!("duplicate C function name: {name}")));
+        }
+        let ty = func.ty(&ctx);
+        let store = &ctx.as_context().store.inner;
+        let FuncEntity::Wasm(entity) = store.resolve_func(func) else {
+            return Err(Error::new(format!(
+                "cannot emit host function as C function: {name}"
+            )));
+        };
+        let source = store.engine().resolve_compiled(
+            entity.func_body(),
+            |instrs, consts, len_registers| {
+                FuncEmitter {
+                    instrs,
+                    consts,
+                    len_registers: len_registers - consts.len() as u16,
+                    targets: BTreeSet::new(),
+                }
+                .emit(name, &ty)
+            },
+        )??;
+        self.source.push_str(&source);
+        self.names.insert(String::from(name));
+        Ok(self)
+    }
+
+    /// Returns the emitted C source code.
+    pub fn finish(self) -> String {
+        self.source
+    }
+
+    /// Returns the [`TrapCode`] for the non-zero `status` returned by an emitted function.
+    pub fn trap_code(status: i32) -> Option<TrapCode> {
+        let index = usize::try_from(status).ok()?.checked_sub(1)?;
+        TRAP_CODES.get(index).copied()
+    }
+
+    /// Creates a host function of type `ty` that calls the compiled emitted function `f`.
+    ///
+    /// Traps of `f` are returned as errors with their [`TrapCode`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `f` has been compiled from the C code emitted
+    /// by an [`AotEmitter`] for a Wasm function of type `ty`.
+    pub unsafe fn host_func<T>(
+        ctx: impl AsContextMut<Data = T>,
+        ty: FuncType,
+        f: unsafe extern "C" fn(*mut u64) -> i32,
+    ) -> Func {
+        let results = ty.results().to_vec();
+        let len_values = ty.params().len().max(results.len()).max(1);
+        Func::new(ctx, ty, move |_caller, params, outputs| {
+            let mut values = vec![0_u64; len_values];
+            for (value, param) in values.iter_mut().zip(params) {
+                *value = u64::from(UntypedVal::from(param.clone()));
+            }
+            // Safety: the caller guarantees that `f` is an emitted function of this type
+            //         and `values` is large enough for all of its parameters and results.
+            let status = unsafe { f(values.as_mut_ptr()) };
+            if status != 0 {
+                let trap_code = Self::trap_code(status).unwrap_or(TrapCode::UnreachableCodeReached);
+                return Err(Error::from(trap_code));
+            }
+            for ((output, ty), value) in outputs.iter_mut().zip(&results).zip(&values) {
+                *output = UntypedVal::from(*value).with_type(*ty);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The integer type of an emitted operation.
+#[derive(Debug, Copy, Clone)]
+enum Ty {
+    /// The Wasm `i32` type.
+    I32,
+    /// The Wasm `i64` type.
+    I64,
+}
+
+impl Ty {
+    /// Returns the bit width of the [`Ty`].
+    fn bits(self) -> u32 {
+        match self {
+            Self::I32 => 32,
+            Self::I64 => 64,
+        }
+    }
+
+    /// Returns the unsigned C type of the [`Ty`].
+    fn unsigned(self) -> &'static str {
+        match self {
+            Self::I32 => "uint32_t",
+            Self::I64 => "uint64_t",
+        }
+    }
+
+    /// Returns the signed C type of the [`Ty`].
+    fn signed(self) -> &'static str {
+        match self {
+            Self::I32 => "int32_t",
+            Self::I64 => "int64_t",
+        }
+    }
+
+    /// Returns the C literal of the unsigned `value` of the [`Ty`].
+    fn literal(self, value: u64) -> String {
+        match self {
+            Self::I32 => format!("UINT32_C({:#x})", value as u32),
+            Self::I64 => format!("UINT64_C({value:#x})"),
+        }
+    }
+}
+
+/// An operand of an emitted operation.
+#[derive(Debug, Copy, Clone)]
+enum Operand {
+    /// A register or function local constant.
+    Reg(Reg),
+    /// An immediate value.
+    Imm(u64),
+}
+
+impl From<Reg> for Operand {
+    fn from(reg: Reg) -> Self {
+        Self::Reg(reg)
+    }
+}
+
+macro_rules! impl_imm_operand {
+    ( $( $ty:ty => |$value:ident| $bits:expr ),* $(,)? ) => {
+        $(
+            impl From<$ty> for Operand {
+                fn from($value: $ty) -> Self {
+                    Self::Imm($bits)
+                }
+            }
+        )*
+    };
+}
+impl_imm_operand! {
+    Const16<i32> => |value| u64::from(i32::from(value) as u32),
+    Const16<u32> => |value| u64::from(u32::from(value)),
+    Const16<i64> => |value| i64::from(value) as u64,
+    Const16<u64> => |value| u64::from(value),
+    Const16<NonZeroI32> => |value| u64::from(NonZeroI32::from(value).get() as u32),
+    Const16<NonZeroU32> => |value| u64::from(NonZeroU32::from(value).get()),
+    Const16<NonZeroI64> => |value| NonZeroI64::from(value).get() as u64,
+    Const16<NonZeroU64> => |value| NonZeroU64::from(value).get(),
+    ShiftAmount<i32> => |value| u64::from(i32::from(value) as u32),
+    ShiftAmount<i64> => |value| i64::from(value) as u64,
+}
+
+/// The C expression templates of the emitted operations.
+///
+/// - `{a}` and `{b}` are replaced by the operands.
+/// - `{S}` and `{U}` are replaced by the signed and unsigned C types.
+/// - `{N}` is replaced by the bit width.
+mod op {
+    pub const ADD: &str = "{a} + {b}";
+    pub const SUB: &str = "{a} - {b}";
+    pub const MUL: &str = "{a} * {b}";
+    pub const AND: &str = "{a} & {b}";
+    pub const OR: &str = "{a} | {b}";
+    pub const XOR: &str = "{a} ^ {b}";
+    pub const AND_EQZ: &str = "({a} & {b}) == 0";
+    pub const OR_EQZ: &str = "({a} | {b}) == 0";
+    pub const XOR_EQZ: &str = "({a} ^ {b}) == 0";
+    pub const SHL: &str = "{a} << ({b} & ({N} - 1))";
+    pub const SHR_U: &str = "{a} >> ({b} & ({N} - 1))";
+    pub const SHR_S: &str = "wasmi_shr_s{N}({a}, {b})";
+    pub const ROTL: &str = "wasmi_rotl{N}({a}, {b})";
+    pub const ROTR: &str = "wasmi_rotr{N}({a}, {b})";
+    pub const EQ: &str = "{a} == {b}";
+    pub const NE: &str = "{a} != {b}";
+    pub const LT_S: &str = "({S}){a} < ({S}){b}";
+    pub const LT_U: &str = "{a} < {b}";
+    pub const LE_S: &str = "({S}){a} <= ({S}){b}";
+    pub const LE_U: &str = "{a} <= {b}";
+    pub const CLZ: &str = "wasmi_clz{N}({a})";
+    pub const CTZ: &str = "wasmi_ctz{N}({a})";
+    pub const POPCNT: &str = "wasmi_popcnt{N}({a})";
+    pub const NEZ: &str = "{a} != 0";
+}
+
+/// The kind of an emitted division or remainder operation.
+#[derive(Debug, Copy, Clone)]
+enum Div {
+    /// Signed division.
+    DivS,
+    /// Unsigned division.
+    DivU,
+    /// Signed remainder.
+    RemS,
+    /// Unsigned remainder.
+    RemU,
+}
+
+/// Emits the C function of a single Wasm function.
+struct FuncEmitter<'a> {
+    /// The Wasmi IR of the function.
+    instrs: &'a [Instruction],
+    /// The function local constant values of the function in their frame order.
+    consts: &'a [UntypedVal],
+    /// The number of non-constant registers of the function.
+    len_registers: u16,
+    /// The positions of all branch targets.
+    targets: BTreeSet<usize>,
+}
+
+impl FuncEmitter<'_> {
+    /// Emits the C function `name` of type `ty`.
+    fn emit(mut self, name: &str, ty: &FuncType) -> Result<String, Error> {
+        let mut bodies = Vec::with_capacity(self.instrs.len());
+        for (pos, instr) in self.instrs.iter().enumerate() {
+            bodies.push(self.instr(pos, instr)?);
+        }
+        let mut source = String::new();
+        let _ = writeln!(
+            source,
+            "\n/* Wasm function of type {:?} -> {:?}. */\nint32_t {name}(uint64_t *values) {{",
+            ty.params(),
+            ty.results(),
+        );
+        if !self.consts.is_empty() {
+            let consts = self
+                .consts
+                .iter()
+                .map(|value| format!("UINT64_C({:#x})", u64::from(*value)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                source,
+                "    static const uint64_t c[{}] = {{ {consts} }};",
+                self.consts.len()
+            );
+        }
+        let _ = writeln!(
+            source,
+            "    uint64_t r[{}] = {{ 0 }};",
+            self.len_registers.max(1)
+        );
+        for index in 0..ty.params().len() {
+            let _ = writeln!(source, "    r[{index}] = values[{index}];");
+        }
+        for (pos, body) in bodies.iter().enumerate() {
+            if self.targets.contains(&pos) {
+                let _ = writeln!(source, "L{pos}:");
+            }
+            if !body.is_empty() {
+                let _ = writeln!(source, "    {body}");
+            }
+        }
+        let _ = writeln!(
+            source,
+            "    return {};\n}}",
+            trap_status(TrapCode::UnreachableCodeReached)?
+        );
+        Ok(source)
+    }
+
+    /// Returns the C statements of `instr` at `pos`.
+    #[rustfmt::skip]
+    fn instr(&mut self, pos: usize, instr: &Instruction) -> Result<String, Error> {
+        use Instruction as I;
+        use Ty::{I32, I64};
+        let stmt = match *instr {
+            I::Trap { trap_code } => format!("return {};", trap_status(trap_code)?),
+            I::ConsumeFuel { .. } => String::new(),
+            I::Return => self.ret(&[])?,
+            I::ReturnReg { value } => self.ret(&[value.into()])?,
+            I::ReturnReg2 { values: [a, b] } => self.ret(&[a.into(), b.into()])?,
+            I::ReturnReg3 { values: [a, b, c] } => self.ret(&[a.into(), b.into(), c.into()])?,
+            I::ReturnImm32 { value } => self.ret(&[Operand::Imm(u64::from(u32::from(value)))])?,
+            I::ReturnI64Imm32 { value } => self.ret(&[Operand::Imm(i64::from(value) as u64)])?,
+            I::ReturnF64Imm32 { value } => self.ret(&[Operand::Imm(f64::from(value).to_bits())])?,
+            I::ReturnSpan { values } => {
+                let values = values.iter().map(Operand::from).collect::<Vec<_>>();
+                self.ret(&values)?
+            }
+            I::ReturnNez { condition } => self.ret_nez(condition, &[])?,
+            I::ReturnNezReg { condition, value } => self.ret_nez(condition, &[value.into()])?,
+            I::ReturnNezReg2 { condition, values: [a, b] } => self.ret_nez(condition, &[a.into(), b.into()])?,
+            I::ReturnNezImm32 { condition, value } => self.ret_nez(condition, &[Operand::Imm(u64::from(u32::from(value)))])?,
+            I::ReturnNezI64Imm32 { condition, value } => self.ret_nez(condition, &[Operand::Imm(i64::from(value) as u64)])?,
+            I::ReturnNezF64Imm32 { condition, value } => self.ret_nez(condition, &[Operand::Imm(f64::from(value).to_bits())])?,
+            I::ReturnNezSpan { condition, values } => {
+                let values = values.iter().map(Operand::from).collect::<Vec<_>>();
+                self.ret_nez(condition, &values)?
+            }
+            I::Branch { offset } => format!("goto L{};", self.target(pos, offset.to_i32())?),
+            I::BranchI32And { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::AND)?,
+            I::BranchI32AndImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::AND)?,
+            I::BranchI32Or { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::OR)?,
+            I::BranchI32OrImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::OR)?,
+            I::BranchI32Xor { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::XOR)?,
+            I::BranchI32XorImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::XOR)?,
+            I::BranchI32AndEqz { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::AND_EQZ)?,
+            I::BranchI32AndEqzImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::AND_EQZ)?,
+            I::BranchI32OrEqz { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::OR_EQZ)?,
+            I::BranchI32OrEqzImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::OR_EQZ)?,
+            I::BranchI32XorEqz { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::XOR_EQZ)?,
+            I::BranchI32XorEqzImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::XOR_EQZ)?,
+            I::BranchI32Eq { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::EQ)?,
+            I::BranchI32EqImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::EQ)?,
+            I::BranchI32Ne { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::NE)?,
+            I::BranchI32NeImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::NE)?,
+            I::BranchI32LtS { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LT_S)?,
+            I::BranchI32LtSImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LT_S)?,
+            I::BranchI32LtSImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LT_S)?,
+            I::BranchI32LtU { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LT_U)?,
+            I::BranchI32LtUImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LT_U)?,
+            I::BranchI32LtUImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LT_U)?,
+            I::BranchI32LeS { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LE_S)?,
+            I::BranchI32LeSImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LE_S)?,
+            I::BranchI32LeSImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LE_S)?,
+            I::BranchI32LeU { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LE_U)?,
+            I::BranchI32LeUImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LE_U)?,
+            I::BranchI32LeUImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I32, lhs, rhs, op::LE_U)?,
+            I::BranchI64Eq { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::EQ)?,
+            I::BranchI64EqImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::EQ)?,
+            I::BranchI64Ne { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::NE)?,
+            I::BranchI64NeImm16 { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::NE)?,
+            I::BranchI64LtS { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LT_S)?,
+            I::BranchI64LtSImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LT_S)?,
+            I::BranchI64LtSImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LT_S)?,
+            I::BranchI64LtU { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LT_U)?,
+            I::BranchI64LtUImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LT_U)?,
+            I::BranchI64LtUImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LT_U)?,
+            I::BranchI64LeS { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LE_S)?,
+            I::BranchI64LeSImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LE_S)?,
+            I::BranchI64LeSImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LE_S)?,
+            I::BranchI64LeU { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LE_U)?,
+            I::BranchI64LeUImm16Lhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LE_U)?,
+            I::BranchI64LeUImm16Rhs { lhs, rhs, offset } => self.branch(pos, offset.to_i16(), I64, lhs, rhs, op::LE_U)?,
+            I::Copy { result, value } => self.assign(result, I64, &self.operand(I64, value)?)?,
+            I::Copy2 { results, values: [a, b] } => {
+                // Note: both values are read before writing any result since they may overlap.
+                let a = self.operand(I64, a)?;
+                let b = self.operand(I64, b)?;
+                let mut results = results.iter();
+                let (Some(ra), Some(rb)) = (results.next(), results.next()) else {
+                    return Err(Error::new("malformed copy2 instruction"));
+                };
+                format!(
+                    "{{ uint64_t a = {a}, b = {b}; {} = a; {} = b; }}",
+                    self.result(ra)?,
+                    self.result(rb)?,
+                )
+            }
+            I::CopyImm32 { result, value } => self.assign(result, I64, &I64.literal(u64::from(u32::from(value))))?,
+            I::CopyI64Imm32 { result, value } => self.assign(result, I64, &I64.literal(i64::from(value) as u64))?,
+            I::CopyF64Imm32 { result, value } => self.assign(result, I64, &I64.literal(f64::from(value).to_bits()))?,
+            I::CopySpan { results, values, len } |
+            I::CopySpanNonOverlapping { results, values, len } => {
+                let mut stmt = String::from("{ uint64_t t[] = { ");
+                for value in values.iter(len) {
+                    let _ = write!(stmt, "{}, ", self.operand(I64, value)?);
+                }
+                stmt.push_str("0 };");
+                for (index, result) in results.iter(len).enumerate() {
+                    let _ = write!(stmt, " {} = t[{index}];", self.result(result)?);
+                }
+                stmt.push_str(" }");
+                stmt
+            }
+            I::I32Eq { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::EQ)?,
+            I::I32EqImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::EQ)?,
+            I::I32Ne { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::NE)?,
+            I::I32NeImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::NE)?,
+            I::I32LtS { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LT_S)?,
+            I::I32LtSImm16Lhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LT_S)?,
+            I::I32LtSImm16Rhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LT_S)?,
+            I::I32LtU { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LT_U)?,
+            I::I32LtUImm16Lhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LT_U)?,
+            I::I32LtUImm16Rhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LT_U)?,
+            I::I32LeS { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LE_S)?,
+            I::I32LeSImm16Lhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LE_S)?,
+            I::I32LeSImm16Rhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LE_S)?,
+            I::I32LeU { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LE_U)?,
+            I::I32LeUImm16Lhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LE_U)?,
+            I::I32LeUImm16Rhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::LE_U)?,
+            I::I64Eq { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::EQ)?,
+            I::I64EqImm16 { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::EQ)?,
+            I::I64Ne { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::NE)?,
+            I::I64NeImm16 { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::NE)?,
+            I::I64LtS { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LT_S)?,
+            I::I64LtSImm16Lhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LT_S)?,
+            I::I64LtSImm16Rhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LT_S)?,
+            I::I64LtU { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LT_U)?,
+            I::I64LtUImm16Lhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LT_U)?,
+            I::I64LtUImm16Rhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LT_U)?,
+            I::I64LeS { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LE_S)?,
+            I::I64LeSImm16Lhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LE_S)?,
+            I::I64LeSImm16Rhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LE_S)?,
+            I::I64LeU { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LE_U)?,
+            I::I64LeUImm16Lhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LE_U)?,
+            I::I64LeUImm16Rhs { result, lhs, rhs } => self.compare(result, I64, lhs, rhs, op::LE_U)?,
+            I::I32Clz { result, input } => self.unary(result, I32, input, op::CLZ)?,
+            I::I32Ctz { result, input } => self.unary(result, I32, input, op::CTZ)?,
+            I::I32Popcnt { result, input } => self.unary(result, I32, input, op::POPCNT)?,
+            I::I32Add { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ADD)?,
+            I::I32AddImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ADD)?,
+            I::I32Sub { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SUB)?,
+            I::I32SubImm16Lhs { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SUB)?,
+            I::I32Mul { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::MUL)?,
+            I::I32MulImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::MUL)?,
+            I::I32DivS { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::DivS)?,
+            I::I32DivSImm16Rhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::DivS)?,
+            I::I32DivSImm16Lhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::DivS)?,
+            I::I32DivU { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::DivU)?,
+            I::I32DivUImm16Rhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::DivU)?,
+            I::I32DivUImm16Lhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::DivU)?,
+            I::I32RemS { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::RemS)?,
+            I::I32RemSImm16Rhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::RemS)?,
+            I::I32RemSImm16Lhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::RemS)?,
+            I::I32RemU { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::RemU)?,
+            I::I32RemUImm16Rhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::RemU)?,
+            I::I32RemUImm16Lhs { result, lhs, rhs } => self.div(result, I32, lhs, rhs, Div::RemU)?,
+            I::I32And { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::AND)?,
+            I::I32AndImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::AND)?,
+            I::I32Or { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::OR)?,
+            I::I32OrImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::OR)?,
+            I::I32Xor { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::XOR)?,
+            I::I32XorImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::XOR)?,
+            I::I32AndEqz { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::AND_EQZ)?,
+            I::I32AndEqzImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::AND_EQZ)?,
+            I::I32OrEqz { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::OR_EQZ)?,
+            I::I32OrEqzImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::OR_EQZ)?,
+            I::I32XorEqz { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::XOR_EQZ)?,
+            I::I32XorEqzImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::XOR_EQZ)?,
+            I::I32Shl { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHL)?,
+            I::I32ShlBy { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHL)?,
+            I::I32ShlImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHL)?,
+            I::I32ShrU { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHR_U)?,
+            I::I32ShrUBy { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHR_U)?,
+            I::I32ShrUImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHR_U)?,
+            I::I32ShrS { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHR_S)?,
+            I::I32ShrSBy { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHR_S)?,
+            I::I32ShrSImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::SHR_S)?,
+            I::I32Rotl { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ROTL)?,
+            I::I32RotlBy { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ROTL)?,
+            I::I32RotlImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ROTL)?,
+            I::I32Rotr { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ROTR)?,
+            I::I32RotrBy { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ROTR)?,
+            I::I32RotrImm16 { result, lhs, rhs } => self.binary(result, I32, lhs, rhs, op::ROTR)?,
+            I::I64Clz { result, input } => self.unary(result, I64, input, op::CLZ)?,
+            I::I64Ctz { result, input } => self.unary(result, I64, input, op::CTZ)?,
+            I::I64Popcnt { result, input } => self.unary(result, I64, input, op::POPCNT)?,
+            I::I64Add { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ADD)?,
+            I::I64AddImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ADD)?,
+            I::I64Sub { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SUB)?,
+            I::I64SubImm16Lhs { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SUB)?,
+            I::I64Mul { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::MUL)?,
+            I::I64MulImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::MUL)?,
+            I::I64DivS { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::DivS)?,
+            I::I64DivSImm16Rhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::DivS)?,
+            I::I64DivSImm16Lhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::DivS)?,
+            I::I64DivU { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::DivU)?,
+            I::I64DivUImm16Rhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::DivU)?,
+            I::I64DivUImm16Lhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::DivU)?,
+            I::I64RemS { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::RemS)?,
+            I::I64RemSImm16Rhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::RemS)?,
+            I::I64RemSImm16Lhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::RemS)?,
+            I::I64RemU { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::RemU)?,
+            I::I64RemUImm16Rhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::RemU)?,
+            I::I64RemUImm16Lhs { result, lhs, rhs } => self.div(result, I64, lhs, rhs, Div::RemU)?,
+            I::I64And { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::AND)?,
+            I::I64AndImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::AND)?,
+            I::I64Or { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::OR)?,
+            I::I64OrImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::OR)?,
+            I::I64Xor { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::XOR)?,
+            I::I64XorImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::XOR)?,
+            I::I64Shl { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHL)?,
+            I::I64ShlBy { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHL)?,
+            I::I64ShlImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHL)?,
+            I::I64ShrU { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHR_U)?,
+            I::I64ShrUBy { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHR_U)?,
+            I::I64ShrUImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHR_U)?,
+            I::I64ShrS { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHR_S)?,
+            I::I64ShrSBy { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHR_S)?,
+            I::I64ShrSImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::SHR_S)?,
+            I::I64Rotl { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ROTL)?,
+            I::I64RotlBy { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ROTL)?,
+            I::I64RotlImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ROTL)?,
+            I::I64Rotr { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ROTR)?,
+            I::I64RotrBy { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ROTR)?,
+            I::I64RotrImm16 { result, lhs, rhs } => self.binary(result, I64, lhs, rhs, op::ROTR)?,
+            I::I32WrapI64 { result, input } => self.assign(result, I32, &self.operand(I64, input)?)?,
+            I::I32Extend8S { result, input } => self.unary(result, I32, input, "(int8_t){a}")?,
+            I::I32Extend16S { result, input } => self.unary(result, I32, input, "(int16_t){a}")?,
+            I::I64Extend8S { result, input } => self.unary(result, I64, input, "(int8_t){a}")?,
+            I::I64Extend16S { result, input } => self.unary(result, I64, input, "(int16_t){a}")?,
+            I::I64Extend32S { result, input } => self.unary(result, I64, input, "(int32_t){a}")?,
+            ref instr => {
+                return Err(Error::new(format!(
+                    "unsupported instruction for AOT emission at position {pos}: {instr:?}"
+                )))
+            }
+        };
+        Ok(stmt)
+    }
+
+    /// Returns the C expression reading `operand` as value of type `ty`.
+    fn operand(&self, ty: Ty, operand: impl Into<Operand>) -> Result<String, Error> {
+        let reg = match operand.into() {
+            Operand::Reg(reg) => reg,
+            Operand::Imm(value) => return Ok(ty.literal(value)),
+        };
+        let index = i16::from(reg);
+        let slot = match index {
+            0.. if (index as u16) < self.len_registers => format!("r[{index}]"),
+            ..0 if usize::from(index.unsigned_abs()) <= self.consts.len() => {
+                format!(
+                    "c[{}]",
+                    self.consts.len() - usize::from(index.unsigned_abs())
+                )
+            }
+            _ => return Err(Error::new(format!("out of bounds register: {index}"))),
+        };
+        Ok(match ty {
+            Ty::I32 => format!("(uint32_t){slot}"),
+            Ty::I64 => slot,
+        })
+    }
+
+    /// Returns the C lvalue of the `result` register.
+    fn result(&self, result: Reg) -> Result<String, Error> {
+        let index = i16::from(result);
+        if index < 0 || index as u16 >= self.len_registers {
+            return Err(Error::new(format!("invalid result register: {index}")));
+        }
+        Ok(format!("r[{index}]"))
+    }
+
+    /// Returns the C statement assigning `expr` of type `ty` to `result`.
+    fn assign(&self, result: Reg, ty: Ty, expr: &str) -> Result<String, Error> {
+        Ok(format!(
+            "{} = (uint64_t)({})({expr});",
+            self.result(result)?,
+            ty.unsigned()
+        ))
+    }
+
+    /// Returns the C expression of the operation `template` on `lhs` and `rhs`.
+    fn expr(
+        &self,
+        ty: Ty,
+        lhs: impl Into<Operand>,
+        rhs: Option<Operand>,
+        template: &str,
+    ) -> Result<String, Error> {
+        let mut expr = template
+            .replace("{a}", &self.operand(ty, lhs)?)
+            .replace("{S}", ty.signed())
+            .replace("{U}", ty.unsigned())
+            .replace("{N}", &format!("{}", ty.bits()));
+        if let Some(rhs) = rhs {
+            expr = expr.replace("{b}", &self.operand(ty, rhs)?);
+        }
+        Ok(expr)
+    }
+
+    /// Returns the C statement of the unary operation `template` of type `ty`.
+    fn unary(&self, result: Reg, ty: Ty, input: Reg, template: &str) -> Result<String, Error> {
+        let expr = self.expr(ty, input, None, template)?;
+        self.assign(result, ty, &expr)
+    }
+
+    /// Returns the C statement of the binary operation `template` of type `ty`.
+    fn binary(
+        &self,
+        result: Reg,
+        ty: Ty,
+        lhs: impl Into<Operand>,
+        rhs: impl Into<Operand>,
+        template: &str,
+    ) -> Result<String, Error> {
+        let expr = self.expr(ty, lhs, Some(rhs.into()), template)?;
+        self.assign(result, ty, &expr)
+    }
+
+    /// Returns the C statement of the comparison `template` of type `ty` with `i32` result.
+    fn compare(
+        &self,
+        result: Reg,
+        ty: Ty,
+        lhs: impl Into<Operand>,
+        rhs: impl Into<Operand>,
+        template: &str,
+    ) -> Result<String, Error> {
+        let expr = self.expr(ty, lhs, Some(rhs.into()), template)?;
+        self.assign(result, Ty::I32, &expr)
+    }
+
+    /// Returns the C statement of the division or remainder operation `kind` of type `ty`.
+    fn div(
+        &self,
+        result: Reg,
+        ty: Ty,
+        lhs: impl Into<Operand>,
+        rhs: impl Into<Operand>,
+        kind: Div,
+    ) -> Result<String, Error> {
+        let (unsigned, signed) = (ty.unsigned(), ty.signed());
+        let lhs = self.operand(ty, lhs)?;
+        let rhs = self.operand(ty, rhs)?;
+        let min = ty.literal(1 << (ty.bits() - 1));
+        let div_by_zero = trap_status(TrapCode::IntegerDivisionByZero)?;
+        let overflow = trap_status(TrapCode::IntegerOverflow)?;
+        let (check, expr) = match kind {
+            Div::DivS => (
+                format!(" if (a == {min} && b == ({unsigned})-1) return {overflow};"),
+                format!("({unsigned})(({signed})a / ({signed})b)"),
+            ),
+            Div::DivU => (String::new(), String::from("a / b")),
+            Div::RemS => (
+                String::new(),
+                format!("b == ({unsigned})-1 ? 0 : ({unsigned})(({signed})a % ({signed})b)"),
+            ),
+            Div::RemU => (String::new(), String::from("a % b")),
+        };
+        Ok(format!(
+            "{{ {unsigned} a = {lhs}, b = {rhs}; if (b == 0) return {div_by_zero};{check} {} = (uint64_t)({expr}); }}",
+            self.result(result)?,
+        ))
+    }
+
+    /// Returns the position of the branch target at `offset` relative to `pos`.
+    ///
+    /// # Errors
+    ///
+    /// If the branch target is out of bounds.
+    fn target(&mut self, pos: usize, offset: i32) -> Result<usize, Error> {
+        let target = pos as i64 + i64::from(offset);
+        let Some(target) = usize::try_from(target)
+            .ok()
+            .filter(|target| *target < self.instrs.len())
+        else {
+            return Err(Error::new(format!(
+                "out of bounds branch target at position {pos}"
+            )));
+        };
+        self.targets.insert(target);
+        Ok(target)
+    }
+
+    /// Returns the C statement of the fused compare-and-branch with condition `template`.
+    fn branch(
+        &mut self,
+        pos: usize,
+        offset: i16,
+        ty: Ty,
+        lhs: impl Into<Operand>,
+        rhs: impl Into<Operand>,
+        template: &str,
+    ) -> Result<String, Error> {
+        let condition = self.expr(ty, lhs, Some(rhs.into()), template)?;
+        let target = self.target(pos, i32::from(offset))?;
+        Ok(format!("if ({condition}) goto L{target};"))
+    }
+
+    /// Returns the C statements returning `results`.
+    fn ret(&self, results: &[Operand]) -> Result<String, Error> {
+        let mut stmt = String::new();
+        for (index, result) in results.iter().enumerate() {
+            let _ = write!(
+                stmt,
+                "values[{index}] = {}; ",
+                self.operand(Ty::I64, *result)?
+            );
+        }
+        stmt.push_str("return 0;");
+        Ok(stmt)
+    }
+
+    /// Returns the C statement returning `results` if `condition` is not zero.
+    fn ret_nez(&self, condition: Reg, results: &[Operand]) -> Result<String, Error> {
+        let condition = self.expr(Ty::I32, condition, None, op::NEZ)?;
+        Ok(format!("if ({condition}) {{ {} }}", self.ret(results)?))
+    }
+}
+
+/// Returns the status returned by emitted functions for `trap_code`.
+///
+/// # Errors
+///
+/// If `trap_code` cannot be returned by emitted functions.
+fn trap_status(trap_code: TrapCode) -> Result<i32, Error> {
+    TRAP_CODES
+        .iter()
+        .position(|code| *code == trap_code)
+        .map(|index| index as i32 + 1)
+        .ok_or_else(|| {
+            Error::new(format!(
+                "unsupported trap code for AOT emission: {trap_code:?}"
+            ))
+        })
+}
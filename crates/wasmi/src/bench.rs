@@ -0,0 +1,551 @@
+//! Benchmark workloads for instantiation, call overhead, memory operations and dispatch.
+//!
+//! The workloads are the same for upstream Wasmi and its downstream forks so that
+//! their performance can be compared programmatically. Every [`Benchmark`] prepares a
+//! [`BenchRoutine`] that is run repeatedly which fits the `iter` method of the bencher
+//! of harnesses such as `criterion`. Alternatively [`run_suite`] measures all benchmarks
+//! and [`BenchReport::check_regressions`] compares the resulting [`BenchReport`] against
+//! a baseline, e.g. in order to gate regressions in CI.
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::{bench, Engine};
+//! let engine = Engine::default();
+//! for benchmark in bench::benchmarks() {
+//!     let mut routine = benchmark.prepare(&engine).unwrap();
+//!     // With `criterion` this would be:
+//!     // c.bench_function(benchmark.name(), |b| b.iter(|| routine.run().unwrap()));
+//!     routine.run().unwrap();
+//! }
+//! let report = bench::run_suite(&engine, 1).unwrap();
+//! let baseline = bench::BenchReport::from_text(&report.to_text()).unwrap();
+//! report.check_regressions(&baseline, 0.5).unwrap();
+//! ```
+
+use crate::{Engine, Error, Instance, Linker, Module, Store, TypedFunc, WasmParams, WasmResults};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write as _;
+use std::time::Instant;
+
+/// A prepared benchmark workload that is run repeatedly.
+pub trait BenchRoutine {
+    /// Runs a single iteration of the benchmark.
+    ///
+    /// # Errors
+    ///
+    /// If the benchmarked operation fails.
+    fn run(&mut self) -> Result<(), Error>;
+}
+
+/// A benchmark of the Wasmi benchmark suite.
+#[derive(Debug, Copy, Clone)]
+pub struct Benchmark {
+    /// The unique name of the benchmark.
+    name: &'static str,
+    /// Prepares the [`BenchRoutine`] of the benchmark.
+    prepare: fn(&Engine) -> Result<Box<dyn BenchRoutine>, Error>,
+}
+
+impl Benchmark {
+    /// Returns the unique name of the [`Benchmark`], e.g. `call/typed/0`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Prepares the [`BenchRoutine`] of the [`Benchmark`] for the `engine`.
+    ///
+    /// Preparation, e.g. compiling Wasm modules, is not part of the measured routine.
+    ///
+    /// # Errors
+    ///
+    /// If the preparation fails, e.g. since the `engine` does not support a required Wasm proposal.
+    pub fn prepare(&self, engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+        (self.prepare)(engine)
+    }
+}
+
+/// The number of calls per iteration of the call overhead benchmarks.
+const CALLS_PER_ITER: usize = 1_000;
+
+/// Returns all benchmarks of the Wasmi benchmark suite.
+pub fn benchmarks() -> &'static [Benchmark] {
+    &[
+        Benchmark {
+            name: "compile/small",
+            prepare: prepare_compile,
+        },
+        Benchmark {
+            name: "instantiate/small",
+            prepare: prepare_instantiate,
+        },
+        Benchmark {
+            name: "call/typed/0",
+            prepare: prepare_call_typed_0,
+        },
+        Benchmark {
+            name: "call/typed/4",
+            prepare: prepare_call_typed_4,
+        },
+        Benchmark {
+            name: "call/host",
+            prepare: prepare_call_host,
+        },
+        Benchmark {
+            name: "memory/fill",
+            prepare: prepare_memory_fill,
+        },
+        Benchmark {
+            name: "memory/copy",
+            prepare: prepare_memory_copy,
+        },
+        Benchmark {
+            name: "memory/load_store",
+            prepare: prepare_memory_load_store,
+        },
+        Benchmark {
+            name: "dispatch/loop",
+            prepare: prepare_dispatch_loop,
+        },
+        Benchmark {
+            name: "dispatch/br_table",
+            prepare: prepare_dispatch_br_table,
+        },
+        Benchmark {
+            name: "dispatch/fibonacci",
+            prepare: prepare_dispatch_fibonacci,
+        },
+    ]
+}
+
+/// The Wasm module used by the instantiation and compilation benchmarks.
+const SMALL_MODULE: &str = r#"
+    (module
+        (import "env" "nop" (func $nop))
+        (memory (export "memory") 1)
+        (table 4 funcref)
+        (global $g0 (mut i32) (i32.const 0))
+        (global $g1 (mut i64) (i64.const 0))
+        (data (i32.const 0) "Hello, Wasmi benchmark suite!")
+        (elem (i32.const 0) $f0 $f1 $f2 $f3)
+        (func $f0 (param i32) (result i32)
+            (i32.add (local.get 0) (global.get $g0))
+        )
+        (func $f1 (param i64) (result i64)
+            (i64.mul (local.get 0) (global.get $g1))
+        )
+        (func $f2 (param i32 i32) (result i32)
+            (i32.load (i32.add (local.get 0) (local.get 1)))
+        )
+        (func $f3 (param i32)
+            (call $nop)
+            (global.set $g0 (local.get 0))
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $f3 (local.get 0))
+            (call_indirect (param i32) (result i32) (local.get 0) (i32.const 0))
+        )
+    )
+"#;
+
+/// The Wasm module used by the call overhead, memory and dispatch benchmarks.
+const EXECUTE_MODULE: &str = r#"
+    (module
+        (import "env" "nop" (func $nop))
+        (memory (export "memory") 2)
+        (func (export "call/0"))
+        (func (export "call/4") (param i32 i64 i32 i64) (result i32 i64 i32 i64)
+            (local.get 0) (local.get 1) (local.get 2) (local.get 3)
+        )
+        (func (export "call/host") (param $n i32)
+            (loop $continue
+                (call $nop)
+                (br_if $continue
+                    (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+                )
+            )
+        )
+        (func (export "memory/fill") (param $value i32)
+            (memory.fill (i32.const 0) (local.get $value) (i32.const 65536))
+        )
+        (func (export "memory/copy")
+            (memory.copy (i32.const 65536) (i32.const 0) (i32.const 65536))
+        )
+        (func (export "memory/load_store") (param $n i32)
+            (local $addr i32)
+            (loop $continue
+                (local.set $addr (i32.shl (local.get $n) (i32.const 3)))
+                (i64.store
+                    (local.get $addr)
+                    (i64.add (i64.load (local.get $addr)) (i64.const 1))
+                )
+                (br_if $continue
+                    (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+                )
+            )
+        )
+        (func (export "dispatch/loop") (param $n i32) (result i32)
+            (local $acc i32)
+            (loop $continue
+                (local.set $acc
+                    (i32.xor
+                        (i32.mul (local.get $acc) (i32.const 31))
+                        (local.get $n)
+                    )
+                )
+                (br_if $continue
+                    (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+                )
+            )
+            (local.get $acc)
+        )
+        (func (export "dispatch/br_table") (param $n i32) (result i32)
+            (local $acc i32)
+            (loop $continue
+                (block $b3
+                    (block $b2
+                        (block $b1
+                            (block $b0
+                                (br_table $b0 $b1 $b2 $b3
+                                    (i32.and (local.get $n) (i32.const 3))
+                                )
+                            )
+                            (local.set $acc (i32.add (local.get $acc) (i32.const 1)))
+                            (br $b3)
+                        )
+                        (local.set $acc (i32.sub (local.get $acc) (i32.const 2)))
+                        (br $b3)
+                    )
+                    (local.set $acc (i32.xor (local.get $acc) (i32.const 3)))
+                )
+                (br_if $continue
+                    (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+                )
+            )
+            (local.get $acc)
+        )
+        (func $fib (export "dispatch/fibonacci") (param $n i64) (result i64)
+            (if (result i64) (i64.le_u (local.get $n) (i64.const 1))
+                (then (local.get $n))
+                (else
+                    (i64.add
+                        (call $fib (i64.sub (local.get $n) (i64.const 1)))
+                        (call $fib (i64.sub (local.get $n) (i64.const 2)))
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// Returns a [`Linker`] that defines the imports of the benchmark modules.
+fn linker(engine: &Engine) -> Result<Linker<()>, Error> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap("env", "nop", || {})?;
+    Ok(linker)
+}
+
+/// Creates a new [`Store`] with unlimited fuel if fuel metering is enabled.
+fn store(engine: &Engine) -> Result<Store<()>, Error> {
+    let mut store = Store::new(engine, ());
+    if engine.config().get_consume_fuel() {
+        store.set_fuel(u64::MAX)?;
+    }
+    Ok(store)
+}
+
+/// Compiles and instantiates the `wat` module in a new [`Store`].
+fn instantiate(engine: &Engine, wat: &str) -> Result<(Store<()>, Instance), Error> {
+    let module = Module::new(engine, wat)?;
+    let mut store = store(engine)?;
+    let instance = linker(engine)?
+        .instantiate(&mut store, &module)?
+        .start(&mut store)?;
+    Ok((store, instance))
+}
+
+/// Repeatedly compiles a Wasm module.
+struct Compile {
+    engine: Engine,
+    wasm: Vec<u8>,
+}
+
+impl BenchRoutine for Compile {
+    fn run(&mut self) -> Result<(), Error> {
+        Module::new(&self.engine, &self.wasm[..])?;
+        Ok(())
+    }
+}
+
+fn prepare_compile(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    let wasm = wat::parse_str(SMALL_MODULE).map_err(|error| Error::new(error.to_string()))?;
+    Ok(Box::new(Compile {
+        engine: engine.clone(),
+        wasm,
+    }))
+}
+
+/// Repeatedly instantiates a compiled Wasm module in a new [`Store`].
+struct Instantiate {
+    linker: Linker<()>,
+    module: Module,
+}
+
+impl BenchRoutine for Instantiate {
+    fn run(&mut self) -> Result<(), Error> {
+        let mut store = store(self.module.engine())?;
+        self.linker
+            .instantiate(&mut store, &self.module)?
+            .start(&mut store)?;
+        Ok(())
+    }
+}
+
+fn prepare_instantiate(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    Ok(Box::new(Instantiate {
+        linker: linker(engine)?,
+        module: Module::new(engine, SMALL_MODULE)?,
+    }))
+}
+
+/// Repeatedly calls a Wasm function from the host.
+struct Call<Params, Results> {
+    store: Store<()>,
+    func: TypedFunc<Params, Results>,
+    params: Params,
+    /// The number of calls per iteration.
+    repetitions: usize,
+}
+
+impl<Params, Results> BenchRoutine for Call<Params, Results>
+where
+    Params: WasmParams + Copy,
+    Results: WasmResults,
+{
+    fn run(&mut self) -> Result<(), Error> {
+        for _ in 0..self.repetitions {
+            self.func.call(&mut self.store, self.params)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prepares a [`Call`] of the exported function `name` of [`EXECUTE_MODULE`].
+fn prepare_call<Params, Results>(
+    engine: &Engine,
+    name: &str,
+    params: Params,
+    repetitions: usize,
+) -> Result<Box<dyn BenchRoutine>, Error>
+where
+    Params: WasmParams + Copy + 'static,
+    Results: WasmResults + 'static,
+{
+    let (store, instance) = instantiate(engine, EXECUTE_MODULE)?;
+    let func = instance.get_typed_func::<Params, Results>(&store, name)?;
+    Ok(Box::new(Call {
+        store,
+        func,
+        params,
+        repetitions,
+    }))
+}
+
+fn prepare_call_typed_0(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<(), ()>(engine, "call/0", (), CALLS_PER_ITER)
+}
+
+fn prepare_call_typed_4(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<(i32, i64, i32, i64), (i32, i64, i32, i64)>(
+        engine,
+        "call/4",
+        (1, 2, 3, 4),
+        CALLS_PER_ITER,
+    )
+}
+
+fn prepare_call_host(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<i32, ()>(engine, "call/host", CALLS_PER_ITER as i32, 1)
+}
+
+fn prepare_memory_fill(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<i32, ()>(engine, "memory/fill", 0x5A, 1)
+}
+
+fn prepare_memory_copy(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<(), ()>(engine, "memory/copy", (), 1)
+}
+
+fn prepare_memory_load_store(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<i32, ()>(engine, "memory/load_store", 8_191, 1)
+}
+
+fn prepare_dispatch_loop(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<i32, i32>(engine, "dispatch/loop", 10_000, 1)
+}
+
+fn prepare_dispatch_br_table(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<i32, i32>(engine, "dispatch/br_table", 10_000, 1)
+}
+
+fn prepare_dispatch_fibonacci(engine: &Engine) -> Result<Box<dyn BenchRoutine>, Error> {
+    prepare_call::<i64, i64>(engine, "dispatch/fibonacci", 20, 1)
+}
+
+/// The measured time of a [`Benchmark`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    /// The name of the benchmark.
+    name: String,
+    /// The average time of a single iteration in nanoseconds.
+    nanos_per_iter: f64,
+}
+
+impl BenchResult {
+    /// Creates a new [`BenchResult`] for the benchmark `name`.
+    pub fn new(name: impl Into<String>, nanos_per_iter: f64) -> Self {
+        Self {
+            name: name.into(),
+            nanos_per_iter,
+        }
+    }
+
+    /// Returns the name of the measured benchmark.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the average time of a single iteration in nanoseconds.
+    pub fn nanos_per_iter(&self) -> f64 {
+        self.nanos_per_iter
+    }
+}
+
+/// Measures the average time of `iterations` runs of the `benchmark` on the `engine`.
+///
+/// Runs the prepared [`BenchRoutine`] once before measuring in order to warm up caches.
+///
+/// # Errors
+///
+/// If preparing or running the `benchmark` fails.
+pub fn measure(
+    benchmark: &Benchmark,
+    engine: &Engine,
+    iterations: u32,
+) -> Result<BenchResult, Error> {
+    let mut routine = benchmark.prepare(engine)?;
+    routine.run()?;
+    let iterations = iterations.max(1);
+    let start = Instant::now();
+    for _ in 0..iterations {
+        routine.run()?;
+    }
+    let nanos_per_iter = start.elapsed().as_nanos() as f64 / f64::from(iterations);
+    Ok(BenchResult::new(benchmark.name(), nanos_per_iter))
+}
+
+/// Measures all [`benchmarks`] on the `engine` with `iterations` runs each.
+///
+/// # Errors
+///
+/// If preparing or running any benchmark fails.
+pub fn run_suite(engine: &Engine, iterations: u32) -> Result<BenchReport, Error> {
+    let results = benchmarks()
+        .iter()
+        .map(|benchmark| measure(benchmark, engine, iterations))
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(BenchReport { results })
+}
+
+/// The [`BenchResult`]s of a run of the benchmark suite.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BenchReport {
+    /// The results in the order of their measurement.
+    results: Vec<BenchResult>,
+}
+
+impl FromIterator<BenchResult> for BenchReport {
+    fn from_iter<I: IntoIterator<Item = BenchResult>>(iter: I) -> Self {
+        Self {
+            results: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl BenchReport {
+    /// Returns the [`BenchResult`]s in the order of their measurement.
+    pub fn results(&self) -> &[BenchResult] {
+        &self.results
+    }
+
+    /// Returns the [`BenchResult`] of the benchmark `name` if any.
+    pub fn get(&self, name: &str) -> Option<&BenchResult> {
+        self.results.iter().find(|result| result.name == name)
+    }
+
+    /// Encodes the [`BenchReport`] as text with one `name nanos_per_iter` line per result.
+    ///
+    /// Decode the result via [`BenchReport::from_text`], e.g. to store baselines in repositories.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for result in &self.results {
+            _ = writeln!(text, "{} {}", result.name, result.nanos_per_iter);
+        }
+        text
+    }
+
+    /// Decodes a [`BenchReport`] encoded by [`BenchReport::to_text`].
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If a line is not a `name nanos_per_iter` pair.
+    pub fn from_text(text: &str) -> Result<Self, Error> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let parsed = line.split_once(' ').and_then(|(name, nanos)| {
+                    let nanos = nanos.trim().parse::<f64>().ok()?;
+                    Some(BenchResult::new(name, nanos))
+                });
+                parsed.ok_or_else(|| Error::new(format!("invalid benchmark result: {line}")))
+            })
+            .collect()
+    }
+
+    /// Checks that no benchmark regressed by more than `tolerance` compared to `baseline`.
+    ///
+    /// The `tolerance` is relative, e.g. `0.1` permits benchmarks to be up to 10% slower.
+    /// Benchmarks missing in either report are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If any benchmark regressed. The returned [`Error`] lists all regressed benchmarks.
+    pub fn check_regressions(&self, baseline: &BenchReport, tolerance: f64) -> Result<(), Error> {
+        let mut message = String::new();
+        for result in &self.results {
+            let Some(base) = baseline.get(&result.name) else {
+                continue;
+            };
+            if result.nanos_per_iter > base.nanos_per_iter * (1.0 + tolerance) {
+                let change = (result.nanos_per_iter / base.nanos_per_iter - 1.0) * 100.0;
+                _ = write!(
+                    message,
+                    "\n  {}: {:.0}ns vs {:.0}ns baseline (+{change:.1}%)",
+                    result.name, result.nanos_per_iter, base.nanos_per_iter,
+                );
+            }
+        }
+        if message.is_empty() {
+            return Ok(());
+        }
+        Err(Error::new(format!("benchmarks regressed:{message}")))
+    }
+}
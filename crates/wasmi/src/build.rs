@@ -0,0 +1,297 @@
+//! Constructs Wasm binaries programmatically without external dependencies.
+//!
+//! This is meant for tests and fuzzers of downstream crates that need synthetic
+//! Wasm modules but do not want to depend on a full-blown Wasm encoder. Function
+//! bodies are written as raw opcodes via [`Code`] and are not validated before
+//! the resulting binary is compiled, e.g. via [`Module::new`](crate::Module::new).
+//!
+//! # Example
+//!
+//! ```
+//! # use wasmi::{core::ValType, Engine, Linker, Module, Store};
+//! use wasmi::build::{Code, ModuleBuilder};
+//! let mut builder = ModuleBuilder::new();
+//! let ty = builder.ty(&[ValType::I32, ValType::I32], &[ValType::I32]);
+//! let add = builder.func(
+//!     ty,
+//!     &[],
+//!     Code::new()
+//!         .op(0x20).u32(0) // local.get 0
+//!         .op(0x20).u32(1) // local.get 1
+//!         .op(0x6A), // i32.add
+//! );
+//! builder.export_func("add", add);
+//! let wasm = builder.finish();
+//!
+//! let engine = Engine::default();
+//! let module = Module::new(&engine, &wasm[..]).unwrap();
+//! let mut store = Store::new(&engine, ());
+//! let instance = Linker::<()>::new(&engine)
+//!     .instantiate(&mut store, &module)
+//!     .unwrap()
+//!     .start(&mut store)
+//!     .unwrap();
+//! let add = instance.get_typed_func::<(i32, i32), i32>(&store, "add").unwrap();
+//! assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+//! ```
+
+use crate::core::ValType;
+use alloc::{string::String, vec::Vec};
+
+/// The `end` opcode that terminates function bodies.
+const END: u8 = 0x0B;
+
+/// Constructs a Wasm binary from types, functions, memories and exports.
+///
+/// Indices returned by the builder are the Wasm indices of the respective items.
+#[derive(Debug, Default, Clone)]
+pub struct ModuleBuilder {
+    /// The function types as `(params, results)`.
+    types: Vec<(Vec<ValType>, Vec<ValType>)>,
+    /// The imported functions as `(module, name, type)`.
+    imports: Vec<(String, String, u32)>,
+    /// The types of the defined functions.
+    funcs: Vec<u32>,
+    /// The encoded bodies of the defined functions.
+    bodies: Vec<Vec<u8>>,
+    /// The memories as `(minimum, maximum)` pages.
+    memories: Vec<(u32, Option<u32>)>,
+    /// The exports as `(name, kind, index)`.
+    exports: Vec<(String, u8, u32)>,
+}
+
+impl ModuleBuilder {
+    /// Creates a new empty [`ModuleBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a function type with `params` and `results` and returns its index.
+    ///
+    /// Identical function types are deduplicated.
+    pub fn ty(&mut self, params: &[ValType], results: &[ValType]) -> u32 {
+        if let Some(index) = self
+            .types
+            .iter()
+            .position(|(p, r)| p[..] == *params && r[..] == *results)
+        {
+            return index as u32;
+        }
+        self.types.push((params.into(), results.into()));
+        (self.types.len() - 1) as u32
+    }
+
+    /// Adds a function import `module::name` of type `ty` and returns its function index.
+    ///
+    /// # Panics
+    ///
+    /// If a function has already been defined since imported functions precede
+    /// defined functions in the function index space.
+    pub fn import_func(&mut self, module: &str, name: &str, ty: u32) -> u32 {
+        assert!(
+            self.funcs.is_empty(),
+            "functions must be imported before any function is defined"
+        );
+        self.imports.push((module.into(), name.into(), ty));
+        (self.imports.len() - 1) as u32
+    }
+
+    /// Defines a function of type `ty` with additional `locals` and `body` and returns its function index.
+    ///
+    /// The terminating `end` opcode of `body` is added automatically.
+    pub fn func(&mut self, ty: u32, locals: &[ValType], body: &Code) -> u32 {
+        let mut encoded = Vec::new();
+        let mut groups: Vec<(u32, ValType)> = Vec::new();
+        for local in locals {
+            match groups.last_mut() {
+                Some((count, ty)) if ty == local => *count += 1,
+                _ => groups.push((1, *local)),
+            }
+        }
+        write_u32(&mut encoded, groups.len() as u32);
+        for (count, ty) in groups {
+            write_u32(&mut encoded, count);
+            encoded.push(val_type(ty));
+        }
+        encoded.extend_from_slice(&body.bytes);
+        encoded.push(END);
+        self.funcs.push(ty);
+        self.bodies.push(encoded);
+        (self.imports.len() + self.funcs.len() - 1) as u32
+    }
+
+    /// Adds a memory with `minimum` and optional `maximum` pages and returns its index.
+    pub fn memory(&mut self, minimum: u32, maximum: Option<u32>) -> u32 {
+        self.memories.push((minimum, maximum));
+        (self.memories.len() - 1) as u32
+    }
+
+    /// Exports the function at index `func` as `name`.
+    pub fn export_func(&mut self, name: &str, func: u32) -> &mut Self {
+        self.exports.push((name.into(), 0x00, func));
+        self
+    }
+
+    /// Exports the memory at index `memory` as `name`.
+    pub fn export_memory(&mut self, name: &str, memory: u32) -> &mut Self {
+        self.exports.push((name.into(), 0x02, memory));
+        self
+    }
+
+    /// Returns the encoded Wasm binary.
+    pub fn finish(&self) -> Vec<u8> {
+        let mut wasm = Vec::from(*b"\0asm");
+        wasm.extend_from_slice(&1_u32.to_le_bytes());
+        section(&mut wasm, 1, &self.types, |out, (params, results)| {
+            out.push(0x60);
+            write_u32(out, params.len() as u32);
+            out.extend(params.iter().copied().map(val_type));
+            write_u32(out, results.len() as u32);
+            out.extend(results.iter().copied().map(val_type));
+        });
+        section(&mut wasm, 2, &self.imports, |out, (module, name, ty)| {
+            write_name(out, module);
+            write_name(out, name);
+            out.push(0x00);
+            write_u32(out, *ty);
+        });
+        section(&mut wasm, 3, &self.funcs, |out, ty| write_u32(out, *ty));
+        section(
+            &mut wasm,
+            5,
+            &self.memories,
+            |out, (minimum, maximum)| match maximum {
+                None => {
+                    out.push(0x00);
+                    write_u32(out, *minimum);
+                }
+                Some(maximum) => {
+                    out.push(0x01);
+                    write_u32(out, *minimum);
+                    write_u32(out, *maximum);
+                }
+            },
+        );
+        section(&mut wasm, 7, &self.exports, |out, (name, kind, index)| {
+            write_name(out, name);
+            out.push(*kind);
+            write_u32(out, *index);
+        });
+        section(&mut wasm, 10, &self.bodies, |out, body| {
+            write_u32(out, body.len() as u32);
+            out.extend_from_slice(body);
+        });
+        wasm
+    }
+}
+
+/// The raw opcodes and immediates of a function body.
+///
+/// All immediates are encoded as required by the Wasm binary format.
+#[derive(Debug, Default, Clone)]
+pub struct Code {
+    /// The encoded bytes.
+    bytes: Vec<u8>,
+}
+
+impl Code {
+    /// Creates a new empty [`Code`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the opcode `op`, e.g. `0x6A` for `i32.add`.
+    pub fn op(&mut self, op: u8) -> &mut Self {
+        self.bytes.push(op);
+        self
+    }
+
+    /// Appends the unsigned LEB128 encoded `value`, e.g. a local index.
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        write_u32(&mut self.bytes, value);
+        self
+    }
+
+    /// Appends the signed LEB128 encoded `value`, e.g. the immediate of `i32.const`.
+    pub fn i32(&mut self, value: i32) -> &mut Self {
+        self.i64(i64::from(value))
+    }
+
+    /// Appends the signed LEB128 encoded `value`, e.g. the immediate of `i64.const`.
+    pub fn i64(&mut self, mut value: i64) -> &mut Self {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+            if done {
+                self.bytes.push(byte);
+                return self;
+            }
+            self.bytes.push(byte | 0x80);
+        }
+    }
+
+    /// Appends the little endian encoded `value`, e.g. the immediate of `f32.const`.
+    pub fn f32(&mut self, value: f32) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Appends the little endian encoded `value`, e.g. the immediate of `f64.const`.
+    pub fn f64(&mut self, value: f64) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Appends the raw `bytes`.
+    pub fn raw(&mut self, bytes: &[u8]) -> &mut Self {
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+}
+
+/// Appends the section with `id` encoding `items` via `encode` to `wasm` unless `items` is empty.
+fn section<T>(wasm: &mut Vec<u8>, id: u8, items: &[T], mut encode: impl FnMut(&mut Vec<u8>, &T)) {
+    if items.is_empty() {
+        return;
+    }
+    let mut contents = Vec::new();
+    write_u32(&mut contents, items.len() as u32);
+    for item in items {
+        encode(&mut contents, item);
+    }
+    wasm.push(id);
+    write_u32(wasm, contents.len() as u32);
+    wasm.extend_from_slice(&contents);
+}
+
+/// Appends the unsigned LEB128 encoded `value` to `out`.
+fn write_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Appends the length prefixed UTF-8 encoded `name` to `out`.
+fn write_name(out: &mut Vec<u8>, name: &str) {
+    write_u32(out, name.len() as u32);
+    out.extend_from_slice(name.as_bytes());
+}
+
+/// Returns the binary encoding of `ty`.
+fn val_type(ty: ValType) -> u8 {
+    match ty {
+        ValType::I32 => 0x7F,
+        ValType::I64 => 0x7E,
+        ValType::F32 => 0x7D,
+        ValType::F64 => 0x7C,
+        ValType::FuncRef => 0x70,
+        ValType::ExternRef => 0x6F,
+    }
+}
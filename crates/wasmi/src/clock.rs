@@ -0,0 +1,154 @@
+use crate::{AsContext, Caller, Error, Linker};
+use core::time::Duration;
+
+/// The name of the host module that provides the virtual clock functions.
+const CLOCK_MODULE: &str = "wasmi:clock";
+
+/// A deterministic clock for Wasm guests whose time only advances with consumed fuel
+/// or via host-controlled steps.
+///
+/// A [`VirtualClock`] is usually stored in the host state of a [`Store`] and its
+/// functions are made available to Wasm guests via [`VirtualClock::add_to_linker`].
+///
+/// The elapsed time of a [`VirtualClock`] is the total fuel consumed by its [`Store`]
+/// multiplied by [`VirtualClock::nanos_per_fuel`] plus all steps taken via
+/// [`VirtualClock::advance`] or by guests sleeping. Since none of these depend on
+/// the wall-clock time of the host, time-based guest logic behaves deterministically
+/// in simulations and replays.
+///
+/// # Note
+///
+/// Time only advances with consumed fuel if fuel metering is enabled via
+/// [`Config::consume_fuel`]. Otherwise it only advances via steps.
+///
+/// # Guest Interface
+///
+/// All functions are imported from the `wasmi:clock` module:
+///
+/// - `monotonic_now() -> i64`: Returns the elapsed time in nanoseconds.
+/// - `realtime_now() -> i64`: Returns the time in nanoseconds since the UNIX epoch.
+/// - `sleep(nanos: i64)`: Advances the time by `nanos` nanoseconds and returns immediately.
+///
+/// [`Store`]: crate::Store
+/// [`Config::consume_fuel`]: crate::Config::consume_fuel
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VirtualClock {
+    /// The realtime at which the clock started as duration since the UNIX epoch.
+    epoch: Duration,
+    /// The nanoseconds that pass per unit of consumed fuel.
+    nanos_per_fuel: u64,
+    /// The total time advanced by steps.
+    stepped: Duration,
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new(Duration::ZERO)
+    }
+}
+
+impl VirtualClock {
+    /// Creates a new [`VirtualClock`] that starts at `epoch` since the UNIX epoch.
+    ///
+    /// The time advances by one nanosecond per unit of consumed fuel.
+    pub fn new(epoch: Duration) -> Self {
+        Self {
+            epoch,
+            nanos_per_fuel: 1,
+            stepped: Duration::ZERO,
+        }
+    }
+
+    /// Sets the nanoseconds that pass per unit of consumed fuel to `nanos_per_fuel`.
+    ///
+    /// A value of zero makes the time advance only via steps.
+    pub fn with_nanos_per_fuel(mut self, nanos_per_fuel: u64) -> Self {
+        self.nanos_per_fuel = nanos_per_fuel;
+        self
+    }
+
+    /// Returns the realtime at which the [`VirtualClock`] started as duration since the UNIX epoch.
+    pub fn epoch(&self) -> Duration {
+        self.epoch
+    }
+
+    /// Returns the nanoseconds that pass per unit of consumed fuel.
+    pub fn nanos_per_fuel(&self) -> u64 {
+        self.nanos_per_fuel
+    }
+
+    /// Returns the total time the [`VirtualClock`] advanced by steps.
+    pub fn stepped(&self) -> Duration {
+        self.stepped
+    }
+
+    /// Advances the [`VirtualClock`] by `step`.
+    pub fn advance(&mut self, step: Duration) {
+        self.stepped = self.stepped.saturating_add(step);
+    }
+
+    /// Returns the elapsed time of the [`VirtualClock`] for its [`Store`](crate::Store) `ctx`.
+    pub fn elapsed(&self, ctx: impl AsContext) -> Duration {
+        self.elapsed_after(fuel_consumed(ctx))
+    }
+
+    /// Returns the time of the [`VirtualClock`] for its [`Store`](crate::Store) `ctx`
+    /// as duration since the UNIX epoch.
+    pub fn realtime(&self, ctx: impl AsContext) -> Duration {
+        self.epoch.saturating_add(self.elapsed(ctx))
+    }
+
+    /// Returns the elapsed time after `consumed` units of fuel.
+    fn elapsed_after(&self, consumed: u64) -> Duration {
+        let nanos = consumed.saturating_mul(self.nanos_per_fuel);
+        Duration::from_nanos(nanos).saturating_add(self.stepped)
+    }
+
+    /// Defines the functions of the `wasmi:clock` host module in `linker`.
+    ///
+    /// The [`VirtualClock`] of the host state is accessed via `get`.
+    ///
+    /// # Errors
+    ///
+    /// If any of the functions is already defined in `linker`.
+    pub fn add_to_linker<T: 'static>(
+        linker: &mut Linker<T>,
+        get: fn(&mut T) -> &mut VirtualClock,
+    ) -> Result<(), Error> {
+        linker.func_wrap(
+            CLOCK_MODULE,
+            "monotonic_now",
+            move |mut caller: Caller<'_, T>| {
+                let consumed = fuel_consumed(&caller);
+                nanos(get(caller.data_mut()).elapsed_after(consumed))
+            },
+        )?;
+        linker.func_wrap(
+            CLOCK_MODULE,
+            "realtime_now",
+            move |mut caller: Caller<'_, T>| {
+                let consumed = fuel_consumed(&caller);
+                let clock = get(caller.data_mut());
+                nanos(clock.epoch.saturating_add(clock.elapsed_after(consumed)))
+            },
+        )?;
+        linker.func_wrap(
+            CLOCK_MODULE,
+            "sleep",
+            move |mut caller: Caller<'_, T>, nanos: u64| {
+                get(caller.data_mut()).advance(Duration::from_nanos(nanos));
+            },
+        )?;
+        Ok(())
+    }
+}
+
+/// Returns the total fuel consumed by the [`Store`](crate::Store) of `ctx`.
+fn fuel_consumed(ctx: impl AsContext) -> u64 {
+    ctx.as_context().store.inner.fuel().consumed()
+}
+
+/// Returns `duration` in nanoseconds saturating at [`u64::MAX`].
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
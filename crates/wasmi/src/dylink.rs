@@ -0,0 +1,450 @@
+use crate::{
+    errors::LinkerError,
+    module::ImportName,
+    AsContext,
+    AsContextMut,
+    Error,
+    Extern,
+    Func,
+    FuncRef,
+    Global,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Mutability,
+    Table,
+    Val,
+};
+use alloc::{
+    boxed::Box,
+    collections::{btree_map::Entry, BTreeMap},
+    format,
+    vec::Vec,
+};
+
+/// The namespace of imports resolved by the [`DynamicLinker`].
+const ENV_MODULE: &str = "env";
+
+/// The namespace of imported memory addresses of data symbols.
+const GOT_MEM_MODULE: &str = "GOT.mem";
+
+/// The namespace of imported table indices of function symbols.
+const GOT_FUNC_MODULE: &str = "GOT.func";
+
+/// The name of the custom section describing the memory and table requirements of a module.
+const DYLINK_SECTION: &str = "dylink.0";
+
+/// The identifier of the memory info subsection of the `dylink.0` custom section.
+const DYLINK_MEM_INFO: u8 = 1;
+
+/// The address of the first memory region allocated by the [`DynamicLinker`].
+///
+/// This leaves the lowest addresses unused, matching Emscripten's `GLOBAL_BASE`.
+const GLOBAL_BASE: u32 = 1024;
+
+/// The alignment of the stack allocated via [`DynamicLinker::allocate_stack`].
+const STACK_ALIGN: u32 = 16;
+
+/// The size of a Wasm linear memory page in bytes.
+const PAGE_SIZE: u64 = 65536;
+
+/// Exports of dynamically linked modules that are not symbols.
+const INTERNAL_EXPORTS: &[&str] = &[
+    "__wasm_apply_data_relocs",
+    "__wasm_call_ctors",
+    "__post_instantiate",
+    "__memory_base",
+    "__table_base",
+    "__stack_pointer",
+    "__indirect_function_table",
+    "memory",
+];
+
+/// A symbol defined by a module loaded via a [`DynamicLinker`].
+#[derive(Debug, Copy, Clone)]
+pub enum DylinkSymbol {
+    /// A function symbol.
+    Func(Func),
+    /// A data symbol at the given address of the shared linear memory.
+    Data(u32),
+}
+
+/// Dynamically links Emscripten-style `MAIN_MODULE` and `SIDE_MODULE` Wasm binaries.
+///
+/// All modules loaded via [`DynamicLinker::load`] share a single linear memory,
+/// a single function table and a single stack pointer. This allows guest
+/// applications that have been split into several modules to be loaded at runtime,
+/// similar to how `dlopen` loads shared libraries.
+///
+/// # Guest Interface
+///
+/// Upon loading a module the [`DynamicLinker`] resolves its imports as follows:
+///
+/// - `env.memory`: the shared linear memory.
+/// - `env.__indirect_function_table`: the shared function table.
+/// - `env.__stack_pointer`: the shared stack pointer.
+/// - `env.__memory_base`: the address of the memory region allocated for the module.
+/// - `env.__table_base`: the index of the table region allocated for the module.
+/// - `GOT.mem.<symbol>`: the address of the data symbol.
+/// - `GOT.func.<symbol>`: the table index of the function symbol.
+/// - `env.<symbol>`: the function symbol defined by a previously loaded module.
+///
+/// All other imports are resolved using the [`Linker`] provided to [`DynamicLinker::load`].
+///
+/// The size and alignment of the memory and table regions of a module are read from
+/// its `dylink.0` custom section. Afterwards the exported functions and data symbols
+/// of the module are made available to subsequently loaded modules and its
+/// `__wasm_apply_data_relocs` and `__wasm_call_ctors` exports are called if any.
+///
+/// # Note
+///
+/// - Symbols are resolved in load order and the first definition of a symbol wins.
+/// - `GOT.mem` and `GOT.func` imports of symbols that are not defined yet are
+///   resolved once a module defining them is loaded. Until then they are zero.
+/// - The stack must be allocated via [`DynamicLinker::allocate_stack`] before any
+///   guest code that uses the stack runs.
+#[derive(Debug)]
+pub struct DynamicLinker {
+    /// The linear memory shared by all loaded modules.
+    memory: Memory,
+    /// The function table shared by all loaded modules.
+    table: Table,
+    /// The stack pointer shared by all loaded modules.
+    stack_pointer: Global,
+    /// The address of the next allocated memory region.
+    memory_end: u32,
+    /// The symbols defined by the loaded modules.
+    symbols: BTreeMap<Box<str>, DylinkSymbol>,
+    /// The table indices of function symbols referenced by `GOT.func` entries.
+    func_slots: BTreeMap<Box<str>, u32>,
+    /// The `GOT.mem` entries by symbol name.
+    got_mem: BTreeMap<Box<str>, Global>,
+    /// The `GOT.func` entries by symbol name.
+    got_func: BTreeMap<Box<str>, Global>,
+}
+
+/// The memory and table requirements of a module read from its `dylink.0` custom section.
+#[derive(Debug, Default, Copy, Clone)]
+struct MemInfo {
+    /// The size of the memory region in bytes.
+    memory_size: u32,
+    /// The alignment of the memory region as power of two.
+    memory_align: u32,
+    /// The size of the table region in elements.
+    table_size: u32,
+    /// The alignment of the table region as power of two.
+    table_align: u32,
+}
+
+impl DynamicLinker {
+    /// Creates a new [`DynamicLinker`] that shares `memory` and `table` between loaded modules.
+    ///
+    /// The `table` must be a `funcref` table.
+    pub fn new(ctx: impl AsContextMut, memory: Memory, table: Table) -> Self {
+        let stack_pointer = Global::new(ctx, Val::I32(0), Mutability::Var);
+        Self {
+            memory,
+            table,
+            stack_pointer,
+            memory_end: GLOBAL_BASE,
+            symbols: BTreeMap::new(),
+            func_slots: BTreeMap::new(),
+            got_mem: BTreeMap::new(),
+            got_func: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the linear memory shared by all loaded modules.
+    pub fn memory(&self) -> Memory {
+        self.memory
+    }
+
+    /// Returns the function table shared by all loaded modules.
+    pub fn table(&self) -> Table {
+        self.table
+    }
+
+    /// Returns the stack pointer shared by all loaded modules.
+    pub fn stack_pointer(&self) -> Global {
+        self.stack_pointer
+    }
+
+    /// Returns the symbol with `name` defined by a loaded module if any.
+    ///
+    /// This is the equivalent of `dlsym`.
+    pub fn symbol(&self, name: &str) -> Option<DylinkSymbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// Returns the names of symbols imported via `GOT.mem` or `GOT.func` that are not defined yet.
+    pub fn unresolved_symbols(&self) -> impl Iterator<Item = &str> {
+        self.got_mem
+            .keys()
+            .chain(self.got_func.keys())
+            .map(|name| &**name)
+            .filter(|name| !self.symbols.contains_key(*name))
+    }
+
+    /// Allocates a stack of `size` bytes and points the stack pointer to its top.
+    ///
+    /// Returns the address of the top of the stack.
+    ///
+    /// # Errors
+    ///
+    /// If the shared linear memory cannot grow to fit the stack.
+    pub fn allocate_stack(&mut self, mut ctx: impl AsContextMut, size: u32) -> Result<u32, Error> {
+        let base = self.allocate_memory(&mut ctx, size, STACK_ALIGN)?;
+        let top = base + size;
+        self.stack_pointer.set(ctx, Val::I32(top as i32))?;
+        Ok(top)
+    }
+
+    /// Loads `module` and links it with the previously loaded modules.
+    ///
+    /// This is the equivalent of `dlopen`. Imports that are not resolved
+    /// by the [`DynamicLinker`] are resolved using `linker`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `dylink.0` custom section of `module` is malformed.
+    /// - If the shared linear memory or table cannot grow to fit `module`.
+    /// - If an import of `module` cannot be resolved or has a mismatching type.
+    /// - If the instantiation of `module` or any of its initialization functions fails.
+    pub fn load<T>(
+        &mut self,
+        mut ctx: impl AsContextMut<Data = T>,
+        linker: &Linker<T>,
+        module: &Module,
+    ) -> Result<Instance, Error> {
+        let info = MemInfo::from_module(module)?;
+        let memory_base =
+            self.allocate_memory(&mut ctx, info.memory_size, 1 << info.memory_align)?;
+        let table_base = self.allocate_table(&mut ctx, info.table_size, 1 << info.table_align)?;
+        let memory_base_global =
+            Global::new(&mut ctx, Val::I32(memory_base as i32), Mutability::Const);
+        let table_base_global =
+            Global::new(&mut ctx, Val::I32(table_base as i32), Mutability::Const);
+        let mut externals = Vec::new();
+        for import in module.imports() {
+            let (module_name, name) = (import.module(), import.name());
+            let resolved = match (module_name, name) {
+                (ENV_MODULE, "memory") => Some(Extern::Memory(self.memory)),
+                (ENV_MODULE, "__indirect_function_table") => Some(Extern::Table(self.table)),
+                (ENV_MODULE, "__stack_pointer") => Some(Extern::Global(self.stack_pointer)),
+                (ENV_MODULE, "__memory_base") => Some(Extern::Global(memory_base_global)),
+                (ENV_MODULE, "__table_base") => Some(Extern::Global(table_base_global)),
+                (GOT_MEM_MODULE, _) => Some(Extern::Global(Self::got_entry(
+                    &mut ctx,
+                    &mut self.got_mem,
+                    name,
+                ))),
+                (GOT_FUNC_MODULE, _) => Some(Extern::Global(Self::got_entry(
+                    &mut ctx,
+                    &mut self.got_func,
+                    name,
+                ))),
+                (ENV_MODULE, _) => match self.symbols.get(name) {
+                    Some(DylinkSymbol::Func(func)) => Some(Extern::Func(*func)),
+                    _ => linker.get(&ctx, module_name, name),
+                },
+                _ => linker.get(&ctx, module_name, name),
+            };
+            let Some(resolved) = resolved else {
+                return Err(Error::from(LinkerError::MissingDefinition {
+                    name: ImportName::new(module_name, name),
+                    ty: import.ty().clone(),
+                }));
+            };
+            externals.push(resolved);
+        }
+        let instance = Instance::new(&mut ctx, module, &externals)?;
+        self.define_symbols(&mut ctx, instance, memory_base);
+        self.resolve_got(&mut ctx)?;
+        for init in [
+            "__wasm_apply_data_relocs",
+            "__wasm_call_ctors",
+            "__post_instantiate",
+        ] {
+            if let Some(func) = instance.get_func(&ctx, init) {
+                func.call(&mut ctx, &[], &mut [])?;
+            }
+        }
+        Ok(instance)
+    }
+
+    /// Returns the `GOT` entry for the symbol `name` in `got` and creates it if necessary.
+    fn got_entry(
+        ctx: impl AsContextMut,
+        got: &mut BTreeMap<Box<str>, Global>,
+        name: &str,
+    ) -> Global {
+        *got.entry(name.into())
+            .or_insert_with(|| Global::new(ctx, Val::I32(0), Mutability::Var))
+    }
+
+    /// Defines the exported symbols of `instance` whose data is located at `memory_base`.
+    fn define_symbols(&mut self, ctx: impl AsContext, instance: Instance, memory_base: u32) {
+        let ctx = ctx.as_context();
+        for export in instance.exports(&ctx) {
+            let name = export.name();
+            if INTERNAL_EXPORTS.contains(&name) {
+                continue;
+            }
+            let symbol = match export.into_extern() {
+                Extern::Func(func) => DylinkSymbol::Func(func),
+                Extern::Global(global) => match global.get(&ctx) {
+                    Val::I32(offset) => DylinkSymbol::Data(memory_base.wrapping_add(offset as u32)),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            if let Entry::Vacant(entry) = self.symbols.entry(name.into()) {
+                entry.insert(symbol);
+            }
+        }
+    }
+
+    /// Writes the addresses and table indices of defined symbols to their `GOT` entries.
+    ///
+    /// # Errors
+    ///
+    /// If the shared table cannot grow to fit a function symbol.
+    fn resolve_got(&mut self, mut ctx: impl AsContextMut) -> Result<(), Error> {
+        for (name, entry) in &self.got_mem {
+            if let Some(DylinkSymbol::Data(address)) = self.symbols.get(name) {
+                entry.set(&mut ctx, Val::I32(*address as i32))?;
+            }
+        }
+        for (name, entry) in &self.got_func {
+            let Some(DylinkSymbol::Func(func)) = self.symbols.get(name) else {
+                continue;
+            };
+            let index = match self.func_slots.get(name) {
+                Some(index) => *index,
+                None => {
+                    let index = self
+                        .table
+                        .grow(&mut ctx, 1, Val::FuncRef(FuncRef::new(*func)))?;
+                    self.func_slots.insert(name.clone(), index);
+                    index
+                }
+            };
+            entry.set(&mut ctx, Val::I32(index as i32))?;
+        }
+        Ok(())
+    }
+
+    /// Allocates a memory region of `size` bytes aligned to `align` bytes.
+    ///
+    /// Returns the address of the allocated region.
+    ///
+    /// # Errors
+    ///
+    /// If the shared linear memory cannot grow to fit the region.
+    fn allocate_memory(
+        &mut self,
+        mut ctx: impl AsContextMut,
+        size: u32,
+        align: u32,
+    ) -> Result<u32, Error> {
+        let out_of_memory = || Error::new(format!("cannot allocate {size} bytes of linear memory"));
+        let base = align_to(self.memory_end, align).ok_or_else(out_of_memory)?;
+        let end = base.checked_add(size).ok_or_else(out_of_memory)?;
+        let required_pages = u64::from(end).div_ceil(PAGE_SIZE) as u32;
+        let current_pages = self.memory.size(&ctx);
+        if required_pages > current_pages {
+            self.memory.grow(&mut ctx, required_pages - current_pages)?;
+        }
+        self.memory_end = end;
+        Ok(base)
+    }
+
+    /// Allocates a table region of `size` elements aligned to `align` elements.
+    ///
+    /// Returns the index of the allocated region.
+    ///
+    /// # Errors
+    ///
+    /// If the shared table cannot grow to fit the region.
+    fn allocate_table(
+        &mut self,
+        mut ctx: impl AsContextMut,
+        size: u32,
+        align: u32,
+    ) -> Result<u32, Error> {
+        let current = self.table.size(&ctx);
+        let base = align_to(current, align)
+            .ok_or_else(|| Error::new(format!("cannot allocate {size} table elements")))?;
+        let delta = (base - current).saturating_add(size);
+        if delta != 0 {
+            self.table
+                .grow(&mut ctx, delta, Val::FuncRef(FuncRef::null()))?;
+        }
+        Ok(base)
+    }
+}
+
+/// Rounds `value` up to the next multiple of `align`.
+///
+/// Returns `None` if the result overflows.
+fn align_to(value: u32, align: u32) -> Option<u32> {
+    let align = align.max(1);
+    value.checked_next_multiple_of(align)
+}
+
+impl MemInfo {
+    /// Reads the [`MemInfo`] from the `dylink.0` custom section of `module`.
+    ///
+    /// Returns the default [`MemInfo`] if `module` has no `dylink.0` custom section.
+    ///
+    /// # Errors
+    ///
+    /// If the `dylink.0` custom section is malformed.
+    fn from_module(module: &Module) -> Result<Self, Error> {
+        let Some(section) = module
+            .custom_sections()
+            .find(|section| section.name() == DYLINK_SECTION)
+        else {
+            return Ok(Self::default());
+        };
+        let malformed = || Error::new("malformed `dylink.0` custom section");
+        let mut reader = section.data();
+        while let Some((&id, rest)) = reader.split_first() {
+            reader = rest;
+            let len = read_u32(&mut reader).ok_or_else(malformed)? as usize;
+            if len > reader.len() {
+                return Err(malformed());
+            }
+            let (mut payload, rest) = reader.split_at(len);
+            reader = rest;
+            if id != DYLINK_MEM_INFO {
+                continue;
+            }
+            let mut read = || read_u32(&mut payload).ok_or_else(malformed);
+            return Ok(Self {
+                memory_size: read()?,
+                memory_align: read()?.min(31),
+                table_size: read()?,
+                table_align: read()?.min(31),
+            });
+        }
+        Ok(Self::default())
+    }
+}
+
+/// Reads a LEB128 encoded `u32` from `bytes` and advances `bytes` past it.
+///
+/// Returns `None` if `bytes` does not start with a valid LEB128 encoded `u32`.
+fn read_u32(bytes: &mut &[u8]) -> Option<u32> {
+    let mut result: u32 = 0;
+    for (index, &byte) in bytes.iter().enumerate().take(5) {
+        let shift = 7 * index as u32;
+        result |= u32::from(byte & 0x7F).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[index + 1..];
+            return Some(result);
+        }
+    }
+    None
+}
@@ -0,0 +1,414 @@
+use super::{Engine, FuelCosts};
+use crate::{Error, Instance, Module, Store, TypedFunc};
+use core::{fmt, num::NonZeroU64};
+use std::{borrow::Cow, time::Instant, vec::Vec};
+use wasm_encoder as enc;
+
+/// The number of times each benchmarked operation is repeated per loop iteration.
+const OPS_PER_ITERATION: u32 = 16;
+
+/// The minimum duration of a single benchmark run in nanoseconds.
+const MIN_RUN_NANOS: u128 = 5_000_000;
+
+/// The number of runs per benchmark of which the fastest is taken.
+const RUNS: usize = 3;
+
+/// The number of bytes filled by the `memory.fill` benchmark per operation.
+const FILL_BYTES: i32 = 4096;
+
+/// The operation `name` that is benchmarked via `ops`.
+struct Bench {
+    /// The name of the benchmarked operation.
+    name: &'static str,
+    /// The units processed per operation, e.g. bytes.
+    units: u32,
+    /// The Wasm instructions of a single benchmarked operation.
+    ops: &'static [enc::Instruction<'static>],
+}
+
+/// Local variable of the benchmark loop counter.
+const N: u32 = 0;
+/// Local variable of type `i32`.
+const A: u32 = 1;
+/// Local variable of type `i64`.
+const B: u32 = 2;
+/// Local variable of type `f64`.
+const C: u32 = 3;
+/// Local variable of type `i32` that always holds zero.
+const ZERO: u32 = 4;
+
+/// The benchmarked operations that do not operate on floats.
+const INT_BENCHES: &[Bench] = {
+    use enc::Instruction as I;
+    const MEM: enc::MemArg = enc::MemArg {
+        offset: 0,
+        align: 2,
+        memory_index: 0,
+    };
+    &[
+        Bench {
+            name: "i32.add",
+            units: 1,
+            ops: &[I::LocalGet(A), I::LocalGet(N), I::I32Add, I::LocalSet(A)],
+        },
+        Bench {
+            name: "i64.mul",
+            units: 1,
+            ops: &[I::LocalGet(B), I::LocalGet(B), I::I64Mul, I::LocalSet(B)],
+        },
+        Bench {
+            name: "i32.div_u",
+            units: 1,
+            ops: &[I::LocalGet(A), I::LocalGet(N), I::I32DivU, I::LocalSet(A)],
+        },
+        Bench {
+            name: "i64.div_s",
+            units: 1,
+            ops: &[I::LocalGet(B), I::LocalGet(B), I::I64DivS, I::LocalSet(B)],
+        },
+        Bench {
+            name: "local.copy",
+            units: 1,
+            ops: &[I::LocalGet(N), I::LocalSet(A)],
+        },
+        Bench {
+            name: "global.get",
+            units: 1,
+            ops: &[I::GlobalGet(0), I::LocalSet(A)],
+        },
+        Bench {
+            name: "global.set",
+            units: 1,
+            ops: &[I::LocalGet(N), I::GlobalSet(0)],
+        },
+        Bench {
+            name: "i32.load",
+            units: 1,
+            ops: &[I::LocalGet(ZERO), I::I32Load(MEM), I::LocalSet(A)],
+        },
+        Bench {
+            name: "i32.store",
+            units: 1,
+            ops: &[I::LocalGet(ZERO), I::LocalGet(N), I::I32Store(MEM)],
+        },
+        Bench {
+            name: "call",
+            units: 1,
+            ops: &[I::Call(1)],
+        },
+        Bench {
+            name: "call_indirect",
+            units: 1,
+            ops: &[
+                I::LocalGet(ZERO),
+                I::CallIndirect {
+                    type_index: 1,
+                    table_index: 0,
+                },
+            ],
+        },
+    ]
+};
+
+/// The benchmarked operations that operate on floats.
+const FLOAT_BENCHES: &[Bench] = {
+    use enc::Instruction as I;
+    &[
+        Bench {
+            name: "f64.add",
+            units: 1,
+            ops: &[I::LocalGet(C), I::LocalGet(C), I::F64Add, I::LocalSet(C)],
+        },
+        Bench {
+            name: "f64.div",
+            units: 1,
+            ops: &[I::LocalGet(C), I::LocalGet(C), I::F64Div, I::LocalSet(C)],
+        },
+        Bench {
+            name: "f64.sqrt",
+            units: 1,
+            ops: &[I::LocalGet(C), I::F64Sqrt, I::LocalSet(C)],
+        },
+    ]
+};
+
+/// The benchmarked bulk memory operations.
+const BULK_BENCHES: &[Bench] = {
+    use enc::Instruction as I;
+    &[Bench {
+        name: "memory.fill",
+        units: FILL_BYTES as u32,
+        ops: &[
+            I::LocalGet(ZERO),
+            I::LocalGet(N),
+            I::I32Const(FILL_BYTES),
+            I::MemoryFill(0),
+        ],
+    }]
+};
+
+/// The measured execution cost of a single Wasm operation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OpcodeCost {
+    /// The name of the Wasm operation.
+    name: &'static str,
+    /// The measured nanoseconds per unit processed by the operation.
+    nanos: f64,
+    /// The suggested fuel per unit processed by the operation.
+    fuel: u64,
+}
+
+impl OpcodeCost {
+    /// Returns the name of the Wasm operation, e.g. `"i32.add"`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the measured nanoseconds per unit processed by the Wasm operation.
+    ///
+    /// # Note
+    ///
+    /// The unit is a single execution for all operations but `memory.fill`
+    /// for which the unit is a single filled byte.
+    pub fn nanos(&self) -> f64 {
+        self.nanos
+    }
+
+    /// Returns the suggested fuel per unit processed by the Wasm operation.
+    ///
+    /// This is the measured cost relative to the cost of `i32.add` rounded to
+    /// the nearest integer and at least 1. For `memory.fill` this is the number
+    /// of bytes that can be filled per unit of fuel instead.
+    pub fn fuel(&self) -> u64 {
+        self.fuel
+    }
+}
+
+/// The result of [`Engine::calibrate_fuel_costs`].
+///
+/// Contains the measured execution costs of representative Wasm operations
+/// on the current host as well as suggested [`FuelCosts`] derived from them.
+#[derive(Debug, Clone)]
+pub struct FuelCalibration {
+    /// The measured costs of all benchmarked operations.
+    costs: Vec<OpcodeCost>,
+    /// The suggested fuel costs.
+    fuel_costs: FuelCosts,
+}
+
+impl FuelCalibration {
+    /// Returns the measured costs of all benchmarked Wasm operations.
+    pub fn costs(&self) -> &[OpcodeCost] {
+        &self.costs
+    }
+
+    /// Returns the measured cost of the Wasm operation `name` if it was benchmarked.
+    pub fn get(&self, name: &str) -> Option<&OpcodeCost> {
+        self.costs.iter().find(|cost| cost.name == name)
+    }
+
+    /// Returns the suggested [`FuelCosts`] for the current host.
+    ///
+    /// Use [`Config::set_fuel_costs`] to apply them.
+    ///
+    /// [`Config::set_fuel_costs`]: crate::Config::set_fuel_costs
+    pub fn suggested_fuel_costs(&self) -> FuelCosts {
+        self.fuel_costs
+    }
+}
+
+impl fmt::Display for FuelCalibration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16} {:>12} {:>8}", "operation", "ns/unit", "fuel")?;
+        for cost in &self.costs {
+            writeln!(f, "{:<16} {:>12.3} {:>8}", cost.name, cost.nanos, cost.fuel)?;
+        }
+        Ok(())
+    }
+}
+
+impl Engine {
+    /// Micro-benchmarks representative Wasm operations on the current host
+    /// and returns their measured costs and suggested fuel costs.
+    ///
+    /// This allows embedders to base their gas schedules on measured costs.
+    /// The costs are measured with the [`Config`] of the [`Engine`], so
+    /// floating point operations are only benchmarked if enabled and
+    /// `memory.fill` only if the `bulk-memory` Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// - This takes in the order of a second and should not be called on hot paths.
+    /// - Measurements are subject to noise. Calibrate on an otherwise idle host
+    ///   and with an optimized build of Wasmi for meaningful results.
+    ///
+    /// # Errors
+    ///
+    /// If the benchmark modules fail to compile or execute with the [`Config`].
+    ///
+    /// [`Config`]: crate::Config
+    pub fn calibrate_fuel_costs(&self) -> Result<FuelCalibration, Error> {
+        let config = self.config();
+        let mut benches = Vec::from_iter(INT_BENCHES);
+        if config.get_floats() {
+            benches.extend(FLOAT_BENCHES);
+        }
+        if config.get_wasm_bulk_memory() {
+            benches.extend(BULK_BENCHES);
+        }
+        let baseline = self.measure(&[])?;
+        let mut measured = Vec::with_capacity(benches.len());
+        for bench in &benches {
+            let nanos = (self.measure(bench.ops)? - baseline).max(0.0);
+            let nanos = nanos / f64::from(OPS_PER_ITERATION) / f64::from(bench.units);
+            measured.push((bench.name, nanos));
+        }
+        let base_nanos = measured[0].1.max(f64::MIN_POSITIVE);
+        let costs = measured
+            .into_iter()
+            .map(|(name, nanos)| {
+                let ratio = match name {
+                    "memory.fill" => base_nanos / nanos.max(f64::MIN_POSITIVE),
+                    _ => nanos / base_nanos,
+                };
+                let fuel = (ratio.round() as u64).max(1);
+                OpcodeCost { name, nanos, fuel }
+            })
+            .collect::<Vec<_>>();
+        let mut fuel_costs = *config.get_fuel_costs();
+        fuel_costs.base = 1;
+        if let Some(fill) = costs.iter().find(|cost| cost.name == "memory.fill") {
+            let bytes_per_fuel = fill.fuel;
+            let bytes_per_register = core::mem::size_of::<u64>() as u64;
+            fuel_costs.bytes_per_fuel = NonZeroU64::new(bytes_per_fuel).unwrap_or(NonZeroU64::MIN);
+            fuel_costs.copies_per_fuel =
+                NonZeroU64::new(bytes_per_fuel / bytes_per_register).unwrap_or(NonZeroU64::MIN);
+        }
+        Ok(FuelCalibration { costs, fuel_costs })
+    }
+
+    /// Returns the nanoseconds per benchmark loop iteration executing `ops`
+    /// [`OPS_PER_ITERATION`] times.
+    fn measure(&self, ops: &[enc::Instruction]) -> Result<f64, Error> {
+        let wasm = bench_module(ops, self.config().get_floats());
+        let module = Module::new(self, &wasm[..])?;
+        let mut store = Store::new(self, ());
+        if self.config().get_consume_fuel() {
+            store.set_fuel(u64::MAX)?;
+        }
+        let instance = Instance::new(&mut store, &module, &[])?;
+        let run = instance.get_typed_func::<i32, ()>(&store, "run")?;
+        let mut iterations = 64_i32;
+        let mut elapsed = time(&mut store, &run, iterations)?;
+        while elapsed < MIN_RUN_NANOS && iterations < i32::MAX / 2 {
+            iterations *= 2;
+            elapsed = time(&mut store, &run, iterations)?;
+        }
+        for _ in 1..RUNS {
+            elapsed = elapsed.min(time(&mut store, &run, iterations)?);
+        }
+        Ok(elapsed as f64 / f64::from(iterations))
+    }
+}
+
+/// Returns the nanoseconds it takes to execute `run` with `iterations`.
+fn time(store: &mut Store<()>, run: &TypedFunc<i32, ()>, iterations: i32) -> Result<u128, Error> {
+    let start = Instant::now();
+    run.call(&mut *store, iterations)?;
+    Ok(start.elapsed().as_nanos())
+}
+
+/// Returns a Wasm module exporting a `run` function that executes `ops`
+/// [`OPS_PER_ITERATION`] times per loop iteration for a given number of iterations.
+///
+/// The `f64` local variable is only declared if `floats` are enabled.
+fn bench_module(ops: &[enc::Instruction], floats: bool) -> Vec<u8> {
+    use enc::Instruction as I;
+    let mut module = enc::Module::new();
+    let mut types = enc::TypeSection::new();
+    types.ty().function([enc::ValType::I32], []);
+    types.ty().function([], []);
+    module.section(&types);
+    let mut funcs = enc::FunctionSection::new();
+    funcs.function(0).function(1);
+    module.section(&funcs);
+    let mut tables = enc::TableSection::new();
+    tables.table(enc::TableType {
+        element_type: enc::RefType::FUNCREF,
+        table64: false,
+        minimum: 1,
+        maximum: Some(1),
+        shared: false,
+    });
+    module.section(&tables);
+    let mut memories = enc::MemorySection::new();
+    memories.memory(enc::MemoryType {
+        minimum: 1,
+        maximum: Some(1),
+        memory64: false,
+        shared: false,
+        page_size_log2: None,
+    });
+    module.section(&memories);
+    let mut globals = enc::GlobalSection::new();
+    globals.global(
+        enc::GlobalType {
+            val_type: enc::ValType::I32,
+            mutable: true,
+            shared: false,
+        },
+        &enc::ConstExpr::i32_const(0),
+    );
+    module.section(&globals);
+    let mut exports = enc::ExportSection::new();
+    exports.export("run", enc::ExportKind::Func, 0);
+    module.section(&exports);
+    let mut elements = enc::ElementSection::new();
+    elements.active(
+        None,
+        &enc::ConstExpr::i32_const(0),
+        enc::Elements::Functions(Cow::Borrowed(&[1])),
+    );
+    module.section(&elements);
+    let mut code = enc::CodeSection::new();
+    let mut run = enc::Function::new([
+        (1, enc::ValType::I32),
+        (1, enc::ValType::I64),
+        (
+            1,
+            if floats {
+                enc::ValType::F64
+            } else {
+                enc::ValType::I64
+            },
+        ),
+        (1, enc::ValType::I32),
+    ]);
+    run.instruction(&I::I32Const(1))
+        .instruction(&I::LocalSet(A))
+        .instruction(&I::I64Const(7))
+        .instruction(&I::LocalSet(B));
+    if floats {
+        run.instruction(&I::F64Const(1.5))
+            .instruction(&I::LocalSet(C));
+    }
+    run.instruction(&I::Loop(enc::BlockType::Empty));
+    for _ in 0..OPS_PER_ITERATION {
+        for op in ops {
+            run.instruction(op);
+        }
+    }
+    run.instruction(&I::LocalGet(N))
+        .instruction(&I::I32Const(1))
+        .instruction(&I::I32Sub)
+        .instruction(&I::LocalTee(N))
+        .instruction(&I::BrIf(0))
+        .instruction(&I::End)
+        .instruction(&I::End);
+    code.function(&run);
+    let mut empty = enc::Function::new([]);
+    empty.instruction(&I::End);
+    code.function(&empty);
+    module.section(&code);
+    module.finish()
+}
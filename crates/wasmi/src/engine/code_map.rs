@@ -277,6 +277,29 @@ impl CodeMap {
         ));
     }
 
+    /// Returns the compiled [`EngineFunc`] containing `instr` and the offset of `instr` into it.
+    ///
+    /// The offset is `None` if `instr` points to the end of the function body.
+    /// Returns `None` if `instr` does not point into any compiled function.
+    ///
+    /// # Note
+    ///
+    /// This performs a linear search over all functions and thus is only
+    /// meant to be used on cold paths such as diagnostics.
+    #[cold]
+    pub fn find_instr(&self, instr: *const Instruction) -> Option<(EngineFunc, Option<usize>)> {
+        let funcs = self.funcs.lock();
+        funcs.iter().find_map(|(func, entity)| {
+            let instrs = entity.get_compiled()?.instrs();
+            let start = instrs.as_ptr() as usize;
+            let offset = (instr as usize).checked_sub(start)? / mem::size_of::<Instruction>();
+            if offset > instrs.len() {
+                return None;
+            }
+            Some((func, (offset < instrs.len()).then_some(offset)))
+        })
+    }
+
     /// Returns the [`FuncEntity`] of the [`EngineFunc`].
     ///
     /// # Errors
@@ -636,7 +659,7 @@ impl UncompiledFuncEntity {
         if let Some(fuel) = fuel {
             match fuel.consume_fuel(compilation_fuel) {
                 Err(FuelError::OutOfFuel) => return Err(Error::from(TrapCode::OutOfFuel)),
-                Ok(_) | Err(FuelError::FuelMeteringDisabled) => {}
+                Ok(_) | Err(FuelError::FuelMeteringDisabled | FuelError::OverQuota) => {}
             }
         }
         let module = self.module.clone();
@@ -774,6 +797,18 @@ pub struct CompiledFuncEntity {
     len_registers: u16,
 }
 
+/// Returns the size in bytes of a compiled function with `len_instrs` instructions and `len_consts` constants.
+///
+/// This is the size of the Wasmi bytecode and function local constants stored for
+/// the compiled function and used by [`Config::max_translated_func_size`].
+///
+/// [`Config::max_translated_func_size`]: crate::Config::max_translated_func_size
+pub fn translated_func_size(len_instrs: usize, len_consts: usize) -> usize {
+    len_instrs
+        .saturating_mul(mem::size_of::<Instruction>())
+        .saturating_add(len_consts.saturating_mul(mem::size_of::<UntypedVal>()))
+}
+
 impl CompiledFuncEntity {
     /// Create a new initialized [`CompiledFuncEntity`].
     ///
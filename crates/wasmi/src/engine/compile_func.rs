@@ -0,0 +1,195 @@
+use super::Engine;
+use crate::{
+    core::ValType,
+    AsContextMut,
+    Error,
+    ExternType,
+    Func,
+    FuncType,
+    GlobalType,
+    Linker,
+    MemoryType,
+    Module,
+    TableType,
+};
+use alloc::vec::Vec;
+
+impl Engine {
+    /// Compiles a standalone Wasm function and returns it as callable [`Func`] of `store`.
+    ///
+    /// - `imports` are the entities available to the function in the order of their
+    ///   Wasm indices per kind. Imported functions are referred to by `call` via their
+    ///   index within the imported functions and the compiled function itself is referred
+    ///   to by the index following all imported functions.
+    /// - `ty` is the [`FuncType`] of the compiled function.
+    /// - `body` is the Wasm encoded function body as found in the Wasm code section, i.e. its
+    ///   local variable declarations followed by its instructions terminated by `end`.
+    ///
+    /// The `imports` are resolved using the definitions of `linker`.
+    ///
+    /// # Note
+    ///
+    /// This allows code generating hosts to compile single functions without
+    /// encoding a whole Wasm module for each of them.
+    ///
+    /// # Errors
+    ///
+    /// - If an import of `imports` is not defined by `linker`.
+    /// - If Wasm validation or translation of `body` fails.
+    ///
+    /// # Panics
+    ///
+    /// If `linker` or `store` do not belong to the [`Engine`].
+    pub fn compile_function<T>(
+        &self,
+        mut store: impl AsContextMut<Data = T>,
+        linker: &Linker<T>,
+        imports: &[(&str, &str, ExternType)],
+        ty: &FuncType,
+        body: &[u8],
+    ) -> Result<Func, Error> {
+        let wasm = encode_module(imports, ty, body);
+        let module = Module::new(self, &wasm[..])?;
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+        let len_imported_funcs = imports
+            .iter()
+            .filter(|(_, _, ty)| matches!(ty, ExternType::Func(_)))
+            .count() as u32;
+        let func = instance
+            .get_func_by_index(&store, len_imported_funcs)
+            .unwrap_or_else(|| panic!("missing compiled function at index {len_imported_funcs}"));
+        Ok(func)
+    }
+}
+
+/// Encodes a Wasm module with `imports` that defines a single function of type `ty` with `body`.
+fn encode_module(imports: &[(&str, &str, ExternType)], ty: &FuncType, body: &[u8]) -> Vec<u8> {
+    let mut wasm = Vec::from(*b"\0asm\x01\0\0\0");
+    let mut types = Vec::new();
+    let mut import_section = Vec::new();
+    for (module, name, ty) in imports {
+        encode_name(&mut import_section, module);
+        encode_name(&mut import_section, name);
+        match ty {
+            ExternType::Func(func_type) => {
+                import_section.push(0x00);
+                encode_u32(&mut import_section, types.len() as u32);
+                types.push(func_type);
+            }
+            ExternType::Table(table_type) => {
+                import_section.push(0x01);
+                encode_table_type(&mut import_section, table_type);
+            }
+            ExternType::Memory(memory_type) => {
+                import_section.push(0x02);
+                encode_memory_type(&mut import_section, memory_type);
+            }
+            ExternType::Global(global_type) => {
+                import_section.push(0x03);
+                encode_global_type(&mut import_section, global_type);
+            }
+        }
+    }
+    let func_type_index = types.len() as u32;
+    types.push(ty);
+    let mut type_section = Vec::new();
+    encode_u32(&mut type_section, types.len() as u32);
+    for func_type in types {
+        encode_func_type(&mut type_section, func_type);
+    }
+    encode_section(&mut wasm, 1, &type_section);
+    let mut imports_vec = Vec::new();
+    encode_u32(&mut imports_vec, imports.len() as u32);
+    imports_vec.extend_from_slice(&import_section);
+    encode_section(&mut wasm, 2, &imports_vec);
+    let mut func_section = Vec::new();
+    encode_u32(&mut func_section, 1);
+    encode_u32(&mut func_section, func_type_index);
+    encode_section(&mut wasm, 3, &func_section);
+    let mut code_section = Vec::new();
+    encode_u32(&mut code_section, 1);
+    encode_u32(&mut code_section, body.len() as u32);
+    code_section.extend_from_slice(body);
+    encode_section(&mut wasm, 10, &code_section);
+    wasm
+}
+
+/// Encodes the section with section `id` and `contents` into `wasm`.
+fn encode_section(wasm: &mut Vec<u8>, id: u8, contents: &[u8]) {
+    wasm.push(id);
+    encode_u32(wasm, contents.len() as u32);
+    wasm.extend_from_slice(contents);
+}
+
+/// Encodes `value` as unsigned LEB128 into `bytes`.
+fn encode_u32(bytes: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Encodes the Wasm `name` into `bytes`.
+fn encode_name(bytes: &mut Vec<u8>, name: &str) {
+    encode_u32(bytes, name.len() as u32);
+    bytes.extend_from_slice(name.as_bytes());
+}
+
+/// Encodes the [`ValType`] `ty` into `bytes`.
+fn encode_val_type(bytes: &mut Vec<u8>, ty: ValType) {
+    bytes.push(match ty {
+        ValType::I32 => 0x7F,
+        ValType::I64 => 0x7E,
+        ValType::F32 => 0x7D,
+        ValType::F64 => 0x7C,
+        ValType::FuncRef => 0x70,
+        ValType::ExternRef => 0x6F,
+    });
+}
+
+/// Encodes the [`FuncType`] `ty` into `bytes`.
+fn encode_func_type(bytes: &mut Vec<u8>, ty: &FuncType) {
+    bytes.push(0x60);
+    for types in [ty.params(), ty.results()] {
+        encode_u32(bytes, types.len() as u32);
+        for ty in types {
+            encode_val_type(bytes, *ty);
+        }
+    }
+}
+
+/// Encodes the limits `min` and `max` with additional `flags` into `bytes`.
+fn encode_limits(bytes: &mut Vec<u8>, flags: u8, min: u32, max: Option<u32>) {
+    bytes.push(flags | u8::from(max.is_some()));
+    encode_u32(bytes, min);
+    if let Some(max) = max {
+        encode_u32(bytes, max);
+    }
+}
+
+/// Encodes the [`TableType`] `ty` into `bytes`.
+fn encode_table_type(bytes: &mut Vec<u8>, ty: &TableType) {
+    encode_val_type(bytes, ty.element());
+    encode_limits(bytes, 0x00, ty.minimum(), ty.maximum());
+}
+
+/// Encodes the [`MemoryType`] `ty` into `bytes`.
+fn encode_memory_type(bytes: &mut Vec<u8>, ty: &MemoryType) {
+    let custom_page_size = ty.page_size() != 1 << 16;
+    let flags = if custom_page_size { 0x08 } else { 0x00 };
+    encode_limits(bytes, flags, ty.minimum(), ty.maximum());
+    if custom_page_size {
+        encode_u32(bytes, u32::from(ty.page_size_log2()));
+    }
+}
+
+/// Encodes the [`GlobalType`] `ty` into `bytes`.
+fn encode_global_type(bytes: &mut Vec<u8>, ty: &GlobalType) {
+    encode_val_type(bytes, ty.content());
+    bytes.push(u8::from(ty.mutability().is_mut()));
+}
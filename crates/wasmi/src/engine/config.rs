@@ -1,15 +1,34 @@
 use super::{EnforcedLimits, StackLimits};
-use crate::core::UntypedVal;
-use core::{mem::size_of, num::NonZeroU64};
+use crate::{core::UntypedVal, ModuleVerifier};
+use alloc::vec::Vec;
+use core::{fmt, mem::size_of, num::NonZeroU64};
 use wasmparser::WasmFeatures;
 
 /// The default amount of stacks kept in the cache at most.
 const DEFAULT_CACHED_STACKS: usize = 2;
 
+/// The names of the Wasm proposals that can be configured via [`Config`].
+pub(crate) const WASM_PROPOSALS: [(&str, WasmFeatures); 11] = [
+    ("mutable-global", WasmFeatures::MUTABLE_GLOBAL),
+    ("sign-extension", WasmFeatures::SIGN_EXTENSION),
+    (
+        "saturating-float-to-int",
+        WasmFeatures::SATURATING_FLOAT_TO_INT,
+    ),
+    ("multi-value", WasmFeatures::MULTI_VALUE),
+    ("multi-memory", WasmFeatures::MULTI_MEMORY),
+    ("bulk-memory", WasmFeatures::BULK_MEMORY),
+    ("reference-types", WasmFeatures::REFERENCE_TYPES),
+    ("tail-call", WasmFeatures::TAIL_CALL),
+    ("extended-const", WasmFeatures::EXTENDED_CONST),
+    ("custom-page-sizes", WasmFeatures::CUSTOM_PAGE_SIZES),
+    ("floats", WasmFeatures::FLOATS),
+];
+
 /// Configuration for an [`Engine`].
 ///
 /// [`Engine`]: [`crate::Engine`]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Config {
     /// The limits set on the value stack and call stack.
     stack_limits: StackLimits,
@@ -21,23 +40,57 @@ pub struct Config {
     consume_fuel: bool,
     /// Is `true` if Wasmi shall ignore Wasm custom sections when parsing Wasm modules.
     ignore_custom_sections: bool,
+    /// Is `true` if integer division and remainder shall not trap.
+    saturating_div_rem: bool,
+    /// Is `true` if the iterations of Wasm `loop`s shall be counted.
+    count_loop_iterations: bool,
+    /// Is `true` if the maximum sizes of imported linear memories and tables shall not be checked.
+    relaxed_import_limits: bool,
+    /// Is `true` if freed Wasm stack values and linear memory bytes shall be zeroed.
+    zero_on_free: bool,
+    /// Is `true` if traps shall capture the Wasm call frames of their execution.
+    wasm_backtrace: bool,
     /// The configured fuel costs of all Wasmi bytecode instructions.
     fuel_costs: FuelCosts,
     /// The mode of Wasm to Wasmi bytecode compilation.
     compilation_mode: CompilationMode,
+    /// The behavior for Wasm operators of disabled Wasm proposals.
+    unsupported_proposals: UnsupportedProposals,
     /// Enforced limits for Wasm module parsing and compilation.
     limits: EnforcedLimits,
+    /// The compilation fuel available to the translation of a single Wasm module if any.
+    compilation_fuel: Option<u64>,
+    /// The maximum size of the translated Wasmi bytecode of a single function in bytes if any.
+    max_translated_func_size: Option<usize>,
+    /// The verifier of signed Wasm modules if modules are required to be signed.
+    module_verifier: Option<VerifierFn>,
 }
 
-/// Type storing all kinds of fuel costs of instructions.
+/// A [`ModuleVerifier`] that is compared by address.
 #[derive(Debug, Copy, Clone)]
+struct VerifierFn(ModuleVerifier);
+
+impl PartialEq for VerifierFn {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 as usize == other.0 as usize
+    }
+}
+
+impl Eq for VerifierFn {}
+
+/// Type storing all kinds of fuel costs of instructions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct FuelCosts {
     /// The base fuel costs for all instructions.
-    base: u64,
+    pub(super) base: u64,
     /// The register copies that can be performed per unit of fuel.
-    copies_per_fuel: NonZeroU64,
+    pub(super) copies_per_fuel: NonZeroU64,
     /// The bytes that can be copied per unit of fuel.
-    bytes_per_fuel: NonZeroU64,
+    pub(super) bytes_per_fuel: NonZeroU64,
+    /// The fuel charged per linear memory page added by `memory.grow`.
+    fuel_per_memory_page: u64,
+    /// The fuel charged per table element added by `table.grow`.
+    fuel_per_table_element: u64,
 }
 
 impl FuelCosts {
@@ -71,12 +124,12 @@ impl FuelCosts {
     }
 
     /// Returns the number of register copies performed per unit of fuel.
-    fn copies_per_fuel(&self) -> NonZeroU64 {
+    pub fn copies_per_fuel(&self) -> NonZeroU64 {
         self.copies_per_fuel
     }
 
     /// Returns the number of byte copies performed per unit of fuel.
-    fn bytes_per_fuel(&self) -> NonZeroU64 {
+    pub fn bytes_per_fuel(&self) -> NonZeroU64 {
         self.bytes_per_fuel
     }
 
@@ -90,6 +143,7 @@ impl FuelCosts {
     /// - `copy_span`
     /// - `copy_many`
     /// - `return_span`
+    /// - `return_reg{4..8}`
     /// - `return_many`
     /// - `table.grow` (+ variants)
     /// - `table.copy` (+ variants)
@@ -113,6 +167,36 @@ impl FuelCosts {
         Self::costs_per(len_bytes, self.bytes_per_fuel())
     }
 
+    /// Returns the fuel charged per linear memory page added by `memory.grow`.
+    pub fn fuel_per_memory_page(&self) -> u64 {
+        self.fuel_per_memory_page
+    }
+
+    /// Returns the fuel charged per table element added by `table.grow`.
+    pub fn fuel_per_table_element(&self) -> u64 {
+        self.fuel_per_table_element
+    }
+
+    /// Returns the fuel costs for growing a linear memory by `len_pages` pages.
+    ///
+    /// # Note
+    ///
+    /// This includes the costs for the added bytes as well as the costs per added page.
+    pub fn fuel_for_memory_grow(&self, len_pages: u64, len_bytes: u64) -> u64 {
+        self.fuel_for_bytes(len_bytes)
+            .saturating_add(len_pages.saturating_mul(self.fuel_per_memory_page))
+    }
+
+    /// Returns the fuel costs for growing a table by `len_elements` elements.
+    ///
+    /// # Note
+    ///
+    /// This includes the costs for the added element copies as well as the costs per added element.
+    pub fn fuel_for_table_grow(&self, len_elements: u64) -> u64 {
+        self.fuel_for_copies(len_elements)
+            .saturating_add(len_elements.saturating_mul(self.fuel_per_table_element))
+    }
+
     /// Returns the fuel consumption of the amount of items with costs per items.
     fn costs_per(len_items: u64, items_per_fuel: NonZeroU64) -> u64 {
         len_items / items_per_fuel
@@ -130,12 +214,69 @@ impl Default for FuelCosts {
                 .unwrap_or_else(|| panic!("invalid zero value for copies_per_fuel value")),
             bytes_per_fuel: NonZeroU64::new(bytes_per_fuel)
                 .unwrap_or_else(|| panic!("invalid zero value for copies_per_fuel value")),
+            fuel_per_memory_page: 0,
+            fuel_per_table_element: 0,
+        }
+    }
+}
+
+/// The behavior for Wasm operators of Wasm proposals that are disabled in the [`Config`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UnsupportedProposals {
+    /// Wasm modules using operators of disabled Wasm proposals fail to validate.
+    #[default]
+    Reject,
+    /// Operators of disabled Wasm proposals validate but trap once executed.
+    ///
+    /// # Note
+    ///
+    /// - This applies to the operators of the `sign-extension`, `saturating-float-to-int`,
+    ///   `bulk-memory`, `reference-types` and `tail-call` Wasm proposals.
+    /// - Executing such an operator traps with [`TrapCode::UnsupportedOperator`].
+    /// - Other constructs of those Wasm proposals, such as `externref` types
+    ///   or passive data segments, are accepted as well.
+    ///
+    /// [`TrapCode::UnsupportedOperator`]: crate::core::TrapCode::UnsupportedOperator
+    TrapAtUse,
+}
+
+/// An error that may occur upon validating a [`Config`].
+///
+/// Returned by [`Config::validate`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// An enabled Wasm proposal requires another Wasm proposal that is disabled.
+    MissingRequiredProposal {
+        /// The name of the enabled Wasm proposal.
+        proposal: &'static str,
+        /// The name of the required but disabled Wasm proposal.
+        requires: &'static str,
+    },
+    /// Custom [`FuelCosts`] are configured while fuel metering is disabled.
+    FuelCostsWithoutFuelMetering,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConfigError {}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingRequiredProposal { proposal, requires } => write!(
+                f,
+                "the `{proposal}` Wasm proposal requires the `{requires}` Wasm proposal to be enabled"
+            ),
+            Self::FuelCostsWithoutFuelMetering => write!(
+                f,
+                "custom fuel costs have no effect unless fuel metering is enabled"
+            ),
         }
     }
 }
 
 /// The chosen mode of Wasm to Wasmi bytecode compilation.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum CompilationMode {
     /// The Wasm code is compiled eagerly to Wasmi bytecode.
     #[default]
@@ -159,9 +300,18 @@ impl Default for Config {
             features: Self::default_features(),
             consume_fuel: false,
             ignore_custom_sections: false,
+            saturating_div_rem: false,
+            count_loop_iterations: false,
+            relaxed_import_limits: false,
+            zero_on_free: false,
+            wasm_backtrace: false,
             fuel_costs: FuelCosts::default(),
             compilation_mode: CompilationMode::default(),
+            unsupported_proposals: UnsupportedProposals::default(),
             limits: EnforcedLimits::default(),
+            compilation_fuel: None,
+            max_translated_func_size: None,
+            module_verifier: None,
         }
     }
 }
@@ -180,11 +330,81 @@ impl Config {
         features.set(WasmFeatures::GC_TYPES, true); // required by reference-types
         features.set(WasmFeatures::TAIL_CALL, true);
         features.set(WasmFeatures::EXTENDED_CONST, true);
-        features.set(WasmFeatures::FLOATS, true);
+        features.set(WasmFeatures::FLOATS, !cfg!(feature = "no-floats"));
         features.set(WasmFeatures::CUSTOM_PAGE_SIZES, false);
         features
     }
 
+    /// Returns a [`Config`] preset for executions that must be deterministic, e.g. for consensus.
+    ///
+    /// This preset
+    ///
+    /// - enables fuel metering so that executions halt deterministically,
+    /// - compiles eagerly so that invalid Wasm modules are rejected upfront,
+    /// - disables Wasm floating point instructions and types whose NaN bit patterns
+    ///   are not deterministic across platforms,
+    /// - rejects operators of disabled Wasm proposals and
+    /// - enforces [`EnforcedLimits::strict`] to protect against malicious Wasm modules.
+    pub fn deterministic() -> Self {
+        let mut config = Self::default();
+        config
+            .consume_fuel(true)
+            .compilation_mode(CompilationMode::Eager)
+            .floats(false)
+            .unsupported_proposals(UnsupportedProposals::Reject)
+            .enforced_limits(EnforcedLimits::strict());
+        config
+    }
+
+    /// Returns a [`Config`] preset for memory constrained devices such as microcontrollers.
+    ///
+    /// This preset
+    ///
+    /// - uses small [`StackLimits`] and caches at most one Wasm stack,
+    /// - validates eagerly but translates Wasm functions lazily on first use so that
+    ///   only the Wasmi bytecode of executed functions is kept in memory,
+    /// - ignores Wasm custom sections and
+    /// - enforces [`EnforcedLimits::strict`] which also limits Wasm modules to a single memory.
+    pub fn embedded() -> Self {
+        let register_len = size_of::<UntypedVal>();
+        let stack_limits = StackLimits {
+            initial_value_stack_height: 256 / register_len,
+            maximum_value_stack_height: 64 * 1024 / register_len,
+            maximum_recursion_depth: 256,
+        };
+        let mut config = Self::default();
+        config
+            .set_stack_limits(stack_limits)
+            .set_cached_stacks(1)
+            .compilation_mode(CompilationMode::LazyTranslation)
+            .ignore_custom_sections(true)
+            .enforced_limits(EnforcedLimits::strict());
+        config
+    }
+
+    /// Returns a [`Config`] preset for maximum throughput.
+    ///
+    /// This preset
+    ///
+    /// - disables fuel metering,
+    /// - validates and translates Wasm functions lazily on first use,
+    /// - caches more Wasm stacks for reuse and
+    /// - ignores Wasm custom sections.
+    ///
+    /// # Note
+    ///
+    /// Due to lazy validation this preset must not be used if the result of Wasm
+    /// execution must be deterministic amongst multiple Wasm implementations.
+    pub fn fast() -> Self {
+        let mut config = Self::default();
+        config
+            .consume_fuel(false)
+            .compilation_mode(CompilationMode::Lazy)
+            .set_cached_stacks(16)
+            .ignore_custom_sections(true);
+        config
+    }
+
     /// Sets the [`StackLimits`] for the [`Config`].
     pub fn set_stack_limits(&mut self, stack_limits: StackLimits) -> &mut Self {
         self.stack_limits = stack_limits;
@@ -192,7 +412,7 @@ impl Config {
     }
 
     /// Returns the [`StackLimits`] of the [`Config`].
-    pub(super) fn stack_limits(&self) -> StackLimits {
+    pub fn get_stack_limits(&self) -> StackLimits {
         self.stack_limits
     }
 
@@ -200,14 +420,18 @@ impl Config {
     ///
     /// # Note
     ///
-    /// Defaults to 2.
+    /// - Every [`Store`] additionally caches the stack of its last call from the host
+    ///   unless `amount` is zero.
+    /// - Defaults to 2.
+    ///
+    /// [`Store`]: crate::Store
     pub fn set_cached_stacks(&mut self, amount: usize) -> &mut Self {
         self.cached_stacks = amount;
         self
     }
 
     /// Returns the maximum amount of cached stacks for reuse of the [`Config`].
-    pub(super) fn cached_stacks(&self) -> usize {
+    pub fn get_cached_stacks(&self) -> usize {
         self.cached_stacks
     }
 
@@ -223,6 +447,13 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`mutable-global`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`mutable-global`]: Config::wasm_mutable_global
+    pub fn get_wasm_mutable_global(&self) -> bool {
+        self.features.contains(WasmFeatures::MUTABLE_GLOBAL)
+    }
+
     /// Enable or disable the [`sign-extension`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -235,6 +466,13 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`sign-extension`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`sign-extension`]: Config::wasm_sign_extension
+    pub fn get_wasm_sign_extension(&self) -> bool {
+        self.features.contains(WasmFeatures::SIGN_EXTENSION)
+    }
+
     /// Enable or disable the [`saturating-float-to-int`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -249,6 +487,14 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`saturating-float-to-int`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`saturating-float-to-int`]: Config::wasm_saturating_float_to_int
+    pub fn get_wasm_saturating_float_to_int(&self) -> bool {
+        self.features
+            .contains(WasmFeatures::SATURATING_FLOAT_TO_INT)
+    }
+
     /// Enable or disable the [`multi-value`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -261,6 +507,13 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`multi-value`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`multi-value`]: Config::wasm_multi_value
+    pub fn get_wasm_multi_value(&self) -> bool {
+        self.features.contains(WasmFeatures::MULTI_VALUE)
+    }
+
     /// Enable or disable the [`multi-memory`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -273,11 +526,23 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`multi-memory`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`multi-memory`]: Config::wasm_multi_memory
+    pub fn get_wasm_multi_memory(&self) -> bool {
+        self.features.contains(WasmFeatures::MULTI_MEMORY)
+    }
+
     /// Enable or disable the [`bulk-memory`] Wasm proposal for the [`Config`].
     ///
     /// # Note
     ///
-    /// Enabled by default.
+    /// - Enabled by default.
+    /// - Bulk memory and table operations such as `memory.copy`, `memory.fill`,
+    ///   `memory.init`, `table.copy`, `table.fill` and `table.init` always check
+    ///   all bounds and consume their fuel before writing anything. A trapping bulk
+    ///   operation thus never leaves partial writes behind, not even if its region
+    ///   straddles the end of a linear memory or table.
     ///
     /// [`bulk-memory`]: https://github.com/WebAssembly/bulk-memory-operations
     pub fn wasm_bulk_memory(&mut self, enable: bool) -> &mut Self {
@@ -285,6 +550,13 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`bulk-memory`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`bulk-memory`]: Config::wasm_bulk_memory
+    pub fn get_wasm_bulk_memory(&self) -> bool {
+        self.features.contains(WasmFeatures::BULK_MEMORY)
+    }
+
     /// Enable or disable the [`reference-types`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -298,6 +570,13 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`reference-types`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`reference-types`]: Config::wasm_reference_types
+    pub fn get_wasm_reference_types(&self) -> bool {
+        self.features.contains(WasmFeatures::REFERENCE_TYPES)
+    }
+
     /// Enable or disable the [`tail-call`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -310,6 +589,13 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`tail-call`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`tail-call`]: Config::wasm_tail_call
+    pub fn get_wasm_tail_call(&self) -> bool {
+        self.features.contains(WasmFeatures::TAIL_CALL)
+    }
+
     /// Enable or disable the [`extended-const`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -322,6 +608,13 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`extended-const`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`extended-const`]: Config::wasm_extended_const
+    pub fn get_wasm_extended_const(&self) -> bool {
+        self.features.contains(WasmFeatures::EXTENDED_CONST)
+    }
+
     /// Enable or disable the [`custom-page-sizes`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -334,14 +627,31 @@ impl Config {
         self
     }
 
+    /// Returns `true` if the [`custom-page-sizes`] Wasm proposal is enabled for the [`Config`].
+    ///
+    /// [`custom-page-sizes`]: Config::wasm_custom_page_sizes
+    pub fn get_wasm_custom_page_sizes(&self) -> bool {
+        self.features.contains(WasmFeatures::CUSTOM_PAGE_SIZES)
+    }
+
     /// Enable or disable Wasm floating point (`f32` and `f64`) instructions and types.
     ///
     /// Enabled by default.
+    ///
+    /// # Note
+    ///
+    /// Floating point support is always disabled if the `no-floats` crate feature is enabled.
     pub fn floats(&mut self, enable: bool) -> &mut Self {
+        let enable = enable && !cfg!(feature = "no-floats");
         self.features.set(WasmFeatures::FLOATS, enable);
         self
     }
 
+    /// Returns `true` if Wasm floating point (`f32` and `f64`) instructions and types are enabled.
+    pub fn get_floats(&self) -> bool {
+        self.features.contains(WasmFeatures::FLOATS)
+    }
+
     /// Configures whether Wasmi will consume fuel during execution to either halt execution as desired.
     ///
     /// # Note
@@ -367,7 +677,7 @@ impl Config {
     /// Returns `true` if the [`Config`] enables fuel consumption by the [`Engine`].
     ///
     /// [`Engine`]: crate::Engine
-    pub(crate) fn get_consume_fuel(&self) -> bool {
+    pub fn get_consume_fuel(&self) -> bool {
         self.consume_fuel
     }
 
@@ -380,12 +690,190 @@ impl Config {
     }
 
     /// Returns `true` if the [`Config`] mandates to ignore Wasm custom sections when parsing Wasm modules.
-    pub(crate) fn get_ignore_custom_sections(&self) -> bool {
+    pub fn get_ignore_custom_sections(&self) -> bool {
         self.ignore_custom_sections
     }
 
+    /// Configures whether integer division and remainder produce defined results instead of trapping.
+    ///
+    /// When enabled, Wasmi translates the Wasm `i32` and `i64` `div_s`, `div_u`, `rem_s` and
+    /// `rem_u` operators into non-trapping variants with the following semantics:
+    ///
+    /// - Division or remainder by zero returns `0`.
+    /// - The overflowing signed division `MIN / -1` saturates to `MAX`.
+    ///
+    /// # Note
+    ///
+    /// This deviates from the WebAssembly specification and is intended for embedders
+    /// porting legacy sandboxes with lenient arithmetic semantics.
+    ///
+    /// Default value: `false`
+    pub fn saturating_div_rem(&mut self, enable: bool) -> &mut Self {
+        self.saturating_div_rem = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables non-trapping integer division and remainder.
+    pub fn get_saturating_div_rem(&self) -> bool {
+        self.saturating_div_rem
+    }
+
+    /// Configures whether Wasmi instruments Wasm `loop`s to count their iterations.
+    ///
+    /// When enabled, Wasmi translates every Wasm `loop` with a counter that is incremented
+    /// each time the `loop` is entered or branched back to. Use [`Store::loop_iterations`]
+    /// to query the iteration counts of a Wasm function in order to find its hot loops.
+    ///
+    /// # Note
+    ///
+    /// - Loop iteration counting slows down the execution of all Wasm `loop`s.
+    /// - Functions with counted loops cannot be emitted ahead-of-time.
+    ///
+    /// Default value: `false`
+    ///
+    /// [`Store::loop_iterations`]: crate::Store::loop_iterations
+    pub fn count_loop_iterations(&mut self, enable: bool) -> &mut Self {
+        self.count_loop_iterations = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables counting the iterations of Wasm `loop`s.
+    pub fn get_count_loop_iterations(&self) -> bool {
+        self.count_loop_iterations
+    }
+
+    /// Configures whether the maximum sizes of imported linear memories and tables are checked.
+    ///
+    /// By default a linear memory or table can only be imported if its maximum size does not
+    /// exceed the maximum size declared by the import as required by the [import subtyping]
+    /// rules of the WebAssembly specification. When enabled, Wasmi only checks the page size,
+    /// element type and minimum size of imported linear memories and tables which allows to
+    /// instantiate Wasm modules of producers that rely on relaxed import limits, e.g. modules
+    /// that declare a smaller maximum size for an imported linear memory than provided.
+    ///
+    /// # Note
+    ///
+    /// - This deviates from the WebAssembly specification.
+    /// - Imported linear memories and tables may grow beyond the maximum size declared by
+    ///   the import since growth is always limited by their own maximum size.
+    ///
+    /// Default value: `false`
+    ///
+    /// [import subtyping]: https://webassembly.github.io/spec/core/valid/types.html#import-subtyping
+    pub fn relaxed_import_limits(&mut self, enable: bool) -> &mut Self {
+        self.relaxed_import_limits = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables relaxed import limits.
+    pub fn get_relaxed_import_limits(&self) -> bool {
+        self.relaxed_import_limits
+    }
+
+    /// Configures whether Wasmi zeroes Wasm stack values and linear memory bytes once they are freed.
+    ///
+    /// When enabled, secrets processed by Wasm code, e.g. keys or FHE parameters, do not
+    /// linger in host memory after their use:
+    ///
+    /// - The registers of a Wasm or host function call frame are zeroed upon returning.
+    /// - Wasm stacks are zeroed before they are reallocated, reused or dropped.
+    /// - Linear memories are zeroed before they are reallocated by `memory.grow`
+    ///   and when they are dropped together with their [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - Zeroing is best effort: copies made by the host, e.g. via [`Memory::read`],
+    ///   snapshots or results of Wasm function calls, are not covered.
+    /// - The overhead is proportional to the number of returning calls and their registers.
+    ///   The call intensive `execute/zero_on_free` benchmark, a recursive Fibonacci, runs
+    ///   about 5-10% slower while loops without calls are not affected measurably.
+    ///
+    /// Default value: `false`
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Memory::read`]: crate::Memory::read
+    pub fn zero_on_free(&mut self, enable: bool) -> &mut Self {
+        self.zero_on_free = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables zeroing of freed Wasm stack values and linear memory bytes.
+    pub fn get_zero_on_free(&self) -> bool {
+        self.zero_on_free
+    }
+
+    /// Configures whether traps capture the Wasm call frames of the trapping execution.
+    ///
+    /// When enabled, traps raised while executing Wasm carry the innermost Wasm call frames
+    /// via [`ErrorCategory::Trap`] and out of fuel traps carry them via [`FuelTrap::frames`].
+    ///
+    /// # Note
+    ///
+    /// Capturing call frames resolves the executed function, its export name and symbol
+    /// for every captured call frame upon each trap. This is expensive for hosts that
+    /// frequently trap, e.g. when slicing executions via fuel, and thus disabled by default.
+    ///
+    /// Default value: `false`
+    ///
+    /// [`ErrorCategory::Trap`]: crate::errors::ErrorCategory::Trap
+    /// [`FuelTrap::frames`]: crate::errors::FuelTrap::frames
+    pub fn wasm_backtrace(&mut self, enable: bool) -> &mut Self {
+        self.wasm_backtrace = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables capturing the Wasm call frames of traps.
+    pub fn get_wasm_backtrace(&self) -> bool {
+        self.wasm_backtrace
+    }
+
+    /// Sets the fuel charged per linear memory page added by `memory.grow`.
+    ///
+    /// This is charged in addition to the fuel for the added bytes and makes the
+    /// costs of `memory.grow` proportional to the number of added pages, e.g. to
+    /// price memory inflation under fuel metering independent of the page size.
+    ///
+    /// # Note
+    ///
+    /// This only has an effect if fuel metering is enabled via [`Config::consume_fuel`].
+    ///
+    /// Default value: `0`
+    pub fn fuel_per_memory_page(&mut self, fuel: u64) -> &mut Self {
+        self.fuel_costs.fuel_per_memory_page = fuel;
+        self
+    }
+
+    /// Sets the fuel charged per table element added by `table.grow`.
+    ///
+    /// This is charged in addition to the fuel for copying the added elements and
+    /// makes the costs of `table.grow` proportional to the number of added elements.
+    ///
+    /// # Note
+    ///
+    /// This only has an effect if fuel metering is enabled via [`Config::consume_fuel`].
+    ///
+    /// Default value: `0`
+    pub fn fuel_per_table_element(&mut self, fuel: u64) -> &mut Self {
+        self.fuel_costs.fuel_per_table_element = fuel;
+        self
+    }
+
+    /// Sets the [`FuelCosts`] used for fuel metering to `fuel_costs`.
+    ///
+    /// This is useful to apply fuel costs that were calibrated for the current host.
+    ///
+    /// # Note
+    ///
+    /// This only has an effect if fuel metering is enabled via [`Config::consume_fuel`].
+    ///
+    /// Default value: [`FuelCosts::default`]
+    pub fn set_fuel_costs(&mut self, fuel_costs: FuelCosts) -> &mut Self {
+        self.fuel_costs = fuel_costs;
+        self
+    }
+
     /// Returns the configured [`FuelCosts`].
-    pub(crate) fn fuel_costs(&self) -> &FuelCosts {
+    pub fn get_fuel_costs(&self) -> &FuelCosts {
         &self.fuel_costs
     }
 
@@ -402,10 +890,26 @@ impl Config {
     /// Returns the [`CompilationMode`] used for the [`Engine`].
     ///
     /// [`Engine`]: crate::Engine
-    pub(super) fn get_compilation_mode(&self) -> CompilationMode {
+    pub fn get_compilation_mode(&self) -> CompilationMode {
         self.compilation_mode
     }
 
+    /// Sets the behavior for Wasm operators of Wasm proposals that are disabled in the [`Config`].
+    ///
+    /// With [`UnsupportedProposals::TrapAtUse`] hosts can load Wasm modules that only
+    /// optionally use disabled Wasm proposals and fail lazily once those are used.
+    ///
+    /// By default [`UnsupportedProposals::Reject`] is used.
+    pub fn unsupported_proposals(&mut self, mode: UnsupportedProposals) -> &mut Self {
+        self.unsupported_proposals = mode;
+        self
+    }
+
+    /// Returns the behavior for Wasm operators of Wasm proposals that are disabled in the [`Config`].
+    pub fn get_unsupported_proposals(&self) -> UnsupportedProposals {
+        self.unsupported_proposals
+    }
+
     /// Sets the [`EnforcedLimits`] enforced by the [`Engine`] for Wasm module parsing and compilation.
     ///
     /// By default no limits are enforced.
@@ -419,12 +923,515 @@ impl Config {
     /// Returns the [`EnforcedLimits`] used for the [`Engine`].
     ///
     /// [`Engine`]: crate::Engine
-    pub(crate) fn get_enforced_limits(&self) -> &EnforcedLimits {
+    pub fn get_enforced_limits(&self) -> &EnforcedLimits {
         &self.limits
     }
 
+    /// Sets the compilation fuel available to the translation of a single Wasm module.
+    ///
+    /// [`Module::new`] and friends fail with an error if the translation of a
+    /// Wasm module consumes more than `limit` units of compilation fuel which
+    /// protects against Wasm binaries crafted to explode translation time or memory.
+    ///
+    /// By default compilation fuel is not metered.
+    ///
+    /// # Note
+    ///
+    /// - One unit of compilation fuel is consumed per byte of a function body and
+    ///   per local variable declared by a function body.
+    /// - Compilation fuel is consumed upon parsing the code section regardless
+    ///   of the [`CompilationMode`] in use.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    pub fn compilation_fuel(&mut self, limit: u64) -> &mut Self {
+        self.compilation_fuel = Some(limit);
+        self
+    }
+
+    /// Returns the compilation fuel available to the translation of a single Wasm module if any.
+    ///
+    /// Returns `None` if compilation fuel is not metered.
+    pub fn get_compilation_fuel(&self) -> Option<u64> {
+        self.compilation_fuel
+    }
+
+    /// Sets the maximum size of the translated Wasmi bytecode of a single function in bytes.
+    ///
+    /// The translation of a function fails with an error if its Wasmi bytecode and function
+    /// local constants exceed `limit` bytes. This protects memory constrained hosts against
+    /// small Wasm functions that are amplified into huge Wasmi bytecode, e.g. by large
+    /// `br_table` instructions or long sequences of straight-line code.
+    ///
+    /// By default the size of translated functions is not limited.
+    ///
+    /// # Note
+    ///
+    /// - With lazy [`CompilationMode`]s the error is reported by the first call
+    ///   of the offending function instead of by [`Module::new`].
+    /// - Use [`Module::translated_func_size`] to query the size of translated functions.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::translated_func_size`]: crate::Module::translated_func_size
+    pub fn max_translated_func_size(&mut self, limit: usize) -> &mut Self {
+        self.max_translated_func_size = Some(limit);
+        self
+    }
+
+    /// Returns the maximum size of the translated Wasmi bytecode of a single function if any.
+    ///
+    /// Returns `None` if the size of translated functions is not limited.
+    pub fn get_max_translated_func_size(&self) -> Option<usize> {
+        self.max_translated_func_size
+    }
+
+    /// Requires Wasm modules to be signed and verified by `verifier`.
+    ///
+    /// With this setting only [`Module::new_signed`] creates Wasm modules and only if
+    /// `verifier` accepts their [`ModuleSignature`]. All other ways to create a Wasm
+    /// module, such as [`Module::new`] or [`Module::new_streaming`], fail with an error.
+    /// This allows plugin marketplaces to enforce the provenance of the executed Wasm
+    /// binaries at the [`Engine`] boundary.
+    ///
+    /// By default Wasm modules are not required to be signed.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::new_signed`]: crate::Module::new_signed
+    /// [`Module::new_streaming`]: crate::Module::new_streaming
+    /// [`Engine`]: crate::Engine
+    pub fn require_signed_modules(&mut self, verifier: ModuleVerifier) -> &mut Self {
+        self.module_verifier = Some(VerifierFn(verifier));
+        self
+    }
+
+    /// Returns the [`ModuleVerifier`] if Wasm modules are required to be signed.
+    pub fn get_module_verifier(&self) -> Option<ModuleVerifier> {
+        self.module_verifier.map(|verifier| verifier.0)
+    }
+
+    /// Validates the interplay of the settings of the [`Config`].
+    ///
+    /// This is checked by [`Engine::new_with_features`] so that misconfigurations
+    /// are reported upon [`Engine`] construction instead of causing obscure failures
+    /// upon Wasm module translation.
+    ///
+    /// # Note
+    ///
+    /// Wasmi supports neither the `threads` nor the `function-references` Wasm proposal,
+    /// thus Wasm modules using them are always rejected and there is nothing to validate.
+    ///
+    /// # Errors
+    ///
+    /// - If the `reference-types` Wasm proposal is enabled without the `bulk-memory`
+    ///   Wasm proposal since declaring the functions referenced via `ref.func` requires
+    ///   the element segment encodings of the latter.
+    /// - If [`FuelCosts`] are configured while fuel metering is disabled.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::new_with_features`]: crate::Engine::new_with_features
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let features = self.wasm_features();
+        if features.contains(WasmFeatures::REFERENCE_TYPES)
+            && !features.contains(WasmFeatures::BULK_MEMORY)
+        {
+            return Err(ConfigError::MissingRequiredProposal {
+                proposal: "reference-types",
+                requires: "bulk-memory",
+            });
+        }
+        if !self.consume_fuel && self.fuel_costs != FuelCosts::default() {
+            return Err(ConfigError::FuelCostsWithoutFuelMetering);
+        }
+        Ok(())
+    }
+
     /// Returns the [`WasmFeatures`] represented by the [`Config`].
+    ///
+    /// # Note
+    ///
+    /// This includes the [`Config::trapping_features`] which are required
+    /// to parse and validate the operators that are translated into traps.
     pub(crate) fn wasm_features(&self) -> WasmFeatures {
-        self.features
+        self.features | self.trapping_features()
+    }
+
+    /// Returns the [`WasmFeatures`] of disabled Wasm proposals whose operators translate into traps.
+    pub(crate) fn trapping_features(&self) -> WasmFeatures {
+        match self.unsupported_proposals {
+            UnsupportedProposals::Reject => WasmFeatures::empty(),
+            UnsupportedProposals::TrapAtUse => {
+                let trappable = WasmFeatures::SIGN_EXTENSION
+                    | WasmFeatures::SATURATING_FLOAT_TO_INT
+                    | WasmFeatures::BULK_MEMORY
+                    | WasmFeatures::REFERENCE_TYPES
+                    | WasmFeatures::TAIL_CALL;
+                trappable.difference(self.features)
+            }
+        }
+    }
+
+    /// Returns a hash of all [`Config`] settings that affect Wasm module compilation.
+    ///
+    /// # Note
+    ///
+    /// - Two [`Config`]s with the same compatibility hash produce the same Wasmi
+    ///   bytecode for the same Wasm module with the same version of Wasmi.
+    ///   Therefore the returned value can be used as a cache key for compiled or
+    ///   serialized Wasm modules.
+    /// - Settings that only affect execution or instantiation are not part of the hash.
+    ///   These are the [`StackLimits`], the number of cached stacks, relaxed import limits,
+    ///   zeroing freed memory and capturing trap backtraces.
+    /// - Settings that limit or reject the compilation of Wasm modules, such as the
+    ///   compilation fuel, are part of the hash so that cached Wasm modules cannot be
+    ///   used to bypass them.
+    /// - Only whether Wasm modules are required to be signed is part of the hash but not
+    ///   the [`ModuleVerifier`] itself since function addresses are not stable across processes.
+    /// - The hash is stable across processes and platforms but may change between
+    ///   different versions of Wasmi.
+    pub fn compatibility_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        hasher.write_bytes(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.write_u64(u64::from(self.features.bits()));
+        hasher.write_bool(self.consume_fuel);
+        hasher.write_bool(self.ignore_custom_sections);
+        hasher.write_bool(self.saturating_div_rem);
+        hasher.write_bool(self.count_loop_iterations);
+        hasher.write_u64(self.fuel_costs.base);
+        hasher.write_u64(self.fuel_costs.copies_per_fuel.get());
+        hasher.write_u64(self.fuel_costs.bytes_per_fuel.get());
+        hasher.write_u64(self.fuel_costs.fuel_per_memory_page);
+        hasher.write_u64(self.fuel_costs.fuel_per_table_element);
+        hasher.write_u64(match self.compilation_mode {
+            CompilationMode::Eager => 0,
+            CompilationMode::LazyTranslation => 1,
+            CompilationMode::Lazy => 2,
+        });
+        hasher.write_bool(self.unsupported_proposals == UnsupportedProposals::TrapAtUse);
+        let limits = &self.limits;
+        for limit in [
+            limits.max_globals,
+            limits.max_functions,
+            limits.max_tables,
+            limits.max_element_segments,
+            limits.max_memories,
+            limits.max_data_segments,
+            limits.max_section_items,
+            limits.max_types,
+            limits.max_imports,
+            limits.max_br_table_targets,
+            limits.max_nesting_depth,
+        ] {
+            hasher.write_opt_u64(limit.map(u64::from));
+        }
+        for limit in [limits.max_params, limits.max_results] {
+            hasher.write_opt_u64(limit.map(|limit| limit as u64));
+        }
+        let min_avg = limits.min_avg_bytes_per_function;
+        hasher.write_opt_u64(min_avg.map(|limit| u64::from(limit.req_funcs_bytes)));
+        hasher.write_opt_u64(min_avg.map(|limit| u64::from(limit.min_avg_bytes_per_function)));
+        hasher.write_opt_u64(self.compilation_fuel);
+        hasher.write_opt_u64(self.max_translated_func_size.map(|limit| limit as u64));
+        hasher.write_bool(self.module_verifier.is_some());
+        hasher.finish()
+    }
+
+    /// Returns the names of all settings that differ between `self` and `other`.
+    ///
+    /// # Note
+    ///
+    /// Wasm proposals are reported individually by their proposal name.
+    pub fn diff(&self, other: &Self) -> ConfigDiff {
+        let mut diff = ConfigDiff::default();
+        let mut check = |name: &'static str, differs: bool| {
+            if differs {
+                diff.settings.push(name);
+            }
+        };
+        check("stack-limits", self.stack_limits != other.stack_limits);
+        check("cached-stacks", self.cached_stacks != other.cached_stacks);
+        for (name, feature) in WASM_PROPOSALS {
+            check(
+                name,
+                self.features.contains(feature) != other.features.contains(feature),
+            );
+        }
+        check("consume-fuel", self.consume_fuel != other.consume_fuel);
+        check(
+            "ignore-custom-sections",
+            self.ignore_custom_sections != other.ignore_custom_sections,
+        );
+        check(
+            "saturating-div-rem",
+            self.saturating_div_rem != other.saturating_div_rem,
+        );
+        check(
+            "count-loop-iterations",
+            self.count_loop_iterations != other.count_loop_iterations,
+        );
+        check(
+            "relaxed-import-limits",
+            self.relaxed_import_limits != other.relaxed_import_limits,
+        );
+        check("zero-on-free", self.zero_on_free != other.zero_on_free);
+        check(
+            "wasm-backtrace",
+            self.wasm_backtrace != other.wasm_backtrace,
+        );
+        check("fuel-costs", self.fuel_costs != other.fuel_costs);
+        check(
+            "compilation-mode",
+            self.compilation_mode != other.compilation_mode,
+        );
+        check(
+            "unsupported-proposals",
+            self.unsupported_proposals != other.unsupported_proposals,
+        );
+        check("enforced-limits", self.limits != other.limits);
+        check(
+            "compilation-fuel",
+            self.compilation_fuel != other.compilation_fuel,
+        );
+        check(
+            "max-translated-func-size",
+            self.max_translated_func_size != other.max_translated_func_size,
+        );
+        check(
+            "require-signed-modules",
+            self.module_verifier != other.module_verifier,
+        );
+        diff
+    }
+}
+
+/// The differences between two [`Config`]s as returned by [`Config::diff`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// The names of the settings that differ.
+    settings: Vec<&'static str>,
+}
+
+impl ConfigDiff {
+    /// Returns `true` if both [`Config`]s are equal.
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+
+    /// Returns `true` if the setting with `name` differs.
+    pub fn contains(&self, name: &str) -> bool {
+        self.settings.contains(&name)
+    }
+
+    /// Returns an iterator over the names of all settings that differ.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'static str> + '_ {
+        self.settings.iter().copied()
+    }
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, setting) in self.settings.iter().enumerate() {
+            if n != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{setting}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A 64-bit FNV-1a hasher used to compute [`Config::compatibility_hash`] and [`Module::hash`].
+///
+/// # Note
+///
+/// We cannot use [`core::hash::Hash`] since its output is not guaranteed
+/// to be stable across platforms and Rust versions.
+///
+/// [`Module::hash`]: crate::Module::hash
+#[derive(Debug, Clone)]
+pub(crate) struct StableHasher {
+    state: u64,
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self {
+            state: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+}
+
+impl StableHasher {
+    /// Feeds `bytes` into the [`StableHasher`].
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Feeds `value` into the [`StableHasher`].
+    fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    /// Feeds `value` into the [`StableHasher`].
+    fn write_bool(&mut self, value: bool) {
+        self.write_bytes(&[u8::from(value)]);
+    }
+
+    /// Feeds the optional `value` into the [`StableHasher`].
+    fn write_opt_u64(&mut self, value: Option<u64>) {
+        match value {
+            Some(value) => {
+                self.write_bool(true);
+                self.write_u64(value);
+            }
+            None => self.write_bool(false),
+        }
+    }
+
+    /// Returns the computed hash value.
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn compatibility_hash_works() {
+        let default = Config::default();
+        assert_eq!(
+            default.compatibility_hash(),
+            Config::default().compatibility_hash()
+        );
+        let mut fuel = Config::default();
+        fuel.consume_fuel(true);
+        assert_ne!(default.compatibility_hash(), fuel.compatibility_hash());
+        let mut lazy = Config::default();
+        lazy.compilation_mode(CompilationMode::Lazy);
+        assert_ne!(default.compatibility_hash(), lazy.compatibility_hash());
+        let mut trap = Config::default();
+        trap.unsupported_proposals(UnsupportedProposals::TrapAtUse);
+        assert_ne!(default.compatibility_hash(), trap.compatibility_hash());
+        // Execution-only settings do not affect the compatibility hash.
+        let mut stacks = Config::default();
+        stacks.set_cached_stacks(10);
+        assert_eq!(default.compatibility_hash(), stacks.compatibility_hash());
+    }
+
+    #[test]
+    fn compatibility_hash_agrees_with_diff() {
+        fn verifier(_wasm: &[u8], _signature: &crate::ModuleSignature) -> bool {
+            true
+        }
+        // The settings reported by `Config::diff` that are not part of the hash.
+        let execution_only = [
+            "stack-limits",
+            "cached-stacks",
+            "relaxed-import-limits",
+            "zero-on-free",
+            "wasm-backtrace",
+        ];
+        let mut configs = Vec::new();
+        let mut push = |f: fn(&mut Config)| {
+            let mut config = Config::default();
+            f(&mut config);
+            configs.push(config);
+        };
+        push(|_| {});
+        push(|c| {
+            c.set_stack_limits(StackLimits::new(1, 1024, 10).unwrap());
+        });
+        push(|c| {
+            c.set_cached_stacks(10);
+        });
+        push(|c| {
+            c.wasm_tail_call(false);
+        });
+        push(|c| {
+            c.wasm_custom_page_sizes(true);
+        });
+        push(|c| {
+            c.consume_fuel(true);
+        });
+        push(|c| {
+            c.consume_fuel(true).fuel_per_memory_page(1);
+        });
+        push(|c| {
+            c.consume_fuel(true).fuel_per_table_element(1);
+        });
+        push(|c| {
+            c.ignore_custom_sections(true);
+        });
+        push(|c| {
+            c.saturating_div_rem(true);
+        });
+        push(|c| {
+            c.count_loop_iterations(true);
+        });
+        push(|c| {
+            c.relaxed_import_limits(true);
+        });
+        push(|c| {
+            c.zero_on_free(true);
+        });
+        push(|c| {
+            c.wasm_backtrace(true);
+        });
+        push(|c| {
+            c.compilation_mode(CompilationMode::Lazy);
+        });
+        push(|c| {
+            c.unsupported_proposals(UnsupportedProposals::TrapAtUse);
+        });
+        push(|c| {
+            c.enforced_limits(EnforcedLimits::strict());
+        });
+        push(|c| {
+            c.compilation_fuel(1_000);
+        });
+        push(|c| {
+            c.max_translated_func_size(1_000);
+        });
+        push(|c| {
+            c.require_signed_modules(verifier);
+        });
+        for a in &configs {
+            for b in &configs {
+                let diff = a.diff(b);
+                let same_hash = a.compatibility_hash() == b.compatibility_hash();
+                if diff.is_empty() {
+                    assert!(same_hash, "equal configs with different hashes");
+                    continue;
+                }
+                let compiles_same = diff.iter().all(|name| execution_only.contains(&name));
+                assert_eq!(same_hash, compiles_same, "differing settings: {diff}");
+            }
+        }
+    }
+
+    #[test]
+    fn diff_works() {
+        let default = Config::default();
+        assert!(default.diff(&Config::default()).is_empty());
+        let mut config = Config::default();
+        config
+            .wasm_tail_call(false)
+            .consume_fuel(true)
+            .enforced_limits(EnforcedLimits::strict());
+        let diff = default.diff(&config);
+        assert_eq!(
+            diff.iter().collect::<Vec<_>>(),
+            ["tail-call", "consume-fuel", "enforced-limits"],
+        );
+        assert!(diff.contains("consume-fuel"));
+        assert!(!diff.contains("floats"));
+        assert_eq!(diff.to_string(), "tail-call, consume-fuel, enforced-limits");
+        assert!(!config.get_wasm_tail_call());
+        assert!(config.get_consume_fuel());
+        assert_eq!(config.get_enforced_limits().max_memories(), Some(1));
     }
 }
@@ -1,9 +1,10 @@
 use crate::{
     core::UntypedVal,
     engine::DedupFuncType,
+    global::GlobalEntity,
     instance::InstanceEntity,
     ir::index,
-    memory::DataSegment,
+    memory::{mark_dirty, DataSegment},
     module::DEFAULT_MEMORY_INDEX,
     store::StoreInner,
     table::ElementSegment,
@@ -16,25 +17,46 @@ use crate::{
 use core::ptr::{self, NonNull};
 
 /// Cached WebAssembly instance.
+///
+/// # Invalidation
+///
+/// The cached pointers into linear memories and global variables are invalidated by ...
+///
+/// - changing the currently used instance via calls and returns,
+/// - calling host functions and signal handlers,
+/// - successfully growing a linear memory of the currently used instance.
+///
+/// Upon these events either [`CachedInstance::update`] or [`CachedInstance::update_memory`]
+/// is called which reloads the cached default linear memory and global variables
+/// and invalidates all other cached linear memories and global variables.
 #[derive(Debug)]
 pub struct CachedInstance {
     /// The currently used instance.
     instance: NonNull<InstanceEntity>,
     /// The cached bytes of the default linear memory.
     pub memory: CachedMemory,
+    /// The cached bytes of the recently used linear memories at other indices.
+    pub memories: CachedMemories,
     /// The cached value of the global variable at index 0.
     pub global: CachedGlobal,
+    /// The cached flat global variables of the instance.
+    pub flat_globals: CachedFlatGlobals,
+    /// The cached values of the recently used global variables at other indices.
+    pub globals: CachedGlobals,
 }
 
 impl CachedInstance {
     /// Creates a new [`CachedInstance`].
     #[inline]
     pub fn new(ctx: &mut StoreInner, instance: &Instance) -> Self {
-        let (instance, memory, global) = Self::load_caches(ctx, instance);
+        let (instance, memory, global, flat_globals) = Self::load_caches(ctx, instance);
         Self {
             instance,
             memory,
+            memories: CachedMemories::default(),
             global,
+            flat_globals,
+            globals: CachedGlobals::default(),
         }
     }
 
@@ -44,15 +66,23 @@ impl CachedInstance {
         ctx.resolve_instance(instance)
     }
 
-    /// Loads the cached global and linear memory.
+    /// Loads the cached global variables and linear memory.
     #[inline]
     fn load_caches(
         ctx: &mut StoreInner,
         instance: &Instance,
-    ) -> (NonNull<InstanceEntity>, CachedMemory, CachedGlobal) {
+    ) -> (
+        NonNull<InstanceEntity>,
+        CachedMemory,
+        CachedGlobal,
+        CachedFlatGlobals,
+    ) {
         let entity = Self::load_instance(ctx, instance);
         let memory = entity.get_memory(DEFAULT_MEMORY_INDEX);
         let global = entity.get_global(0);
+        let flat_start = entity.flat_globals();
+        let flat_first = entity.get_global(flat_start);
+        let flat_len = entity.globals().len() as u32 - flat_start;
         let instance = entity.into();
         let memory = memory
             .map(|memory| CachedMemory::new(ctx, &memory))
@@ -60,13 +90,19 @@ impl CachedInstance {
         let global = global
             .map(|global| CachedGlobal::new(ctx, &global))
             .unwrap_or_default();
-        (instance, memory, global)
+        let flat_globals = flat_first
+            .map(|first| CachedFlatGlobals::new(ctx, &first, flat_start, flat_len))
+            .unwrap_or_default();
+        (instance, memory, global, flat_globals)
     }
 
     /// Update the cached instance, linear memory and global variable.
     #[inline]
     pub fn update(&mut self, ctx: &mut StoreInner, instance: &Instance) {
-        (self.instance, self.memory, self.global) = Self::load_caches(ctx, instance);
+        (self.instance, self.memory, self.global, self.flat_globals) =
+            Self::load_caches(ctx, instance);
+        self.memories.invalidate();
+        self.globals.invalidate();
     }
 
     /// Returns a shared reference to the cached [`InstanceEntity`].
@@ -79,7 +115,24 @@ impl CachedInstance {
         unsafe { self.instance.as_ref() }
     }
 
-    /// Updates the [`CachedMemory`]'s linear memory data pointer.
+    /// Returns `true` if the caches are consistent with the `instance` of the `ctx`.
+    #[cfg(feature = "checked-execution")]
+    pub fn is_consistent(&self, ctx: &mut StoreInner, instance: &Instance) -> bool {
+        let (expected, memory, global, flat_globals) = Self::load_caches(ctx, instance);
+        self.instance == expected
+            && self.memory.data.cast::<u8>() == memory.data.cast::<u8>()
+            && self.memory.data.len() == memory.data.len()
+            && self
+                .memories
+                .is_consistent(ctx, unsafe { expected.as_ref() })
+            && self.global.data == global.data
+            && self.flat_globals == flat_globals
+            && self
+                .globals
+                .is_consistent(ctx, unsafe { expected.as_ref() })
+    }
+
+    /// Updates the [`CachedMemory`]'s linear memory data pointer and invalidates the [`CachedMemories`].
     ///
     /// # Note
     ///
@@ -88,7 +141,7 @@ impl CachedInstance {
     /// The linear memory pointer might change when ...
     ///
     /// - calling a host function
-    /// - successfully growing the default linear memory
+    /// - successfully growing a linear memory
     /// - calling functions defined in other instances via imported or indirect calls
     /// - returning from functions that changed the currently used instance
     ///
@@ -102,6 +155,7 @@ impl CachedInstance {
             .get_memory(DEFAULT_MEMORY_INDEX)
             .map(|memory| CachedMemory::new(ctx, &memory))
             .unwrap_or_default();
+        self.memories.invalidate();
     }
 
     /// Returns the [`Func`] at the `index` if any.
@@ -182,10 +236,11 @@ impl CachedInstance {
     }
 }
 
-/// Cached default linear memory bytes.
-#[derive(Debug)]
+/// Cached linear memory bytes and dirty page bitmap.
+#[derive(Debug, Copy, Clone)]
 pub struct CachedMemory {
     data: NonNull<[u8]>,
+    dirty: CachedDirtyPages,
 }
 
 impl Default for CachedMemory {
@@ -193,54 +248,195 @@ impl Default for CachedMemory {
     fn default() -> Self {
         Self {
             data: NonNull::from(&mut []),
+            dirty: CachedDirtyPages::default(),
         }
     }
 }
 
 impl CachedMemory {
-    /// Create a new [`CachedMemory`].
-    #[inline]
-    fn new(ctx: &mut StoreInner, instance: &Memory) -> Self {
-        let data = Self::load_default_memory(ctx, instance);
-        Self { data }
-    }
-
-    /// Loads the default [`Memory`] of the currently used [`Instance`].
+    /// Creates a new [`CachedMemory`] from the bytes and dirty page bitmap of `memory`.
     ///
     /// # Note
     ///
-    /// Must be called whenever the heap allocation of the [`CachedMemory`]
-    /// could have been changed and thus the cached pointer invalidated.
-    ///
-    /// # Panics
-    ///
-    /// If the currently used [`Instance`] does not have a default linear memory.
-    ///
-    /// [`Memory`]: crate::Memory
+    /// Must be called whenever the heap allocations of the [`CachedMemory`]
+    /// could have been changed and thus the cached pointers invalidated.
     #[inline]
-    fn load_default_memory(ctx: &mut StoreInner, memory: &Memory) -> NonNull<[u8]> {
-        ctx.resolve_memory_mut(memory).data_mut().into()
+    pub fn new(ctx: &mut StoreInner, memory: &Memory) -> Self {
+        let memory = ctx.resolve_memory_mut(memory);
+        let dirty = CachedDirtyPages {
+            page_size_log2: memory.ty().page_size_log2(),
+            bits: memory.dirty_bits_mut().map(NonNull::from),
+        };
+        let data = memory.data_mut_untracked().into();
+        Self { data, dirty }
     }
 
-    /// Returns a shared slice to the bytes of the cached default linear memory.
+    /// Returns the pointer to the bytes of the cached linear memory.
+    #[inline]
+    pub fn data_ptr(&self) -> NonNull<[u8]> {
+        self.data
+    }
+
+    /// Returns a shared slice to the bytes of the cached linear memory.
     ///
     /// # Safety
     ///
-    /// The user is required to call [`CachedMemory::load_default_memory`] according to its specification.
+    /// The user is required to call [`CachedMemory::new`] according to its specification.
     #[inline]
     pub unsafe fn data(&self) -> &[u8] {
         unsafe { self.data.as_ref() }
     }
 
-    /// Returns an exclusive slice to the bytes of the cached default linear memory.
+    /// Returns an exclusive slice to the bytes of the cached linear memory.
+    ///
+    /// # Note
+    ///
+    /// Writes to the returned slice must be marked via [`CachedDirtyPages::mark`].
     ///
     /// # Safety
     ///
-    /// The user is required to call [`CachedMemory::load_default_memory`] according to its specification.
+    /// The user is required to call [`CachedMemory::new`] according to its specification.
     #[inline]
     pub unsafe fn data_mut(&mut self) -> &mut [u8] {
         unsafe { self.data.as_mut() }
     }
+
+    /// Returns the cached dirty page bitmap of the cached linear memory.
+    #[inline]
+    pub fn dirty(&self) -> CachedDirtyPages {
+        self.dirty
+    }
+}
+
+/// Cached dirty page bitmap of a linear memory.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CachedDirtyPages {
+    /// The dirty page bitmap if the linear memory tracks its dirty pages.
+    bits: Option<NonNull<[u64]>>,
+    /// The binary logarithm of the page size of the linear memory.
+    page_size_log2: u8,
+}
+
+impl CachedDirtyPages {
+    /// Marks all pages overlapping with the `len` bytes at `offset` as dirty.
+    ///
+    /// # Safety
+    ///
+    /// The user is required to call [`CachedMemory::new`] according to its specification.
+    #[inline]
+    pub unsafe fn mark(self, offset: usize, len: usize) {
+        if let Some(mut bits) = self.bits {
+            mark_dirty(unsafe { bits.as_mut() }, self.page_size_log2, offset, len);
+        }
+    }
+}
+
+/// Cached bytes of the recently used linear memories at indices other than 0.
+///
+/// # Note
+///
+/// This is a direct mapped cache so that multi-memory Wasm modules do not
+/// have to resolve their non-default linear memories for every access.
+#[derive(Debug)]
+pub struct CachedMemories {
+    /// The cached entries indexed by the lowest bits of the linear memory index.
+    entries: [CachedMemoriesEntry; CachedMemories::LEN],
+    /// The current epoch of the [`CachedMemories`].
+    ///
+    /// Entries of older epochs are stale which makes invalidation cheap.
+    epoch: u32,
+}
+
+/// An entry of the [`CachedMemories`].
+#[derive(Debug, Copy, Clone)]
+struct CachedMemoriesEntry {
+    /// The index of the cached linear memory.
+    index: u32,
+    /// The epoch in which the entry has been cached.
+    epoch: u32,
+    /// The bytes and dirty page bitmap of the cached linear memory.
+    memory: CachedMemory,
+}
+
+impl Default for CachedMemoriesEntry {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            index: 0,
+            epoch: 0,
+            memory: CachedMemory::default(),
+        }
+    }
+}
+
+impl Default for CachedMemories {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            entries: [CachedMemoriesEntry::default(); Self::LEN],
+            epoch: 1,
+        }
+    }
+}
+
+impl CachedMemories {
+    /// The number of entries of the [`CachedMemories`].
+    const LEN: usize = 4;
+
+    /// Returns the entry slot for the linear memory at `index`.
+    #[inline]
+    fn slot(index: u32) -> usize {
+        index as usize % Self::LEN
+    }
+
+    /// Invalidates all entries of the [`CachedMemories`].
+    ///
+    /// # Note
+    ///
+    /// Must be called whenever [`CachedMemory`] is reloaded.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.epoch = self.epoch.wrapping_add(1);
+        if self.epoch == 0 {
+            // Note: upon wrap-around stale entries could become fresh again.
+            *self = Self::default();
+        }
+    }
+
+    /// Returns the cached linear memory at `index` if any.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<CachedMemory> {
+        let entry = &self.entries[Self::slot(index)];
+        if entry.index != index || entry.epoch != self.epoch {
+            return None;
+        }
+        Some(entry.memory)
+    }
+
+    /// Caches the linear `memory` at `index`.
+    #[inline]
+    pub fn insert(&mut self, index: u32, memory: CachedMemory) {
+        self.entries[Self::slot(index)] = CachedMemoriesEntry {
+            index,
+            epoch: self.epoch,
+            memory,
+        };
+    }
+
+    /// Returns `true` if all fresh entries point to the linear memories of `instance`.
+    #[cfg(feature = "checked-execution")]
+    fn is_consistent(&self, ctx: &mut StoreInner, instance: &InstanceEntity) -> bool {
+        self.entries
+            .iter()
+            .filter(|entry| entry.epoch == self.epoch)
+            .all(|entry| {
+                instance.get_memory(entry.index).is_some_and(|memory| {
+                    let data = ctx.resolve_memory_mut(&memory).data_mut_untracked();
+                    data.as_ptr() == entry.memory.data.cast::<u8>().as_ptr()
+                        && data.len() == entry.memory.data.len()
+                })
+            })
+    }
 }
 
 /// Cached default global variable value.
@@ -292,7 +488,19 @@ impl CachedGlobal {
     /// [`Global`]: crate::Global
     #[inline]
     fn load_global(ctx: &mut StoreInner, global: &Global) -> *mut UntypedVal {
-        ctx.resolve_global_mut(global).get_untyped_ptr().as_ptr()
+        ctx.resolve_global_mut(global)
+            .get_untyped_ptr()
+            .map_or(ptr::null_mut(), NonNull::as_ptr)
+    }
+
+    /// Returns `true` if the value of the global variable is cached.
+    ///
+    /// # Note
+    ///
+    /// The values of computed global variables are never cached.
+    #[inline]
+    pub fn is_cached(&self) -> bool {
+        !self.data.is_null()
     }
 
     /// Returns the value of the cached global variable.
@@ -319,3 +527,163 @@ impl CachedGlobal {
         unsafe { self.data.write(new_value) };
     }
 }
+
+/// Cached base pointer to the flat global variables of an instance.
+///
+/// The flat global variables of an instance are stored in order and without gaps in
+/// the [`StoreInner`] which allows to access them via their offset to a base pointer.
+/// Usually these are all global variables defined by the Wasm module of the instance.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CachedFlatGlobals {
+    /// The first flat global variable.
+    base: *mut GlobalEntity,
+    /// The index of the first flat global variable.
+    start: u32,
+    /// The number of flat global variables.
+    len: u32,
+}
+
+impl Default for CachedFlatGlobals {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            base: ptr::null_mut(),
+            start: 0,
+            len: 0,
+        }
+    }
+}
+
+impl CachedFlatGlobals {
+    /// Creates a new [`CachedFlatGlobals`] for `len` flat global variables starting with `first` at index `start`.
+    #[inline]
+    fn new(ctx: &mut StoreInner, first: &Global, start: u32, len: u32) -> Self {
+        let base = ctx.resolve_globals_mut(first, len as usize).as_mut_ptr();
+        Self { base, start, len }
+    }
+
+    /// Returns the pointer to the value of the flat global variable at `index` if any.
+    ///
+    /// # Note
+    ///
+    /// The returned pointer is invalidated whenever the [`CachedFlatGlobals`] are reloaded.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<*mut UntypedVal> {
+        let offset = index.wrapping_sub(self.start);
+        if offset >= self.len {
+            return None;
+        }
+        // Safety: flat global variables are stored without gaps in the store
+        //         and are never computed and `offset` is within their bounds.
+        Some(unsafe { GlobalEntity::untyped_ptr_raw(self.base.add(offset as usize)) })
+    }
+}
+
+/// Cached values of the recently used global variables at indices other than 0.
+///
+/// # Note
+///
+/// This is a direct mapped cache so that global variables with large indices
+/// are accessed nearly as efficiently as the global variable at index 0.
+#[derive(Debug)]
+pub struct CachedGlobals {
+    /// The cached entries indexed by the lowest bits of the global variable index.
+    entries: [CachedGlobalsEntry; CachedGlobals::LEN],
+    /// The current epoch of the [`CachedGlobals`].
+    ///
+    /// Entries of older epochs are stale which makes invalidation cheap.
+    epoch: u32,
+}
+
+/// An entry of the [`CachedGlobals`].
+#[derive(Debug, Copy, Clone)]
+struct CachedGlobalsEntry {
+    /// The index of the cached global variable.
+    index: u32,
+    /// The epoch in which the entry has been cached.
+    epoch: u32,
+    /// The pointer to the value of the cached global variable.
+    data: *mut UntypedVal,
+}
+
+impl CachedGlobalsEntry {
+    /// An entry that is stale in all epochs of the [`CachedGlobals`].
+    const STALE: Self = Self {
+        index: 0,
+        epoch: 0,
+        data: ptr::null_mut(),
+    };
+}
+
+impl Default for CachedGlobals {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            entries: [CachedGlobalsEntry::STALE; Self::LEN],
+            epoch: 1,
+        }
+    }
+}
+
+impl CachedGlobals {
+    /// The number of entries of the [`CachedGlobals`].
+    const LEN: usize = 16;
+
+    /// Returns the entry slot for the global variable at `index`.
+    #[inline]
+    fn slot(index: u32) -> usize {
+        index as usize % Self::LEN
+    }
+
+    /// Invalidates all entries of the [`CachedGlobals`].
+    ///
+    /// # Note
+    ///
+    /// Must be called whenever [`CachedGlobal`] is reloaded.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.epoch = self.epoch.wrapping_add(1);
+        if self.epoch == 0 {
+            // Note: upon wrap-around stale entries could become fresh again.
+            *self = Self::default();
+        }
+    }
+
+    /// Returns the pointer to the value of the cached global variable at `index` if any.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<*mut UntypedVal> {
+        let entry = &self.entries[Self::slot(index)];
+        if entry.index != index || entry.epoch != self.epoch {
+            return None;
+        }
+        Some(entry.data)
+    }
+
+    /// Caches the pointer `data` to the value of the global variable at `index`.
+    ///
+    /// # Note
+    ///
+    /// The values of computed global variables must not be cached.
+    #[inline]
+    pub fn insert(&mut self, index: u32, data: NonNull<UntypedVal>) {
+        self.entries[Self::slot(index)] = CachedGlobalsEntry {
+            index,
+            epoch: self.epoch,
+            data: data.as_ptr(),
+        };
+    }
+
+    /// Returns `true` if all fresh entries point to the global variables of `instance`.
+    #[cfg(feature = "checked-execution")]
+    fn is_consistent(&self, ctx: &mut StoreInner, instance: &InstanceEntity) -> bool {
+        self.entries
+            .iter()
+            .filter(|entry| entry.epoch == self.epoch)
+            .all(|entry| {
+                instance
+                    .get_global(entry.index)
+                    .and_then(|global| ctx.resolve_global_mut(&global).get_untyped_ptr())
+                    .is_some_and(|data| data.as_ptr() == entry.data)
+            })
+    }
+}
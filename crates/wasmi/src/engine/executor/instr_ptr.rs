@@ -47,6 +47,11 @@ impl InstructionPtr {
         self.ptr = unsafe { self.ptr.add(delta) };
     }
 
+    /// Returns the raw pointer to the currently pointed at [`Instruction`].
+    pub fn as_ptr(&self) -> *const Instruction {
+        self.ptr
+    }
+
     /// Returns a shared reference to the currently pointed at [`Instruction`].
     ///
     /// # Safety
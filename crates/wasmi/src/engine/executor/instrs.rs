@@ -9,7 +9,8 @@ use crate::{
         DedupFuncType,
         EngineFunc,
     },
-    ir::{index, BlockFuel, Const16, Instruction, Reg, ShiftAmount},
+    fuel_trap::{FuelTrap, FuelTrapFrame},
+    ir::{index, BlockFuel, Instruction, Reg, ShiftAmount},
     memory::DataSegment,
     store::StoreInner,
     table::ElementSegment,
@@ -21,6 +22,10 @@ use crate::{
     Store,
     Table,
 };
+use core::iter;
+
+#[cfg(not(feature = "compact-dispatch"))]
+use crate::ir::Const16;
 
 #[cfg(doc)]
 use crate::Instance;
@@ -28,9 +33,12 @@ use crate::Instance;
 mod binary;
 mod branch;
 mod call;
+#[cfg(feature = "checked-execution")]
+mod checks;
 mod comparison;
 mod conversion;
 mod copy;
+mod extension;
 mod global;
 mod load;
 mod memory;
@@ -111,15 +119,35 @@ impl<'engine> Executor<'engine> {
     }
 
     /// Executes the function frame until it returns or traps.
+    ///
+    /// Attaches the Wasm call frames to the [`Error`] if the execution traps.
     #[inline(always)]
     fn execute<T>(mut self, store: &mut Store<T>) -> Result<(), Error> {
+        self.execute_loop(store)
+            .map_err(|error| self.with_backtrace(&store.inner, error))
+    }
+
+    /// Executes the instructions of the function frame until it returns or traps.
+    #[inline(always)]
+    fn execute_loop<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
         use Instruction as Instr;
         loop {
+            #[cfg(feature = "checked-execution")]
+            self.check_invariants(&mut store.inner);
             match *self.ip.get() {
                 Instr::Trap { trap_code } => self.execute_trap(trap_code)?,
                 Instr::ConsumeFuel { block_fuel } => {
+                    #[cfg(feature = "time-travel")]
+                    forward_return!(self.pause_if_due(&store.inner));
+                    #[cfg(feature = "std")]
+                    if hint::unlikely(store.inner.interrupt_state().signals().is_pending()) {
+                        self.call_signal_handlers(store)?;
+                    }
                     self.execute_consume_fuel(&mut store.inner, block_fuel)?
                 }
+                Instr::CountLoopIteration { func } => {
+                    self.execute_count_loop_iteration(&mut store.inner, EngineFunc::from(func))
+                }
                 Instr::Return => {
                     forward_return!(self.execute_return(&mut store.inner))
                 }
@@ -132,12 +160,28 @@ impl<'engine> Executor<'engine> {
                 Instr::ReturnReg3 { values } => {
                     forward_return!(self.execute_return_reg3(&mut store.inner, values))
                 }
+                Instr::ReturnReg4 { values } => {
+                    forward_return!(self.execute_return_reg4(&mut store.inner, values))
+                }
+                Instr::ReturnReg5 { values } => {
+                    forward_return!(self.execute_return_reg5(&mut store.inner, values))
+                }
+                Instr::ReturnReg6 { values } => {
+                    forward_return!(self.execute_return_reg6(&mut store.inner, values))
+                }
+                Instr::ReturnReg7 { values } => {
+                    forward_return!(self.execute_return_reg7(&mut store.inner, values))
+                }
+                Instr::ReturnReg8 { values } => {
+                    forward_return!(self.execute_return_reg8(&mut store.inner, values))
+                }
                 Instr::ReturnImm32 { value } => {
                     forward_return!(self.execute_return_imm32(&mut store.inner, value))
                 }
                 Instr::ReturnI64Imm32 { value } => {
                     forward_return!(self.execute_return_i64imm32(&mut store.inner, value))
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::ReturnF64Imm32 { value } => {
                     forward_return!(self.execute_return_f64imm32(&mut store.inner, value))
                 }
@@ -174,6 +218,7 @@ impl<'engine> Executor<'engine> {
                         value
                     ))
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::ReturnNezF64Imm32 { condition, value } => {
                     forward_return!(self.execute_return_nez_f64imm32(
                         &mut store.inner,
@@ -349,27 +394,35 @@ impl<'engine> Executor<'engine> {
                 Instr::BranchI64LeUImm16Rhs { lhs, rhs, offset } => {
                     self.execute_branch_i64_le_u_imm16_rhs(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF32Eq { lhs, rhs, offset } => {
                     self.execute_branch_f32_eq(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF32Ne { lhs, rhs, offset } => {
                     self.execute_branch_f32_ne(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF32Lt { lhs, rhs, offset } => {
                     self.execute_branch_f32_lt(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF32Le { lhs, rhs, offset } => {
                     self.execute_branch_f32_le(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF64Eq { lhs, rhs, offset } => {
                     self.execute_branch_f64_eq(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF64Ne { lhs, rhs, offset } => {
                     self.execute_branch_f64_ne(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF64Lt { lhs, rhs, offset } => {
                     self.execute_branch_f64_lt(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::BranchF64Le { lhs, rhs, offset } => {
                     self.execute_branch_f64_le(lhs, rhs, offset)
                 }
@@ -377,6 +430,7 @@ impl<'engine> Executor<'engine> {
                 Instr::Copy2 { results, values } => self.execute_copy_2(results, values),
                 Instr::CopyImm32 { result, value } => self.execute_copy_imm32(result, value),
                 Instr::CopyI64Imm32 { result, value } => self.execute_copy_i64imm32(result, value),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::CopyF64Imm32 { result, value } => self.execute_copy_f64imm32(result, value),
                 Instr::CopySpan {
                     results,
@@ -430,6 +484,22 @@ impl<'engine> Executor<'engine> {
                 Instr::CallImported { results, func } => {
                     self.execute_call_imported::<T>(store, results, func)?
                 }
+                Instr::Extension0 {
+                    results,
+                    extension,
+                    len_results,
+                    opcode,
+                } => {
+                    self.execute_extension_0(&store.inner, results, extension, len_results, opcode)?
+                }
+                Instr::Extension {
+                    results,
+                    extension,
+                    len_results,
+                    opcode,
+                } => {
+                    self.execute_extension(&store.inner, results, extension, len_results, opcode)?
+                }
                 Instr::CallIndirect0 { results, func_type } => {
                     self.execute_call_indirect_0::<T>(store, results, func_type)?
                 }
@@ -453,16 +523,19 @@ impl<'engine> Executor<'engine> {
                     self.execute_select_i64imm32_lhs(result, lhs)
                 }
                 Instr::SelectI64Imm32 { result, lhs } => self.execute_select_i64imm32(result, lhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::SelectF64Imm32Rhs { result, lhs } => {
                     self.execute_select_f64imm32_rhs(result, lhs)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::SelectF64Imm32Lhs { result, lhs } => {
                     self.execute_select_f64imm32_lhs(result, lhs)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::SelectF64Imm32 { result, lhs } => self.execute_select_f64imm32(result, lhs),
                 Instr::RefFunc { result, func } => self.execute_ref_func(result, func),
                 Instr::GlobalGet { result, global } => {
-                    self.execute_global_get(&store.inner, result, global)
+                    self.execute_global_get(&mut store.inner, result, global)
                 }
                 Instr::GlobalSet { global, input } => {
                     self.execute_global_set(&mut store.inner, global, input)
@@ -474,132 +547,156 @@ impl<'engine> Executor<'engine> {
                     self.execute_global_set_i64imm16(&mut store.inner, global, input)
                 }
                 Instr::Load32 { result, memory } => {
-                    self.execute_load32(&store.inner, result, memory)?
+                    self.execute_load32(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Load32At { result, address } => {
-                    self.execute_load32_at(&store.inner, result, address)?
+                    self.execute_load32_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Load32Offset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_load32_offset16(result, ptr, offset)?,
                 Instr::Load64 { result, memory } => {
-                    self.execute_load64(&store.inner, result, memory)?
+                    self.execute_load64(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Load64At { result, address } => {
-                    self.execute_load64_at(&store.inner, result, address)?
+                    self.execute_load64_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Load64Offset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_load64_offset16(result, ptr, offset)?,
                 Instr::I32Load8s { result, memory } => {
-                    self.execute_i32_load8_s(&store.inner, result, memory)?
+                    self.execute_i32_load8_s(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load8sAt { result, address } => {
-                    self.execute_i32_load8_s_at(&store.inner, result, address)?
+                    self.execute_i32_load8_s_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load8sOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i32_load8_s_offset16(result, ptr, offset)?,
                 Instr::I32Load8u { result, memory } => {
-                    self.execute_i32_load8_u(&store.inner, result, memory)?
+                    self.execute_i32_load8_u(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load8uAt { result, address } => {
-                    self.execute_i32_load8_u_at(&store.inner, result, address)?
+                    self.execute_i32_load8_u_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load8uOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i32_load8_u_offset16(result, ptr, offset)?,
                 Instr::I32Load16s { result, memory } => {
-                    self.execute_i32_load16_s(&store.inner, result, memory)?
+                    self.execute_i32_load16_s(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load16sAt { result, address } => {
-                    self.execute_i32_load16_s_at(&store.inner, result, address)?
+                    self.execute_i32_load16_s_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load16sOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i32_load16_s_offset16(result, ptr, offset)?,
                 Instr::I32Load16u { result, memory } => {
-                    self.execute_i32_load16_u(&store.inner, result, memory)?
+                    self.execute_i32_load16_u(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load16uAt { result, address } => {
-                    self.execute_i32_load16_u_at(&store.inner, result, address)?
+                    self.execute_i32_load16_u_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Load16uOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i32_load16_u_offset16(result, ptr, offset)?,
                 Instr::I64Load8s { result, memory } => {
-                    self.execute_i64_load8_s(&store.inner, result, memory)?
+                    self.execute_i64_load8_s(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load8sAt { result, address } => {
-                    self.execute_i64_load8_s_at(&store.inner, result, address)?
+                    self.execute_i64_load8_s_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load8sOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i64_load8_s_offset16(result, ptr, offset)?,
                 Instr::I64Load8u { result, memory } => {
-                    self.execute_i64_load8_u(&store.inner, result, memory)?
+                    self.execute_i64_load8_u(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load8uAt { result, address } => {
-                    self.execute_i64_load8_u_at(&store.inner, result, address)?
+                    self.execute_i64_load8_u_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load8uOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i64_load8_u_offset16(result, ptr, offset)?,
                 Instr::I64Load16s { result, memory } => {
-                    self.execute_i64_load16_s(&store.inner, result, memory)?
+                    self.execute_i64_load16_s(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load16sAt { result, address } => {
-                    self.execute_i64_load16_s_at(&store.inner, result, address)?
+                    self.execute_i64_load16_s_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load16sOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i64_load16_s_offset16(result, ptr, offset)?,
                 Instr::I64Load16u { result, memory } => {
-                    self.execute_i64_load16_u(&store.inner, result, memory)?
+                    self.execute_i64_load16_u(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load16uAt { result, address } => {
-                    self.execute_i64_load16_u_at(&store.inner, result, address)?
+                    self.execute_i64_load16_u_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load16uOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i64_load16_u_offset16(result, ptr, offset)?,
                 Instr::I64Load32s { result, memory } => {
-                    self.execute_i64_load32_s(&store.inner, result, memory)?
+                    self.execute_i64_load32_s(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load32sAt { result, address } => {
-                    self.execute_i64_load32_s_at(&store.inner, result, address)?
+                    self.execute_i64_load32_s_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load32sOffset16 {
                     result,
                     ptr,
                     offset,
                 } => self.execute_i64_load32_s_offset16(result, ptr, offset)?,
                 Instr::I64Load32u { result, memory } => {
-                    self.execute_i64_load32_u(&store.inner, result, memory)?
+                    self.execute_i64_load32_u(&mut store.inner, result, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load32uAt { result, address } => {
-                    self.execute_i64_load32_u_at(&store.inner, result, address)?
+                    self.execute_i64_load32_u_at(&mut store.inner, result, address)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Load32uOffset16 {
                     result,
                     ptr,
@@ -608,27 +705,33 @@ impl<'engine> Executor<'engine> {
                 Instr::Store32 { ptr, memory } => {
                     self.execute_store32(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Store32Offset16 { ptr, offset, value } => {
                     self.execute_store32_offset16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Store32At { address, value } => {
                     self.execute_store32_at(&mut store.inner, address, value)?
                 }
                 Instr::Store64 { ptr, memory } => {
                     self.execute_store64(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Store64Offset16 { ptr, offset, value } => {
                     self.execute_store64_offset16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::Store64At { address, value } => {
                     self.execute_store64_at(&mut store.inner, address, value)?
                 }
                 Instr::I32StoreImm16 { ptr, memory } => {
                     self.execute_i32_store_imm16(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32StoreOffset16Imm16 { ptr, offset, value } => {
                     self.execute_i32_store_offset16_imm16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32StoreAtImm16 { address, value } => {
                     self.execute_i32_store_at_imm16(&mut store.inner, address, value)?
                 }
@@ -638,15 +741,19 @@ impl<'engine> Executor<'engine> {
                 Instr::I32Store8Imm { ptr, memory } => {
                     self.execute_i32_store8_imm(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store8Offset16 { ptr, offset, value } => {
                     self.execute_i32_store8_offset16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store8Offset16Imm { ptr, offset, value } => {
                     self.execute_i32_store8_offset16_imm(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store8At { address, value } => {
                     self.execute_i32_store8_at(&mut store.inner, address, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store8AtImm { address, value } => {
                     self.execute_i32_store8_at_imm(&mut store.inner, address, value)?
                 }
@@ -656,24 +763,30 @@ impl<'engine> Executor<'engine> {
                 Instr::I32Store16Imm { ptr, memory } => {
                     self.execute_i32_store16_imm(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store16Offset16 { ptr, offset, value } => {
                     self.execute_i32_store16_offset16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store16Offset16Imm { ptr, offset, value } => {
                     self.execute_i32_store16_offset16_imm(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store16At { address, value } => {
                     self.execute_i32_store16_at(&mut store.inner, address, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32Store16AtImm { address, value } => {
                     self.execute_i32_store16_at_imm(&mut store.inner, address, value)?
                 }
                 Instr::I64StoreImm16 { ptr, memory } => {
                     self.execute_i64_store_imm16(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64StoreOffset16Imm16 { ptr, offset, value } => {
                     self.execute_i64_store_offset16_imm16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64StoreAtImm16 { address, value } => {
                     self.execute_i64_store_at_imm16(&mut store.inner, address, value)?
                 }
@@ -683,15 +796,19 @@ impl<'engine> Executor<'engine> {
                 Instr::I64Store8Imm { ptr, memory } => {
                     self.execute_i64_store8_imm(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store8Offset16 { ptr, offset, value } => {
                     self.execute_i64_store8_offset16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store8Offset16Imm { ptr, offset, value } => {
                     self.execute_i64_store8_offset16_imm(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store8At { address, value } => {
                     self.execute_i64_store8_at(&mut store.inner, address, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store8AtImm { address, value } => {
                     self.execute_i64_store8_at_imm(&mut store.inner, address, value)?
                 }
@@ -701,15 +818,19 @@ impl<'engine> Executor<'engine> {
                 Instr::I64Store16Imm { ptr, memory } => {
                     self.execute_i64_store16_imm(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store16Offset16 { ptr, offset, value } => {
                     self.execute_i64_store16_offset16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store16Offset16Imm { ptr, offset, value } => {
                     self.execute_i64_store16_offset16_imm(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store16At { address, value } => {
                     self.execute_i64_store16_at(&mut store.inner, address, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store16AtImm { address, value } => {
                     self.execute_i64_store16_at_imm(&mut store.inner, address, value)?
                 }
@@ -719,167 +840,229 @@ impl<'engine> Executor<'engine> {
                 Instr::I64Store32Imm16 { ptr, memory } => {
                     self.execute_i64_store32_imm16(&mut store.inner, ptr, memory)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store32Offset16 { ptr, offset, value } => {
                     self.execute_i64_store32_offset16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store32Offset16Imm16 { ptr, offset, value } => {
                     self.execute_i64_store32_offset16_imm16(ptr, offset, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store32At { address, value } => {
                     self.execute_i64_store32_at(&mut store.inner, address, value)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64Store32AtImm16 { address, value } => {
                     self.execute_i64_store32_at_imm16(&mut store.inner, address, value)?
                 }
                 Instr::I32Eq { result, lhs, rhs } => self.execute_i32_eq(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32EqImm16 { result, lhs, rhs } => {
                     self.execute_i32_eq_imm16(result, lhs, rhs)
                 }
                 Instr::I32Ne { result, lhs, rhs } => self.execute_i32_ne(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32NeImm16 { result, lhs, rhs } => {
                     self.execute_i32_ne_imm16(result, lhs, rhs)
                 }
                 Instr::I32LtS { result, lhs, rhs } => self.execute_i32_lt_s(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LtSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_lt_s_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LtSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_lt_s_imm16_rhs(result, lhs, rhs)
                 }
                 Instr::I32LtU { result, lhs, rhs } => self.execute_i32_lt_u(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LtUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_lt_u_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LtUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_lt_u_imm16_rhs(result, lhs, rhs)
                 }
                 Instr::I32LeS { result, lhs, rhs } => self.execute_i32_le_s(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LeSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_le_s_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LeSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_le_s_imm16_rhs(result, lhs, rhs)
                 }
                 Instr::I32LeU { result, lhs, rhs } => self.execute_i32_le_u(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LeUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_le_u_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32LeUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_le_u_imm16_rhs(result, lhs, rhs)
                 }
                 Instr::I64Eq { result, lhs, rhs } => self.execute_i64_eq(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64EqImm16 { result, lhs, rhs } => {
                     self.execute_i64_eq_imm16(result, lhs, rhs)
                 }
                 Instr::I64Ne { result, lhs, rhs } => self.execute_i64_ne(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64NeImm16 { result, lhs, rhs } => {
                     self.execute_i64_ne_imm16(result, lhs, rhs)
                 }
                 Instr::I64LtS { result, lhs, rhs } => self.execute_i64_lt_s(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LtSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_lt_s_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LtSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_lt_s_imm16_rhs(result, lhs, rhs)
                 }
                 Instr::I64LtU { result, lhs, rhs } => self.execute_i64_lt_u(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LtUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_lt_u_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LtUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_lt_u_imm16_rhs(result, lhs, rhs)
                 }
                 Instr::I64LeS { result, lhs, rhs } => self.execute_i64_le_s(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LeSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_le_s_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LeSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_le_s_imm16_rhs(result, lhs, rhs)
                 }
                 Instr::I64LeU { result, lhs, rhs } => self.execute_i64_le_u(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LeUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_le_u_imm16_lhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64LeUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_le_u_imm16_rhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Eq { result, lhs, rhs } => self.execute_f32_eq(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Ne { result, lhs, rhs } => self.execute_f32_ne(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Lt { result, lhs, rhs } => self.execute_f32_lt(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Le { result, lhs, rhs } => self.execute_f32_le(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Eq { result, lhs, rhs } => self.execute_f64_eq(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Ne { result, lhs, rhs } => self.execute_f64_ne(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Lt { result, lhs, rhs } => self.execute_f64_lt(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Le { result, lhs, rhs } => self.execute_f64_le(result, lhs, rhs),
                 Instr::I32Clz { result, input } => self.execute_i32_clz(result, input),
                 Instr::I32Ctz { result, input } => self.execute_i32_ctz(result, input),
                 Instr::I32Popcnt { result, input } => self.execute_i32_popcnt(result, input),
                 Instr::I32Add { result, lhs, rhs } => self.execute_i32_add(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32AddImm16 { result, lhs, rhs } => {
                     self.execute_i32_add_imm16(result, lhs, rhs)
                 }
                 Instr::I32Sub { result, lhs, rhs } => self.execute_i32_sub(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32SubImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_sub_imm16_lhs(result, lhs, rhs)
                 }
                 Instr::I32Mul { result, lhs, rhs } => self.execute_i32_mul(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32MulImm16 { result, lhs, rhs } => {
                     self.execute_i32_mul_imm16(result, lhs, rhs)
                 }
                 Instr::I32DivS { result, lhs, rhs } => self.execute_i32_div_s(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32DivSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_div_s_imm16_rhs(result, lhs, rhs)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32DivSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_div_s_imm16_lhs(result, lhs, rhs)?
                 }
                 Instr::I32DivU { result, lhs, rhs } => self.execute_i32_div_u(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32DivUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_div_u_imm16_rhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32DivUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_div_u_imm16_lhs(result, lhs, rhs)?
                 }
                 Instr::I32RemS { result, lhs, rhs } => self.execute_i32_rem_s(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32RemSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_rem_s_imm16_rhs(result, lhs, rhs)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32RemSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_rem_s_imm16_lhs(result, lhs, rhs)?
                 }
                 Instr::I32RemU { result, lhs, rhs } => self.execute_i32_rem_u(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32RemUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i32_rem_u_imm16_rhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32RemUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i32_rem_u_imm16_lhs(result, lhs, rhs)?
                 }
+                Instr::I32DivSSat { result, lhs, rhs } => {
+                    self.execute_i32_div_s_sat(result, lhs, rhs)
+                }
+                Instr::I32DivUSat { result, lhs, rhs } => {
+                    self.execute_i32_div_u_sat(result, lhs, rhs)
+                }
+                Instr::I32RemSSat { result, lhs, rhs } => {
+                    self.execute_i32_rem_s_sat(result, lhs, rhs)
+                }
+                Instr::I32RemUSat { result, lhs, rhs } => {
+                    self.execute_i32_rem_u_sat(result, lhs, rhs)
+                }
                 Instr::I32And { result, lhs, rhs } => self.execute_i32_and(result, lhs, rhs),
                 Instr::I32AndEqz { result, lhs, rhs } => self.execute_i32_and_eqz(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32AndEqzImm16 { result, lhs, rhs } => {
                     self.execute_i32_and_eqz_imm16(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32AndImm16 { result, lhs, rhs } => {
                     self.execute_i32_and_imm16(result, lhs, rhs)
                 }
                 Instr::I32Or { result, lhs, rhs } => self.execute_i32_or(result, lhs, rhs),
                 Instr::I32OrEqz { result, lhs, rhs } => self.execute_i32_or_eqz(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32OrEqzImm16 { result, lhs, rhs } => {
                     self.execute_i32_or_eqz_imm16(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32OrImm16 { result, lhs, rhs } => {
                     self.execute_i32_or_imm16(result, lhs, rhs)
                 }
                 Instr::I32Xor { result, lhs, rhs } => self.execute_i32_xor(result, lhs, rhs),
                 Instr::I32XorEqz { result, lhs, rhs } => self.execute_i32_xor_eqz(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32XorEqzImm16 { result, lhs, rhs } => {
                     self.execute_i32_xor_eqz_imm16(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32XorImm16 { result, lhs, rhs } => {
                     self.execute_i32_xor_imm16(result, lhs, rhs)
                 }
                 Instr::I32Shl { result, lhs, rhs } => self.execute_i32_shl(result, lhs, rhs),
                 Instr::I32ShlBy { result, lhs, rhs } => self.execute_i32_shl_by(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32ShlImm16 { result, lhs, rhs } => {
                     self.execute_i32_shl_imm16(result, lhs, rhs)
                 }
@@ -887,6 +1070,7 @@ impl<'engine> Executor<'engine> {
                 Instr::I32ShrUBy { result, lhs, rhs } => {
                     self.execute_i32_shr_u_by(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32ShrUImm16 { result, lhs, rhs } => {
                     self.execute_i32_shr_u_imm16(result, lhs, rhs)
                 }
@@ -894,16 +1078,19 @@ impl<'engine> Executor<'engine> {
                 Instr::I32ShrSBy { result, lhs, rhs } => {
                     self.execute_i32_shr_s_by(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32ShrSImm16 { result, lhs, rhs } => {
                     self.execute_i32_shr_s_imm16(result, lhs, rhs)
                 }
                 Instr::I32Rotl { result, lhs, rhs } => self.execute_i32_rotl(result, lhs, rhs),
                 Instr::I32RotlBy { result, lhs, rhs } => self.execute_i32_rotl_by(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32RotlImm16 { result, lhs, rhs } => {
                     self.execute_i32_rotl_imm16(result, lhs, rhs)
                 }
                 Instr::I32Rotr { result, lhs, rhs } => self.execute_i32_rotr(result, lhs, rhs),
                 Instr::I32RotrBy { result, lhs, rhs } => self.execute_i32_rotr_by(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I32RotrImm16 { result, lhs, rhs } => {
                     self.execute_i32_rotr_imm16(result, lhs, rhs)
                 }
@@ -911,59 +1098,86 @@ impl<'engine> Executor<'engine> {
                 Instr::I64Ctz { result, input } => self.execute_i64_ctz(result, input),
                 Instr::I64Popcnt { result, input } => self.execute_i64_popcnt(result, input),
                 Instr::I64Add { result, lhs, rhs } => self.execute_i64_add(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64AddImm16 { result, lhs, rhs } => {
                     self.execute_i64_add_imm16(result, lhs, rhs)
                 }
                 Instr::I64Sub { result, lhs, rhs } => self.execute_i64_sub(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64SubImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_sub_imm16_lhs(result, lhs, rhs)
                 }
                 Instr::I64Mul { result, lhs, rhs } => self.execute_i64_mul(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64MulImm16 { result, lhs, rhs } => {
                     self.execute_i64_mul_imm16(result, lhs, rhs)
                 }
                 Instr::I64DivS { result, lhs, rhs } => self.execute_i64_div_s(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64DivSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_div_s_imm16_rhs(result, lhs, rhs)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64DivSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_div_s_imm16_lhs(result, lhs, rhs)?
                 }
                 Instr::I64DivU { result, lhs, rhs } => self.execute_i64_div_u(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64DivUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_div_u_imm16_rhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64DivUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_div_u_imm16_lhs(result, lhs, rhs)?
                 }
                 Instr::I64RemS { result, lhs, rhs } => self.execute_i64_rem_s(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64RemSImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_rem_s_imm16_rhs(result, lhs, rhs)?
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64RemSImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_rem_s_imm16_lhs(result, lhs, rhs)?
                 }
                 Instr::I64RemU { result, lhs, rhs } => self.execute_i64_rem_u(result, lhs, rhs)?,
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64RemUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_rem_u_imm16_rhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64RemUImm16Lhs { result, lhs, rhs } => {
                     self.execute_i64_rem_u_imm16_lhs(result, lhs, rhs)?
                 }
+                Instr::I64DivSSat { result, lhs, rhs } => {
+                    self.execute_i64_div_s_sat(result, lhs, rhs)
+                }
+                Instr::I64DivUSat { result, lhs, rhs } => {
+                    self.execute_i64_div_u_sat(result, lhs, rhs)
+                }
+                Instr::I64RemSSat { result, lhs, rhs } => {
+                    self.execute_i64_rem_s_sat(result, lhs, rhs)
+                }
+                Instr::I64RemUSat { result, lhs, rhs } => {
+                    self.execute_i64_rem_u_sat(result, lhs, rhs)
+                }
                 Instr::I64And { result, lhs, rhs } => self.execute_i64_and(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64AndImm16 { result, lhs, rhs } => {
                     self.execute_i64_and_imm16(result, lhs, rhs)
                 }
                 Instr::I64Or { result, lhs, rhs } => self.execute_i64_or(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64OrImm16 { result, lhs, rhs } => {
                     self.execute_i64_or_imm16(result, lhs, rhs)
                 }
                 Instr::I64Xor { result, lhs, rhs } => self.execute_i64_xor(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64XorImm16 { result, lhs, rhs } => {
                     self.execute_i64_xor_imm16(result, lhs, rhs)
                 }
                 Instr::I64Shl { result, lhs, rhs } => self.execute_i64_shl(result, lhs, rhs),
                 Instr::I64ShlBy { result, lhs, rhs } => self.execute_i64_shl_by(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64ShlImm16 { result, lhs, rhs } => {
                     self.execute_i64_shl_imm16(result, lhs, rhs)
                 }
@@ -971,6 +1185,7 @@ impl<'engine> Executor<'engine> {
                 Instr::I64ShrUBy { result, lhs, rhs } => {
                     self.execute_i64_shr_u_by(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64ShrUImm16 { result, lhs, rhs } => {
                     self.execute_i64_shr_u_imm16(result, lhs, rhs)
                 }
@@ -978,16 +1193,19 @@ impl<'engine> Executor<'engine> {
                 Instr::I64ShrSBy { result, lhs, rhs } => {
                     self.execute_i64_shr_s_by(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64ShrSImm16 { result, lhs, rhs } => {
                     self.execute_i64_shr_s_imm16(result, lhs, rhs)
                 }
                 Instr::I64Rotl { result, lhs, rhs } => self.execute_i64_rotl(result, lhs, rhs),
                 Instr::I64RotlBy { result, lhs, rhs } => self.execute_i64_rotl_by(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64RotlImm16 { result, lhs, rhs } => {
                     self.execute_i64_rotl_imm16(result, lhs, rhs)
                 }
                 Instr::I64Rotr { result, lhs, rhs } => self.execute_i64_rotr(result, lhs, rhs),
                 Instr::I64RotrBy { result, lhs, rhs } => self.execute_i64_rotr_by(result, lhs, rhs),
+                #[cfg(not(feature = "compact-dispatch"))]
                 Instr::I64RotrImm16 { result, lhs, rhs } => {
                     self.execute_i64_rotr_imm16(result, lhs, rhs)
                 }
@@ -997,117 +1215,173 @@ impl<'engine> Executor<'engine> {
                 Instr::I64Extend8S { result, input } => self.execute_i64_extend8_s(result, input),
                 Instr::I64Extend16S { result, input } => self.execute_i64_extend16_s(result, input),
                 Instr::I64Extend32S { result, input } => self.execute_i64_extend32_s(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Abs { result, input } => self.execute_f32_abs(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Neg { result, input } => self.execute_f32_neg(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Ceil { result, input } => self.execute_f32_ceil(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Floor { result, input } => self.execute_f32_floor(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Trunc { result, input } => self.execute_f32_trunc(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Nearest { result, input } => self.execute_f32_nearest(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Sqrt { result, input } => self.execute_f32_sqrt(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Add { result, lhs, rhs } => self.execute_f32_add(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Sub { result, lhs, rhs } => self.execute_f32_sub(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Mul { result, lhs, rhs } => self.execute_f32_mul(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Div { result, lhs, rhs } => self.execute_f32_div(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Min { result, lhs, rhs } => self.execute_f32_min(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Max { result, lhs, rhs } => self.execute_f32_max(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32Copysign { result, lhs, rhs } => {
                     self.execute_f32_copysign(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32CopysignImm { result, lhs, rhs } => {
                     self.execute_f32_copysign_imm(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Abs { result, input } => self.execute_f64_abs(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Neg { result, input } => self.execute_f64_neg(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Ceil { result, input } => self.execute_f64_ceil(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Floor { result, input } => self.execute_f64_floor(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Trunc { result, input } => self.execute_f64_trunc(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Nearest { result, input } => self.execute_f64_nearest(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Sqrt { result, input } => self.execute_f64_sqrt(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Add { result, lhs, rhs } => self.execute_f64_add(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Sub { result, lhs, rhs } => self.execute_f64_sub(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Mul { result, lhs, rhs } => self.execute_f64_mul(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Div { result, lhs, rhs } => self.execute_f64_div(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Min { result, lhs, rhs } => self.execute_f64_min(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Max { result, lhs, rhs } => self.execute_f64_max(result, lhs, rhs),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64Copysign { result, lhs, rhs } => {
                     self.execute_f64_copysign(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64CopysignImm { result, lhs, rhs } => {
                     self.execute_f64_copysign_imm(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncF32S { result, input } => {
                     self.execute_i32_trunc_f32_s(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncF32U { result, input } => {
                     self.execute_i32_trunc_f32_u(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncF64S { result, input } => {
                     self.execute_i32_trunc_f64_s(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncF64U { result, input } => {
                     self.execute_i32_trunc_f64_u(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncF32S { result, input } => {
                     self.execute_i64_trunc_f32_s(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncF32U { result, input } => {
                     self.execute_i64_trunc_f32_u(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncF64S { result, input } => {
                     self.execute_i64_trunc_f64_s(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncF64U { result, input } => {
                     self.execute_i64_trunc_f64_u(result, input)?
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncSatF32S { result, input } => {
                     self.execute_i32_trunc_sat_f32_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncSatF32U { result, input } => {
                     self.execute_i32_trunc_sat_f32_u(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncSatF64S { result, input } => {
                     self.execute_i32_trunc_sat_f64_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I32TruncSatF64U { result, input } => {
                     self.execute_i32_trunc_sat_f64_u(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncSatF32S { result, input } => {
                     self.execute_i64_trunc_sat_f32_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncSatF32U { result, input } => {
                     self.execute_i64_trunc_sat_f32_u(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncSatF64S { result, input } => {
                     self.execute_i64_trunc_sat_f64_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::I64TruncSatF64U { result, input } => {
                     self.execute_i64_trunc_sat_f64_u(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32DemoteF64 { result, input } => self.execute_f32_demote_f64(result, input),
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64PromoteF32 { result, input } => {
                     self.execute_f64_promote_f32(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32ConvertI32S { result, input } => {
                     self.execute_f32_convert_i32_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32ConvertI32U { result, input } => {
                     self.execute_f32_convert_i32_u(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32ConvertI64S { result, input } => {
                     self.execute_f32_convert_i64_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F32ConvertI64U { result, input } => {
                     self.execute_f32_convert_i64_u(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64ConvertI32S { result, input } => {
                     self.execute_f64_convert_i32_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64ConvertI32U { result, input } => {
                     self.execute_f64_convert_i32_u(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64ConvertI64S { result, input } => {
                     self.execute_f64_convert_i64_s(result, input)
                 }
+                #[cfg(not(feature = "no-floats"))]
                 Instr::F64ConvertI64U { result, input } => {
                     self.execute_f64_convert_i64_u(result, input)
                 }
@@ -1297,6 +1571,8 @@ impl<'engine> Executor<'engine> {
                 | Instr::RegisterList { .. }
                 | Instr::CallIndirectParams { .. }
                 | Instr::CallIndirectParamsImm16 { .. } => self.invalid_instruction_word()?,
+                #[cfg(any(feature = "no-floats", feature = "compact-dispatch"))]
+                _ => self.unsupported_instruction()?,
             }
         }
     }
@@ -1350,6 +1626,8 @@ impl Executor<'_> {
 
     /// Returns the [`Reg`] value.
     fn get_register(&self, register: Reg) -> UntypedVal {
+        #[cfg(feature = "checked-execution")]
+        self.check_register(register);
         // Safety: - It is the responsibility of the `Executor`
         //           implementation to keep the `sp` pointer valid
         //           whenever this method is accessed.
@@ -1368,6 +1646,8 @@ impl Executor<'_> {
 
     /// Sets the [`Reg`] value to `value`.
     fn set_register(&mut self, register: Reg, value: impl Into<UntypedVal>) {
+        #[cfg(feature = "checked-execution")]
+        self.check_register(register);
         // Safety: - It is the responsibility of the `Executor`
         //           implementation to keep the `sp` pointer valid
         //           whenever this method is accessed.
@@ -1462,6 +1742,7 @@ impl Executor<'_> {
 
     /// Executes a fallible generic unary [`Instruction`].
     #[inline(always)]
+    #[cfg(not(feature = "no-floats"))]
     fn try_execute_unary(
         &mut self,
         result: Reg,
@@ -1489,6 +1770,7 @@ impl Executor<'_> {
     }
 
     /// Executes a generic binary [`Instruction`].
+    #[cfg(not(feature = "compact-dispatch"))]
     #[inline(always)]
     fn execute_binary_imm16_rhs<T>(
         &mut self,
@@ -1507,6 +1789,7 @@ impl Executor<'_> {
     }
 
     /// Executes a generic binary [`Instruction`] with reversed operands.
+    #[cfg(not(feature = "compact-dispatch"))]
     #[inline(always)]
     fn execute_binary_imm16_lhs<T>(
         &mut self,
@@ -1558,6 +1841,7 @@ impl Executor<'_> {
     }
 
     /// Executes a fallible generic binary [`Instruction`].
+    #[cfg(not(feature = "compact-dispatch"))]
     #[inline(always)]
     fn try_execute_divrem_imm16_rhs<NonZeroT>(
         &mut self,
@@ -1576,6 +1860,7 @@ impl Executor<'_> {
     }
 
     /// Executes a fallible generic binary [`Instruction`].
+    #[cfg(not(feature = "compact-dispatch"))]
     #[inline(always)]
     fn execute_divrem_imm16_rhs<NonZeroT>(
         &mut self,
@@ -1593,6 +1878,7 @@ impl Executor<'_> {
     }
 
     /// Executes a fallible generic binary [`Instruction`] with reversed operands.
+    #[cfg(not(feature = "compact-dispatch"))]
     #[inline(always)]
     fn try_execute_binary_imm16_lhs<T>(
         &mut self,
@@ -1632,6 +1918,7 @@ impl Executor<'_> {
     ///
     /// - Returns the default [`index::Memory`] if the parameter is missing.
     /// - Bumps `self.ip` if a [`Instruction::MemoryIndex`] parameter was found.
+    #[cfg(not(feature = "compact-dispatch"))]
     #[inline(always)]
     fn fetch_optional_memory(&mut self) -> index::Memory {
         let mut addr: InstructionPtr = self.ip;
@@ -1664,6 +1951,21 @@ impl Executor<'_> {
         }
     }
 
+    /// Used for all [`Instruction`] variants that are compiled out by the enabled crate features.
+    ///
+    /// # Note
+    ///
+    /// The Wasmi translator never produces those [`Instruction`] variants, however,
+    /// users of the `IrFuncBuilder` may. Therefore this traps instead of being unreachable.
+    #[cold]
+    #[cfg(any(feature = "no-floats", feature = "compact-dispatch"))]
+    fn unsupported_instruction(&mut self) -> Result<(), Error> {
+        Err(Error::new(alloc::format!(
+            "instruction is not supported by the enabled crate features: {:?}",
+            self.ip.get()
+        )))
+    }
+
     /// Executes a Wasm `unreachable` instruction.
     fn execute_trap(&mut self, trap_code: TrapCode) -> Result<(), Error> {
         Err(Error::from(trap_code))
@@ -1678,12 +1980,126 @@ impl Executor<'_> {
         // We do not have to check if fuel metering is enabled since
         // [`Instruction::ConsumeFuel`] are only generated if fuel metering
         // is enabled to begin with.
-        store
+        if store
             .fuel_mut()
-            .consume_fuel_unchecked(block_fuel.to_u64())?;
+            .consume_fuel_unchecked(block_fuel.to_u64())
+            .is_err()
+        {
+            return Err(self.out_of_fuel(store));
+        }
+        #[cfg(feature = "fuel-profile")]
+        self.record_fuel_profile(store, block_fuel);
         self.try_next_instr()
     }
 
+    /// Pauses the execution before the current instruction if the [`StoreInner`] asks for it.
+    ///
+    /// The paused execution is resumed by executing the [`Stack`] once again.
+    #[cfg(feature = "time-travel")]
+    #[inline(always)]
+    fn pause_if_due(&mut self, store: &StoreInner) -> ControlFlow {
+        if hint::likely(!store.is_pause_due()) {
+            return ControlFlow::Continue(());
+        }
+        self.stack
+            .calls
+            .peek_mut()
+            .expect("must have a call frame on the call stack")
+            .update_instr_ptr(self.ip);
+        ControlFlow::Break(())
+    }
+
+    /// Calls the guest functions raised via the [`SignalHandle`] of the [`Store`].
+    ///
+    /// [`SignalHandle`]: crate::SignalHandle
+    #[cfg(feature = "std")]
+    #[cold]
+    fn call_signal_handlers<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
+        let signals = store.inner.interrupt_state().signals().clone();
+        while let Some(handler) = signals.take() {
+            if !store.inner.owns_func(&handler) {
+                return Err(Error::new(
+                    "raised signal handler does not belong to the store",
+                ));
+            }
+            handler.call(&mut *store, &[], &mut [])?;
+        }
+        // Signal handlers may have changed the cached instance data, e.g. by growing memory.
+        let instance = *self.stack.calls.instance_expect();
+        self.cache.update(&mut store.inner, &instance);
+        Ok(())
+    }
+
+    /// Prepares the [`Stack`] for resumption after running out of fuel and returns the [`FuelTrap`] error.
+    #[cold]
+    fn out_of_fuel(&mut self, store: &StoreInner) -> Error {
+        self.stack
+            .calls
+            .peek_mut()
+            .expect("must have a call frame on the call stack")
+            .update_instr_ptr(self.ip);
+        self.stack.out_of_fuel = true;
+        let frames = match store.engine().config().get_wasm_backtrace() {
+            true => self.stack.calls.frames(),
+            false => &[],
+        };
+        let frames = frames
+            .iter()
+            .rev()
+            .filter_map(|frame| self.code_map.find_instr(frame.instr_ptr().as_ptr()));
+        Error::from(FuelTrap::new(store, frames, self.stack.calls.len()))
+    }
+
+    /// Attaches the Wasm call frames to `error` if it is a [`TrapCode`] without backtrace.
+    ///
+    /// Does nothing unless capturing backtraces is enabled via [`Config::wasm_backtrace`].
+    ///
+    /// [`Config::wasm_backtrace`]: crate::Config::wasm_backtrace
+    #[cold]
+    fn with_backtrace(&self, store: &StoreInner, mut error: Error) -> Error {
+        if !store.engine().config().get_wasm_backtrace() || !error.needs_backtrace() {
+            return error;
+        }
+        // Note: the innermost call frame has not yet stored its instruction pointer.
+        let callers = self.stack.calls.frames().iter().rev().skip(1);
+        let frames = iter::once(self.ip.as_ptr())
+            .chain(callers.map(|frame| frame.instr_ptr().as_ptr()))
+            .filter_map(|ip| self.code_map.find_instr(ip));
+        error.set_backtrace(FuelTrapFrame::capture(store, frames));
+        error
+    }
+
+    /// Attributes the consumed `block_fuel` to the currently executed function.
+    #[cfg(feature = "fuel-profile")]
+    fn record_fuel_profile(&self, store: &mut StoreInner, block_fuel: BlockFuel) {
+        let func = self
+            .stack
+            .calls
+            .peek()
+            .expect("must have a call frame on the call stack")
+            .func();
+        store.fuel_profile_mut().record(func, block_fuel.to_u64());
+    }
+
+    /// Executes an [`Instruction::CountLoopIteration`].
+    fn execute_count_loop_iteration(&mut self, store: &mut StoreInner, func: EngineFunc) {
+        let mut addr: InstructionPtr = self.ip;
+        addr.add(1);
+        let index = match *addr.get() {
+            Instruction::Const32 { value } => u32::from(value),
+            unexpected => {
+                // Safety: Wasmi translation guarantees that [`Instruction::Const32`] exists.
+                unsafe {
+                    unreachable_unchecked!(
+                        "expected `Instruction::Const32` but found: {unexpected:?}"
+                    )
+                }
+            }
+        };
+        store.loop_counters_mut().record(func, index);
+        self.next_instr_at(2);
+    }
+
     /// Executes an [`Instruction::RefFunc`].
     fn execute_ref_func(&mut self, result: Reg, func_index: index::Func) {
         let func = self.get_func(func_index);
@@ -1,11 +1,18 @@
 use super::{Executor, UntypedValueExt};
 use crate::{
-    core::{TrapCode, UntypedVal},
-    ir::{Const16, Reg, ShiftAmount, Sign},
+    core::UntypedVal,
+    ir::{Reg, ShiftAmount},
     Error,
 };
+
+#[cfg(not(feature = "compact-dispatch"))]
+use crate::{core::TrapCode, ir::Const16};
+#[cfg(not(feature = "compact-dispatch"))]
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
 
+#[cfg(not(feature = "no-floats"))]
+use crate::ir::Sign;
+
 #[cfg(doc)]
 use crate::ir::Instruction;
 
@@ -30,6 +37,10 @@ impl Executor<'_> {
         (Instruction::I32OrEqz, execute_i32_or_eqz, UntypedVal::i32_or_eqz),
         (Instruction::I32Xor, execute_i32_xor, UntypedVal::i32_xor),
         (Instruction::I32XorEqz, execute_i32_xor_eqz, UntypedVal::i32_xor_eqz),
+        (Instruction::I32DivSSat, execute_i32_div_s_sat, UntypedVal::i32_div_s_sat),
+        (Instruction::I32DivUSat, execute_i32_div_u_sat, UntypedVal::i32_div_u_sat),
+        (Instruction::I32RemSSat, execute_i32_rem_s_sat, UntypedVal::i32_rem_s_sat),
+        (Instruction::I32RemUSat, execute_i32_rem_u_sat, UntypedVal::i32_rem_u_sat),
 
         (Instruction::I64Add, execute_i64_add, UntypedVal::i64_add),
         (Instruction::I64Sub, execute_i64_sub, UntypedVal::i64_sub),
@@ -37,6 +48,10 @@ impl Executor<'_> {
         (Instruction::I64And, execute_i64_and, UntypedVal::i64_and),
         (Instruction::I64Or, execute_i64_or, UntypedVal::i64_or),
         (Instruction::I64Xor, execute_i64_xor, UntypedVal::i64_xor),
+        (Instruction::I64DivSSat, execute_i64_div_s_sat, UntypedVal::i64_div_s_sat),
+        (Instruction::I64DivUSat, execute_i64_div_u_sat, UntypedVal::i64_div_u_sat),
+        (Instruction::I64RemSSat, execute_i64_rem_s_sat, UntypedVal::i64_rem_s_sat),
+        (Instruction::I64RemUSat, execute_i64_rem_u_sat, UntypedVal::i64_rem_u_sat),
 
         (Instruction::I32Shl, execute_i32_shl, UntypedVal::i32_shl),
         (Instruction::I32ShrU, execute_i32_shr_u, UntypedVal::i32_shr_u),
@@ -49,7 +64,12 @@ impl Executor<'_> {
         (Instruction::I64ShrS, execute_i64_shr_s, UntypedVal::i64_shr_s),
         (Instruction::I64Rotl, execute_i64_rotl, UntypedVal::i64_rotl),
         (Instruction::I64Rotr, execute_i64_rotr, UntypedVal::i64_rotr),
+    }
+}
 
+#[cfg(not(feature = "no-floats"))]
+impl Executor<'_> {
+    impl_binary! {
         (Instruction::F32Add, execute_f32_add, UntypedVal::f32_add),
         (Instruction::F32Sub, execute_f32_sub, UntypedVal::f32_sub),
         (Instruction::F32Mul, execute_f32_mul, UntypedVal::f32_mul),
@@ -68,6 +88,7 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 macro_rules! impl_binary_imm16 {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -78,6 +99,7 @@ macro_rules! impl_binary_imm16 {
         )*
     };
 }
+#[cfg(not(feature = "compact-dispatch"))]
 impl Executor<'_> {
     impl_binary_imm16! {
         (i32, Instruction::I32AddImm16, execute_i32_add_imm16, UntypedVal::i32_add),
@@ -124,6 +146,7 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 macro_rules! impl_binary_imm16_lhs {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -134,6 +157,7 @@ macro_rules! impl_binary_imm16_lhs {
         )*
     };
 }
+#[cfg(not(feature = "compact-dispatch"))]
 impl Executor<'_> {
     impl_binary_imm16_lhs! {
         (i32, Instruction::I32SubImm16Lhs, execute_i32_sub_imm16_lhs, UntypedVal::i32_sub),
@@ -178,6 +202,7 @@ impl Executor<'_> {
 }
 
 /// Extension trait to provide more optimized divide and remainder implementations.
+#[cfg(not(feature = "compact-dispatch"))]
 pub trait DivRemExt: Sized {
     /// Optimized variant of Wasm `i32.div_s` for immutable non-zero `rhs` values.
     fn i32_div_s(self, rhs: NonZeroI32) -> Result<Self, Error>;
@@ -198,6 +223,7 @@ pub trait DivRemExt: Sized {
     fn i64_rem_u(self, rhs: NonZeroU64) -> Self;
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 impl DivRemExt for UntypedVal {
     fn i32_div_s(self, rhs: NonZeroI32) -> Result<Self, Error> {
         i32::from(self)
@@ -244,6 +270,7 @@ impl DivRemExt for UntypedVal {
     }
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 macro_rules! impl_divrem_s_imm16_rhs {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -254,6 +281,7 @@ macro_rules! impl_divrem_s_imm16_rhs {
         )*
     };
 }
+#[cfg(not(feature = "compact-dispatch"))]
 impl Executor<'_> {
     impl_divrem_s_imm16_rhs! {
         (NonZeroI32, Instruction::I32DivSImm16Rhs, execute_i32_div_s_imm16_rhs, <UntypedVal as DivRemExt>::i32_div_s),
@@ -264,6 +292,7 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 macro_rules! impl_divrem_u_imm16_rhs {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -274,6 +303,7 @@ macro_rules! impl_divrem_u_imm16_rhs {
         )*
     };
 }
+#[cfg(not(feature = "compact-dispatch"))]
 impl Executor<'_> {
     impl_divrem_u_imm16_rhs! {
         (NonZeroU32, Instruction::I32DivUImm16Rhs, execute_i32_div_u_imm16_rhs, <UntypedVal as DivRemExt>::i32_div_u),
@@ -284,6 +314,7 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 macro_rules! impl_fallible_binary_imm16_lhs {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -294,6 +325,7 @@ macro_rules! impl_fallible_binary_imm16_lhs {
         )*
     };
 }
+#[cfg(not(feature = "compact-dispatch"))]
 impl Executor<'_> {
     impl_fallible_binary_imm16_lhs! {
         (i32, Instruction::I32DivSImm16Lhs, execute_i32_div_s_imm16_lhs, UntypedVal::i32_div_s),
@@ -308,6 +340,7 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 impl Executor<'_> {
     /// Executes an [`Instruction::F32CopysignImm`].
     pub fn execute_f32_copysign_imm(&mut self, result: Reg, lhs: Reg, rhs: Sign<f32>) {
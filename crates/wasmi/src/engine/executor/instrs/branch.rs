@@ -334,7 +334,9 @@ impl_execute_branch_binop! {
     (u64, Instruction::BranchI64LtU, execute_branch_i64_lt_u, cmp_lt),
     (i64, Instruction::BranchI64LeS, execute_branch_i64_le_s, cmp_le),
     (u64, Instruction::BranchI64LeU, execute_branch_i64_le_u, cmp_le),
-
+}
+#[cfg(not(feature = "no-floats"))]
+impl_execute_branch_binop! {
     (f32, Instruction::BranchF32Eq, execute_branch_f32_eq, cmp_eq),
     (f32, Instruction::BranchF32Ne, execute_branch_f32_ne, cmp_ne),
     (f32, Instruction::BranchF32Lt, execute_branch_f32_lt, cmp_lt),
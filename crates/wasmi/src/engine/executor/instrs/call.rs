@@ -8,8 +8,8 @@ use crate::{
         EngineFunc,
         FuncParams,
     },
-    func::{FuncEntity, HostFuncEntity},
-    ir::{index, Instruction, Reg, RegSpan},
+    func::{FuncEntity, HostFuncEntity, MAX_HOST_CALL_BATCH},
+    ir::{index, BoundedRegSpan, Instruction, Reg, RegSpan},
     store::StoreInner,
     CallHook,
     Error,
@@ -18,7 +18,12 @@ use crate::{
     Instance,
     Store,
 };
-use core::{array, fmt};
+use arrayvec::ArrayVec;
+use core::{
+    array,
+    fmt,
+    ops::{Deref, DerefMut},
+};
 
 /// Dispatches and executes the host function.
 ///
@@ -26,36 +31,124 @@ use core::{array, fmt};
 ///
 /// # Errors
 ///
-/// Returns the error of the host function if an error occurred.
+/// - Returns the error of the host function if an error occurred.
+/// - If the host function exceeded its [`HostFuncBudget`](crate::HostFuncBudget).
 pub fn dispatch_host_func<T>(
     store: &mut Store<T>,
     value_stack: &mut ValueStack,
     host_func: HostFuncEntity,
     instance: Option<&Instance>,
+) -> Result<(u16, u16), Error> {
+    dispatch_host_func_batch(store, value_stack, host_func, instance, 1)
+}
+
+/// Dispatches and executes a batch of `len` calls to the host function.
+///
+/// The parameters and results of all calls of the batch are stored consecutively.
+///
+/// Returns the number of parameters and results of the called host function.
+///
+/// # Errors
+///
+/// - Returns the error of the host function if an error occurred.
+/// - If the host function exceeded its [`HostFuncBudget`](crate::HostFuncBudget).
+fn dispatch_host_func_batch<T>(
+    store: &mut Store<T>,
+    value_stack: &mut ValueStack,
+    host_func: HostFuncEntity,
+    instance: Option<&Instance>,
+    len: usize,
 ) -> Result<(u16, u16), Error> {
     let len_params = host_func.len_params();
     let len_results = host_func.len_results();
-    let max_inout = len_params.max(len_results);
+    let batch_params = usize::from(len_params) * len;
+    let batch_results = usize::from(len_results) * len;
+    let max_inout = batch_params.max(batch_results);
     let values = value_stack.as_slice_mut();
-    let params_results = FuncParams::new(
-        values.split_at_mut(values.len() - usize::from(max_inout)).1,
-        usize::from(len_params),
-        usize::from(len_results),
-    );
+    let params_results = values.split_at_mut(values.len() - max_inout).1;
     let trampoline = store.resolve_trampoline(host_func.trampoline()).clone();
-    trampoline
-        .call(store, instance, params_results)
-        .inspect_err(|_error| {
-            // Note: We drop the values that have been temporarily added to
-            //       the stack to act as parameter and result buffer for the
-            //       called host function. Since the host function failed we
-            //       need to clean up the temporary buffer values here.
-            //       This is required for resumable calls to work properly.
-            value_stack.drop(usize::from(max_inout));
-        })?;
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    let started = host_func
+        .budget()
+        .map(|budget| (budget, std::time::Instant::now()));
+    let mut store = HostCallGuard::new(store, instance);
+    let result = match store.inner.take_host_call_log() {
+        None => trampoline
+            .call(
+                &mut *store,
+                instance,
+                FuncParams::new(params_results, batch_params, batch_results),
+            )
+            .map(|_| ()),
+        Some(mut log) => {
+            let result = log.call(
+                &mut store,
+                &trampoline,
+                instance,
+                params_results,
+                batch_params,
+                batch_results,
+            );
+            store.inner.restore_host_call_log(log);
+            result
+        }
+    };
+    drop(store);
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    let result = match started {
+        Some((budget, started)) => result.and_then(|_| budget.check(started.elapsed())),
+        None => result,
+    };
+    result.inspect_err(|_error| {
+        // Note: We drop the values that have been temporarily added to
+        //       the stack to act as parameter and result buffer for the
+        //       called host function. Since the host function failed we
+        //       need to clean up the temporary buffer values here.
+        //       This is required for resumable calls to work properly.
+        value_stack.drop(max_inout);
+    })?;
     Ok((len_params, len_results))
 }
 
+/// Registers a host function call via [`StoreInner::enter_host_call`] for its lifetime.
+///
+/// This unregisters the host function call even if the host function panics
+/// so that the [`Store`] remains usable if the embedder catches the panic.
+struct HostCallGuard<'a, T> {
+    /// The [`Store`] of the host function call.
+    store: &'a mut Store<T>,
+    /// Is `true` if the host function call has been registered.
+    registered: bool,
+}
+
+impl<'a, T> HostCallGuard<'a, T> {
+    /// Registers the host function call from the `caller` instance to `store`.
+    fn new(store: &'a mut Store<T>, caller: Option<&Instance>) -> Self {
+        let registered = store.inner.enter_host_call(caller);
+        Self { store, registered }
+    }
+}
+
+impl<T> Deref for HostCallGuard<'_, T> {
+    type Target = Store<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.store
+    }
+}
+
+impl<T> DerefMut for HostCallGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.store
+    }
+}
+
+impl<T> Drop for HostCallGuard<'_, T> {
+    fn drop(&mut self) {
+        self.store.inner.leave_host_call(self.registered);
+    }
+}
+
 /// The kind of a function call.
 #[derive(Debug, Copy, Clone)]
 pub enum CallKind {
@@ -77,7 +170,11 @@ pub struct ResumableHostError {
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for ResumableHostError {}
+impl std::error::Error for ResumableHostError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.host_error.source()
+    }
+}
 
 impl fmt::Display for ResumableHostError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -101,6 +198,16 @@ impl ResumableHostError {
         self.host_error
     }
 
+    /// Returns a shared reference to the underlying [`Error`].
+    pub(crate) fn error(&self) -> &Error {
+        &self.host_error
+    }
+
+    /// Returns an exclusive reference to the underlying [`Error`].
+    pub(crate) fn error_mut(&mut self) -> &mut Error {
+        &mut self.host_error
+    }
+
     /// Returns the [`Func`] of the [`ResumableHostError`].
     pub(crate) fn host_func(&self) -> &Func {
         &self.host_func
@@ -228,7 +335,8 @@ impl Executor<'_> {
     fn dispatch_compiled_func<C: CallContext>(
         &mut self,
         results: RegSpan,
-        func: CompiledFuncRef,
+        #[cfg(feature = "fuel-profile")] func: EngineFunc,
+        compiled_func: CompiledFuncRef,
     ) -> Result<CallFrame, Error> {
         // We have to reinstantiate the `self.sp` [`FrameRegisters`] since we just called
         // [`ValueStack::alloc_call_frame`] which might invalidate all live [`FrameRegisters`].
@@ -237,12 +345,19 @@ impl Executor<'_> {
             .calls
             .peek()
             .expect("need to have a caller on the call stack");
-        let (mut uninit_params, offsets) = self.stack.values.alloc_call_frame(func, |this| {
-            // Safety: We use the base offset of a live call frame on the call stack.
-            self.sp = unsafe { this.stack_ptr_at(caller.base_offset()) };
-        })?;
-        let instr_ptr = InstructionPtr::new(func.instrs().as_ptr());
-        let frame = CallFrame::new(instr_ptr, offsets, results);
+        let (mut uninit_params, offsets) =
+            self.stack.values.alloc_call_frame(compiled_func, |this| {
+                // Safety: We use the base offset of a live call frame on the call stack.
+                self.sp = unsafe { this.stack_ptr_at(caller.base_offset()) };
+            })?;
+        let instr_ptr = InstructionPtr::new(compiled_func.instrs().as_ptr());
+        let frame = CallFrame::new(
+            instr_ptr,
+            offsets,
+            results,
+            #[cfg(feature = "fuel-profile")]
+            func,
+        );
         if <C as CallContext>::HAS_PARAMS {
             self.copy_call_params(&mut uninit_params);
         }
@@ -313,8 +428,14 @@ impl Executor<'_> {
         func: EngineFunc,
         mut instance: Option<Instance>,
     ) -> Result<(), Error> {
-        let func = self.code_map.get(Some(store.fuel_mut()), func)?;
-        let mut called = self.dispatch_compiled_func::<C>(results, func)?;
+        store.check_injected_trap()?;
+        let compiled_func = self.code_map.get(Some(store.fuel_mut()), func)?;
+        let mut called = self.dispatch_compiled_func::<C>(
+            results,
+            #[cfg(feature = "fuel-profile")]
+            func,
+            compiled_func,
+        )?;
         match <C as CallContext>::KIND {
             CallKind::Nested => {
                 // We need to update the instruction pointer of the caller call frame.
@@ -339,6 +460,13 @@ impl Executor<'_> {
         }
         self.init_call_frame(&called);
         self.stack.calls.push(called, instance)?;
+        #[cfg(feature = "trace")]
+        if let Some(tracer) = store.tracer_mut() {
+            if matches!(<C as CallContext>::KIND, CallKind::Tail) {
+                tracer.exit();
+            }
+            tracer.enter(func);
+        }
         Ok(())
     }
 
@@ -453,11 +581,141 @@ impl Executor<'_> {
         results: RegSpan,
         func: index::Func,
     ) -> Result<(), Error> {
+        let func_index = func;
         let func = self.get_func(func);
+        if let FuncEntity::Host(host_func) = store.inner.resolve_func(&func) {
+            if host_func.is_batched() {
+                let host_func = *host_func;
+                return self
+                    .execute_call_imported_batch(store, results, func_index, &func, host_func);
+            }
+        }
         self.execute_call_imported_impl::<marker::NestedCall, T>(store, Some(results), &func)?;
         Ok(())
     }
 
+    /// Executes an [`Instruction::CallImported`] of a batched host function and all
+    /// directly following calls to it that can be dispatched together as a single batch.
+    ///
+    /// An [`Instruction::CallImported`] is added to the batch if it calls the same `func`
+    /// and none of its parameters is a result of a preceding call of the batch since
+    /// those are only written after the whole batch was executed.
+    ///
+    /// Read more about batches in [`Func::new_batched`].
+    fn execute_call_imported_batch<T>(
+        &mut self,
+        store: &mut Store<T>,
+        results: RegSpan,
+        func_index: index::Func,
+        func: &Func,
+        host_func: HostFuncEntity,
+    ) -> Result<(), Error> {
+        let len_results = host_func.len_results();
+        let mut batch = ArrayVec::<(InstructionPtr, BoundedRegSpan), MAX_HOST_CALL_BATCH>::new();
+        batch.push((self.ip, BoundedRegSpan::new(results, len_results)));
+        let mut ip = Self::visit_call_params(self.ip, |_| ());
+        while !batch.is_full() {
+            let mut next = ip;
+            next.add(1);
+            let Instruction::CallImported {
+                results: next_results,
+                func: next_func,
+            } = *next.get()
+            else {
+                break;
+            };
+            if next_func != func_index {
+                break;
+            }
+            let mut depends_on_batch = false;
+            let last = Self::visit_call_params(next, |reg| {
+                depends_on_batch |= batch.iter().any(|(_, results)| results.contains(reg));
+            });
+            if depends_on_batch {
+                break;
+            }
+            batch.push((next, BoundedRegSpan::new(next_results, len_results)));
+            ip = last;
+        }
+        if batch.len() == 1 {
+            return self
+                .execute_call_imported_impl::<marker::NestedCall, T>(store, Some(results), func)
+                .map(|_| ());
+        }
+        let len_params = usize::from(host_func.len_params());
+        let max_inout = (len_params * batch.len()).max(usize::from(len_results) * batch.len());
+        let instance = *self.stack.calls.instance_expect();
+        let caller = *self
+            .stack
+            .calls
+            .peek()
+            .expect("need to have a caller on the call stack");
+        store.inner.check_injected_trap()?;
+        store.invoke_call_hook(CallHook::CallingHost)?;
+        #[cfg(feature = "trace")]
+        store.inner.trace_host_call(func);
+        let buffer = self.stack.values.extend_by(max_inout, |this| {
+            // Safety: we use the base offset of a live call frame on the call stack.
+            self.sp = unsafe { this.stack_ptr_at(caller.base_offset()) };
+        })?;
+        let mut uninit_params = FrameParams::new(buffer);
+        for (call, _) in &batch {
+            self.ip = *call;
+            self.copy_call_params(&mut uninit_params);
+        }
+        self.update_instr_ptr_at(1);
+        let result = dispatch_host_func_batch(
+            store,
+            &mut self.stack.values,
+            host_func,
+            Some(&instance),
+            batch.len(),
+        );
+        #[cfg(feature = "trace")]
+        if let Some(tracer) = store.inner.tracer_mut() {
+            tracer.exit();
+        }
+        result?;
+        self.cache.update(&mut store.inner, &instance);
+        let returned = self.stack.values.drop_return(max_inout);
+        let chunk_len = usize::from(len_results).max(1);
+        for ((_, results), values) in batch.iter().zip(returned.chunks(chunk_len)) {
+            for (result, value) in results.iter().zip(values) {
+                // Safety: the result registers of the batched calls are registers of
+                //         the caller's call frame which is distinct from the buffer
+                //         of the host function call.
+                unsafe { self.sp.set(result, *value) };
+            }
+        }
+        store.invoke_call_hook(CallHook::ReturningFromHost)?;
+        Ok(())
+    }
+
+    /// Calls `f` for every parameter register of the call [`Instruction`] at `ip`.
+    ///
+    /// Returns the [`InstructionPtr`] pointing to the last call parameter [`Instruction`].
+    fn visit_call_params(mut ip: InstructionPtr, mut f: impl FnMut(Reg)) -> InstructionPtr {
+        ip.add(1);
+        while let Instruction::RegisterList { regs } = ip.get() {
+            regs.iter().copied().for_each(&mut f);
+            ip.add(1);
+        }
+        match ip.get() {
+            Instruction::Register { reg } => f(*reg),
+            Instruction::Register2 { regs } => regs.iter().copied().for_each(&mut f),
+            Instruction::Register3 { regs } => regs.iter().copied().for_each(&mut f),
+            unexpected => {
+                // Safety: Wasmi translation guarantees that register list finalizer exists.
+                unsafe {
+                    unreachable_unchecked!(
+                        "expected register-list finalizer but found: {unexpected:?}"
+                    )
+                }
+            }
+        }
+        ip
+    }
+
     /// Executes an imported or indirect (tail) call instruction.
     fn execute_call_imported_impl<C: CallContext, T>(
         &mut self,
@@ -482,8 +740,22 @@ impl Executor<'_> {
             FuncEntity::Host(host_func) => {
                 let host_func = *host_func;
 
+                store.inner.check_injected_trap()?;
                 store.invoke_call_hook(CallHook::CallingHost)?;
-                let control = self.execute_host_func::<C, T>(store, results, func, host_func)?;
+                #[cfg(feature = "trace")]
+                if let Some(tracer) = store.inner.tracer_mut() {
+                    if matches!(<C as CallContext>::KIND, CallKind::Tail) {
+                        tracer.exit();
+                    }
+                }
+                #[cfg(feature = "trace")]
+                store.inner.trace_host_call(func);
+                let control = self.execute_host_func::<C, T>(store, results, func, host_func);
+                #[cfg(feature = "trace")]
+                if let Some(tracer) = store.inner.tracer_mut() {
+                    tracer.exit();
+                }
+                let control = control?;
                 store.invoke_call_hook(CallHook::ReturningFromHost)?;
 
                 Ok(control)
@@ -0,0 +1,66 @@
+use super::Executor;
+use crate::{ir::Reg, store::StoreInner};
+
+impl Executor<'_> {
+    /// Asserts that the `register` of the executed call frame is in bounds of the value stack.
+    ///
+    /// # Panics
+    ///
+    /// If the `register` is out of bounds.
+    pub(super) fn check_register(&self, register: Reg) {
+        assert!(
+            self.stack.values.contains_register(&self.sp, register),
+            "register {register:?} is out of bounds of the value stack",
+        );
+    }
+
+    /// Asserts the runtime invariants of the [`Executor`] before executing an instruction.
+    ///
+    /// # Panics
+    ///
+    /// If any runtime invariant is broken.
+    pub(super) fn check_invariants(&mut self, store: &mut StoreInner) {
+        self.check_call_stack();
+        self.check_cache(store);
+    }
+
+    /// Asserts that the call frames are ordered and that the stack pointer belongs to the executed one.
+    fn check_call_stack(&mut self) {
+        let len_values = self.stack.values.as_slice().len();
+        let mut caller_base = 0;
+        for frame in self.stack.calls.frames() {
+            let frame_offset = usize::from(frame.frame_offset());
+            let base_offset = usize::from(frame.base_offset());
+            assert!(
+                caller_base <= frame_offset,
+                "call frame at {frame_offset} overlaps its caller at {caller_base}",
+            );
+            assert!(
+                frame_offset <= base_offset && base_offset <= len_values,
+                "call frame at {frame_offset} with base {base_offset} is out of bounds of the value stack with length {len_values}",
+            );
+            caller_base = base_offset;
+        }
+        let frame = self
+            .stack
+            .calls
+            .peek()
+            .expect("must have call frame on the call stack");
+        // Safety: the base offset of the call frame is in bounds of the value stack as asserted above.
+        let expected = unsafe { self.stack.values.stack_ptr_at(frame.base_offset()) };
+        assert!(
+            self.sp == expected,
+            "stack pointer {:?} does not belong to the executed call frame at {expected:?}",
+            self.sp,
+        );
+    }
+
+    /// Asserts that the cached instance, linear memory and global variable are fresh.
+    fn check_cache(&self, store: &mut StoreInner) {
+        let instance = self.stack.calls.instance_expect();
+        assert!(
+            self.cache.is_consistent(store, instance),
+            "cached instance data is stale for {instance:?}",
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use super::Executor;
-use crate::{
-    core::UntypedVal,
-    ir::{Const16, Reg},
-};
+use crate::{core::UntypedVal, ir::Reg};
+
+#[cfg(not(feature = "compact-dispatch"))]
+use crate::ir::Const16;
 
 #[cfg(doc)]
 use crate::ir::Instruction;
@@ -33,7 +33,12 @@ impl Executor<'_> {
         (Instruction::I64LtU, execute_i64_lt_u, UntypedVal::i64_lt_u),
         (Instruction::I64LeS, execute_i64_le_s, UntypedVal::i64_le_s),
         (Instruction::I64LeU, execute_i64_le_u, UntypedVal::i64_le_u),
+    }
+}
 
+#[cfg(not(feature = "no-floats"))]
+impl Executor<'_> {
+    impl_comparison! {
         (Instruction::F32Eq, execute_f32_eq, UntypedVal::f32_eq),
         (Instruction::F32Ne, execute_f32_ne, UntypedVal::f32_ne),
         (Instruction::F32Lt, execute_f32_lt, UntypedVal::f32_lt),
@@ -46,6 +51,7 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 macro_rules! impl_comparison_imm16_rhs {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -57,6 +63,7 @@ macro_rules! impl_comparison_imm16_rhs {
     };
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 impl Executor<'_> {
     impl_comparison_imm16_rhs! {
         (i32, Instruction::I32EqImm16, execute_i32_eq_imm16, UntypedVal::i32_eq),
@@ -75,6 +82,7 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 macro_rules! impl_comparison_imm16_lhs {
     ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -86,6 +94,7 @@ macro_rules! impl_comparison_imm16_lhs {
     };
 }
 
+#[cfg(not(feature = "compact-dispatch"))]
 impl Executor<'_> {
     impl_comparison_imm16_lhs! {
         (i32, Instruction::I32LtSImm16Lhs, execute_i32_lt_s_imm16_lhs, UntypedVal::i32_lt_s),
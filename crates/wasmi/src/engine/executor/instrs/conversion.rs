@@ -1,5 +1,8 @@
 use super::Executor;
-use crate::{core::UntypedVal, ir::Reg, Error};
+use crate::{core::UntypedVal, ir::Reg};
+
+#[cfg(not(feature = "no-floats"))]
+use crate::Error;
 
 #[cfg(doc)]
 use crate::ir::Instruction;
@@ -15,6 +18,7 @@ macro_rules! impl_conversion_impls {
     };
 }
 
+#[cfg(not(feature = "no-floats"))]
 macro_rules! impl_fallible_conversion_impls {
     ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -30,6 +34,17 @@ impl Executor<'_> {
     impl_conversion_impls! {
         (Instruction::I32WrapI64, execute_i32_wrap_i64, UntypedVal::i32_wrap_i64),
 
+        (Instruction::I32Extend8S, execute_i32_extend8_s, UntypedVal::i32_extend8_s),
+        (Instruction::I32Extend16S, execute_i32_extend16_s, UntypedVal::i32_extend16_s),
+        (Instruction::I64Extend8S, execute_i64_extend8_s, UntypedVal::i64_extend8_s),
+        (Instruction::I64Extend16S, execute_i64_extend16_s, UntypedVal::i64_extend16_s),
+        (Instruction::I64Extend32S, execute_i64_extend32_s, UntypedVal::i64_extend32_s),
+    }
+}
+
+#[cfg(not(feature = "no-floats"))]
+impl Executor<'_> {
+    impl_conversion_impls! {
         (Instruction::I32TruncSatF32S, execute_i32_trunc_sat_f32_s, UntypedVal::i32_trunc_sat_f32_s),
         (Instruction::I32TruncSatF32U, execute_i32_trunc_sat_f32_u, UntypedVal::i32_trunc_sat_f32_u),
         (Instruction::I32TruncSatF64S, execute_i32_trunc_sat_f64_s, UntypedVal::i32_trunc_sat_f64_s),
@@ -39,12 +54,6 @@ impl Executor<'_> {
         (Instruction::I64TruncSatF64S, execute_i64_trunc_sat_f64_s, UntypedVal::i64_trunc_sat_f64_s),
         (Instruction::I64TruncSatF64U, execute_i64_trunc_sat_f64_u, UntypedVal::i64_trunc_sat_f64_u),
 
-        (Instruction::I32Extend8S, execute_i32_extend8_s, UntypedVal::i32_extend8_s),
-        (Instruction::I32Extend16S, execute_i32_extend16_s, UntypedVal::i32_extend16_s),
-        (Instruction::I64Extend8S, execute_i64_extend8_s, UntypedVal::i64_extend8_s),
-        (Instruction::I64Extend16S, execute_i64_extend16_s, UntypedVal::i64_extend16_s),
-        (Instruction::I64Extend32S, execute_i64_extend32_s, UntypedVal::i64_extend32_s),
-
         (Instruction::F32DemoteF64, execute_f32_demote_f64, UntypedVal::f32_demote_f64),
         (Instruction::F64PromoteF32, execute_f64_promote_f32, UntypedVal::f64_promote_f32),
 
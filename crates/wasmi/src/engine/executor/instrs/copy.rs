@@ -47,6 +47,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::CopyF64Imm32`].
+    #[cfg(not(feature = "no-floats"))]
     pub fn execute_copy_f64imm32(&mut self, result: Reg, value: Const32<f64>) {
         self.execute_copy_impl(result, value, |_, value| UntypedVal::from(f64::from(value)))
     }
@@ -0,0 +1,174 @@
+use super::Executor;
+use crate::{
+    core::UntypedVal,
+    engine::{
+        utils::unreachable_unchecked,
+        InstructionExtension,
+        MAX_EXTENSION_ARITY,
+        MAX_EXTENSION_BATCH,
+    },
+    ir::{BoundedRegSpan, Instruction, Reg, RegSpan},
+    store::StoreInner,
+    Error,
+};
+use arrayvec::ArrayVec;
+
+impl Executor<'_> {
+    /// Executes an [`Instruction::Extension0`].
+    pub fn execute_extension_0(
+        &mut self,
+        store: &StoreInner,
+        results: RegSpan,
+        extension: u8,
+        len_results: u8,
+        opcode: u16,
+    ) -> Result<(), Error> {
+        self.execute_extension_impl(store, results, extension, len_results, opcode, &[])
+    }
+
+    /// Executes an [`Instruction::Extension`].
+    pub fn execute_extension(
+        &mut self,
+        store: &StoreInner,
+        results: RegSpan,
+        extension: u8,
+        len_results: u8,
+        opcode: u16,
+    ) -> Result<(), Error> {
+        let ext = store.engine().extensions().get(extension);
+        if ext.supports_batch(opcode) {
+            return self.execute_extension_batch(ext, results, extension, len_results, opcode);
+        }
+        let params = self
+            .fetch_extension_params()
+            .into_iter()
+            .map(|reg| self.get_register(reg))
+            .collect::<ArrayVec<_, MAX_EXTENSION_ARITY>>();
+        self.execute_extension_impl(store, results, extension, len_results, opcode, &params)
+    }
+
+    /// Executes an [`Instruction::Extension`] and all directly following instances
+    /// of it that can be dispatched together as a single batch.
+    ///
+    /// An [`Instruction::Extension`] is added to the batch if it executes the same
+    /// `opcode` and none of its parameters is a result of a preceding instruction of
+    /// the batch since those are only written after the whole batch was executed.
+    fn execute_extension_batch(
+        &mut self,
+        ext: &dyn InstructionExtension,
+        mut results: RegSpan,
+        extension: u8,
+        len_results: u8,
+        opcode: u16,
+    ) -> Result<(), Error> {
+        let len_results = u16::from(len_results);
+        let mut batch = ArrayVec::<BoundedRegSpan, MAX_EXTENSION_BATCH>::new();
+        let mut params =
+            ArrayVec::<UntypedVal, { MAX_EXTENSION_ARITY * MAX_EXTENSION_BATCH }>::new();
+        let mut regs = self.fetch_extension_params();
+        loop {
+            params.extend(regs.iter().map(|reg| self.get_register(*reg)));
+            batch.push(BoundedRegSpan::new(results, len_results));
+            if batch.is_full() {
+                break;
+            }
+            let mut ip = self.ip;
+            ip.add(1);
+            let Instruction::Extension {
+                results: next_results,
+                extension: next_extension,
+                opcode: next_opcode,
+                ..
+            } = *ip.get()
+            else {
+                break;
+            };
+            if (next_extension, next_opcode) != (extension, opcode) {
+                break;
+            }
+            let last_ip = self.ip;
+            self.ip = ip;
+            let next_regs = self.fetch_extension_params();
+            let depends_on_batch = next_regs
+                .iter()
+                .any(|reg| batch.iter().any(|results| results.contains(*reg)));
+            if depends_on_batch {
+                self.ip = last_ip;
+                break;
+            }
+            regs = next_regs;
+            results = next_results;
+        }
+        let mut values = [UntypedVal::default(); MAX_EXTENSION_ARITY * MAX_EXTENSION_BATCH];
+        let values = &mut values[..batch.len() * usize::from(len_results)];
+        ext.execute_batch(opcode, batch.len(), &params, values)?;
+        for (results, values) in batch
+            .iter()
+            .zip(values.chunks(usize::from(len_results).max(1)))
+        {
+            for (result, value) in results.iter().zip(values) {
+                self.set_register(result, *value);
+            }
+        }
+        self.next_instr();
+        Ok(())
+    }
+
+    /// Fetches the parameter registers of an [`Instruction::Extension`].
+    ///
+    /// This will make the [`InstructionPtr`] point to the last parameter [`Instruction`].
+    ///
+    /// [`InstructionPtr`]: super::InstructionPtr
+    fn fetch_extension_params(&mut self) -> ArrayVec<Reg, MAX_EXTENSION_ARITY> {
+        let mut params = ArrayVec::new();
+        self.ip.add(1);
+        while let Instruction::RegisterList { regs } = self.ip.get() {
+            params.extend(regs.iter().copied());
+            self.ip.add(1);
+        }
+        match self.ip.get() {
+            Instruction::Register { reg } => {
+                params.push(*reg);
+            }
+            Instruction::Register2 { regs } => {
+                params.extend(regs.iter().copied());
+            }
+            Instruction::Register3 { regs } => {
+                params.extend(regs.iter().copied());
+            }
+            unexpected => {
+                // Safety: Wasmi translation guarantees that register list finalizer exists.
+                unsafe {
+                    unreachable_unchecked!(
+                        "expected register-list finalizer but found: {unexpected:?}"
+                    )
+                }
+            }
+        }
+        params
+    }
+
+    /// Executes an extension instruction with the given `params`.
+    fn execute_extension_impl(
+        &mut self,
+        store: &StoreInner,
+        results: RegSpan,
+        extension: u8,
+        len_results: u8,
+        opcode: u16,
+        params: &[UntypedVal],
+    ) -> Result<(), Error> {
+        let mut values = [UntypedVal::default(); MAX_EXTENSION_ARITY];
+        let values = &mut values[..usize::from(len_results)];
+        store
+            .engine()
+            .extensions()
+            .get(extension)
+            .execute(opcode, params, values)?;
+        for (result, value) in results.iter(u16::from(len_results)).zip(values) {
+            self.set_register(result, *value);
+        }
+        self.next_instr();
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use super::Executor;
 use crate::{
-    core::{hint, UntypedVal},
+    core::UntypedVal,
+    global::GlobalEntity,
     ir::{index, Const16, Reg},
     store::StoreInner,
 };
@@ -10,14 +11,26 @@ use crate::ir::Instruction;
 
 impl Executor<'_> {
     /// Executes an [`Instruction::GlobalGet`].
-    pub fn execute_global_get(&mut self, store: &StoreInner, result: Reg, global: index::Global) {
-        let value = match u32::from(global) {
-            0 => unsafe { self.cache.global.get() },
-            _ => {
-                hint::cold();
-                let global = self.get_global(global);
-                store.resolve_global(&global).get_untyped()
-            }
+    pub fn execute_global_get(
+        &mut self,
+        store: &mut StoreInner,
+        result: Reg,
+        global: index::Global,
+    ) {
+        let index = u32::from(global);
+        let value = match self.cache.flat_globals.get(index) {
+            // Safety: the pointers of the flat globals are invalidated
+            //         whenever the cached instance is updated.
+            Some(data) => unsafe { data.read() },
+            None => match index {
+                0 if self.cache.global.is_cached() => unsafe { self.cache.global.get() },
+                _ => match self.cache.globals.get(index) {
+                    // Safety: the pointers of the cached globals are invalidated
+                    //         whenever the cached instance is updated.
+                    Some(data) => unsafe { data.read() },
+                    None => self.load_global(store, index).get_untyped(),
+                },
+            },
         };
         self.set_register(result, value);
         self.next_instr()
@@ -65,12 +78,44 @@ impl Executor<'_> {
     ) {
         match u32::from(global) {
             0 => unsafe { self.cache.global.set(new_value) },
-            _ => {
-                hint::cold();
-                let global = self.get_global(global);
-                store.resolve_global_mut(&global).set_untyped(new_value)
-            }
+            index => match self.cached_global_ptr(index) {
+                // Safety: the pointers of the cached globals are invalidated
+                //         whenever the cached instance is updated.
+                Some(data) => unsafe { data.write(new_value) },
+                None => self.load_global(store, index).set_untyped(new_value),
+            },
         };
         self.next_instr()
     }
+
+    /// Returns the pointer to the value of the global variable at `index` if cached.
+    ///
+    /// The flat global variables of the instance are accessed via base pointer and
+    /// offset. Other global variables, e.g. imported ones, are served by [`CachedGlobals`].
+    ///
+    /// [`CachedGlobals`]: crate::engine::executor::cache::CachedGlobals
+    #[inline]
+    fn cached_global_ptr(&self, index: u32) -> Option<*mut UntypedVal> {
+        self.cache
+            .flat_globals
+            .get(index)
+            .or_else(|| self.cache.globals.get(index))
+    }
+
+    /// Loads the global variable at `index` and caches the pointer to its value.
+    ///
+    /// # Note
+    ///
+    /// - The values of computed global variables are not cached.
+    /// - Only cache misses end up here so that cache hits stay on the fast path.
+    #[cold]
+    #[inline(never)]
+    fn load_global<'a>(&mut self, store: &'a mut StoreInner, index: u32) -> &'a mut GlobalEntity {
+        let global = self.get_global(index::Global::from(index));
+        let global = store.resolve_global_mut(&global);
+        if let Some(data) = global.get_untyped_ptr() {
+            self.cache.globals.insert(index, data);
+        }
+        global
+    }
 }
@@ -2,11 +2,14 @@ use super::Executor;
 use crate::{
     core::{TrapCode, UntypedVal},
     engine::{executor::instr_ptr::InstructionPtr, utils::unreachable_unchecked},
-    ir::{index::Memory, Const16, Instruction, Reg},
+    ir::{index::Memory, Instruction, Reg},
     store::StoreInner,
     Error,
 };
 
+#[cfg(not(feature = "compact-dispatch"))]
+use crate::ir::Const16;
+
 /// The function signature of Wasm load operations.
 type WasmLoadOp =
     fn(memory: &[u8], address: UntypedVal, offset: u32) -> Result<UntypedVal, TrapCode>;
@@ -38,9 +41,9 @@ impl Executor<'_> {
 
     /// Fetches the bytes of the given `memory`.
     fn fetch_memory_bytes<'exec, 'store, 'bytes>(
-        &'exec self,
+        &'exec mut self,
         memory: Memory,
-        store: &'store StoreInner,
+        store: &'store mut StoreInner,
     ) -> &'bytes [u8]
     where
         'exec: 'bytes,
@@ -48,25 +51,15 @@ impl Executor<'_> {
     {
         match memory.is_default() {
             true => self.fetch_default_memory_bytes(),
-            false => self.fetch_non_default_memory_bytes(memory, store),
+            false => {
+                let data = self.fetch_cached_memory(memory, store).data_ptr();
+                // Safety: the cached non-default memory pointers are invalidated
+                //         conservatively whenever they could have been invalidated.
+                unsafe { data.as_ref() }
+            }
         }
     }
 
-    /// Fetches the bytes of the given non-default `memory`.
-    #[cold]
-    fn fetch_non_default_memory_bytes<'exec, 'store, 'bytes>(
-        &'exec self,
-        memory: Memory,
-        store: &'store StoreInner,
-    ) -> &'bytes [u8]
-    where
-        'exec: 'bytes,
-        'store: 'bytes,
-    {
-        let memory = self.get_memory(memory);
-        store.resolve_memory(&memory).data()
-    }
-
     /// Executes a generic Wasm `load[N_{s|u}]` operation.
     ///
     /// # Note
@@ -82,7 +75,7 @@ impl Executor<'_> {
     /// - `i64.load32_u`
     fn execute_load_extend(
         &mut self,
-        store: &StoreInner,
+        store: &mut StoreInner,
         memory: Memory,
         result: Reg,
         address: UntypedVal,
@@ -108,6 +101,7 @@ impl Executor<'_> {
     /// - `{i32, i64}.load16_u`
     /// - `i64.load32_s`
     /// - `i64.load32_u`
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_load_extend_mem0(
         &mut self,
         result: Reg,
@@ -124,7 +118,7 @@ impl Executor<'_> {
     /// Executes a generic `load` [`Instruction`].
     fn execute_load_impl(
         &mut self,
-        store: &StoreInner,
+        store: &mut StoreInner,
         result: Reg,
         memory: Memory,
         load_extend: WasmLoadOp,
@@ -136,9 +130,10 @@ impl Executor<'_> {
     }
 
     /// Executes a generic `load_at` [`Instruction`].
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_load_at_impl(
         &mut self,
-        store: &StoreInner,
+        store: &mut StoreInner,
         result: Reg,
         address: u32,
         load_extend: WasmLoadOp,
@@ -157,6 +152,7 @@ impl Executor<'_> {
     }
 
     /// Executes a generic `load_offset16` [`Instruction`].
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_load_offset16_impl(
         &mut self,
         result: Reg,
@@ -182,15 +178,17 @@ macro_rules! impl_execute_load {
     ),* $(,)? ) => {
         $(
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_load), "`].")]
-            pub fn $fn_load(&mut self, store: &StoreInner, result: Reg, memory: Memory) -> Result<(), Error> {
+            pub fn $fn_load(&mut self, store: &mut StoreInner, result: Reg, memory: Memory) -> Result<(), Error> {
                 self.execute_load_impl(store, result, memory, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_load_at), "`].")]
-            pub fn $fn_load_at(&mut self, store: &StoreInner, result: Reg, address: u32) -> Result<(), Error> {
+            pub fn $fn_load_at(&mut self, store: &mut StoreInner, result: Reg, address: u32) -> Result<(), Error> {
                 self.execute_load_at_impl(store, result, address, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_load_off16), "`].")]
             pub fn $fn_load_off16(&mut self, result: Reg, ptr: Reg, offset: Const16<u32>) -> Result<(), Error> {
                 self.execute_load_offset16_impl(result, ptr, offset, $impl_fn)
@@ -1,7 +1,7 @@
 use super::{Executor, InstructionPtr};
 use crate::{
     core::TrapCode,
-    engine::utils::unreachable_unchecked,
+    engine::{executor::cache::CachedMemory, utils::unreachable_unchecked},
     error::EntityGrowError,
     ir::{
         index::{Data, Memory},
@@ -32,6 +32,41 @@ impl Executor<'_> {
         }
     }
 
+    /// Returns the non-default linear `memory` of the currently used instance.
+    ///
+    /// # Note
+    ///
+    /// The returned pointer is cached in [`CachedMemories`] and thus stays valid
+    /// until the cached instance is updated or a linear memory has been grown.
+    ///
+    /// [`CachedMemories`]: crate::engine::executor::cache::CachedMemories
+    #[inline]
+    pub(super) fn fetch_cached_memory(
+        &mut self,
+        memory: Memory,
+        store: &mut StoreInner,
+    ) -> CachedMemory {
+        match self.cache.memories.get(u32::from(memory)) {
+            Some(cached) => cached,
+            None => self.load_cached_memory(memory, store),
+        }
+    }
+
+    /// Loads the non-default linear `memory` of the currently used instance and caches it.
+    ///
+    /// # Note
+    ///
+    /// Only cache misses end up here so that cache hits stay on the fast path.
+    #[cold]
+    #[inline(never)]
+    fn load_cached_memory(&mut self, memory: Memory, store: &mut StoreInner) -> CachedMemory {
+        let index = u32::from(memory);
+        let memory = self.get_memory(memory);
+        let cached = CachedMemory::new(store, &memory);
+        self.cache.memories.insert(index, cached);
+        cached
+    }
+
     /// Returns the [`Instruction::DataIndex`] parameter for an [`Instruction`].
     fn fetch_data_segment_index(&self, offset: usize) -> Data {
         let mut addr: InstructionPtr = self.ip;
@@ -77,6 +112,9 @@ impl Executor<'_> {
         delta: Reg,
     ) -> Result<(), Error> {
         let delta: u32 = self.get_register_as(delta);
+        if !self.check_memory_watermark(store, delta)? {
+            return self.execute_memory_grow_denied(result);
+        }
         let (store, mut resource_limiter) = store.store_inner_and_resource_limiter_ref();
         self.execute_memory_grow_impl(store, result, delta, &mut resource_limiter)
     }
@@ -88,10 +126,33 @@ impl Executor<'_> {
         result: Reg,
         delta: u32,
     ) -> Result<(), Error> {
+        if !self.check_memory_watermark(store, delta)? {
+            return self.execute_memory_grow_denied(result);
+        }
         let (store, mut resource_limiter) = store.store_inner_and_resource_limiter_ref();
         self.execute_memory_grow_impl(store, result, delta, &mut resource_limiter)
     }
 
+    /// Checks the watermark of the memory grown by the current `memory.grow` instruction.
+    ///
+    /// Returns `Ok(false)` if the callback set by [`Store::memory_watermark_hook`] denied the growth.
+    ///
+    /// [`Store::memory_watermark_hook`]: crate::Store::memory_watermark_hook
+    fn check_memory_watermark<T>(&self, store: &mut Store<T>, delta: u32) -> Result<bool, Error> {
+        if delta == 0 {
+            return Ok(true);
+        }
+        let memory = self.get_memory(self.fetch_memory_index(1));
+        store.check_memory_watermark(&memory, delta)
+    }
+
+    /// Finishes a `memory.grow` instruction whose growth was denied.
+    #[cold]
+    fn execute_memory_grow_denied(&mut self, result: Reg) -> Result<(), Error> {
+        self.set_register(result, EntityGrowError::ERROR_CODE);
+        self.try_next_instr_at(2)
+    }
+
     /// Executes a generic `memory.grow` instruction.
     #[inline(never)]
     fn execute_memory_grow_impl<'store>(
@@ -267,12 +328,13 @@ impl Executor<'_> {
             .and_then(|memory| memory.get(..len as usize))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
         let dst_bytes = dst_memory
-            .data_mut()
+            .data_mut_untracked()
             .get_mut(dst_index..)
             .and_then(|memory| memory.get_mut(..len as usize))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
         dst_bytes.copy_from_slice(src_bytes);
+        dst_memory.mark_dirty(dst_index, len as usize);
         self.try_next_instr_at(3)
     }
 
@@ -287,7 +349,7 @@ impl Executor<'_> {
     ) -> Result<(), Error> {
         let memory = self.get_memory(memory);
         let (memory, fuel) = store.resolve_memory_and_fuel_mut(&memory);
-        let bytes = memory.data_mut();
+        let bytes = memory.data_mut_untracked();
         // These accesses just perform the bounds checks required by the Wasm spec.
         bytes
             .get(src_index..)
@@ -299,6 +361,7 @@ impl Executor<'_> {
             .ok_or(TrapCode::MemoryOutOfBounds)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
         bytes.copy_within(src_index..src_index.wrapping_add(len as usize), dst_index);
+        memory.mark_dirty(dst_index, len as usize);
         self.try_next_instr_at(3)
     }
 
@@ -425,12 +488,13 @@ impl Executor<'_> {
         let memory = self.get_memory(memory);
         let (memory, fuel) = store.resolve_memory_and_fuel_mut(&memory);
         let slice = memory
-            .data_mut()
+            .data_mut_untracked()
             .get_mut(dst..)
             .and_then(|memory| memory.get_mut(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
         slice.fill(value);
+        memory.mark_dirty(dst, len);
         self.try_next_instr_at(2)
     }
 
@@ -564,8 +628,8 @@ impl Executor<'_> {
             &self.get_memory(memory_index),
             &self.get_data_segment(data_index),
         );
-        let memory = memory
-            .data_mut()
+        let bytes = memory
+            .data_mut_untracked()
             .get_mut(dst_index..)
             .and_then(|memory| memory.get_mut(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
@@ -575,7 +639,8 @@ impl Executor<'_> {
             .and_then(|data| data.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
-        memory.copy_from_slice(data);
+        bytes.copy_from_slice(data);
+        memory.mark_dirty(dst_index, len);
         self.try_next_instr_at(3)
     }
 }
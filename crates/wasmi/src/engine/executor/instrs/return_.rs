@@ -18,6 +18,10 @@ impl Executor<'_> {
             .calls
             .pop()
             .expect("the executing call frame is always on the stack");
+        #[cfg(feature = "trace")]
+        if let Some(tracer) = store.tracer_mut() {
+            tracer.exit();
+        }
         self.stack.values.truncate(returned.frame_offset());
         let new_instance = popped_instance.and_then(|_| self.stack.calls.instance());
         if let Some(new_instance) = new_instance {
@@ -108,7 +112,85 @@ impl Executor<'_> {
         self.execute_return_reg_n_impl::<3>(store, values)
     }
 
-    /// Executes an [`Instruction::ReturnReg2`] or [`Instruction::ReturnReg3`] generically.
+    /// Execute an [`Instruction::ReturnReg4`] returning four [`Reg`] values.
+    pub fn execute_return_reg4(
+        &mut self,
+        store: &mut StoreInner,
+        [v0, v1, v2]: [Reg; 3],
+    ) -> ControlFlow {
+        let [v3] = self.fetch_return_params::<1>(1);
+        self.execute_return_reg_n_impl::<4>(store, [v0, v1, v2, v3])
+    }
+
+    /// Execute an [`Instruction::ReturnReg5`] returning five [`Reg`] values.
+    pub fn execute_return_reg5(
+        &mut self,
+        store: &mut StoreInner,
+        [v0, v1, v2]: [Reg; 3],
+    ) -> ControlFlow {
+        let [v3, v4] = self.fetch_return_params::<2>(1);
+        self.execute_return_reg_n_impl::<5>(store, [v0, v1, v2, v3, v4])
+    }
+
+    /// Execute an [`Instruction::ReturnReg6`] returning six [`Reg`] values.
+    pub fn execute_return_reg6(
+        &mut self,
+        store: &mut StoreInner,
+        [v0, v1, v2]: [Reg; 3],
+    ) -> ControlFlow {
+        let [v3, v4, v5] = self.fetch_return_params::<3>(1);
+        self.execute_return_reg_n_impl::<6>(store, [v0, v1, v2, v3, v4, v5])
+    }
+
+    /// Execute an [`Instruction::ReturnReg7`] returning seven [`Reg`] values.
+    pub fn execute_return_reg7(
+        &mut self,
+        store: &mut StoreInner,
+        [v0, v1, v2]: [Reg; 3],
+    ) -> ControlFlow {
+        let [v3, v4, v5] = self.fetch_return_params::<3>(1);
+        let [v6] = self.fetch_return_params::<1>(2);
+        self.execute_return_reg_n_impl::<7>(store, [v0, v1, v2, v3, v4, v5, v6])
+    }
+
+    /// Execute an [`Instruction::ReturnReg8`] returning eight [`Reg`] values.
+    pub fn execute_return_reg8(
+        &mut self,
+        store: &mut StoreInner,
+        [v0, v1, v2]: [Reg; 3],
+    ) -> ControlFlow {
+        let [v3, v4, v5] = self.fetch_return_params::<3>(1);
+        let [v6, v7] = self.fetch_return_params::<2>(2);
+        self.execute_return_reg_n_impl::<8>(store, [v0, v1, v2, v3, v4, v5, v6, v7])
+    }
+
+    /// Fetches `N` returned [`Reg`] values of an [`Instruction::ReturnReg4`] up to [`Instruction::ReturnReg8`].
+    ///
+    /// The values are stored in the [`Instruction::Register`], [`Instruction::Register2`]
+    /// or [`Instruction::Register3`] parameter at `offset` from the executed instruction.
+    /// Unlike for [`Instruction::ReturnMany`] the parameter kind is known statically.
+    fn fetch_return_params<const N: usize>(&self, offset: usize) -> [Reg; N] {
+        let mut addr: InstructionPtr = self.ip;
+        addr.add(offset);
+        let regs: &[Reg] = match addr.get() {
+            Instruction::Register { reg } if N == 1 => slice::from_ref(reg),
+            Instruction::Register2 { regs } if N == 2 => regs,
+            Instruction::Register3 { regs } if N == 3 => regs,
+            unexpected => {
+                // Safety: Wasmi translation guarantees that the parameter exists.
+                unsafe {
+                    unreachable_unchecked!(
+                        "expected `Instruction::Register{N}` parameter but found: {unexpected:?}"
+                    )
+                }
+            }
+        };
+        let mut values = [Reg::from(0); N];
+        values.copy_from_slice(regs);
+        values
+    }
+
+    /// Executes an [`Instruction::ReturnReg2`] up to [`Instruction::ReturnReg8`] generically.
     fn execute_return_reg_n_impl<const N: usize>(
         &mut self,
         store: &mut StoreInner,
@@ -146,6 +228,7 @@ impl Executor<'_> {
     }
 
     /// Execute an [`Instruction::ReturnF64Imm32`] returning a single 32-bit encoded `f64` value.
+    #[cfg(not(feature = "no-floats"))]
     pub fn execute_return_f64imm32(
         &mut self,
         store: &mut StoreInner,
@@ -291,6 +374,7 @@ impl Executor<'_> {
     }
 
     /// Execute an [`Instruction::ReturnNezF64Imm32`] returning a single 32-bit encoded immediate `f64` value.
+    #[cfg(not(feature = "no-floats"))]
     pub fn execute_return_nez_f64imm32(
         &mut self,
         store: &mut StoreInner,
@@ -55,10 +55,23 @@ impl<'engine> Executor<'engine> {
         R: Into<UntypedVal>,
     {
         let condition: bool = self.get_register_as(condition);
+        #[cfg(not(feature = "hardened-dispatch"))]
         let selected = match condition {
             true => lhs(self).into(),
             false => rhs(self).into(),
         };
+        #[cfg(feature = "hardened-dispatch")]
+        let selected = {
+            // Note: both operands are always loaded so that neither the loaded
+            //       registers nor the control flow depend on `condition`.
+            let lhs: UntypedVal = lhs(self).into();
+            let rhs: UntypedVal = rhs(self).into();
+            UntypedVal::from_bits(crate::core::ct::select(
+                condition,
+                lhs.to_bits(),
+                rhs.to_bits(),
+            ))
+        };
         self.set_register(result, selected);
         self.next_instr_at(2);
     }
@@ -131,6 +144,7 @@ impl<'engine> Executor<'engine> {
     }
 
     /// Executes an [`Instruction::SelectF64Imm32Rhs`].
+    #[cfg(not(feature = "no-floats"))]
     pub fn execute_select_f64imm32_rhs(&mut self, result: Reg, lhs: Reg) {
         let (condition, rhs) = self.fetch_register_and_imm32::<f32>();
         self.execute_select_impl(
@@ -142,6 +156,7 @@ impl<'engine> Executor<'engine> {
     }
 
     /// Executes an [`Instruction::SelectF64Imm32Lhs`].
+    #[cfg(not(feature = "no-floats"))]
     pub fn execute_select_f64imm32_lhs(&mut self, result: Reg, lhs: Const32<f64>) {
         let (condition, rhs) = self.fetch_register_2();
         self.execute_select_impl(
@@ -153,6 +168,7 @@ impl<'engine> Executor<'engine> {
     }
 
     /// Executes an [`Instruction::SelectF64Imm32`].
+    #[cfg(not(feature = "no-floats"))]
     pub fn execute_select_f64imm32(&mut self, result: Reg, lhs: Const32<f64>) {
         let (condition, rhs) = self.fetch_register_and_imm32::<f32>();
         self.execute_select_impl(result, condition, |_| f64::from(lhs), |_| f64::from(rhs))
@@ -1,19 +1,64 @@
 use super::{Executor, InstructionPtr};
 use crate::{
     core::{TrapCode, UntypedVal},
-    engine::utils::unreachable_unchecked,
-    ir::{index::Memory, AnyConst16, Const16, Instruction, Reg},
+    engine::{executor::cache::CachedMemory, utils::unreachable_unchecked},
+    ir::{index::Memory, AnyConst16, Instruction, Reg},
     store::StoreInner,
     Error,
 };
 
-/// The function signature of Wasm store operations.
-type WasmStoreOp = fn(
-    memory: &mut [u8],
-    address: UntypedVal,
-    offset: u32,
-    value: UntypedVal,
-) -> Result<(), TrapCode>;
+#[cfg(not(feature = "compact-dispatch"))]
+use crate::ir::Const16;
+
+/// A Wasm store operation and the number of bytes it stores.
+#[derive(Copy, Clone)]
+struct WasmStoreOp {
+    /// Stores `value` at the effective address `address + offset` of `memory`.
+    store: fn(
+        memory: &mut [u8],
+        address: UntypedVal,
+        offset: u32,
+        value: UntypedVal,
+    ) -> Result<(), TrapCode>,
+    /// The number of bytes stored by the operation.
+    len: usize,
+}
+
+impl WasmStoreOp {
+    /// Creates a new [`WasmStoreOp`] for `store` that stores `len` bytes.
+    const fn new(
+        store: fn(
+            memory: &mut [u8],
+            address: UntypedVal,
+            offset: u32,
+            value: UntypedVal,
+        ) -> Result<(), TrapCode>,
+        len: usize,
+    ) -> Self {
+        Self { store, len }
+    }
+
+    /// Executes the [`WasmStoreOp`] and marks the stored bytes of `memory` as dirty.
+    ///
+    /// # Safety
+    ///
+    /// The `memory` must have been loaded according to the specification of [`CachedMemory::new`].
+    #[inline]
+    unsafe fn execute(
+        self,
+        mut memory: CachedMemory,
+        address: UntypedVal,
+        offset: u32,
+        value: UntypedVal,
+    ) -> Result<(), TrapCode> {
+        let dirty = memory.dirty();
+        (self.store)(unsafe { memory.data_mut() }, address, offset, value)?;
+        // Note: the effective address cannot overflow since the store succeeded.
+        let address = u32::from(address).wrapping_add(offset) as usize;
+        unsafe { dirty.mark(address, self.len) };
+        Ok(())
+    }
+}
 
 impl Executor<'_> {
     /// Returns the register `value` and `offset` parameters for a `load` [`Instruction`].
@@ -53,47 +98,15 @@ impl Executor<'_> {
         }
     }
 
-    /// Fetches the bytes of the default memory at index 0.
+    /// Fetches the cached linear `memory`.
     #[inline]
-    fn fetch_default_memory_bytes_mut(&mut self) -> &mut [u8] {
-        // Safety: the `self.cache.memory` pointer is always synchronized
-        //         conservatively whenever it could have been invalidated.
-        unsafe { self.cache.memory.data_mut() }
-    }
-
-    /// Fetches the bytes of the given `memory`.
-    #[inline]
-    fn fetch_memory_bytes_mut<'exec, 'store, 'bytes>(
-        &'exec mut self,
-        memory: Memory,
-        store: &'store mut StoreInner,
-    ) -> &'bytes mut [u8]
-    where
-        'exec: 'bytes,
-        'store: 'bytes,
-    {
+    fn fetch_memory_mut(&mut self, memory: Memory, store: &mut StoreInner) -> CachedMemory {
         match memory.is_default() {
-            true => self.fetch_default_memory_bytes_mut(),
-            false => self.fetch_non_default_memory_bytes_mut(memory, store),
+            true => self.cache.memory,
+            false => self.fetch_cached_memory(memory, store),
         }
     }
 
-    /// Fetches the bytes of the given non-default `memory`.
-    #[cold]
-    #[inline]
-    fn fetch_non_default_memory_bytes_mut<'exec, 'store, 'bytes>(
-        &'exec mut self,
-        memory: Memory,
-        store: &'store mut StoreInner,
-    ) -> &'bytes mut [u8]
-    where
-        'exec: 'bytes,
-        'store: 'bytes,
-    {
-        let memory = self.get_memory(memory);
-        store.resolve_memory_mut(&memory).data_mut()
-    }
-
     /// Executes a generic Wasm `store[N]` operation.
     ///
     /// # Note
@@ -113,8 +126,10 @@ impl Executor<'_> {
         value: UntypedVal,
         store_wrap: WasmStoreOp,
     ) -> Result<(), Error> {
-        let memory = self.fetch_memory_bytes_mut(memory, store);
-        store_wrap(memory, address, offset, value)?;
+        let memory = self.fetch_memory_mut(memory, store);
+        // Safety: the cached memory pointers are always synchronized
+        //         conservatively whenever they could have been invalidated.
+        unsafe { store_wrap.execute(memory, address, offset, value) }?;
         Ok(())
     }
 
@@ -128,6 +143,7 @@ impl Executor<'_> {
     /// - `{i32, i64}.store8`
     /// - `{i32, i64}.store16`
     /// - `i64.store32`
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_store_wrap_mem0(
         &mut self,
         address: UntypedVal,
@@ -135,8 +151,9 @@ impl Executor<'_> {
         value: UntypedVal,
         store_wrap: WasmStoreOp,
     ) -> Result<(), Error> {
-        let memory = self.fetch_default_memory_bytes_mut();
-        store_wrap(memory, address, offset, value)?;
+        // Safety: the `self.cache.memory` pointers are always synchronized
+        //         conservatively whenever they could have been invalidated.
+        unsafe { store_wrap.execute(self.cache.memory, address, offset, value) }?;
         Ok(())
     }
 
@@ -181,6 +198,7 @@ impl Executor<'_> {
         self.try_next_instr_at(2)
     }
 
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_store_offset16(
         &mut self,
         ptr: Reg,
@@ -197,6 +215,7 @@ impl Executor<'_> {
         self.try_next_instr()
     }
 
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_store_offset16_imm16<T, V>(
         &mut self,
         ptr: Reg,
@@ -216,6 +235,7 @@ impl Executor<'_> {
         self.try_next_instr()
     }
 
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_store_at(
         &mut self,
         store: &mut StoreInner,
@@ -235,6 +255,7 @@ impl Executor<'_> {
         self.try_next_instr()
     }
 
+    #[cfg(not(feature = "compact-dispatch"))]
     fn execute_store_at_imm16<T, V>(
         &mut self,
         store: &mut StoreInner,
@@ -274,6 +295,7 @@ macro_rules! impl_execute_istore {
                 self.execute_store_imm::<$to_ty>(store, ptr, memory, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16_imm16), "`].")]
             pub fn $fn_store_off16_imm16(
                 &mut self,
@@ -284,6 +306,7 @@ macro_rules! impl_execute_istore {
                 self.execute_store_offset16_imm16::<$to_ty, _>(ptr, offset, value, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at_imm16), "`].")]
             pub fn $fn_store_at_imm16(
                 &mut self,
@@ -303,14 +326,14 @@ impl Executor<'_> {
             (Instruction::I32StoreImm16, execute_i32_store_imm16),
             (Instruction::I32StoreOffset16Imm16, execute_i32_store_offset16_imm16),
             (Instruction::I32StoreAtImm16, execute_i32_store_at_imm16),
-            UntypedVal::store32,
+            WasmStoreOp::new(UntypedVal::store32, 4),
         ),
         (
             (Const16<i64> => i64),
             (Instruction::I64StoreImm16, execute_i64_store_imm16),
             (Instruction::I64StoreOffset16Imm16, execute_i64_store_offset16_imm16),
             (Instruction::I64StoreAtImm16, execute_i64_store_at_imm16),
-            UntypedVal::store64,
+            WasmStoreOp::new(UntypedVal::store64, 8),
         ),
     }
 }
@@ -344,6 +367,7 @@ macro_rules! impl_execute_istore_trunc {
                 self.execute_store(store, ptr, memory, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
             pub fn $fn_store_off16(
                 &mut self,
@@ -354,6 +378,7 @@ macro_rules! impl_execute_istore_trunc {
                 self.execute_store_offset16(ptr, offset, value, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             pub fn $fn_store_at(&mut self, store: &mut StoreInner, address: u32, value: Reg) -> Result<(), Error> {
                 self.execute_store_at(store, address, value, $impl_fn)
@@ -371,7 +396,7 @@ impl Executor<'_> {
             (Instruction::I32Store8Offset16Imm, execute_i32_store8_offset16_imm),
             (Instruction::I32Store8At, execute_i32_store8_at),
             (Instruction::I32Store8AtImm, execute_i32_store8_at_imm),
-            UntypedVal::i32_store8,
+            WasmStoreOp::new(UntypedVal::i32_store8, 1),
         ),
         (
             (i16 => i16),
@@ -381,7 +406,7 @@ impl Executor<'_> {
             (Instruction::I32Store16Offset16Imm, execute_i32_store16_offset16_imm),
             (Instruction::I32Store16At, execute_i32_store16_at),
             (Instruction::I32Store16AtImm, execute_i32_store16_at_imm),
-            UntypedVal::i32_store16,
+            WasmStoreOp::new(UntypedVal::i32_store16, 2),
         ),
         (
             (i8 => i8),
@@ -391,7 +416,7 @@ impl Executor<'_> {
             (Instruction::I64Store8Offset16Imm, execute_i64_store8_offset16_imm),
             (Instruction::I64Store8At, execute_i64_store8_at),
             (Instruction::I64Store8AtImm, execute_i64_store8_at_imm),
-            UntypedVal::i64_store8,
+            WasmStoreOp::new(UntypedVal::i64_store8, 1),
         ),
         (
             (i16 => i16),
@@ -401,7 +426,7 @@ impl Executor<'_> {
             (Instruction::I64Store16Offset16Imm, execute_i64_store16_offset16_imm),
             (Instruction::I64Store16At, execute_i64_store16_at),
             (Instruction::I64Store16AtImm, execute_i64_store16_at_imm),
-            UntypedVal::i64_store16,
+            WasmStoreOp::new(UntypedVal::i64_store16, 2),
         ),
         (
             (Const16<i32> => i32),
@@ -411,7 +436,7 @@ impl Executor<'_> {
             (Instruction::I64Store32Offset16Imm16, execute_i64_store32_offset16_imm16),
             (Instruction::I64Store32At, execute_i64_store32_at),
             (Instruction::I64Store32AtImm16, execute_i64_store32_at_imm16),
-            UntypedVal::i64_store32,
+            WasmStoreOp::new(UntypedVal::i64_store32, 4),
         ),
     }
 }
@@ -431,6 +456,7 @@ macro_rules! impl_execute_store {
                 self.execute_store(store, ptr, memory, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
             pub fn $fn_store_off16(
                 &mut self,
@@ -441,6 +467,7 @@ macro_rules! impl_execute_store {
                 self.execute_store_offset16(ptr, offset, value, $impl_fn)
             }
 
+            #[cfg(not(feature = "compact-dispatch"))]
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             pub fn $fn_store_at(&mut self, store: &mut StoreInner,address: u32, value: Reg) -> Result<(), Error> {
                 self.execute_store_at(store, address, value, $impl_fn)
@@ -455,13 +482,13 @@ impl Executor<'_> {
             (Instruction::Store32, execute_store32),
             (Instruction::Store32Offset16, execute_store32_offset16),
             (Instruction::Store32At, execute_store32_at),
-            UntypedVal::store32,
+            WasmStoreOp::new(UntypedVal::store32, 4),
         ),
         (
             (Instruction::Store64, execute_store64),
             (Instruction::Store64Offset16, execute_store64_offset16),
             (Instruction::Store64At, execute_store64_at),
-            UntypedVal::store64,
+            WasmStoreOp::new(UntypedVal::store64, 8),
         ),
     }
 }
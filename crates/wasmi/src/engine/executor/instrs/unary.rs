@@ -24,7 +24,12 @@ impl Executor<'_> {
         (Instruction::I64Clz, execute_i64_clz, UntypedVal::i64_clz),
         (Instruction::I64Ctz, execute_i64_ctz, UntypedVal::i64_ctz),
         (Instruction::I64Popcnt, execute_i64_popcnt, UntypedVal::i64_popcnt),
+    }
+}
 
+#[cfg(not(feature = "no-floats"))]
+impl Executor<'_> {
+    impl_unary_impls! {
         (Instruction::F32Abs, execute_f32_abs, UntypedVal::f32_abs),
         (Instruction::F32Neg, execute_f32_neg, UntypedVal::f32_neg),
         (Instruction::F32Ceil, execute_f32_ceil, UntypedVal::f32_ceil),
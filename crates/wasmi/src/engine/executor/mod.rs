@@ -6,22 +6,36 @@ use self::{
     stack::CallFrame,
 };
 use crate::{
-    engine::{CallParams, CallResults, EngineInner, ResumableCallBase, ResumableInvocation},
+    core::UntypedVal,
+    engine::{
+        CallParams,
+        CallResults,
+        EngineFunc,
+        EngineInner,
+        PreemptibleCall,
+        ResumableCallBase,
+        ResumableInvocation,
+    },
     func::HostFuncEntity,
     ir::{Reg, RegSpan},
     CallHook,
     Error,
     Func,
     FuncEntity,
+    Instance,
     Store,
     StoreContextMut,
 };
+use alloc::vec::Vec;
 
 #[cfg(doc)]
 use crate::engine::StackLimits;
 
 use super::code_map::CodeMap;
 
+#[cfg(feature = "time-travel")]
+use crate::TraceStep;
+
 mod cache;
 mod instr_ptr;
 mod instrs;
@@ -45,17 +59,45 @@ impl EngineInner {
     where
         Results: CallResults,
     {
-        let mut stack = self.stacks.lock().reuse_or_new();
+        let store = ctx.store;
+        let mut stack = match store.inner.take_cached_stack() {
+            Some(stack) => stack,
+            None => self.stacks.lock().reuse_or_new(),
+        };
         let results = EngineExecutor::new(&self.code_map, &mut stack)
-            .execute_root_func(ctx.store, func, params, results)
+            .execute_root_func(store, func, params, results)
             .map_err(|error| match error.into_resumable() {
                 Ok(error) => error.into_error(),
                 Err(error) => error,
             });
-        self.stacks.lock().recycle(stack);
+        store.inner.sync_fuel_share();
+        self.cache_stack(store, stack);
         results
     }
 
+    /// Caches the `stack` in the `store` for reuse by its next call from the host.
+    ///
+    /// This avoids acquiring the [`Stack`]s of the [`EngineInner`] for frequent calls
+    /// to tiny Wasm functions. If the `store` already caches a [`Stack`], e.g. after a
+    /// nested call from a host function, the surplus [`Stack`] is recycled instead.
+    ///
+    /// The `stack` is recycled without being cached if the [`Engine`] does not keep
+    /// any [`Stack`]s for reuse or if the `stack` did not allocate any memory.
+    ///
+    /// [`Engine`]: crate::Engine
+    fn cache_stack<T>(&self, store: &mut Store<T>, mut stack: Stack) {
+        if self.config.get_cached_stacks() == 0 || stack.capacity() == 0 {
+            self.stacks.lock().recycle(stack);
+            return;
+        }
+        if self.config.get_zero_on_free() {
+            stack.reset();
+        }
+        if let Some(surplus) = store.inner.cache_stack(stack) {
+            self.stacks.lock().recycle(surplus);
+        }
+    }
+
     /// Executes the given [`Func`] resumably with the given `params` and returns the `results`.
     ///
     /// Uses the [`StoreContextMut`] for context information about the Wasm [`Store`].
@@ -77,6 +119,7 @@ impl EngineInner {
         let mut stack = self.stacks.lock().reuse_or_new();
         let results = EngineExecutor::new(&self.code_map, &mut stack)
             .execute_root_func(store, func, params, results);
+        store.inner.sync_fuel_share();
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(stack);
@@ -130,6 +173,7 @@ impl EngineInner {
             caller_results,
             results,
         );
+        ctx.store.inner.sync_fuel_share();
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(invocation.take_stack());
@@ -151,6 +195,139 @@ impl EngineInner {
     }
 }
 
+impl EngineInner {
+    /// Executes the given [`Func`] with the given `params` and preempts it when running out of fuel.
+    ///
+    /// Uses the [`StoreContextMut`] for context information about the Wasm [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm execution traps or runs out of resources other than fuel.
+    pub fn execute_func_preemptible<T, Results>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        results: Results,
+    ) -> Result<PreemptibleCall<<Results as CallResults>::Results>, Error>
+    where
+        Results: CallResults,
+    {
+        let mut stack = self.stacks.lock().reuse_or_new();
+        let results = EngineExecutor::new(&self.code_map, &mut stack)
+            .execute_root_func(ctx.store, func, params, results);
+        ctx.store.inner.sync_fuel_share();
+        self.finish_preemptible(stack, results)
+    }
+
+    /// Resumes the execution preempted with `stack` and returns the `results`.
+    ///
+    /// Uses the [`StoreContextMut`] for context information about the Wasm [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm execution traps or runs out of resources other than fuel.
+    pub fn resume_func_preempted<T, Results>(
+        &self,
+        ctx: StoreContextMut<T>,
+        mut stack: Stack,
+        results: Results,
+    ) -> Result<PreemptibleCall<<Results as CallResults>::Results>, Error>
+    where
+        Results: CallResults,
+    {
+        stack.out_of_fuel = false;
+        let mut executor = EngineExecutor::new(&self.code_map, &mut stack);
+        let results = executor
+            .execute_func(ctx.store)
+            .map(|()| executor.write_results_back(results));
+        ctx.store.inner.sync_fuel_share();
+        self.finish_preemptible(stack, results)
+    }
+
+    /// Converts the `results` of a preemptible execution using `stack` into a [`PreemptibleCall`].
+    fn finish_preemptible<Results>(
+        &self,
+        stack: Stack,
+        results: Result<Results, Error>,
+    ) -> Result<PreemptibleCall<Results>, Error> {
+        match results {
+            Ok(results) => {
+                self.stacks.lock().recycle(stack);
+                Ok(PreemptibleCall::Finished(results))
+            }
+            Err(_) if stack.out_of_fuel => Ok(PreemptibleCall::OutOfFuel(stack)),
+            Err(error) => {
+                self.stacks.lock().recycle(stack);
+                Err(match error.into_resumable() {
+                    Ok(error) => error.into_error(),
+                    Err(error) => error,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(feature = "time-travel")]
+impl EngineInner {
+    /// Resumes the paused execution captured by `stack` and calls `tracer` before every basic block.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm execution traps or runs out of resources.
+    pub fn rewind<T, Results>(
+        &self,
+        store: &mut Store<T>,
+        mut stack: Stack,
+        tracer: &mut dyn FnMut(&TraceStep),
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, Error>
+    where
+        Results: CallResults,
+    {
+        let mut executor = EngineExecutor::new(&self.code_map, &mut stack);
+        let consumed = store.inner.fuel().consumed();
+        if let Some(time_travel) = store.inner.time_travel_mut() {
+            time_travel.schedule_pause(consumed, 0);
+        }
+        let result = executor.execute_paused(store, |store, stack| {
+            let consumed = store.inner.fuel().consumed();
+            if let Some(time_travel) = store.inner.time_travel_mut() {
+                time_travel.schedule_pause(consumed, 1);
+            }
+            tracer(&TraceStep::new(&store.inner, stack.calls.len()));
+        });
+        let consumed = store.inner.fuel().consumed();
+        if let Some(time_travel) = store.inner.time_travel_mut() {
+            time_travel.schedule_checkpoint(consumed);
+        }
+        result?;
+        Ok(executor.write_results_back(results))
+    }
+}
+
+/// The root Wasm function of the last call from the host with its resolved call layout.
+///
+/// # Note
+///
+/// This is cached by the [`Store`] so that frequent calls to the same Wasm function
+/// from the host do not have to resolve the function and its instance every time.
+#[derive(Debug, Copy, Clone)]
+pub struct CachedCall {
+    /// The called Wasm function.
+    pub func: Func,
+    /// The instance of the called Wasm function.
+    pub instance: Instance,
+    /// The compiled body of the called Wasm function.
+    pub engine_func: EngineFunc,
+    /// The number of flattened parameters of the called Wasm function.
+    pub len_params: usize,
+    /// The number of flattened results of the called Wasm function.
+    pub len_results: usize,
+}
+
 /// The internal state of the Wasmi engine.
 #[derive(Debug)]
 pub struct EngineExecutor<'engine> {
@@ -190,59 +367,130 @@ impl<'engine> EngineExecutor<'engine> {
         Results: CallResults,
     {
         self.stack.reset();
-        match store.inner.resolve_func(func) {
-            FuncEntity::Wasm(wasm_func) => {
-                // We reserve space on the stack to write the results of the root function execution.
-                let len_results = results.len_results();
-                self.stack.values.extend_by(len_results, do_nothing)?;
-                let instance = *wasm_func.instance();
-                let engine_func = wasm_func.func_body();
-                let compiled_func = self
-                    .code_map
-                    .get(Some(store.inner.fuel_mut()), engine_func)?;
-                let (mut uninit_params, offsets) = self
-                    .stack
-                    .values
-                    .alloc_call_frame(compiled_func, do_nothing)?;
-                for value in params.call_params() {
-                    unsafe { uninit_params.init_next(value) };
+        store.inner.check_fuel_share()?;
+        store.inner.check_injected_trap()?;
+        let params = params.call_params();
+        let call = match store.inner.cached_call(func) {
+            Some(call) => call,
+            None => match store.inner.resolve_func(func) {
+                FuncEntity::Wasm(wasm_func) => {
+                    let call = CachedCall {
+                        func: *func,
+                        instance: *wasm_func.instance(),
+                        engine_func: wasm_func.func_body(),
+                        len_params: params.len(),
+                        len_results: results.len_results(),
+                    };
+                    store.inner.cache_call(call);
+                    call
                 }
-                uninit_params.init_zeroes();
-                self.stack.calls.push(
-                    CallFrame::new(
-                        InstructionPtr::new(compiled_func.instrs().as_ptr()),
-                        offsets,
-                        RegSpan::new(Reg::from(0)),
-                    ),
-                    Some(instance),
-                )?;
-                store.invoke_call_hook(CallHook::CallingWasm)?;
-                self.execute_func(store)?;
-                store.invoke_call_hook(CallHook::ReturningFromWasm)?;
-            }
-            FuncEntity::Host(host_func) => {
-                // The host function signature is required for properly
-                // adjusting, inspecting and manipulating the value stack.
-                // In case the host function returns more values than it takes
-                // we are required to extend the value stack.
-                let len_params = host_func.len_params();
-                let len_results = host_func.len_results();
-                let max_inout = len_params.max(len_results);
-                let uninit = self
-                    .stack
-                    .values
-                    .extend_by(usize::from(max_inout), do_nothing)?;
-                for (uninit, param) in uninit.iter_mut().zip(params.call_params()) {
-                    uninit.write(param);
+                FuncEntity::Host(host_func) => {
+                    let host_func = *host_func;
+                    #[cfg(feature = "trace")]
+                    store.inner.trace_host_call(func);
+                    let result = self.execute_root_host_func(store, host_func, params);
+                    #[cfg(feature = "trace")]
+                    if let Some(tracer) = store.inner.tracer_mut() {
+                        tracer.exit();
+                    }
+                    result?;
+                    return Ok(self.write_results_back(results));
                 }
-                let host_func = *host_func;
-                self.dispatch_host_func(store, host_func)?;
-            }
+            },
         };
+        debug_assert_eq!(call.len_params, params.len());
+        debug_assert_eq!(call.len_results, results.len_results());
+        let instance = call.instance;
+        let engine_func = call.engine_func;
+        store.inner.check_reentrancy(&instance)?;
+        // We reserve space on the stack to write the results of the root function execution.
+        self.stack.values.extend_by(call.len_results, do_nothing)?;
+        let compiled_func = self
+            .code_map
+            .get(Some(store.inner.fuel_mut()), engine_func)?;
+        let (mut uninit_params, offsets) = self
+            .stack
+            .values
+            .alloc_call_frame(compiled_func, do_nothing)?;
+        for value in params {
+            unsafe { uninit_params.init_next(value) };
+        }
+        uninit_params.init_zeroes();
+        self.stack.calls.push(
+            CallFrame::new(
+                InstructionPtr::new(compiled_func.instrs().as_ptr()),
+                offsets,
+                RegSpan::new(Reg::from(0)),
+                #[cfg(feature = "fuel-profile")]
+                engine_func,
+            ),
+            Some(instance),
+        )?;
+        #[cfg(feature = "time-travel")]
+        if let Some(time_travel) = store.inner.time_travel_mut() {
+            time_travel.enter_root_func(*func);
+        }
+        store.invoke_call_hook(CallHook::CallingWasm)?;
+        #[cfg(feature = "trace")]
+        let depth = store.inner.tracer_mut().map(|tracer| {
+            let depth = tracer.depth();
+            tracer.enter(engine_func);
+            depth
+        });
+        let result = self.execute_func(store);
+        #[cfg(feature = "trace")]
+        if let (Some(depth), Some(tracer)) = (depth, store.inner.tracer_mut()) {
+            // Closes the functions of trapped or suspended executions.
+            tracer.unwind(depth);
+        }
+        if let Err(error) = result {
+            let handled = store
+                .inner
+                .trap_handler()
+                .and_then(|handler| handler.convert(&*store, func, &error));
+            let Some(handled) = handled else {
+                return Err(error);
+            };
+            let handled = handled?
+                .into_iter()
+                .map(UntypedVal::from)
+                .collect::<Vec<_>>();
+            store.invoke_call_hook(CallHook::ReturningFromWasm)?;
+            return Ok(results.call_results(&handled));
+        }
+        store.invoke_call_hook(CallHook::ReturningFromWasm)?;
         let results = self.write_results_back(results);
         Ok(results)
     }
 
+    /// Executes the root host function `host_func` using the given `params`.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a host trap during the execution of `host_func`.
+    fn execute_root_host_func<T>(
+        &mut self,
+        store: &mut Store<T>,
+        host_func: HostFuncEntity,
+        params: impl Iterator<Item = UntypedVal>,
+    ) -> Result<(), Error> {
+        // The host function signature is required for properly
+        // adjusting, inspecting and manipulating the value stack.
+        // In case the host function returns more values than it takes
+        // we are required to extend the value stack.
+        let len_params = host_func.len_params();
+        let len_results = host_func.len_results();
+        let max_inout = len_params.max(len_results);
+        let uninit = self
+            .stack
+            .values
+            .extend_by(usize::from(max_inout), do_nothing)?;
+        for (uninit, param) in uninit.iter_mut().zip(params) {
+            uninit.write(param);
+        }
+        self.dispatch_host_func(store, host_func)
+    }
+
     /// Resumes the execution of the given [`Func`] using `params`.
     ///
     /// Stores the execution result into `results` upon a successful execution.
@@ -284,11 +532,57 @@ impl<'engine> EngineExecutor<'engine> {
     /// # Errors
     ///
     /// When encountering a Wasm or host trap during execution.
+    #[cfg(not(feature = "time-travel"))]
     #[inline(always)]
     fn execute_func<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
         execute_instrs(store, self.stack, self.code_map)
     }
 
+    /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
+    ///
+    /// Takes a [`Checkpoint`] whenever the execution pauses.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during execution.
+    ///
+    /// [`Checkpoint`]: crate::Checkpoint
+    #[cfg(feature = "time-travel")]
+    fn execute_func<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
+        self.execute_paused(store, |store, stack| store.inner.take_checkpoint(stack))
+    }
+
+    /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
+    ///
+    /// Calls `on_pause` whenever the execution pauses before resuming it.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during execution.
+    #[cfg(feature = "time-travel")]
+    fn execute_paused<T>(
+        &mut self,
+        store: &mut Store<T>,
+        mut on_pause: impl FnMut(&mut Store<T>, &Stack),
+    ) -> Result<(), Error> {
+        if let Some(time_travel) = store.inner.time_travel_mut() {
+            time_travel.enter_execution();
+        }
+        let result = loop {
+            if let Err(error) = execute_instrs(store, self.stack, self.code_map) {
+                break Err(error);
+            }
+            if self.stack.calls.is_empty() {
+                break Ok(());
+            }
+            on_pause(store, self.stack);
+        };
+        if let Some(time_travel) = store.inner.time_travel_mut() {
+            time_travel.exit_execution();
+        }
+        result
+    }
+
     /// Convenience forwarder to [`dispatch_host_func`].
     #[inline(always)]
     fn dispatch_host_func<T>(
@@ -8,19 +8,14 @@ use crate::{
 };
 use alloc::vec::Vec;
 
+#[cfg(feature = "fuel-profile")]
+use crate::engine::EngineFunc;
+
 #[cfg(doc)]
-use crate::{
-    engine::executor::stack::ValueStack,
-    engine::EngineFunc,
-    ir::Instruction,
-    ir::Reg,
-    Global,
-    Memory,
-    Table,
-};
+use crate::{engine::executor::stack::ValueStack, ir::Instruction, ir::Reg, Global, Memory, Table};
 
 /// The stack of nested function calls.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CallStack {
     /// The stack of nested function call frames.
     frames: Vec<CallFrame>,
@@ -60,10 +55,15 @@ impl CallStack {
 
     /// Returns the number of [`CallFrame`]s on the [`CallStack`].
     #[inline(always)]
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.frames.len()
     }
 
+    /// Returns the [`CallFrame`]s on the [`CallStack`] starting with the outermost one.
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
     /// Returns `true` if the [`CallStack`] is empty.
     #[inline(always)]
     pub fn is_empty(&self) -> bool {
@@ -191,7 +191,7 @@ impl StackOffsets {
     }
 }
 
-/// A single frame of a called [`EngineFunc`].
+/// A single frame of a called [`EngineFunc`](crate::engine::EngineFunc).
 #[derive(Debug, Copy, Clone)]
 pub struct CallFrame {
     /// The pointer to the [`Instruction`] that is executed next.
@@ -200,6 +200,9 @@ pub struct CallFrame {
     offsets: StackOffsets,
     /// Span of registers were the caller expects them in its [`CallFrame`].
     results: RegSpan,
+    /// The [`EngineFunc`] that is executed by the [`CallFrame`].
+    #[cfg(feature = "fuel-profile")]
+    func: EngineFunc,
     /// Is `true` if this [`CallFrame`] changed the currently used [`Instance`].
     ///
     /// - This flag is an optimization to reduce the amount of accesses on the
@@ -212,11 +215,18 @@ pub struct CallFrame {
 
 impl CallFrame {
     /// Creates a new [`CallFrame`].
-    pub fn new(instr_ptr: InstructionPtr, offsets: StackOffsets, results: RegSpan) -> Self {
+    pub fn new(
+        instr_ptr: InstructionPtr,
+        offsets: StackOffsets,
+        results: RegSpan,
+        #[cfg(feature = "fuel-profile")] func: EngineFunc,
+    ) -> Self {
         Self {
             instr_ptr,
             offsets,
             results,
+            #[cfg(feature = "fuel-profile")]
+            func,
             changed_instance: false,
         }
     }
@@ -263,4 +273,10 @@ impl CallFrame {
     pub fn results(&self) -> RegSpan {
         self.results
     }
+
+    /// Returns the [`EngineFunc`] executed by the [`CallFrame`].
+    #[cfg(feature = "fuel-profile")]
+    pub fn func(&self) -> EngineFunc {
+        self.func
+    }
 }
@@ -20,12 +20,17 @@ fn err_stack_overflow() -> TrapCode {
 }
 
 /// Data structure that combines both value stack and call stack.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Stack {
     /// The call stack.
     pub calls: CallStack,
     /// The value stack.
     pub values: ValueStack,
+    /// Is `true` if the execution ran out of fuel at the start of a basic block.
+    ///
+    /// Such an execution can be resumed after refueling since the
+    /// instruction pointer of its top-most [`CallFrame`] is up to date.
+    pub out_of_fuel: bool,
 }
 
 impl Stack {
@@ -38,13 +43,18 @@ impl Stack {
             limits.initial_value_stack_height,
             limits.maximum_value_stack_height,
         );
-        Self { calls, values }
+        Self {
+            calls,
+            values,
+            out_of_fuel: false,
+        }
     }
 
     /// Resets the [`Stack`] for clean reuse.
     pub fn reset(&mut self) {
         self.calls.reset();
         self.values.reset();
+        self.out_of_fuel = false;
     }
 
     /// Create an empty [`Stack`].
@@ -56,6 +66,7 @@ impl Stack {
         Self {
             values: ValueStack::empty(),
             calls: CallStack::default(),
+            out_of_fuel: false,
         }
     }
 
@@ -3,6 +3,7 @@ use crate::{
     core::{TrapCode, UntypedVal},
     engine::code_map::CompiledFuncRef,
     ir::Reg,
+    memory::zero_bytes,
 };
 use alloc::vec::Vec;
 use core::{
@@ -23,6 +24,34 @@ pub struct ValueStack {
     values: Vec<UntypedVal>,
     /// Maximal possible `sp` value.
     max_len: usize,
+    /// The number of cells that may be non-zero including freed cells.
+    ///
+    /// # Note
+    ///
+    /// This is only tracked if `zero_on_free` is `true`.
+    dirty_len: usize,
+    /// Is `true` if freed cells of the [`ValueStack`] are zeroed.
+    zero_on_free: bool,
+}
+
+impl Clone for ValueStack {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            max_len: self.max_len,
+            dirty_len: self.values.len(),
+            zero_on_free: self.zero_on_free,
+        }
+    }
+}
+
+impl Drop for ValueStack {
+    fn drop(&mut self) {
+        if self.zero_on_free {
+            self.values.clear();
+            self.zero_freed();
+        }
+    }
 }
 
 impl ValueStack {
@@ -81,6 +110,8 @@ impl ValueStack {
         Self {
             values: Vec::with_capacity(initial_len),
             max_len: maximum_len,
+            dirty_len: 0,
+            zero_on_free: false,
         }
     }
 
@@ -94,9 +125,50 @@ impl ValueStack {
         Self {
             values: Vec::new(),
             max_len: 0,
+            dirty_len: 0,
+            zero_on_free: false,
         }
     }
 
+    /// Makes the [`ValueStack`] zero its cells once they are freed.
+    ///
+    /// # Note
+    ///
+    /// Cells dropped via [`ValueStack::drop_return`] are zeroed upon the next
+    /// [`ValueStack::truncate`] or [`ValueStack::reset`] since they are still read.
+    pub fn set_zero_on_free(&mut self) {
+        self.zero_on_free = true;
+        self.dirty_len = self.values.len();
+    }
+
+    /// Zeroes all freed cells of the [`ValueStack`] that may be non-zero.
+    #[inline]
+    fn zero_freed(&mut self) {
+        let len = self.values.len();
+        if self.dirty_len > len {
+            let amount = self.dirty_len - len;
+            // Safety: `dirty_len` never exceeds the capacity of `values`.
+            unsafe {
+                let freed = self.values.as_mut_ptr().add(len);
+                zero_bytes(freed.cast(), amount * mem::size_of::<UntypedVal>());
+            }
+        }
+        self.dirty_len = len;
+    }
+
+    /// Reallocates the [`ValueStack`] to fit `additional` cells and zeroes the old allocation.
+    #[cold]
+    fn reserve_zeroed(&mut self, additional: usize) {
+        let len = self.values.len();
+        let capacity = (len + additional).max(2 * self.values.capacity());
+        let mut values = Vec::with_capacity(capacity);
+        values.extend_from_slice(&self.values);
+        self.values.clear();
+        self.zero_freed();
+        self.values = values;
+        self.dirty_len = len;
+    }
+
     /// Resets the [`ValueStack`] for reuse.
     ///
     /// # Note
@@ -107,17 +179,32 @@ impl ValueStack {
     /// provide a clean slate for all executions.
     pub fn reset(&mut self) {
         self.values.clear();
+        if self.zero_on_free {
+            self.zero_freed();
+        }
     }
 
     /// Returns the root [`FrameRegisters`] pointing to the first value on the [`ValueStack`].
     pub fn root_stack_ptr(&mut self) -> FrameRegisters {
-        FrameRegisters::new(self.values.as_mut_ptr())
+        // Safety: the first value is always in bounds of the value stack allocation.
+        unsafe { FrameRegisters::new(&mut self.values, 0) }
     }
 
     /// Returns the [`FrameRegisters`] at the given `offset`.
     pub unsafe fn stack_ptr_at(&mut self, offset: impl Into<ValueStackOffset>) -> FrameRegisters {
-        let ptr = self.values.as_mut_ptr().add(offset.into().0);
-        FrameRegisters::new(ptr)
+        FrameRegisters::new(&mut self.values, offset.into().0)
+    }
+
+    /// Returns `true` if the `register` of `sp` refers to a value on the [`ValueStack`].
+    #[cfg(feature = "checked-execution")]
+    pub fn contains_register(&self, sp: &FrameRegisters, register: Reg) -> bool {
+        let Some(offset) = (sp.ptr as usize).checked_sub(self.values.as_ptr() as usize) else {
+            return false;
+        };
+        let index = offset / mem::size_of::<UntypedVal>();
+        index
+            .checked_add_signed(isize::from(i16::from(register)))
+            .is_some_and(|index| index < self.values.len())
     }
 
     /// Returns the capacity of the [`ValueStack`].
@@ -143,12 +230,18 @@ impl ValueStack {
             return Err(err_stack_overflow());
         }
         let prev_capacity = self.capacity();
+        if self.zero_on_free && additional > prev_capacity - self.len() {
+            self.reserve_zeroed(additional);
+        }
         self.values.reserve(additional);
         if prev_capacity != self.capacity() {
             on_resize(self);
         }
         let spare = self.values.spare_capacity_mut().as_mut_ptr();
         unsafe { self.values.set_len(self.values.len() + additional) };
+        if self.zero_on_free {
+            self.dirty_len = self.dirty_len.max(self.values.len());
+        }
         Ok(unsafe { slice::from_raw_parts_mut(spare, additional) })
     }
 
@@ -202,6 +295,9 @@ impl ValueStack {
         assert!(new_len <= self.len());
         // Safety: we just asserted that the new length is valid.
         unsafe { self.values.set_len(new_len) };
+        if self.zero_on_free {
+            self.zero_freed();
+        }
     }
 
     /// Allocates a new [`EngineFunc`] on the [`ValueStack`].
@@ -276,6 +372,9 @@ impl ValueStack {
         debug_assert!(to <= self.len());
         let len_drained = to - from;
         self.values.drain(from..to);
+        if self.zero_on_free {
+            self.zero_freed();
+        }
         len_drained
     }
 }
@@ -384,9 +483,16 @@ impl FrameParams {
 /// Accessor to the [`Reg`] values of a [`CallFrame`] on the [`CallStack`].
 ///
 /// [`CallStack`]: [`super::CallStack`]
+#[derive(PartialEq, Eq)]
 pub struct FrameRegisters {
     /// The underlying raw pointer to a [`CallFrame`] on the [`ValueStack`].
     ptr: *mut UntypedVal,
+    /// The bounds of the live values of the [`ValueStack`] that `ptr` points into.
+    ///
+    /// Under Miri all register accesses are checked against these bounds so that
+    /// broken register indices panic instead of accessing uninitialized or freed values.
+    #[cfg(miri)]
+    bounds: Range<*mut UntypedVal>,
 }
 
 impl Debug for FrameRegisters {
@@ -396,9 +502,23 @@ impl Debug for FrameRegisters {
 }
 
 impl FrameRegisters {
-    /// Creates a new [`FrameRegisters`].
-    fn new(ptr: *mut UntypedVal) -> Self {
-        Self { ptr }
+    /// Creates a new [`FrameRegisters`] pointing to the value at `offset` of `values`.
+    ///
+    /// # Safety
+    ///
+    /// The `offset` must be in bounds of the allocation of `values`.
+    unsafe fn new(values: &mut Vec<UntypedVal>, offset: usize) -> Self {
+        let ptr = unsafe { values.as_mut_ptr().add(offset) };
+        #[cfg(miri)]
+        let bounds = {
+            let start = values.as_mut_ptr();
+            start..start.wrapping_add(values.len())
+        };
+        Self {
+            ptr,
+            #[cfg(miri)]
+            bounds,
+        }
     }
 
     /// Returns the [`UntypedVal`] at the given [`Reg`].
@@ -422,7 +542,97 @@ impl FrameRegisters {
     }
 
     /// Returns the underlying pointer offset by the [`Reg`] index.
+    #[cfg(not(miri))]
     unsafe fn register_offset(&self, register: Reg) -> *mut UntypedVal {
         unsafe { self.ptr.offset(isize::from(i16::from(register))) }
     }
+
+    /// Returns the underlying pointer offset by the [`Reg`] index.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Reg`] index is out of bounds of the live values of the [`ValueStack`].
+    #[cfg(miri)]
+    unsafe fn register_offset(&self, register: Reg) -> *mut UntypedVal {
+        let ptr = self.ptr.wrapping_offset(isize::from(i16::from(register)));
+        assert!(
+            self.bounds.contains(&ptr),
+            "register {register:?} is out of bounds of the value stack",
+        );
+        ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the `len` cells following the last cell of `stack`.
+    fn spare(stack: &mut ValueStack, len: usize) -> Vec<UntypedVal> {
+        stack.values.spare_capacity_mut()[..len]
+            .iter()
+            // Safety: the cells have been initialized before they were freed.
+            .map(|cell| unsafe { cell.assume_init() })
+            .collect()
+    }
+
+    #[test]
+    fn zero_on_free() {
+        let mut stack = ValueStack::new(4, 100);
+        stack.set_zero_on_free();
+        let values = [1_u64, 2, 3, 4, 5, 6].map(UntypedVal::from);
+        stack.extend_by(2, |_| {}).unwrap();
+        stack.as_slice_mut().copy_from_slice(&values[..2]);
+        // Reallocation retains all live cells.
+        stack.extend_by(4, |_| {}).unwrap();
+        stack.as_slice_mut()[2..].copy_from_slice(&values[2..]);
+        assert_eq!(stack.as_slice(), &values[..]);
+        // Cells returned via `drop_return` are zeroed upon the next truncation.
+        assert_eq!(stack.drop_return(2), &values[4..]);
+        assert_eq!(spare(&mut stack, 2), &values[4..]);
+        stack.truncate(ValueStackOffset(1));
+        assert_eq!(spare(&mut stack, 5), [UntypedVal::from(0_u64); 5]);
+        assert_eq!(stack.as_slice(), &values[..1]);
+        stack.reset();
+        assert_eq!(spare(&mut stack, 6), [UntypedVal::from(0_u64); 6]);
+    }
+
+    #[test]
+    fn frame_registers() {
+        let mut stack = ValueStack::new(4, 100);
+        stack.extend_by(4, |_| {}).unwrap();
+        // Safety: the offset is in bounds of the value stack.
+        let mut sp = unsafe { stack.stack_ptr_at(ValueStackOffset(2)) };
+        // Safety: the registers are in bounds of the value stack.
+        unsafe {
+            sp.set(Reg::from(-2), UntypedVal::from(1_u64));
+            sp.set(Reg::from(1), UntypedVal::from(2_u64));
+            assert_eq!(sp.get(Reg::from(-2)), UntypedVal::from(1_u64));
+            assert_eq!(sp.get(Reg::from(1)), UntypedVal::from(2_u64));
+        }
+        assert_eq!(stack.as_slice()[0], UntypedVal::from(1_u64));
+        assert_eq!(stack.as_slice()[3], UntypedVal::from(2_u64));
+    }
+
+    #[test]
+    #[cfg(miri)]
+    #[should_panic = "out of bounds of the value stack"]
+    fn frame_registers_out_of_bounds() {
+        let mut stack = ValueStack::new(4, 100);
+        stack.extend_by(4, |_| {}).unwrap();
+        let sp = stack.root_stack_ptr();
+        // Safety: out of bounds register accesses panic under Miri.
+        unsafe { sp.get(Reg::from(-1)) };
+    }
+
+    #[test]
+    #[cfg(miri)]
+    #[should_panic = "out of bounds of the value stack"]
+    fn frame_registers_beyond_len() {
+        let mut stack = ValueStack::new(16, 100);
+        stack.extend_by(4, |_| {}).unwrap();
+        let sp = stack.root_stack_ptr();
+        // Safety: accesses to the spare capacity panic under Miri.
+        unsafe { sp.get(Reg::from(4)) };
+    }
 }
@@ -0,0 +1,243 @@
+use crate::{core::UntypedVal, value::WithType, AsContextMut, Error, Func, FuncType};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
+use arrayvec::ArrayVec;
+use core::fmt;
+
+#[cfg(doc)]
+use crate::{ir::Instruction, Engine, Linker};
+
+/// The maximum number of parameters or results of an extension instruction.
+///
+/// Imported functions with more parameters or results are not lowered to
+/// extension instructions but called as ordinary host functions instead.
+pub const MAX_EXTENSION_ARITY: usize = 16;
+
+/// The maximum number of extension instructions that are dispatched as a single batch.
+///
+/// Read more about batches in [`InstructionExtension::execute_batch`].
+pub const MAX_EXTENSION_BATCH: usize = 16;
+
+/// A set of instructions that extends the Wasmi engine.
+///
+/// Extensions allow embedders and downstream forks to add instructions to the Wasmi
+/// engine without patching the Wasmi translator or executor. They are registered to
+/// an [`Engine`] via [`Engine::with_extensions`].
+///
+/// # Translation
+///
+/// Wasm modules use extension instructions by importing functions from the
+/// [`InstructionExtension::module`] namespace. Calls to imported functions that
+/// are resolved via [`InstructionExtension::resolve`] are lowered to
+/// [`Instruction::Extension`] instructions which directly dispatch to
+/// [`InstructionExtension::execute`] without the overhead of host function calls.
+///
+/// # Linking
+///
+/// A [`Linker`] automatically defines resolved imports that it does not define itself
+/// as host functions that call [`InstructionExtension::execute`]. Those are used when
+/// the imported functions are not called directly, e.g. via `call_indirect` or by the host.
+/// Definitions of the [`Linker`] are ignored by direct calls to resolved imports.
+pub trait InstructionExtension: Send + Sync + 'static {
+    /// Returns the Wasm import module namespace of the extension.
+    fn module(&self) -> &str;
+
+    /// Resolves the imported function `name` of type `ty` to an opcode of the extension.
+    ///
+    /// Returns `None` if the extension does not provide an instruction for the import.
+    fn resolve(&self, name: &str, ty: &FuncType) -> Option<u16>;
+
+    /// Executes the extension instruction `opcode` with `params` and writes its `results`.
+    ///
+    /// The lengths of `params` and `results` match the function type of the
+    /// import that was resolved to `opcode`.
+    ///
+    /// # Errors
+    ///
+    /// If the execution traps.
+    fn execute(
+        &self,
+        opcode: u16,
+        params: &[UntypedVal],
+        results: &mut [UntypedVal],
+    ) -> Result<(), Error>;
+
+    /// Returns `true` if consecutive executions of `opcode` shall be dispatched as batches.
+    ///
+    /// Defaults to `false`. Read more in [`InstructionExtension::execute_batch`].
+    fn supports_batch(&self, opcode: u16) -> bool {
+        _ = opcode;
+        false
+    }
+
+    /// Executes `len` consecutive extension instructions `opcode` at once.
+    ///
+    /// The parameters and results of the `n`-th instruction of the batch are stored at
+    /// `params[n * len_params..][..len_params]` and `results[n * len_results..][..len_results]`
+    /// respectively where `len_params` and `len_results` match the function type of the
+    /// import that was resolved to `opcode`.
+    ///
+    /// This is only used for `opcode`s for which [`InstructionExtension::supports_batch`]
+    /// returns `true`. Up to [`MAX_EXTENSION_BATCH`] calls to the same resolved import
+    /// that directly follow each other in a Wasm function are then dispatched as a single
+    /// batch if none of them uses the results of a preceding call of the batch.
+    ///
+    /// # Note
+    ///
+    /// Batches are formed from adjacent instructions only, e.g. from unrolled loop bodies.
+    /// Calls that are executed by different iterations of a Wasm `loop` or that are separated
+    /// by other instructions are never batched. Use [`Func::new_batched`] to batch calls
+    /// to ordinary host functions instead.
+    ///
+    /// Defaults to calling [`InstructionExtension::execute`] for every instruction of the batch.
+    ///
+    /// # Errors
+    ///
+    /// If the execution of any instruction of the batch traps.
+    fn execute_batch(
+        &self,
+        opcode: u16,
+        len: usize,
+        params: &[UntypedVal],
+        results: &mut [UntypedVal],
+    ) -> Result<(), Error> {
+        let len_params = params.len() / len;
+        let len_results = results.len() / len;
+        for n in 0..len {
+            self.execute(
+                opcode,
+                &params[n * len_params..][..len_params],
+                &mut results[n * len_results..][..len_results],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The [`InstructionExtension`]s registered to an [`Engine`].
+#[derive(Default, Clone)]
+pub struct InstructionExtensions {
+    /// The registered extensions in the order of their registration.
+    extensions: Vec<Arc<dyn InstructionExtension>>,
+}
+
+impl fmt::Debug for InstructionExtensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.extensions.iter().map(|extension| extension.module()))
+            .finish()
+    }
+}
+
+/// An imported function that is resolved to an extension instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExtensionOp {
+    /// The index of the extension within its [`InstructionExtensions`].
+    pub extension: u8,
+    /// The opcode within the extension.
+    pub opcode: u16,
+    /// The number of results of the extension instruction.
+    pub len_results: u8,
+}
+
+impl InstructionExtensions {
+    /// Creates a new empty [`InstructionExtensions`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the `extension`.
+    ///
+    /// # Errors
+    ///
+    /// - If an extension with the same [`InstructionExtension::module`] is already registered.
+    /// - If more than 256 extensions are registered.
+    pub fn register(&mut self, extension: impl InstructionExtension) -> Result<&mut Self, Error> {
+        let module = extension.module();
+        if self.find(module).is_some() {
+            return Err(Error::new(format!(
+                "instruction extension for module `{module}` is already registered"
+            )));
+        }
+        if self.extensions.len() > usize::from(u8::MAX) {
+            return Err(Error::new(format!(
+                "cannot register more than 256 instruction extensions: {module}"
+            )));
+        }
+        self.extensions.push(Arc::new(extension));
+        Ok(self)
+    }
+
+    /// Returns `true` if no extensions are registered.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// Returns the index of the extension for `module` if any.
+    fn find(&self, module: &str) -> Option<usize> {
+        self.extensions
+            .iter()
+            .position(|extension| extension.module() == module)
+    }
+
+    /// Resolves the imported function `module::name` of type `ty` to an [`ExtensionOp`] if any.
+    pub(crate) fn resolve(&self, module: &str, name: &str, ty: &FuncType) -> Option<ExtensionOp> {
+        let index = self.find(module)?;
+        if ty.params().len() > MAX_EXTENSION_ARITY || ty.results().len() > MAX_EXTENSION_ARITY {
+            return None;
+        }
+        let opcode = self.extensions[index].resolve(name, ty)?;
+        Some(ExtensionOp {
+            extension: index as u8,
+            opcode,
+            len_results: ty.results().len() as u8,
+        })
+    }
+
+    /// Returns the [`InstructionExtension`] at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no extension at `index`.
+    pub(crate) fn get(&self, index: u8) -> &dyn InstructionExtension {
+        &*self.extensions[usize::from(index)]
+    }
+
+    /// Creates a host [`Func`] of type `ty` for the imported function `module::name`.
+    ///
+    /// Returns `None` if the import is not resolved to an extension instruction.
+    ///
+    /// The host [`Func`] is used whenever the import is not called directly.
+    pub(crate) fn host_func(
+        &self,
+        ctx: impl AsContextMut,
+        module: &str,
+        name: &str,
+        ty: &FuncType,
+    ) -> Option<Func> {
+        let ExtensionOp {
+            extension, opcode, ..
+        } = self.resolve(module, name, ty)?;
+        let extension = self.extensions[usize::from(extension)].clone();
+        let result_types: Box<[_]> = ty.results().into();
+        let func = Func::new(ctx, ty.clone(), move |_caller, params, results| {
+            let params = params
+                .iter()
+                .cloned()
+                .map(UntypedVal::from)
+                .collect::<ArrayVec<_, MAX_EXTENSION_ARITY>>();
+            let mut values = [UntypedVal::default(); MAX_EXTENSION_ARITY];
+            let values = &mut values[..results.len()];
+            extension.execute(opcode, &params, values)?;
+            for ((result, value), ty) in results.iter_mut().zip(values).zip(&result_types) {
+                *result = value.with_type(*ty);
+            }
+            Ok(())
+        });
+        Some(func)
+    }
+}
+
+/// The [`ExtensionOp`]s of the imported functions of a Wasm module.
+///
+/// Empty if the Wasm module has no imported functions resolved to extension instructions.
+pub(crate) type ExtensionOps = Box<[Option<ExtensionOp>]>;
@@ -0,0 +1,171 @@
+use crate::store::StoreIdx;
+use alloc::vec::Vec;
+use core::num::NonZeroU32;
+
+/// The fuel usage of a [`Store`] with a proportional share of the fuel of its [`Engine`].
+///
+/// Queried via [`Store::fuel_usage`].
+///
+/// [`Store`]: crate::Store
+/// [`Engine`]: crate::Engine
+/// [`Store::fuel_usage`]: crate::Store::fuel_usage
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FuelUsage {
+    /// The proportional share of the [`Store`](crate::Store).
+    share: NonZeroU32,
+    /// The fuel accounted to the [`Store`](crate::Store).
+    consumed: u64,
+    /// Is `true` if the [`Store`](crate::Store) exceeds its proportional share.
+    over_quota: bool,
+}
+
+impl FuelUsage {
+    /// Returns the proportional share of the [`Store`](crate::Store).
+    pub fn share(&self) -> NonZeroU32 {
+        self.share
+    }
+
+    /// Returns the fuel accounted to the [`Store`](crate::Store).
+    ///
+    /// # Note
+    ///
+    /// Stores that join the proportional-share accounting late start with the
+    /// consumption of the least served store scaled by their share.
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    /// Returns `true` if the [`Store`](crate::Store) exceeds its proportional share.
+    ///
+    /// Calls from the host into a [`Store`](crate::Store) that exceeds its share fail
+    /// until the other stores caught up.
+    pub fn is_over_quota(&self) -> bool {
+        self.over_quota
+    }
+}
+
+/// The fuel account of a [`Store`](crate::Store) in the [`FuelLedger`].
+#[derive(Debug, Copy, Clone)]
+struct FuelAccount {
+    /// The store owning the account.
+    store: StoreIdx,
+    /// The proportional share of the store.
+    share: NonZeroU32,
+    /// The fuel consumed by the store.
+    consumed: u64,
+}
+
+impl FuelAccount {
+    /// Returns `true` if `self` consumed less fuel than `other` relative to their shares.
+    fn is_behind(&self, other: &Self) -> bool {
+        u128::from(self.consumed) * u128::from(other.share.get())
+            < u128::from(other.consumed) * u128::from(self.share.get())
+    }
+}
+
+/// Accounts the fuel consumed by the stores of an [`Engine`](crate::Engine) relative to their shares.
+#[derive(Debug, Default)]
+pub struct FuelLedger {
+    /// The fuel accounts of all stores with a proportional share.
+    accounts: Vec<FuelAccount>,
+    /// The fuel a store may consume per unit of share ahead of the least served store.
+    ///
+    /// Shares are not enforced if this is `None`.
+    slack: Option<u64>,
+}
+
+impl FuelLedger {
+    /// Returns the fuel a store may consume per unit of share ahead of the least served store.
+    pub fn slack(&self) -> Option<u64> {
+        self.slack
+    }
+
+    /// Sets the fuel a store may consume per unit of share ahead of the least served store.
+    pub fn set_slack(&mut self, slack: Option<u64>) {
+        self.slack = slack;
+    }
+
+    /// Registers the `store` with its proportional `share`.
+    ///
+    /// New stores start at the consumption of the least served store scaled by their
+    /// `share` so that they do not starve the other stores in order to catch up.
+    pub fn register(&mut self, store: StoreIdx, share: NonZeroU32) {
+        if let Some(account) = self.account_mut(store) {
+            account.share = share;
+            return;
+        }
+        let consumed = self.least_served().map_or(0, |least| {
+            let scaled = u128::from(least.consumed) * u128::from(share.get())
+                / u128::from(least.share.get());
+            u64::try_from(scaled).unwrap_or(u64::MAX)
+        });
+        self.accounts.push(FuelAccount {
+            store,
+            share,
+            consumed,
+        });
+    }
+
+    /// Unregisters the `store`.
+    pub fn unregister(&mut self, store: StoreIdx) {
+        self.accounts.retain(|account| account.store != store);
+    }
+
+    /// Accounts `fuel` consumed by the `store`.
+    pub fn charge(&mut self, store: StoreIdx, fuel: u64) {
+        if let Some(account) = self.account_mut(store) {
+            account.consumed = account.consumed.saturating_add(fuel);
+        }
+    }
+
+    /// Returns the [`FuelUsage`] of the `store` if registered.
+    pub fn usage(&self, store: StoreIdx) -> Option<FuelUsage> {
+        let account = self.account(store)?;
+        Some(FuelUsage {
+            share: account.share,
+            consumed: account.consumed,
+            over_quota: self.is_over_quota(account),
+        })
+    }
+
+    /// Returns `true` if the `store` is registered and exceeds its proportional share.
+    pub fn is_store_over_quota(&self, store: StoreIdx) -> bool {
+        self.account(store)
+            .is_some_and(|account| self.is_over_quota(account))
+    }
+
+    /// Returns `true` if `account` exceeds its proportional share.
+    fn is_over_quota(&self, account: &FuelAccount) -> bool {
+        let (Some(slack), Some(least)) = (self.slack, self.least_served()) else {
+            return false;
+        };
+        // Checks `consumed / share > least.consumed / least.share + slack` without divisions.
+        let share = u128::from(account.share.get());
+        let least_share = u128::from(least.share.get());
+        u128::from(account.consumed) * least_share
+            > u128::from(least.consumed) * share + u128::from(slack) * share * least_share
+    }
+
+    /// Returns the account that consumed the least fuel relative to its share if any.
+    fn least_served(&self) -> Option<&FuelAccount> {
+        self.accounts.iter().reduce(|least, account| {
+            if account.is_behind(least) {
+                account
+            } else {
+                least
+            }
+        })
+    }
+
+    /// Returns a shared reference to the account of the `store` if any.
+    fn account(&self, store: StoreIdx) -> Option<&FuelAccount> {
+        self.accounts.iter().find(|account| account.store == store)
+    }
+
+    /// Returns an exclusive reference to the account of the `store` if any.
+    fn account_mut(&mut self, store: StoreIdx) -> Option<&mut FuelAccount> {
+        self.accounts
+            .iter_mut()
+            .find(|account| account.store == store)
+    }
+}
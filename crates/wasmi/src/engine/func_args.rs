@@ -81,7 +81,7 @@ impl<'a> FuncParams<'a> {
     ///
     /// If the length of hte `params_results` slice does not match the maximum
     /// of the `len_params` and `Len_results`.
-    pub(super) fn new(
+    pub(crate) fn new(
         params_results: &'a mut [UntypedVal],
         len_params: usize,
         len_results: usize,
@@ -94,6 +94,11 @@ impl<'a> FuncParams<'a> {
         }
     }
 
+    /// Returns the number of untyped function parameters.
+    pub(crate) fn len_params(&self) -> usize {
+        self.len_params
+    }
+
     /// Returns a slice over the untyped function parameters.
     fn params(&self) -> &[UntypedVal] {
         &self.params_results[..self.len_params]
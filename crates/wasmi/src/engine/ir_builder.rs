@@ -0,0 +1,353 @@
+use super::{code_map::CompiledFuncEntity, Engine};
+use crate::{
+    core::UntypedVal,
+    func::WasmFuncEntity,
+    ir::{Instruction, Reg, RegSpan, VisitRegs},
+    AsContextMut,
+    Error,
+    Func,
+    FuncType,
+    Instance,
+};
+use alloc::{format, vec::Vec};
+
+/// The version of the Wasmi IR that is accepted by [`IrFuncBuilder`].
+///
+/// # Note
+///
+/// The Wasmi IR is not covered by the semantic versioning guarantees of Wasmi.
+/// This version is bumped whenever the Wasmi IR changes in any way so that
+/// users of [`IrFuncBuilder`] notice changes upon construction instead of
+/// silently producing miscompiled functions.
+pub const IR_VERSION: u32 = 4;
+
+/// Builds Wasmi functions directly from Wasmi IR [`Instruction`]s.
+///
+/// This allows front-ends that perform their own register allocation to skip
+/// the Wasm encoding and decoding roundtrip entirely.
+///
+/// # Register Layout
+///
+/// - Function parameters are stored in registers `0..n` where `n` is the number of parameters.
+/// - Function local constant values are referred to via negative registers as returned by
+///   [`IrFuncBuilder::alloc_const`].
+/// - All other registers up to [`IrFuncBuilder::len_registers`] are free to use.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{core::ValType, ir::{Instruction, Reg}, *};
+/// # fn main() -> Result<(), wasmi::Error> {
+/// let engine = Engine::default();
+/// let mut store = Store::new(&engine, ());
+/// let module = Module::new(&engine, "(module)")?;
+/// let instance = Linker::new(&engine).instantiate(&mut store, &module)?.start(&mut store)?;
+/// let ty = FuncType::new([ValType::I32, ValType::I32], [ValType::I32]);
+/// let mut builder = IrFuncBuilder::new(IR_VERSION, ty)?;
+/// builder.len_registers(3);
+/// builder.push_instr(Instruction::i32_add(Reg::from(2), Reg::from(0), Reg::from(1)));
+/// builder.push_instr(Instruction::return_reg(Reg::from(2)));
+/// // Safety: the function only consists of well-formed instructions.
+/// let add = unsafe { builder.finish(&mut store, instance)? };
+/// let add = add.typed::<(i32, i32), i32>(&store)?;
+/// assert_eq!(add.call(&mut store, (20, 22))?, 42);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct IrFuncBuilder {
+    /// The function type of the built function.
+    ty: FuncType,
+    /// The number of non-constant registers including the function parameters.
+    len_registers: u16,
+    /// The instructions of the built function.
+    instrs: Vec<Instruction>,
+    /// The function local constant values in their allocation order.
+    consts: Vec<UntypedVal>,
+}
+
+impl IrFuncBuilder {
+    /// The maximum number of function local constant values.
+    const MAX_LEN_CONSTS: usize = i16::MAX as usize;
+
+    /// The maximum number of non-constant registers.
+    const MAX_LEN_REGISTERS: u16 = i16::MAX as u16 + 1;
+
+    /// Creates a new [`IrFuncBuilder`] for a function of type `ty`.
+    ///
+    /// The `version` is the [`IR_VERSION`] the user of the [`IrFuncBuilder`] was written for.
+    ///
+    /// # Errors
+    ///
+    /// If `version` does not match [`IR_VERSION`].
+    pub fn new(version: u32, ty: FuncType) -> Result<Self, Error> {
+        if version != IR_VERSION {
+            return Err(Error::new(format!(
+                "mismatching Wasmi IR version: expected {IR_VERSION} but found {version}"
+            )));
+        }
+        let len_registers = ty.params().len() as u16;
+        Ok(Self {
+            ty,
+            len_registers,
+            instrs: Vec::new(),
+            consts: Vec::new(),
+        })
+    }
+
+    /// Sets the number of non-constant registers used by the function including its parameters.
+    ///
+    /// Defaults to the number of function parameters.
+    pub fn len_registers(&mut self, len: u16) -> &mut Self {
+        self.len_registers = len;
+        self
+    }
+
+    /// Allocates the function local constant `value` and returns the [`Reg`] referring to it.
+    ///
+    /// # Errors
+    ///
+    /// If too many function local constant values have been allocated.
+    pub fn alloc_const(&mut self, value: impl Into<UntypedVal>) -> Result<Reg, Error> {
+        if self.consts.len() >= Self::MAX_LEN_CONSTS {
+            return Err(Error::new("too many function local constant values"));
+        }
+        let reg = Reg::from(-1 - self.consts.len() as i16);
+        self.consts.push(value.into());
+        Ok(reg)
+    }
+
+    /// Pushes `instr` to the function and returns its position.
+    ///
+    /// The position is the one branch offsets are relative to.
+    pub fn push_instr(&mut self, instr: Instruction) -> u32 {
+        let pos = self.instrs.len() as u32;
+        self.instrs.push(instr);
+        pos
+    }
+
+    /// Returns the number of instructions pushed so far.
+    pub fn len_instrs(&self) -> u32 {
+        self.instrs.len() as u32
+    }
+
+    /// Validates the function built so far.
+    ///
+    /// # Errors
+    ///
+    /// - If the function has no instructions.
+    /// - If there are fewer registers than function parameters.
+    /// - If an instruction refers to a register or function local constant that does not exist.
+    /// - If an instruction writes its result to a function local constant.
+    /// - If a branch instruction targets a position outside of the function.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.instrs.is_empty() {
+            return Err(Error::new("functions must have at least one instruction"));
+        }
+        if self.len_registers > Self::MAX_LEN_REGISTERS {
+            return Err(Error::new(format!(
+                "too many registers: {}",
+                self.len_registers
+            )));
+        }
+        if usize::from(self.len_registers) < self.ty.params().len() {
+            return Err(Error::new(format!(
+                "function has {} parameters but only {} registers",
+                self.ty.params().len(),
+                self.len_registers
+            )));
+        }
+        let len_instrs = self.instrs.len() as i64;
+        for (pos, instr) in self.instrs.iter().enumerate() {
+            let mut visitor = RegValidator {
+                len_registers: self.len_registers,
+                len_consts: self.consts.len(),
+                is_valid: true,
+            };
+            instr.clone().visit_regs(&mut visitor);
+            if !visitor.is_valid {
+                return Err(Error::new(format!(
+                    "invalid register use at position {pos}: {instr:?}"
+                )));
+            }
+            if let Some(offset) = branch_offset(instr) {
+                let target = pos as i64 + i64::from(offset);
+                if !(0..len_instrs).contains(&target) {
+                    return Err(Error::new(format!(
+                        "out of bounds branch target at position {pos}: {instr:?}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates and finishes the function and returns it as [`Func`] of `instance`.
+    ///
+    /// The function uses the entities of `instance` for all of its
+    /// instructions that refer to functions, globals, memories and tables.
+    ///
+    /// # Errors
+    ///
+    /// If [`IrFuncBuilder::validate`] fails.
+    ///
+    /// # Note
+    ///
+    /// Executing an instruction that is compiled out by the enabled crate features,
+    /// such as [`Instruction::F32Add`] with `no-floats` or [`Instruction::I32AddImm16`]
+    /// with `compact-dispatch`, traps with an error.
+    ///
+    /// # Panics
+    ///
+    /// If `instance` does not belong to the store of `ctx`.
+    ///
+    /// # Safety
+    ///
+    /// Unlike Wasm validation, [`IrFuncBuilder::validate`] cannot verify all invariants of
+    /// the Wasmi IR which Wasmi relies on during execution. The caller must guarantee that:
+    ///
+    /// - Instructions are followed by the instruction parameters they require,
+    ///   for example [`Instruction::RegisterList`] for instructions with many registers.
+    /// - Instruction parameters such as [`Instruction::RegisterList`] are never executed
+    ///   directly, neither by falling through nor by branching to them.
+    /// - Instructions only refer to entities of `instance` that exist.
+    /// - Instructions only operate on registers holding values of the expected types.
+    /// - All execution paths end in a return, tail call or trap instruction.
+    /// - The function returns values matching the results of its function type.
+    pub unsafe fn finish(
+        self,
+        mut ctx: impl AsContextMut,
+        instance: Instance,
+    ) -> Result<Func, Error> {
+        self.validate()?;
+        let ctx = ctx.as_context_mut();
+        let store = &mut ctx.store.inner;
+        _ = store.resolve_instance(&instance);
+        let engine: Engine = store.engine().clone();
+        let ty = engine.alloc_func_type(self.ty);
+        let engine_func = engine.alloc_funcs(1).get_or_panic(0);
+        let len_registers = self.len_registers + self.consts.len() as u16;
+        // Note: function local constant values are stored in reverse order
+        //       of their allocation in the function call frame.
+        engine.inner.init_func(
+            engine_func,
+            CompiledFuncEntity::new(len_registers, self.instrs, self.consts.into_iter().rev()),
+        );
+        let func = WasmFuncEntity::new(ty, engine_func, instance);
+        Ok(store.alloc_func(func.into()))
+    }
+}
+
+/// Checks that visited registers are within the bounds of the function.
+struct RegValidator {
+    /// The number of non-constant registers.
+    len_registers: u16,
+    /// The number of function local constant values.
+    len_consts: usize,
+    /// Is `false` if an invalid register has been visited.
+    is_valid: bool,
+}
+
+impl RegValidator {
+    /// Returns `true` if `reg` refers to an existing register or function local constant.
+    fn is_valid_input(&self, reg: Reg) -> bool {
+        let index = i16::from(reg);
+        if reg.is_const() {
+            return (index.unsigned_abs() as usize) <= self.len_consts;
+        }
+        (index as u16) < self.len_registers
+    }
+
+    /// Returns `true` if `len` registers starting at `span` refer to existing non-constant registers.
+    fn is_valid_span(&self, span: RegSpan, len: Option<u16>) -> bool {
+        let head = i16::from(span.head());
+        if head < 0 {
+            return false;
+        }
+        let len = len.unwrap_or(1);
+        u32::from(head as u16) + u32::from(len) <= u32::from(self.len_registers)
+    }
+}
+
+impl VisitRegs for RegValidator {
+    fn visit_result_reg(&mut self, reg: &mut Reg) {
+        self.is_valid &= !reg.is_const() && self.is_valid_input(*reg);
+    }
+
+    fn visit_result_regs(&mut self, regs: &mut RegSpan, len: Option<u16>) {
+        self.is_valid &= self.is_valid_span(*regs, len);
+    }
+
+    fn visit_input_reg(&mut self, reg: &mut Reg) {
+        self.is_valid &= self.is_valid_input(*reg);
+    }
+
+    fn visit_input_regs(&mut self, regs: &mut RegSpan, len: Option<u16>) {
+        self.is_valid &= self.is_valid_span(*regs, len);
+    }
+}
+
+/// Returns the branch offset of `instr` relative to its position if `instr` is a branch.
+#[rustfmt::skip]
+fn branch_offset(instr: &Instruction) -> Option<i32> {
+    use Instruction as I;
+    let offset = match instr {
+        I::Branch { offset } |
+        I::BranchTableTarget { offset, .. } |
+        I::BranchTableTargetNonOverlapping { offset, .. } => offset.to_i32(),
+        I::BranchI32And { offset, .. } |
+        I::BranchI32Or { offset, .. } |
+        I::BranchI32Xor { offset, .. } |
+        I::BranchI32AndEqz { offset, .. } |
+        I::BranchI32OrEqz { offset, .. } |
+        I::BranchI32XorEqz { offset, .. } |
+        I::BranchI32Eq { offset, .. } |
+        I::BranchI32Ne { offset, .. } |
+        I::BranchI32LtS { offset, .. } |
+        I::BranchI32LtU { offset, .. } |
+        I::BranchI32LeS { offset, .. } |
+        I::BranchI32LeU { offset, .. } |
+        I::BranchI64Eq { offset, .. } |
+        I::BranchI64Ne { offset, .. } |
+        I::BranchI64LtS { offset, .. } |
+        I::BranchI64LtU { offset, .. } |
+        I::BranchI64LeS { offset, .. } |
+        I::BranchI64LeU { offset, .. } |
+        I::BranchF32Eq { offset, .. } |
+        I::BranchF32Ne { offset, .. } |
+        I::BranchF32Lt { offset, .. } |
+        I::BranchF32Le { offset, .. } |
+        I::BranchF64Eq { offset, .. } |
+        I::BranchF64Ne { offset, .. } |
+        I::BranchF64Lt { offset, .. } |
+        I::BranchF64Le { offset, .. } |
+        I::BranchI32AndImm16 { offset, .. } |
+        I::BranchI32OrImm16 { offset, .. } |
+        I::BranchI32XorImm16 { offset, .. } |
+        I::BranchI32AndEqzImm16 { offset, .. } |
+        I::BranchI32OrEqzImm16 { offset, .. } |
+        I::BranchI32XorEqzImm16 { offset, .. } |
+        I::BranchI32EqImm16 { offset, .. } |
+        I::BranchI32NeImm16 { offset, .. } |
+        I::BranchI32LtSImm16Lhs { offset, .. } |
+        I::BranchI32LtSImm16Rhs { offset, .. } |
+        I::BranchI32LeSImm16Lhs { offset, .. } |
+        I::BranchI32LeSImm16Rhs { offset, .. } |
+        I::BranchI32LtUImm16Lhs { offset, .. } |
+        I::BranchI32LtUImm16Rhs { offset, .. } |
+        I::BranchI32LeUImm16Lhs { offset, .. } |
+        I::BranchI32LeUImm16Rhs { offset, .. } |
+        I::BranchI64EqImm16 { offset, .. } |
+        I::BranchI64NeImm16 { offset, .. } |
+        I::BranchI64LtSImm16Lhs { offset, .. } |
+        I::BranchI64LtSImm16Rhs { offset, .. } |
+        I::BranchI64LeSImm16Lhs { offset, .. } |
+        I::BranchI64LeSImm16Rhs { offset, .. } |
+        I::BranchI64LtUImm16Lhs { offset, .. } |
+        I::BranchI64LtUImm16Rhs { offset, .. } |
+        I::BranchI64LeUImm16Lhs { offset, .. } |
+        I::BranchI64LeUImm16Rhs { offset, .. } => i32::from(offset.to_i16()),
+        _ => return None,
+    };
+    Some(offset)
+}
@@ -21,6 +21,20 @@ pub enum EnforcedLimitsError {
     TooManyResults { limit: usize },
     /// When a Wasm module exceeds the average bytes per function limit.
     MinAvgBytesPerFunction { limit: u32, avg: u32 },
+    /// When a Wasm module section exceeds the section items limit.
+    TooManySectionItems { limit: u32 },
+    /// When a Wasm module exceeds the type limit.
+    TooManyTypes { limit: u32 },
+    /// When a Wasm module exceeds the import limit.
+    TooManyImports { limit: u32 },
+    /// When a `br_table` instruction exceeds the branch targets limit.
+    TooManyBrTableTargets { limit: u32 },
+    /// When a function body exceeds the control flow nesting depth limit.
+    TooDeeplyNested { limit: u32 },
+    /// When the translation of a Wasm module exceeds its compilation fuel.
+    OutOfCompilationFuel { limit: u64 },
+    /// When the translated Wasmi bytecode of a function exceeds the size limit.
+    TranslatedFuncTooLarge { limit: usize },
 }
 
 #[cfg(feature = "std")]
@@ -61,6 +75,32 @@ impl Display for EnforcedLimitsError {
                 "the Wasm module failed to meet the minimum average bytes per function of {limit}: \
                 avg={avg}"
             ),
+            Self::TooManySectionItems { limit } => write!(
+                f,
+                "a Wasm module section exceeds the limit of {limit} items"
+            ),
+            Self::TooManyTypes { limit } => {
+                write!(f, "the Wasm module exceeds the limit of {limit} types")
+            }
+            Self::TooManyImports { limit } => {
+                write!(f, "the Wasm module exceeds the limit of {limit} imports")
+            }
+            Self::TooManyBrTableTargets { limit } => write!(
+                f,
+                "a `br_table` instruction exceeds the limit of {limit} branch targets"
+            ),
+            Self::TooDeeplyNested { limit } => write!(
+                f,
+                "a function body exceeds the control flow nesting depth limit of {limit}"
+            ),
+            Self::OutOfCompilationFuel { limit } => write!(
+                f,
+                "the Wasm module exceeds the compilation fuel limit of {limit}"
+            ),
+            Self::TranslatedFuncTooLarge { limit } => write!(
+                f,
+                "a function exceeds the translated bytecode size limit of {limit} bytes"
+            ),
         }
     }
 }
@@ -70,7 +110,7 @@ impl Display for EnforcedLimitsError {
 /// By default no limits are enforced.
 ///
 /// [`Engine`]: crate::Engine
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct EnforcedLimits {
     /// Number of global variables a single Wasm module can have at most.
     ///
@@ -172,10 +212,63 @@ pub struct EnforcedLimits {
     /// [`Module::new`]: crate::Module::new
     /// [`Module::new_unchecked`]: crate::Module::new_unchecked
     pub(crate) min_avg_bytes_per_function: Option<AvgBytesPerFunctionLimit>,
+    /// Number of items a single Wasm module section can have at most.
+    ///
+    /// # Note
+    ///
+    /// - This is checked in [`Module::new`] or [`Module::new_unchecked`].
+    /// - This applies to all sections with items, e.g. the type, import, export or code section.
+    /// - `None` means the limit is not enforced.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::new_unchecked`]: crate::Module::new_unchecked
+    pub(crate) max_section_items: Option<u32>,
+    /// Number of types a single Wasm module can have at most.
+    ///
+    /// # Note
+    ///
+    /// - This is checked in [`Module::new`] or [`Module::new_unchecked`].
+    /// - `None` means the limit is not enforced.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::new_unchecked`]: crate::Module::new_unchecked
+    pub(crate) max_types: Option<u32>,
+    /// Number of imports a single Wasm module can have at most.
+    ///
+    /// # Note
+    ///
+    /// - This is checked in [`Module::new`] or [`Module::new_unchecked`].
+    /// - `None` means the limit is not enforced.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::new_unchecked`]: crate::Module::new_unchecked
+    pub(crate) max_imports: Option<u32>,
+    /// Number of branch targets a single `br_table` instruction can have at most.
+    ///
+    /// # Note
+    ///
+    /// - This is checked in [`Module::new`] or [`Module::new_unchecked`].
+    /// - The default branch target is not counted.
+    /// - `None` means the limit is not enforced.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::new_unchecked`]: crate::Module::new_unchecked
+    pub(crate) max_br_table_targets: Option<u32>,
+    /// Depth of nested control structures a single function body can have at most.
+    ///
+    /// # Note
+    ///
+    /// - This is checked in [`Module::new`] or [`Module::new_unchecked`].
+    /// - The implicit block of the function body itself is not counted.
+    /// - `None` means the limit is not enforced.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Module::new_unchecked`]: crate::Module::new_unchecked
+    pub(crate) max_nesting_depth: Option<u32>,
 }
 
 /// The limit for average bytes per function limit and the threshold at which it is enforced.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct AvgBytesPerFunctionLimit {
     /// The number of Wasm module bytes at which the limit is actually enforced.
     ///
@@ -216,6 +309,121 @@ impl EnforcedLimits {
                 // and should not be exceeded for non-malicous Wasm modules.
                 min_avg_bytes_per_function: 40,
             }),
+            max_section_items: Some(100_000),
+            max_types: Some(10_000),
+            max_imports: Some(10_000),
+            max_br_table_targets: Some(10_000),
+            max_nesting_depth: Some(1000),
         }
     }
+
+    /// Returns the maximum number of global variables of a single Wasm module if enforced.
+    pub fn max_globals(&self) -> Option<u32> {
+        self.max_globals
+    }
+
+    /// Returns the maximum number of functions of a single Wasm module if enforced.
+    pub fn max_functions(&self) -> Option<u32> {
+        self.max_functions
+    }
+
+    /// Returns the maximum number of tables of a single Wasm module if enforced.
+    pub fn max_tables(&self) -> Option<u32> {
+        self.max_tables
+    }
+
+    /// Returns the maximum number of element segments of a single Wasm module if enforced.
+    pub fn max_element_segments(&self) -> Option<u32> {
+        self.max_element_segments
+    }
+
+    /// Returns the maximum number of linear memories of a single Wasm module if enforced.
+    pub fn max_memories(&self) -> Option<u32> {
+        self.max_memories
+    }
+
+    /// Returns the maximum number of data segments of a single Wasm module if enforced.
+    pub fn max_data_segments(&self) -> Option<u32> {
+        self.max_data_segments
+    }
+
+    /// Returns the maximum number of parameters of functions and control structures if enforced.
+    pub fn max_params(&self) -> Option<usize> {
+        self.max_params
+    }
+
+    /// Returns the maximum number of results of functions and control structures if enforced.
+    pub fn max_results(&self) -> Option<usize> {
+        self.max_results
+    }
+
+    /// Returns the minimum average number of bytes per function if enforced.
+    pub fn min_avg_bytes_per_function(&self) -> Option<AvgBytesPerFunctionLimit> {
+        self.min_avg_bytes_per_function
+    }
+
+    /// Returns the maximum number of items of a single Wasm module section if enforced.
+    pub fn max_section_items(&self) -> Option<u32> {
+        self.max_section_items
+    }
+
+    /// Returns the maximum number of types of a single Wasm module if enforced.
+    pub fn max_types(&self) -> Option<u32> {
+        self.max_types
+    }
+
+    /// Returns the maximum number of imports of a single Wasm module if enforced.
+    pub fn max_imports(&self) -> Option<u32> {
+        self.max_imports
+    }
+
+    /// Returns the maximum number of branch targets of a `br_table` instruction if enforced.
+    pub fn max_br_table_targets(&self) -> Option<u32> {
+        self.max_br_table_targets
+    }
+
+    /// Returns the maximum depth of nested control structures of a function body if enforced.
+    pub fn max_nesting_depth(&self) -> Option<u32> {
+        self.max_nesting_depth
+    }
+
+    /// Sets the maximum number of items of a single Wasm module section.
+    ///
+    /// `None` means the limit is not enforced.
+    pub fn set_max_section_items(&mut self, limit: Option<u32>) -> &mut Self {
+        self.max_section_items = limit;
+        self
+    }
+
+    /// Sets the maximum number of types of a single Wasm module.
+    ///
+    /// `None` means the limit is not enforced.
+    pub fn set_max_types(&mut self, limit: Option<u32>) -> &mut Self {
+        self.max_types = limit;
+        self
+    }
+
+    /// Sets the maximum number of imports of a single Wasm module.
+    ///
+    /// `None` means the limit is not enforced.
+    pub fn set_max_imports(&mut self, limit: Option<u32>) -> &mut Self {
+        self.max_imports = limit;
+        self
+    }
+
+    /// Sets the maximum number of branch targets of a `br_table` instruction.
+    ///
+    /// `None` means the limit is not enforced.
+    pub fn set_max_br_table_targets(&mut self, limit: Option<u32>) -> &mut Self {
+        self.max_br_table_targets = limit;
+        self
+    }
+
+    /// Sets the maximum depth of nested control structures of a function body.
+    ///
+    /// `None` means the limit is not enforced.
+    pub fn set_max_nesting_depth(&mut self, limit: Option<u32>) -> &mut Self {
+        self.max_nesting_depth = limit;
+        self
+    }
 }
@@ -5,6 +5,6 @@ mod stack;
 mod tests;
 
 pub use self::{
-    engine::{EnforcedLimits, EnforcedLimitsError},
+    engine::{AvgBytesPerFunctionLimit, EnforcedLimits, EnforcedLimitsError},
     stack::StackLimits,
 };
@@ -14,7 +14,7 @@ const DEFAULT_MAX_VALUE_STACK_HEIGHT: usize = 1024 * DEFAULT_MIN_VALUE_STACK_HEI
 const DEFAULT_MAX_RECURSION_DEPTH: usize = 1024;
 
 /// The configured limits of the Wasm stack.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct StackLimits {
     /// The initial value stack height that the Wasm stack prepares.
     pub initial_value_stack_height: usize,
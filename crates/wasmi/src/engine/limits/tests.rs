@@ -1,6 +1,6 @@
 use self::engine::AvgBytesPerFunctionLimit;
 use super::*;
-use crate::{error::ErrorKind, Config, Engine, Error, Module};
+use crate::{error::ErrorKind, CompilationMode, Config, Engine, Error, Module};
 
 /// Parses and returns the Wasm module `wasm` with the given [`EnforcedLimits`] `limits`.
 fn parse_with(wasm: &str, limits: EnforcedLimits) -> Result<Module, Error> {
@@ -494,3 +494,210 @@ fn min_avg_code_bytes_ok_threshold() {
     };
     parse_with(wasm, limits).unwrap();
 }
+
+#[test]
+fn max_section_items_ok() {
+    let wasm = "
+        (module
+            (func (export \"a\"))
+            (func (export \"b\"))
+        )
+    ";
+    parse_with(
+        wasm,
+        EnforcedLimits {
+            max_section_items: Some(2),
+            ..EnforcedLimits::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn max_section_items_err() {
+    let wasm = "
+        (module
+            (func (export \"a\"))
+            (func (export \"b\"))
+            (func (export \"c\"))
+        )
+    ";
+    let limits = EnforcedLimits {
+        max_section_items: Some(2),
+        ..EnforcedLimits::default()
+    };
+    assert!(matches!(
+        parse_with(wasm, limits).unwrap_err().kind(),
+        ErrorKind::Limits(EnforcedLimitsError::TooManySectionItems { limit: 2 }),
+    ))
+}
+
+#[test]
+fn max_types_ok() {
+    let wasm = "
+        (module
+            (type (func))
+            (type (func (param i32)))
+        )
+    ";
+    parse_with(
+        wasm,
+        EnforcedLimits {
+            max_types: Some(2),
+            ..EnforcedLimits::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn max_types_err() {
+    let wasm = "
+        (module
+            (type (func))
+            (type (func (param i32)))
+            (type (func (param i64)))
+        )
+    ";
+    let limits = EnforcedLimits {
+        max_types: Some(2),
+        ..EnforcedLimits::default()
+    };
+    assert!(matches!(
+        parse_with(wasm, limits).unwrap_err().kind(),
+        ErrorKind::Limits(EnforcedLimitsError::TooManyTypes { limit: 2 }),
+    ))
+}
+
+#[test]
+fn max_imports_ok() {
+    let wasm = "
+        (module
+            (import \"env\" \"a\" (func))
+            (import \"env\" \"b\" (global i32))
+        )
+    ";
+    parse_with(
+        wasm,
+        EnforcedLimits {
+            max_imports: Some(2),
+            ..EnforcedLimits::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn max_imports_err() {
+    let wasm = "
+        (module
+            (import \"env\" \"a\" (func))
+            (import \"env\" \"b\" (global i32))
+            (import \"env\" \"c\" (memory 1))
+        )
+    ";
+    let limits = EnforcedLimits {
+        max_imports: Some(2),
+        ..EnforcedLimits::default()
+    };
+    assert!(matches!(
+        parse_with(wasm, limits).unwrap_err().kind(),
+        ErrorKind::Limits(EnforcedLimitsError::TooManyImports { limit: 2 }),
+    ))
+}
+
+#[test]
+fn max_br_table_targets_ok() {
+    let wasm = "
+        (module
+            (func (param i32)
+                (block (block (br_table 0 1 0 (local.get 0))))
+            )
+        )
+    ";
+    parse_with(
+        wasm,
+        EnforcedLimits {
+            max_br_table_targets: Some(2),
+            ..EnforcedLimits::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn max_br_table_targets_err() {
+    let wasm = "
+        (module
+            (func (param i32)
+                (block (block (br_table 0 1 0 1 (local.get 0))))
+            )
+        )
+    ";
+    let limits = EnforcedLimits {
+        max_br_table_targets: Some(2),
+        ..EnforcedLimits::default()
+    };
+    assert!(matches!(
+        parse_with(wasm, limits).unwrap_err().kind(),
+        ErrorKind::Limits(EnforcedLimitsError::TooManyBrTableTargets { limit: 2 }),
+    ))
+}
+
+#[test]
+fn max_nesting_depth_ok() {
+    let wasm = "
+        (module
+            (func
+                (block (loop))
+                (if (i32.const 1) (then (block)))
+            )
+        )
+    ";
+    parse_with(
+        wasm,
+        EnforcedLimits {
+            max_nesting_depth: Some(2),
+            ..EnforcedLimits::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn max_nesting_depth_err() {
+    let wasm = "
+        (module
+            (func
+                (block (loop (block)))
+            )
+        )
+    ";
+    let limits = EnforcedLimits {
+        max_nesting_depth: Some(2),
+        ..EnforcedLimits::default()
+    };
+    assert!(matches!(
+        parse_with(wasm, limits).unwrap_err().kind(),
+        ErrorKind::Limits(EnforcedLimitsError::TooDeeplyNested { limit: 2 }),
+    ))
+}
+
+#[test]
+fn max_nesting_depth_lazy() {
+    let wasm = "
+        (module
+            (func
+                (block (loop (block)))
+            )
+        )
+    ";
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    config.enforced_limits(*EnforcedLimits::default().set_max_nesting_depth(Some(2)));
+    let engine = Engine::new(&config);
+    assert!(matches!(
+        Module::new(&engine, wasm).unwrap_err().kind(),
+        ErrorKind::Limits(EnforcedLimitsError::TooDeeplyNested { limit: 2 }),
+    ))
+}
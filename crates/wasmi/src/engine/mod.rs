@@ -1,13 +1,21 @@
 //! The Wasmi interpreter.
 
 mod block_type;
+#[cfg(feature = "calibrate")]
+mod calibrate;
 mod code_map;
+mod compile_func;
 mod config;
 mod executor;
+mod extension;
+mod fuel_shares;
 mod func_args;
 mod func_types;
+#[cfg(feature = "ir-builder")]
+mod ir_builder;
 mod limits;
 mod resumable;
+mod self_check;
 mod traits;
 mod translator;
 mod utils;
@@ -15,12 +23,19 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "calibrate")]
+pub use self::calibrate::{FuelCalibration, OpcodeCost};
+#[cfg(feature = "ir-builder")]
+pub use self::ir_builder::{IrFuncBuilder, IR_VERSION};
 pub(crate) use self::{
     block_type::BlockType,
-    config::FuelCosts,
-    executor::Stack,
+    code_map::translated_func_size,
+    config::{StableHasher, WASM_PROPOSALS},
+    executor::{CachedCall, Stack},
+    extension::{ExtensionOp, ExtensionOps},
     func_args::{FuncFinished, FuncParams, FuncResults},
     func_types::DedupFuncType,
+    resumable::PreemptibleCall,
     translator::{
         FuncTranslationDriver,
         FuncTranslator,
@@ -32,24 +47,36 @@ pub(crate) use self::{
 };
 use self::{
     code_map::{CodeMap, CompiledFuncEntity},
+    fuel_shares::FuelLedger,
     func_types::FuncTypeRegistry,
     resumable::ResumableCallBase,
 };
 pub use self::{
     code_map::{EngineFunc, EngineFuncSpan, EngineFuncSpanIter},
-    config::{CompilationMode, Config},
+    config::{CompilationMode, Config, ConfigDiff, ConfigError, FuelCosts, UnsupportedProposals},
     executor::ResumableHostError,
-    limits::{EnforcedLimits, EnforcedLimitsError, StackLimits},
+    extension::{
+        InstructionExtension,
+        InstructionExtensions,
+        MAX_EXTENSION_ARITY,
+        MAX_EXTENSION_BATCH,
+    },
+    fuel_shares::FuelUsage,
+    limits::{AvgBytesPerFunctionLimit, EnforcedLimits, EnforcedLimitsError, StackLimits},
     resumable::{ResumableCall, ResumableInvocation, TypedResumableCall, TypedResumableInvocation},
     traits::{CallParams, CallResults},
     translator::{Instr, TranslationError},
 };
 use crate::{
     collections::arena::{ArenaIndex, GuardedEntity},
+    ir::Instruction,
     module::{FuncIdx, ModuleHeader},
+    symbols::{FuncSymbol, SymbolRegistry},
     Error,
     Func,
     FuncType,
+    Module,
+    ModuleSymbols,
     StoreContextMut,
 };
 use alloc::{
@@ -57,7 +84,7 @@ use alloc::{
     vec::Vec,
 };
 use core::sync::atomic::{AtomicU32, Ordering};
-use spin::{Mutex, RwLock};
+use spin::{Mutex, MutexGuard, RwLock};
 use wasmparser::{FuncToValidate, FuncValidatorAllocations, ValidatorResources};
 
 #[cfg(doc)]
@@ -138,11 +165,31 @@ impl Engine {
     ///
     /// Users should ues [`Engine::default`] to construct a default [`Engine`].
     pub fn new(config: &Config) -> Self {
+        Self::with_extensions(config, InstructionExtensions::new())
+    }
+
+    /// Creates a new [`Engine`] with the `config` and instruction `extensions`.
+    ///
+    /// Read more about extensions in [`InstructionExtension`].
+    pub fn with_extensions(config: &Config, extensions: InstructionExtensions) -> Self {
         Self {
-            inner: Arc::new(EngineInner::new(config)),
+            inner: Arc::new(EngineInner::new(config, extensions)),
         }
     }
 
+    /// Creates a new [`Engine`] with the `config` after validating it.
+    ///
+    /// Unlike [`Engine::new`] this reports conflicting Wasm proposals and other
+    /// misconfigurations upfront instead of during Wasm module translation.
+    ///
+    /// # Errors
+    ///
+    /// If [`Config::validate`] fails for `config`.
+    pub fn new_with_features(config: &Config) -> Result<Self, Error> {
+        config.validate()?;
+        Ok(Self::new(config))
+    }
+
     /// Creates an [`EngineWeak`] from the given [`Engine`].
     pub fn weak(&self) -> EngineWeak {
         EngineWeak {
@@ -155,6 +202,78 @@ impl Engine {
         self.inner.config()
     }
 
+    /// Returns the [`InstructionExtensions`] of the [`Engine`].
+    pub fn extensions(&self) -> &InstructionExtensions {
+        &self.inner.extensions
+    }
+
+    /// Sets the fuel a [`Store`] may consume per unit of its share ahead of the least served [`Store`].
+    ///
+    /// Stores of the [`Engine`] with a proportional share of fuel set via [`Store::set_fuel_share`]
+    /// are accounted the fuel they consume. With `Some(slack)` calls from the host into a [`Store`]
+    /// fail with [`FuelError::OverQuota`] while it is more than `slack` fuel per unit of share ahead
+    /// of the least served [`Store`], so that the other stores can catch up. With `None`, which is
+    /// the default, the fuel consumption is only accounted.
+    ///
+    /// [`Store::set_fuel_share`]: crate::Store::set_fuel_share
+    /// [`FuelError::OverQuota`]: crate::errors::FuelError::OverQuota
+    pub fn set_fuel_share_slack(&self, slack: Option<u64>) {
+        self.inner.fuel_ledger.lock().set_slack(slack);
+    }
+
+    /// Returns the fuel a [`Store`] may consume per unit of its share ahead of the least served [`Store`].
+    ///
+    /// Read more in [`Engine::set_fuel_share_slack`].
+    pub fn fuel_share_slack(&self) -> Option<u64> {
+        self.inner.fuel_ledger.lock().slack()
+    }
+
+    /// Returns the [`FuelLedger`] accounting the fuel consumed by the stores of the [`Engine`].
+    pub(crate) fn fuel_ledger(&self) -> MutexGuard<'_, FuelLedger> {
+        self.inner.fuel_ledger.lock()
+    }
+
+    /// Registers the `symbols` for all modules with the same [`Module::hash`] as `module`.
+    ///
+    /// Traps, profiles and traces of the functions of these modules are symbolicated
+    /// using the `symbols` regardless of the [`Store`] they originate from.
+    /// Replaces the previously registered [`ModuleSymbols`] if any.
+    ///
+    /// Read more in [`Func::symbol`].
+    pub fn register_symbols(&self, module: &Module, symbols: ModuleSymbols) {
+        self.inner.symbols.write().register(module.hash(), symbols);
+    }
+
+    /// Unregisters the symbols of all modules with the same [`Module::hash`] as `module`.
+    ///
+    /// Returns `true` if symbols have been registered.
+    pub fn unregister_symbols(&self, module: &Module) -> bool {
+        self.inner.symbols.write().unregister(module.hash())
+    }
+
+    /// Returns the [`ModuleSymbols`] registered for the modules with the same [`Module::hash`] as `module`.
+    pub fn module_symbols(&self, module: &Module) -> Option<ModuleSymbols> {
+        self.inner.symbols.read().get(module.hash()).cloned()
+    }
+
+    /// Records that the function bodies `funcs` belong to a module with `hash`.
+    pub(crate) fn register_module_hash(
+        &self,
+        funcs: EngineFuncSpan,
+        len_imported_funcs: u32,
+        hash: u64,
+    ) {
+        self.inner
+            .symbols
+            .write()
+            .add_module(funcs, len_imported_funcs, hash);
+    }
+
+    /// Resolves the [`FuncSymbol`] of the function body `func` if its module has registered symbols.
+    pub(crate) fn resolve_symbol(&self, func: EngineFunc) -> Option<FuncSymbol> {
+        self.inner.symbols.read().resolve(func)
+    }
+
     /// Returns `true` if both [`Engine`] references `a` and `b` refer to the same [`Engine`].
     pub fn same(a: &Engine, b: &Engine) -> bool {
         Arc::ptr_eq(&a.inner, &b.inner)
@@ -222,6 +341,71 @@ impl Engine {
         )
     }
 
+    /// Calls `f` with the Wasmi bytecode of the internal function `func`.
+    ///
+    /// # Note
+    ///
+    /// Compiles `func` first if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of `func` failed.
+    pub(crate) fn resolve_instrs<R>(
+        &self,
+        func: EngineFunc,
+        f: impl FnOnce(&[Instruction]) -> R,
+    ) -> Result<R, Error> {
+        let compiled_func = self.inner.code_map.get(None, func)?;
+        Ok(f(compiled_func.instrs()))
+    }
+
+    /// Calls `f` with the Wasmi bytecode, function local constant values
+    /// and number of registers of the internal function `func`.
+    ///
+    /// # Note
+    ///
+    /// Compiles `func` first if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of `func` failed.
+    pub(crate) fn resolve_compiled<R>(
+        &self,
+        func: EngineFunc,
+        f: impl FnOnce(&[Instruction], &[crate::core::UntypedVal], u16) -> R,
+    ) -> Result<R, Error> {
+        let compiled_func = self.inner.code_map.get(None, func)?;
+        Ok(f(
+            compiled_func.instrs(),
+            compiled_func.consts(),
+            compiled_func.len_registers(),
+        ))
+    }
+
+    /// Returns the number of registers of the internal function `func`.
+    ///
+    /// # Note
+    ///
+    /// Compiles `func` first if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of `func` failed.
+    pub(crate) fn resolve_len_registers(&self, func: EngineFunc) -> Result<u16, Error> {
+        let compiled_func = self.inner.code_map.get(None, func)?;
+        Ok(compiled_func.len_registers())
+    }
+
+    /// Compiles the internal function `func` if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of `func` failed.
+    pub(crate) fn ensure_compiled(&self, func: EngineFunc) -> Result<(), Error> {
+        self.inner.code_map.get(None, func)?;
+        Ok(())
+    }
+
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
     pub(crate) fn get_translation_allocs(&self) -> FuncTranslatorAllocations {
         self.inner.get_translation_allocs()
@@ -339,6 +523,78 @@ impl Engine {
             .execute_func_resumable(ctx, func, params, results)
     }
 
+    /// Rewinds to the paused execution captured by `stack` and resumes it.
+    ///
+    /// Calls `tracer` before every executed basic block and stores the execution
+    /// result into `results` upon a successful execution.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during the execution.
+    #[cfg(feature = "time-travel")]
+    pub(crate) fn rewind<T, Results>(
+        &self,
+        store: &mut crate::Store<T>,
+        stack: Stack,
+        tracer: &mut dyn FnMut(&crate::TraceStep),
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, Error>
+    where
+        Results: CallResults,
+    {
+        self.inner.rewind(store, stack, tracer, results)
+    }
+
+    /// Executes the given [`Func`] with parameters `params` and preempts it when running out of fuel.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    /// If the execution runs out of fuel at the start of a basic block it returns
+    /// the preempted state that allows to resume the execution after refueling.
+    ///
+    /// # Note
+    ///
+    /// Assumes that the `params` and `results` are well typed.
+    ///
+    /// # Errors
+    ///
+    /// - If the given `results` do not match the the length of the expected results of `func`.
+    /// - When encountering a Wasm or host trap during the execution of `func`.
+    #[inline]
+    pub(crate) fn execute_func_preemptible<T, Results>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        results: Results,
+    ) -> Result<PreemptibleCall<<Results as CallResults>::Results>, Error>
+    where
+        Results: CallResults,
+    {
+        self.inner
+            .execute_func_preemptible(ctx, func, params, results)
+    }
+
+    /// Resumes the execution preempted with `stack`.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    ///
+    /// # Errors
+    ///
+    /// - If the given `results` do not match the the length of the expected results.
+    /// - When encountering a Wasm or host trap during the execution.
+    #[inline]
+    pub(crate) fn resume_func_preempted<T, Results>(
+        &self,
+        ctx: StoreContextMut<T>,
+        stack: Stack,
+        results: Results,
+    ) -> Result<PreemptibleCall<<Results as CallResults>::Results>, Error>
+    where
+        Results: CallResults,
+    {
+        self.inner.resume_func_preempted(ctx, stack, results)
+    }
+
     /// Resumes the given `invocation` given the `params`.
     ///
     /// Stores the execution result into `results` upon a successful execution.
@@ -386,6 +642,8 @@ impl Engine {
 pub struct EngineInner {
     /// The [`Config`] of the engine.
     config: Config,
+    /// The instruction extensions of the engine.
+    extensions: InstructionExtensions,
     /// Stores information about all compiled functions.
     code_map: CodeMap,
     /// Deduplicated function types.
@@ -403,6 +661,10 @@ pub struct EngineInner {
     /// operate on. Therefore a Wasm engine is required to provide stacks and
     /// ideally recycles old ones since creation of a new stack is rather expensive.
     stacks: Mutex<EngineStacks>,
+    /// Accounts the fuel consumed by the stores with a proportional share of fuel.
+    fuel_ledger: Mutex<FuelLedger>,
+    /// The registered symbols of compiled Wasm modules.
+    symbols: RwLock<SymbolRegistry>,
 }
 
 /// Stacks to hold and distribute reusable allocations.
@@ -478,6 +740,8 @@ pub struct EngineStacks {
     limits: StackLimits,
     /// How many stacks should be kept for reuse at most.
     keep: usize,
+    /// Is `true` if freed cells of engine stacks are zeroed.
+    zero_on_free: bool,
 }
 
 impl EngineStacks {
@@ -485,8 +749,9 @@ impl EngineStacks {
     pub fn new(config: &Config) -> Self {
         Self {
             stacks: Vec::new(),
-            limits: config.stack_limits(),
-            keep: config.cached_stacks(),
+            limits: config.get_stack_limits(),
+            keep: config.get_cached_stacks(),
+            zero_on_free: config.get_zero_on_free(),
         }
     }
 
@@ -494,12 +759,21 @@ impl EngineStacks {
     pub fn reuse_or_new(&mut self) -> Stack {
         match self.stacks.pop() {
             Some(stack) => stack,
-            None => Stack::new(self.limits),
+            None => {
+                let mut stack = Stack::new(self.limits);
+                if self.zero_on_free {
+                    stack.values.set_zero_on_free();
+                }
+                stack
+            }
         }
     }
 
     /// Disose and recycle the `stack`.
-    pub fn recycle(&mut self, stack: Stack) {
+    pub fn recycle(&mut self, mut stack: Stack) {
+        if self.zero_on_free {
+            stack.reset();
+        }
         if stack.capacity() > 0 && self.stacks.len() < self.keep {
             self.stacks.push(stack);
         }
@@ -507,15 +781,18 @@ impl EngineStacks {
 }
 
 impl EngineInner {
-    /// Creates a new [`EngineInner`] with the given [`Config`].
-    fn new(config: &Config) -> Self {
+    /// Creates a new [`EngineInner`] with the given [`Config`] and `extensions`.
+    fn new(config: &Config, extensions: InstructionExtensions) -> Self {
         let engine_idx = EngineIdx::new();
         Self {
             config: *config,
+            extensions,
             code_map: CodeMap::new(config),
             func_types: RwLock::new(FuncTypeRegistry::new(engine_idx)),
             allocs: Mutex::new(ReusableAllocationStack::default()),
             stacks: Mutex::new(EngineStacks::new(config)),
+            fuel_ledger: Mutex::new(FuelLedger::default()),
+            symbols: RwLock::new(SymbolRegistry::default()),
         }
     }
 
@@ -1,7 +1,7 @@
 use super::Func;
 use crate::{
     engine::Stack,
-    func::CallResultsTuple,
+    func::{CallResultsTuple, WasmTyList},
     ir::RegSpan,
     AsContextMut,
     Engine,
@@ -9,7 +9,14 @@ use crate::{
     Val,
     WasmResults,
 };
-use core::{fmt, marker::PhantomData, mem::replace, ops::Deref};
+use alloc::boxed::Box;
+use core::{
+    any::Any,
+    fmt,
+    marker::PhantomData,
+    mem::replace,
+    ops::{Deref, DerefMut},
+};
 
 /// Returned by [`Engine`] methods for calling a function in a resumable way.
 ///
@@ -28,6 +35,15 @@ pub(crate) enum ResumableCallBase<T> {
     Resumable(ResumableInvocation),
 }
 
+/// Returned by [`Engine`] methods for calling a function that is preempted when running out of fuel.
+#[derive(Debug)]
+pub(crate) enum PreemptibleCall<T> {
+    /// The preemptible call has finished properly and returned a result.
+    Finished(T),
+    /// The preemptible call ran out of fuel and can be resumed using its [`Stack`].
+    OutOfFuel(Stack),
+}
+
 /// Returned by calling a [`Func`] in a resumable way.
 #[derive(Debug)]
 pub enum ResumableCall {
@@ -183,6 +199,25 @@ impl ResumableInvocation {
         &self.host_error
     }
 
+    /// Returns an exclusive reference to the payload of the host error if it is of type `P`.
+    ///
+    /// Returns `None` if the host function did not suspend the execution via
+    /// [`Error::suspend`] or if its payload is not of type `P`.
+    pub fn payload_mut<P>(&mut self) -> Option<&mut P>
+    where
+        P: Any,
+    {
+        self.host_error.payload_mut()
+    }
+
+    /// Takes the payload of the host error if any.
+    ///
+    /// Returns `None` if the host function did not suspend the execution via
+    /// [`Error::suspend`] or if its payload has already been taken.
+    pub fn take_payload(&mut self) -> Option<Box<dyn Any + Send>> {
+        self.host_error.take_payload()
+    }
+
     /// Returns the caller results [`RegSpan`].
     ///
     /// # Note
@@ -308,6 +343,48 @@ impl<Results> TypedResumableInvocation<Results> {
     }
 }
 
+impl<Results> TypedResumableInvocation<Results> {
+    /// Resumes the call to the [`TypedFunc`] with the given statically typed inputs.
+    ///
+    /// The `inputs` are fed back as the results of the host function that returned
+    /// the host error and thus must match its result types.
+    ///
+    /// Returns a resumable handle to the function invocation upon
+    /// encountering host errors with which it is possible to handle
+    /// the error and continue the execution as if no error occurred.
+    ///
+    /// # Errors
+    ///
+    /// - If the function resumption returned a Wasm [`Error`].
+    /// - If the types of `Inputs` do not match the result types of the erroneous host function.
+    ///
+    /// [`TypedFunc`]: [`crate::TypedFunc`]
+    pub fn resume_typed<T, Inputs>(
+        self,
+        mut ctx: impl AsContextMut<Data = T>,
+        inputs: Inputs,
+    ) -> Result<TypedResumableCall<Results>, Error>
+    where
+        Inputs: WasmResults,
+        Results: WasmResults,
+    {
+        let input_types = <Inputs as WasmTyList>::types();
+        self.engine
+            .resolve_func_type(self.host_func().ty_dedup(ctx.as_context()), |func_type| {
+                func_type.match_results(input_types.as_ref(), true)
+            })?;
+        self.engine
+            .clone()
+            .resume_func(
+                ctx.as_context_mut(),
+                self.invocation,
+                inputs,
+                <CallResultsTuple<Results>>::default(),
+            )
+            .map(TypedResumableCall::new)
+    }
+}
+
 impl<Results> Deref for TypedResumableInvocation<Results> {
     type Target = ResumableInvocation;
 
@@ -316,6 +393,12 @@ impl<Results> Deref for TypedResumableInvocation<Results> {
     }
 }
 
+impl<Results> DerefMut for TypedResumableInvocation<Results> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.invocation
+    }
+}
+
 impl<Results> fmt::Debug for TypedResumableInvocation<Results> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("TypedResumableInvocation")
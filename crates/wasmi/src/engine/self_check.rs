@@ -0,0 +1,153 @@
+use super::Engine;
+use crate::{core::TrapCode, Error, Instance, Module, Store};
+use alloc::format;
+
+/// The Wasm binary executed by [`Engine::self_check`].
+///
+/// Encodes the following Wasm module:
+///
+/// ```wat
+/// (module
+///     (type $i (func (param i32) (result i32)))
+///     (type $v (func))
+///     (memory 1)
+///     (table 1 funcref)
+///     (elem (i32.const 0) 0)
+///     (func (export "sum") (type $i) (local i32)
+///         (block (loop
+///             (br_if 1 (i32.eqz (local.get 0)))
+///             (local.set 1 (i32.add (local.get 1) (local.get 0)))
+///             (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+///             (br 0)))
+///         (local.get 1))
+///     (func (export "load") (type $i) (i32.load (local.get 0)))
+///     (func (export "div") (type $i) (i32.div_u (i32.const 1) (local.get 0)))
+///     (func (export "recurse") (type $i) (call 3 (local.get 0)))
+///     (func (export "call_indirect") (type $i)
+///         (call_indirect (type $i) (local.get 0) (i32.const 0)))
+///     (func (export "bad_signature") (type $i)
+///         (call_indirect (type $v) (i32.const 0)) (local.get 0))
+/// )
+/// ```
+#[rustfmt::skip]
+const SELF_CHECK_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, 0x01, 0x09, 0x02, 0x60,
+    0x01, 0x7F, 0x01, 0x7F, 0x60, 0x00, 0x00, 0x03, 0x07, 0x06, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x04, 0x04, 0x01, 0x70, 0x00, 0x01, 0x05, 0x03,
+    0x01, 0x00, 0x01, 0x07, 0x3E, 0x06, 0x03, 0x73, 0x75, 0x6D, 0x00, 0x00,
+    0x04, 0x6C, 0x6F, 0x61, 0x64, 0x00, 0x01, 0x03, 0x64, 0x69, 0x76, 0x00,
+    0x02, 0x07, 0x72, 0x65, 0x63, 0x75, 0x72, 0x73, 0x65, 0x00, 0x03, 0x0D,
+    0x63, 0x61, 0x6C, 0x6C, 0x5F, 0x69, 0x6E, 0x64, 0x69, 0x72, 0x65, 0x63,
+    0x74, 0x00, 0x04, 0x0D, 0x62, 0x61, 0x64, 0x5F, 0x73, 0x69, 0x67, 0x6E,
+    0x61, 0x74, 0x75, 0x72, 0x65, 0x00, 0x05, 0x09, 0x07, 0x01, 0x00, 0x41,
+    0x00, 0x0B, 0x01, 0x00, 0x0A, 0x4E, 0x06, 0x21, 0x01, 0x01, 0x7F, 0x02,
+    0x40, 0x03, 0x40, 0x20, 0x00, 0x45, 0x0D, 0x01, 0x20, 0x01, 0x20, 0x00,
+    0x6A, 0x21, 0x01, 0x20, 0x00, 0x41, 0x01, 0x6B, 0x21, 0x00, 0x0C, 0x00,
+    0x0B, 0x0B, 0x20, 0x01, 0x0B, 0x07, 0x00, 0x20, 0x00, 0x28, 0x02, 0x00,
+    0x0B, 0x07, 0x00, 0x41, 0x01, 0x20, 0x00, 0x6E, 0x0B, 0x06, 0x00, 0x20,
+    0x00, 0x10, 0x03, 0x0B, 0x09, 0x00, 0x20, 0x00, 0x41, 0x00, 0x11, 0x00,
+    0x00, 0x0B, 0x09, 0x00, 0x41, 0x00, 0x11, 0x01, 0x00, 0x20, 0x00, 0x0B,
+];
+
+/// A check of [`Engine::self_check`] calling the exported function `func` with `input`.
+struct SelfCheck {
+    /// The name of the checked property.
+    name: &'static str,
+    /// The name of the called function of [`SELF_CHECK_WASM`].
+    func: &'static str,
+    /// The input of the called function.
+    input: i32,
+    /// The expected result or trap of the call.
+    expected: Result<i32, TrapCode>,
+}
+
+/// The checks performed by [`Engine::self_check`].
+const SELF_CHECKS: &[SelfCheck] = &[
+    SelfCheck {
+        name: "arithmetic and control flow",
+        func: "sum",
+        input: 100,
+        expected: Ok(5050),
+    },
+    SelfCheck {
+        name: "memory access",
+        func: "load",
+        input: 65532,
+        expected: Ok(0),
+    },
+    SelfCheck {
+        name: "memory bounds",
+        func: "load",
+        input: 65533,
+        expected: Err(TrapCode::MemoryOutOfBounds),
+    },
+    SelfCheck {
+        name: "division by zero",
+        func: "div",
+        input: 0,
+        expected: Err(TrapCode::IntegerDivisionByZero),
+    },
+    SelfCheck {
+        name: "indirect call",
+        func: "call_indirect",
+        input: 10,
+        expected: Ok(55),
+    },
+    SelfCheck {
+        name: "indirect call signature",
+        func: "bad_signature",
+        input: 0,
+        expected: Err(TrapCode::BadSignature),
+    },
+    SelfCheck {
+        name: "stack overflow",
+        func: "recurse",
+        input: 0,
+        expected: Err(TrapCode::StackOverflow),
+    },
+];
+
+impl Engine {
+    /// Runs the sandbox self-tests of the [`Engine`].
+    ///
+    /// Executes a built-in Wasm module with the [`Config`] of the [`Engine`] and checks
+    /// that arithmetic, control flow and indirect calls compute the expected results and
+    /// that out of bounds memory accesses, divisions by zero, indirect call signature
+    /// mismatches and unbounded recursion trap as required by the sandbox.
+    ///
+    /// Run this once at startup when qualifying Wasmi for safety-critical use. Enable the
+    /// `checked-execution` crate feature to additionally assert the runtime invariants of
+    /// the executor during the self-tests and all other executions.
+    ///
+    /// # Errors
+    ///
+    /// If any of the self-tests fails or the built-in Wasm module cannot be executed with
+    /// the [`Config`] of the [`Engine`], e.g. because its Wasm features are disabled.
+    ///
+    /// [`Config`]: crate::Config
+    pub fn self_check(&self) -> Result<(), Error> {
+        let module = Module::new_builtin(self, SELF_CHECK_WASM)?;
+        let mut store = Store::new(self, ());
+        if self.config().get_consume_fuel() {
+            store.set_fuel(u64::MAX)?;
+        }
+        let instance = Instance::new(&mut store, &module, &[])?;
+        for check in SELF_CHECKS {
+            let func = instance.get_typed_func::<i32, i32>(&store, check.func)?;
+            let found = match func.call(&mut store, check.input) {
+                Ok(result) => Ok(result),
+                Err(error) => match error.as_trap_code() {
+                    Some(trap_code) => Err(trap_code),
+                    None => return Err(error),
+                },
+            };
+            if found != check.expected {
+                return Err(Error::new(format!(
+                    "engine self-check `{}` failed: expected {:?} but found {found:?}",
+                    check.name, check.expected,
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     engine::{code_map::CompiledFuncEntity, WasmTranslator},
     Error,
 };
-use wasmparser::{BinaryReader, FunctionBody};
+use wasmparser::{BinaryReader, FunctionBody, Operator, OperatorsReader, WasmFeatures};
 
 /// Translates Wasm bytecode into Wasmi bytecode for a single Wasm function.
 pub struct FuncTranslationDriver<'parser, T> {
@@ -40,7 +40,8 @@ where
         mut self,
         finalize: impl FnOnce(CompiledFuncEntity),
     ) -> Result<T::Allocations, Error> {
-        if self.translator.setup(self.bytes)? {
+        let offset = self.func_body.range().start;
+        if self.translator.setup(offset, self.bytes)? {
             let allocations = self.translator.finish(finalize)?;
             return Ok(allocations);
         }
@@ -78,13 +79,55 @@ where
     ///
     /// Returns the offset of the `End` Wasm operator.
     fn translate_operators(&mut self) -> Result<usize, Error> {
+        let trapping_features = self.translator.trapping_features();
         let mut reader = self.func_body.get_operators_reader()?;
         while !reader.eof() {
             let pos = reader.original_position();
             self.translator.update_pos(pos);
+            if !trapping_features.is_empty() && is_trapping(&reader, trapping_features)? {
+                self.translator
+                    .translate_unsupported_operator(&mut reader)?;
+                continue;
+            }
             reader.visit_operator(&mut self.translator)??;
         }
         reader.ensure_end()?;
         Ok(reader.original_position())
     }
 }
+
+/// Returns `true` if the next operator of `reader` belongs to one of the `trapping_features`.
+fn is_trapping(reader: &OperatorsReader, trapping_features: WasmFeatures) -> Result<bool, Error> {
+    let operator = reader.clone().read()?;
+    let Some(feature) = operator_feature(&operator) else {
+        return Ok(false);
+    };
+    Ok(trapping_features.contains(feature))
+}
+
+macro_rules! define_operator_proposal {
+    ( $( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $ann:tt )* ) => {
+        /// Returns the name of the Wasm proposal that introduced `operator`.
+        fn operator_proposal(operator: &Operator) -> &'static str {
+            #[allow(unreachable_patterns)]
+            match operator {
+                $( Operator::$op { .. } => stringify!($proposal), )*
+                _ => "unknown",
+            }
+        }
+    };
+}
+wasmparser::for_each_visit_operator!(define_operator_proposal);
+
+/// Returns the [`WasmFeatures`] that enables `operator` if it can be translated into a trap.
+fn operator_feature(operator: &Operator) -> Option<WasmFeatures> {
+    let feature = match operator_proposal(operator) {
+        "sign_extension" => WasmFeatures::SIGN_EXTENSION,
+        "saturating_float_to_int" => WasmFeatures::SATURATING_FLOAT_TO_INT,
+        "bulk_memory" => WasmFeatures::BULK_MEMORY,
+        "reference_types" => WasmFeatures::REFERENCE_TYPES,
+        "tail_call" => WasmFeatures::TAIL_CALL,
+        _ => return None,
+    };
+    Some(feature)
+}
@@ -29,6 +29,8 @@ pub enum TranslationError {
     TooManyFunctionParams,
     /// The function failed to compiled lazily.
     LazyCompilationFailed,
+    /// The streaming compilation of a Wasm module has been cancelled.
+    Cancelled,
 }
 
 impl TranslationError {
@@ -103,6 +105,9 @@ impl Display for TranslationError {
                     "lazy function compilation encountered a Wasm validation or translation error"
                 )
             }
+            Self::Cancelled => {
+                write!(f, "the streaming compilation has been cancelled")
+            }
         }
     }
 }
@@ -99,6 +99,26 @@ pub struct InstrEncoder {
     /// defragmentation of the register space due to `local.set` register
     /// preservations.
     notified_preservation: Option<Instr>,
+    /// The out-of-line cold stubs encoded after the function body.
+    cold_stubs: Vec<ColdStub>,
+    /// The [`Instruction`] words of all cold stubs in order.
+    cold_instrs: Vec<Instruction>,
+}
+
+/// An out-of-line sequence of [`Instruction`] words that is encoded after the function body.
+///
+/// # Note
+///
+/// Cold stubs move the copies of unlikely taken conditional branches out of the
+/// way so that the likely path falls through without executing any branch.
+#[derive(Debug, Copy, Clone)]
+struct ColdStub {
+    /// The label that is pinned to the first [`Instruction`] of the cold stub.
+    label: LabelRef,
+    /// The number of [`Instruction`] words of the cold stub.
+    len: usize,
+    /// The label of the branch target at the end of the cold stub.
+    target: LabelRef,
 }
 
 /// The sequence of encoded [`Instruction`].
@@ -178,10 +198,19 @@ impl InstrSequence {
     /// # Note
     ///
     /// The [`InstrSequence`] will be in an empty state after this operation.
-    pub fn drain(&mut self) -> Drain<Instruction> {
+    pub fn drain(&mut self) -> Drain<'_, Instruction> {
         self.instrs.drain(..)
     }
 
+    /// Returns an iterator over the [`Instruction`] words starting at `start` and removes them.
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds for [`InstrSequence`].
+    pub fn drain_from(&mut self, start: Instr) -> Drain<'_, Instruction> {
+        self.instrs.drain(start.into_usize()..)
+    }
+
     /// Returns a slice to the sequence of [`Instruction`] starting at `start`.
     ///
     /// # Panics
@@ -208,6 +237,8 @@ impl InstrEncoder {
         self.labels.reset();
         self.reset_last_instr();
         self.notified_preservation = None;
+        self.cold_stubs.clear();
+        self.cold_instrs.clear();
     }
 
     /// Resets the [`Instr`] last created via [`InstrEncoder::push_instr`].
@@ -230,7 +261,7 @@ impl InstrEncoder {
     /// # Note
     ///
     /// The [`InstrEncoder`] will be in an empty state after this operation.
-    pub fn drain_instrs(&mut self) -> Drain<Instruction> {
+    pub fn drain_instrs(&mut self) -> Drain<'_, Instruction> {
         self.instrs.drain()
     }
 
@@ -308,6 +339,63 @@ impl InstrEncoder {
         Ok(())
     }
 
+    /// Encodes an unlikely taken conditional branch to `label` with `values` copied to `results`.
+    ///
+    /// # Note
+    ///
+    /// The copies and the branch to `label` are encoded into a cold stub that is
+    /// placed after the function body via [`InstrEncoder::encode_cold_stubs`].
+    /// This way the likely path of the conditional branch falls through.
+    pub fn encode_cold_branch_nez(
+        &mut self,
+        stack: &mut ValueStack,
+        condition: Reg,
+        label: LabelRef,
+        results: BoundedRegSpan,
+        values: &[TypedProvider],
+        fuel_info: FuelInfo,
+    ) -> Result<(), Error> {
+        let stub = self.new_label();
+        self.encode_branch_nez(stack, condition, stub)?;
+        let last_instr = self.last_instr;
+        let start = self.instrs.next_instr();
+        self.encode_copies(stack, results, values, fuel_info)?;
+        self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
+        let len_instrs = self.cold_instrs.len();
+        self.cold_instrs.extend(self.instrs.drain_from(start));
+        self.cold_stubs.push(ColdStub {
+            label: stub,
+            len: self.cold_instrs.len() - len_instrs,
+            target: label,
+        });
+        // Note: the copies have been moved into the cold stub and thus cannot be merged.
+        self.last_instr = last_instr;
+        Ok(())
+    }
+
+    /// Encodes all cold stubs after the function body.
+    ///
+    /// # Note
+    ///
+    /// This must be called after the function body has been translated
+    /// and before the register space is defragmented.
+    pub fn encode_cold_stubs(&mut self) -> Result<(), Error> {
+        let mut start = 0;
+        for index in 0..self.cold_stubs.len() {
+            let stub = self.cold_stubs[index];
+            self.pin_label(stub.label);
+            for &instr in &self.cold_instrs[start..start + stub.len] {
+                self.instrs.push(instr)?;
+            }
+            start += stub.len;
+            let offset = self.try_resolve_label(stub.target)?;
+            self.instrs.push(Instruction::branch(offset))?;
+        }
+        self.cold_stubs.clear();
+        self.cold_instrs.clear();
+        Ok(())
+    }
+
     /// Push the [`Instruction`] to the [`InstrEncoder`].
     pub fn push_instr(&mut self, instr: Instruction) -> Result<Instr, Error> {
         let last_instr = self.instrs.push(instr)?;
@@ -633,8 +721,20 @@ impl InstrEncoder {
                 let reg0 = stack.provider2reg(v0)?;
                 let reg1 = stack.provider2reg(v1)?;
                 let reg2 = stack.provider2reg(v2)?;
-                self.push_instr(Instruction::return_many_ext(reg0, reg1, reg2))?;
-                self.encode_register_list(stack, rest)?;
+                let instr = match rest.len() {
+                    1 => Instruction::return_reg4_ext(reg0, reg1, reg2),
+                    2 => Instruction::return_reg5_ext(reg0, reg1, reg2),
+                    3 => Instruction::return_reg6_ext(reg0, reg1, reg2),
+                    4 => Instruction::return_reg7_ext(reg0, reg1, reg2),
+                    5 => Instruction::return_reg8_ext(reg0, reg1, reg2),
+                    _ => {
+                        self.push_instr(Instruction::return_many_ext(reg0, reg1, reg2))?;
+                        self.encode_register_list(stack, rest)?;
+                        return Ok(());
+                    }
+                };
+                self.push_instr(instr)?;
+                self.encode_register_params(stack, rest)?;
                 return Ok(());
             }
         };
@@ -756,6 +856,38 @@ impl InstrEncoder {
         Ok(())
     }
 
+    /// Encode the given slice of [`TypedProvider`] as fixed [`Reg`] parameters.
+    ///
+    /// # Note
+    ///
+    /// This is used for [`Instruction::ReturnReg4`] up to [`Instruction::ReturnReg8`]
+    /// which encode their remaining values as an optional [`Instruction::Register3`]
+    /// followed by an [`Instruction::Register`], [`Instruction::Register2`] or
+    /// [`Instruction::Register3`] so that they can be decoded at fixed offsets.
+    fn encode_register_params(
+        &mut self,
+        stack: &mut ValueStack,
+        inputs: &[TypedProvider],
+    ) -> Result<(), Error> {
+        debug_assert!((1..=6).contains(&inputs.len()));
+        for params in inputs.chunks(3) {
+            let instr = match params {
+                [v0] => Instruction::register(stack.provider2reg(v0)?),
+                [v0, v1] => {
+                    Instruction::register2_ext(stack.provider2reg(v0)?, stack.provider2reg(v1)?)
+                }
+                [v0, v1, v2] => Instruction::register3_ext(
+                    stack.provider2reg(v0)?,
+                    stack.provider2reg(v1)?,
+                    stack.provider2reg(v2)?,
+                ),
+                _ => unreachable!("chunks are never empty or larger than 3"),
+            };
+            self.instrs.push(instr)?;
+        }
+        Ok(())
+    }
+
     /// Encode a `local.set` or `local.tee` instruction.
     ///
     /// This also applies an optimization in that the previous instruction
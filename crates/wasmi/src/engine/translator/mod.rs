@@ -43,7 +43,7 @@ pub use self::{
 use super::code_map::CompiledFuncEntity;
 use crate::{
     core::{TrapCode, Typed, TypedVal, UntypedVal, ValType},
-    engine::{config::FuelCosts, BlockType, EngineFunc},
+    engine::{config::FuelCosts, translated_func_size, BlockType, EnforcedLimitsError, EngineFunc},
     ir::{
         index,
         AnyConst16,
@@ -58,7 +58,7 @@ use crate::{
         ShiftAmount,
         Sign,
     },
-    module::{FuncIdx, FuncTypeIdx, ModuleHeader},
+    module::{BranchHint, FuncIdx, FuncTypeIdx, ModuleHeader},
     Engine,
     Error,
     ExternRef,
@@ -74,6 +74,7 @@ use wasmparser::{
     FuncToValidate,
     FuncValidatorAllocations,
     MemArg,
+    OperatorsReader,
     ValidatorResources,
     VisitOperator,
     WasmFeatures,
@@ -206,11 +207,27 @@ pub trait WasmTranslator<'parser>: VisitOperator<'parser, Output = Result<(), Er
     ///   Wasm function body (including local variables).
     /// - Also `module` must be a reference to the Wasm module header that is going to be
     ///   used for translation of the Wasm function body.
-    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error>;
+    /// - The `offset` is the byte position of the Wasm function body with respect to the
+    ///   positions given to [`WasmTranslator::update_pos`].
+    fn setup(&mut self, offset: usize, bytes: &[u8]) -> Result<bool, Error>;
 
     /// Returns a reference to the [`WasmFeatures`] used by the [`WasmTranslator`].
     fn features(&self) -> WasmFeatures;
 
+    /// Returns the [`WasmFeatures`] of Wasm proposals whose operators are translated into traps.
+    fn trapping_features(&self) -> WasmFeatures;
+
+    /// Translates the next Wasm operator of `reader` into a trap.
+    ///
+    /// # Note
+    ///
+    /// This is used for operators of Wasm proposals that are part of
+    /// [`WasmTranslator::trapping_features`]. The operator is still validated if necessary.
+    fn translate_unsupported_operator(
+        &mut self,
+        reader: &mut OperatorsReader<'parser>,
+    ) -> Result<(), Error>;
+
     /// Translates the given local variables for the translated function.
     fn translate_locals(
         &mut self,
@@ -287,8 +304,8 @@ where
 {
     type Allocations = ReusableAllocations<T::Allocations>;
 
-    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error> {
-        self.translator.setup(bytes)?;
+    fn setup(&mut self, offset: usize, bytes: &[u8]) -> Result<bool, Error> {
+        self.translator.setup(offset, bytes)?;
         // Note: Wasm validation always need to be driven, therefore returning `Ok(false)`
         //       even if the underlying Wasm translator does not need a translation driver.
         Ok(false)
@@ -298,6 +315,21 @@ where
         self.translator.features()
     }
 
+    fn trapping_features(&self) -> WasmFeatures {
+        self.translator.trapping_features()
+    }
+
+    fn translate_unsupported_operator(
+        &mut self,
+        reader: &mut OperatorsReader<'parser>,
+    ) -> Result<(), Error> {
+        let offset = self.current_pos();
+        reader
+            .clone()
+            .visit_operator(&mut self.validator.visitor(offset))??;
+        self.translator.translate_unsupported_operator(reader)
+    }
+
     fn translate_locals(
         &mut self,
         amount: u32,
@@ -316,6 +348,7 @@ where
 
     fn update_pos(&mut self, pos: usize) {
         self.pos = pos;
+        self.translator.update_pos(pos);
     }
 
     fn finish(
@@ -489,7 +522,7 @@ impl LazyFuncTranslator {
 impl WasmTranslator<'_> for LazyFuncTranslator {
     type Allocations = ();
 
-    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error> {
+    fn setup(&mut self, _offset: usize, bytes: &[u8]) -> Result<bool, Error> {
         self.module
             .engine()
             .upgrade()
@@ -514,6 +547,21 @@ impl WasmTranslator<'_> for LazyFuncTranslator {
         self.validation.features()
     }
 
+    #[inline]
+    fn trapping_features(&self) -> WasmFeatures {
+        // Note: operators are translated lazily by the `FuncTranslator` instead.
+        WasmFeatures::empty()
+    }
+
+    #[inline]
+    fn translate_unsupported_operator(
+        &mut self,
+        reader: &mut OperatorsReader,
+    ) -> Result<(), Error> {
+        reader.read()?;
+        Ok(())
+    }
+
     #[inline]
     fn translate_locals(
         &mut self,
@@ -585,14 +633,21 @@ pub struct FuncTranslator {
     ///
     /// `None` if fuel metering is disabled.
     fuel_costs: Option<FuelCosts>,
+    /// The byte position of the translated Wasm function body.
+    body_offset: usize,
+    /// The byte position of the currently translated Wasm operator.
+    pos: usize,
+    /// The number of Wasm `loop`s of the function body translated so far.
+    len_loops: u32,
     /// The reusable data structures of the [`FuncTranslator`].
     alloc: FuncTranslatorAllocations,
 }
 
-impl WasmTranslator<'_> for FuncTranslator {
+impl<'parser> WasmTranslator<'parser> for FuncTranslator {
     type Allocations = FuncTranslatorAllocations;
 
-    fn setup(&mut self, _bytes: &[u8]) -> Result<bool, Error> {
+    fn setup(&mut self, offset: usize, _bytes: &[u8]) -> Result<bool, Error> {
+        self.body_offset = offset;
         Ok(false)
     }
 
@@ -601,6 +656,22 @@ impl WasmTranslator<'_> for FuncTranslator {
         self.engine.config().wasm_features()
     }
 
+    #[inline]
+    fn trapping_features(&self) -> WasmFeatures {
+        self.engine.config().trapping_features()
+    }
+
+    fn translate_unsupported_operator(
+        &mut self,
+        reader: &mut OperatorsReader<'parser>,
+    ) -> Result<(), Error> {
+        reader.read()?;
+        bail_unreachable!(self);
+        self.push_base_instr(Instruction::trap(TrapCode::UnsupportedOperator))?;
+        self.reachable = false;
+        Ok(())
+    }
+
     fn translate_locals(
         &mut self,
         amount: u32,
@@ -614,12 +685,15 @@ impl WasmTranslator<'_> for FuncTranslator {
         Ok(())
     }
 
-    fn update_pos(&mut self, _pos: usize) {}
+    fn update_pos(&mut self, pos: usize) {
+        self.pos = pos;
+    }
 
     fn finish(
         mut self,
         finalize: impl FnOnce(CompiledFuncEntity),
     ) -> Result<Self::Allocations, Error> {
+        self.alloc.instr_encoder.encode_cold_stubs()?;
         self.alloc
             .instr_encoder
             .defrag_registers(&mut self.alloc.stack)?;
@@ -644,6 +718,13 @@ impl WasmTranslator<'_> for FuncTranslator {
         }
         let func_consts = self.alloc.stack.func_local_consts();
         let instrs = self.alloc.instr_encoder.drain_instrs();
+        if let Some(limit) = self.engine.config().get_max_translated_func_size() {
+            if translated_func_size(instrs.len(), func_consts.len()) > limit {
+                return Err(Error::from(EnforcedLimitsError::TranslatedFuncTooLarge {
+                    limit,
+                }));
+            }
+        }
         finalize(CompiledFuncEntity::new(len_registers, instrs, func_consts));
         Ok(self.into_allocations())
     }
@@ -704,7 +785,7 @@ impl FuncTranslator {
         let config = engine.config();
         let fuel_costs = config
             .get_consume_fuel()
-            .then(|| config.fuel_costs())
+            .then(|| config.get_fuel_costs())
             .copied();
         Self {
             func,
@@ -712,6 +793,9 @@ impl FuncTranslator {
             module: res,
             reachable: true,
             fuel_costs,
+            body_offset: 0,
+            pos: 0,
+            len_loops: 0,
             alloc,
         }
         .init()
@@ -760,6 +844,13 @@ impl FuncTranslator {
         Ok(())
     }
 
+    /// Returns the [`BranchHint`] of the currently translated Wasm operator if any.
+    fn branch_hint(&self) -> Option<BranchHint> {
+        let offset = self.pos.checked_sub(self.body_offset)?;
+        let offset = u32::try_from(offset).ok()?;
+        self.module.get_branch_hint(self.func, offset)
+    }
+
     /// Consumes `self` and returns the underlying reusable [`FuncTranslatorAllocations`].
     fn into_allocations(self) -> FuncTranslatorAllocations {
         self.alloc
@@ -1308,6 +1399,7 @@ impl FuncTranslator {
     /// - Returns `Ok(true)` is the optimization was applied.
     /// - Returns `Ok(false)` is the optimization could not be applied.
     /// - Returns `Err(_)` if a translation error occurred.
+    /// - Never applies the optimization if the `compact-dispatch` crate feature is enabled.
     fn try_push_binary_instr_imm16<T>(
         &mut self,
         lhs: Reg,
@@ -1317,6 +1409,9 @@ impl FuncTranslator {
     where
         T: Copy + TryInto<Const16<T>>,
     {
+        if cfg!(feature = "compact-dispatch") {
+            return Ok(false);
+        }
         if let Ok(rhs) = rhs.try_into() {
             // Optimization: We can use a compact instruction for small constants.
             let result = self.alloc.stack.push_dynamic()?;
@@ -1336,6 +1431,9 @@ impl FuncTranslator {
     where
         T: Copy + TryInto<Const16<T>>,
     {
+        if cfg!(feature = "compact-dispatch") {
+            return Ok(false);
+        }
         if let Ok(lhs) = lhs.try_into() {
             // Optimization: We can use a compact instruction for small constants.
             let result = self.alloc.stack.push_dynamic()?;
@@ -1846,6 +1944,68 @@ impl FuncTranslator {
         }
     }
 
+    /// Translates a Wasm integer division or remainder to its non-trapping Wasmi bytecode variant.
+    ///
+    /// # Note
+    ///
+    /// Used instead of [`Self::translate_divrem`] if [`Config::saturating_div_rem`] is enabled.
+    ///
+    /// [`Config::saturating_div_rem`]: crate::Config::saturating_div_rem
+    fn translate_divrem_sat(
+        &mut self,
+        make_instr: fn(result: Reg, lhs: Reg, rhs: Reg) -> Instruction,
+        consteval: fn(TypedVal, TypedVal) -> TypedVal,
+    ) -> Result<(), Error> {
+        bail_unreachable!(self);
+        match self.alloc.stack.pop2() {
+            (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
+                self.push_binary_instr(lhs, rhs, make_instr)
+            }
+            (TypedProvider::Register(lhs), TypedProvider::Const(rhs)) => {
+                self.push_binary_instr_imm(lhs, rhs, make_instr)
+            }
+            (TypedProvider::Const(lhs), TypedProvider::Register(rhs)) => {
+                self.push_binary_instr_imm_rev(lhs, rhs, make_instr)
+            }
+            (TypedProvider::Const(lhs), TypedProvider::Const(rhs)) => {
+                self.alloc.stack.push_const(consteval(lhs, rhs));
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns `true` if integer division and remainder shall be translated to non-trapping variants.
+    fn is_saturating_div_rem(&self) -> bool {
+        self.engine().config().get_saturating_div_rem()
+    }
+
+    /// Returns the index of the next Wasm `loop` of the function body.
+    fn next_loop_index(&mut self) -> u32 {
+        let index = self.len_loops;
+        self.len_loops += 1;
+        index
+    }
+
+    /// Encodes an [`Instruction::CountLoopIteration`] if loop iteration counting is enabled.
+    ///
+    /// The `index` refers to the Wasm `loop` in order of appearance in the function body.
+    fn encode_count_loop_iteration(&mut self, index: u32) -> Result<(), Error> {
+        if !self.engine().config().get_count_loop_iterations() {
+            return Ok(());
+        }
+        let func = self
+            .module
+            .get_engine_func(self.func)
+            .expect("compiled functions always have an engine function");
+        self.alloc
+            .instr_encoder
+            .push_instr(Instruction::count_loop_iteration(func))?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::const32(index))?;
+        Ok(())
+    }
+
     /// Can be used for [`Self::translate_binary`] (and variants) if no custom optimization shall be applied.
     fn no_custom_opt<Lhs, Rhs>(&mut self, _lhs: Lhs, _rhs: Rhs) -> Result<bool, Error> {
         Ok(false)
@@ -1945,6 +2105,10 @@ impl FuncTranslator {
                 let Some(address) = Self::effective_address(u32::from(ptr), offset) else {
                     return self.translate_trap(TrapCode::MemoryOutOfBounds);
                 };
+                if cfg!(feature = "compact-dispatch") {
+                    let ptr = self.alloc.stack.alloc_const(ptr)?;
+                    return self.push_load_instr(ptr, offset, memory, make_instr);
+                }
                 let result = self.alloc.stack.push_dynamic()?;
                 self.push_fueled_instr(make_instr_at(result, address), FuelCosts::load)?;
                 if !memory.is_default() {
@@ -1955,13 +2119,25 @@ impl FuncTranslator {
                 return Ok(());
             }
         };
-        let result = self.alloc.stack.push_dynamic()?;
-        if memory.is_default() {
+        if memory.is_default() && !cfg!(feature = "compact-dispatch") {
             if let Ok(offset) = <Const16<u32>>::try_from(offset) {
+                let result = self.alloc.stack.push_dynamic()?;
                 self.push_fueled_instr(make_instr_offset16(result, ptr, offset), FuelCosts::load)?;
                 return Ok(());
             }
         }
+        self.push_load_instr(ptr, offset, memory, make_instr)
+    }
+
+    /// Pushes the general form of a Wasm `load` instruction.
+    fn push_load_instr(
+        &mut self,
+        ptr: Reg,
+        offset: u32,
+        memory: index::Memory,
+        make_instr: fn(result: Reg, memory: index::Memory) -> Instruction,
+    ) -> Result<(), Error> {
+        let result = self.alloc.stack.push_dynamic()?;
         self.push_fueled_instr(make_instr(result, memory), FuelCosts::load)?;
         self.alloc
             .instr_encoder
@@ -2032,6 +2208,12 @@ impl FuncTranslator {
         let (ptr, value) = self.alloc.stack.pop2();
         let ptr = match ptr {
             Provider::Register(ptr) => ptr,
+            Provider::Const(ptr) if cfg!(feature = "compact-dispatch") => {
+                if Self::effective_address(u32::from(ptr), offset).is_none() {
+                    return self.translate_trap(TrapCode::MemoryOutOfBounds);
+                }
+                self.alloc.stack.alloc_const(ptr)?
+            }
             Provider::Const(ptr) => {
                 return self.translate_istore_wrap_at::<Src, Wrapped, Field>(
                     memory,
@@ -2043,7 +2225,7 @@ impl FuncTranslator {
                 )
             }
         };
-        if memory.is_default() {
+        if memory.is_default() && !cfg!(feature = "compact-dispatch") {
             if let Some(_instr) = self.translate_istore_wrap_mem0::<Src, Wrapped, Field>(
                 ptr,
                 offset,
@@ -2184,6 +2366,12 @@ impl FuncTranslator {
         let (ptr, value) = self.alloc.stack.pop2();
         let ptr = match ptr {
             Provider::Register(ptr) => ptr,
+            Provider::Const(ptr) if cfg!(feature = "compact-dispatch") => {
+                if Self::effective_address(u32::from(ptr), offset).is_none() {
+                    return self.translate_trap(TrapCode::MemoryOutOfBounds);
+                }
+                self.alloc.stack.alloc_const(ptr)?
+            }
             Provider::Const(ptr) => {
                 return self.translate_fstore_at(
                     memory,
@@ -2195,7 +2383,7 @@ impl FuncTranslator {
             }
         };
         let value = self.alloc.stack.provider2reg(&value)?;
-        if memory.is_default() {
+        if memory.is_default() && !cfg!(feature = "compact-dispatch") {
             if let Ok(offset) = u16::try_from(offset) {
                 self.push_fueled_instr(make_instr_offset16(ptr, offset, value), FuelCosts::store)?;
                 return Ok(());
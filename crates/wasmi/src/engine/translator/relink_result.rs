@@ -94,6 +94,21 @@ impl RelinkResult for Instruction {
             | Self::CallIndirectImm16 { results, func_type } => {
                 relink_call_indirect(results, *func_type, module, new_result, old_result)
             }
+            Self::Extension0 {
+                results,
+                len_results,
+                ..
+            }
+            | Self::Extension {
+                results,
+                len_results,
+                ..
+            } => {
+                if *len_results != 1 {
+                    return Ok(false);
+                }
+                relink_simple(results.head_mut(), new_result, old_result)
+            }
             instr => {
                 // Fallback: only relink results of instructions with statically known single results.
                 let mut visitor = Visitor::new(new_result, old_result);
@@ -10,6 +10,7 @@ use crate::{
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn fuzz_regression_0() {
     let wasm = include_str!("wat/fuzz_0.wat");
     TranslationTest::new(wasm)
@@ -23,6 +24,7 @@ fn fuzz_regression_0() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn fuzz_regression_1() {
     let wasm = include_str!("wat/fuzz_1.wat");
     TranslationTest::new(wasm)
@@ -179,6 +181,7 @@ fn fuzz_regression_10() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn fuzz_regression_11() {
     let wasm = include_str!("wat/fuzz_11.wat");
     TranslationTest::new(wasm)
@@ -194,6 +197,7 @@ fn fuzz_regression_11() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn fuzz_regression_12_f32() {
     let wasm = include_str!("wat/fuzz_12_f32.wat");
     TranslationTest::new(wasm)
@@ -214,6 +218,7 @@ fn fuzz_regression_12_f32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn fuzz_regression_12_f64() {
     let wasm = include_str!("wat/fuzz_12_f64.wat");
     TranslationTest::new(wasm)
@@ -278,6 +283,7 @@ fn fuzz_regression_14() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn fuzz_regression_15_01_codegen() {
     let wasm = include_str!("wat/fuzz_15_01.wat");
     TranslationTest::new(wasm)
@@ -302,6 +308,7 @@ fn fuzz_regression_15_01_codegen() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn fuzz_regression_15_01_execute() {
     let wasm = include_str!("wat/fuzz_15_01.wat");
     ExecutionTest::default()
@@ -312,6 +319,7 @@ fn fuzz_regression_15_01_execute() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn fuzz_regression_15_02() {
     let wasm = include_str!("wat/fuzz_15_02.wat");
     TranslationTest::new(wasm)
@@ -362,6 +370,8 @@ fn fuzz_regression_15_03() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
+#[cfg(not(feature = "compact-dispatch"))]
 fn fuzz_regression_16() {
     // The bug in this regression test was a forgotten adjustment
     // for the preserved local value causing the `value` register
@@ -380,6 +390,8 @@ fn fuzz_regression_16() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
+#[cfg(not(feature = "compact-dispatch"))]
 fn fuzz_regression_17() {
     // The bug in this regression test was a forgotten adjustment
     // for the preserved local value causing the `value` register
@@ -403,7 +415,7 @@ fn audit_0_codegen() {
     TranslationTest::new(wasm)
         .expect_func(
             ExpectedFunc::new([
-                Instruction::return_many_ext(-1, -2, -1),
+                Instruction::return_reg4_ext(-1, -2, -1),
                 Instruction::register(-2),
             ])
             .consts([1, 0]),
@@ -436,6 +448,7 @@ fn audit_0_execution() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn audit_1_codegen() {
     let wasm = include_str!("wat/audit_1.wat");
     TranslationTest::new(wasm)
@@ -452,6 +465,7 @@ fn audit_1_codegen() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn audit_1_execution() {
     let wasm = include_str!("wat/audit_1.wat");
     ExecutionTest::default()
@@ -469,7 +483,7 @@ fn audit_2_codegen() {
             Instruction::copy(2, 0),
             Instruction::copy(0, 2),
             Instruction::copy(1, 0),
-            Instruction::return_many_ext(2, 1, 0),
+            Instruction::return_reg4_ext(2, 1, 0),
             Instruction::register(0),
         ])
         .run()
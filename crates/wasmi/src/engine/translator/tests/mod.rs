@@ -1,5 +1,11 @@
 //! Tests for the register-machine Wasmi engine translation implementation.
 
+// Some test helpers are unused when feature gated tests are compiled out.
+#![cfg_attr(
+    any(feature = "no-floats", feature = "compact-dispatch"),
+    allow(dead_code, unused_imports, unused_macros)
+)]
+
 mod display_wasm;
 pub mod driver;
 mod fuzz;
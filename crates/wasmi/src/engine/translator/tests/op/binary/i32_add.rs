@@ -10,16 +10,25 @@ fn reg_reg() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_imm16() {
     test_binary_reg_imm16_rhs::<i32>(WASM_OP, 100, Instruction::i32_add_imm16)
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_imm16_lhs() {
     test_binary_reg_imm16_lhs::<i32>(WASM_OP, 100, swap_ops!(Instruction::i32_add_imm16))
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg(feature = "compact-dispatch")]
+fn reg_imm16_compact() {
+    test_binary_reg_imm32(WASM_OP, 100_i32, Instruction::i32_add)
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn reg_imm() {
@@ -22,6 +22,7 @@ fn reg_reg() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_imm16_rhs() {
     test_binary_reg_imm16_rhs::<NonZeroU32>(
         WASM_OP,
@@ -32,6 +33,7 @@ fn reg_imm16_rhs() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_imm16_lhs() {
     test_binary_reg_imm16_lhs::<u32>(WASM_OP, 100, Instruction::i32_div_u_imm16_lhs)
 }
@@ -10,12 +10,14 @@ fn reg_reg() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_imm16() {
     test_binary_reg_imm16_rhs::<i32>(WASM_OP, 100, Instruction::i32_mul_imm16)
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_imm16_lhs() {
     test_binary_reg_imm16_lhs::<i32>(WASM_OP, 100, swap_ops!(Instruction::i32_mul_imm16))
 }
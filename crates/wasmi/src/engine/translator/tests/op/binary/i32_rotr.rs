@@ -16,6 +16,7 @@ fn reg_imm_lhs() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_imm16_lhs() {
     test_binary_reg_imm16_lhs::<i32>(WASM_OP, 100, Instruction::i32_rotr_imm16)
 }
@@ -8,19 +8,33 @@ use super::*;
 use crate::ir::{IntoShiftAmount, ShiftAmount};
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_add;
+#[cfg(not(feature = "no-floats"))]
 mod f32_copysign;
+#[cfg(not(feature = "no-floats"))]
 mod f32_div;
+#[cfg(not(feature = "no-floats"))]
 mod f32_max;
+#[cfg(not(feature = "no-floats"))]
 mod f32_min;
+#[cfg(not(feature = "no-floats"))]
 mod f32_mul;
+#[cfg(not(feature = "no-floats"))]
 mod f32_sub;
+#[cfg(not(feature = "no-floats"))]
 mod f64_add;
+#[cfg(not(feature = "no-floats"))]
 mod f64_copysign;
+#[cfg(not(feature = "no-floats"))]
 mod f64_div;
+#[cfg(not(feature = "no-floats"))]
 mod f64_max;
+#[cfg(not(feature = "no-floats"))]
 mod f64_min;
+#[cfg(not(feature = "no-floats"))]
 mod f64_mul;
+#[cfg(not(feature = "no-floats"))]
 mod f64_sub;
 mod i32_add;
 mod i32_and;
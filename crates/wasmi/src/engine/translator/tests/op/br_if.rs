@@ -102,8 +102,11 @@ fn consteval_return_1_imm() {
     }
     test_for_both::<i64>(i64::MIN, i64::MAX);
     test_for_both::<i64>(i64::from(i32::MIN) - 1, i64::from(i32::MAX) + 1);
-    test_for_both::<f64>(0.3, -0.3);
-    test_for_both::<f64>(0.123456789, -0.987654321);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for_both::<f64>(0.3, -0.3);
+        test_for_both::<f64>(0.123456789, -0.987654321);
+    }
 }
 
 #[test]
@@ -148,6 +151,7 @@ fn consteval_return_1_imm32() {
         test_for::<T>(false, if_true, if_false);
     }
     test_for_both::<i32>(5, 42);
+    #[cfg(not(feature = "no-floats"))]
     test_for_both::<f32>(5.5, -42.25);
 }
 
@@ -190,6 +194,7 @@ fn consteval_return_1_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn consteval_return_1_f64imm32() {
     fn test_for(condition: bool, if_true: f64, if_false: f64) {
         let expected: f64 = match condition {
@@ -344,12 +349,15 @@ fn return_if_results_1_imm() {
     test_for::<i64>(i64::MIN);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(0.987654321);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(-0.987654321);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(0.987654321);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(-0.987654321);
+    }
 }
 
 #[test]
@@ -384,10 +392,13 @@ fn return_if_results_1_imm32() {
     test_for::<i32>(1);
     test_for::<i32>(-1);
     test_for::<i32>(42);
-    test_for::<f32>(0.0);
-    test_for::<f32>(5.5);
-    test_for::<f32>(42.25);
-    test_for::<f32>(f32::NAN);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(5.5);
+        test_for::<f32>(42.25);
+        test_for::<f32>(f32::NAN);
+    }
 }
 
 #[test]
@@ -424,6 +435,7 @@ fn return_if_results_1_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn return_if_results_1_f64imm32() {
     fn test_for(returned_value: f64) {
         let display_value = DisplayWasm::from(returned_value);
@@ -637,7 +649,7 @@ fn return_if_results_4() {
         .expect_func_instrs([
             Instruction::return_nez_many_ext(Reg::from(2), 0, 1),
             Instruction::register2_ext(0, 1),
-            Instruction::return_many_ext(0, 1, 0),
+            Instruction::return_reg4_ext(0, 1, 0),
             Instruction::register(1),
         ])
         .run()
@@ -663,7 +675,7 @@ fn return_if_results_4_imm() {
             ExpectedFunc::new([
                 Instruction::return_nez_many_ext(Reg::from(0), -1, -2),
                 Instruction::register2_ext(-1, -2),
-                Instruction::return_many_ext(-1, -2, -1),
+                Instruction::return_reg4_ext(-1, -2, -1),
                 Instruction::register(-2),
             ])
             .consts([10_i32, 20]),
@@ -691,7 +703,7 @@ fn return_if_results_5() {
         .expect_func_instrs([
             Instruction::return_nez_many_ext(Reg::from(2), 0, 1),
             Instruction::register3_ext(0, 1, 0),
-            Instruction::return_many_ext(0, 1, 0),
+            Instruction::return_reg5_ext(0, 1, 0),
             Instruction::register2_ext(1, 0),
         ])
         .run()
@@ -718,7 +730,7 @@ fn return_if_results_5_imm() {
             ExpectedFunc::new([
                 Instruction::return_nez_many_ext(Reg::from(0), -1, -2),
                 Instruction::register3_ext(-1, -2, -1),
-                Instruction::return_many_ext(-1, -2, -1),
+                Instruction::return_reg5_ext(-1, -2, -1),
                 Instruction::register2_ext(-2, -1),
             ])
             .consts([10_i32, 20]),
@@ -748,7 +760,7 @@ fn return_if_results_6() {
             Instruction::return_nez_many_ext(Reg::from(2), 0, 1),
             Instruction::register_list_ext(0, 1, 0),
             Instruction::register(1),
-            Instruction::return_many_ext(0, 1, 0),
+            Instruction::return_reg6_ext(0, 1, 0),
             Instruction::register3_ext(1, 0, 1),
         ])
         .run()
@@ -777,7 +789,7 @@ fn return_if_results_6_imm() {
                 Instruction::return_nez_many_ext(Reg::from(0), -1, -2),
                 Instruction::register_list_ext(-1, -2, -1),
                 Instruction::register(-2),
-                Instruction::return_many_ext(-1, -2, -1),
+                Instruction::return_reg6_ext(-1, -2, -1),
                 Instruction::register3_ext(-2, -1, -2),
             ])
             .consts([10_i32, 20]),
@@ -992,6 +1004,7 @@ fn branch_if_results_4_mixed_2() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn branch_if_i32_eqz() {
     let wasm = r"
         (module
@@ -1014,6 +1027,7 @@ fn branch_if_i32_eqz() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn return_if_i32_eqz() {
     let wasm = r"
         (module
@@ -167,6 +167,7 @@ fn reg_params_0_return() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_params_1_return() {
     let wasm = r"
         (module
@@ -343,6 +344,7 @@ fn reg_params_2_return() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_params_1_diff() {
     // Test that uses `br_table` with targets that do not share
     // common branch parameters. We achieve this by interleaving
@@ -516,6 +518,7 @@ fn all_same_targets_0() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn all_same_targets_1() {
     fn test_for(same: u32, value: i16) {
         let wasm = &format!(
@@ -552,6 +555,7 @@ fn all_same_targets_1() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_params_3() {
     let wasm = r"
         (module
@@ -591,6 +595,7 @@ fn reg_params_3() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_params_4_span() {
     let wasm = r"
         (module
@@ -642,6 +647,7 @@ fn reg_params_4_span() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg_params_4_many() {
     let wasm = r"
         (module
@@ -694,6 +700,7 @@ fn reg_params_4_many() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn i64imm32_ok() {
     fn test_for(imm: i32) {
         let wasm = &format!(
@@ -745,6 +752,7 @@ fn i64imm32_ok() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn i64imm32_err() {
     fn test_for(imm: i64) {
         let wasm = &format!(
@@ -806,6 +814,7 @@ fn i64imm32_err() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn f64imm32_ok() {
     fn test_for(imm: f32) {
         let wasm = &format!(
@@ -865,6 +874,7 @@ fn f64imm32_ok() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn f64imm32_err() {
     fn test_for(imm: f64) {
         let wasm = &format!(
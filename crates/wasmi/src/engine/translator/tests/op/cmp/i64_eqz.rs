@@ -4,6 +4,7 @@ const PARAM: WasmType = WasmType::I64;
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn reg() {
     let wasm = format!(
         r#"
@@ -18,22 +18,34 @@ use super::*;
 mod i32_eqz;
 mod i64_eqz;
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_eq;
+#[cfg(not(feature = "no-floats"))]
 mod f32_ne;
+#[cfg(not(feature = "no-floats"))]
 mod f64_eq;
+#[cfg(not(feature = "no-floats"))]
 mod f64_ne;
 mod i32_eq;
 mod i32_ne;
 mod i64_eq;
 mod i64_ne;
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_ge;
+#[cfg(not(feature = "no-floats"))]
 mod f32_gt;
+#[cfg(not(feature = "no-floats"))]
 mod f32_le;
+#[cfg(not(feature = "no-floats"))]
 mod f32_lt;
+#[cfg(not(feature = "no-floats"))]
 mod f64_ge;
+#[cfg(not(feature = "no-floats"))]
 mod f64_gt;
+#[cfg(not(feature = "no-floats"))]
 mod f64_le;
+#[cfg(not(feature = "no-floats"))]
 mod f64_lt;
 
 mod i32_ge_s;
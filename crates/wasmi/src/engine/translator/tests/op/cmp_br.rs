@@ -92,39 +92,43 @@ fn loop_backward() {
         swap_cmp_br_ops!(Instruction::branch_i64_le_u),
     );
 
-    test_for(ValType::F32, "eq", Instruction::branch_f32_eq);
-    test_for(ValType::F32, "ne", Instruction::branch_f32_ne);
-    test_for(ValType::F32, "lt", Instruction::branch_f32_lt);
-    test_for(ValType::F32, "le", Instruction::branch_f32_le);
-    test_for(
-        ValType::F32,
-        "gt",
-        swap_cmp_br_ops!(Instruction::branch_f32_lt),
-    );
-    test_for(
-        ValType::F32,
-        "ge",
-        swap_cmp_br_ops!(Instruction::branch_f32_le),
-    );
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for(ValType::F32, "eq", Instruction::branch_f32_eq);
+        test_for(ValType::F32, "ne", Instruction::branch_f32_ne);
+        test_for(ValType::F32, "lt", Instruction::branch_f32_lt);
+        test_for(ValType::F32, "le", Instruction::branch_f32_le);
+        test_for(
+            ValType::F32,
+            "gt",
+            swap_cmp_br_ops!(Instruction::branch_f32_lt),
+        );
+        test_for(
+            ValType::F32,
+            "ge",
+            swap_cmp_br_ops!(Instruction::branch_f32_le),
+        );
 
-    test_for(ValType::F64, "eq", Instruction::branch_f64_eq);
-    test_for(ValType::F64, "ne", Instruction::branch_f64_ne);
-    test_for(ValType::F64, "lt", Instruction::branch_f64_lt);
-    test_for(ValType::F64, "le", Instruction::branch_f64_le);
-    test_for(
-        ValType::F64,
-        "gt",
-        swap_cmp_br_ops!(Instruction::branch_f64_lt),
-    );
-    test_for(
-        ValType::F64,
-        "ge",
-        swap_cmp_br_ops!(Instruction::branch_f64_le),
-    );
+        test_for(ValType::F64, "eq", Instruction::branch_f64_eq);
+        test_for(ValType::F64, "ne", Instruction::branch_f64_ne);
+        test_for(ValType::F64, "lt", Instruction::branch_f64_lt);
+        test_for(ValType::F64, "le", Instruction::branch_f64_le);
+        test_for(
+            ValType::F64,
+            "gt",
+            swap_cmp_br_ops!(Instruction::branch_f64_lt),
+        );
+        test_for(
+            ValType::F64,
+            "ge",
+            swap_cmp_br_ops!(Instruction::branch_f64_le),
+        );
+    }
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn loop_backward_imm_rhs() {
     fn test_for<T>(
         op: &str,
@@ -222,6 +226,7 @@ fn loop_backward_imm_rhs() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn loop_backward_imm_lhs() {
     fn test_for<T>(
         op: &str,
@@ -400,35 +405,38 @@ fn block_forward() {
         swap_cmp_br_ops!(Instruction::branch_i64_le_u),
     );
 
-    test_for(ValType::F32, "eq", Instruction::branch_f32_eq);
-    test_for(ValType::F32, "ne", Instruction::branch_f32_ne);
-    test_for(ValType::F32, "lt", Instruction::branch_f32_lt);
-    test_for(ValType::F32, "le", Instruction::branch_f32_le);
-    test_for(
-        ValType::F32,
-        "gt",
-        swap_cmp_br_ops!(Instruction::branch_f32_lt),
-    );
-    test_for(
-        ValType::F32,
-        "ge",
-        swap_cmp_br_ops!(Instruction::branch_f32_le),
-    );
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for(ValType::F32, "eq", Instruction::branch_f32_eq);
+        test_for(ValType::F32, "ne", Instruction::branch_f32_ne);
+        test_for(ValType::F32, "lt", Instruction::branch_f32_lt);
+        test_for(ValType::F32, "le", Instruction::branch_f32_le);
+        test_for(
+            ValType::F32,
+            "gt",
+            swap_cmp_br_ops!(Instruction::branch_f32_lt),
+        );
+        test_for(
+            ValType::F32,
+            "ge",
+            swap_cmp_br_ops!(Instruction::branch_f32_le),
+        );
 
-    test_for(ValType::F64, "eq", Instruction::branch_f64_eq);
-    test_for(ValType::F64, "ne", Instruction::branch_f64_ne);
-    test_for(ValType::F64, "lt", Instruction::branch_f64_lt);
-    test_for(ValType::F64, "le", Instruction::branch_f64_le);
-    test_for(
-        ValType::F64,
-        "gt",
-        swap_cmp_br_ops!(Instruction::branch_f64_lt),
-    );
-    test_for(
-        ValType::F64,
-        "ge",
-        swap_cmp_br_ops!(Instruction::branch_f64_le),
-    );
+        test_for(ValType::F64, "eq", Instruction::branch_f64_eq);
+        test_for(ValType::F64, "ne", Instruction::branch_f64_ne);
+        test_for(ValType::F64, "lt", Instruction::branch_f64_lt);
+        test_for(ValType::F64, "le", Instruction::branch_f64_le);
+        test_for(
+            ValType::F64,
+            "gt",
+            swap_cmp_br_ops!(Instruction::branch_f64_lt),
+        );
+        test_for(
+            ValType::F64,
+            "ge",
+            swap_cmp_br_ops!(Instruction::branch_f64_le),
+        );
+    }
 }
 
 #[test]
@@ -520,35 +528,38 @@ fn block_forward_nop_copy() {
         swap_cmp_br_ops!(Instruction::branch_i64_le_u),
     );
 
-    test_for(ValType::F32, "eq", Instruction::branch_f32_eq);
-    test_for(ValType::F32, "ne", Instruction::branch_f32_ne);
-    test_for(ValType::F32, "lt", Instruction::branch_f32_lt);
-    test_for(ValType::F32, "le", Instruction::branch_f32_le);
-    test_for(
-        ValType::F32,
-        "gt",
-        swap_cmp_br_ops!(Instruction::branch_f32_lt),
-    );
-    test_for(
-        ValType::F32,
-        "ge",
-        swap_cmp_br_ops!(Instruction::branch_f32_le),
-    );
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for(ValType::F32, "eq", Instruction::branch_f32_eq);
+        test_for(ValType::F32, "ne", Instruction::branch_f32_ne);
+        test_for(ValType::F32, "lt", Instruction::branch_f32_lt);
+        test_for(ValType::F32, "le", Instruction::branch_f32_le);
+        test_for(
+            ValType::F32,
+            "gt",
+            swap_cmp_br_ops!(Instruction::branch_f32_lt),
+        );
+        test_for(
+            ValType::F32,
+            "ge",
+            swap_cmp_br_ops!(Instruction::branch_f32_le),
+        );
 
-    test_for(ValType::F64, "eq", Instruction::branch_f64_eq);
-    test_for(ValType::F64, "ne", Instruction::branch_f64_ne);
-    test_for(ValType::F64, "lt", Instruction::branch_f64_lt);
-    test_for(ValType::F64, "le", Instruction::branch_f64_le);
-    test_for(
-        ValType::F64,
-        "gt",
-        swap_cmp_br_ops!(Instruction::branch_f64_lt),
-    );
-    test_for(
-        ValType::F64,
-        "ge",
-        swap_cmp_br_ops!(Instruction::branch_f64_le),
-    );
+        test_for(ValType::F64, "eq", Instruction::branch_f64_eq);
+        test_for(ValType::F64, "ne", Instruction::branch_f64_ne);
+        test_for(ValType::F64, "lt", Instruction::branch_f64_lt);
+        test_for(ValType::F64, "le", Instruction::branch_f64_le);
+        test_for(
+            ValType::F64,
+            "gt",
+            swap_cmp_br_ops!(Instruction::branch_f64_lt),
+        );
+        test_for(
+            ValType::F64,
+            "ge",
+            swap_cmp_br_ops!(Instruction::branch_f64_le),
+        );
+    }
 }
 
 #[test]
@@ -791,6 +802,7 @@ fn if_i32_eqz_fuse() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn block_i64_eqz_fuse() {
     let wasm = r"
         (module
@@ -811,6 +823,7 @@ fn block_i64_eqz_fuse() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn if_i64_eqz_fuse() {
     let wasm = r"
         (module
@@ -831,6 +844,7 @@ fn if_i64_eqz_fuse() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn cmp_br_fallback() {
     // Required amount of instructions to trigger the `cmp+br` fallback instruction generation.
     let len_adds = (1 << 15) + 1;
@@ -59,6 +59,9 @@ fn binop_i32_eqz() {
         ("i64", "gt_u", Instruction::i64_le_u),
         ("i64", "ge_s", Instruction::i64_lt_s),
         ("i64", "ge_u", Instruction::i64_lt_u),
+    );
+    #[cfg(not(feature = "no-floats"))]
+    test_for!(
         ("f32", "eq", Instruction::f32_ne),
         ("f32", "ne", Instruction::f32_eq),
         ("f64", "eq", Instruction::f64_ne),
@@ -75,6 +78,7 @@ macro_rules! test_for_imm {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn binop_imm_i32_eqz_rhs() {
     fn test_for<T>(
         op: &str,
@@ -138,6 +142,7 @@ fn binop_imm_i32_eqz_rhs() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn binop_imm_i32_eqz_lhs() {
     fn test_for<T>(
         op: &str,
@@ -247,6 +252,9 @@ fn binop_i32_eqz_double() {
         ("i64", "gt_u", swap_ops!(Instruction::i64_lt_u)),
         ("i64", "ge_s", swap_ops!(Instruction::i64_le_s)),
         ("i64", "ge_u", swap_ops!(Instruction::i64_le_u)),
+    );
+    #[cfg(not(feature = "no-floats"))]
+    test_for!(
         ("f32", "eq", Instruction::f32_eq),
         ("f32", "ne", Instruction::f32_ne),
         ("f64", "eq", Instruction::f64_eq),
@@ -256,6 +264,7 @@ fn binop_i32_eqz_double() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn binop_imm_i32_eqz_rhs_double() {
     fn test_for<T>(
         op: &str,
@@ -317,6 +326,7 @@ fn binop_imm_i32_eqz_rhs_double() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn binop_imm_i32_eqz_lhs_double() {
     fn test_for<T>(
         op: &str,
@@ -376,6 +386,7 @@ fn binop_imm_i32_eqz_lhs_double() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn binop_i32_eqz_double_invalid() {
     fn test_for(
         input_ty: &str,
@@ -411,6 +422,7 @@ fn binop_i32_eqz_double_invalid() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn binop_imm_i32_eqz_rhs_double_invalid() {
     fn test_for<T>(
         op: &str,
@@ -456,6 +468,7 @@ fn binop_imm_i32_eqz_rhs_double_invalid() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn binop_imm_i32_eqz_lhs_double_invalid() {
     fn test_for<T>(
         op: &str,
@@ -9,7 +9,7 @@ fn test_load_mem0(
     offset: u32,
 ) {
     assert!(
-        offset > u32::from(u16::MAX),
+        offset > u32::from(u16::MAX) || cfg!(feature = "compact-dispatch"),
         "offset must not be 16-bit encodable in this testcase"
     );
     let result_ty = wasm_op.result_ty();
@@ -214,6 +214,7 @@ macro_rules! generate_tests {
 
         #[test]
         #[cfg_attr(miri, ignore)]
+        #[cfg(not(feature = "compact-dispatch"))]
         fn offset16() {
             test_load_offset16(WASM_OP, 0, $make_instr_offset16);
             test_load_offset16(WASM_OP, u16::MAX, $make_instr_offset16);
@@ -221,6 +222,15 @@ macro_rules! generate_tests {
 
         #[test]
         #[cfg_attr(miri, ignore)]
+        #[cfg(feature = "compact-dispatch")]
+        fn offset16_compact() {
+            test_load_mem0(WASM_OP, $make_instr, 0);
+            test_load_mem0(WASM_OP, $make_instr, u32::from(u16::MAX));
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        #[cfg(not(feature = "compact-dispatch"))]
         fn at_mem0() {
             test_load_at_mem0(WASM_OP, $make_instr_at, 42, 5);
             test_load_at_mem0(WASM_OP, $make_instr_at, u32::MAX, 0);
@@ -229,6 +239,7 @@ macro_rules! generate_tests {
 
         #[test]
         #[cfg_attr(miri, ignore)]
+        #[cfg(not(feature = "compact-dispatch"))]
         fn at() {
             test_load_at(WASM_OP, $make_instr_at, 42, 5);
             test_load_at(WASM_OP, $make_instr_at, u32::MAX, 0);
@@ -409,6 +420,7 @@ mod i64_load32_u {
     );
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_load {
     use super::*;
 
@@ -422,6 +434,7 @@ mod f32_load {
     );
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_load {
     use super::*;
 
@@ -155,7 +155,7 @@ fn simple_block_4_params_2() {
             Instruction::copy_imm32(Reg::from(2), 30_i32),
             Instruction::copy_imm32(Reg::from(3), 40_i32),
             Instruction::copy2_ext(RegSpan::new(Reg::from(5)), 9, 10),
-            Instruction::return_many_ext(7, 8, 5),
+            Instruction::return_reg4_ext(7, 8, 5),
             Instruction::register(6),
         ])
         .run()
@@ -375,7 +375,7 @@ fn simple_if_4_params_2() {
             Instruction::copy2_ext(RegSpan::new(Reg::from(5)), 9, 10),
             Instruction::branch(BranchOffset::from(2)),
             Instruction::copy2_ext(RegSpan::new(Reg::from(5)), 9, 10),
-            Instruction::return_many_ext(7, 8, 5),
+            Instruction::return_reg4_ext(7, 8, 5),
             Instruction::register(6),
         ])
         .run()
@@ -609,6 +609,7 @@ fn concat_local_tee_pair() {
 }
 
 #[test]
+#[cfg(not(feature = "compact-dispatch"))]
 fn loop_iter_1() {
     let wasm = r#"
         (module
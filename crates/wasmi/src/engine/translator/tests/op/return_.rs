@@ -60,10 +60,13 @@ fn return_1_imm() {
     test_for::<i64>(i64::MAX);
     test_for::<i64>(i64::from(i32::MIN) - 1);
     test_for::<i64>(i64::from(i32::MAX) + 1);
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(0.987654321);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(0.987654321);
+    }
 }
 
 #[test]
@@ -91,8 +94,11 @@ fn return_1_imm32() {
     }
     test_for::<i32>(5);
     test_for::<i32>(42);
-    test_for::<f32>(5.5);
-    test_for::<f32>(-42.25);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f32>(5.5);
+        test_for::<f32>(-42.25);
+    }
 }
 
 #[test]
@@ -122,6 +128,7 @@ fn return_1_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn return_1_f64imm32() {
     fn test_for(value: f64) {
         let display_value = DisplayWasm::from(value);
@@ -283,7 +290,7 @@ fn return_4() {
         )";
     TranslationTest::new(wasm)
         .expect_func_instrs([
-            Instruction::return_many_ext(0, 0, 0),
+            Instruction::return_reg4_ext(0, 0, 0),
             Instruction::register(0),
         ])
         .run()
@@ -324,7 +331,7 @@ fn return_5() {
         )";
     TranslationTest::new(wasm)
         .expect_func_instrs([
-            Instruction::return_many_ext(0, 1, 0),
+            Instruction::return_reg5_ext(0, 1, 0),
             Instruction::register2_ext(1, 0),
         ])
         .run()
@@ -347,7 +354,7 @@ fn return_6() {
         )";
     TranslationTest::new(wasm)
         .expect_func_instrs([
-            Instruction::return_many_ext(0, 1, 0),
+            Instruction::return_reg6_ext(0, 1, 0),
             Instruction::register3_ext(1, 0, 1),
         ])
         .run()
@@ -371,8 +378,8 @@ fn return_7() {
         )";
     TranslationTest::new(wasm)
         .expect_func_instrs([
-            Instruction::return_many_ext(0, 1, 0),
-            Instruction::register_list_ext(1, 0, 1),
+            Instruction::return_reg7_ext(0, 1, 0),
+            Instruction::register3_ext(1, 0, 1),
             Instruction::register(0),
         ])
         .run()
@@ -397,8 +404,8 @@ fn return_8() {
         )";
     TranslationTest::new(wasm)
         .expect_func_instrs([
-            Instruction::return_many_ext(0, 1, 0),
-            Instruction::register_list_ext(1, 0, 1),
+            Instruction::return_reg8_ext(0, 1, 0),
+            Instruction::register3_ext(1, 0, 1),
             Instruction::register2_ext(0, 1),
         ])
         .run()
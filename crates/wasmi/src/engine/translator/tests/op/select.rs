@@ -74,8 +74,11 @@ fn reg() {
     fn test_for(kind: SelectKind) {
         test_reg(kind, ValType::I32);
         test_reg(kind, ValType::I64);
-        test_reg(kind, ValType::F32);
-        test_reg(kind, ValType::F64);
+        #[cfg(not(feature = "no-floats"))]
+        {
+            test_reg(kind, ValType::F32);
+            test_reg(kind, ValType::F64);
+        }
     }
     test_for(SelectKind::Select);
     test_for(SelectKind::TypedSelect);
@@ -108,8 +111,11 @@ fn same_reg() {
     fn test_for(kind: SelectKind) {
         test_same_reg(kind, ValType::I32);
         test_same_reg(kind, ValType::I64);
-        test_same_reg(kind, ValType::F32);
-        test_same_reg(kind, ValType::F64);
+        #[cfg(not(feature = "no-floats"))]
+        {
+            test_same_reg(kind, ValType::F32);
+            test_same_reg(kind, ValType::F64);
+        }
     }
     test_for(SelectKind::Select);
     test_for(SelectKind::TypedSelect);
@@ -172,15 +178,18 @@ fn same_imm32() {
     test_for::<i32>(i32::MIN);
     test_for::<i32>(i32::MAX);
 
-    test_for::<f32>(0.0);
-    test_for::<f32>(0.25);
-    test_for::<f32>(-0.25);
-    test_for::<f32>(1.0);
-    test_for::<f32>(-1.0);
-    test_for::<f32>(f32::NEG_INFINITY);
-    test_for::<f32>(f32::INFINITY);
-    test_for::<f32>(f32::NAN);
-    test_for::<f32>(f32::EPSILON);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(0.25);
+        test_for::<f32>(-0.25);
+        test_for::<f32>(1.0);
+        test_for::<f32>(-1.0);
+        test_for::<f32>(f32::NEG_INFINITY);
+        test_for::<f32>(f32::INFINITY);
+        test_for::<f32>(f32::NAN);
+        test_for::<f32>(f32::EPSILON);
+    }
 }
 
 #[test]
@@ -207,6 +216,7 @@ fn same_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn same_f64imm32() {
     fn test_for(value: f64) {
         let expected = [return_f64imm32_instr(value)];
@@ -254,12 +264,15 @@ fn same_imm() {
     test_for::<i64>(i64::MAX - 1);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(9.87654321);
-    test_for::<f64>(-9.87654321);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(9.87654321);
+        test_for::<f64>(-9.87654321);
+    }
 }
 
 fn test_reg_imm<T>(kind: SelectKind, rhs: T) -> TranslationTest
@@ -324,17 +337,20 @@ fn reg_imm32() {
     test_for::<i32>(i32::MAX - 1);
     test_for::<i32>(i32::MAX);
 
-    test_for::<f32>(0.0);
-    test_for::<f32>(0.25);
-    test_for::<f32>(-0.25);
-    test_for::<f32>(0.3);
-    test_for::<f32>(-0.3);
-    test_for::<f32>(1.0);
-    test_for::<f32>(-1.0);
-    test_for::<f32>(f32::NEG_INFINITY);
-    test_for::<f32>(f32::INFINITY);
-    test_for::<f32>(f32::NAN);
-    test_for::<f32>(f32::EPSILON);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(0.25);
+        test_for::<f32>(-0.25);
+        test_for::<f32>(0.3);
+        test_for::<f32>(-0.3);
+        test_for::<f32>(1.0);
+        test_for::<f32>(-1.0);
+        test_for::<f32>(f32::NEG_INFINITY);
+        test_for::<f32>(f32::INFINITY);
+        test_for::<f32>(f32::NAN);
+        test_for::<f32>(f32::EPSILON);
+    }
 }
 
 #[test]
@@ -373,12 +389,15 @@ fn reg_imm() {
     test_for::<i64>(i64::MAX - 1);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(9.87654321);
-    test_for::<f64>(-9.87654321);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(9.87654321);
+        test_for::<f64>(-9.87654321);
+    }
 }
 
 #[test]
@@ -412,6 +431,7 @@ fn reg_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn reg_f64imm32() {
     fn test_for_kind(kind: SelectKind, value: f64) {
         let result = Reg::from(2);
@@ -503,17 +523,20 @@ fn imm32_reg() {
     test_for::<i32>(i32::MAX - 1);
     test_for::<i32>(i32::MAX);
 
-    test_for::<f32>(0.0);
-    test_for::<f32>(0.25);
-    test_for::<f32>(-0.25);
-    test_for::<f32>(0.3);
-    test_for::<f32>(-0.3);
-    test_for::<f32>(1.0);
-    test_for::<f32>(-1.0);
-    test_for::<f32>(f32::NEG_INFINITY);
-    test_for::<f32>(f32::INFINITY);
-    test_for::<f32>(f32::NAN);
-    test_for::<f32>(f32::EPSILON);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(0.25);
+        test_for::<f32>(-0.25);
+        test_for::<f32>(0.3);
+        test_for::<f32>(-0.3);
+        test_for::<f32>(1.0);
+        test_for::<f32>(-1.0);
+        test_for::<f32>(f32::NEG_INFINITY);
+        test_for::<f32>(f32::INFINITY);
+        test_for::<f32>(f32::NAN);
+        test_for::<f32>(f32::EPSILON);
+    }
 }
 
 #[test]
@@ -552,12 +575,15 @@ fn imm_reg() {
     test_for::<i64>(i64::MAX - 1);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(9.87654321);
-    test_for::<f64>(-9.87654321);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(9.87654321);
+        test_for::<f64>(-9.87654321);
+    }
 }
 
 #[test]
@@ -591,6 +617,7 @@ fn i64imm32_reg() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn f64imm32_reg() {
     fn test_for_kind(kind: SelectKind, value: f64) {
         let result = Reg::from(2);
@@ -685,10 +712,13 @@ fn both_imm32() {
     test_for::<i32>(i32::MIN + 1, i32::MAX - 1);
     test_for::<i32>(i32::MIN, i32::MAX);
 
-    test_for::<f32>(0.0, 1.0);
-    test_for::<f32>(0.3, -0.3);
-    test_for::<f32>(f32::NEG_INFINITY, f32::INFINITY);
-    test_for::<f32>(f32::NAN, f32::EPSILON);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f32>(0.0, 1.0);
+        test_for::<f32>(0.3, -0.3);
+        test_for::<f32>(f32::NEG_INFINITY, f32::INFINITY);
+        test_for::<f32>(f32::NAN, f32::EPSILON);
+    }
 }
 
 #[test]
@@ -727,8 +757,11 @@ fn both_imm() {
     test_for::<i64>(i64::from(i32::MIN) - 1, i64::from(i32::MAX) + 1);
     test_for::<i64>(i64::MIN, i64::MAX);
 
-    test_for::<f64>(0.3, -0.3);
-    test_for::<f64>(0.123456789, -0.987654321);
+    #[cfg(not(feature = "no-floats"))]
+    {
+        test_for::<f64>(0.3, -0.3);
+        test_for::<f64>(0.123456789, -0.987654321);
+    }
 }
 
 #[test]
@@ -764,6 +797,7 @@ fn both_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "no-floats"))]
 fn both_f64imm32() {
     fn test_for_kind(kind: SelectKind, lhs: f64, rhs: f64) {
         let result = Reg::from(1);
@@ -795,6 +829,7 @@ fn both_f64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn fuzz_fail_01() {
     let wasm = r#"
         (module
@@ -22,12 +22,14 @@ fn imm() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16() {
     test_store_offset16(WASM_OP, Instruction::store64_offset16);
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16_imm() {
     test_store_offset16_imm::<f64>(WASM_OP, 0.0, Instruction::store64_offset16);
     test_store_offset16_imm::<f64>(WASM_OP, 1.0, Instruction::store64_offset16);
@@ -39,6 +41,7 @@ fn offset16_imm() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at() {
     test_store_at(WASM_OP, Instruction::store64_at);
 }
@@ -51,6 +54,7 @@ fn at_overflow() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at_imm() {
     test_store_at_imm::<f64>(WASM_OP, 0.0, Instruction::store64_at);
     test_store_at_imm::<f64>(WASM_OP, 1.0, Instruction::store64_at);
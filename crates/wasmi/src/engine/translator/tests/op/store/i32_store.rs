@@ -44,12 +44,22 @@ fn imm16() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "compact-dispatch")]
+fn offset16_compact() {
+    test_store_for(WASM_OP, 0, Instruction::store32);
+    test_store_for(WASM_OP, u32::from(u16::MAX), Instruction::store32);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16() {
     test_store_offset16(WASM_OP, Instruction::store32_offset16);
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16_imm() {
     test_store_offset16_imm::<i32>(
         WASM_OP,
@@ -69,6 +79,7 @@ fn offset16_imm() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16_imm16() {
     test_store_offset16_imm16::<i16>(WASM_OP, 0, Instruction::i32_store_offset16_imm16);
     test_store_offset16_imm16::<i16>(WASM_OP, 1, Instruction::i32_store_offset16_imm16);
@@ -81,6 +92,7 @@ fn offset16_imm16() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at() {
     test_store_at(WASM_OP, Instruction::store32_at);
 }
@@ -93,6 +105,7 @@ fn at_overflow() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at_imm() {
     test_store_at_imm::<i32>(WASM_OP, i32::from(i16::MAX) + 1, Instruction::store32_at);
     test_store_at_imm::<i32>(WASM_OP, i32::MAX - 1, Instruction::store32_at);
@@ -34,12 +34,14 @@ fn imm() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16() {
     test_store_offset16(WASM_OP, Instruction::i32_store8_offset16);
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16_imm() {
     let values = [
         0,
@@ -75,6 +77,7 @@ fn offset16_imm() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at() {
     test_store_at(WASM_OP, Instruction::i32_store8_at);
 }
@@ -87,6 +90,7 @@ fn at_overflow() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at_imm() {
     let values = [
         0,
@@ -44,12 +44,14 @@ fn imm16() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16() {
     test_store_offset16(WASM_OP, Instruction::store64_offset16);
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16_imm() {
     test_store_offset16_imm::<i64>(
         WASM_OP,
@@ -69,6 +71,7 @@ fn offset16_imm() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn offset16_imm16() {
     test_store_offset16_imm16::<i16>(WASM_OP, 0, Instruction::i64_store_offset16_imm16);
     test_store_offset16_imm16::<i16>(WASM_OP, 1, Instruction::i64_store_offset16_imm16);
@@ -81,6 +84,7 @@ fn offset16_imm16() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at() {
     test_store_at(WASM_OP, Instruction::store64_at);
 }
@@ -93,6 +97,7 @@ fn at_overflow() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(not(feature = "compact-dispatch"))]
 fn at_imm() {
     test_store_at_imm::<i64>(WASM_OP, i64::from(i16::MAX) + 1, Instruction::store64_at);
     test_store_at_imm::<i64>(WASM_OP, i64::MAX - 1, Instruction::store64_at);
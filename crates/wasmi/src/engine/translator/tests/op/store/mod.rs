@@ -8,7 +8,9 @@ use crate::{
 };
 use std::vec;
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_store;
+#[cfg(not(feature = "no-floats"))]
 mod f64_store;
 mod i32_store;
 mod i32_store16;
@@ -27,7 +29,7 @@ fn test_store_for(
     make_instr: fn(ptr: Reg, memory: Memory) -> Instruction,
 ) {
     assert!(
-        u16::try_from(offset).is_err(),
+        u16::try_from(offset).is_err() || cfg!(feature = "compact-dispatch"),
         "this test requires non-16 bit offsets but found {offset}"
     );
     let param_ty = wasm_op.param_ty();
@@ -83,6 +83,7 @@ macro_rules! signed_trunc_tests {
         )*
     ) => {
         $(
+            #[cfg(not(feature = "no-floats"))]
             mod $name {
                 use super::*;
                 const OP: &str = $op_name;
@@ -132,6 +133,7 @@ macro_rules! unsigned_trunc_tests {
         )*
     ) => {
         $(
+            #[cfg(not(feature = "no-floats"))]
             mod $name {
                 use super::*;
                 const OP: &str = $op_name;
@@ -181,6 +183,7 @@ macro_rules! trunc_sat_tests {
         )*
     ) => {
         $(
+            #[cfg(not(feature = "no-floats"))]
             mod $name {
                 use super::*;
                 const OP: &str = $op_name;
@@ -225,6 +228,7 @@ macro_rules! convert_tests {
         )*
     ) => {
         $(
+            #[cfg(not(feature = "no-floats"))]
             mod $name {
                 use super::*;
                 const OP: &str = $op_name;
@@ -261,6 +265,7 @@ convert_tests! {
     fn f64_convert_i64_u("convert_i64_u", i64, f64);
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_demote_f64 {
     use super::*;
     const OP: &str = "demote_f64";
@@ -287,6 +292,7 @@ mod f32_demote_f64 {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_promote_f32 {
     use super::*;
     const OP: &str = "promote_f32";
@@ -316,6 +322,7 @@ mod f64_promote_f32 {
 macro_rules! iN_reinterpret_fN_tests {
     ( $( fn $name:ident($op:literal, $input_ty:ty, $output_ty:ty); )* ) => {
         $(
+            #[cfg(not(feature = "no-floats"))]
             mod $name {
                 use super::*;
                 const OP: &str = $op;
@@ -350,6 +357,7 @@ iN_reinterpret_fN_tests! {
 macro_rules! fN_reinterpret_iN_tests {
     ( $( fn $name:ident($op:literal, $input_ty:ty, $output_ty:ty); )* ) => {
         $(
+            #[cfg(not(feature = "no-floats"))]
             mod $name {
                 use super::*;
                 const OP: &str = $op;
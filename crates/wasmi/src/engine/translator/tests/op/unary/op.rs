@@ -96,6 +96,7 @@ mod i64_popcnt {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_abs {
     use super::*;
 
@@ -115,6 +116,7 @@ mod f32_abs {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_neg {
     use super::*;
 
@@ -135,6 +137,7 @@ mod f32_neg {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_ceil {
     use super::*;
 
@@ -154,6 +157,7 @@ mod f32_ceil {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_floor {
     use super::*;
 
@@ -173,6 +177,7 @@ mod f32_floor {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_trunc {
     use super::*;
 
@@ -192,6 +197,7 @@ mod f32_trunc {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_nearest {
     use super::*;
 
@@ -225,6 +231,7 @@ mod f32_nearest {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f32_sqrt {
     use super::*;
 
@@ -244,6 +251,7 @@ mod f32_sqrt {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_abs {
     use super::*;
 
@@ -263,6 +271,7 @@ mod f64_abs {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_neg {
     use super::*;
 
@@ -283,6 +292,7 @@ mod f64_neg {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_ceil {
     use super::*;
 
@@ -302,6 +312,7 @@ mod f64_ceil {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_floor {
     use super::*;
 
@@ -321,6 +332,7 @@ mod f64_floor {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_trunc {
     use super::*;
 
@@ -340,6 +352,7 @@ mod f64_trunc {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_nearest {
     use super::*;
 
@@ -373,6 +386,7 @@ mod f64_nearest {
     }
 }
 
+#[cfg(not(feature = "no-floats"))]
 mod f64_sqrt {
     use super::*;
 
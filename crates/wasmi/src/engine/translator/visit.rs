@@ -19,6 +19,7 @@ use crate::{
     engine::{
         translator::{AcquiredTarget, Provider},
         BlockType,
+        ExtensionOp,
         FuelCosts,
     },
     ir::{self, index, index::FuncType, BoundedRegSpan, Const16, Instruction, Reg},
@@ -141,6 +142,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_loop(&mut self, block_type: wasmparser::BlockType) -> Self::Output {
         let block_type = BlockType::new(block_type, &self.module);
+        let loop_index = self.next_loop_index();
         if !self.is_reachable() {
             // See `visit_block` for rational of tracking unreachable control flow.
             self.alloc
@@ -181,6 +183,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         // Note: The fuel instruction for the loop must be encoded after the loop header is
         //       pinned so that loop iterations will properly consume fuel per iteration.
         let consume_fuel = self.make_fuel_instr()?;
+        self.encode_count_loop_iteration(loop_index)?;
         // Finally create the loop control frame.
         self.alloc.control_stack.push_frame(LoopControlFrame::new(
             block_type,
@@ -442,6 +445,24 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             )?;
             return Ok(());
         }
+        if self
+            .branch_hint()
+            .is_some_and(|hint| !hint.is_likely_taken())
+        {
+            // Case: the branch is unlikely taken so we move the copies
+            //       of the branch inputs into a cold stub that is placed
+            //       after the function body. This way the likely path
+            //       simply falls through the `branch_nez`.
+            self.alloc.instr_encoder.encode_cold_branch_nez(
+                &mut self.alloc.stack,
+                condition,
+                branch_dst,
+                branch_params,
+                &self.alloc.buffer.providers[..],
+                fuel_info,
+            )?;
+            return Ok(());
+        }
         // Case: We need to copy the branch inputs to where the
         //       control frame expects them before actually branching
         //       to it.
@@ -486,8 +507,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         let provider_params = &mut self.alloc.buffer.providers;
         self.alloc.stack.pop_n(params.len(), provider_params);
         let results = self.alloc.stack.push_dynamic_n(results.len())?;
-        let instr = match self.module.get_engine_func(func_idx) {
-            Some(engine_func) => {
+        let extension_op = self.module.get_extension_op(func_idx);
+        let instr = match (self.module.get_engine_func(func_idx), extension_op) {
+            (Some(engine_func), _) => {
                 // Case: We are calling an internal function and can optimize
                 //       this case by using the special instruction for it.
                 match params.len() {
@@ -495,7 +517,22 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                     _ => Instruction::call_internal(results, engine_func),
                 }
             }
-            None => {
+            (
+                None,
+                Some(ExtensionOp {
+                    extension,
+                    opcode,
+                    len_results,
+                }),
+            ) => {
+                // Case: We are calling an imported function that is resolved
+                //       to an extension instruction which we can execute directly.
+                match params.len() {
+                    0 => Instruction::extension_0(results, extension, len_results, opcode),
+                    _ => Instruction::extension(results, extension, len_results, opcode),
+                }
+            }
+            (None, None) => {
                 // Case: We are calling an imported function and must use the
                 //       general calling operator for it.
                 match params.len() {
@@ -2097,6 +2134,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i32_div_s(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i32_div_s_sat, TypedVal::i32_div_s_sat);
+        }
         self.translate_divrem(
             Instruction::i32_div_s,
             Instruction::i32_div_s_imm16_rhs,
@@ -2115,6 +2155,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i32_div_u(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i32_div_u_sat, TypedVal::i32_div_u_sat);
+        }
         self.translate_divrem::<u32, NonZeroU32>(
             Instruction::i32_div_u,
             Instruction::i32_div_u_imm16_rhs,
@@ -2133,6 +2176,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i32_rem_s(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i32_rem_s_sat, TypedVal::i32_rem_s_sat);
+        }
         self.translate_divrem(
             Instruction::i32_rem_s,
             Instruction::i32_rem_s_imm16_rhs,
@@ -2151,6 +2197,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i32_rem_u(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i32_rem_u_sat, TypedVal::i32_rem_u_sat);
+        }
         self.translate_divrem::<u32, NonZeroU32>(
             Instruction::i32_rem_u,
             Instruction::i32_rem_u_imm16_rhs,
@@ -2414,6 +2463,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i64_div_s(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i64_div_s_sat, TypedVal::i64_div_s_sat);
+        }
         self.translate_divrem(
             Instruction::i64_div_s,
             Instruction::i64_div_s_imm16_rhs,
@@ -2432,6 +2484,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i64_div_u(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i64_div_u_sat, TypedVal::i64_div_u_sat);
+        }
         self.translate_divrem::<u64, NonZeroU64>(
             Instruction::i64_div_u,
             Instruction::i64_div_u_imm16_rhs,
@@ -2450,6 +2505,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i64_rem_s(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i64_rem_s_sat, TypedVal::i64_rem_s_sat);
+        }
         self.translate_divrem(
             Instruction::i64_rem_s,
             Instruction::i64_rem_s_imm16_rhs,
@@ -2468,6 +2526,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_i64_rem_u(&mut self) -> Self::Output {
+        if self.is_saturating_div_rem() {
+            return self.translate_divrem_sat(Instruction::i64_rem_u_sat, TypedVal::i64_rem_u_sat);
+        }
         self.translate_divrem::<u64, NonZeroU64>(
             Instruction::i64_rem_u,
             Instruction::i64_rem_u_imm16_rhs,
@@ -1,4 +1,5 @@
 use super::errors::{
+    ConfigError,
     EnforcedLimitsError,
     FuelError,
     FuncError,
@@ -12,10 +13,11 @@ use super::errors::{
 use crate::{
     core::{HostError, TrapCode},
     engine::{ResumableHostError, TranslationError},
+    fuel_trap::{FuelTrap, FuelTrapFrame},
     module::ReadError,
 };
 use alloc::{boxed::Box, string::String};
-use core::{fmt, fmt::Display};
+use core::{any::Any, fmt, fmt::Display};
 use wasmparser::BinaryReaderError as WasmError;
 
 #[cfg(feature = "wat")]
@@ -24,8 +26,19 @@ use wat::Error as WatError;
 /// The generic Wasmi root error type.
 #[derive(Debug)]
 pub struct Error {
+    /// The underlying kind of the error together with its trap backtrace.
+    inner: Box<ErrorInner>,
+}
+
+/// The boxed contents of an [`Error`].
+#[derive(Debug)]
+struct ErrorInner {
     /// The underlying kind of the error and its specific information.
-    kind: Box<ErrorKind>,
+    kind: ErrorKind,
+    /// The Wasm call frames captured when the execution trapped, starting with the innermost one.
+    ///
+    /// Empty unless the [`Error`] is a [`TrapCode`] raised while executing Wasm.
+    backtrace: Box<[FuelTrapFrame]>,
 }
 
 #[test]
@@ -38,7 +51,10 @@ impl Error {
     /// Creates a new [`Error`] from the [`ErrorKind`].
     fn from_kind(kind: ErrorKind) -> Self {
         Self {
-            kind: Box::new(kind),
+            inner: Box::new(ErrorInner {
+                kind,
+                backtrace: Box::default(),
+            }),
         }
     }
 
@@ -62,6 +78,25 @@ impl Error {
         Self::from_kind(ErrorKind::Host(Box::new(host_error)))
     }
 
+    /// Creates a new [`Error`] that suspends the execution with the host `payload`.
+    ///
+    /// Host functions return this [`Error`] in order to suspend a resumable call until
+    /// the embedder completed an operation described by `payload`, e.g. an asynchronous
+    /// system call. The `payload` can be retrieved from the [`ResumableInvocation`] via
+    /// [`ResumableInvocation::payload_mut`] or [`ResumableInvocation::take_payload`].
+    ///
+    /// [`ResumableInvocation`]: crate::ResumableInvocation
+    /// [`ResumableInvocation::payload_mut`]: crate::ResumableInvocation::payload_mut
+    /// [`ResumableInvocation::take_payload`]: crate::ResumableInvocation::take_payload
+    #[inline]
+    #[cold]
+    pub fn suspend<P>(payload: P) -> Self
+    where
+        P: Any + Send,
+    {
+        Self::from_kind(ErrorKind::Suspended(HostPayload::new(Box::new(payload))))
+    }
+
     /// Creates a new `Error` representing an explicit program exit with a classic `i32` exit status value.
     ///
     /// # Note
@@ -75,7 +110,39 @@ impl Error {
 
     /// Returns the [`ErrorKind`] of the [`Error`].
     pub fn kind(&self) -> &ErrorKind {
-        &self.kind
+        &self.inner.kind
+    }
+
+    /// Returns the [`ErrorCategory`] of the [`Error`].
+    ///
+    /// This allows to handle errors by their category without string matching their messages.
+    pub fn category(&self) -> ErrorCategory<'_> {
+        if let ErrorKind::TrapCode(code) = self.kind() {
+            return ErrorCategory::Trap {
+                code: *code,
+                backtrace: &self.inner.backtrace,
+            };
+        }
+        self.kind().category()
+    }
+
+    /// Returns `true` if the [`Error`] is a [`TrapCode`] that is still missing its backtrace.
+    ///
+    /// This includes [`TrapCode`] errors returned by host functions.
+    pub(crate) fn needs_backtrace(&self) -> bool {
+        match self.kind() {
+            ErrorKind::TrapCode(_) => self.inner.backtrace.is_empty(),
+            ErrorKind::ResumableHost(error) => error.error().needs_backtrace(),
+            _ => false,
+        }
+    }
+
+    /// Attaches the Wasm call frames of the trap `backtrace` to the [`Error`].
+    pub(crate) fn set_backtrace(&mut self, backtrace: Box<[FuelTrapFrame]>) {
+        match &mut self.inner.kind {
+            ErrorKind::ResumableHost(error) => error.error_mut().set_backtrace(backtrace),
+            _ => self.inner.backtrace = backtrace,
+        }
     }
 
     /// Returns a reference to [`TrapCode`] if [`Error`] is a [`TrapCode`].
@@ -90,15 +157,32 @@ impl Error {
         self.kind().as_i32_exit_status()
     }
 
+    /// Returns `true` if the [`Error`] reports a cancelled streaming compilation.
+    ///
+    /// Read more about cancellation in [`CancellationToken`](crate::CancellationToken).
+    pub fn is_cancelled(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::Translation(TranslationError::Cancelled)
+        )
+    }
+
     /// Downcasts the [`Error`] into the `T: HostError` if possible.
     ///
     /// Returns `None` otherwise.
+    ///
+    /// # Note
+    ///
+    /// The [`HostError`] returned by a host function is preserved as is while it
+    /// propagates through Wasm call frames, including indirect and tail calls, as
+    /// well as through host functions that forward the [`Error`] of nested calls.
     #[inline]
     pub fn downcast_ref<T>(&self) -> Option<&T>
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .as_host()
             .and_then(<(dyn HostError + 'static)>::downcast_ref)
     }
@@ -111,7 +195,8 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .as_host_mut()
             .and_then(<(dyn HostError + 'static)>::downcast_mut)
     }
@@ -124,15 +209,41 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .into_host()
             .and_then(|error| error.downcast().ok())
             .map(|boxed| *boxed)
     }
 
+    /// Returns an exclusive reference to the suspension payload if it is of type `P`.
+    ///
+    /// Returns `None` if the [`Error`] has no suspension payload or if it is not of type `P`.
+    #[inline]
+    pub fn payload_mut<P>(&mut self) -> Option<&mut P>
+    where
+        P: Any,
+    {
+        match &mut self.inner.kind {
+            ErrorKind::Suspended(payload) => payload.downcast_mut(),
+            _ => None,
+        }
+    }
+
+    /// Takes the suspension payload out of the [`Error`] if any.
+    ///
+    /// Returns `None` if the [`Error`] has no suspension payload or if it has already been taken.
+    #[inline]
+    pub fn take_payload(&mut self) -> Option<Box<dyn Any + Send>> {
+        match &mut self.inner.kind {
+            ErrorKind::Suspended(payload) => payload.take(),
+            _ => None,
+        }
+    }
+
     pub(crate) fn into_resumable(self) -> Result<ResumableHostError, Error> {
-        if matches!(&*self.kind, ErrorKind::ResumableHost(_)) {
-            let ErrorKind::ResumableHost(error) = *self.kind else {
+        if matches!(self.kind(), ErrorKind::ResumableHost(_)) {
+            let ErrorKind::ResumableHost(error) = self.inner.kind else {
                 unreachable!("asserted that host error is resumable")
             };
             return Ok(error);
@@ -142,11 +253,15 @@ impl Error {
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.kind().source()
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(&self.kind, f)
+        Display::fmt(self.kind(), f)
     }
 }
 
@@ -162,6 +277,14 @@ pub enum ErrorKind {
     I32ExitStatus(i32),
     /// A trap as defined by the WebAssembly specification.
     Host(Box<dyn HostError>),
+    /// A Wasm execution ran out of fuel.
+    ///
+    /// # Note
+    ///
+    /// This is equivalent to [`TrapCode::OutOfFuel`] but carries diagnostics.
+    FuelTrap(FuelTrap),
+    /// A host function suspended the execution with a payload.
+    Suspended(HostPayload),
     /// An error stemming from a host function call with resumable state information.
     ///
     /// # Note
@@ -193,6 +316,8 @@ pub enum ErrorKind {
     Translation(TranslationError),
     /// Encountered when an enforced limit is exceeded.
     Limits(EnforcedLimitsError),
+    /// Encountered when a [`Config`](crate::Config) is invalid.
+    Config(ConfigError),
     /// Encountered for Wasmi bytecode related errors.
     Ir(IrError),
     /// Encountered an error from the `wat` crate.
@@ -201,10 +326,60 @@ pub enum ErrorKind {
 }
 
 impl ErrorKind {
+    /// Returns the [`ErrorCategory`] of the [`ErrorKind`].
+    ///
+    /// # Note
+    ///
+    /// The backtrace of an [`ErrorKind::TrapCode`] is stored in its [`Error`]
+    /// and therefore only available via [`Error::category`].
+    pub fn category(&self) -> ErrorCategory<'_> {
+        match self {
+            Self::TrapCode(code) => ErrorCategory::Trap {
+                code: *code,
+                backtrace: &[],
+            },
+            Self::FuelTrap(trap) => ErrorCategory::Trap {
+                code: trap.trap_code(),
+                backtrace: trap.frames(),
+            },
+            Self::I32ExitStatus(status) => ErrorCategory::Exit { status: *status },
+            Self::Wasm(error) => ErrorCategory::Validation {
+                offset: Some(error.offset()),
+            },
+            Self::Translation(TranslationError::Cancelled) => ErrorCategory::Other,
+            Self::Read(_) | Self::Translation(_) | Self::Limits(_) => {
+                ErrorCategory::Validation { offset: None }
+            }
+            #[cfg(feature = "wat")]
+            Self::Wat(_) => ErrorCategory::Validation { offset: None },
+            Self::Linker(error) => {
+                let import = error.import_name();
+                ErrorCategory::Instantiation {
+                    import: Some((import.module(), import.name())),
+                }
+            }
+            Self::Instantiation(_) => ErrorCategory::Instantiation { import: None },
+            Self::Host(error) => ErrorCategory::Host {
+                payload: error.as_ref(),
+            },
+            Self::ResumableHost(error) => error.error().category(),
+            Self::Suspended(_) => ErrorCategory::Suspended,
+            Self::Message(_)
+            | Self::Global(_)
+            | Self::Memory(_)
+            | Self::Table(_)
+            | Self::Fuel(_)
+            | Self::Func(_)
+            | Self::Config(_)
+            | Self::Ir(_) => ErrorCategory::Other,
+        }
+    }
+
     /// Returns a reference to [`TrapCode`] if [`ErrorKind`] is a [`TrapCode`].
     pub fn as_trap_code(&self) -> Option<TrapCode> {
         match self {
             Self::TrapCode(trap_code) => Some(*trap_code),
+            Self::FuelTrap(trap) => Some(trap.trap_code()),
             _ => None,
         }
     }
@@ -221,6 +396,7 @@ impl ErrorKind {
     pub fn as_host(&self) -> Option<&dyn HostError> {
         match self {
             Self::Host(error) => Some(error.as_ref()),
+            Self::ResumableHost(error) => error.error().kind().as_host(),
             _ => None,
         }
     }
@@ -229,6 +405,7 @@ impl ErrorKind {
     pub fn as_host_mut(&mut self) -> Option<&mut dyn HostError> {
         match self {
             Self::Host(error) => Some(error.as_mut()),
+            Self::ResumableHost(error) => error.error_mut().inner.kind.as_host_mut(),
             _ => None,
         }
     }
@@ -237,13 +414,79 @@ impl ErrorKind {
     pub fn into_host(self) -> Option<Box<dyn HostError>> {
         match self {
             Self::Host(error) => Some(error),
+            Self::ResumableHost(error) => error.into_error().inner.kind.into_host(),
             _ => None,
         }
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for ErrorKind {}
+impl std::error::Error for ErrorKind {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        // Note: the wrapped errors are not returned themselves since
+        //       `ErrorKind` displays the same message as its wrapped error.
+        //       Host errors are the exception since they are not known to Wasmi.
+        let error: &dyn std::error::Error = match self {
+            Self::Host(error) => return Some(HostErrorSource::new(error)),
+            Self::Global(error) => error,
+            Self::Memory(error) => error,
+            Self::Table(error) => error,
+            Self::Linker(error) => error,
+            Self::Instantiation(error) => error,
+            Self::Fuel(error) => error,
+            Self::Func(error) => error,
+            Self::Read(error) => error,
+            Self::Wasm(error) => error,
+            Self::Translation(error) => error,
+            Self::Limits(error) => error,
+            Self::Config(error) => error,
+            Self::ResumableHost(error) => error,
+            Self::Ir(error) => error,
+            #[cfg(feature = "wat")]
+            Self::Wat(error) => error,
+            Self::TrapCode(_)
+            | Self::Message(_)
+            | Self::I32ExitStatus(_)
+            | Self::FuelTrap(_)
+            | Self::Suspended(_) => return None,
+        };
+        error.source()
+    }
+}
+
+/// Adapter exposing a [`HostError`] as the [`std::error::Error::source`] of an [`Error`].
+#[cfg(feature = "std")]
+#[repr(transparent)]
+struct HostErrorSource(Box<dyn HostError>);
+
+#[cfg(feature = "std")]
+impl HostErrorSource {
+    /// Wraps the boxed host `error` without moving it.
+    ///
+    /// The `&Box` is required since `dyn HostError` itself cannot be coerced to `dyn Error`.
+    #[allow(clippy::borrowed_box)]
+    fn new(error: &Box<dyn HostError>) -> &Self {
+        // Safety: `HostErrorSource` is a `#[repr(transparent)]` wrapper around `Box<dyn HostError>`.
+        unsafe { &*(error as *const Box<dyn HostError>).cast::<Self>() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for HostErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for HostErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HostErrorSource {}
 
 impl Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -252,6 +495,8 @@ impl Display for ErrorKind {
             Self::I32ExitStatus(status) => writeln!(f, "Exited with i32 exit status {status}"),
             Self::Message(message) => Display::fmt(message, f),
             Self::Host(error) => Display::fmt(error, f),
+            Self::FuelTrap(trap) => Display::fmt(trap, f),
+            Self::Suspended(payload) => Display::fmt(payload, f),
             Self::Global(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Table(error) => Display::fmt(error, f),
@@ -263,6 +508,7 @@ impl Display for ErrorKind {
             Self::Wasm(error) => Display::fmt(error, f),
             Self::Translation(error) => Display::fmt(error, f),
             Self::Limits(error) => Display::fmt(error, f),
+            Self::Config(error) => Display::fmt(error, f),
             Self::ResumableHost(error) => Display::fmt(error, f),
             Self::Ir(error) => Display::fmt(error, f),
             #[cfg(feature = "wat")]
@@ -271,6 +517,110 @@ impl Display for ErrorKind {
     }
 }
 
+/// The category of an [`Error`] together with its structured information.
+///
+/// Returned by [`Error::category`] in order to handle errors without string matching their messages.
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub enum ErrorCategory<'a> {
+    /// The execution trapped.
+    Trap {
+        /// The [`TrapCode`] of the trap.
+        code: TrapCode,
+        /// The captured Wasm call frames starting with the innermost one.
+        ///
+        /// # Note
+        ///
+        /// If enabled via [`Config::wasm_backtrace`], call frames are captured for all traps
+        /// that occur while executing Wasm, including traps of called host functions.
+        /// Otherwise they are empty. Only the innermost [`FuelTrap::MAX_FRAMES`] call frames
+        /// are captured.
+        ///
+        /// [`Config::wasm_backtrace`]: crate::Config::wasm_backtrace
+        backtrace: &'a [FuelTrapFrame],
+    },
+    /// The execution exited with an `i32` exit status, e.g. via WASI `proc_exit`.
+    Exit {
+        /// The `i32` exit status.
+        status: i32,
+    },
+    /// The Wasm input failed to parse, validate or translate.
+    Validation {
+        /// The byte offset within the Wasm binary if known.
+        offset: Option<usize>,
+    },
+    /// The module failed to link or instantiate.
+    Instantiation {
+        /// The module and field name of the offending import if known.
+        import: Option<(&'a str, &'a str)>,
+    },
+    /// A host function returned a [`HostError`].
+    Host {
+        /// The [`HostError`] returned by the host function.
+        ///
+        /// Use [`Error::downcast_ref`] to access the concrete type.
+        payload: &'a dyn HostError,
+    },
+    /// A host function suspended the execution.
+    ///
+    /// Use [`Error::payload_mut`] to access the suspension payload.
+    Suspended,
+    /// Any other error, e.g. invalid uses of the Wasmi API or custom messages.
+    Other,
+}
+
+/// The payload of an [`Error`] created via [`Error::suspend`].
+pub struct HostPayload {
+    /// The payload unless it has been taken.
+    payload: Option<Box<dyn Any + Send>>,
+}
+
+// # Safety
+//
+// `HostPayload` is not `Sync` because its payload is only required to be `Send`.
+//
+// However, `HostPayload` never grants access to its payload via shared references
+// but only via exclusive references or by value which cannot be used concurrently.
+//
+// Therefore `HostPayload` can safely be assumed to be `Sync`.
+unsafe impl Sync for HostPayload {}
+
+impl HostPayload {
+    /// Creates a new [`HostPayload`] from `payload`.
+    fn new(payload: Box<dyn Any + Send>) -> Self {
+        Self {
+            payload: Some(payload),
+        }
+    }
+
+    /// Returns an exclusive reference to the payload if it is of type `P`.
+    pub fn downcast_mut<P>(&mut self) -> Option<&mut P>
+    where
+        P: Any,
+    {
+        self.payload.as_mut()?.downcast_mut()
+    }
+
+    /// Takes the payload out of the [`HostPayload`] if it has not been taken, yet.
+    pub fn take(&mut self) -> Option<Box<dyn Any + Send>> {
+        self.payload.take()
+    }
+}
+
+impl fmt::Debug for HostPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostPayload")
+            .field("is_taken", &self.payload.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for HostPayload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "execution suspended by host function")
+    }
+}
+
 macro_rules! impl_from {
     ( $( impl From<$from:ident> for Error::$name:ident );* $(;)? ) => {
         $(
@@ -286,6 +636,7 @@ macro_rules! impl_from {
 }
 impl_from! {
     impl From<TrapCode> for Error::TrapCode;
+    impl From<FuelTrap> for Error::FuelTrap;
     impl From<GlobalError> for Error::Global;
     impl From<MemoryError> for Error::Memory;
     impl From<TableError> for Error::Table;
@@ -297,6 +648,7 @@ impl_from! {
     impl From<FuelError> for Error::Fuel;
     impl From<FuncError> for Error::Func;
     impl From<EnforcedLimitsError> for Error::Limits;
+    impl From<ConfigError> for Error::Config;
     impl From<ResumableHostError> for Error::ResumableHost;
     impl From<IrError> for Error::Ir;
 }
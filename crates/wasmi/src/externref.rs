@@ -89,6 +89,12 @@ impl ExternObject {
 }
 
 /// Represents a nullable opaque reference to any data within WebAssembly.
+///
+/// # Note
+///
+/// Wasmi does not support the Wasm `gc` proposal. Therefore host data is visible to
+/// Wasm guests only as opaque `externref` and cannot be converted to or from guest
+/// GC structs or arrays, e.g. via `any.convert_extern` or `extern.convert_any`.
 #[derive(Debug, Default, Copy, Clone)]
 #[repr(transparent)]
 pub struct ExternRef {
@@ -157,12 +163,17 @@ impl ExternRef {
     }
 
     /// Creates a new [`ExternRef`] to the given [`ExternObject`].
-    fn from_object(object: ExternObject) -> Self {
+    pub(crate) fn from_object(object: ExternObject) -> Self {
         Self {
             inner: Some(object),
         }
     }
 
+    /// Returns the [`ExternObject`] of the [`ExternRef`] unless it is `null`.
+    pub(crate) fn object(&self) -> Option<&ExternObject> {
+        self.inner.as_ref()
+    }
+
     /// Returns `true` if [`ExternRef`] is `null`.
     pub fn is_null(&self) -> bool {
         self.inner.is_none()
@@ -0,0 +1,161 @@
+use crate::{engine::EngineFunc, func::FuncEntity, store::StoreInner, Func};
+use alloc::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::{cmp::Reverse, slice};
+
+/// The raw fuel counters of a [`Store`] attributed to the executed Wasm function bodies.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Default, Clone)]
+pub struct FuelProfileCounters {
+    /// The fuel consumed by each executed [`EngineFunc`].
+    consumed: BTreeMap<EngineFunc, u64>,
+}
+
+impl FuelProfileCounters {
+    /// Attributes `delta` consumed fuel to the [`EngineFunc`] `func`.
+    #[inline]
+    pub fn record(&mut self, func: EngineFunc, delta: u64) {
+        match self.consumed.entry(func) {
+            Entry::Occupied(mut entry) => {
+                let consumed = entry.get_mut();
+                *consumed = consumed.saturating_add(delta);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(delta);
+            }
+        }
+    }
+
+    /// Resets all fuel counters to zero.
+    pub fn reset(&mut self) {
+        self.consumed.clear();
+    }
+
+    /// Returns the fuel consumed by the [`EngineFunc`] `func` if any.
+    fn get(&self, func: EngineFunc) -> Option<u64> {
+        self.consumed.get(&func).copied()
+    }
+}
+
+/// A single entry of a [`FuelProfile`].
+#[derive(Debug, Copy, Clone)]
+pub struct FuelProfileEntry {
+    /// The Wasm function that consumed the fuel.
+    func: Func,
+    /// The amount of fuel consumed by `func`.
+    consumed: u64,
+}
+
+impl FuelProfileEntry {
+    /// Returns the Wasm [`Func`] that consumed the fuel.
+    ///
+    /// Use [`Func::symbol`] to symbolicate the [`Func`].
+    pub fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the amount of fuel consumed by the [`Func`].
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+}
+
+/// A breakdown of the fuel consumed by each executed Wasm function of a [`Store`].
+///
+/// # Note
+///
+/// - Fuel is attributed to Wasm function bodies. Therefore multiple instances
+///   of the same [`Module`] share their fuel counters and only the first
+///   instantiated [`Func`] is reported for each function body.
+/// - Only fuel consumed by the execution of Wasm basic blocks is attributed.
+///   Fuel consumed by bulk operations such as `memory.copy` or by lazy function
+///   compilation is not part of the [`FuelProfile`].
+///
+/// [`Store`]: crate::Store
+/// [`Module`]: crate::Module
+#[derive(Debug, Default, Clone)]
+pub struct FuelProfile {
+    /// The entries sorted by consumed fuel in descending order.
+    entries: Vec<FuelProfileEntry>,
+}
+
+impl FuelProfile {
+    /// Creates a new [`FuelProfile`] from the fuel counters of the [`StoreInner`].
+    pub(crate) fn new(store: &StoreInner, counters: &FuelProfileCounters) -> Self {
+        let mut visited = BTreeSet::new();
+        let mut entries = Vec::new();
+        for (func, entity) in store.funcs() {
+            let FuncEntity::Wasm(entity) = entity else {
+                continue;
+            };
+            let body = entity.func_body();
+            let Some(consumed) = counters.get(body) else {
+                continue;
+            };
+            if !visited.insert(body) {
+                continue;
+            }
+            entries.push(FuelProfileEntry { func, consumed });
+        }
+        entries.sort_by_key(|entry| Reverse(entry.consumed));
+        Self { entries }
+    }
+
+    /// Returns an iterator over the [`FuelProfileEntry`] of the [`FuelProfile`].
+    ///
+    /// The entries are yielded in descending order of their consumed fuel.
+    pub fn iter(&self) -> FuelProfileIter<'_> {
+        FuelProfileIter {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns the number of Wasm functions that consumed fuel.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no Wasm function consumed fuel.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the total amount of fuel attributed to Wasm functions.
+    pub fn total(&self) -> u64 {
+        self.entries
+            .iter()
+            .fold(0_u64, |sum, entry| sum.saturating_add(entry.consumed))
+    }
+}
+
+impl<'a> IntoIterator for &'a FuelProfile {
+    type Item = &'a FuelProfileEntry;
+    type IntoIter = FuelProfileIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the [`FuelProfileEntry`] of a [`FuelProfile`].
+#[derive(Debug)]
+pub struct FuelProfileIter<'a> {
+    iter: slice::Iter<'a, FuelProfileEntry>,
+}
+
+impl<'a> Iterator for FuelProfileIter<'a> {
+    type Item = &'a FuelProfileEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl ExactSizeIterator for FuelProfileIter<'_> {}
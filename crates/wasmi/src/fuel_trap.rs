@@ -0,0 +1,192 @@
+use crate::{
+    core::TrapCode,
+    engine::EngineFunc,
+    func::FuncEntity,
+    store::StoreInner,
+    Func,
+    FuncSymbol,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Display};
+
+/// Diagnostics of a Wasm execution that ran out of fuel.
+///
+/// Attached to the [`Error`] of an execution that trapped with [`TrapCode::OutOfFuel`]
+/// while executing a Wasm basic block and accessible via [`ErrorKind::FuelTrap`].
+///
+/// # Note
+///
+/// - Call frames are only captured if enabled via [`Config::wasm_backtrace`].
+/// - Only the innermost [`FuelTrap::MAX_FRAMES`] call frames are captured.
+/// - Executions that ran out of fuel in bulk operations such as `memory.copy`,
+///   in host functions or upon lazy function compilation trap with a plain
+///   [`TrapCode::OutOfFuel`] without diagnostics.
+///
+/// [`Error`]: crate::Error
+/// [`Config::wasm_backtrace`]: crate::Config::wasm_backtrace
+/// [`ErrorKind::FuelTrap`]: crate::errors::ErrorKind::FuelTrap
+#[derive(Debug)]
+pub struct FuelTrap {
+    /// The captured call frames starting with the innermost one.
+    frames: Box<[FuelTrapFrame]>,
+    /// The number of Wasm call frames at the time of the trap.
+    call_depth: usize,
+}
+
+/// A call frame of a Wasm execution that ran out of fuel.
+#[derive(Debug, Clone)]
+pub struct FuelTrapFrame {
+    /// The Wasm function executed by the call frame.
+    func: Func,
+    /// The name under which `func` is exported if any.
+    name: Option<Box<str>>,
+    /// The symbol of `func` if its module has registered symbols.
+    symbol: Option<FuncSymbol>,
+    /// The index of the Wasmi bytecode instruction executed next by the call frame.
+    instr_offset: Option<usize>,
+}
+
+impl FuelTrap {
+    /// The maximum number of captured call frames.
+    pub const MAX_FRAMES: usize = 16;
+
+    /// Creates a new [`FuelTrap`] symbolicating the call `frames` using `store`.
+    ///
+    /// The `frames` are the executed Wasm function bodies and their instruction offsets
+    /// starting with the innermost call frame.
+    pub(crate) fn new(
+        store: &StoreInner,
+        frames: impl IntoIterator<Item = (EngineFunc, Option<usize>)>,
+        call_depth: usize,
+    ) -> Self {
+        Self {
+            frames: FuelTrapFrame::capture(store, frames),
+            call_depth,
+        }
+    }
+
+    /// Returns the [`TrapCode`] of the [`FuelTrap`] which is always [`TrapCode::OutOfFuel`].
+    pub fn trap_code(&self) -> TrapCode {
+        TrapCode::OutOfFuel
+    }
+
+    /// Returns the innermost call frame which ran out of fuel if any.
+    pub fn current(&self) -> Option<&FuelTrapFrame> {
+        self.frames.first()
+    }
+
+    /// Returns the captured call frames starting with the innermost one.
+    pub fn frames(&self) -> &[FuelTrapFrame] {
+        &self.frames
+    }
+
+    /// Returns the number of Wasm call frames at the time of the trap.
+    ///
+    /// This might be larger than the number of captured call frames.
+    pub fn call_depth(&self) -> usize {
+        self.call_depth
+    }
+}
+
+impl FuelTrapFrame {
+    /// Symbolicates the innermost [`FuelTrap::MAX_FRAMES`] call `frames` using `store`.
+    ///
+    /// The `frames` are the executed Wasm function bodies and their instruction offsets
+    /// starting with the innermost call frame.
+    pub(crate) fn capture(
+        store: &StoreInner,
+        frames: impl IntoIterator<Item = (EngineFunc, Option<usize>)>,
+    ) -> Box<[Self]> {
+        frames
+            .into_iter()
+            .take(FuelTrap::MAX_FRAMES)
+            .filter_map(|(body, instr_offset)| {
+                let func = find_func(store, body)?;
+                Some(Self {
+                    func,
+                    name: find_export_name(store, body),
+                    symbol: store.engine().resolve_symbol(body),
+                    instr_offset,
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice()
+    }
+
+    /// Returns the Wasm [`Func`] executed by the call frame.
+    ///
+    /// # Note
+    ///
+    /// Multiple instances of the same Wasm module share their function bodies.
+    /// In this case the first instantiated [`Func`] is returned.
+    pub fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the name under which the executed Wasm function is exported if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the [`FuncSymbol`] of the executed Wasm function if its module has registered symbols.
+    ///
+    /// Read more in [`Engine::register_symbols`](crate::Engine::register_symbols).
+    pub fn symbol(&self) -> Option<&FuncSymbol> {
+        self.symbol.as_ref()
+    }
+
+    /// Returns the index of the Wasmi bytecode instruction executed next by the call frame.
+    ///
+    /// For the innermost call frame this is the instruction that ran out of fuel.
+    /// For all other call frames this is the instruction following the call.
+    pub fn instr_offset(&self) -> Option<usize> {
+        self.instr_offset
+    }
+}
+
+/// Returns the first Wasm [`Func`] of `store` with the function `body`.
+fn find_func(store: &StoreInner, body: EngineFunc) -> Option<Func> {
+    store.funcs().find_map(|(func, entity)| match entity {
+        FuncEntity::Wasm(entity) if entity.func_body() == body => Some(func),
+        _ => None,
+    })
+}
+
+/// Returns the name of an export of `store` that refers to a Wasm function with `body`.
+fn find_export_name(store: &StoreInner, body: EngineFunc) -> Option<Box<str>> {
+    store.instances().find_map(|instance| {
+        instance.exports().find_map(|export| {
+            let name = export.name();
+            let func = export.into_func()?;
+            match store.resolve_func(&func) {
+                FuncEntity::Wasm(entity) if entity.func_body() == body => Some(name.into()),
+                _ => None,
+            }
+        })
+    })
+}
+
+impl Display for FuelTrap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.trap_code().trap_message())?;
+        for (depth, frame) in self.frames.iter().enumerate() {
+            write!(f, "\n    #{depth} ")?;
+            match (frame.symbol(), frame.name()) {
+                (Some(symbol), _) => write!(f, "{symbol}")?,
+                (None, Some(name)) => write!(f, "{name}")?,
+                (None, None) => write!(f, "<unnamed>")?,
+            }
+            if let Some(offset) = frame.instr_offset() {
+                write!(f, " at instruction {offset}")?;
+            }
+        }
+        if self.call_depth > self.frames.len() {
+            write!(
+                f,
+                "\n    ... {} more frames",
+                self.call_depth - self.frames.len()
+            )?;
+        }
+        Ok(())
+    }
+}
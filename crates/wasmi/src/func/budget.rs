@@ -0,0 +1,95 @@
+use crate::{core::TrapCode, Error};
+use core::time::Duration;
+
+/// The maximum wall-clock time a [`Linker`] defined host function may take per call.
+///
+/// Set via [`Linker::func_budget`].
+///
+/// # Note
+///
+/// A running host function cannot be interrupted. Instead its duration is measured
+/// and an overrun is reported once the host function returned. The results of an
+/// overrunning host function are discarded.
+///
+/// Only available with the `std` crate feature on non-`wasm32` targets since
+/// calls are measured via [`std::time::Instant`] which is unsupported on
+/// `wasm32-unknown-unknown`.
+///
+/// [`Linker`]: crate::Linker
+/// [`Linker::func_budget`]: crate::Linker::func_budget
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HostFuncBudget {
+    /// The maximum duration of a single call.
+    limit: Duration,
+    /// What happens if a call takes longer than `limit`.
+    overrun: BudgetOverrun,
+}
+
+/// What happens if a host function call exceeds its [`HostFuncBudget`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BudgetOverrun {
+    /// The host function call traps with [`TrapCode::HostBudgetExceeded`].
+    Trap,
+    /// The host function call suspends the execution with a [`BudgetExceeded`] payload.
+    ///
+    /// The payload is available via [`ResumableInvocation::payload_mut`] for
+    /// resumable calls and via [`Error::payload_mut`] otherwise.
+    ///
+    /// [`ResumableInvocation::payload_mut`]: crate::ResumableInvocation::payload_mut
+    Suspend,
+}
+
+/// The suspension payload of a host function call that exceeded its [`HostFuncBudget`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    /// The budget of the host function.
+    pub limit: Duration,
+    /// The actual duration of the host function call.
+    pub elapsed: Duration,
+}
+
+impl HostFuncBudget {
+    /// Creates a [`HostFuncBudget`] that traps calls that take longer than `limit`.
+    pub fn trap(limit: Duration) -> Self {
+        Self {
+            limit,
+            overrun: BudgetOverrun::Trap,
+        }
+    }
+
+    /// Creates a [`HostFuncBudget`] that suspends calls that take longer than `limit`.
+    pub fn suspend(limit: Duration) -> Self {
+        Self {
+            limit,
+            overrun: BudgetOverrun::Suspend,
+        }
+    }
+
+    /// Returns the maximum duration of a single call.
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    /// Returns what happens if a call takes longer than the limit.
+    pub fn overrun(&self) -> BudgetOverrun {
+        self.overrun
+    }
+
+    /// Checks a host function call that took `elapsed` against the [`HostFuncBudget`].
+    ///
+    /// # Errors
+    ///
+    /// If `elapsed` exceeds the limit of the [`HostFuncBudget`].
+    pub(crate) fn check(&self, elapsed: Duration) -> Result<(), Error> {
+        if elapsed <= self.limit {
+            return Ok(());
+        }
+        match self.overrun {
+            BudgetOverrun::Trap => Err(Error::from(TrapCode::HostBudgetExceeded)),
+            BudgetOverrun::Suspend => Err(Error::suspend(BudgetExceeded {
+                limit: self.limit,
+                elapsed,
+            })),
+        }
+    }
+}
@@ -13,6 +13,10 @@ pub enum FuncError {
     MismatchingResultType,
     /// Specified an incorrect number of results.
     MismatchingResultLen,
+    /// A host function called back into the instance that called it.
+    ///
+    /// Only denied if the `StorePolicy::DenyReentrantGuestCalls` policy is set.
+    ReentrantCall,
 }
 
 #[cfg(feature = "std")]
@@ -36,6 +40,12 @@ impl Display for FuncError {
             FuncError::MismatchingResultLen => {
                 write!(f, "encountered an incorrect number of results")
             }
+            FuncError::ReentrantCall => {
+                write!(
+                    f,
+                    "denied reentrant call into the instance of the calling host function"
+                )
+            }
         }
     }
 }
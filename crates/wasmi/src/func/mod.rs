@@ -1,3 +1,5 @@
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+mod budget;
 mod caller;
 mod error;
 mod func_type;
@@ -5,6 +7,8 @@ mod funcref;
 mod into_func;
 mod typed_func;
 
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub use self::budget::{BudgetExceeded, BudgetOverrun, HostFuncBudget};
 pub(crate) use self::typed_func::CallResultsTuple;
 pub use self::{
     caller::Caller,
@@ -22,10 +26,22 @@ use super::{
     StoreContext,
     Stored,
 };
-use crate::{collections::arena::ArenaIndex, engine::ResumableCall, Engine, Error, Val};
-use alloc::{boxed::Box, sync::Arc};
+use crate::{
+    collections::arena::ArenaIndex,
+    engine::ResumableCall,
+    Engine,
+    Error,
+    FuncSymbol,
+    Val,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::{fmt, fmt::Debug, num::NonZeroU32};
 
+/// The maximum number of calls to a batched host function that are dispatched as a single batch.
+///
+/// Read more about batches in [`Func::new_batched`].
+pub const MAX_HOST_CALL_BATCH: usize = 16;
+
 /// A raw index to a function entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FuncIdx(NonZeroU32);
@@ -109,6 +125,11 @@ pub struct HostFuncEntity {
     ty: DedupFuncType,
     /// A reference to the trampoline of the host function.
     func: Trampoline,
+    /// Is `true` if consecutive calls to the host function can be dispatched as a batch.
+    batched: bool,
+    /// The optional time budget of the host function.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    budget: Option<HostFuncBudget>,
 }
 
 impl HostFuncEntity {
@@ -122,9 +143,38 @@ impl HostFuncEntity {
             len_results,
             ty,
             func,
+            batched: false,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            budget: None,
         }
     }
 
+    /// Sets whether consecutive calls to the [`HostFuncEntity`] can be dispatched as a batch.
+    pub fn with_batched(mut self, batched: bool) -> Self {
+        self.batched = batched;
+        self
+    }
+
+    /// Returns `true` if consecutive calls to the [`HostFuncEntity`] can be dispatched as a batch.
+    ///
+    /// Read more about batches in [`Func::new_batched`].
+    pub fn is_batched(&self) -> bool {
+        self.batched
+    }
+
+    /// Sets the time budget of the [`HostFuncEntity`].
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub fn with_budget(mut self, budget: Option<HostFuncBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Returns the time budget of the [`HostFuncEntity`] if any.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub fn budget(&self) -> Option<HostFuncBudget> {
+        self.budget
+    }
+
     /// Returns the number of parameters of the [`HostFuncEntity`].
     pub fn len_params(&self) -> u16 {
         self.len_params
@@ -199,6 +249,11 @@ pub struct HostFuncTrampolineEntity<T> {
     ty: FuncType,
     /// The trampoline of the associated host function.
     trampoline: TrampolineEntity<T>,
+    /// Is `true` if the trampoline accepts batches of calls.
+    batched: bool,
+    /// The optional time budget of the associated host function.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    budget: Option<HostFuncBudget>,
 }
 
 impl<T> Clone for HostFuncTrampolineEntity<T> {
@@ -206,6 +261,9 @@ impl<T> Clone for HostFuncTrampolineEntity<T> {
         Self {
             ty: self.ty.clone(),
             trampoline: self.trampoline.clone(),
+            batched: self.batched,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            budget: self.budget,
         }
     }
 }
@@ -242,14 +300,89 @@ impl<T> HostFuncTrampolineEntity<T> {
             func(caller, params, results)?;
             Ok(func_results.encode_results_from_slice(results).unwrap())
         });
-        Self { ty, trampoline }
+        Self {
+            ty,
+            trampoline,
+            batched: false,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            budget: None,
+        }
+    }
+
+    /// Creates a new host function trampoline from the given dynamically typed batch closure.
+    ///
+    /// Read more about batches in [`Func::new_batched`].
+    pub fn new_batched(
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, usize, &[Val], &mut [Val]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        let param_types: Box<[_]> = ty.params().into();
+        let result_types: Box<[_]> = ty.results().into();
+        let trampoline = <TrampolineEntity<T>>::new(move |caller, args| {
+            // Note: the number of calls in the batch is only known upon invocation
+            //       so the parameters and results buffers are allocated here.
+            let len = match param_types.len() {
+                0 => 1,
+                len_params => args.len_params() / len_params,
+            };
+            let mut params = param_types
+                .iter()
+                .copied()
+                .cycle()
+                .take(len * param_types.len())
+                .map(Val::default)
+                .collect::<Vec<_>>();
+            let mut results = result_types
+                .iter()
+                .copied()
+                .cycle()
+                .take(len * result_types.len())
+                .map(Val::default)
+                .collect::<Vec<_>>();
+            let func_results = args.decode_params_into_slice(&mut params).unwrap();
+            func(caller, len, &params, &mut results)?;
+            Ok(func_results.encode_results_from_slice(&results).unwrap())
+        });
+        Self {
+            ty,
+            trampoline,
+            batched: true,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            budget: None,
+        }
     }
 
     /// Creates a new host function trampoline from the given statically typed closure.
     pub fn wrap<Params, Results>(func: impl IntoFunc<T, Params, Results>) -> Self {
         let (ty, trampoline) = func.into_func();
         // let ty = engine.alloc_func_type(signature);
-        Self { ty, trampoline }
+        Self {
+            ty,
+            trampoline,
+            batched: false,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            budget: None,
+        }
+    }
+
+    /// Returns `true` if the trampoline of the host function accepts batches of calls.
+    pub fn is_batched(&self) -> bool {
+        self.batched
+    }
+
+    /// Returns the time budget of the host function if any.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub fn budget(&self) -> Option<HostFuncBudget> {
+        self.budget
+    }
+
+    /// Sets the time budget of the host function.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub fn set_budget(&mut self, budget: Option<HostFuncBudget>) {
+        self.budget = budget;
     }
 
     /// Returns the [`FuncType`] of the host function.
@@ -321,7 +454,7 @@ impl Func {
     }
 
     /// Returns the underlying stored representation.
-    pub(super) fn as_inner(&self) -> &Stored<FuncIdx> {
+    pub(crate) fn as_inner(&self) -> &Stored<FuncIdx> {
         &self.0
     }
 
@@ -365,6 +498,45 @@ impl Func {
             .alloc_func(host_func.into())
     }
 
+    /// Creates a new host [`Func`] to which consecutive calls can be dispatched as a single batch.
+    ///
+    /// This is like [`Func::new`] but the `func` closure is called with the number of calls
+    /// `len` in the batch followed by the parameters and results of all calls of the batch.
+    /// The parameters and results of the `n`-th call are stored at
+    /// `params[n * len_params..][..len_params]` and `results[n * len_results..][..len_results]`
+    /// respectively where `len_params` and `len_results` match the function type `ty`.
+    ///
+    /// Calls to the host [`Func`] that directly follow each other in a Wasm function, e.g. in
+    /// unrolled loops that encrypt or decrypt arrays per element, are dispatched as a single
+    /// batch of up to [`MAX_HOST_CALL_BATCH`] calls if none of them uses the results of a
+    /// preceding call of the batch. All other calls are dispatched as batches of one call.
+    ///
+    /// # Note
+    ///
+    /// - Only calls to imported functions with parameters are batched.
+    /// - Call hooks and time budgets apply to whole batches instead of single calls.
+    /// - Errors returned by `func` for batches of more than one call are not resumable.
+    ///
+    /// [`MAX_HOST_CALL_BATCH`]: crate::MAX_HOST_CALL_BATCH
+    pub fn new_batched<T>(
+        mut ctx: impl AsContextMut<Data = T>,
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, usize, &[Val], &mut [Val]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        let host_func = HostFuncTrampolineEntity::new_batched(ty.clone(), func);
+        let trampoline = host_func.trampoline().clone();
+        let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
+        let host_func =
+            HostFuncEntity::new(ctx.as_context().engine(), &ty, func).with_batched(true);
+        ctx.as_context_mut()
+            .store
+            .inner
+            .alloc_func(host_func.into())
+    }
+
     /// Creates a new host function from the given closure.
     pub fn wrap<T, Params, Results>(
         mut ctx: impl AsContextMut<Data = T>,
@@ -397,6 +569,21 @@ impl Func {
             .resolve_func_type(self.ty_dedup(&ctx))
     }
 
+    /// Returns the [`FuncSymbol`] of the [`Func`] if its [`Module`] has registered symbols.
+    ///
+    /// Returns `None` for host functions and for Wasm functions of modules without symbols
+    /// registered via [`Engine::register_symbols`].
+    ///
+    /// [`Module`]: crate::Module
+    /// [`Engine::register_symbols`]: crate::Engine::register_symbols
+    pub fn symbol(&self, ctx: impl AsContext) -> Option<FuncSymbol> {
+        let store = &ctx.as_context().store.inner;
+        match store.resolve_func(self) {
+            FuncEntity::Wasm(func) => store.engine().resolve_symbol(func.func_body()),
+            FuncEntity::Host(_) => None,
+        }
+    }
+
     /// Calls the Wasm or host function with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer.
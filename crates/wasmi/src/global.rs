@@ -4,8 +4,14 @@ use crate::{
     core::{UntypedVal, ValType},
     value::WithType,
     Val,
+    WasmTy,
+};
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    fmt::Display,
+    ptr::{self, NonNull},
 };
-use core::{fmt, fmt::Display, ptr::NonNull};
 
 /// A raw index to a global variable entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -143,13 +149,78 @@ impl GlobalType {
     }
 }
 
+/// A host provider that computes the value of a global variable upon every read.
+type GlobalProvider = Arc<dyn Fn() -> UntypedVal + Send + Sync>;
+
+/// A [`Store`] independent computed global variable.
+///
+/// Used by the [`Linker`] to define computed global variables for multiple [`Store`]s.
+///
+/// [`Store`]: crate::Store
+/// [`Linker`]: crate::Linker
+#[derive(Clone)]
+pub(crate) struct ComputedGlobal {
+    /// The value type of the computed global variable.
+    content: ValType,
+    /// The host provider computing the value of the global variable.
+    provider: GlobalProvider,
+}
+
+impl fmt::Debug for ComputedGlobal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ComputedGlobal")
+            .field("content", &self.content)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ComputedGlobal {
+    /// Creates a new [`ComputedGlobal`] whose value is computed by `provider`.
+    pub fn new<V>(provider: impl Fn() -> V + Send + Sync + 'static) -> Self
+    where
+        V: WasmTy,
+    {
+        Self {
+            content: V::ty(),
+            provider: Arc::new(move || provider().into()),
+        }
+    }
+
+    /// Returns the [`GlobalType`] of the [`ComputedGlobal`].
+    pub fn ty(&self) -> GlobalType {
+        GlobalType::new(self.content, Mutability::Const)
+    }
+
+    /// Allocates a new [`Global`] for the [`ComputedGlobal`] in `ctx`.
+    pub fn instantiate(&self, mut ctx: impl AsContextMut) -> Global {
+        ctx.as_context_mut().store.inner.alloc_global(GlobalEntity {
+            value: UntypedVal::default(),
+            ty: self.ty(),
+            provider: Some(self.provider.clone()),
+        })
+    }
+}
+
 /// A global variable entity.
-#[derive(Debug)]
 pub struct GlobalEntity {
     /// The current value of the global variable.
+    ///
+    /// Unused if the global variable is computed by a `provider`.
     value: UntypedVal,
     /// The type of the global variable.
     ty: GlobalType,
+    /// The host provider computing the value of a computed global variable.
+    provider: Option<GlobalProvider>,
+}
+
+impl fmt::Debug for GlobalEntity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GlobalEntity")
+            .field("value", &self.value)
+            .field("ty", &self.ty)
+            .field("computed", &self.provider.is_some())
+            .finish()
+    }
 }
 
 impl GlobalEntity {
@@ -158,6 +229,7 @@ impl GlobalEntity {
         Self {
             ty: GlobalType::new(initial_value.ty(), mutability),
             value: initial_value.into(),
+            provider: None,
         }
     }
 
@@ -204,13 +276,43 @@ impl GlobalEntity {
     }
 
     /// Returns the current untyped value of the global variable.
+    ///
+    /// Computes the value if the global variable is computed.
     pub(crate) fn get_untyped(&self) -> UntypedVal {
-        self.value
+        match &self.provider {
+            Some(provider) => provider(),
+            None => self.value,
+        }
     }
 
     /// Returns a pointer to the untyped value of the global variable.
-    pub(crate) fn get_untyped_ptr(&mut self) -> NonNull<UntypedVal> {
-        NonNull::from(&mut self.value)
+    ///
+    /// Returns `None` if the global variable is computed since it has no stored value.
+    pub(crate) fn get_untyped_ptr(&mut self) -> Option<NonNull<UntypedVal>> {
+        if self.provider.is_some() {
+            return None;
+        }
+        Some(NonNull::from(&mut self.value))
+    }
+
+    /// Returns `true` if the global variable is computed by a host provider.
+    pub(crate) fn is_computed(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// Returns a pointer to the untyped value of the global variable at `this`.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`GlobalEntity::get_untyped_ptr`] this does not create a reference
+    /// to the global variable and thus allows to offset `this` within a flat array.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `this` points to a valid [`GlobalEntity`]
+    /// that is not computed.
+    pub(crate) unsafe fn untyped_ptr_raw(this: *mut Self) -> *mut UntypedVal {
+        unsafe { ptr::addr_of_mut!((*this).value) }
     }
 }
 
@@ -244,6 +346,40 @@ impl Global {
             .alloc_global(GlobalEntity::new(initial_value, mutability))
     }
 
+    /// Creates a new immutable global variable whose value is computed by `provider`.
+    ///
+    /// The `provider` is called upon every read of the global variable, e.g. by
+    /// `global.get` or [`Global::get`], which allows hosts to expose dynamic values
+    /// such as the current epoch to Wasm guests without a host function call.
+    ///
+    /// # Note
+    ///
+    /// - The type of the global variable is `V` and it is immutable.
+    /// - The `provider` must not rely on being called a specific number of times.
+    pub fn new_computed<V>(
+        ctx: impl AsContextMut,
+        provider: impl Fn() -> V + Send + Sync + 'static,
+    ) -> Self
+    where
+        V: WasmTy,
+    {
+        ComputedGlobal::new(provider).instantiate(ctx)
+    }
+
+    /// Returns the stable ID of the [`Global`] within its [`Store`](crate::Store).
+    ///
+    /// # Note
+    ///
+    /// IDs are assigned in the order of allocation, starting at 0, and are never reused.
+    /// They are deterministic for the same sequence of operations on a [`Store`](crate::Store).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Global`].
+    pub fn id(&self, ctx: impl AsContext) -> u32 {
+        ctx.as_context().store.inner.entity_id(self.as_inner())
+    }
+
     /// Returns the [`GlobalType`] of the global variable.
     pub fn ty(&self, ctx: impl AsContext) -> GlobalType {
         ctx.as_context().store.inner.resolve_global(self).ty()
@@ -0,0 +1,767 @@
+use crate::{
+    core::{F32, F64},
+    AsContext,
+    AsContextMut,
+    Caller,
+    Error,
+    Extern,
+    Func,
+    Instance,
+    Memory,
+    StoreContext,
+    StoreContextMut,
+    TypedFunc,
+    Val,
+};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// Marshals strings and byte slices between the host and a Wasm guest.
+///
+/// Strings and byte slices are passed to and from the guest as pairs of `i32`
+/// values `(ptr, len)` that refer to the exported linear memory of the guest.
+///
+/// # Guest Interface
+///
+/// The guest must export its linear memory as `memory` and at least one of the
+/// following allocators:
+///
+/// - `cabi_realloc(old_ptr: i32, old_len: i32, align: i32, new_len: i32) -> i32`:
+///   The canonical ABI allocator. Guest memory allocated via `cabi_realloc` is never freed.
+/// - `malloc(len: i32) -> i32` and optionally `free(ptr: i32)`:
+///   The C allocator. Guest memory is freed via `free` if it is exported.
+///
+/// `cabi_realloc` is preferred if the guest exports both allocators.
+///
+/// # Ownership
+///
+/// - Guest memory allocated for parameters of [`GuestAbi::call`] is freed after the call.
+/// - Guest memory of `(ptr, len)` results of [`GuestAbi::call`] is owned by the host
+///   and freed after it has been copied into a [`String`] or [`Vec<u8>`].
+/// - Guest memory allocated via [`Caller::scratch`] is freed once the [`GuestScratch`] is dropped.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, GuestAbi, Linker, Module, Store};
+/// # let wasm = wat::parse_str(r#"
+/// #     (module
+/// #         (memory (export "memory") 1)
+/// #         (global $next (mut i32) (i32.const 1024))
+/// #         (func (export "malloc") (param $len i32) (result i32)
+/// #             (global.get $next)
+/// #             (global.set $next (i32.add (global.get $next) (local.get $len)))
+/// #         )
+/// #         (func (export "len") (param i32 i32) (result i32) (local.get 1))
+/// #     )
+/// # "#).unwrap();
+/// let engine = Engine::default();
+/// let module = Module::new(&engine, &wasm[..]).unwrap();
+/// let mut store = Store::new(&engine, ());
+/// let instance = Linker::new(&engine)
+///     .instantiate(&mut store, &module)
+///     .unwrap()
+///     .start(&mut store)
+///     .unwrap();
+/// let abi = GuestAbi::new(&store, &instance).unwrap();
+/// let len = instance.get_func(&store, "len").unwrap();
+/// let result: i32 = abi.call(&mut store, &len, "hello").unwrap();
+/// assert_eq!(result, 5);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct GuestAbi {
+    /// The exported linear memory of the guest.
+    memory: Memory,
+    /// The exported allocator of the guest.
+    allocator: Allocator,
+}
+
+/// The allocator exported by a Wasm guest.
+#[derive(Debug, Copy, Clone)]
+enum Allocator {
+    /// The canonical ABI `cabi_realloc` allocator.
+    CabiRealloc(TypedFunc<(i32, i32, i32, i32), i32>),
+    /// The C `malloc` allocator with its optional `free` counterpart.
+    Malloc {
+        malloc: TypedFunc<i32, i32>,
+        free: Option<TypedFunc<i32, ()>>,
+    },
+}
+
+/// A `(ptr, len)` pair referring to bytes in the linear memory of a Wasm guest.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct GuestSlice {
+    /// The offset of the first byte within the guest memory.
+    pub ptr: u32,
+    /// The number of bytes.
+    pub len: u32,
+}
+
+impl GuestSlice {
+    /// Creates a new [`GuestSlice`] from the `i32` values `ptr` and `len` as used by Wasm.
+    pub fn new(ptr: i32, len: i32) -> Self {
+        Self {
+            ptr: ptr as u32,
+            len: len as u32,
+        }
+    }
+}
+
+impl GuestAbi {
+    /// Creates a new [`GuestAbi`] for the exports of `instance`.
+    ///
+    /// # Errors
+    ///
+    /// - If `instance` does not export its linear memory as `memory`.
+    /// - If `instance` does not export a supported allocator.
+    /// - If the exported allocator functions have unexpected types.
+    pub fn new(ctx: impl AsContext, instance: &Instance) -> Result<Self, Error> {
+        let ctx = ctx.as_context();
+        Self::from_exports(&ctx, |name| instance.get_export(&ctx, name))
+    }
+
+    /// Creates a new [`GuestAbi`] for the exports of the instance that called the host function.
+    ///
+    /// This allows host functions defined via [`Func::wrap`] to read `(ptr, len)` parameters
+    /// and to return strings or byte slices to the guest.
+    ///
+    /// # Errors
+    ///
+    /// - If `caller` is not associated to an instance.
+    /// - If the instance does not export its linear memory as `memory`.
+    /// - If the instance does not export a supported allocator.
+    /// - If the exported allocator functions have unexpected types.
+    pub fn from_caller<T>(caller: &Caller<'_, T>) -> Result<Self, Error> {
+        Self::from_exports(caller, |name| caller.get_export(name))
+    }
+
+    /// Creates a new [`GuestAbi`] from the exports queried via `get_export`.
+    fn from_exports(
+        ctx: impl AsContext,
+        get_export: impl Fn(&str) -> Option<Extern>,
+    ) -> Result<Self, Error> {
+        let Some(memory) = get_export("memory").and_then(Extern::into_memory) else {
+            return Err(Error::new("missing exported linear memory: memory"));
+        };
+        let get_func = |name: &str| get_export(name).and_then(Extern::into_func);
+        let allocator = if let Some(realloc) = get_func("cabi_realloc") {
+            Allocator::CabiRealloc(realloc.typed(&ctx)?)
+        } else if let Some(malloc) = get_func("malloc") {
+            let free = get_func("free").map(|free| free.typed(&ctx)).transpose()?;
+            Allocator::Malloc {
+                malloc: malloc.typed(&ctx)?,
+                free,
+            }
+        } else {
+            return Err(Error::new(
+                "missing exported allocator: cabi_realloc or malloc",
+            ));
+        };
+        Ok(Self { memory, allocator })
+    }
+
+    /// Returns the exported linear [`Memory`] of the guest.
+    pub fn memory(&self) -> Memory {
+        self.memory
+    }
+
+    /// Allocates `len` bytes in the guest memory and returns their offset.
+    ///
+    /// # Errors
+    ///
+    /// If the guest allocator traps or returns a null pointer.
+    pub fn alloc(&self, mut ctx: impl AsContextMut, len: u32) -> Result<u32, Error> {
+        let len = len as i32;
+        let ptr = match self.allocator {
+            Allocator::CabiRealloc(realloc) => realloc.call(&mut ctx, (0, 0, 1, len))?,
+            Allocator::Malloc { malloc, .. } => malloc.call(&mut ctx, len)?,
+        };
+        if ptr == 0 {
+            return Err(Error::new(format!(
+                "guest allocator failed to allocate {len} bytes"
+            )));
+        }
+        Ok(ptr as u32)
+    }
+
+    /// Frees the guest memory referred to by `slice`.
+    ///
+    /// This does nothing if `slice` is empty or if the guest allocator does not support freeing memory.
+    ///
+    /// # Errors
+    ///
+    /// If the guest allocator traps.
+    pub fn free(&self, ctx: impl AsContextMut, slice: GuestSlice) -> Result<(), Error> {
+        if slice.len == 0 {
+            return Ok(());
+        }
+        if let Allocator::Malloc {
+            free: Some(free), ..
+        } = self.allocator
+        {
+            free.call(ctx, slice.ptr as i32)?;
+        }
+        Ok(())
+    }
+
+    /// Copies `bytes` into newly allocated guest memory.
+    ///
+    /// No guest memory is allocated if `bytes` is empty.
+    ///
+    /// # Errors
+    ///
+    /// - If the guest allocator traps or fails.
+    /// - If the allocated guest memory is out of bounds.
+    pub fn write_bytes(
+        &self,
+        mut ctx: impl AsContextMut,
+        bytes: &[u8],
+    ) -> Result<GuestSlice, Error> {
+        let Ok(len) = u32::try_from(bytes.len()) else {
+            return Err(Error::new(format!(
+                "cannot pass {} bytes to a 32-bit guest",
+                bytes.len()
+            )));
+        };
+        if len == 0 {
+            return Ok(GuestSlice::default());
+        }
+        let ptr = self.alloc(&mut ctx, len)?;
+        self.memory.write(&mut ctx, ptr as usize, bytes)?;
+        Ok(GuestSlice { ptr, len })
+    }
+
+    /// Copies the string `s` into newly allocated guest memory.
+    ///
+    /// # Errors
+    ///
+    /// - If the guest allocator traps or fails.
+    /// - If the allocated guest memory is out of bounds.
+    pub fn write_str(&self, ctx: impl AsContextMut, s: &str) -> Result<GuestSlice, Error> {
+        self.write_bytes(ctx, s.as_bytes())
+    }
+
+    /// Copies the guest memory referred to by `slice` into a [`Vec<u8>`].
+    ///
+    /// # Errors
+    ///
+    /// If `slice` is out of bounds of the guest memory.
+    pub fn read_bytes(&self, ctx: impl AsContext, slice: GuestSlice) -> Result<Vec<u8>, Error> {
+        let mut bytes = vec![0x00_u8; slice.len as usize];
+        self.memory.read(ctx, slice.ptr as usize, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Copies the UTF-8 encoded guest memory referred to by `slice` into a [`String`].
+    ///
+    /// # Errors
+    ///
+    /// - If `slice` is out of bounds of the guest memory.
+    /// - If the bytes referred to by `slice` are not valid UTF-8.
+    pub fn read_string(&self, ctx: impl AsContext, slice: GuestSlice) -> Result<String, Error> {
+        let bytes = self.read_bytes(ctx, slice)?;
+        String::from_utf8(bytes)
+            .map_err(|error| Error::new(format!("guest string is not valid UTF-8: {error}")))
+    }
+
+    /// Calls `func` with `params` marshalled according to the `(ptr, len)` convention.
+    ///
+    /// - Strings and byte slices of `params` are copied into guest memory and passed as `(ptr, len)`.
+    /// - [`String`] and [`Vec<u8>`] results are read from `(ptr, len)` results of `func`.
+    /// - Numeric parameters and results are passed as they are.
+    ///
+    /// # Errors
+    ///
+    /// - If marshalling a parameter or result fails.
+    /// - If the marshalled `params` or `Results` do not match the type of `func`.
+    /// - If the execution of `func` traps.
+    pub fn call<T, Params, Results>(
+        &self,
+        mut ctx: impl AsContextMut<Data = T>,
+        func: &Func,
+        params: Params,
+    ) -> Result<Results, Error>
+    where
+        Params: GuestParams,
+        Results: GuestResults,
+    {
+        let mut ctx = ctx.as_context_mut();
+        let mut inputs = Vec::new();
+        let mut allocs = Vec::new();
+        let lowered = params.lower(self, &mut ctx, &mut inputs, &mut allocs);
+        let called = lowered.and_then(|()| {
+            let mut outputs = func
+                .ty(&ctx)
+                .results()
+                .iter()
+                .copied()
+                .map(Val::default)
+                .collect::<Vec<_>>();
+            func.call(&mut ctx, &inputs, &mut outputs)?;
+            Ok(outputs)
+        });
+        for slice in allocs {
+            self.free(&mut ctx, slice)?;
+        }
+        let outputs = called?;
+        let mut vals = outputs.iter();
+        let results = Results::lift(self, &mut ctx, &mut vals)?;
+        if vals.len() != 0 {
+            return Err(Error::new(format!(
+                "expected {} results but found {}",
+                outputs.len() - vals.len(),
+                outputs.len()
+            )));
+        }
+        Ok(results)
+    }
+}
+
+impl<'a, T> Caller<'a, T> {
+    /// Returns a [`GuestScratch`] to allocate temporary buffers in the guest memory.
+    ///
+    /// Buffers allocated via the returned [`GuestScratch`] are freed once it is dropped,
+    /// e.g. when the host function returns.
+    ///
+    /// # Errors
+    ///
+    /// If [`GuestAbi::from_caller`] fails for `self`.
+    pub fn scratch(&mut self) -> Result<GuestScratch<'_, 'a, T>, Error> {
+        let abi = GuestAbi::from_caller(self)?;
+        Ok(GuestScratch {
+            caller: self,
+            abi,
+            allocs: Vec::new(),
+        })
+    }
+}
+
+/// Temporary buffers allocated in the guest memory on behalf of a host function.
+///
+/// Created via [`Caller::scratch`]. Host functions use scratch buffers to pass data
+/// to guest callbacks. Scratch buffers are allocated via the guest allocator and freed
+/// in reverse allocation order once the [`GuestScratch`] is dropped.
+/// Use [`GuestScratch::free`] in order to handle errors of the guest allocator.
+///
+/// # Note
+///
+/// - The [`GuestScratch`] dereferences to its [`Caller`] in order to call guest callbacks.
+/// - Scratch buffers allocated via `cabi_realloc` are never freed. Read more in [`GuestAbi`].
+pub struct GuestScratch<'a, 'b, T> {
+    /// The caller of the host function that owns the scratch buffers.
+    caller: &'a mut Caller<'b, T>,
+    /// The [`GuestAbi`] of the caller.
+    abi: GuestAbi,
+    /// The scratch buffers that have not yet been freed.
+    allocs: Vec<GuestSlice>,
+}
+
+impl<T> GuestScratch<'_, '_, T> {
+    /// Returns the [`GuestAbi`] used to allocate the scratch buffers.
+    pub fn abi(&self) -> GuestAbi {
+        self.abi
+    }
+
+    /// Allocates a scratch buffer of `len` bytes in the guest memory.
+    ///
+    /// No guest memory is allocated if `len` is zero.
+    ///
+    /// # Errors
+    ///
+    /// If the guest allocator traps or fails.
+    pub fn alloc(&mut self, len: u32) -> Result<GuestSlice, Error> {
+        if len == 0 {
+            return Ok(GuestSlice::default());
+        }
+        let ptr = self.abi.alloc(&mut *self.caller, len)?;
+        let slice = GuestSlice { ptr, len };
+        self.allocs.push(slice);
+        Ok(slice)
+    }
+
+    /// Copies `bytes` into a newly allocated scratch buffer.
+    ///
+    /// # Errors
+    ///
+    /// - If the guest allocator traps or fails.
+    /// - If the allocated guest memory is out of bounds.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<GuestSlice, Error> {
+        let slice = self.abi.write_bytes(&mut *self.caller, bytes)?;
+        if slice.len != 0 {
+            self.allocs.push(slice);
+        }
+        Ok(slice)
+    }
+
+    /// Copies the string `s` into a newly allocated scratch buffer.
+    ///
+    /// # Errors
+    ///
+    /// - If the guest allocator traps or fails.
+    /// - If the allocated guest memory is out of bounds.
+    pub fn write_str(&mut self, s: &str) -> Result<GuestSlice, Error> {
+        self.write_bytes(s.as_bytes())
+    }
+
+    /// Frees all scratch buffers.
+    ///
+    /// # Errors
+    ///
+    /// If the guest allocator traps. Remaining scratch buffers are freed upon drop.
+    pub fn free(mut self) -> Result<(), Error> {
+        self.free_allocs()
+    }
+
+    /// Frees all scratch buffers in reverse allocation order.
+    fn free_allocs(&mut self) -> Result<(), Error> {
+        while let Some(slice) = self.allocs.pop() {
+            self.abi.free(&mut *self.caller, slice)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> fmt::Debug for GuestScratch<'_, '_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GuestScratch")
+            .field("abi", &self.abi)
+            .field("allocs", &self.allocs)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'b, T> Deref for GuestScratch<'_, 'b, T> {
+    type Target = Caller<'b, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.caller
+    }
+}
+
+impl<T> DerefMut for GuestScratch<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.caller
+    }
+}
+
+impl<T> AsContext for GuestScratch<'_, '_, T> {
+    type Data = T;
+
+    fn as_context(&self) -> StoreContext<'_, T> {
+        self.caller.as_context()
+    }
+}
+
+impl<T> AsContextMut for GuestScratch<'_, '_, T> {
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, T> {
+        self.caller.as_context_mut()
+    }
+}
+
+impl<T> Drop for GuestScratch<'_, '_, T> {
+    fn drop(&mut self) {
+        // Note: errors cannot be reported upon drop. Use `GuestScratch::free` instead.
+        _ = self.free_allocs();
+    }
+}
+
+/// Host values that can be passed to a Wasm guest via [`GuestAbi::call`].
+pub trait LowerGuest {
+    /// Lowers `self` into Wasm values pushed to `out`.
+    ///
+    /// Guest memory allocated for `self` is pushed to `allocs` in order to be freed after the call.
+    ///
+    /// # Errors
+    ///
+    /// If `self` cannot be copied into guest memory.
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error>;
+}
+
+/// Host values that can be returned from a Wasm guest via [`GuestAbi::call`].
+pub trait LiftGuest: Sized {
+    /// Lifts `Self` from the Wasm values yielded by `vals`.
+    ///
+    /// # Errors
+    ///
+    /// If `vals` do not match `Self` or the referenced guest memory cannot be read.
+    fn lift<T>(
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        vals: &mut core::slice::Iter<Val>,
+    ) -> Result<Self, Error>;
+}
+
+/// The parameters of a [`GuestAbi::call`], i.e. a [`LowerGuest`] value or a tuple of them.
+pub trait GuestParams {
+    /// Lowers all parameters into Wasm values pushed to `out`.
+    ///
+    /// Guest memory allocated for the parameters is pushed to `allocs` in order to be freed after the call.
+    ///
+    /// # Errors
+    ///
+    /// If a parameter cannot be copied into guest memory.
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error>;
+}
+
+/// The results of a [`GuestAbi::call`], i.e. a [`LiftGuest`] value or a tuple of them.
+pub trait GuestResults: Sized {
+    /// Lifts all results from the Wasm values yielded by `vals`.
+    ///
+    /// # Errors
+    ///
+    /// If `vals` do not match the results or the referenced guest memory cannot be read.
+    fn lift<T>(
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        vals: &mut core::slice::Iter<Val>,
+    ) -> Result<Self, Error>;
+}
+
+/// Returns the next value of `vals` or an error if there are no more values.
+fn next_val<'a>(vals: &mut core::slice::Iter<'a, Val>) -> Result<&'a Val, Error> {
+    vals.next()
+        .ok_or_else(|| Error::new("too few results returned by the guest"))
+}
+
+/// Returns the next [`GuestSlice`] of `vals` made up of two `i32` values.
+fn next_slice(vals: &mut core::slice::Iter<Val>) -> Result<GuestSlice, Error> {
+    let ptr = i32::lift_val(next_val(vals)?)?;
+    let len = i32::lift_val(next_val(vals)?)?;
+    Ok(GuestSlice::new(ptr, len))
+}
+
+/// Numeric values that are passed as single Wasm values.
+trait NumericGuest: Sized {
+    /// Converts the Wasm `val` into `Self`.
+    fn lift_val(val: &Val) -> Result<Self, Error>;
+}
+
+macro_rules! impl_numeric_guest {
+    ( $( $ty:ty => $getter:ident as $val:ty ),* $(,)? ) => {
+        $(
+            impl NumericGuest for $ty {
+                fn lift_val(val: &Val) -> Result<Self, Error> {
+                    val.$getter().map(<$ty>::from).ok_or_else(|| {
+                        Error::new(format!(
+                            "expected a guest value of type {} but found {:?}",
+                            stringify!($ty),
+                            val.ty(),
+                        ))
+                    })
+                }
+            }
+
+            impl LowerGuest for $ty {
+                fn lower<T>(
+                    &self,
+                    _abi: &GuestAbi,
+                    _ctx: &mut StoreContextMut<T>,
+                    out: &mut Vec<Val>,
+                    _allocs: &mut Vec<GuestSlice>,
+                ) -> Result<(), Error> {
+                    out.push(Val::from(<$val>::from(*self)));
+                    Ok(())
+                }
+            }
+
+            impl LiftGuest for $ty {
+                fn lift<T>(
+                    _abi: &GuestAbi,
+                    _ctx: &mut StoreContextMut<T>,
+                    vals: &mut core::slice::Iter<Val>,
+                ) -> Result<Self, Error> {
+                    Self::lift_val(next_val(vals)?)
+                }
+            }
+        )*
+    };
+}
+impl_numeric_guest! {
+    i32 => i32 as i32,
+    i64 => i64 as i64,
+    f32 => f32 as F32,
+    f64 => f64 as F64,
+}
+
+impl LowerGuest for [u8] {
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error> {
+        let slice = abi.write_bytes(&mut *ctx, self)?;
+        allocs.push(slice);
+        out.push(Val::I32(slice.ptr as i32));
+        out.push(Val::I32(slice.len as i32));
+        Ok(())
+    }
+}
+
+impl LowerGuest for str {
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error> {
+        LowerGuest::lower(self.as_bytes(), abi, ctx, out, allocs)
+    }
+}
+
+impl LowerGuest for Vec<u8> {
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error> {
+        LowerGuest::lower(&self[..], abi, ctx, out, allocs)
+    }
+}
+
+impl LowerGuest for String {
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error> {
+        LowerGuest::lower(self.as_str(), abi, ctx, out, allocs)
+    }
+}
+
+impl<L> LowerGuest for &L
+where
+    L: LowerGuest + ?Sized,
+{
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error> {
+        LowerGuest::lower(&**self, abi, ctx, out, allocs)
+    }
+}
+
+impl LiftGuest for Vec<u8> {
+    fn lift<T>(
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        vals: &mut core::slice::Iter<Val>,
+    ) -> Result<Self, Error> {
+        let slice = next_slice(vals)?;
+        let bytes = abi.read_bytes(&*ctx, slice)?;
+        abi.free(&mut *ctx, slice)?;
+        Ok(bytes)
+    }
+}
+
+impl LiftGuest for String {
+    fn lift<T>(
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        vals: &mut core::slice::Iter<Val>,
+    ) -> Result<Self, Error> {
+        let slice = next_slice(vals)?;
+        let string = abi.read_string(&*ctx, slice);
+        abi.free(&mut *ctx, slice)?;
+        string
+    }
+}
+
+impl<L> GuestParams for L
+where
+    L: LowerGuest + ?Sized,
+{
+    fn lower<T>(
+        &self,
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        out: &mut Vec<Val>,
+        allocs: &mut Vec<GuestSlice>,
+    ) -> Result<(), Error> {
+        LowerGuest::lower(self, abi, ctx, out, allocs)
+    }
+}
+
+impl<L> GuestResults for L
+where
+    L: LiftGuest,
+{
+    fn lift<T>(
+        abi: &GuestAbi,
+        ctx: &mut StoreContextMut<T>,
+        vals: &mut core::slice::Iter<Val>,
+    ) -> Result<Self, Error> {
+        LiftGuest::lift(abi, ctx, vals)
+    }
+}
+
+macro_rules! impl_guest_tuples {
+    ( $( ( $( $name:ident ),* ) ),* $(,)? ) => {
+        $(
+            impl<$($name),*> GuestParams for ($($name,)*)
+            where
+                $( $name: LowerGuest, )*
+            {
+                #[allow(non_snake_case, unused_variables)]
+                fn lower<T>(
+                    &self,
+                    abi: &GuestAbi,
+                    ctx: &mut StoreContextMut<T>,
+                    out: &mut Vec<Val>,
+                    allocs: &mut Vec<GuestSlice>,
+                ) -> Result<(), Error> {
+                    let ($($name,)*) = self;
+                    $( LowerGuest::lower($name, abi, ctx, out, allocs)?; )*
+                    Ok(())
+                }
+            }
+
+            impl<$($name),*> GuestResults for ($($name,)*)
+            where
+                $( $name: LiftGuest, )*
+            {
+                #[allow(unused_variables)]
+                fn lift<T>(
+                    abi: &GuestAbi,
+                    ctx: &mut StoreContextMut<T>,
+                    vals: &mut core::slice::Iter<Val>,
+                ) -> Result<Self, Error> {
+                    Ok(($(<$name as LiftGuest>::lift(abi, ctx, vals)?,)*))
+                }
+            }
+        )*
+    };
+}
+impl_guest_tuples! {
+    (),
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+}
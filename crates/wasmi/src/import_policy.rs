@@ -0,0 +1,226 @@
+use crate::{ExternType, ImportType};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::fmt::{self, Debug, Display};
+
+/// A predicate used by [`ImportPolicy::deny_if`].
+type ImportPredicate = Arc<dyn Fn(&ImportType<'_>) -> bool + Send + Sync>;
+
+/// A policy that a [`Linker`] enforces on all imports of instantiated [`Module`]s.
+///
+/// An [`ImportPolicy`] is a list of deny rules. An import is rejected if at least
+/// one of the rules matches it, otherwise the import is resolved as usual.
+///
+/// This is useful to enforce determinism or capability policies centrally,
+/// for example by rejecting clock or entropy imports regardless of whether
+/// the [`Linker`] happens to define them.
+///
+/// # Patterns
+///
+/// Module and item names are matched against patterns in which `*` matches
+/// any sequence of characters, including the empty sequence. All other
+/// characters match themselves.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::ImportPolicy;
+/// let policy = ImportPolicy::new()
+///     .deny_name("*random*")
+///     .deny_name("clock_*")
+///     .deny_mutable_globals();
+/// ```
+///
+/// [`Linker`]: crate::Linker
+/// [`Module`]: crate::Module
+#[derive(Debug, Default, Clone)]
+pub struct ImportPolicy {
+    /// The deny rules of the [`ImportPolicy`] in the order of their definition.
+    rules: Vec<ImportRule>,
+}
+
+/// A single deny rule of an [`ImportPolicy`].
+#[derive(Clone)]
+enum ImportRule {
+    /// Denies all imports with a module name matching the pattern.
+    Module(Box<str>),
+    /// Denies all imports with an item name matching the pattern.
+    Name(Box<str>),
+    /// Denies all imports of mutable global variables.
+    MutableGlobals,
+    /// Denies all imports for which the predicate returns `true`.
+    Custom {
+        /// The reason reported when the predicate denies an import.
+        reason: Box<str>,
+        /// The predicate deciding whether an import is denied.
+        predicate: ImportPredicate,
+    },
+}
+
+impl Debug for ImportRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Module(pattern) => f.debug_tuple("Module").field(pattern).finish(),
+            Self::Name(pattern) => f.debug_tuple("Name").field(pattern).finish(),
+            Self::MutableGlobals => f.debug_tuple("MutableGlobals").finish(),
+            Self::Custom { reason, .. } => f
+                .debug_struct("Custom")
+                .field("reason", reason)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl ImportRule {
+    /// Returns the [`ImportDenial`] if the [`ImportRule`] denies `import`.
+    fn check(&self, import: &ImportType) -> Option<ImportDenial> {
+        match self {
+            Self::Module(pattern) if matches_pattern(pattern, import.module()) => {
+                Some(ImportDenial::Module(pattern.clone()))
+            }
+            Self::Name(pattern) if matches_pattern(pattern, import.name()) => {
+                Some(ImportDenial::Name(pattern.clone()))
+            }
+            Self::MutableGlobals => match import.ty() {
+                ExternType::Global(ty) if ty.mutability().is_mut() => {
+                    Some(ImportDenial::MutableGlobal)
+                }
+                _ => None,
+            },
+            Self::Custom { reason, predicate } if predicate(import) => {
+                Some(ImportDenial::Custom(reason.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ImportPolicy {
+    /// Creates a new [`ImportPolicy`] that allows all imports.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Denies all imports with a module name matching `pattern`.
+    ///
+    /// Read more about patterns in the [`ImportPolicy`] docs.
+    pub fn deny_module(mut self, pattern: &str) -> Self {
+        self.rules.push(ImportRule::Module(pattern.into()));
+        self
+    }
+
+    /// Denies all imports with an item name matching `pattern`.
+    ///
+    /// Read more about patterns in the [`ImportPolicy`] docs.
+    pub fn deny_name(mut self, pattern: &str) -> Self {
+        self.rules.push(ImportRule::Name(pattern.into()));
+        self
+    }
+
+    /// Denies all imports of mutable global variables.
+    pub fn deny_mutable_globals(mut self) -> Self {
+        self.rules.push(ImportRule::MutableGlobals);
+        self
+    }
+
+    /// Denies all imports for which `predicate` returns `true`.
+    ///
+    /// The `reason` is reported in the [`ImportDenial`] of rejected imports.
+    pub fn deny_if(
+        mut self,
+        reason: &str,
+        predicate: impl Fn(&ImportType<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.rules.push(ImportRule::Custom {
+            reason: reason.into(),
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Returns `true` if the [`ImportPolicy`] has no deny rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Checks `import` against the [`ImportPolicy`].
+    ///
+    /// Returns the [`ImportDenial`] of the first rule that denies `import`, if any.
+    pub fn check(&self, import: &ImportType) -> Option<ImportDenial> {
+        self.rules.iter().find_map(|rule| rule.check(import))
+    }
+}
+
+/// The reason why an [`ImportPolicy`] denied an import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportDenial {
+    /// The module name of the import matched the denied pattern.
+    Module(Box<str>),
+    /// The item name of the import matched the denied pattern.
+    Name(Box<str>),
+    /// The import is a mutable global variable.
+    MutableGlobal,
+    /// A custom predicate denied the import for the given reason.
+    Custom(Box<str>),
+}
+
+impl Display for ImportDenial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Module(pattern) => write!(f, "module name matches denied pattern `{pattern}`"),
+            Self::Name(pattern) => write!(f, "item name matches denied pattern `{pattern}`"),
+            Self::MutableGlobal => write!(f, "mutable global imports are denied"),
+            Self::Custom(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// Returns `true` if `name` matches `pattern`.
+///
+/// A `*` in `pattern` matches any sequence of characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(prefix) = parts.next() else {
+        return name.is_empty();
+    };
+    let Some(mut rest) = name.strip_prefix(prefix) else {
+        return false;
+    };
+    let mut parts = parts.peekable();
+    if parts.peek().is_none() {
+        // There was no `*` in the pattern so we require an exact match.
+        return rest.is_empty();
+    }
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            // The last part must match the end of `name`.
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_work() {
+        assert!(matches_pattern("random", "random"));
+        assert!(!matches_pattern("random", "random_get"));
+        assert!(matches_pattern("random*", "random_get"));
+        assert!(matches_pattern("*random*", "get_random_bytes"));
+        assert!(matches_pattern("*_get", "random_get"));
+        assert!(!matches_pattern("*_get", "random_set"));
+        assert!(matches_pattern("clock_*_get", "clock_time_get"));
+        assert!(!matches_pattern("clock_*_get", "clock_get"));
+        assert!(matches_pattern("*", ""));
+        assert!(matches_pattern("", ""));
+        assert!(!matches_pattern("", "a"));
+        assert!(matches_pattern("a*a", "aa"));
+        assert!(!matches_pattern("a*a", "a"));
+    }
+}
@@ -21,6 +21,8 @@ pub struct InstanceEntityBuilder {
     func_types: Arc<[DedupFuncType]>,
     tables: Vec<Table>,
     funcs: Vec<Func>,
+    /// The number of imported functions of the [`Module`].
+    len_imported_funcs: u32,
     memories: Vec<Memory>,
     globals: Vec<Global>,
     start_fn: Option<FuncIdx>,
@@ -38,6 +40,7 @@ impl InstanceEntityBuilder {
             v
         }
         let mut len_funcs = module.len_funcs();
+        let mut len_imported_funcs = 0;
         let mut len_globals = module.len_globals();
         let mut len_tables = module.len_tables();
         let mut len_memories = module.len_memories();
@@ -45,6 +48,7 @@ impl InstanceEntityBuilder {
             match import.ty() {
                 ExternType::Func(_) => {
                     len_funcs += 1;
+                    len_imported_funcs += 1;
                 }
                 ExternType::Table(_) => {
                     len_tables += 1;
@@ -61,6 +65,7 @@ impl InstanceEntityBuilder {
             func_types: module.func_types_cloned(),
             tables: vec_with_capacity_exact(len_tables),
             funcs: vec_with_capacity_exact(len_funcs),
+            len_imported_funcs,
             memories: vec_with_capacity_exact(len_memories),
             globals: vec_with_capacity_exact(len_globals),
             start_fn: None,
@@ -137,6 +142,11 @@ impl InstanceEntityBuilder {
             .unwrap_or_else(|| panic!("missing `Func` at index: {index}"))
     }
 
+    /// Returns the number of functions pushed so far.
+    pub fn len_funcs(&self) -> usize {
+        self.funcs.len()
+    }
+
     /// Pushes a new [`Memory`] to the [`InstanceEntity`] under construction.
     pub fn push_memory(&mut self, memory: Memory) {
         self.memories.push(memory);
@@ -189,11 +199,14 @@ impl InstanceEntityBuilder {
             func_types: self.func_types,
             tables: self.tables.into(),
             funcs: self.funcs.into(),
+            len_imported_funcs: self.len_imported_funcs,
             memories: self.memories.into(),
             globals: self.globals.into(),
+            flat_globals: 0,
             exports: self.exports,
             data_segments: self.data_segments.into(),
             elem_segments: self.elem_segments.into(),
+            pending_start: None,
         }
     }
 }
@@ -54,11 +54,25 @@ pub struct InstanceEntity {
     func_types: Arc<[DedupFuncType]>,
     tables: Box<[Table]>,
     funcs: Box<[Func]>,
+    /// The number of imported functions in `funcs`.
+    ///
+    /// Imported functions precede all internal functions in `funcs`.
+    len_imported_funcs: u32,
     memories: Box<[Memory]>,
     globals: Box<[Global]>,
+    /// The index of the first global variable of the flat globals of the instance.
+    ///
+    /// All global variables starting at this index are stored in order and without
+    /// gaps in the [`Store`] and none of them is computed. This allows the executor to
+    /// access them via a base pointer and offset instead of resolving each [`Global`].
+    ///
+    /// [`Store`]: crate::Store
+    flat_globals: u32,
     exports: Map<Box<str>, Extern>,
     data_segments: Box<[DataSegment]>,
     elem_segments: Box<[ElementSegment]>,
+    /// The `start` function that has been deferred and not yet executed if any.
+    pending_start: Option<Func>,
 }
 
 impl InstanceEntity {
@@ -69,11 +83,14 @@ impl InstanceEntity {
             func_types: Arc::new([]),
             tables: [].into(),
             funcs: [].into(),
+            len_imported_funcs: 0,
             memories: [].into(),
             globals: [].into(),
+            flat_globals: 0,
             exports: Map::new(),
             data_segments: [].into(),
             elem_segments: [].into(),
+            pending_start: None,
         }
     }
 
@@ -107,6 +124,43 @@ impl InstanceEntity {
         self.funcs.get(index as usize).copied()
     }
 
+    /// Returns all functions of the [`InstanceEntity`] in index order.
+    pub fn funcs(&self) -> &[Func] {
+        &self.funcs
+    }
+
+    /// Returns the number of imported functions of the [`InstanceEntity`].
+    pub fn len_imported_funcs(&self) -> usize {
+        self.len_imported_funcs as usize
+    }
+
+    /// Returns all tables of the [`InstanceEntity`] in index order.
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    /// Returns all linear memories of the [`InstanceEntity`] in index order.
+    pub fn memories(&self) -> &[Memory] {
+        &self.memories
+    }
+
+    /// Returns all global variables of the [`InstanceEntity`] in index order.
+    pub fn globals(&self) -> &[Global] {
+        &self.globals
+    }
+
+    /// Returns the index of the first flat global variable of the [`InstanceEntity`].
+    ///
+    /// Equals the number of global variables if there are no flat global variables.
+    pub fn flat_globals(&self) -> u32 {
+        self.flat_globals
+    }
+
+    /// Sets the index of the first flat global variable of the [`InstanceEntity`] to `start`.
+    pub fn set_flat_globals(&mut self, start: u32) {
+        self.flat_globals = start;
+    }
+
     /// Returns the signature at the `index` if any.
     pub fn get_signature(&self, index: u32) -> Option<&DedupFuncType> {
         self.func_types.get(index as usize)
@@ -133,6 +187,21 @@ impl InstanceEntity {
     pub fn exports(&self) -> ExportsIter {
         ExportsIter::new(self.exports.iter())
     }
+
+    /// Returns the deferred `start` function that has not yet been executed if any.
+    pub fn pending_start(&self) -> Option<Func> {
+        self.pending_start
+    }
+
+    /// Sets the deferred `start` function that is yet to be executed.
+    pub fn set_pending_start(&mut self, start: Func) {
+        self.pending_start = Some(start);
+    }
+
+    /// Takes the deferred `start` function that has not yet been executed if any.
+    pub fn take_pending_start(&mut self) -> Option<Func> {
+        self.pending_start.take()
+    }
 }
 
 /// An instantiated WebAssembly [`Module`].
@@ -208,6 +277,20 @@ impl Instance {
         &self.0
     }
 
+    /// Returns the stable ID of the [`Instance`] within its [`Store`](crate::Store).
+    ///
+    /// # Note
+    ///
+    /// IDs are assigned in the order of allocation, starting at 0, and are never reused.
+    /// They are deterministic for the same sequence of operations on a [`Store`](crate::Store).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub fn id(&self, store: impl AsContext) -> u32 {
+        store.as_context().store.inner.entity_id(self.as_inner())
+    }
+
     /// Returns the function at the `index` if any.
     ///
     /// # Panics
@@ -326,4 +409,130 @@ impl Instance {
     ) -> ExportsIter<'ctx> {
         store.into().store.inner.resolve_instance(self).exports()
     }
+
+    /// Returns `true` if the [`Instance`] has a deferred `start` function that has not yet run.
+    ///
+    /// Returns `false` if the `start` function already ran or if there is no `start` function.
+    /// Read more about deferred `start` functions in [`InstancePre::defer_start`].
+    ///
+    /// # Panics
+    ///
+    /// If `store` does not own this [`Instance`].
+    ///
+    /// [`InstancePre::defer_start`]: crate::InstancePre::defer_start
+    pub fn has_pending_start(&self, store: impl AsContext) -> bool {
+        store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .pending_start()
+            .is_some()
+    }
+
+    /// Runs the deferred `start` function of the [`Instance`] if it has not yet run.
+    ///
+    /// Does nothing if the `start` function already ran or if there is no `start` function.
+    ///
+    /// # Note
+    ///
+    /// The `start` function is considered to have run even if its execution traps.
+    ///
+    /// # Errors
+    ///
+    /// If executing the `start` function traps.
+    ///
+    /// # Panics
+    ///
+    /// If `store` does not own this [`Instance`].
+    pub fn run_start(&self, mut store: impl AsContextMut) -> Result<(), Error> {
+        let Some(start) = self.take_pending_start(&mut store) else {
+            return Ok(());
+        };
+        start.call(store, &[], &mut [])
+    }
+
+    /// Runs the deferred `start` function of the [`Instance`] with at most `fuel` units of fuel.
+    ///
+    /// The `start` function never gets more fuel than the `store` has left.
+    /// Fuel consumed by the `start` function is deducted from the fuel of the `store`.
+    /// Does nothing if the `start` function already ran or if there is no `start` function.
+    ///
+    /// # Note
+    ///
+    /// The `start` function is considered to have run even if its execution traps.
+    ///
+    /// # Errors
+    ///
+    /// - If fuel metering is disabled.
+    /// - If executing the `start` function traps or runs out of `fuel`.
+    ///
+    /// # Panics
+    ///
+    /// If `store` does not own this [`Instance`].
+    pub fn run_start_with_fuel(
+        &self,
+        mut store: impl AsContextMut,
+        fuel: u64,
+    ) -> Result<(), Error> {
+        let mut ctx = store.as_context_mut();
+        let before = ctx.get_fuel()?;
+        let Some(start) = self.take_pending_start(&mut ctx) else {
+            return Ok(());
+        };
+        let budget = fuel.min(before);
+        ctx.set_fuel(budget)?;
+        let result = start.call(&mut ctx, &[], &mut []);
+        let consumed = budget.saturating_sub(ctx.get_fuel()?);
+        ctx.set_fuel(before.saturating_sub(consumed))?;
+        result
+    }
+
+    /// Hot-swaps the code of this [`Instance`] with the code of `module`.
+    ///
+    /// Returns a new [`Instance`] of `module` that shares the imports as well as the
+    /// tables, linear memories and global variables of this [`Instance`]. This allows
+    /// to live-update long-running guests without losing their state.
+    ///
+    /// # Note
+    ///
+    /// - The entities of `module` are matched with the entities of this [`Instance`]
+    ///   by their indices. Therefore `module` must import the same functions and must
+    ///   have the same number of tables, linear memories and global variables.
+    /// - The active data and element segments and the `start` function of `module`
+    ///   are not applied since they would overwrite the preserved state.
+    /// - Table entries referring to functions of this [`Instance`] are redirected to
+    ///   the functions of the new [`Instance`] with the same index.
+    /// - This [`Instance`] stays valid so that ongoing or suspended executions
+    ///   can finish with the old code.
+    ///
+    /// # Errors
+    ///
+    /// - If `module` does not import the same number of functions with the same
+    ///   signatures as this [`Instance`].
+    /// - If the tables, linear memories or global variables of this [`Instance`]
+    ///   do not satisfy the types required by `module`.
+    /// - If an exported function of `module` has another signature than the
+    ///   exported function of this [`Instance`] with the same name.
+    /// - If a function of this [`Instance`] referred to by a table entry has no
+    ///   counterpart with the same signature in `module`.
+    ///
+    /// The `store` is left unchanged if the hot-swap is rejected.
+    ///
+    /// # Panics
+    ///
+    /// If `store` does not own this [`Instance`].
+    pub fn hot_swap(&self, store: impl AsContextMut, module: &Module) -> Result<Instance, Error> {
+        module.hot_swap(store, self)
+    }
+
+    /// Takes the deferred `start` function of the [`Instance`] if any.
+    fn take_pending_start(&self, mut store: impl AsContextMut) -> Option<Func> {
+        store
+            .as_context_mut()
+            .store
+            .inner
+            .resolve_instance_mut(self)
+            .take_pending_start()
+    }
 }
@@ -7,9 +7,12 @@ use crate::{
     module::InstantiationError,
     table::TableError,
     Caller,
+    Config,
     Engine,
     ExternRef,
     FuncRef,
+    InstantiateOptions,
+    Linker,
     MemoryType,
     Mutability,
     Store,
@@ -216,3 +219,184 @@ fn instantiate_with_invalid_func_import() {
         ErrorKind::Instantiation(InstantiationError::SignatureMismatch { .. })
     ));
 }
+
+#[test]
+fn instantiate_with_deferred_start() {
+    let wasm = r#"
+        (module
+            (memory (export "memory") 1)
+            (global $g (export "g") (mut i32) (i32.const 0))
+            (func $start
+                (global.set $g (i32.load (i32.const 0)))
+            )
+            (start $start)
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let instance = linker
+        .instantiate_without_start(&mut store, &module)
+        .unwrap();
+    assert!(instance.has_pending_start(&store));
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    memory.write(&mut store, 0, &42_i32.to_le_bytes()).unwrap();
+    instance.run_start(&mut store).unwrap();
+    assert!(!instance.has_pending_start(&store));
+    let g = instance.get_global(&store, "g").unwrap();
+    assert_eq!(g.get(&store).i32(), Some(42));
+    // Running `start` again does nothing.
+    g.set(&mut store, Val::I32(0)).unwrap();
+    instance.run_start(&mut store).unwrap();
+    assert_eq!(g.get(&store).i32(), Some(0));
+}
+
+#[test]
+fn instantiate_without_start_fn() {
+    let wasm = r#"(module)"#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate_without_start(&mut store, &module)
+        .unwrap();
+    assert!(!instance.has_pending_start(&store));
+    instance.run_start(&mut store).unwrap();
+}
+
+#[test]
+fn deferred_start_with_fuel() {
+    let wasm = r#"
+        (module
+            (func $start
+                (loop $continue
+                    (br $continue)
+                )
+            )
+            (start $start)
+        )
+    "#;
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(1_000).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate_without_start(&mut store, &module)
+        .unwrap();
+    let error = instance.run_start_with_fuel(&mut store, 100).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert!(!instance.has_pending_start(&store));
+    // Only the fuel consumed by `start` is deducted from the store's fuel.
+    let remaining = store.get_fuel().unwrap();
+    assert!((900..1_000).contains(&remaining));
+}
+
+#[test]
+fn deferred_start_with_fuel_is_capped_by_store_fuel() {
+    let wasm = r#"
+        (module
+            (global $g (export "g") (mut i32) (i32.const 0))
+            (func $start
+                (loop $continue
+                    (global.set $g (i32.add (global.get $g) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (start $start)
+        )
+    "#;
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(100).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate_without_start(&mut store, &module)
+        .unwrap();
+    let error = instance
+        .run_start_with_fuel(&mut store, u64::MAX)
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    // The `start` function cannot consume more fuel than the store had left.
+    let remaining = store.get_fuel().unwrap();
+    assert!(remaining < 100);
+    let g = instance.get_global(&store, "g").unwrap();
+    assert!(matches!(g.get(&store).i32(), Some(1..100)));
+}
+
+#[test]
+fn instantiate_with_images() {
+    let wasm = r#"
+        (module
+            (memory (export "memory") 1)
+            (table (export "table") 4 funcref)
+            (data (i32.const 0) "\01\02\03\04")
+            (elem (i32.const 0) $f $f)
+            (func $f (result i32) (i32.const 1))
+            (func $g (result i32) (i32.const 2))
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let options = InstantiateOptions::new()
+        .memory_image(0, 2, [0xAA, 0xBB, 0xCC])
+        .table_image(0, 1, [Some(1), None, Some(0)]);
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate_with_options(&mut store, &module, &options)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(
+        &memory.data(&store)[..6],
+        &[0x01, 0x02, 0xAA, 0xBB, 0xCC, 0x00]
+    );
+    let table = instance.get_table(&store, "table").unwrap();
+    let call_at = |store: &mut Store<()>, index: u32| -> Option<i32> {
+        let func = *table.get(&*store, index)?.funcref()?.func()?;
+        Some(
+            func.typed::<(), i32>(&*store)
+                .unwrap()
+                .call(store, ())
+                .unwrap(),
+        )
+    };
+    assert_eq!(call_at(&mut store, 0), Some(1));
+    assert_eq!(call_at(&mut store, 1), Some(2));
+    assert_eq!(call_at(&mut store, 2), None);
+    assert_eq!(call_at(&mut store, 3), Some(1));
+}
+
+#[test]
+fn instantiate_with_invalid_images() {
+    let wasm = r#"
+        (module
+            (memory 1)
+            (table 1 funcref)
+            (func $f)
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let mut assert_error = |options: InstantiateOptions| {
+        let error = linker
+            .instantiate_with_options(&mut store, &module, &options)
+            .unwrap_err();
+        assert!(
+            matches!(error.kind(), ErrorKind::Instantiation(_)),
+            "{error}"
+        );
+    };
+    assert_error(InstantiateOptions::new().memory_image(1, 0, [0x00]));
+    assert_error(InstantiateOptions::new().memory_image(0, 65_535, [0x00, 0x00]));
+    assert_error(InstantiateOptions::new().table_image(1, 0, [Some(0)]));
+    assert_error(InstantiateOptions::new().table_image(0, 0, [Some(1)]));
+    assert_error(InstantiateOptions::new().table_image(0, 1, [Some(0)]));
+}
@@ -0,0 +1,177 @@
+use crate::{Engine, Error, Instance, Linker, Module, Store};
+use alloc::{boxed::Box, vec::Vec};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The factory creating the [`Linker`] and the store data of new pooled instances.
+type InstanceFactory<T> = Box<dyn Fn(&Engine) -> Result<(Linker<T>, T), Error> + Send + Sync>;
+
+/// A pool of instances of the same [`Module`] that are shared across threads.
+///
+/// Every pooled instance lives in its own [`Store`] which is handed out exclusively
+/// to a single thread via [`InstancePool::with_instance`]. New instances are created
+/// on demand whenever all pooled instances are in use. The compiled [`Module`] is
+/// shared between all instances.
+///
+/// This is the common pattern for multi-threaded servers that embed Wasmi and
+/// handle requests concurrently.
+///
+/// # Note
+///
+/// Pooled instances keep their state, e.g. their linear memories and globals, across
+/// calls to [`InstancePool::with_instance`]. Use [`PooledInstance::discard`] to drop an
+/// instance instead of returning it to the pool.
+pub struct InstancePool<T> {
+    /// The module of all pooled instances.
+    module: Module,
+    /// Creates the [`Linker`] and the store data of new instances.
+    factory: InstanceFactory<T>,
+    /// The currently unused instances.
+    idle: Mutex<Vec<PooledInstance<T>>>,
+    /// The maximum number of unused instances kept in the pool.
+    max_idle: usize,
+}
+
+/// An instance of an [`InstancePool`] together with its [`Store`].
+pub struct PooledInstance<T> {
+    /// The store owning the instance.
+    store: Store<T>,
+    /// The instance of the pooled module.
+    instance: Instance,
+    /// Is `true` if the instance is not returned to its pool.
+    discarded: bool,
+}
+
+impl<T> PooledInstance<T> {
+    /// Returns the pooled [`Instance`].
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns a shared reference to the [`Store`] of the pooled [`Instance`].
+    pub fn store(&self) -> &Store<T> {
+        &self.store
+    }
+
+    /// Returns an exclusive reference to the [`Store`] of the pooled [`Instance`].
+    pub fn store_mut(&mut self) -> &mut Store<T> {
+        &mut self.store
+    }
+
+    /// Drops the pooled [`Instance`] once it is no longer in use instead of returning it to its pool.
+    pub fn discard(&mut self) {
+        self.discarded = true;
+    }
+}
+
+impl<T> InstancePool<T> {
+    /// The default maximum number of unused instances kept in an [`InstancePool`].
+    pub const DEFAULT_MAX_IDLE: usize = 64;
+
+    /// Creates a new [`InstancePool`] for `module`.
+    ///
+    /// The `factory` creates the [`Linker`] and the store data for every new instance.
+    pub fn new<F>(module: Module, factory: F) -> Self
+    where
+        F: Fn(&Engine) -> Result<(Linker<T>, T), Error> + Send + Sync + 'static,
+    {
+        Self {
+            module,
+            factory: Box::new(factory),
+            idle: Mutex::new(Vec::new()),
+            max_idle: Self::DEFAULT_MAX_IDLE,
+        }
+    }
+
+    /// Sets the maximum number of unused instances kept in the [`InstancePool`].
+    ///
+    /// Instances that are returned to a full pool are dropped.
+    pub fn max_idle(mut self, max_idle: usize) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    /// Returns the [`Module`] of the pooled instances.
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Returns the number of unused instances in the [`InstancePool`].
+    pub fn len_idle(&self) -> usize {
+        self.lock_idle().len()
+    }
+
+    /// Drops all unused instances of the [`InstancePool`].
+    pub fn clear(&self) {
+        self.lock_idle().clear();
+    }
+
+    /// Creates a new instance and adds it to the [`InstancePool`].
+    ///
+    /// This allows to warm up the pool before serving requests.
+    ///
+    /// # Errors
+    ///
+    /// If the instantiation fails.
+    pub fn prewarm(&self) -> Result<(), Error> {
+        let instance = self.instantiate()?;
+        self.recycle(instance);
+        Ok(())
+    }
+
+    /// Calls `f` with exclusive access to an instance of the [`InstancePool`].
+    ///
+    /// Takes an unused instance from the pool or creates a new one if there is none.
+    /// Afterwards the instance is returned to the pool unless `f` returns an error or
+    /// the instance was [discarded](PooledInstance::discard).
+    ///
+    /// # Errors
+    ///
+    /// - If creating a new instance fails.
+    /// - If `f` returns an error.
+    pub fn with_instance<R>(
+        &self,
+        f: impl FnOnce(&mut PooledInstance<T>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let pooled = self.lock_idle().pop();
+        let mut pooled = match pooled {
+            Some(pooled) => pooled,
+            None => self.instantiate()?,
+        };
+        let result = f(&mut pooled);
+        if result.is_ok() {
+            self.recycle(pooled);
+        }
+        result
+    }
+
+    /// Creates a new [`PooledInstance`].
+    fn instantiate(&self) -> Result<PooledInstance<T>, Error> {
+        let engine = self.module.engine();
+        let (linker, data) = (self.factory)(engine)?;
+        let mut store = Store::new(engine, data);
+        let instance = linker
+            .instantiate(&mut store, &self.module)?
+            .start(&mut store)?;
+        Ok(PooledInstance {
+            store,
+            instance,
+            discarded: false,
+        })
+    }
+
+    /// Returns `pooled` to the [`InstancePool`] unless it is discarded or the pool is full.
+    fn recycle(&self, pooled: PooledInstance<T>) {
+        if pooled.discarded {
+            return;
+        }
+        let mut idle = self.lock_idle();
+        if idle.len() < self.max_idle {
+            idle.push(pooled);
+        }
+    }
+
+    /// Locks the unused instances of the [`InstancePool`].
+    fn lock_idle(&self) -> MutexGuard<'_, Vec<PooledInstance<T>>> {
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{
+    core::{TrapCode, UntypedVal, ValType},
+    Error,
+    FuncType,
+    InstructionExtension,
+};
+
+#[cfg(doc)]
+use crate::{Engine, InstructionExtensions};
+
+/// The name of the import module that provides the 128-bit integer instructions.
+const I128_MODULE: &str = "wasmi:i128";
+
+/// An [`InstructionExtension`] that executes 128-bit integer arithmetic natively.
+///
+/// Wasm guests usually emulate 128-bit integer arithmetic with pairs of 64-bit
+/// integers which is slow, especially for multiplication and division. This extension
+/// provides native implementations of those operations which speeds up big integer
+/// and fixed-point arithmetic as used in cryptographic code.
+///
+/// Register it to an [`Engine`] via [`InstructionExtensions::register`].
+///
+/// # Guest Interface
+///
+/// All functions are imported from the `wasmi:i128` module. 128-bit integers
+/// are passed and returned as `(lo: i64, hi: i64)` pairs:
+///
+/// - `add`, `sub`, `mul`: `(i128, i128) -> i128` with wrapping semantics.
+/// - `add_sat_{s,u}`, `sub_sat_{s,u}`, `mul_sat_{s,u}`: `(i128, i128) -> i128` with saturating semantics.
+/// - `add_checked_{s,u}`, `sub_checked_{s,u}`, `mul_checked_{s,u}`: `(i128, i128) -> i128`
+///   that trap with [`TrapCode::IntegerOverflow`] upon overflow.
+/// - `div_{s,u}`, `rem_{s,u}`: `(i128, i128) -> i128` that trap like their Wasm `i64` counterparts.
+/// - `div_rem_{s,u}`: `(i128, i128) -> (i128, i128)` returning quotient and remainder.
+/// - `mul_wide_{s,u}`: `(i64, i64) -> i128` returning the full product of two 64-bit integers.
+/// - `mul_shr_{s,u}`: `(a: i64, b: i64, shift: i32) -> i64` returning `(a * b) >> shift`
+///   with a 128-bit intermediate product as used by fixed-point arithmetic.
+///   The `shift` amount is taken modulo 128.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, I128Ops, InstructionExtensions, Linker, Module, Store};
+/// let mut extensions = InstructionExtensions::new();
+/// extensions.register(I128Ops).unwrap();
+/// let engine = Engine::with_extensions(&Default::default(), extensions);
+/// let wasm = r#"
+///     (module
+///         (import "wasmi:i128" "mul_wide_u" (func $mul_wide_u (param i64 i64) (result i64 i64)))
+///         (func (export "square_hi") (param i64) (result i64)
+///             (call $mul_wide_u (local.get 0) (local.get 0))
+///             (return)
+///         )
+///     )
+/// "#;
+/// let module = Module::new(&engine, wasm).unwrap();
+/// let mut store = Store::new(&engine, ());
+/// let instance = Linker::new(&engine)
+///     .instantiate(&mut store, &module)
+///     .unwrap()
+///     .start(&mut store)
+///     .unwrap();
+/// let square_hi = instance.get_typed_func::<i64, i64>(&store, "square_hi").unwrap();
+/// assert_eq!(square_hi.call(&mut store, -1).unwrap(), -2);
+/// ```
+#[derive(Debug, Default, Copy, Clone)]
+pub struct I128Ops;
+
+/// A 128-bit integer instruction of [`I128Ops`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    AddSatS,
+    AddSatU,
+    SubSatS,
+    SubSatU,
+    MulSatS,
+    MulSatU,
+    AddCheckedS,
+    AddCheckedU,
+    SubCheckedS,
+    SubCheckedU,
+    MulCheckedS,
+    MulCheckedU,
+    DivS,
+    DivU,
+    RemS,
+    RemU,
+    DivRemS,
+    DivRemU,
+    MulWideS,
+    MulWideU,
+    MulShrS,
+    MulShrU,
+}
+
+/// The signature of an [`Op`].
+enum Signature {
+    /// `(i128, i128) -> i128`
+    Binary,
+    /// `(i128, i128) -> (i128, i128)`
+    DivRem,
+    /// `(i64, i64) -> i128`
+    Wide,
+    /// `(i64, i64, i32) -> i64`
+    Shift,
+}
+
+impl Op {
+    /// All [`Op`]s in the order of their opcodes.
+    const ALL: [Self; 25] = [
+        Self::Add,
+        Self::Sub,
+        Self::Mul,
+        Self::AddSatS,
+        Self::AddSatU,
+        Self::SubSatS,
+        Self::SubSatU,
+        Self::MulSatS,
+        Self::MulSatU,
+        Self::AddCheckedS,
+        Self::AddCheckedU,
+        Self::SubCheckedS,
+        Self::SubCheckedU,
+        Self::MulCheckedS,
+        Self::MulCheckedU,
+        Self::DivS,
+        Self::DivU,
+        Self::RemS,
+        Self::RemU,
+        Self::DivRemS,
+        Self::DivRemU,
+        Self::MulWideS,
+        Self::MulWideU,
+        Self::MulShrS,
+        Self::MulShrU,
+    ];
+
+    /// Returns the import name of the [`Op`].
+    fn name(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Sub => "sub",
+            Self::Mul => "mul",
+            Self::AddSatS => "add_sat_s",
+            Self::AddSatU => "add_sat_u",
+            Self::SubSatS => "sub_sat_s",
+            Self::SubSatU => "sub_sat_u",
+            Self::MulSatS => "mul_sat_s",
+            Self::MulSatU => "mul_sat_u",
+            Self::AddCheckedS => "add_checked_s",
+            Self::AddCheckedU => "add_checked_u",
+            Self::SubCheckedS => "sub_checked_s",
+            Self::SubCheckedU => "sub_checked_u",
+            Self::MulCheckedS => "mul_checked_s",
+            Self::MulCheckedU => "mul_checked_u",
+            Self::DivS => "div_s",
+            Self::DivU => "div_u",
+            Self::RemS => "rem_s",
+            Self::RemU => "rem_u",
+            Self::DivRemS => "div_rem_s",
+            Self::DivRemU => "div_rem_u",
+            Self::MulWideS => "mul_wide_s",
+            Self::MulWideU => "mul_wide_u",
+            Self::MulShrS => "mul_shr_s",
+            Self::MulShrU => "mul_shr_u",
+        }
+    }
+
+    /// Returns the [`Signature`] of the [`Op`].
+    fn signature(self) -> Signature {
+        match self {
+            Self::DivRemS | Self::DivRemU => Signature::DivRem,
+            Self::MulWideS | Self::MulWideU => Signature::Wide,
+            Self::MulShrS | Self::MulShrU => Signature::Shift,
+            _ => Signature::Binary,
+        }
+    }
+
+    /// Returns the [`FuncType`] of the [`Op`].
+    fn func_type(self) -> FuncType {
+        use ValType::{I32, I64};
+        match self.signature() {
+            Signature::Binary => FuncType::new([I64; 4], [I64; 2]),
+            Signature::DivRem => FuncType::new([I64; 4], [I64; 4]),
+            Signature::Wide => FuncType::new([I64; 2], [I64; 2]),
+            Signature::Shift => FuncType::new([I64, I64, I32], [I64]),
+        }
+    }
+}
+
+/// Returns the 128-bit integer made up of the `lo` and `hi` halves.
+fn join(lo: UntypedVal, hi: UntypedVal) -> u128 {
+    (u128::from(u64::from(hi)) << 64) | u128::from(u64::from(lo))
+}
+
+/// Writes the `lo` and `hi` halves of `value` to `results`.
+fn split(value: u128, results: &mut [UntypedVal]) {
+    results[0] = (value as u64).into();
+    results[1] = ((value >> 64) as u64).into();
+}
+
+/// Executes the binary [`Op`] on `lhs` and `rhs`.
+fn execute_binary(op: Op, lhs: u128, rhs: u128) -> Result<u128, TrapCode> {
+    let (slhs, srhs) = (lhs as i128, rhs as i128);
+    let overflow = TrapCode::IntegerOverflow;
+    let result = match op {
+        Op::Add => lhs.wrapping_add(rhs),
+        Op::Sub => lhs.wrapping_sub(rhs),
+        Op::Mul => lhs.wrapping_mul(rhs),
+        Op::AddSatS => slhs.saturating_add(srhs) as u128,
+        Op::AddSatU => lhs.saturating_add(rhs),
+        Op::SubSatS => slhs.saturating_sub(srhs) as u128,
+        Op::SubSatU => lhs.saturating_sub(rhs),
+        Op::MulSatS => slhs.saturating_mul(srhs) as u128,
+        Op::MulSatU => lhs.saturating_mul(rhs),
+        Op::AddCheckedS => slhs.checked_add(srhs).ok_or(overflow)? as u128,
+        Op::AddCheckedU => lhs.checked_add(rhs).ok_or(overflow)?,
+        Op::SubCheckedS => slhs.checked_sub(srhs).ok_or(overflow)? as u128,
+        Op::SubCheckedU => lhs.checked_sub(rhs).ok_or(overflow)?,
+        Op::MulCheckedS => slhs.checked_mul(srhs).ok_or(overflow)? as u128,
+        Op::MulCheckedU => lhs.checked_mul(rhs).ok_or(overflow)?,
+        Op::DivS | Op::DivU | Op::RemS | Op::RemU => {
+            let (quotient, remainder) = div_rem(op, lhs, rhs)?;
+            match op {
+                Op::DivS | Op::DivU => quotient,
+                _ => remainder,
+            }
+        }
+        _ => unreachable!("not a binary 128-bit integer instruction: {op:?}"),
+    };
+    Ok(result)
+}
+
+/// Returns the quotient and remainder of `lhs` and `rhs` for the division [`Op`].
+fn div_rem(op: Op, lhs: u128, rhs: u128) -> Result<(u128, u128), TrapCode> {
+    if rhs == 0 {
+        return Err(TrapCode::IntegerDivisionByZero);
+    }
+    let signed = matches!(op, Op::DivS | Op::RemS | Op::DivRemS);
+    if !signed {
+        return Ok((lhs / rhs, lhs % rhs));
+    }
+    let (lhs, rhs) = (lhs as i128, rhs as i128);
+    // Note: like Wasm `i64.rem_s` the remainder of `MIN / -1` is zero.
+    let remainder = lhs.wrapping_rem(rhs) as u128;
+    let quotient = match lhs.checked_div(rhs) {
+        Some(quotient) => quotient as u128,
+        None if op == Op::RemS => 0,
+        None => return Err(TrapCode::IntegerOverflow),
+    };
+    Ok((quotient, remainder))
+}
+
+impl InstructionExtension for I128Ops {
+    fn module(&self) -> &str {
+        I128_MODULE
+    }
+
+    fn resolve(&self, name: &str, ty: &FuncType) -> Option<u16> {
+        let opcode = Op::ALL.iter().position(|op| op.name() == name)?;
+        if Op::ALL[opcode].func_type() != *ty {
+            return None;
+        }
+        Some(opcode as u16)
+    }
+
+    fn execute(
+        &self,
+        opcode: u16,
+        params: &[UntypedVal],
+        results: &mut [UntypedVal],
+    ) -> Result<(), Error> {
+        let op = Op::ALL[usize::from(opcode)];
+        match op.signature() {
+            Signature::Binary => {
+                let lhs = join(params[0], params[1]);
+                let rhs = join(params[2], params[3]);
+                split(execute_binary(op, lhs, rhs)?, results);
+            }
+            Signature::DivRem => {
+                let lhs = join(params[0], params[1]);
+                let rhs = join(params[2], params[3]);
+                let (quotient, remainder) = div_rem(op, lhs, rhs)?;
+                split(quotient, &mut results[..2]);
+                split(remainder, &mut results[2..]);
+            }
+            Signature::Wide => {
+                let (lhs, rhs) = (u64::from(params[0]), u64::from(params[1]));
+                let product = match op {
+                    Op::MulWideS => (i128::from(lhs as i64) * i128::from(rhs as i64)) as u128,
+                    _ => u128::from(lhs) * u128::from(rhs),
+                };
+                split(product, results);
+            }
+            Signature::Shift => {
+                let (lhs, rhs) = (u64::from(params[0]), u64::from(params[1]));
+                let shift = u32::from(params[2]) % 128;
+                let result = match op {
+                    Op::MulShrS => {
+                        ((i128::from(lhs as i64) * i128::from(rhs as i64)) >> shift) as u64
+                    }
+                    _ => ((u128::from(lhs) * u128::from(rhs)) >> shift) as u64,
+                };
+                results[0] = result.into();
+            }
+        }
+        Ok(())
+    }
+}
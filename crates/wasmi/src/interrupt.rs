@@ -0,0 +1,285 @@
+use crate::{core::TrapCode, AsContext, Caller, Error, Func};
+use alloc::{collections::VecDeque, sync::Arc, task::Wake};
+use core::{
+    future::Future,
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use std::{
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+    thread::{self, Thread},
+    time::Instant,
+};
+
+/// The maximum time between two checks for interruption of an interruptible host call.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// A handle to interrupt host calls from other threads.
+///
+/// The [`InterruptHandle`] of a [`Store`] is queried via [`Store::interrupt_handle`] and
+/// interrupts the host calls of the [`Store`] that run via [`Caller::block_on_interruptible`]
+/// or [`Caller::block_on_future_interruptible`].
+///
+/// # Note
+///
+/// An [`InterruptHandle`] stays interrupted until it is [reset](InterruptHandle::reset).
+///
+/// [`Store`]: crate::Store
+/// [`Store::interrupt_handle`]: crate::Store::interrupt_handle
+#[derive(Debug, Default, Clone)]
+pub struct InterruptHandle {
+    /// Set to `true` once interrupted.
+    interrupted: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Interrupts the current and all future interruptible host calls.
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if the [`InterruptHandle`] has been interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::Acquire)
+    }
+
+    /// Resets the [`InterruptHandle`] so that interruptible host calls run again.
+    pub fn reset(&self) {
+        self.interrupted.store(false, Ordering::Release);
+    }
+}
+
+/// A handle to schedule guest functions at the next safepoint of a running execution.
+///
+/// The [`SignalHandle`] of a [`Store`] is queried via [`Store::signal_handle`] and may be
+/// sent to other threads. Similar to a signal handler, a raised guest function is called
+/// at the next safepoint of the current or next Wasm execution of the [`Store`] before the
+/// interrupted execution continues. This enables timers and cancellation callbacks inside
+/// long-running guest loops.
+///
+/// # Note
+///
+/// - Safepoints are the fuel checkpoints at the start of Wasm basic blocks, including loop
+///   headers. Therefore raised functions are only called if fuel metering is enabled via
+///   [`Config::consume_fuel`].
+/// - Raised functions must have the signature `[] -> []` and belong to the [`Store`].
+/// - Raised functions consume the fuel of the [`Store`] and their errors, e.g. traps,
+///   abort the interrupted execution. This allows them to cancel the execution.
+/// - Raised functions are called in the order they have been raised.
+///
+/// [`Store`]: crate::Store
+/// [`Store::signal_handle`]: crate::Store::signal_handle
+/// [`Config::consume_fuel`]: crate::Config::consume_fuel
+#[derive(Debug, Default, Clone)]
+pub struct SignalHandle {
+    /// The raised guest functions shared by all clones of the handle.
+    signals: Arc<Signals>,
+}
+
+/// The raised guest functions of a [`SignalHandle`].
+#[derive(Debug, Default)]
+struct Signals {
+    /// Set to `true` while there are raised guest functions.
+    pending: AtomicBool,
+    /// The raised guest functions in the order they have been raised.
+    queue: Mutex<VecDeque<Func>>,
+}
+
+impl SignalHandle {
+    /// Schedules `func` to be called at the next safepoint of a Wasm execution.
+    pub fn raise(&self, func: Func) {
+        let mut queue = self.lock_queue();
+        queue.push_back(func);
+        self.signals.pending.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if there are raised guest functions that have not been called yet.
+    pub fn is_pending(&self) -> bool {
+        self.signals.pending.load(Ordering::Acquire)
+    }
+
+    /// Removes all raised guest functions that have not been called yet.
+    pub fn clear(&self) {
+        let mut queue = self.lock_queue();
+        queue.clear();
+        self.signals.pending.store(false, Ordering::Release);
+    }
+
+    /// Takes the next raised guest function if any.
+    pub(crate) fn take(&self) -> Option<Func> {
+        let mut queue = self.lock_queue();
+        let func = queue.pop_front();
+        if queue.is_empty() {
+            self.signals.pending.store(false, Ordering::Release);
+        }
+        func
+    }
+
+    /// Locks the queue of raised guest functions.
+    fn lock_queue(&self) -> MutexGuard<'_, VecDeque<Func>> {
+        self.signals
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The interruption state of a [`Store`](crate::Store).
+#[derive(Debug, Default)]
+pub struct InterruptState {
+    /// The handle to interrupt the host calls of the store.
+    handle: InterruptHandle,
+    /// The handle to schedule guest functions at safepoints of executions of the store.
+    signals: SignalHandle,
+    /// The optional deadline after which host calls of the store are interrupted.
+    deadline: Option<Instant>,
+}
+
+impl InterruptState {
+    /// Returns the [`InterruptHandle`] of the store.
+    pub fn handle(&self) -> &InterruptHandle {
+        &self.handle
+    }
+
+    /// Returns the [`SignalHandle`] of the store.
+    pub fn signals(&self) -> &SignalHandle {
+        &self.signals
+    }
+
+    /// Returns the deadline of the store if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Sets the deadline of the store.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Returns the time to wait before the next check for interruption.
+    ///
+    /// # Errors
+    ///
+    /// If the store has been interrupted or its deadline has passed.
+    fn next_wait(&self) -> Result<Duration, TrapCode> {
+        if self.handle.is_interrupted() {
+            return Err(TrapCode::Interrupted);
+        }
+        let Some(deadline) = self.deadline else {
+            return Ok(POLL_INTERVAL);
+        };
+        match deadline.checked_duration_since(Instant::now()) {
+            Some(remaining) if !remaining.is_zero() => Ok(remaining.min(POLL_INTERVAL)),
+            _ => Err(TrapCode::Interrupted),
+        }
+    }
+}
+
+/// Wakes up the thread polling a future.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+impl<T> Caller<'_, T> {
+    /// Runs the blocking operation `op` while staying responsive to interruption of the [`Store`].
+    ///
+    /// The operation `op` runs on a separate thread while the calling thread waits for its
+    /// completion. If the [`InterruptHandle`] of the [`Store`] is interrupted or the deadline of
+    /// the [`Store`] passes before `op` completes the host call traps with [`TrapCode::Interrupted`].
+    ///
+    /// # Note
+    ///
+    /// - A running thread cannot be forcefully stopped. Instead `op` receives an [`InterruptHandle`]
+    ///   that is interrupted once the host call has been cancelled so that `op` can stop early.
+    ///   The result of a cancelled `op` is discarded.
+    /// - Interruption is checked at least every millisecond.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Store`] is interrupted or its deadline passes before `op` completes.
+    /// - If `op` panics.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn block_on_interruptible<R, F>(&self, op: F) -> Result<R, Error>
+    where
+        F: FnOnce(InterruptHandle) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let cancel = InterruptHandle::default();
+        let (sender, receiver) = mpsc::sync_channel(1);
+        thread::spawn({
+            let cancel = cancel.clone();
+            move || {
+                // The receiver is gone if the host call has been cancelled in the meantime.
+                let _ = sender.send(op(cancel));
+            }
+        });
+        loop {
+            let wait = match self.interrupt_state().next_wait() {
+                Ok(wait) => wait,
+                Err(trap) => {
+                    cancel.interrupt();
+                    return Err(trap.into());
+                }
+            };
+            match receiver.recv_timeout(wait) {
+                Ok(result) => return Ok(result),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(Error::new("interruptible host operation panicked"))
+                }
+            }
+        }
+    }
+
+    /// Drives the `future` to completion while staying responsive to interruption of the [`Store`].
+    ///
+    /// The `future` is polled on the calling thread. If the [`InterruptHandle`] of the [`Store`] is
+    /// interrupted or the deadline of the [`Store`] passes before `future` completes then `future`
+    /// is dropped and the host call traps with [`TrapCode::Interrupted`].
+    ///
+    /// # Note
+    ///
+    /// Interruption is checked whenever `future` is woken up and at least every millisecond.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Store`] is interrupted or its deadline passes before `future` completes.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn block_on_future_interruptible<F>(&self, future: F) -> Result<F::Output, Error>
+    where
+        F: Future,
+    {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return Ok(output);
+            }
+            let wait = self.interrupt_state().next_wait()?;
+            thread::park_timeout(wait);
+        }
+    }
+
+    /// Returns the [`InterruptState`] of the [`Store`](crate::Store) of the [`Caller`].
+    fn interrupt_state(&self) -> &InterruptState {
+        self.as_context().store.inner.interrupt_state()
+    }
+}
@@ -82,18 +82,64 @@ mod foreach_tuple;
 #[cfg(test)]
 pub mod tests;
 
+#[cfg(feature = "aot")]
+mod aot;
+#[cfg(feature = "bench")]
+pub mod bench;
+#[cfg(feature = "build")]
+pub mod build;
+#[cfg(feature = "clock")]
+mod clock;
+#[cfg(feature = "dylink")]
+mod dylink;
 mod engine;
 mod error;
 mod externref;
+#[cfg(feature = "fuel-profile")]
+mod fuel_profile;
+mod fuel_trap;
 mod func;
 mod global;
+#[cfg(feature = "guest-abi")]
+mod guest_abi;
+mod import_policy;
 mod instance;
+#[cfg(feature = "std")]
+mod instance_pool;
+#[cfg(feature = "i128")]
+mod int128;
+#[cfg(feature = "std")]
+mod interrupt;
 mod limits;
 mod linker;
+mod loop_counters;
 mod memory;
 mod module;
+#[cfg(feature = "std")]
+mod parallel;
+#[cfg(feature = "preinit")]
+mod preinit;
+#[cfg(feature = "prng")]
+mod prng;
+mod ref_stats;
+mod replay;
+mod scheduler;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod session;
+#[cfg(feature = "emscripten-sjlj")]
+mod sjlj;
+mod snapshot;
 mod store;
+#[cfg(feature = "streams")]
+mod streams;
+mod symbols;
 mod table;
+#[cfg(feature = "time-travel")]
+mod time_travel;
+#[cfg(feature = "trace")]
+mod trace;
+mod trap_handler;
 mod value;
 
 /// Definitions from the `wasmi_core` crate.
@@ -104,15 +150,21 @@ pub use wasmi_core as core;
 #[doc(inline)]
 use wasmi_collections as collections;
 
-/// Definitions from the `wasmi_collections` crate.
+/// Definitions from the `wasmi_ir` crate.
+#[cfg(feature = "ir-builder")]
 #[doc(inline)]
+pub use wasmi_ir as ir;
+
+/// Definitions from the `wasmi_ir` crate.
+#[cfg(not(feature = "ir-builder"))]
 use wasmi_ir as ir;
 
 /// Defines some errors that may occur upon interaction with Wasmi.
 pub mod errors {
     pub use super::{
-        engine::EnforcedLimitsError,
-        error::ErrorKind,
+        engine::{ConfigError, EnforcedLimitsError},
+        error::{ErrorCategory, ErrorKind, HostPayload},
+        fuel_trap::{FuelTrap, FuelTrapFrame},
         func::FuncError,
         global::GlobalError,
         ir::Error as IrError,
@@ -124,18 +176,73 @@ pub mod errors {
     };
 }
 
+#[cfg(feature = "aot")]
+pub use self::aot::AotEmitter;
+#[cfg(feature = "clock")]
+pub use self::clock::VirtualClock;
+#[cfg(feature = "dylink")]
+pub use self::dylink::{DylinkSymbol, DynamicLinker};
+#[cfg(feature = "calibrate")]
+pub use self::engine::{FuelCalibration, OpcodeCost};
+#[cfg(feature = "ir-builder")]
+pub use self::engine::{IrFuncBuilder, IR_VERSION};
+#[cfg(feature = "fuel-profile")]
+pub use self::fuel_profile::{FuelProfile, FuelProfileEntry, FuelProfileIter};
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub use self::func::{BudgetExceeded, BudgetOverrun, HostFuncBudget};
+#[cfg(feature = "guest-abi")]
+pub use self::guest_abi::{
+    GuestAbi,
+    GuestParams,
+    GuestResults,
+    GuestScratch,
+    GuestSlice,
+    LiftGuest,
+    LowerGuest,
+};
+#[cfg(feature = "std")]
+pub use self::instance_pool::{InstancePool, PooledInstance};
+#[cfg(feature = "i128")]
+pub use self::int128::I128Ops;
+#[cfg(feature = "std")]
+pub use self::interrupt::{InterruptHandle, SignalHandle};
+#[cfg(feature = "heap-walk")]
+pub use self::memory::{HeapChunk, HeapWalk};
+#[cfg(feature = "std")]
+pub use self::parallel::{ParallelCall, ParallelOutcome, ParallelPool, ParallelResults};
+#[cfg(feature = "preinit")]
+pub use self::preinit::PreInitializer;
+#[cfg(feature = "prng")]
+pub use self::prng::Prng;
+#[cfg(feature = "emscripten-sjlj")]
+pub use self::sjlj::EmscriptenSjlj;
+#[cfg(feature = "streams")]
+pub use self::streams::Streams;
+#[cfg(feature = "time-travel")]
+pub use self::time_travel::{Checkpoint, TraceStep};
+#[cfg(feature = "trace")]
+pub use self::trace::{Trace, TraceEvent, TraceEventKind};
 pub use self::{
     engine::{
+        AvgBytesPerFunctionLimit,
         CompilationMode,
         Config,
+        ConfigDiff,
         EnforcedLimits,
         Engine,
         EngineWeak,
+        FuelCosts,
+        FuelUsage,
+        InstructionExtension,
+        InstructionExtensions,
         ResumableCall,
         ResumableInvocation,
         StackLimits,
         TypedResumableCall,
         TypedResumableInvocation,
+        UnsupportedProposals,
+        MAX_EXTENSION_ARITY,
+        MAX_EXTENSION_BATCH,
     },
     error::Error,
     externref::ExternRef,
@@ -151,25 +258,73 @@ pub use self::{
         WasmRet,
         WasmTy,
         WasmTyList,
+        MAX_HOST_CALL_BATCH,
     },
     global::{Global, GlobalType, Mutability},
+    import_policy::{ImportDenial, ImportPolicy},
     instance::{Export, ExportsIter, Extern, ExternType, Instance},
     limits::{ResourceLimiter, StoreLimits, StoreLimitsBuilder},
-    linker::{state, Linker, LinkerBuilder},
-    memory::{Memory, MemoryType, MemoryTypeBuilder},
+    linker::{state, Capabilities, Linker, LinkerBuilder},
+    memory::{Memory, MemoryRegionWrite, MemoryType, MemoryTypeBuilder},
     module::{
+        BackEdge,
+        BranchHint,
+        CallEdge,
+        CallGraph,
+        CallTarget,
+        CancellationToken,
+        CompatIssue,
+        CompatReport,
+        ConstantTimeAudit,
+        ConstantTimeReport,
+        ConstantTimeViolation,
+        ConstantTimeViolationKind,
         CustomSection,
         CustomSectionsIter,
         ExportType,
         ImportType,
         InstancePre,
+        InstantiateOptions,
+        InstrHistogram,
+        MemoryImage,
         Module,
+        ModuleAdapter,
         ModuleExportsIter,
+        ModuleFeatures,
         ModuleImportsIter,
+        ModuleSignature,
+        ModuleVerifier,
         Read,
+        Safepoint,
+        SafepointKind,
+        Safepoints,
+        ScannedImport,
+        StackAnalysis,
+        StackUsage,
+        StreamingControl,
+        StreamingProgress,
+        TableImage,
+        CONSTANT_TIME_SECTION,
+    },
+    ref_stats::{ExternRefLeak, RefStats},
+    replay::HostCallRecording,
+    scheduler::{Completion, Scheduler, TaskId},
+    session::Session,
+    snapshot::{StoreDelta, StoreSnapshot},
+    store::{
+        AsContext,
+        AsContextMut,
+        CallHook,
+        MemoryWatermark,
+        Store,
+        StoreContext,
+        StoreContextMut,
+        StorePolicy,
+        WatermarkAction,
     },
-    store::{AsContext, AsContextMut, CallHook, Store, StoreContext, StoreContextMut},
+    symbols::{FuncSymbol, ModuleSymbols},
     table::{Table, TableType},
+    trap_handler::TrapHandler,
     value::Val,
 };
 use self::{
@@ -180,3 +335,5 @@ use self::{
     store::Stored,
     table::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx, TableEntity, TableIdx},
 };
+#[cfg(feature = "bindgen")]
+pub use wasmi_bindgen::{bindgen, host_api};
@@ -4,6 +4,7 @@ use crate::{
         StringInterner,
     },
     func::{FuncEntity, HostFuncEntity, HostFuncTrampolineEntity},
+    global::ComputedGlobal,
     module::{ImportName, ImportType},
     AsContext,
     AsContextMut,
@@ -14,17 +15,24 @@ use crate::{
     ExternType,
     Func,
     FuncType,
+    Global,
     GlobalType,
+    ImportDenial,
+    ImportPolicy,
     Instance,
     InstancePre,
+    InstantiateOptions,
     IntoFunc,
     MemoryType,
     Module,
+    StoreContextMut,
     TableType,
     Val,
+    WasmTy,
 };
 use alloc::{
-    collections::{btree_map::Entry, BTreeMap},
+    boxed::Box,
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
     sync::Arc,
     vec::Vec,
 };
@@ -32,9 +40,14 @@ use core::{
     fmt::{self, Debug, Display},
     marker::PhantomData,
 };
+use spin::Mutex;
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use crate::func::HostFuncBudget;
 
 /// An error that may occur upon operating with [`Linker`] instances.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum LinkerError {
     /// Encountered duplicate definitions for the same name.
     DuplicateDefinition {
@@ -93,6 +106,39 @@ pub enum LinkerError {
         /// The mismatching [`GlobalType`] found.
         found: GlobalType,
     },
+    /// Encountered when an import is denied by the [`ImportPolicy`] of the [`Linker`].
+    ImportDenied {
+        /// The name of the denied import.
+        name: ImportName,
+        /// The type of the denied import.
+        ty: ExternType,
+        /// The reason why the import was denied.
+        reason: ImportDenial,
+    },
+    /// Encountered when an import requires a capability that was not granted.
+    CapabilityNotGranted {
+        /// The name of the import requiring the capability.
+        name: ImportName,
+        /// The type of the import requiring the capability.
+        ty: ExternType,
+    },
+    /// Encountered when an import that is not a function is declared as late-bound.
+    InvalidLateBinding {
+        /// The name of the import declared as late-bound.
+        name: ImportName,
+        /// The type of the import declared as late-bound.
+        ty: ExternType,
+    },
+    /// Encountered when a late-bound function import cannot be resolved upon its call.
+    UnresolvedLateBinding {
+        /// The name of the unresolved late-bound import.
+        name: ImportName,
+    },
+    /// Encountered when a time budget is set for a name that is not a [`Linker`] defined host function.
+    MissingHostFunc {
+        /// The name for which no [`Linker`] defined host function was found.
+        name: ImportName,
+    },
 }
 
 impl LinkerError {
@@ -104,6 +150,54 @@ impl LinkerError {
         }
     }
 
+    /// Returns the name of the import that caused the [`LinkerError`].
+    pub(crate) fn import_name(&self) -> &ImportName {
+        match self {
+            Self::DuplicateDefinition { import_name } => import_name,
+            Self::MissingDefinition { name, .. }
+            | Self::InvalidTypeDefinition { name, .. }
+            | Self::FuncTypeMismatch { name, .. }
+            | Self::InvalidTableSubtype { name, .. }
+            | Self::InvalidMemorySubtype { name, .. }
+            | Self::GlobalTypeMismatch { name, .. }
+            | Self::ImportDenied { name, .. }
+            | Self::CapabilityNotGranted { name, .. }
+            | Self::InvalidLateBinding { name, .. }
+            | Self::UnresolvedLateBinding { name }
+            | Self::MissingHostFunc { name } => name,
+        }
+    }
+
+    /// Creates a new [`LinkerError`] for when an import was denied by the [`ImportPolicy`].
+    fn import_denied(import: &ImportType, reason: ImportDenial) -> Self {
+        Self::ImportDenied {
+            name: import.import_name().clone(),
+            ty: import.ty().clone(),
+            reason,
+        }
+    }
+
+    /// Creates a new [`LinkerError`] for when an import requires a capability that was not granted.
+    fn capability_not_granted(import: &ImportType) -> Self {
+        Self::CapabilityNotGranted {
+            name: import.import_name().clone(),
+            ty: import.ty().clone(),
+        }
+    }
+
+    /// Creates a new [`LinkerError`] for when an import that is not a function is declared as late-bound.
+    fn invalid_late_binding(import: &ImportType) -> Self {
+        Self::InvalidLateBinding {
+            name: import.import_name().clone(),
+            ty: import.ty().clone(),
+        }
+    }
+
+    /// Creates a new [`LinkerError`] for when a late-bound function import cannot be resolved.
+    fn unresolved_late_binding(name: &ImportName) -> Self {
+        Self::UnresolvedLateBinding { name: name.clone() }
+    }
+
     /// Creates a new [`LinkerError`] for when an imported definition has an invalid type.
     fn invalid_type_definition(import: &ImportType, found: &ExternType) -> Self {
         Self::InvalidTypeDefinition {
@@ -187,16 +281,12 @@ impl Display for LinkerError {
                 )
             }
             Self::InvalidTableSubtype { name, ty, other } => {
-                write!(
-                    f,
-                    "import {name}: table type {ty:?} is not a subtype of {other:?}"
-                )
+                write!(f, "import {name}: incompatible table type: ")?;
+                ty.fmt_subtype_mismatch(other, f)
             }
             Self::InvalidMemorySubtype { name, ty, other } => {
-                write!(
-                    f,
-                    "import {name}: memory type {ty:?} is not a subtype of {other:?}"
-                )
+                write!(f, "import {name}: incompatible memory type: ")?;
+                ty.fmt_subtype_mismatch(other, f)
             }
             Self::GlobalTypeMismatch {
                 name,
@@ -209,6 +299,31 @@ impl Display for LinkerError {
                     expected {expected:?} but found {found:?}",
                 )
             }
+            Self::ImportDenied { name, ty, reason } => {
+                write!(
+                    f,
+                    "import {name} with type {ty:?} denied by import policy: {reason}"
+                )
+            }
+            Self::CapabilityNotGranted { name, ty } => {
+                write!(
+                    f,
+                    "import {name} with type {ty:?} requires capability `{}` which was not granted",
+                    name.module(),
+                )
+            }
+            Self::InvalidLateBinding { name, ty } => {
+                write!(
+                    f,
+                    "import {name} with type {ty:?} cannot be late-bound since it is not a function"
+                )
+            }
+            Self::UnresolvedLateBinding { name } => {
+                write!(f, "late-bound import {name} has not been resolved")
+            }
+            Self::MissingHostFunc { name } => {
+                write!(f, "cannot find linker defined host function {name}")
+            }
         }
     }
 }
@@ -260,6 +375,8 @@ enum Definition<T> {
     Extern(Extern),
     /// A [`Linker`] internal host function.
     HostFunc(HostFuncTrampolineEntity<T>),
+    /// A [`Linker`] internal computed global variable.
+    ComputedGlobal(ComputedGlobal),
 }
 
 impl<T> Clone for Definition<T> {
@@ -267,6 +384,7 @@ impl<T> Clone for Definition<T> {
         match self {
             Self::Extern(definition) => Self::Extern(*definition),
             Self::HostFunc(host_func) => Self::HostFunc(host_func.clone()),
+            Self::ComputedGlobal(global) => Self::ComputedGlobal(global.clone()),
         }
     }
 }
@@ -278,7 +396,7 @@ impl<T> Definition<T> {
     fn as_extern(&self) -> Option<&Extern> {
         match self {
             Definition::Extern(item) => Some(item),
-            Definition::HostFunc(_) => None,
+            Definition::HostFunc(_) | Definition::ComputedGlobal(_) => None,
         }
     }
 
@@ -287,6 +405,23 @@ impl<T> Definition<T> {
         match self {
             Definition::Extern(item) => item.ty(ctx),
             Definition::HostFunc(host_func) => ExternType::Func(host_func.func_type().clone()),
+            Definition::ComputedGlobal(global) => ExternType::Global(global.ty()),
+        }
+    }
+
+    /// Returns the [`Global`] of the [`Definition`] if it is a global variable.
+    ///
+    /// Returns `None` otherwise.
+    ///
+    /// # Note
+    ///
+    /// This allocates a new [`Global`] on the `ctx` if it is a [`Linker`]
+    /// defined computed global variable.
+    pub fn as_global(&self, ctx: impl AsContextMut<Data = T>) -> Option<Global> {
+        match self {
+            Definition::Extern(Extern::Global(global)) => Some(*global),
+            Definition::ComputedGlobal(global) => Some(global.instantiate(ctx)),
+            _ => None,
         }
     }
 
@@ -309,7 +444,10 @@ impl<T> Definition<T> {
                     .store
                     .alloc_trampoline(host_func.trampoline().clone());
                 let ty = host_func.func_type();
-                let entity = HostFuncEntity::new(ctx.as_context().engine(), ty, trampoline);
+                let entity = HostFuncEntity::new(ctx.as_context().engine(), ty, trampoline)
+                    .with_batched(host_func.is_batched());
+                #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+                let entity = entity.with_budget(host_func.budget());
                 let func = ctx
                     .as_context_mut()
                     .store
@@ -322,6 +460,34 @@ impl<T> Definition<T> {
     }
 }
 
+/// A set of capabilities granted to a single instantiation via [`Linker::instantiate_with_caps`].
+///
+/// A capability is the name of a [`Linker`] namespace that has been marked
+/// as such using [`Linker::capability`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The names of the granted capability namespaces.
+    granted: BTreeSet<Box<str>>,
+}
+
+impl Capabilities {
+    /// Creates a new empty set of [`Capabilities`] that grants nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grants the capability namespace `module`.
+    pub fn grant(mut self, module: &str) -> Self {
+        self.granted.insert(module.into());
+        self
+    }
+
+    /// Returns `true` if the capability namespace `module` is granted.
+    pub fn is_granted(&self, module: &str) -> bool {
+        self.granted.contains(module)
+    }
+}
+
 /// A linker used to define module imports and instantiate module instances.
 #[derive(Debug)]
 pub struct Linker<T> {
@@ -338,6 +504,38 @@ pub struct Linker<T> {
     shared: Option<Arc<LinkerInner<T>>>,
     /// Inner linker implementation details.
     inner: LinkerInner<T>,
+    /// The [`ImportPolicy`] enforced upon instantiation of [`Module`]s.
+    policy: ImportPolicy,
+    /// The namespaces that must be granted as capabilities upon instantiation.
+    capabilities: BTreeSet<Box<str>>,
+    /// The resolver of late-bound function imports if any.
+    resolver: Option<LateBindingResolver<T>>,
+}
+
+/// The resolver of late-bound function imports of a [`Linker`].
+///
+/// Read more about late-bound imports in [`InstantiateOptions::late_bound`].
+struct LateBindingResolver<T> {
+    /// Creates the host [`Func`] that resolves a late-bound function import upon its first call.
+    bind: Arc<LateBindFn<T>>,
+}
+
+/// Creates the host [`Func`] for a late-bound function import with the given name and type.
+type LateBindFn<T> = dyn Fn(StoreContextMut<T>, &ImportName, &FuncType) -> Func + Send + Sync;
+
+impl<T> Clone for LateBindingResolver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bind: self.bind.clone(),
+        }
+    }
+}
+
+impl<T> Debug for LateBindingResolver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LateBindingResolver")
+            .finish_non_exhaustive()
+    }
 }
 
 impl<T> Clone for Linker<T> {
@@ -346,6 +544,9 @@ impl<T> Clone for Linker<T> {
             engine: self.engine.clone(),
             shared: self.shared.clone(),
             inner: self.inner.clone(),
+            policy: self.policy.clone(),
+            capabilities: self.capabilities.clone(),
+            resolver: self.resolver.clone(),
         }
     }
 }
@@ -363,6 +564,9 @@ impl<T> Linker<T> {
             engine: engine.clone(),
             shared: None,
             inner: LinkerInner::default(),
+            policy: ImportPolicy::default(),
+            capabilities: BTreeSet::new(),
+            resolver: None,
         }
     }
 
@@ -387,6 +591,76 @@ impl<T> Linker<T> {
         self
     }
 
+    /// Returns `true` if this [`Linker`] allows to shadow previous definitions with the same name.
+    pub fn is_shadowing_allowed(&self) -> bool {
+        self.inner.allow_shadowing
+    }
+
+    /// Sets the [`ImportPolicy`] enforced by this [`Linker`] upon instantiation.
+    ///
+    /// Replaces any previously set [`ImportPolicy`].
+    /// By default all imports are allowed.
+    ///
+    /// # Note
+    ///
+    /// The [`ImportPolicy`] is checked for every import of an instantiated [`Module`]
+    /// before its definition is resolved. Therefore denied imports are rejected even
+    /// if this [`Linker`] defines an item for them.
+    pub fn import_policy(&mut self, policy: ImportPolicy) -> &mut Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Marks the namespace `module` of this [`Linker`] as a capability.
+    ///
+    /// Imports from a capability namespace are only resolved if the capability
+    /// has been granted via [`Linker::instantiate_with_caps`]. This allows a single
+    /// [`Linker`] to serve guests with different permission sets.
+    ///
+    /// # Note
+    ///
+    /// [`Linker::instantiate`] grants no capabilities.
+    pub fn capability(&mut self, module: &str) -> &mut Self {
+        self.capabilities.insert(module.into());
+        self
+    }
+
+    /// Returns `true` if the namespace `module` is marked as a capability.
+    pub fn is_capability(&self, module: &str) -> bool {
+        self.capabilities.contains(module)
+    }
+
+    /// Sets the `resolver` of late-bound function imports of this [`Linker`].
+    ///
+    /// Replaces any previously set resolver.
+    ///
+    /// The `resolver` is called upon the first call of a late-bound function import
+    /// with the module and field name of the import and returns the [`Func`] that is called
+    /// in its place for the lifetime of the instance. Returning `None` makes the call
+    /// trap and the `resolver` is called again upon the next call.
+    ///
+    /// Read more about late-bound imports in [`InstantiateOptions::late_bound`].
+    ///
+    /// # Note
+    ///
+    /// Calls to a resolved [`Func`] that does not match the type of the
+    /// late-bound import trap with a [`LinkerError::FuncTypeMismatch`].
+    pub fn late_binding_resolver(
+        &mut self,
+        resolver: impl Fn(&mut Caller<'_, T>, &str, &str) -> Option<Func> + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: 'static,
+    {
+        let resolve = Arc::new(resolver);
+        self.resolver = Some(LateBindingResolver {
+            bind: Arc::new(move |ctx, name, func_type| {
+                late_bound_func(ctx, name, func_type, resolve.clone())
+            }),
+        });
+        self
+    }
+
     /// Ensures that the `name` in `module` is undefined in the shared definitions.
     ///
     /// Returns `Ok` if no shared definition exists.
@@ -443,6 +717,30 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::new_batched`]-style host [`Func`] for this [`Linker`].
+    ///
+    /// For more information see [`Linker::func_wrap`] and [`Func::new_batched`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    pub fn func_new_batched(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, usize, &[Val], &mut [Val]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        self.ensure_undefined(module, name)?;
+        let func = HostFuncTrampolineEntity::new_batched(ty, func);
+        let key = self.inner.new_import_key(module, name);
+        self.inner.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
     /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`].
     ///
     /// For information how to use this API see [`Func::wrap`].
@@ -477,6 +775,66 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Defines a new named immutable global variable whose value is computed by `provider`.
+    ///
+    /// For information how computed global variables behave see [`Global::new_computed`].
+    ///
+    /// Like host functions defined via [`Linker::func_wrap`] the computed global variable
+    /// is [`Store`] independent and a new [`Global`] is created for every instantiation.
+    /// Therefore [`Linker::get`] returns `None` for it.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    ///
+    /// [`Store`]: crate::Store
+    pub fn global_computed<V>(
+        &mut self,
+        module: &str,
+        name: &str,
+        provider: impl Fn() -> V + Send + Sync + 'static,
+    ) -> Result<&mut Self, LinkerError>
+    where
+        V: WasmTy,
+    {
+        self.ensure_undefined(module, name)?;
+        let global = ComputedGlobal::new(provider);
+        let key = self.inner.new_import_key(module, name);
+        self.inner.insert(key, Definition::ComputedGlobal(global))?;
+        Ok(self)
+    }
+
+    /// Sets the time `budget` of the host function `name` in `module` of this [`Linker`].
+    ///
+    /// Every call of the host function is measured and calls exceeding the `budget`
+    /// either trap or suspend the execution depending on the [`HostFuncBudget`].
+    /// Replaces any previously set budget.
+    ///
+    /// # Note
+    ///
+    /// The `budget` applies to instances created after this call.
+    ///
+    /// # Errors
+    ///
+    /// If there is no host function defined via [`Linker::func_new`] or [`Linker::func_wrap`]
+    /// for `name` in `module` of this [`Linker`].
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub fn func_budget(
+        &mut self,
+        module: &str,
+        name: &str,
+        budget: HostFuncBudget,
+    ) -> Result<&mut Self, LinkerError> {
+        let Some(Definition::HostFunc(host_func)) = self.inner.get_definition_mut(module, name)
+        else {
+            return Err(LinkerError::MissingHostFunc {
+                name: ImportName::new(module, name),
+            });
+        };
+        host_func.set_budget(Some(budget));
+        Ok(self)
+    }
+
     /// Looks up a defined [`Extern`] by name in this [`Linker`].
     ///
     /// - Returns `None` if this name was not previously defined in this [`Linker`].
@@ -579,19 +937,92 @@ impl<T> Linker<T> {
     ///
     /// - If the linker does not define imports of the instantiated [`Module`].
     /// - If any imported item does not satisfy its type requirements.
+    /// - If any import is denied by the [`ImportPolicy`] of the [`Linker`].
+    /// - If any import requires a capability since no capabilities are granted.
     pub fn instantiate(
+        &self,
+        context: impl AsContextMut<Data = T>,
+        module: &Module,
+    ) -> Result<InstancePre, Error> {
+        self.instantiate_with_caps(context, module, &Capabilities::new())
+    }
+
+    /// Instantiates the given [`Module`] using the definitions in the [`Linker`]
+    /// and granting the capabilities `caps`.
+    ///
+    /// Read more about capabilities in [`Linker::capability`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define imports of the instantiated [`Module`].
+    /// - If any imported item does not satisfy its type requirements.
+    /// - If any import is denied by the [`ImportPolicy`] of the [`Linker`].
+    /// - If any import requires a capability that is not granted by `caps`.
+    pub fn instantiate_with_caps(
+        &self,
+        context: impl AsContextMut<Data = T>,
+        module: &Module,
+        caps: &Capabilities,
+    ) -> Result<InstancePre, Error> {
+        let options = InstantiateOptions::new().capabilities(caps.clone());
+        self.instantiate_with_options(context, module, &options)
+    }
+
+    /// Instantiates the given [`Module`] using the definitions in the [`Linker`]
+    /// and the given instantiation `options`.
+    ///
+    /// Read more about the available options in [`InstantiateOptions`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define imports of the instantiated [`Module`].
+    /// - If any imported item does not satisfy its type requirements.
+    /// - If any import is denied by the [`ImportPolicy`] of the [`Linker`].
+    /// - If any import requires a capability that is not granted by `options`.
+    /// - If any memory or table image of `options` is invalid or does not fit.
+    pub fn instantiate_with_options(
         &self,
         mut context: impl AsContextMut<Data = T>,
         module: &Module,
+        options: &InstantiateOptions,
     ) -> Result<InstancePre, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
         // TODO: possibly add further resource limtation here on number of externals.
         // Not clear that user can't import the same external lots of times to inflate this.
         let externals = module
             .imports()
-            .map(|import| self.process_import(&mut context, import))
+            .map(|import| self.process_import(&mut context, import, options))
             .collect::<Result<Vec<Extern>, Error>>()?;
-        module.instantiate(context, externals)
+        module.instantiate_with_options(context, externals, options)
+    }
+
+    /// Instantiates the given [`Module`] without running its `start` function.
+    ///
+    /// This is a convenience wrapper around [`Linker::instantiate`] and [`InstancePre::defer_start`].
+    /// Use [`Instance::run_start`] to run the `start` function later.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// If [`Linker::instantiate`] fails.
+    pub fn instantiate_without_start(
+        &self,
+        mut context: impl AsContextMut<Data = T>,
+        module: &Module,
+    ) -> Result<Instance, Error> {
+        let instance_pre = self.instantiate(&mut context, module)?;
+        Ok(instance_pre.defer_start(context))
     }
 
     /// Processes a single [`Module`] import.
@@ -602,19 +1033,55 @@ impl<T> Linker<T> {
     ///
     /// # Errors
     ///
-    /// If the imported item does not satisfy constraints set by the [`Module`].
+    /// - If the imported item does not satisfy constraints set by the [`Module`].
+    /// - If the import is denied by the [`ImportPolicy`] of the [`Linker`].
+    /// - If the import requires a capability that is not granted by `options`.
+    /// - If the import is declared late-bound by `options` but is not a function.
     fn process_import(
         &self,
         mut context: impl AsContextMut<Data = T>,
         import: ImportType,
+        options: &InstantiateOptions,
     ) -> Result<Extern, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
+        if let Some(reason) = self.policy.check(&import) {
+            return Err(Error::from(LinkerError::import_denied(&import, reason)));
+        }
+        let caps = options.get_capabilities();
+        if self.is_capability(import.module()) && !caps.is_granted(import.module()) {
+            return Err(Error::from(LinkerError::capability_not_granted(&import)));
+        }
         let import_name = import.import_name();
         let module_name = import.module();
         let field_name = import.name();
-        let resolved = self
-            .get_definition(context.as_context(), module_name, field_name)
-            .ok_or_else(|| LinkerError::missing_definition(&import))?;
+        if options.is_late_bound(module_name, field_name) {
+            let ExternType::Func(func_type) = import.ty() else {
+                return Err(Error::from(LinkerError::invalid_late_binding(&import)));
+            };
+            let func = match &self.resolver {
+                Some(resolver) => (resolver.bind)(context.as_context_mut(), import_name, func_type),
+                None => {
+                    let name = import_name.clone();
+                    Func::new(context, func_type.clone(), move |_, _, _| {
+                        Err(Error::from(LinkerError::unresolved_late_binding(&name)))
+                    })
+                }
+            };
+            return Ok(Extern::Func(func));
+        }
+        let Some(resolved) = self.get_definition(context.as_context(), module_name, field_name)
+        else {
+            // Note: imports resolved to extension instructions may be left undefined.
+            let ExternType::Func(func_type) = import.ty() else {
+                return Err(Error::from(LinkerError::missing_definition(&import)));
+            };
+            return self
+                .engine()
+                .extensions()
+                .host_func(context, module_name, field_name, func_type)
+                .map(Extern::Func)
+                .ok_or_else(|| Error::from(LinkerError::missing_definition(&import)));
+        };
         let invalid_type = || LinkerError::invalid_type_definition(&import, &resolved.ty(&context));
         match import.ty() {
             ExternType::Func(expected_type) => {
@@ -641,10 +1108,17 @@ impl<T> Linker<T> {
                     .copied()
                     .and_then(Extern::into_table)
                     .ok_or_else(invalid_type)?;
-                let found_type = table.dynamic_ty(context);
-                found_type.is_subtype_or_err(expected_type).map_err(|_| {
-                    LinkerError::table_type_mismatch(import_name, expected_type, &found_type)
-                })?;
+                let found_type = table.dynamic_ty(&context);
+                let relaxed_limits = context
+                    .as_context()
+                    .engine()
+                    .config()
+                    .get_relaxed_import_limits();
+                found_type
+                    .is_subtype_or_err(expected_type, relaxed_limits)
+                    .map_err(|_| {
+                        LinkerError::table_type_mismatch(import_name, &found_type, expected_type)
+                    })?;
                 Ok(Extern::Table(table))
             }
             ExternType::Memory(expected_type) => {
@@ -653,19 +1127,25 @@ impl<T> Linker<T> {
                     .copied()
                     .and_then(Extern::into_memory)
                     .ok_or_else(invalid_type)?;
-                let found_type = memory.dynamic_ty(context);
-                found_type.is_subtype_or_err(expected_type).map_err(|_| {
-                    LinkerError::invalid_memory_subtype(import_name, expected_type, &found_type)
-                })?;
+                let found_type = memory.dynamic_ty(&context);
+                let relaxed_limits = context
+                    .as_context()
+                    .engine()
+                    .config()
+                    .get_relaxed_import_limits();
+                found_type
+                    .is_subtype_or_err(expected_type, relaxed_limits)
+                    .map_err(|_| {
+                        LinkerError::invalid_memory_subtype(import_name, &found_type, expected_type)
+                    })?;
                 Ok(Extern::Memory(memory))
             }
             ExternType::Global(expected_type) => {
-                let global = resolved
-                    .as_extern()
+                let found_type = resolved
+                    .ty(&context)
+                    .global()
                     .copied()
-                    .and_then(Extern::into_global)
                     .ok_or_else(invalid_type)?;
-                let found_type = global.ty(context);
                 if &found_type != expected_type {
                     return Err(Error::from(LinkerError::global_type_mismatch(
                         import_name,
@@ -673,12 +1153,57 @@ impl<T> Linker<T> {
                         &found_type,
                     )));
                 }
+                let global = resolved
+                    .as_global(&mut context)
+                    .expect("already asserted that `resolved` is a global variable");
                 Ok(Extern::Global(global))
             }
         }
     }
 }
 
+/// Creates a host [`Func`] for the late-bound function import `name` of type `func_type`.
+///
+/// The returned [`Func`] resolves the import upon its first call using `resolve`
+/// and forwards all calls to the resolved [`Func`].
+fn late_bound_func<T, F>(
+    ctx: StoreContextMut<T>,
+    name: &ImportName,
+    func_type: &FuncType,
+    resolve: Arc<F>,
+) -> Func
+where
+    T: 'static,
+    F: Fn(&mut Caller<'_, T>, &str, &str) -> Option<Func> + Send + Sync + 'static,
+{
+    let resolved: Arc<Mutex<Option<Func>>> = Arc::new(Mutex::new(None));
+    let name = name.clone();
+    let expected = func_type.clone();
+    Func::new(
+        ctx,
+        func_type.clone(),
+        move |mut caller, params, results| {
+            let cached = *resolved.lock();
+            let func = match cached {
+                Some(func) => func,
+                None => {
+                    let func = resolve(&mut caller, name.module(), name.name())
+                        .ok_or_else(|| LinkerError::unresolved_late_binding(&name))?;
+                    let found = func.ty(&caller);
+                    if found != expected {
+                        return Err(Error::from(LinkerError::func_type_mismatch(
+                            &name, &expected, &found,
+                        )));
+                    }
+                    *resolved.lock() = Some(func);
+                    func
+                }
+            };
+            func.call(&mut caller, params, results)
+        },
+    )
+}
+
 /// Contains type states for the [`LinkerBuilder`] construction process.
 pub mod state {
     /// Signals that the [`LinkerBuilder`] is itself under construction.
@@ -720,6 +1245,9 @@ impl<T> LinkerBuilder<state::Ready, T> {
             engine: engine.clone(),
             shared: self.inner.clone().into(),
             inner: <LinkerInner<T>>::default(),
+            policy: ImportPolicy::default(),
+            capabilities: BTreeSet::new(),
+            resolver: None,
         }
     }
 }
@@ -766,6 +1294,31 @@ impl<T> LinkerBuilder<state::Constructing, T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::new_batched`]-style host [`Func`] for this [`Linker`].
+    ///
+    /// For more information see [`Linker::func_wrap`] and [`Func::new_batched`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`LinkerBuilder`] has already created a [`Linker`] using [`LinkerBuilder::finish`].
+    pub fn func_new_batched(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, usize, &[Val], &mut [Val]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        self.inner_mut().func_new_batched(module, name, ty, func)?;
+        Ok(self)
+    }
+
     /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`].
     ///
     /// For information how to use this API see [`Func::wrap`].
@@ -939,6 +1492,29 @@ impl<T> LinkerInner<T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::new_batched`]-style host [`Func`] for this [`Linker`].
+    ///
+    /// For more information see [`Linker::func_wrap`] and [`Func::new_batched`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    pub fn func_new_batched(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, usize, &[Val], &mut [Val]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        let func = HostFuncTrampolineEntity::new_batched(ty, func);
+        let key = self.new_import_key(module, name);
+        self.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
     /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`].
     ///
     /// For information how to use this API see [`Func::wrap`].
@@ -984,6 +1560,15 @@ impl<T> LinkerInner<T> {
         self.definitions.get(&key)
     }
 
+    /// Looks up a [`Definition`] by name in this [`Linker`] for mutation.
+    ///
+    /// Returns `None` if this name was not previously defined in this [`Linker`].
+    #[cfg(feature = "std")]
+    fn get_definition_mut(&mut self, module: &str, name: &str) -> Option<&mut Definition<T>> {
+        let key = self.get_import_key(module, name)?;
+        self.definitions.get_mut(&key)
+    }
+
     /// Returns `true` if [`LinkerInner`] contains a [`Definition`] for `name` in `module`.
     fn has_definition(&self, module: &str, name: &str) -> bool {
         let Some(key) = self.get_import_key(module, name) else {
@@ -1220,4 +1805,90 @@ mod tests {
         linker.define("host", "hello", func).unwrap();
         linker.instantiate(&mut store, &module).unwrap();
     }
+
+    #[test]
+    fn import_policy_denies_imports() {
+        use crate::{errors::ErrorKind, Global, Mutability};
+        let wasm = r#"
+            (module
+                (import "env" "random_get" (func (result i32)))
+                (import "env" "counter" (global (mut i32)))
+            )"#;
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let module = Module::new(&engine, wasm).unwrap();
+        let mut linker = <Linker<()>>::new(&engine);
+        linker
+            .func_wrap("env", "random_get", || 4_i32)
+            .unwrap()
+            .define(
+                "env",
+                "counter",
+                Global::new(&mut store, Val::I32(0), Mutability::Var),
+            )
+            .unwrap();
+        let assert_denied = |linker: &Linker<()>, store: &mut Store<()>, expected: ImportDenial| {
+            let error = linker.instantiate(store, &module).unwrap_err();
+            match error.kind() {
+                ErrorKind::Linker(LinkerError::ImportDenied { reason, .. }) => {
+                    assert_eq!(reason, &expected)
+                }
+                error => panic!("unexpected error: {error}"),
+            }
+        };
+        // The default policy allows all imports.
+        linker.instantiate(&mut store, &module).unwrap();
+        linker.import_policy(ImportPolicy::new().deny_name("*random*"));
+        assert_denied(&linker, &mut store, ImportDenial::Name("*random*".into()));
+        linker.import_policy(ImportPolicy::new().deny_module("env"));
+        assert_denied(&linker, &mut store, ImportDenial::Module("env".into()));
+        linker.import_policy(ImportPolicy::new().deny_mutable_globals());
+        assert_denied(&linker, &mut store, ImportDenial::MutableGlobal);
+        linker.import_policy(
+            ImportPolicy::new().deny_if("no funcs", |import| import.ty().func().is_some()),
+        );
+        assert_denied(&linker, &mut store, ImportDenial::Custom("no funcs".into()));
+        linker.import_policy(ImportPolicy::new().deny_name("clock_*"));
+        linker.instantiate(&mut store, &module).unwrap();
+    }
+
+    #[test]
+    fn capabilities_must_be_granted() {
+        use crate::errors::ErrorKind;
+        let wasm = r#"
+            (module
+                (import "env" "log" (func))
+                (import "clock" "now" (func (result i64)))
+            )"#;
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let module = Module::new(&engine, wasm).unwrap();
+        let mut linker = <Linker<()>>::new(&engine);
+        linker
+            .func_wrap("env", "log", || ())
+            .unwrap()
+            .func_wrap("clock", "now", || 0_i64)
+            .unwrap();
+        // Without marked capabilities all definitions are available.
+        linker.instantiate(&mut store, &module).unwrap();
+        linker.capability("clock");
+        assert!(linker.is_capability("clock"));
+        assert!(!linker.is_capability("env"));
+        let error = linker.instantiate(&mut store, &module).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::Linker(LinkerError::CapabilityNotGranted { name, .. })
+                if name.module() == "clock" && name.name() == "now"
+        ));
+        let error = linker
+            .instantiate_with_caps(&mut store, &module, &Capabilities::new().grant("env"))
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::Linker(LinkerError::CapabilityNotGranted { .. })
+        ));
+        linker
+            .instantiate_with_caps(&mut store, &module, &Capabilities::new().grant("clock"))
+            .unwrap();
+    }
 }
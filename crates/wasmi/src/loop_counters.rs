@@ -0,0 +1,32 @@
+use crate::engine::EngineFunc;
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// The iteration counters of the Wasm `loop`s of the executed Wasm function bodies.
+#[derive(Debug, Default, Clone)]
+pub struct LoopCounters {
+    /// The iteration counts of each executed [`EngineFunc`] indexed by its `loop`s.
+    counts: BTreeMap<EngineFunc, Vec<u64>>,
+}
+
+impl LoopCounters {
+    /// Counts an iteration of the `loop` at `index` of the [`EngineFunc`] `func`.
+    #[inline]
+    pub fn record(&mut self, func: EngineFunc, index: u32) {
+        let counts = self.counts.entry(func).or_default();
+        let index = index as usize;
+        if index >= counts.len() {
+            counts.resize(index + 1, 0);
+        }
+        counts[index] = counts[index].saturating_add(1);
+    }
+
+    /// Resets all loop iteration counters to zero.
+    pub fn reset(&mut self) {
+        self.counts.clear();
+    }
+
+    /// Returns the iteration counts of the `loop`s of the [`EngineFunc`] `func`.
+    pub fn get(&self, func: EngineFunc) -> &[u64] {
+        self.counts.get(&func).map_or(&[], |counts| &counts[..])
+    }
+}
@@ -1,6 +1,6 @@
 use crate::memory::MemoryError;
 use alloc::{slice, vec::Vec};
-use core::{iter, mem::ManuallyDrop};
+use core::{hint, iter, mem::ManuallyDrop, ptr};
 
 /// A byte buffer implementation.
 ///
@@ -9,6 +9,11 @@ use core::{iter, mem::ManuallyDrop};
 /// This is less efficient than the byte buffer implementation that is
 /// based on actual OS provided virtual memory but it is a safe fallback
 /// solution fitting any platform.
+///
+/// Since no guard pages are reserved every access of Wasm code to the [`ByteBuffer`]
+/// is bounds checked by the executor. Out of bounds accesses therefore trap without
+/// faulting and Wasmi does not need to install any signal handlers, e.g. for `SIGSEGV`,
+/// that could interfere with the signal handlers of the embedder.
 #[derive(Debug)]
 pub struct ByteBuffer {
     /// The pointer to the underlying byte buffer.
@@ -29,6 +34,8 @@ pub struct ByteBuffer {
     capacity: usize,
     /// Whether the [`ByteBuffer`] was initialized from a `&'static [u8]` or a `Vec<u8>`.
     is_static: bool,
+    /// Is `true` if the bytes of the [`ByteBuffer`] are zeroed before they are freed.
+    zero_on_free: bool,
 }
 
 // # Safety
@@ -67,6 +74,18 @@ fn vec_into_raw_parts(vec: Vec<u8>) -> (*mut u8, usize, usize) {
     (vec.as_mut_ptr(), vec.len(), vec.capacity())
 }
 
+/// Zeroes the `len` bytes at `ptr` so that the zeroing is not optimized away.
+///
+/// # Safety
+///
+/// The `ptr` must be valid for writes of `len` bytes.
+pub unsafe fn zero_bytes(ptr: *mut u8, len: usize) {
+    ptr::write_bytes(ptr, 0x00_u8, len);
+    // Note: this prevents the compiler from eliding the zeroing of memory that is
+    //       about to be freed since it must assume that `ptr` is read afterwards.
+    hint::black_box(ptr);
+}
+
 impl ByteBuffer {
     /// Creates a new byte buffer with the given initial `size` in bytes.
     ///
@@ -85,6 +104,7 @@ impl ByteBuffer {
             len,
             capacity,
             is_static: false,
+            zero_on_free: false,
         })
     }
 
@@ -105,6 +125,7 @@ impl ByteBuffer {
             len: size,
             capacity: buffer.len(),
             is_static: true,
+            zero_on_free: false,
         })
     }
 
@@ -129,10 +150,67 @@ impl ByteBuffer {
         }
     }
 
+    /// Reserves capacity for the byte buffer to grow to `new_size` without failing.
+    ///
+    /// The length and contents of the byte buffer are unchanged.
+    ///
+    /// # Errors
+    ///
+    /// - `vec`: If the system allocator ran out of memory to allocate.
+    /// - `static`: If `new_size` is larger than it's the static buffer capacity.
+    pub fn reserve(&mut self, new_size: usize) -> Result<(), MemoryError> {
+        if new_size <= self.capacity {
+            return Ok(());
+        }
+        let Some(vec) = self.get_vec() else {
+            return Err(MemoryError::InvalidStaticBufferSize);
+        };
+        let len = vec.len();
+        self.grow_vec(vec, new_size)?;
+        self.truncate(len);
+        Ok(())
+    }
+
+    /// Shrinks the byte buffer to the given `new_size`.
+    ///
+    /// # Panics
+    ///
+    /// - If the current size of the [`ByteBuffer`] is smaller than `new_size`.
+    pub fn truncate(&mut self, new_size: usize) {
+        assert!(new_size <= self.len());
+        self.len = new_size;
+    }
+
+    /// Makes the byte buffer zero its private bytes before they are freed.
+    ///
+    /// This also zeroes the bytes of a static byte buffer when the byte buffer is dropped.
+    pub fn set_zero_on_free(&mut self) {
+        self.zero_on_free = true;
+    }
+
+    /// Zeroes all bytes of the byte buffer including its spare capacity.
+    fn zero_all(&mut self) {
+        // Safety: both `Vec` and static byte buffers own `capacity` bytes at `ptr`.
+        unsafe { zero_bytes(self.ptr, self.capacity) }
+    }
+
     /// Grow the byte buffer to the given `new_size` when backed by a [`Vec`].
     fn grow_vec(&mut self, mut vec: Vec<u8>, new_size: usize) -> Result<(), MemoryError> {
         debug_assert!(vec.len() <= new_size);
         let additional = new_size - vec.len();
+        if self.zero_on_free && additional > vec.capacity() - vec.len() {
+            // Note: we reallocate manually since the allocator would free the
+            //       old allocation without zeroing it.
+            let mut grown = Vec::new();
+            if grown.try_reserve_exact(new_size).is_err() {
+                (self.ptr, self.len, self.capacity) = vec_into_raw_parts(vec);
+                return Err(MemoryError::OutOfBoundsAllocation);
+            };
+            grown.extend_from_slice(&vec);
+            // Safety: the old allocation owns `capacity` bytes and is freed right after.
+            unsafe { zero_bytes(vec.as_mut_ptr(), vec.capacity()) };
+            vec = grown;
+        }
         if vec.try_reserve(additional).is_err() {
             return Err(MemoryError::OutOfBoundsAllocation);
         };
@@ -196,6 +274,9 @@ impl ByteBuffer {
 
 impl Drop for ByteBuffer {
     fn drop(&mut self) {
+        if self.zero_on_free {
+            self.zero_all();
+        }
         self.get_vec();
     }
 }
@@ -260,6 +341,27 @@ mod test {
         assert_eq!(buffer.data(), &[0; 10]);
     }
 
+    #[test]
+    fn test_growing_buffer_zero_on_free() {
+        let mut buffer = ByteBuffer::new(5).unwrap();
+        buffer.set_zero_on_free();
+        buffer.data_mut().copy_from_slice(&[1, 2, 3, 4, 5]);
+        buffer.grow(1000).unwrap();
+        assert_eq!(&buffer.data()[..6], &[1, 2, 3, 4, 5, 0]);
+        assert!(buffer.data()[5..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn test_static_buffer_zero_on_free() {
+        static mut BUF: [u8; 10] = [7; 10];
+        let buf = unsafe { &mut *core::ptr::addr_of_mut!(BUF) };
+        let mut buffer = ByteBuffer::new_static(buf, 5).unwrap();
+        buffer.set_zero_on_free();
+        buffer.data_mut().fill(42);
+        drop(buffer);
+        assert_eq!(unsafe { BUF }, [0; 10]);
+    }
+
     #[test]
     fn test_static_buffer_overflow() {
         static mut BUF: [u8; 5] = [7; 5];
@@ -116,4 +116,20 @@ impl DataSegmentEntity {
     pub fn drop_bytes(&mut self) {
         self.bytes = None;
     }
+
+    /// Returns a shared handle to the bytes of a passive [`DataSegmentEntity`] if any.
+    ///
+    /// Returns `None` for active and dropped data segments.
+    pub fn passive_bytes(&self) -> Option<&PassiveDataSegmentBytes> {
+        self.bytes.as_ref()
+    }
+
+    /// Replaces the bytes of the [`DataSegmentEntity`] with `bytes`.
+    ///
+    /// This is used to undrop data segments when restoring a [`StoreSnapshot`].
+    ///
+    /// [`StoreSnapshot`]: crate::StoreSnapshot
+    pub fn set_bytes(&mut self, bytes: Option<PassiveDataSegmentBytes>) {
+        self.bytes = bytes;
+    }
 }
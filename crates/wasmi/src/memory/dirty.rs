@@ -0,0 +1,115 @@
+use alloc::{vec, vec::Vec};
+
+/// The number of pages tracked by a single word of a dirty page bitmap.
+const PAGES_PER_WORD: usize = u64::BITS as usize;
+
+/// Tracks the pages of a linear memory that changed since they were last cleared.
+///
+/// # Note
+///
+/// Dirty pages are tracked with a bitmap of one bit per page which is only allocated
+/// upon the first [`DirtyPages::clear`]. Until then all pages are considered dirty.
+#[derive(Debug, Default)]
+pub struct DirtyPages {
+    /// The bitmap of dirty pages if dirty pages are tracked.
+    bits: Option<Vec<u64>>,
+}
+
+impl DirtyPages {
+    /// Returns the number of bitmap words required to track `pages` pages.
+    fn words(pages: u32) -> usize {
+        (pages as usize).div_ceil(PAGES_PER_WORD)
+    }
+
+    /// Returns the indices of all dirty pages within the first `pages` pages in ascending order.
+    pub fn collect(&self, pages: u32) -> Vec<u32> {
+        let Some(bits) = &self.bits else {
+            return (0..pages).collect();
+        };
+        (0..pages)
+            .filter(|&page| is_dirty(bits, page as usize))
+            .collect()
+    }
+
+    /// Marks all of the `pages` pages as clean and starts tracking dirty pages.
+    pub fn clear(&mut self, pages: u32) {
+        let words = Self::words(pages);
+        match &mut self.bits {
+            Some(bits) => {
+                bits.clear();
+                bits.resize(words, 0);
+            }
+            None => self.bits = Some(vec![0; words]),
+        }
+    }
+
+    /// Marks the pages added by growing from `old_pages` to `new_pages` pages as dirty.
+    pub fn grow(&mut self, old_pages: u32, new_pages: u32) {
+        let Some(bits) = &mut self.bits else {
+            return;
+        };
+        bits.resize(Self::words(new_pages), 0);
+        for page in old_pages..new_pages {
+            mark_page(bits, page as usize);
+        }
+    }
+
+    /// Marks all of the `pages` pages as dirty after the linear memory has been resized.
+    pub fn reset(&mut self, pages: u32) {
+        if let Some(bits) = &mut self.bits {
+            bits.clear();
+            bits.resize(Self::words(pages), u64::MAX);
+        }
+    }
+
+    /// Marks all pages as dirty.
+    pub fn mark_all(&mut self) {
+        if let Some(bits) = &mut self.bits {
+            bits.fill(u64::MAX);
+        }
+    }
+
+    /// Marks all pages overlapping with the `len` bytes at `offset` as dirty.
+    pub fn mark(&mut self, page_size_log2: u8, offset: usize, len: usize) {
+        if let Some(bits) = &mut self.bits {
+            mark_dirty(bits, page_size_log2, offset, len);
+        }
+    }
+
+    /// Returns an exclusive slice to the dirty page bitmap if dirty pages are tracked.
+    ///
+    /// # Note
+    ///
+    /// The returned bitmap is reallocated when dirty pages are cleared or the
+    /// linear memory is grown. Use [`mark_dirty`] to mark pages within it.
+    pub fn bits_mut(&mut self) -> Option<&mut [u64]> {
+        self.bits.as_deref_mut()
+    }
+}
+
+/// Returns `true` if `page` is marked as dirty in the bitmap `bits`.
+fn is_dirty(bits: &[u64], page: usize) -> bool {
+    bits.get(page / PAGES_PER_WORD)
+        .is_some_and(|word| word & (1 << (page % PAGES_PER_WORD)) != 0)
+}
+
+/// Marks `page` as dirty in the bitmap `bits`.
+fn mark_page(bits: &mut [u64], page: usize) {
+    if let Some(word) = bits.get_mut(page / PAGES_PER_WORD) {
+        *word |= 1 << (page % PAGES_PER_WORD);
+    }
+}
+
+/// Marks all pages overlapping with the `len` bytes at `offset` as dirty in the bitmap `bits`.
+///
+/// Pages of `page_size_log2` are tracked by `bits` with one bit per page.
+pub fn mark_dirty(bits: &mut [u64], page_size_log2: u8, offset: usize, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let first = offset >> page_size_log2;
+    let last = offset.saturating_add(len - 1) >> page_size_log2;
+    for page in first..=last {
+        mark_page(bits, page);
+    }
+}
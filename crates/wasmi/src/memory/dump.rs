@@ -0,0 +1,140 @@
+use super::{Memory, MemoryError};
+use crate::{AsContext, AsContextMut};
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Write as _, ops::Range};
+
+/// The number of bytes per line of [`Memory::hexdump`].
+const BYTES_PER_LINE: usize = 16;
+
+impl Memory {
+    /// Returns the indices of all pages of the [`Memory`] that changed since the last
+    /// call to [`Memory::clear_dirty_pages`] in ascending order.
+    ///
+    /// Together with [`Memory::dump`] this allows to synchronize the state of a [`Memory`]
+    /// incrementally or to take snapshot deltas by only transferring its dirty pages.
+    ///
+    /// # Note
+    ///
+    /// - Pages are as large as the page size of the [`MemoryType`](crate::MemoryType).
+    /// - All pages are dirty if [`Memory::clear_dirty_pages`] has never been called.
+    /// - Pages that have been added by growing the [`Memory`] since are dirty.
+    /// - Pages are dirty once written to even if their previous contents have been restored.
+    /// - All pages are dirty after [`Memory::data_mut`] since its writes cannot be tracked.
+    /// - Writes through [`Memory::data_ptr`] are not tracked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn dirty_pages(&self, ctx: impl AsContext) -> Vec<u32> {
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .dirty_pages()
+    }
+
+    /// Marks all pages of the [`Memory`] as clean.
+    ///
+    /// # Note
+    ///
+    /// Dirty pages are tracked with one bit per page from the first call of this method on.
+    /// Wasm stores and host writes via [`Memory::write`] mark the pages they write as dirty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn clear_dirty_pages(&self, mut ctx: impl AsContextMut) {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_memory_mut(self)
+            .clear_dirty_pages()
+    }
+
+    /// Writes the raw bytes of the [`Memory`] within `range` to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// - If `range` is out of bounds of the [`Memory`].
+    /// - If writing to `writer` fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    #[cfg(feature = "std")]
+    pub fn dump(
+        &self,
+        ctx: impl AsContext,
+        range: Range<usize>,
+        mut writer: impl std::io::Write,
+    ) -> std::io::Result<()> {
+        let bytes = self
+            .bytes(&ctx, range)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+        writer.write_all(bytes)
+    }
+
+    /// Returns the bytes of the [`Memory`] within `range` formatted as a canonical hexdump.
+    ///
+    /// Every line shows the address of its first byte followed by up to 16 bytes
+    /// in hexadecimal and their printable ASCII characters as in:
+    ///
+    /// ```text
+    /// 00000010  48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 00 00 00  |Hello, World!...|
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn hexdump(&self, ctx: impl AsContext, range: Range<usize>) -> Result<String, MemoryError> {
+        let start = range.start;
+        let bytes = self.bytes(&ctx, range)?;
+        let mut hexdump = String::new();
+        for (n, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+            let _ = write!(hexdump, "{:08x} ", start + n * BYTES_PER_LINE);
+            for column in 0..BYTES_PER_LINE {
+                if column % 8 == 0 {
+                    hexdump.push(' ');
+                }
+                match line.get(column) {
+                    Some(byte) => {
+                        let _ = write!(hexdump, "{byte:02x} ");
+                    }
+                    None => hexdump.push_str("   "),
+                }
+            }
+            hexdump.push(' ');
+            hexdump.push('|');
+            hexdump.extend(line.iter().map(|&byte| match byte {
+                0x20..=0x7E => char::from(byte),
+                _ => '.',
+            }));
+            hexdump.push('|');
+            hexdump.push('\n');
+        }
+        Ok(hexdump)
+    }
+
+    /// Returns the bytes of the [`Memory`] within `range`.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the [`Memory`].
+    fn bytes<'a>(
+        &self,
+        ctx: &'a impl AsContext,
+        range: Range<usize>,
+    ) -> Result<&'a [u8], MemoryError> {
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .data()
+            .get(range)
+            .ok_or(MemoryError::OutOfBoundsAccess)
+    }
+}
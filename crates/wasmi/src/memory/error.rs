@@ -47,7 +47,8 @@ impl Display for MemoryError {
                 write!(f, "tried to create an invalid linear memory type")
             }
             Self::InvalidSubtype { ty, other } => {
-                write!(f, "memory type {ty:?} is not a subtype of {other:?}",)
+                write!(f, "incompatible memory type: ")?;
+                ty.fmt_subtype_mismatch(other, f)
             }
             Self::TooManyMemories => {
                 write!(f, "too many memories")
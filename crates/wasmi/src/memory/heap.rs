@@ -0,0 +1,160 @@
+use super::{Memory, MemoryError};
+use crate::AsContext;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The size of a dlmalloc chunk header on `wasm32`: `prev_foot` and `head`.
+const CHUNK_HEADER_SIZE: usize = 8;
+
+/// The flag bits of the `head` field of a dlmalloc chunk header.
+const FLAG_BITS: u32 = 0b111;
+
+/// The bit of the `head` field of a dlmalloc chunk header marking the chunk as in use.
+const CINUSE_BIT: u32 = 0b010;
+
+impl Memory {
+    /// Walks the chunks of a dlmalloc heap segment of the [`Memory`] within `segment`.
+    ///
+    /// This understands the `wasm32` chunk layout of dlmalloc which is used by Rust's
+    /// default global allocator on Wasm targets as well as by wasi-libc. Together with
+    /// [`Store::tag_memory_region`] this allows to attribute guest heap usage to regions
+    /// of interest, e.g. to diagnose bloated or leaking guest heaps.
+    ///
+    /// # Note
+    ///
+    /// - The first chunk is expected at `segment.start` which usually is the value of the
+    ///   `__heap_base` global exported by the guest, or the start of a page that dlmalloc
+    ///   requested via `memory.grow`.
+    /// - The walk stops at the end of `segment`, at a fencepost or at a malformed chunk.
+    /// - The top chunk and the footer of a segment are reported as free chunks.
+    ///
+    /// # Errors
+    ///
+    /// If `segment` is out of bounds of the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`Store::tag_memory_region`]: crate::Store::tag_memory_region
+    pub fn walk_dlmalloc(
+        &self,
+        ctx: impl AsContext,
+        segment: Range<usize>,
+    ) -> Result<HeapWalk, MemoryError> {
+        let start = segment.start;
+        let bytes = ctx
+            .as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .data()
+            .get(segment)
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while let Some(header) = bytes.get(offset..offset + CHUNK_HEADER_SIZE) {
+            let head = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+            let size = (head & !FLAG_BITS) as usize;
+            // Note: fenceposts that terminate a segment have a size of zero.
+            if size < CHUNK_HEADER_SIZE || size > bytes.len() - offset {
+                break;
+            }
+            chunks.push(HeapChunk {
+                addr: start + offset,
+                size,
+                in_use: head & CINUSE_BIT != 0,
+            });
+            offset += size;
+        }
+        Ok(HeapWalk { chunks })
+    }
+}
+
+/// The chunks of a guest heap.
+///
+/// Returned by [`Memory::walk_dlmalloc`].
+#[derive(Debug, Clone)]
+pub struct HeapWalk {
+    /// The chunks sorted by their address.
+    chunks: Vec<HeapChunk>,
+}
+
+impl HeapWalk {
+    /// Returns all chunks of the heap sorted by their address.
+    pub fn chunks(&self) -> &[HeapChunk] {
+        &self.chunks
+    }
+
+    /// Returns the number of bytes of all chunks that are in use including their headers.
+    pub fn in_use(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.in_use)
+            .map(HeapChunk::size)
+            .sum()
+    }
+
+    /// Returns the number of bytes of all free chunks including their headers.
+    pub fn free(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| !chunk.in_use)
+            .map(HeapChunk::size)
+            .sum()
+    }
+
+    /// Returns the number of bytes of chunks in use that overlap with `range`.
+    ///
+    /// Only the overlapping bytes of each chunk are attributed to `range`.
+    pub fn in_use_within(&self, range: Range<usize>) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.in_use)
+            .map(|chunk| {
+                let end = chunk.range().end.min(range.end);
+                end.saturating_sub(chunk.addr.max(range.start))
+            })
+            .sum()
+    }
+}
+
+/// A chunk of a guest heap.
+///
+/// Returned by [`HeapWalk::chunks`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HeapChunk {
+    /// The address of the chunk header.
+    addr: usize,
+    /// The size of the chunk in bytes including its header.
+    size: usize,
+    /// Is `true` if the chunk is allocated.
+    in_use: bool,
+}
+
+impl HeapChunk {
+    /// Returns the address of the chunk header.
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    /// Returns the size of the chunk in bytes including its header.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the address range of the chunk including its header.
+    pub fn range(&self) -> Range<usize> {
+        self.addr..self.addr + self.size
+    }
+
+    /// Returns the address of the payload that the allocator handed out for the chunk.
+    pub fn payload(&self) -> usize {
+        self.addr + CHUNK_HEADER_SIZE
+    }
+
+    /// Returns `true` if the chunk is allocated.
+    pub fn is_in_use(&self) -> bool {
+        self.in_use
+    }
+}
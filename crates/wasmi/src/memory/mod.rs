@@ -1,14 +1,23 @@
 mod buffer;
 mod data;
+mod dirty;
+mod dump;
 mod error;
+#[cfg(feature = "heap-walk")]
+mod heap;
+mod tags;
 
 #[cfg(test)]
 mod tests;
 
-use self::buffer::ByteBuffer;
+#[cfg(feature = "heap-walk")]
+pub use self::heap::{HeapChunk, HeapWalk};
+pub(crate) use self::{buffer::zero_bytes, dirty::mark_dirty, tags::MemoryTags};
+use self::{buffer::ByteBuffer, dirty::DirtyPages};
 pub use self::{
     data::{DataSegment, DataSegmentEntity, DataSegmentIdx},
     error::MemoryError,
+    tags::MemoryRegionWrite,
 };
 use super::{AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
 use crate::{
@@ -18,6 +27,8 @@ use crate::{
     store::{Fuel, ResourceLimiterRef},
     Error,
 };
+use alloc::vec::Vec;
+use core::fmt;
 
 /// A raw index to a linear memory entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -220,9 +231,14 @@ impl MemoryType {
     /// # Errors
     ///
     /// - If the `minimum` size of `self` is less than or equal to the `minimum` size of `other`.
-    /// - If the `maximum` size of `self` is greater than the `maximum` size of `other`.
-    pub(crate) fn is_subtype_or_err(&self, other: &MemoryType) -> Result<(), MemoryError> {
-        match self.is_subtype_of(other) {
+    /// - If the `maximum` size of `self` is greater than the `maximum` size of `other`
+    ///   and `relaxed_limits` is `false`.
+    pub(crate) fn is_subtype_or_err(
+        &self,
+        other: &MemoryType,
+        relaxed_limits: bool,
+    ) -> Result<(), MemoryError> {
+        match self.is_subtype_with(other, relaxed_limits) {
             true => Ok(()),
             false => Err(MemoryError::InvalidSubtype {
                 ty: *self,
@@ -240,18 +256,75 @@ impl MemoryType {
     /// [import subtyping]:
     /// https://webassembly.github.io/spec/core/valid/types.html#import-subtyping
     pub(crate) fn is_subtype_of(&self, other: &MemoryType) -> bool {
+        self.is_subtype_with(other, false)
+    }
+
+    /// Returns `true` if the [`MemoryType`] is a subtype of the `other` [`MemoryType`].
+    ///
+    /// The `maximum` sizes are ignored if `relaxed_limits` is `true`.
+    fn is_subtype_with(&self, other: &MemoryType, relaxed_limits: bool) -> bool {
         if self.page_size() != other.page_size() {
             return false;
         }
         if self.minimum() < other.minimum() {
             return false;
         }
+        if relaxed_limits {
+            return true;
+        }
         match (self.maximum(), other.maximum()) {
             (_, None) => true,
             (Some(max), Some(other_max)) => max <= other_max,
             _ => false,
         }
     }
+
+    /// Writes the reason why the [`MemoryType`] is not a subtype of `other` to `f`.
+    pub(crate) fn fmt_subtype_mismatch(
+        &self,
+        other: &MemoryType,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        if self.page_size() != other.page_size() {
+            return write!(
+                f,
+                "page size of {} bytes does not match the required page size of {} bytes",
+                self.page_size(),
+                other.page_size(),
+            );
+        }
+        if self.minimum() < other.minimum() {
+            return write!(
+                f,
+                "minimum of {} pages is less than the required minimum of {} pages",
+                self.minimum(),
+                other.minimum(),
+            );
+        }
+        match (self.maximum(), other.maximum()) {
+            (None, Some(other_max)) => write!(
+                f,
+                "unbounded maximum exceeds the declared maximum of {other_max} pages \
+                (allowed via `Config::relaxed_import_limits`)",
+            ),
+            (Some(max), Some(other_max)) if max > other_max => write!(
+                f,
+                "maximum of {max} pages exceeds the declared maximum of {other_max} pages \
+                (allowed via `Config::relaxed_import_limits`)",
+            ),
+            _ => write!(f, "{self:?} is not a subtype of {other:?}"),
+        }
+    }
+}
+
+/// Returns `maximum_byte_size` bounded to the page aligned `host_max` byte size.
+///
+/// # Note
+///
+/// Growing a linear memory beyond the returned size fails gracefully.
+fn host_max_byte_size(maximum_byte_size: u64, host_max: u64, bytes_per_page: u64) -> u64 {
+    let host_max = host_max - (host_max % bytes_per_page);
+    maximum_byte_size.min(host_max)
 }
 
 /// A linear memory entity.
@@ -261,9 +334,20 @@ pub struct MemoryEntity {
     memory_type: MemoryType,
     /// Current size of the linear memory in pages.
     size: u32,
+    /// The optional soft limit of the linear memory in pages.
+    watermark: Option<u32>,
+    /// The pages of the linear memory that changed since they were last cleared.
+    dirty: DirtyPages,
 }
 
 impl MemoryEntity {
+    /// Makes the memory entity zero its bytes before they are freed.
+    ///
+    /// Read more about zeroing in [`Config::zero_on_free`](crate::Config::zero_on_free).
+    pub fn set_zero_on_free(&mut self) {
+        self.bytes.set_zero_on_free();
+    }
+
     /// Creates a new memory entity with the given memory type.
     pub fn new(
         memory_type: MemoryType,
@@ -309,9 +393,11 @@ impl MemoryEntity {
         let Ok(minimum_byte_size) = usize::try_from(minimum_byte_size64) else {
             return Err(MemoryError::InvalidMemoryType);
         };
-        let Ok(maximum_byte_size) = maximum_byte_size64.map(usize::try_from).transpose() else {
-            return Err(MemoryError::InvalidMemoryType);
-        };
+        // Hosts with a narrow address space (e.g. 32-bit) cannot represent the full
+        // maximum size of some memory types, so we bound the maximum to the host instead.
+        let maximum_byte_size = maximum_byte_size64
+            .map(|max| host_max_byte_size(max, usize::MAX as u64, bytes_per_page64))
+            .map(|max| max as usize);
 
         if let Some(limiter) = limiter.as_resource_limiter() {
             if !limiter.memory_growing(0, minimum_byte_size, maximum_byte_size)? {
@@ -332,6 +418,8 @@ impl MemoryEntity {
             bytes,
             memory_type,
             size: minimum_pages,
+            watermark: None,
+            dirty: DirtyPages::default(),
         })
     }
 
@@ -363,6 +451,44 @@ impl MemoryEntity {
         self.size
     }
 
+    /// Returns the watermark of this Wasm linear memory in pages if any.
+    pub fn watermark(&self) -> Option<u32> {
+        self.watermark
+    }
+
+    /// Sets the watermark of this Wasm linear memory in pages.
+    pub fn set_watermark(&mut self, watermark: Option<u32>) {
+        self.watermark = watermark;
+    }
+
+    /// Returns the indices of all pages that changed since the last [`MemoryEntity::clear_dirty_pages`].
+    ///
+    /// All pages are dirty if the dirty pages have never been cleared.
+    pub fn dirty_pages(&self) -> Vec<u32> {
+        self.dirty.collect(self.size)
+    }
+
+    /// Marks all pages of this Wasm linear memory as clean.
+    pub fn clear_dirty_pages(&mut self) {
+        self.dirty.clear(self.size);
+    }
+
+    /// Marks all pages overlapping with the `len` bytes at `offset` as dirty.
+    pub fn mark_dirty(&mut self, offset: usize, len: usize) {
+        let page_size_log2 = self.memory_type.page_size_log2();
+        self.dirty.mark(page_size_log2, offset, len);
+    }
+
+    /// Returns an exclusive slice to the dirty page bitmap if dirty pages are tracked.
+    ///
+    /// # Note
+    ///
+    /// The bitmap tracks one bit per page and is reallocated whenever the dirty
+    /// pages are cleared or the linear memory is grown.
+    pub fn dirty_bits_mut(&mut self) -> Option<&mut [u64]> {
+        self.dirty.bits_mut()
+    }
+
     /// Returns the size of this Wasm linear memory in bytes.
     fn size_in_bytes(&self) -> u32 {
         let pages = self.size();
@@ -377,16 +503,17 @@ impl MemoryEntity {
     }
 
     /// Returns the maximum size of this Wasm linear memory in bytes if any.
-    fn max_size_in_bytes(&self) -> Option<u32> {
-        let max_pages = self.memory_type.maximum()?;
-        let bytes_per_page = self.memory_type.page_size();
-        let Some(max_bytes) = max_pages.checked_mul(bytes_per_page) else {
-            panic!(
-                "unexpected out of bounds linear memory maximum size: \
-                (max_pages = {max_pages}, bytes_per_page = {bytes_per_page})"
-            )
-        };
-        Some(max_bytes)
+    ///
+    /// The maximum size is bounded to the address space of the host.
+    fn max_size_in_bytes(&self) -> Option<usize> {
+        let max_pages = u64::from(self.memory_type.maximum()?);
+        let bytes_per_page = u64::from(self.memory_type.page_size());
+        let max_bytes = host_max_byte_size(
+            max_pages * bytes_per_page,
+            usize::MAX as u64,
+            bytes_per_page,
+        );
+        Some(max_bytes as usize)
     }
 
     /// Grows the linear memory by the given amount of new pages.
@@ -417,7 +544,7 @@ impl MemoryEntity {
             return Ok(self.size());
         }
         let current_byte_size = self.size_in_bytes() as usize;
-        let maximum_byte_size = self.max_size_in_bytes().map(|max| max as usize);
+        let maximum_byte_size = self.max_size_in_bytes();
         let current_size = self.size();
         let Some(desired_size) = current_size.checked_add(additional) else {
             return Err(EntityGrowError::InvalidGrow);
@@ -452,7 +579,9 @@ impl MemoryEntity {
                 .checked_mul(u64::from(bytes_per_page))
                 .expect("additional size is within [min, max) page bounds");
             if fuel
-                .consume_fuel_if(|costs| costs.fuel_for_bytes(additional_bytes))
+                .consume_fuel_if(|costs| {
+                    costs.fuel_for_memory_grow(u64::from(additional), additional_bytes)
+                })
                 .is_err()
             {
                 return notify_limiter(limiter, EntityGrowError::TrapCode(TrapCode::OutOfFuel));
@@ -469,6 +598,7 @@ impl MemoryEntity {
             return notify_limiter(limiter, EntityGrowError::InvalidGrow);
         }
         self.size = desired_size;
+        self.dirty.grow(current_size, desired_size);
         Ok(current_size)
     }
 
@@ -478,10 +608,64 @@ impl MemoryEntity {
     }
 
     /// Returns an exclusive slice to the bytes underlying to the byte buffer.
+    ///
+    /// # Note
+    ///
+    /// This marks all pages as dirty since writes to the returned slice cannot be tracked.
     pub fn data_mut(&mut self) -> &mut [u8] {
+        self.dirty.mark_all();
+        self.bytes.data_mut()
+    }
+
+    /// Returns an exclusive slice to the bytes underlying to the byte buffer.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`MemoryEntity::data_mut`] this does not mark any pages as dirty.
+    /// Callers must mark the pages they write via [`MemoryEntity::mark_dirty`].
+    pub fn data_mut_untracked(&mut self) -> &mut [u8] {
         self.bytes.data_mut()
     }
 
+    /// Reserves the resources to [`MemoryEntity::restore`] the linear memory to `len` bytes.
+    ///
+    /// The size and contents of the linear memory are unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the linear memory cannot be resized to `len` bytes.
+    pub fn reserve_restore(&mut self, len: usize) -> Result<(), MemoryError> {
+        let bytes_per_page = self.memory_type.page_size() as usize;
+        if len % bytes_per_page != 0 || u32::try_from(len / bytes_per_page).is_err() {
+            return Err(MemoryError::OutOfBoundsGrowth);
+        }
+        self.bytes.reserve(len)
+    }
+
+    /// Restores the size and contents of the linear memory to `data`.
+    ///
+    /// # Note
+    ///
+    /// This bypasses the memory limits and the resource limiter since
+    /// `data` is expected to be a previous state of the linear memory.
+    ///
+    /// # Panics
+    ///
+    /// If the resources to restore `data` have not been reserved via
+    /// [`MemoryEntity::reserve_restore`] before.
+    pub fn restore(&mut self, data: &[u8]) {
+        let size = (data.len() / self.memory_type.page_size() as usize) as u32;
+        match data.len() >= self.bytes.len() {
+            true => self.bytes.grow(data.len()).unwrap_or_else(|error| {
+                panic!("failed to grow linear memory with reserved capacity: {error}")
+            }),
+            false => self.bytes.truncate(data.len()),
+        }
+        self.bytes.data_mut().copy_from_slice(data);
+        self.size = size;
+        self.dirty.reset(size);
+    }
+
     /// Returns the base pointer, in the host’s address space, that the [`Memory`] is located at.
     pub fn data_ptr(&self) -> *mut u8 {
         self.bytes.ptr
@@ -504,7 +688,8 @@ impl MemoryEntity {
         let len_buffer = buffer.len();
         let slice = self
             .data()
-            .get(offset..(offset + len_buffer))
+            .get(offset..)
+            .and_then(|memory| memory.get(..len_buffer))
             .ok_or(MemoryError::OutOfBoundsAccess)?;
         buffer.copy_from_slice(slice);
         Ok(())
@@ -516,13 +701,17 @@ impl MemoryEntity {
     /// # Errors
     ///
     /// If this operation accesses out of bounds linear memory.
+    /// In this case nothing is written to the linear memory.
     pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
         let len_buffer = buffer.len();
         let slice = self
+            .bytes
             .data_mut()
-            .get_mut(offset..(offset + len_buffer))
+            .get_mut(offset..)
+            .and_then(|memory| memory.get_mut(..len_buffer))
             .ok_or(MemoryError::OutOfBoundsAccess)?;
         slice.copy_from_slice(buffer);
+        self.mark_dirty(offset, len_buffer);
         Ok(())
     }
 }
@@ -580,6 +769,20 @@ impl Memory {
         Ok(memory)
     }
 
+    /// Returns the stable ID of the [`Memory`] within its [`Store`](crate::Store).
+    ///
+    /// # Note
+    ///
+    /// IDs are assigned in the order of allocation, starting at 0, and are never reused.
+    /// They are deterministic for the same sequence of operations on a [`Store`](crate::Store).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn id(&self, ctx: impl AsContext) -> u32 {
+        ctx.as_context().store.inner.entity_id(self.as_inner())
+    }
+
     /// Returns the memory type of the linear memory.
     ///
     /// # Panics
@@ -616,6 +819,43 @@ impl Memory {
         ctx.as_context().store.inner.resolve_memory(self).size()
     }
 
+    /// Returns the watermark of the [`Memory`] in pages if any.
+    ///
+    /// Read more about watermarks in [`Memory::set_watermark`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn watermark(&self, ctx: impl AsContext) -> Option<u32> {
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .watermark()
+    }
+
+    /// Sets the watermark of the [`Memory`] in pages.
+    ///
+    /// A watermark is a soft limit that is distinct from the maximum of the [`MemoryType`].
+    /// Whenever a Wasm `memory.grow` operation grows the [`Memory`] beyond its watermark
+    /// the callback set by [`Store::memory_watermark_hook`] decides whether the growth
+    /// continues, fails gracefully or traps. This allows for "warn and continue" policies.
+    ///
+    /// A watermark of `None` removes the watermark of the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`Store::memory_watermark_hook`]: crate::Store::memory_watermark_hook
+    pub fn set_watermark(&self, mut ctx: impl AsContextMut, watermark: Option<u32>) {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_memory_mut(self)
+            .set_watermark(watermark)
+    }
+
     /// Grows the linear memory by the given amount of new pages.
     ///
     /// Returns the amount of pages before the operation upon success.
@@ -650,6 +890,8 @@ impl Memory {
 
     /// Returns an exclusive slice to the bytes underlying the [`Memory`].
     ///
+    /// This marks all pages of the [`Memory`] as dirty, see [`Memory::dirty_pages`].
+    ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
@@ -660,6 +902,8 @@ impl Memory {
     /// Returns an exclusive slice to the bytes underlying the [`Memory`], and an exclusive
     /// reference to the user provided state.
     ///
+    /// This marks all pages of the [`Memory`] as dirty, see [`Memory::dirty_pages`].
+    ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
@@ -724,6 +968,7 @@ impl Memory {
     /// # Errors
     ///
     /// If this operation accesses out of bounds linear memory.
+    /// In this case nothing is written to the linear memory.
     ///
     /// # Panics
     ///
@@ -0,0 +1,137 @@
+use super::Memory;
+use crate::store::StoreInner;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::ops::Range;
+
+/// A tagged region of a [`Memory`] whose writes are reported.
+#[derive(Debug)]
+struct TaggedRegion {
+    /// The [`Memory`] of the region.
+    memory: Memory,
+    /// The address of the first byte of the region.
+    start: usize,
+    /// The tag of the region.
+    tag: Arc<str>,
+    /// The contents of the region when it was last checked.
+    ///
+    /// Bytes beyond the size of the [`Memory`] are zero.
+    contents: Box<[u8]>,
+}
+
+/// The tagged regions of all memories of a [`Store`](crate::Store).
+#[derive(Debug, Default)]
+pub struct MemoryTags {
+    /// The tagged regions in the order of their tagging.
+    regions: Vec<TaggedRegion>,
+}
+
+impl MemoryTags {
+    /// Returns `true` if no region is tagged.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Tags the `range` of `memory` in `store` with `tag`.
+    pub fn insert(&mut self, store: &StoreInner, memory: Memory, range: Range<usize>, tag: &str) {
+        let mut contents = zeroed(range.len());
+        copy_available(
+            &mut contents,
+            range.start,
+            store.resolve_memory(&memory).data(),
+        );
+        self.regions.push(TaggedRegion {
+            memory,
+            start: range.start,
+            tag: tag.into(),
+            contents,
+        });
+    }
+
+    /// Removes all regions tagged with `tag`.
+    ///
+    /// Returns `true` if any region has been removed.
+    pub fn remove(&mut self, tag: &str) -> bool {
+        let len = self.regions.len();
+        self.regions.retain(|region| &*region.tag != tag);
+        self.regions.len() != len
+    }
+
+    /// Returns an iterator over the tagged regions as `(memory, range, tag)`.
+    pub fn iter(&self) -> impl Iterator<Item = (Memory, Range<usize>, &str)> + '_ {
+        self.regions.iter().map(|region| {
+            let range = region.start..region.start + region.contents.len();
+            (region.memory, range, &*region.tag)
+        })
+    }
+
+    /// Returns the writes to all tagged regions of memories in `store` since they were last checked.
+    pub fn take_writes(&mut self, store: &StoreInner) -> Vec<MemoryRegionWrite> {
+        let mut writes = Vec::new();
+        for region in &mut self.regions {
+            let mut current = zeroed(region.contents.len());
+            copy_available(
+                &mut current,
+                region.start,
+                store.resolve_memory(&region.memory).data(),
+            );
+            let changed = |(n, (lhs, rhs)): (usize, (&u8, &u8))| (lhs != rhs).then_some(n);
+            let pairs = || current.iter().zip(region.contents.iter()).enumerate();
+            let Some(first) = pairs().find_map(changed) else {
+                continue;
+            };
+            let last = pairs().rev().find_map(changed).unwrap_or(first);
+            writes.push(MemoryRegionWrite {
+                memory: region.memory,
+                tag: region.tag.clone(),
+                range: region.start + first..region.start + last + 1,
+            });
+            region.contents = current;
+        }
+        writes
+    }
+}
+
+/// Returns a zeroed boxed slice of `len` bytes.
+fn zeroed(len: usize) -> Box<[u8]> {
+    alloc::vec![0; len].into_boxed_slice()
+}
+
+/// Copies the bytes of `data` starting at `start` into `dst` as far as they are available.
+fn copy_available(dst: &mut [u8], start: usize, data: &[u8]) {
+    let available = data.get(start..).unwrap_or_default();
+    let len = dst.len().min(available.len());
+    dst[..len].copy_from_slice(&available[..len]);
+}
+
+/// Argument to the callback set by [`Store::memory_write_hook`] describing
+/// a write to a region tagged via [`Store::tag_memory_region`].
+///
+/// [`Store::memory_write_hook`]: crate::Store::memory_write_hook
+/// [`Store::tag_memory_region`]: crate::Store::tag_memory_region
+#[derive(Debug, Clone)]
+pub struct MemoryRegionWrite {
+    /// The written [`Memory`].
+    memory: Memory,
+    /// The tag of the written region.
+    tag: Arc<str>,
+    /// The address range spanning all changed bytes of the region.
+    range: Range<usize>,
+}
+
+impl MemoryRegionWrite {
+    /// Returns the written [`Memory`].
+    pub fn memory(&self) -> Memory {
+        self.memory
+    }
+
+    /// Returns the tag of the written region.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns the address range spanning all changed bytes of the region.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
@@ -14,3 +14,14 @@ fn subtyping_works() {
     assert!(memory_type(0, 1).is_subtype_of(&memory_type(0, None)));
     assert!(!memory_type(0, None).is_subtype_of(&memory_type(0, 1)));
 }
+
+#[test]
+fn host_max_byte_size_works() {
+    let page = 1 << 16;
+    let host32 = u64::from(u32::MAX);
+    let max32 = u64::from(u32::MAX) + 1;
+    assert_eq!(host_max_byte_size(page, host32, page), page);
+    assert_eq!(host_max_byte_size(max32, host32, page), max32 - page);
+    assert_eq!(host_max_byte_size(max32, host32, 1), host32);
+    assert_eq!(host_max_byte_size(max32, u64::MAX, page), max32);
+}
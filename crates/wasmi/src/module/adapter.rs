@@ -0,0 +1,92 @@
+use super::ImportName;
+use alloc::{boxed::Box, collections::BTreeMap};
+
+/// Renames imports and exports of a [`Module`] while it is parsed.
+///
+/// This allows old guest binaries to run against evolved host APIs without
+/// binary rewriting tools, e.g. by mapping the import `env.foo` to `host.foo_v2`.
+///
+/// Use [`Module::new_with_adapter`] to apply a [`ModuleAdapter`].
+///
+/// # Note
+///
+/// - Exact import renames set via [`ModuleAdapter::rename_import`] take precedence
+///   over namespace renames set via [`ModuleAdapter::rename_namespace`].
+/// - Renames are applied once and are not chained.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::ModuleAdapter;
+/// let adapter = ModuleAdapter::new()
+///     .rename_import("env", "foo", "host", "foo_v2")
+///     .rename_namespace("env", "host")
+///     .rename_export("run", "_start");
+/// ```
+///
+/// [`Module`]: crate::Module
+/// [`Module::new_with_adapter`]: crate::Module::new_with_adapter
+#[derive(Debug, Default, Clone)]
+pub struct ModuleAdapter {
+    /// Exact renames of imports given their module and item names.
+    imports: BTreeMap<Box<str>, BTreeMap<Box<str>, ImportName>>,
+    /// Renames of import namespaces.
+    namespaces: BTreeMap<Box<str>, Box<str>>,
+    /// Renames of exports.
+    exports: BTreeMap<Box<str>, Box<str>>,
+}
+
+impl ModuleAdapter {
+    /// Creates a new [`ModuleAdapter`] that does not rename anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames the import `module.name` to `new_module.new_name`.
+    pub fn rename_import(
+        mut self,
+        module: &str,
+        name: &str,
+        new_module: &str,
+        new_name: &str,
+    ) -> Self {
+        self.imports
+            .entry(module.into())
+            .or_default()
+            .insert(name.into(), ImportName::new(new_module, new_name));
+        self
+    }
+
+    /// Renames the namespace of all imports from `module` to `new_module`.
+    pub fn rename_namespace(mut self, module: &str, new_module: &str) -> Self {
+        self.namespaces.insert(module.into(), new_module.into());
+        self
+    }
+
+    /// Renames the export `name` to `new_name`.
+    pub fn rename_export(mut self, name: &str, new_name: &str) -> Self {
+        self.exports.insert(name.into(), new_name.into());
+        self
+    }
+
+    /// Returns `true` if the [`ModuleAdapter`] does not rename anything.
+    pub fn is_empty(&self) -> bool {
+        self.imports.is_empty() && self.namespaces.is_empty() && self.exports.is_empty()
+    }
+
+    /// Returns the adapted [`ImportName`] for the import `module.name`.
+    pub(crate) fn adapt_import(&self, module: &str, name: &str) -> ImportName {
+        if let Some(renamed) = self.imports.get(module).and_then(|names| names.get(name)) {
+            return renamed.clone();
+        }
+        match self.namespaces.get(module) {
+            Some(new_module) => ImportName::new(new_module, name),
+            None => ImportName::new(module, name),
+        }
+    }
+
+    /// Returns the adapted name for the export `name`.
+    pub(crate) fn adapt_export<'a>(&'a self, name: &'a str) -> &'a str {
+        self.exports.get(name).map(Box::as_ref).unwrap_or(name)
+    }
+}
@@ -0,0 +1,98 @@
+use super::{FuncIdx, Module, ModuleHeader};
+use crate::collections::Map;
+use alloc::{boxed::Box, vec::Vec};
+use wasmparser::BranchHintSectionReader;
+
+/// A hint of the Wasm [`branch-hinting`] proposal for a conditional branch of a function.
+///
+/// [`branch-hinting`]: https://github.com/WebAssembly/branch-hinting
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BranchHint {
+    /// The byte offset of the hinted instruction relative to the start of its function body.
+    offset: u32,
+    /// Is `true` if the hinted branch is likely taken.
+    likely_taken: bool,
+}
+
+impl BranchHint {
+    /// Returns the byte offset of the hinted instruction relative to the start of its function body.
+    ///
+    /// The function body starts with the declaration of its local variables.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns `true` if the hinted branch is likely taken.
+    pub fn is_likely_taken(&self) -> bool {
+        self.likely_taken
+    }
+}
+
+/// The branch hints of the functions of a Wasm module.
+#[derive(Debug, Default)]
+pub struct BranchHints {
+    /// The branch hints sorted by offset for every hinted function.
+    funcs: Map<u32, Box<[BranchHint]>>,
+}
+
+impl BranchHints {
+    /// Parses the [`BranchHints`] from the Wasm `metadata.code.branch_hint` custom `section`.
+    ///
+    /// Returns `None` if the `section` is malformed.
+    pub fn from_section(section: BranchHintSectionReader) -> Option<Self> {
+        let mut funcs = Map::new();
+        for func in section {
+            let func = func.ok()?;
+            let mut hints = func
+                .hints
+                .into_iter()
+                .map(|hint| {
+                    hint.map(|hint| BranchHint {
+                        offset: hint.func_offset,
+                        likely_taken: hint.taken,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?;
+            hints.sort_by_key(BranchHint::offset);
+            funcs.insert(func.func, hints.into());
+        }
+        Some(Self { funcs })
+    }
+
+    /// Returns the branch hints of the function at `func_index` sorted by offset.
+    pub fn get(&self, func_index: u32) -> &[BranchHint] {
+        self.funcs.get(&func_index).map_or(&[], |hints| &hints[..])
+    }
+}
+
+impl ModuleHeader {
+    /// Returns the [`BranchHint`] of the instruction at `offset` of the function at `func_idx` if any.
+    pub fn get_branch_hint(&self, func_idx: FuncIdx, offset: u32) -> Option<BranchHint> {
+        let hints = self.inner.branch_hints.get(func_idx.into_u32());
+        let index = hints
+            .binary_search_by_key(&offset, BranchHint::offset)
+            .ok()?;
+        Some(hints[index])
+    }
+}
+
+impl Module {
+    /// Returns the branch hints of the function at `func_index` sorted by their offsets.
+    ///
+    /// The `func_index` refers to the Wasm function index space which starts with the
+    /// imported functions. Returns an empty slice if the function has no branch hints.
+    ///
+    /// # Note
+    ///
+    /// Branch hints are parsed from the `metadata.code.branch_hint` custom section
+    /// as defined by the Wasm [`branch-hinting`] proposal. Hints are ignored if the
+    /// custom section is malformed, does not precede the Wasm code section or if
+    /// [`Config::ignore_custom_sections`] is enabled.
+    ///
+    /// [`branch-hinting`]: https://github.com/WebAssembly/branch-hinting
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    pub fn branch_hints(&self, func_index: u32) -> &[BranchHint] {
+        self.inner.header.inner.branch_hints.get(func_index)
+    }
+}
@@ -2,6 +2,7 @@ use super::{
     data::DataSegmentsBuilder,
     export::ExternIdx,
     import::FuncTypeIdx,
+    BranchHints,
     ConstExpr,
     CustomSectionsBuilder,
     DataSegments,
@@ -13,14 +14,16 @@ use super::{
     ImportName,
     Imported,
     Module,
+    ModuleFeatures,
     ModuleHeader,
     ModuleHeaderInner,
     ModuleImports,
     ModuleInner,
+    ModuleSignature,
 };
 use crate::{
     collections::Map,
-    engine::{DedupFuncType, EngineFuncSpan},
+    engine::{DedupFuncType, EngineFuncSpan, ExtensionOps},
     Engine,
     Error,
     FuncType,
@@ -28,7 +31,7 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
 
 /// A builder for a WebAssembly [`Module`].
 #[derive(Debug)]
@@ -53,6 +56,7 @@ pub struct ModuleHeaderBuilder {
     pub start: Option<FuncIdx>,
     pub engine_funcs: EngineFuncSpan,
     pub element_segments: Box<[ElementSegment]>,
+    pub branch_hints: BranchHints,
 }
 
 impl ModuleHeaderBuilder {
@@ -71,15 +75,18 @@ impl ModuleHeaderBuilder {
             start: None,
             engine_funcs: EngineFuncSpan::default(),
             element_segments: Box::from([]),
+            branch_hints: BranchHints::default(),
         }
     }
 
     /// Finishes construction of [`ModuleHeader`].
     pub fn finish(self) -> ModuleHeader {
+        let extension_ops = self.extension_ops();
         ModuleHeader {
             inner: Arc::new(ModuleHeaderInner {
                 engine: self.engine.weak(),
                 func_types: self.func_types.into(),
+                extension_ops,
                 imports: self.imports.finish(),
                 funcs: self.funcs.into(),
                 tables: self.tables.into(),
@@ -90,11 +97,32 @@ impl ModuleHeaderBuilder {
                 start: self.start,
                 engine_funcs: self.engine_funcs,
                 element_segments: self.element_segments,
+                branch_hints: self.branch_hints,
             }),
         }
     }
 }
 
+impl ModuleHeaderBuilder {
+    /// Resolves the imported functions to extension instructions of the [`Engine`].
+    fn extension_ops(&self) -> ExtensionOps {
+        let extensions = self.engine.extensions();
+        if extensions.is_empty() {
+            return ExtensionOps::default();
+        }
+        self.imports
+            .funcs
+            .iter()
+            .zip(&self.funcs)
+            .map(|(name, func_type)| {
+                self.engine.resolve_func_type(func_type, |func_type| {
+                    extensions.resolve(name.module(), name.name(), func_type)
+                })
+            })
+            .collect()
+    }
+}
+
 /// The import names of the [`Module`] imports.
 #[derive(Debug, Default)]
 pub struct ModuleImportsBuilder {
@@ -344,7 +372,8 @@ impl ModuleHeaderBuilder {
     ///
     /// # Errors
     ///
-    /// If an export declaration fails to validate.
+    /// - If an export declaration fails to validate.
+    /// - If there are duplicate export names.
     ///
     /// # Panics
     ///
@@ -357,7 +386,17 @@ impl ModuleHeaderBuilder {
             self.exports.is_empty(),
             "tried to initialize module export declarations twice"
         );
-        self.exports = exports.into_iter().collect::<Result<Map<_, _>, _>>()?;
+        for export in exports {
+            let (name, idx) = export?;
+            if self.exports.contains_key(&name) {
+                // Note: this can only happen if exports have been renamed
+                //       since Wasm validation rejects duplicate export names.
+                return Err(Error::new(format!(
+                    "encountered duplicate export name: {name}"
+                )));
+            }
+            self.exports.insert(name, idx);
+        }
         Ok(())
     }
 
@@ -407,14 +446,29 @@ impl ModuleBuilder {
         self.data_segments.push_data_segment(data)
     }
 
-    /// Finishes construction of the WebAssembly [`Module`].
-    pub fn finish(self, engine: &Engine) -> Module {
+    /// Finishes construction of the WebAssembly [`Module`] with the `hash` of its Wasm bytecode.
+    ///
+    /// The verified `signature` of the Wasm bytecode is attached to the [`Module`] if any.
+    pub fn finish(
+        self,
+        engine: &Engine,
+        mut features: ModuleFeatures,
+        hash: u64,
+        signature: Option<ModuleSignature>,
+    ) -> Module {
+        let data_segments = self.data_segments.finish();
+        features.visit_module(&self.header, &data_segments);
+        let header = &self.header.inner;
+        engine.register_module_hash(header.engine_funcs, header.imports.len_funcs as u32, hash);
         Module {
             inner: Arc::new(ModuleInner {
                 engine: engine.clone(),
+                hash,
+                signature,
                 header: self.header,
-                data_segments: self.data_segments.finish(),
+                data_segments,
                 custom_sections: self.custom_sections.finish(),
+                features,
             }),
         }
     }
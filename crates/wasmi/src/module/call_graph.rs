@@ -0,0 +1,213 @@
+use super::{FuncTypeIdx, Module};
+use crate::{engine::EngineFunc, ir::Instruction, Error, FuncType};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+impl Module {
+    /// Returns the [`CallGraph`] of the [`Module`] derived from its translated Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// - Functions are identified by their Wasm function index which includes imported functions.
+    /// - Lazily compiled functions of the [`Module`] are compiled if they have not been compiled yet.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of a lazily compiled function fails.
+    pub fn call_graph(&self) -> Result<CallGraph, Error> {
+        let header = &self.inner.header;
+        let engine = self.engine();
+        let len_imported = header.inner.imports.len_funcs() as u32;
+        let mut edges = Vec::new();
+        let mut histograms = Vec::new();
+        for (n, func) in header.inner.engine_funcs.iter().enumerate() {
+            let caller = len_imported + n as u32;
+            let histogram = engine.resolve_instrs(func, |instrs| {
+                let mut histogram = InstrHistogram::default();
+                for instr in instrs {
+                    histogram.push(instr);
+                    let (target, is_tail_call) = match *instr {
+                        Instruction::CallInternal0 { func, .. }
+                        | Instruction::CallInternal { func, .. } => (internal(self, func), false),
+                        Instruction::ReturnCallInternal0 { func }
+                        | Instruction::ReturnCallInternal { func } => (internal(self, func), true),
+                        Instruction::CallImported0 { func, .. }
+                        | Instruction::CallImported { func, .. } => {
+                            (CallTarget::Func(u32::from(func)), false)
+                        }
+                        Instruction::ReturnCallImported0 { func }
+                        | Instruction::ReturnCallImported { func } => {
+                            (CallTarget::Func(u32::from(func)), true)
+                        }
+                        Instruction::CallIndirect0 { func_type, .. }
+                        | Instruction::CallIndirect0Imm16 { func_type, .. }
+                        | Instruction::CallIndirect { func_type, .. }
+                        | Instruction::CallIndirectImm16 { func_type, .. } => {
+                            (indirect(self, func_type), false)
+                        }
+                        Instruction::ReturnCallIndirect0 { func_type }
+                        | Instruction::ReturnCallIndirect0Imm16 { func_type }
+                        | Instruction::ReturnCallIndirect { func_type }
+                        | Instruction::ReturnCallIndirectImm16 { func_type } => {
+                            (indirect(self, func_type), true)
+                        }
+                        _ => continue,
+                    };
+                    let edge = CallEdge {
+                        caller,
+                        target,
+                        is_tail_call,
+                    };
+                    if !edges.contains(&edge) {
+                        edges.push(edge);
+                    }
+                }
+                histogram
+            })?;
+            histograms.push(histogram);
+        }
+        Ok(CallGraph {
+            len_imported,
+            edges,
+            histograms: histograms.into(),
+        })
+    }
+}
+
+/// Returns the [`CallTarget`] of the internal function `func` of `module`.
+fn internal(module: &Module, func: crate::ir::index::InternalFunc) -> CallTarget {
+    let func = module
+        .inner
+        .header
+        .get_func_index(EngineFunc::from(func))
+        .unwrap_or_else(|| panic!("missing function index for internal function: {func:?}"));
+    CallTarget::Func(func.into_u32())
+}
+
+/// Returns the [`CallTarget`] of an indirect call with function type `func_type` in `module`.
+fn indirect(module: &Module, func_type: crate::ir::index::FuncType) -> CallTarget {
+    let func_type = module
+        .inner
+        .header
+        .get_func_type(FuncTypeIdx::from(u32::from(func_type)));
+    let func_type = module
+        .engine()
+        .resolve_func_type(func_type, FuncType::clone);
+    CallTarget::Indirect(func_type)
+}
+
+/// The caller to callee edges and instruction histograms of a [`Module`].
+///
+/// Returned by [`Module::call_graph`].
+#[derive(Debug, Clone)]
+pub struct CallGraph {
+    /// The number of imported functions of the [`Module`].
+    len_imported: u32,
+    /// The unique caller to callee edges in the order of their first appearance.
+    edges: Vec<CallEdge>,
+    /// The instruction histograms of all internal functions.
+    histograms: Box<[InstrHistogram]>,
+}
+
+impl CallGraph {
+    /// Returns all unique caller to callee edges of the [`Module`].
+    pub fn edges(&self) -> &[CallEdge] {
+        &self.edges
+    }
+
+    /// Returns an iterator over the [`CallTarget`]s called by the function at `caller`.
+    pub fn callees(&self, caller: u32) -> impl Iterator<Item = &CallTarget> + '_ {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.caller == caller)
+            .map(CallEdge::target)
+    }
+
+    /// Returns an iterator over the function types indirectly called by the function at `caller`.
+    pub fn indirect_call_types(&self, caller: u32) -> impl Iterator<Item = &FuncType> + '_ {
+        let mut types: Vec<&FuncType> = Vec::new();
+        for target in self.callees(caller) {
+            if let CallTarget::Indirect(func_type) = target {
+                if !types.contains(&func_type) {
+                    types.push(func_type);
+                }
+            }
+        }
+        types.into_iter()
+    }
+
+    /// Returns the [`InstrHistogram`] of the function at `func`.
+    ///
+    /// Returns `None` if `func` is imported or out of bounds.
+    pub fn histogram(&self, func: u32) -> Option<&InstrHistogram> {
+        let index = func.checked_sub(self.len_imported)?;
+        self.histograms.get(index as usize)
+    }
+}
+
+/// A caller to callee edge of a [`CallGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallEdge {
+    /// The function index of the caller.
+    caller: u32,
+    /// The callee.
+    target: CallTarget,
+    /// Is `true` if the call is a tail call.
+    is_tail_call: bool,
+}
+
+impl CallEdge {
+    /// Returns the function index of the caller.
+    pub fn caller(&self) -> u32 {
+        self.caller
+    }
+
+    /// Returns the [`CallTarget`] of the call.
+    pub fn target(&self) -> &CallTarget {
+        &self.target
+    }
+
+    /// Returns `true` if the call is a tail call.
+    pub fn is_tail_call(&self) -> bool {
+        self.is_tail_call
+    }
+}
+
+/// The callee of a [`CallEdge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallTarget {
+    /// A direct call to the function at the function index.
+    Func(u32),
+    /// An indirect call to any function of the [`FuncType`] via a table.
+    Indirect(FuncType),
+}
+
+/// The number of Wasmi bytecode instruction words of a function by their name.
+///
+/// Returned by [`CallGraph::histogram`].
+#[derive(Debug, Default, Clone)]
+pub struct InstrHistogram {
+    /// The number of instruction words by name.
+    counts: BTreeMap<&'static str, u32>,
+}
+
+impl InstrHistogram {
+    /// Counts the instruction word `instr`.
+    fn push(&mut self, instr: &Instruction) {
+        *self.counts.entry(instr.name()).or_default() += 1;
+    }
+
+    /// Returns the number of instruction words with `name`, e.g. `"I32Add"`.
+    pub fn get(&self, name: &str) -> u32 {
+        self.counts.get(name).copied().unwrap_or_default()
+    }
+
+    /// Returns the total number of instruction words.
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// Returns an iterator over the names and counts of all instruction words sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u32)> + '_ {
+        self.counts.iter().map(|(name, count)| (*name, *count))
+    }
+}
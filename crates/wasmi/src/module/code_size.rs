@@ -0,0 +1,59 @@
+use super::Module;
+use crate::{engine::translated_func_size, Error};
+
+impl Module {
+    /// Returns the size in bytes of the translated Wasmi bytecode of the function at `func_index`.
+    ///
+    /// The `func_index` refers to the Wasm function index space which starts with the
+    /// imported functions. Returns `None` if the function is imported or out of bounds.
+    ///
+    /// The size covers the Wasmi bytecode and the function local constants of the
+    /// function and is the size limited by [`Config::max_translated_func_size`].
+    ///
+    /// # Note
+    ///
+    /// Compiles the function if it has not been compiled yet.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of a lazily compiled function fails.
+    ///
+    /// [`Config::max_translated_func_size`]: crate::Config::max_translated_func_size
+    pub fn translated_func_size(&self, func_index: u32) -> Result<Option<usize>, Error> {
+        let header = &self.inner.header.inner;
+        let Some(position) = func_index.checked_sub(header.imports.len_funcs() as u32) else {
+            return Ok(None);
+        };
+        let Some(func) = header.engine_funcs.get(position) else {
+            return Ok(None);
+        };
+        self.engine()
+            .resolve_compiled(func, |instrs, consts, _len_registers| {
+                translated_func_size(instrs.len(), consts.len())
+            })
+            .map(Some)
+    }
+
+    /// Returns the total size in bytes of the translated Wasmi bytecode of all functions of the [`Module`].
+    ///
+    /// This is the sum of [`Module::translated_func_size`] over all internal functions.
+    ///
+    /// # Note
+    ///
+    /// Compiles all functions that have not been compiled yet.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of a lazily compiled function fails.
+    pub fn translated_size(&self) -> Result<usize, Error> {
+        let engine = self.engine();
+        let mut total = 0_usize;
+        for func in self.inner.header.inner.engine_funcs.iter() {
+            let size = engine.resolve_compiled(func, |instrs, consts, _len_registers| {
+                translated_func_size(instrs.len(), consts.len())
+            })?;
+            total = total.saturating_add(size);
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,227 @@
+use super::Module;
+use crate::{ExternType, MemoryType};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+impl Module {
+    /// Checks if `new` can replace `old` without breaking its host or its consumers.
+    ///
+    /// The returned [`CompatReport`] lists all [`CompatIssue`]s found between both modules:
+    ///
+    /// - Every import of `new` must be satisfied by the definitions provided to `old`.
+    /// - Every export of `old` must still be exported by `new` with a compatible type.
+    /// - `new` must define as many tables, linear memories and global variables as `old`
+    ///   and the limits of its linear memories must accommodate those of `old`.
+    ///
+    /// Removed imports and added exports are always compatible.
+    pub fn check_upgrade_compat(old: &Module, new: &Module) -> CompatReport {
+        let mut issues = Vec::new();
+        let old_imports: BTreeMap<(&str, &str), ExternType> = old
+            .imports()
+            .map(|import| ((import.module(), import.name()), import.ty().clone()))
+            .collect();
+        for import in new.imports() {
+            let (module, name) = (import.module(), import.name());
+            match old_imports.get(&(module, name)) {
+                None => issues.push(CompatIssue::AddedImport {
+                    module: module.into(),
+                    name: name.into(),
+                    ty: import.ty().clone(),
+                }),
+                Some(old_ty) if !is_subtype(old_ty, import.ty()) => {
+                    issues.push(CompatIssue::ImportTypeChanged {
+                        module: module.into(),
+                        name: name.into(),
+                        old: old_ty.clone(),
+                        new: import.ty().clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for export in old.exports() {
+            let name = export.name();
+            match new.get_export(name) {
+                None => issues.push(CompatIssue::RemovedExport {
+                    name: name.into(),
+                    ty: export.ty().clone(),
+                }),
+                Some(new_ty) if !is_subtype(&new_ty, export.ty()) => {
+                    issues.push(CompatIssue::ExportTypeChanged {
+                        name: name.into(),
+                        old: export.ty().clone(),
+                        new: new_ty,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        let mut check_len = |kind: &'static str, old: usize, new: usize| {
+            if old != new {
+                issues.push(CompatIssue::EntityCountChanged { kind, old, new });
+            }
+        };
+        check_len(
+            "tables",
+            old.internal_tables().len(),
+            new.internal_tables().len(),
+        );
+        check_len(
+            "memories",
+            old.internal_memories().len(),
+            new.internal_memories().len(),
+        );
+        check_len(
+            "globals",
+            old.internal_globals().count(),
+            new.internal_globals().count(),
+        );
+        for (index, (old, new)) in old
+            .internal_memories()
+            .zip(new.internal_memories())
+            .enumerate()
+        {
+            if !old.is_subtype_of(new) {
+                issues.push(CompatIssue::MemoryLimitsChanged {
+                    index: index as u32,
+                    old: *old,
+                    new: *new,
+                });
+            }
+        }
+        CompatReport { issues }
+    }
+}
+
+/// Returns `true` if a definition of type `ty` can be used where `of` is expected.
+fn is_subtype(ty: &ExternType, of: &ExternType) -> bool {
+    match (ty, of) {
+        (ExternType::Func(ty), ExternType::Func(of)) => ty == of,
+        (ExternType::Global(ty), ExternType::Global(of)) => ty == of,
+        (ExternType::Memory(ty), ExternType::Memory(of)) => ty.is_subtype_of(of),
+        (ExternType::Table(ty), ExternType::Table(of)) => ty.is_subtype_of(of),
+        _ => false,
+    }
+}
+
+/// An incompatibility between two [`Module`]s found by [`Module::check_upgrade_compat`].
+#[derive(Debug, Clone)]
+pub enum CompatIssue {
+    /// The new [`Module`] requires an import that the old [`Module`] did not.
+    AddedImport {
+        /// The module name of the import.
+        module: Box<str>,
+        /// The item name of the import.
+        name: Box<str>,
+        /// The type of the import.
+        ty: ExternType,
+    },
+    /// The new [`Module`] requires an import with a type that is not satisfied by the old import.
+    ImportTypeChanged {
+        /// The module name of the import.
+        module: Box<str>,
+        /// The item name of the import.
+        name: Box<str>,
+        /// The type of the import of the old [`Module`].
+        old: ExternType,
+        /// The type of the import of the new [`Module`].
+        new: ExternType,
+    },
+    /// The new [`Module`] no longer provides an export of the old [`Module`].
+    RemovedExport {
+        /// The name of the export.
+        name: Box<str>,
+        /// The type of the export of the old [`Module`].
+        ty: ExternType,
+    },
+    /// The new [`Module`] provides an export with a type that is incompatible to the old export.
+    ExportTypeChanged {
+        /// The name of the export.
+        name: Box<str>,
+        /// The type of the export of the old [`Module`].
+        old: ExternType,
+        /// The type of the export of the new [`Module`].
+        new: ExternType,
+    },
+    /// The new [`Module`] defines a different number of entities of `kind`.
+    EntityCountChanged {
+        /// The kind of the entities, e.g. `"memories"`.
+        kind: &'static str,
+        /// The number of entities defined by the old [`Module`].
+        old: usize,
+        /// The number of entities defined by the new [`Module`].
+        new: usize,
+    },
+    /// The limits of a linear memory of the new [`Module`] do not accommodate the old limits.
+    MemoryLimitsChanged {
+        /// The index of the linear memory among the defined linear memories.
+        index: u32,
+        /// The type of the linear memory of the old [`Module`].
+        old: MemoryType,
+        /// The type of the linear memory of the new [`Module`].
+        new: MemoryType,
+    },
+}
+
+impl fmt::Display for CompatIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AddedImport { module, name, ty } => {
+                write!(f, "added import {module}::{name} of type {ty:?}")
+            }
+            Self::ImportTypeChanged {
+                module,
+                name,
+                old,
+                new,
+            } => write!(
+                f,
+                "import {module}::{name} changed its type from {old:?} to {new:?}"
+            ),
+            Self::RemovedExport { name, ty } => {
+                write!(f, "removed export {name} of type {ty:?}")
+            }
+            Self::ExportTypeChanged { name, old, new } => {
+                write!(f, "export {name} changed its type from {old:?} to {new:?}")
+            }
+            Self::EntityCountChanged { kind, old, new } => {
+                write!(f, "number of defined {kind} changed from {old} to {new}")
+            }
+            Self::MemoryLimitsChanged { index, old, new } => write!(
+                f,
+                "limits of linear memory {index} changed from {old:?} to {new:?}"
+            ),
+        }
+    }
+}
+
+/// The compatibility verdict between two [`Module`]s as returned by [`Module::check_upgrade_compat`].
+#[derive(Debug, Default, Clone)]
+pub struct CompatReport {
+    /// The incompatibilities found.
+    issues: Vec<CompatIssue>,
+}
+
+impl CompatReport {
+    /// Returns `true` if the new [`Module`] can replace the old [`Module`].
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns an iterator over all [`CompatIssue`]s found.
+    pub fn issues(&self) -> impl ExactSizeIterator<Item = &CompatIssue> + '_ {
+        self.issues.iter()
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, issue) in self.issues.iter().enumerate() {
+            if n != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,666 @@
+use super::Module;
+use crate::Error;
+use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
+use core::fmt;
+use wasmparser::{
+    BlockType,
+    ContType,
+    ExternalKind,
+    FrameKind,
+    FuncType,
+    FunctionBody,
+    KnownCustom,
+    ModuleArity,
+    Name,
+    Operator,
+    Parser,
+    Payload,
+    RefType,
+    SubType,
+    TypeRef,
+};
+
+/// The name of the custom section that marks functions for [`Module::audit_constant_time`].
+pub const CONSTANT_TIME_SECTION: &str = "wasmi.constant_time";
+
+impl Module {
+    /// Audits the marked functions of the Wasm binary `wasm` for constant-time violations.
+    ///
+    /// A marked function violates constant-time guarantees if a secret value influences
+    /// its control flow, the addresses of its linear memory accesses or the indices of its
+    /// table accesses and indirect calls. This is useful to review cryptographic code that
+    /// is executed by Wasmi since those operations may leak secrets via timing side-channels.
+    ///
+    /// Functions are marked if their name is listed in `audit` via [`ConstantTimeAudit::function`]
+    /// or in a custom section named [`CONSTANT_TIME_SECTION`] whose UTF-8 contents list function
+    /// names separated by whitespace. Function names are resolved via exports and the `name`
+    /// custom section of `wasm`.
+    ///
+    /// # Note
+    ///
+    /// - The input `wasm` must be in binary form and is expected to be valid Wasm.
+    /// - This is a conservative static analysis: a value is secret if it is derived from a
+    ///   secret value in any way, e.g. via a local variable that is assigned a secret value
+    ///   anywhere in the function. Therefore reported violations might be false positives.
+    /// - The results of called functions are secret if any of their arguments is secret.
+    ///
+    /// # Errors
+    ///
+    /// - If `wasm` is malformed.
+    /// - If a marked function name cannot be resolved or refers to an imported function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{ConstantTimeAudit, Module};
+    /// let wasm = wat::parse_str(r#"
+    ///     (module
+    ///         (func (export "select") (param $secret i32) (param $a i32) (param $b i32) (result i32)
+    ///             (select (local.get $a) (local.get $b) (local.get $secret))
+    ///         )
+    ///         (func (export "branch") (param $secret i32) (result i32)
+    ///             (if (result i32) (local.get $secret)
+    ///                 (then (i32.const 1))
+    ///                 (else (i32.const 0))
+    ///             )
+    ///         )
+    ///     )
+    /// "#).unwrap();
+    /// let mut audit = ConstantTimeAudit::new();
+    /// audit.function("select").function("branch");
+    /// let report = Module::audit_constant_time(&wasm, &audit).unwrap();
+    /// assert_eq!(report.violations().len(), 1);
+    /// assert_eq!(report.violations()[0].func_name(), "branch");
+    /// ```
+    pub fn audit_constant_time(
+        wasm: &[u8],
+        audit: &ConstantTimeAudit,
+    ) -> Result<ConstantTimeReport, Error> {
+        let mut module = AuditedModule::default();
+        let mut marked: Vec<String> = audit.functions.clone();
+        let mut bodies = Vec::new();
+        for payload in Parser::new(0).parse_all(wasm) {
+            match payload? {
+                Payload::TypeSection(section) => {
+                    for rec_group in section {
+                        module.types.extend(rec_group?.into_types());
+                    }
+                }
+                Payload::ImportSection(section) => {
+                    for import in section {
+                        if let TypeRef::Func(type_index) = import?.ty {
+                            module.funcs.push(type_index);
+                            module.len_imported_funcs += 1;
+                        }
+                    }
+                }
+                Payload::FunctionSection(section) => {
+                    for type_index in section {
+                        module.funcs.push(type_index?);
+                    }
+                }
+                Payload::ExportSection(section) => {
+                    for export in section {
+                        let export = export?;
+                        if let ExternalKind::Func = export.kind {
+                            module.names.insert(export.name.into(), export.index);
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => bodies.push(body),
+                Payload::CustomSection(reader) if reader.name() == CONSTANT_TIME_SECTION => {
+                    let names = core::str::from_utf8(reader.data()).map_err(|error| {
+                        Error::new(format!(
+                            "malformed `{CONSTANT_TIME_SECTION}` custom section: {error}"
+                        ))
+                    })?;
+                    marked.extend(names.split_whitespace().map(String::from));
+                }
+                Payload::CustomSection(reader) => {
+                    if let KnownCustom::Name(section) = reader.as_known() {
+                        for name in section {
+                            let Ok(Name::Function(names)) = name else {
+                                continue;
+                            };
+                            for naming in names {
+                                let naming = naming?;
+                                module
+                                    .names
+                                    .entry(naming.name.into())
+                                    .or_insert(naming.index);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        marked.sort();
+        marked.dedup();
+        let mut report = ConstantTimeReport::default();
+        for name in marked {
+            let Some(&func_index) = module.names.get(&name) else {
+                return Err(Error::new(format!(
+                    "cannot find function `{name}` marked for constant-time audit"
+                )));
+            };
+            let Some(body) = func_index
+                .checked_sub(module.len_imported_funcs)
+                .and_then(|index| bodies.get(index as usize))
+            else {
+                return Err(Error::new(format!(
+                    "cannot audit imported function `{name}` for constant-time violations"
+                )));
+            };
+            let violations = module.audit_func(func_index, body, audit)?;
+            report
+                .violations
+                .extend(
+                    violations
+                        .into_iter()
+                        .map(|(offset, kind)| ConstantTimeViolation {
+                            func_index,
+                            func_name: name.clone(),
+                            offset,
+                            kind,
+                        }),
+                );
+            report.audited.push(name);
+        }
+        Ok(report)
+    }
+}
+
+/// Configures the constant-time audit of [`Module::audit_constant_time`].
+#[derive(Debug, Clone)]
+pub struct ConstantTimeAudit {
+    /// The names of the functions to audit.
+    functions: Vec<String>,
+    /// Is `true` if the parameters of audited functions are secret.
+    secret_params: bool,
+    /// Is `true` if values loaded from linear memory are secret.
+    secret_memory: bool,
+}
+
+impl Default for ConstantTimeAudit {
+    fn default() -> Self {
+        Self {
+            functions: Vec::new(),
+            secret_params: true,
+            secret_memory: false,
+        }
+    }
+}
+
+impl ConstantTimeAudit {
+    /// Creates a new [`ConstantTimeAudit`] with secret parameters and public linear memory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the function with `name` for the audit.
+    pub fn function(&mut self, name: &str) -> &mut Self {
+        self.functions.push(name.into());
+        self
+    }
+
+    /// Configures whether the parameters of audited functions are secret.
+    ///
+    /// Default value: `true`
+    pub fn secret_params(&mut self, enable: bool) -> &mut Self {
+        self.secret_params = enable;
+        self
+    }
+
+    /// Configures whether values loaded from linear memory are secret.
+    ///
+    /// This is useful for functions that receive pointers to secrets in linear memory.
+    /// Usually [`ConstantTimeAudit::secret_params`] is disabled for such functions.
+    ///
+    /// Default value: `false`
+    pub fn secret_memory(&mut self, enable: bool) -> &mut Self {
+        self.secret_memory = enable;
+        self
+    }
+}
+
+/// The result of [`Module::audit_constant_time`].
+#[derive(Debug, Default, Clone)]
+pub struct ConstantTimeReport {
+    /// The names of the audited functions in alphabetical order.
+    audited: Vec<String>,
+    /// The violations of all audited functions.
+    violations: Vec<ConstantTimeViolation>,
+}
+
+impl ConstantTimeReport {
+    /// Returns the names of all audited functions in alphabetical order.
+    pub fn audited(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+        self.audited.iter().map(String::as_str)
+    }
+
+    /// Returns all violations sorted by function name and offset.
+    pub fn violations(&self) -> &[ConstantTimeViolation] {
+        &self.violations
+    }
+
+    /// Returns `true` if no audited function violates constant-time guarantees.
+    pub fn is_constant_time(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for ConstantTimeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in &self.audited {
+            let mut violations = self
+                .violations
+                .iter()
+                .filter(|violation| violation.func_name == *name)
+                .peekable();
+            if violations.peek().is_none() {
+                writeln!(f, "{name}: constant-time")?;
+                continue;
+            }
+            writeln!(f, "{name}:")?;
+            for violation in violations {
+                writeln!(f, "  {:#x}: {}", violation.offset, violation.kind)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A constant-time violation found by [`Module::audit_constant_time`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstantTimeViolation {
+    /// The index of the violating function.
+    func_index: u32,
+    /// The name of the violating function.
+    func_name: String,
+    /// The offset of the violating operator within the Wasm binary.
+    offset: usize,
+    /// The kind of the violation.
+    kind: ConstantTimeViolationKind,
+}
+
+impl ConstantTimeViolation {
+    /// Returns the Wasm function index of the violating function.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the name of the violating function.
+    pub fn func_name(&self) -> &str {
+        &self.func_name
+    }
+
+    /// Returns the offset of the violating operator within the Wasm binary.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the [`ConstantTimeViolationKind`] of the violation.
+    pub fn kind(&self) -> ConstantTimeViolationKind {
+        self.kind
+    }
+}
+
+/// The kind of a [`ConstantTimeViolation`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConstantTimeViolationKind {
+    /// A branch, e.g. `if`, `br_if` or `br_table`, depends on a secret value.
+    SecretBranch,
+    /// The address or length of a linear memory access depends on a secret value.
+    SecretMemoryAddress,
+    /// The index of a table access or indirect call depends on a secret value.
+    SecretTableIndex,
+}
+
+impl fmt::Display for ConstantTimeViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::SecretBranch => "branch depends on a secret value",
+            Self::SecretMemoryAddress => "memory address depends on a secret value",
+            Self::SecretTableIndex => "table index depends on a secret value",
+        };
+        f.write_str(message)
+    }
+}
+
+/// The parts of a Wasm module required by [`Module::audit_constant_time`].
+#[derive(Default)]
+struct AuditedModule {
+    /// The types of the Wasm module.
+    types: Vec<SubType>,
+    /// The type indices of all imported and defined functions.
+    funcs: Vec<u32>,
+    /// The number of imported functions.
+    len_imported_funcs: u32,
+    /// The function indices by their exported or debug names.
+    names: BTreeMap<String, u32>,
+}
+
+impl ModuleArity for AuditedModule {
+    fn sub_type_at(&self, type_idx: u32) -> Option<&SubType> {
+        self.types.get(type_idx as usize)
+    }
+
+    fn tag_type_arity(&self, _at: u32) -> Option<(u32, u32)> {
+        None
+    }
+
+    fn type_index_of_function(&self, function_idx: u32) -> Option<u32> {
+        self.funcs.get(function_idx as usize).copied()
+    }
+
+    fn func_type_of_cont_type(&self, _c: &ContType) -> Option<&FuncType> {
+        None
+    }
+
+    fn sub_type_of_ref_type(&self, _rt: &RefType) -> Option<&SubType> {
+        None
+    }
+
+    fn control_stack_height(&self) -> u32 {
+        // Note: control operators are handled without `operator_arity`.
+        0
+    }
+
+    fn label_block(&self, _depth: u32) -> Option<(BlockType, FrameKind)> {
+        None
+    }
+}
+
+/// A control frame of an audited function.
+struct ControlFrame {
+    /// The height of the value stack upon entering the frame without its parameters.
+    height: usize,
+    /// The number of parameters of the frame.
+    len_params: usize,
+    /// The number of results of the frame.
+    len_results: usize,
+    /// Is `true` if the results of the frame are secret.
+    secret: bool,
+    /// Is `true` if the remaining operators of the frame are unreachable.
+    unreachable: bool,
+}
+
+/// The secrecy of the values of an audited function.
+struct Taint {
+    /// Is `true` for each local variable that is assigned a secret value anywhere.
+    locals: Vec<bool>,
+    /// Is `true` for each secret value on the value stack.
+    stack: Vec<bool>,
+    /// The control frames.
+    frames: Vec<ControlFrame>,
+    /// The found violations as `(offset, kind)`.
+    violations: Vec<(usize, ConstantTimeViolationKind)>,
+}
+
+impl Taint {
+    /// Pops `n` values and returns `true` if any of them is secret.
+    ///
+    /// Values below the current control frame are public in unreachable code.
+    fn pop(&mut self, n: usize) -> bool {
+        let height = self.frames.last().map(|frame| frame.height).unwrap_or(0);
+        let len = self.stack.len().saturating_sub(n).max(height);
+        self.stack.drain(len..).fold(false, |lhs, rhs| lhs | rhs)
+    }
+
+    /// Returns the secrecy of the `n` top-most values from bottom to top.
+    fn peek(&self, n: usize) -> Vec<bool> {
+        let len = self.stack.len();
+        let mut values = self.stack[len.saturating_sub(n)..].to_vec();
+        while values.len() < n {
+            values.insert(0, false);
+        }
+        values
+    }
+
+    /// Pushes `n` values with `secret` secrecy.
+    fn push(&mut self, n: usize, secret: bool) {
+        self.stack.extend(core::iter::repeat(secret).take(n));
+    }
+
+    /// Records a violation of `kind` at `offset` if `secret` is `true`.
+    fn check(&mut self, secret: bool, offset: usize, kind: ConstantTimeViolationKind) {
+        if secret && !self.frame().unreachable {
+            self.violations.push((offset, kind));
+        }
+    }
+
+    /// Returns the top-most control frame.
+    fn frame(&mut self) -> &mut ControlFrame {
+        self.frames
+            .last_mut()
+            .expect("audited functions always have a control frame")
+    }
+
+    /// Marks the results of the frame at `depth` as secret if `secret` is `true`.
+    fn branch(&mut self, depth: u32, secret: bool) {
+        let index = self.frames.len().checked_sub(depth as usize + 1);
+        if let Some(frame) = index.and_then(|index| self.frames.get_mut(index)) {
+            frame.secret |= secret;
+        }
+    }
+
+    /// Marks the remaining operators of the current control frame as unreachable.
+    fn unreachable(&mut self) {
+        let frame = self.frame();
+        frame.unreachable = true;
+        let height = frame.height;
+        self.stack.truncate(height);
+    }
+}
+
+impl AuditedModule {
+    /// Returns the `(params, results)` counts of the block type `ty`.
+    fn block_arity(&self, ty: BlockType) -> Result<(usize, usize), Error> {
+        let (params, results) = self
+            .block_type_arity(ty)
+            .ok_or_else(|| Error::new(format!("unknown block type: {ty:?}")))?;
+        Ok((params as usize, results as usize))
+    }
+
+    /// Audits the function at `func_index` with `body`.
+    ///
+    /// Returns the found violations as `(offset, kind)` sorted by offset.
+    fn audit_func(
+        &self,
+        func_index: u32,
+        body: &FunctionBody,
+        audit: &ConstantTimeAudit,
+    ) -> Result<Vec<(usize, ConstantTimeViolationKind)>, Error> {
+        let (len_params, len_results) = self
+            .type_index_of_function(func_index)
+            .and_then(|index| self.sub_type_arity(self.sub_type_at(index)?))
+            .ok_or_else(|| Error::new(format!("unknown type of function {func_index}")))?;
+        let mut locals = vec![audit.secret_params; len_params as usize];
+        for local in body.get_locals_reader()? {
+            let (count, _) = local?;
+            locals.extend(core::iter::repeat(false).take(count as usize));
+        }
+        // Note: secrecy of local variables only ever grows so this reaches a fixed point.
+        loop {
+            let mut taint = Taint {
+                locals: locals.clone(),
+                stack: Vec::new(),
+                frames: vec![ControlFrame {
+                    height: 0,
+                    len_params: 0,
+                    len_results: len_results as usize,
+                    secret: false,
+                    unreachable: false,
+                }],
+                violations: Vec::new(),
+            };
+            self.audit_ops(body, audit, &mut taint)?;
+            if taint.locals == locals {
+                let mut violations = taint.violations;
+                violations.sort_by_key(|(offset, _)| *offset);
+                violations.dedup();
+                return Ok(violations);
+            }
+            locals = taint.locals;
+        }
+    }
+
+    /// Propagates the secrecy of values through the operators of `body`.
+    fn audit_ops(
+        &self,
+        body: &FunctionBody,
+        audit: &ConstantTimeAudit,
+        taint: &mut Taint,
+    ) -> Result<(), Error> {
+        use ConstantTimeViolationKind as Kind;
+        use Operator as Op;
+        for op in body.get_operators_reader()?.into_iter_with_offsets() {
+            let (op, offset) = op?;
+            match op {
+                Op::Block { blockty } | Op::Loop { blockty } | Op::If { blockty } => {
+                    let (len_params, len_results) = self.block_arity(blockty)?;
+                    let condition = matches!(op, Op::If { .. }) && taint.pop(1);
+                    taint.check(condition, offset, Kind::SecretBranch);
+                    let height = taint.stack.len().saturating_sub(len_params);
+                    taint.frames.push(ControlFrame {
+                        height,
+                        len_params,
+                        len_results,
+                        secret: condition,
+                        unreachable: false,
+                    });
+                }
+                Op::Else => {
+                    let height = taint.frame().height.min(taint.stack.len());
+                    let results = taint.stack[height..].iter().any(|secret| *secret);
+                    let frame = taint.frame();
+                    frame.secret |= results;
+                    frame.unreachable = false;
+                    let (secret, len_params) = (frame.secret, frame.len_params);
+                    taint.stack.truncate(height);
+                    taint.push(len_params, secret);
+                }
+                Op::End => {
+                    let frame = taint.frame();
+                    let height = frame.height;
+                    let secret = frame.secret;
+                    let len_results = frame.len_results;
+                    let results = taint.pop(taint.stack.len().saturating_sub(height));
+                    taint.frames.pop();
+                    if taint.frames.is_empty() {
+                        break;
+                    }
+                    taint.push(len_results, secret | results);
+                }
+                Op::Br { relative_depth } => {
+                    let values = taint.pop(usize::MAX);
+                    taint.branch(relative_depth, values);
+                    taint.unreachable();
+                }
+                Op::BrIf { relative_depth } => {
+                    let condition = taint.pop(1);
+                    taint.check(condition, offset, Kind::SecretBranch);
+                    let values = taint.stack.iter().any(|secret| *secret);
+                    taint.branch(relative_depth, condition | values);
+                }
+                Op::BrTable { targets } => {
+                    let condition = taint.pop(1);
+                    taint.check(condition, offset, Kind::SecretBranch);
+                    let values = taint.pop(usize::MAX);
+                    for target in targets.targets() {
+                        taint.branch(target?, condition | values);
+                    }
+                    taint.branch(targets.default(), condition | values);
+                    taint.unreachable();
+                }
+                Op::Return | Op::Unreachable => taint.unreachable(),
+                Op::LocalGet { local_index } => {
+                    let secret = taint.locals.get(local_index as usize).copied();
+                    taint.push(1, secret.unwrap_or(false));
+                }
+                Op::LocalSet { local_index } | Op::LocalTee { local_index } => {
+                    let secret = taint.pop(1);
+                    if let Some(local) = taint.locals.get_mut(local_index as usize) {
+                        *local |= secret;
+                    }
+                    if matches!(op, Op::LocalTee { .. }) {
+                        taint.push(1, secret);
+                    }
+                }
+                op => {
+                    let (len_params, len_results) = op.operator_arity(self).ok_or_else(|| {
+                        Error::new(format!("unsupported operator at offset {offset}: {op:?}"))
+                    })?;
+                    let operands = taint.peek(len_params as usize);
+                    let secret = taint.pop(len_params as usize);
+                    let result = match access_kind(&op) {
+                        Some(Access::Load) => {
+                            taint.check(operands[0], offset, Kind::SecretMemoryAddress);
+                            audit.secret_memory | operands[0]
+                        }
+                        Some(Access::Store) => {
+                            taint.check(operands[0], offset, Kind::SecretMemoryAddress);
+                            false
+                        }
+                        Some(Access::Bulk) => {
+                            taint.check(secret, offset, Kind::SecretMemoryAddress);
+                            false
+                        }
+                        Some(Access::Table) => {
+                            taint.check(secret, offset, Kind::SecretTableIndex);
+                            secret
+                        }
+                        Some(Access::CallIndirect) => {
+                            let index = operands.last().copied().unwrap_or(false);
+                            taint.check(index, offset, Kind::SecretTableIndex);
+                            secret
+                        }
+                        None => secret,
+                    };
+                    taint.push(len_results as usize, result);
+                    if matches!(op, Op::ReturnCall { .. } | Op::ReturnCallIndirect { .. }) {
+                        taint.unreachable();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The kind of access of an operator to linear memory or tables.
+enum Access {
+    /// A load from linear memory at the address of the first operand.
+    Load,
+    /// A store to linear memory at the address of the first operand.
+    Store,
+    /// A bulk operation on linear memory whose operands are all addresses or lengths.
+    Bulk,
+    /// An operation on a table whose operands are all indices, lengths or values.
+    Table,
+    /// An indirect call with the table index as the last operand.
+    CallIndirect,
+}
+
+/// Returns the [`Access`] of `op` to linear memory or tables if any.
+#[rustfmt::skip]
+fn access_kind(op: &Operator) -> Option<Access> {
+    use Operator as Op;
+    let access = match op {
+        Op::I32Load { .. } | Op::I64Load { .. } | Op::F32Load { .. } | Op::F64Load { .. } |
+        Op::I32Load8S { .. } | Op::I32Load8U { .. } | Op::I32Load16S { .. } |
+        Op::I32Load16U { .. } | Op::I64Load8S { .. } | Op::I64Load8U { .. } |
+        Op::I64Load16S { .. } | Op::I64Load16U { .. } | Op::I64Load32S { .. } |
+        Op::I64Load32U { .. } => Access::Load,
+        Op::I32Store { .. } | Op::I64Store { .. } | Op::F32Store { .. } | Op::F64Store { .. } |
+        Op::I32Store8 { .. } | Op::I32Store16 { .. } | Op::I64Store8 { .. } |
+        Op::I64Store16 { .. } | Op::I64Store32 { .. } => Access::Store,
+        Op::MemoryFill { .. } | Op::MemoryCopy { .. } | Op::MemoryInit { .. } => Access::Bulk,
+        Op::TableGet { .. } | Op::TableSet { .. } | Op::TableFill { .. } |
+        Op::TableCopy { .. } | Op::TableInit { .. } => Access::Table,
+        Op::CallIndirect { .. } | Op::ReturnCallIndirect { .. } => Access::CallIndirect,
+        _ => return None,
+    };
+    Some(access)
+}
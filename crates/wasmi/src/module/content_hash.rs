@@ -0,0 +1,183 @@
+use super::{FuncIdx, Module};
+use crate::{
+    core::UntypedVal,
+    engine::{EngineFunc, StableHasher},
+    ir::{index::InternalFunc, Instruction},
+    Error,
+    FuncType,
+};
+use core::fmt::{self, Write as _};
+
+impl Module {
+    /// Returns the stable hash of the translated Wasmi bytecode of the [`Module`].
+    ///
+    /// Unlike [`Module::hash`] which is computed over the Wasm binary, the content hash
+    /// is computed over the function hashes returned by [`Module::func_hash`] and
+    /// therefore also identifies the Wasmi bytecode that is actually executed. This makes
+    /// it suitable as a cache key and for attesting which code version executed.
+    ///
+    /// # Note
+    ///
+    /// - The content hash is stable across engines and processes but differs between
+    ///   [`Config`]s and Wasmi versions that translate the Wasm binary differently.
+    /// - Lazily compiled functions of the [`Module`] are compiled if they have not been compiled yet.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of a lazily compiled function fails.
+    ///
+    /// [`Config`]: crate::Config
+    pub fn content_hash(&self) -> Result<u64, Error> {
+        let header = &self.inner.header.inner;
+        let mut hasher = StableHasher::default();
+        hasher.write_bytes(&self.hash().to_le_bytes());
+        hasher.write_bytes(&(header.imports.len_funcs() as u64).to_le_bytes());
+        for func in header.engine_funcs.iter() {
+            hasher.write_bytes(&self.engine_func_hash(func)?.to_le_bytes());
+        }
+        Ok(hasher.finish())
+    }
+
+    /// Returns the stable hash of the translated Wasmi bytecode of the function at `func_index`.
+    ///
+    /// The `func_index` refers to the Wasm function index space which starts with the
+    /// imported functions. Returns `None` if the function is imported or out of bounds.
+    ///
+    /// The function hash covers the function type and the canonicalized Wasmi bytecode of
+    /// the function including its function local constants. Calls to other functions of the
+    /// [`Module`] are canonicalized to their module local index so that the same function
+    /// has the same hash regardless of the [`Engine`](crate::Engine) it has been compiled by.
+    ///
+    /// # Note
+    ///
+    /// Compiles the function if it has not been compiled yet.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of a lazily compiled function fails.
+    pub fn func_hash(&self, func_index: u32) -> Result<Option<u64>, Error> {
+        let header = &self.inner.header.inner;
+        let Some(position) = func_index.checked_sub(header.imports.len_funcs() as u32) else {
+            return Ok(None);
+        };
+        let Some(func) = header.engine_funcs.get(position) else {
+            return Ok(None);
+        };
+        self.engine_func_hash(func).map(Some)
+    }
+
+    /// Returns the stable hash of the internal function `func` of the [`Module`].
+    fn engine_func_hash(&self, func: EngineFunc) -> Result<u64, Error> {
+        let header = &self.inner.header;
+        let engine = self.engine();
+        let func_idx = header
+            .get_func_index(func)
+            .unwrap_or_else(|| panic!("missing function index for internal function: {func:?}"));
+        let mut hasher = HashWriter(StableHasher::default());
+        let func_type = header.get_type_of_func(func_idx);
+        engine.resolve_func_type(func_type, |func_type: &FuncType| {
+            hasher.write_debug(func_type.params());
+            hasher.write_debug(func_type.results());
+        });
+        engine.resolve_compiled(func, |instrs, consts, len_registers| {
+            hasher.write_debug(len_registers);
+            for value in consts {
+                hasher
+                    .0
+                    .write_bytes(&UntypedVal::to_bits(*value).to_le_bytes());
+            }
+            for instr in instrs {
+                let instr = self.canonicalize(*instr, &mut hasher);
+                hasher.write_debug(instr);
+            }
+        })?;
+        Ok(hasher.0.finish())
+    }
+
+    /// Returns `instr` with its engine specific indices replaced by module local ones.
+    ///
+    /// Engine specific information that cannot be encoded in `instr` is fed into `hasher`.
+    fn canonicalize(&self, instr: Instruction, hasher: &mut HashWriter) -> Instruction {
+        let local = |func: InternalFunc| {
+            let index = self
+                .inner
+                .header
+                .get_func_index(EngineFunc::from(func))
+                .map_or(u32::MAX, FuncIdx::into_u32);
+            InternalFunc::from(index)
+        };
+        match instr {
+            Instruction::CallInternal0 { results, func } => Instruction::CallInternal0 {
+                results,
+                func: local(func),
+            },
+            Instruction::CallInternal { results, func } => Instruction::CallInternal {
+                results,
+                func: local(func),
+            },
+            Instruction::ReturnCallInternal0 { func } => {
+                Instruction::ReturnCallInternal0 { func: local(func) }
+            }
+            Instruction::ReturnCallInternal { func } => {
+                Instruction::ReturnCallInternal { func: local(func) }
+            }
+            Instruction::Extension0 {
+                results,
+                extension,
+                len_results,
+                opcode,
+            } => {
+                hasher.write_extension(self, extension);
+                Instruction::Extension0 {
+                    results,
+                    extension: 0,
+                    len_results,
+                    opcode,
+                }
+            }
+            Instruction::Extension {
+                results,
+                extension,
+                len_results,
+                opcode,
+            } => {
+                hasher.write_extension(self, extension);
+                Instruction::Extension {
+                    results,
+                    extension: 0,
+                    len_results,
+                    opcode,
+                }
+            }
+            instr => instr,
+        }
+    }
+}
+
+/// Feeds formatted values into a [`StableHasher`].
+struct HashWriter(StableHasher);
+
+impl HashWriter {
+    /// Feeds the [`Debug`](fmt::Debug) representation of `value` terminated by `;`.
+    fn write_debug(&mut self, value: impl fmt::Debug) {
+        // Note: writing into a `HashWriter` never fails.
+        let _ = write!(self, "{value:?};");
+    }
+
+    /// Feeds the import namespace of the instruction `extension` of the engine of `module`.
+    ///
+    /// Extensions are identified by their import namespace since their indices depend on
+    /// the order in which they have been registered with the engine.
+    fn write_extension(&mut self, module: &Module, extension: u8) {
+        let extensions = module.engine().extensions();
+        self.0
+            .write_bytes(extensions.get(extension).module().as_bytes());
+    }
+}
+
+impl fmt::Write for HashWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
@@ -52,7 +52,7 @@ impl ActiveDataSegment {
 }
 
 /// The bytes of the passive data segment.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PassiveDataSegmentBytes {
     bytes: Arc<[u8]>,
 }
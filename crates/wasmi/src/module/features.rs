@@ -0,0 +1,258 @@
+use super::{
+    export::ExternIdx,
+    ConstExpr,
+    DataSegments,
+    ElementSegmentKind,
+    InitDataSegment,
+    ModuleHeader,
+};
+use crate::{core::ValType, engine::WASM_PROPOSALS, Error};
+use core::fmt;
+use wasmparser::{BlockType, FunctionBody, Operator, WasmFeatures};
+
+#[cfg(doc)]
+use crate::{Config, Module};
+
+/// The Wasm proposals used by a [`Module`] as returned by [`Module::required_features`].
+///
+/// Wasm proposals are identified by the same names as in [`Config::diff`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ModuleFeatures {
+    /// The Wasm proposals used by the [`Module`].
+    features: WasmFeatures,
+}
+
+impl Default for ModuleFeatures {
+    fn default() -> Self {
+        Self {
+            features: WasmFeatures::empty(),
+        }
+    }
+}
+
+impl ModuleFeatures {
+    /// Returns `true` if the [`Module`] uses no Wasm proposal.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns `true` if the [`Module`] uses the Wasm proposal with `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.iter().any(|feature| feature == name)
+    }
+
+    /// Returns an iterator over the names of all Wasm proposals used by the [`Module`].
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        WASM_PROPOSALS
+            .iter()
+            .filter(|(_, feature)| self.features.contains(*feature))
+            .map(|(name, _)| *name)
+    }
+
+    /// Marks the Wasm proposals of `features` as used.
+    fn require(&mut self, features: WasmFeatures) {
+        self.features |= features;
+    }
+
+    /// Marks the Wasm proposals required to use values of type `ty` as used.
+    fn visit_val_type(&mut self, ty: ValType) {
+        match ty {
+            ValType::F32 | ValType::F64 => self.require(WasmFeatures::FLOATS),
+            ValType::FuncRef | ValType::ExternRef => self.require(WasmFeatures::REFERENCE_TYPES),
+            ValType::I32 | ValType::I64 => {}
+        }
+    }
+
+    /// Marks the Wasm proposals required to use values of Wasm type `ty` as used.
+    fn visit_wasm_val_type(&mut self, ty: wasmparser::ValType) {
+        match ty {
+            wasmparser::ValType::F32 | wasmparser::ValType::F64 => {
+                self.require(WasmFeatures::FLOATS)
+            }
+            wasmparser::ValType::Ref(_) => self.require(WasmFeatures::REFERENCE_TYPES),
+            _ => {}
+        }
+    }
+
+    /// Marks the Wasm proposals required by the function type `ty` as used.
+    pub(crate) fn visit_func_type(&mut self, ty: &wasmparser::FuncType) {
+        if ty.results().len() > 1 {
+            self.require(WasmFeatures::MULTI_VALUE);
+        }
+        for ty in ty.params().iter().chain(ty.results()) {
+            self.visit_wasm_val_type(*ty);
+        }
+    }
+
+    /// Marks the Wasm proposals required by the locals and operators of `func_body` as used.
+    ///
+    /// # Errors
+    ///
+    /// If the locals or operators of `func_body` are malformed.
+    pub(crate) fn visit_func_body(&mut self, func_body: &FunctionBody) -> Result<(), Error> {
+        let mut locals = func_body.get_locals_reader()?;
+        for _ in 0..locals.get_count() {
+            let (_, ty) = locals.read()?;
+            self.visit_wasm_val_type(ty);
+        }
+        let mut operators = func_body.get_operators_reader()?;
+        while !operators.eof() {
+            let operator = operators.read()?;
+            match &operator {
+                Operator::Block { blockty }
+                | Operator::Loop { blockty }
+                | Operator::If { blockty } => match blockty {
+                    BlockType::Empty => {}
+                    BlockType::Type(ty) => self.visit_wasm_val_type(*ty),
+                    BlockType::FuncType(_) => self.require(WasmFeatures::MULTI_VALUE),
+                },
+                Operator::CallIndirect { table_index, .. } if *table_index != 0 => {
+                    self.require(WasmFeatures::REFERENCE_TYPES)
+                }
+                _ => {}
+            }
+            self.require(operator_features(&operator));
+        }
+        Ok(())
+    }
+
+    /// Marks the Wasm proposals required by the entities of a parsed [`Module`] as used.
+    ///
+    /// # Note
+    ///
+    /// Function types and function bodies are visited while parsing.
+    pub(crate) fn visit_module(&mut self, header: &ModuleHeader, data_segments: &DataSegments) {
+        let header = &*header.inner;
+        if header.memories.len() > 1 {
+            self.require(WasmFeatures::MULTI_MEMORY);
+        }
+        if header
+            .memories
+            .iter()
+            .any(|memory| memory.page_size_log2() != 16)
+        {
+            self.require(WasmFeatures::CUSTOM_PAGE_SIZES);
+        }
+        if header.tables.len() > 1 {
+            self.require(WasmFeatures::REFERENCE_TYPES);
+        }
+        for table in &header.tables[..] {
+            if table.element() == ValType::ExternRef {
+                self.require(WasmFeatures::REFERENCE_TYPES);
+            }
+        }
+        for global in &header.globals[..] {
+            self.visit_val_type(global.content());
+        }
+        let imported_globals = &header.globals[..header.imports.len_globals];
+        let exported_globals = header.exports.values().filter_map(|export| match export {
+            ExternIdx::Global(index) => header.globals.get(index.into_u32() as usize),
+            _ => None,
+        });
+        if imported_globals
+            .iter()
+            .chain(exported_globals)
+            .any(|global| global.mutability().is_mut())
+        {
+            self.require(WasmFeatures::MUTABLE_GLOBAL);
+        }
+        if header.globals_init.iter().any(ConstExpr::is_extended) {
+            self.require(WasmFeatures::EXTENDED_CONST);
+        }
+        for segment in &header.element_segments[..] {
+            if segment.ty() == ValType::ExternRef {
+                self.require(WasmFeatures::REFERENCE_TYPES);
+            }
+            match segment.kind() {
+                ElementSegmentKind::Passive => self.require(WasmFeatures::BULK_MEMORY),
+                ElementSegmentKind::Declared => self.require(WasmFeatures::REFERENCE_TYPES),
+                ElementSegmentKind::Active(segment) => {
+                    if segment.table_index().into_u32() != 0 {
+                        self.require(WasmFeatures::REFERENCE_TYPES);
+                    }
+                    if segment.offset().is_extended() {
+                        self.require(WasmFeatures::EXTENDED_CONST);
+                    }
+                }
+            }
+        }
+        for segment in data_segments {
+            match segment {
+                InitDataSegment::Active { offset, .. } if offset.is_extended() => {
+                    self.require(WasmFeatures::EXTENDED_CONST)
+                }
+                InitDataSegment::Active { .. } => {}
+                InitDataSegment::Passive { .. } => self.require(WasmFeatures::BULK_MEMORY),
+            }
+        }
+    }
+}
+
+impl fmt::Display for ModuleFeatures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, feature) in self.iter().enumerate() {
+            if n != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{feature}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Evaluates to [`WasmFeatures::FLOATS`] if the operator annotation refers to `f32` or `f64`.
+macro_rules! float_features {
+    ( () ) => {
+        WasmFeatures::empty()
+    };
+    ( (f32 $($rest:tt)*) ) => {
+        WasmFeatures::FLOATS
+    };
+    ( (f64 $($rest:tt)*) ) => {
+        WasmFeatures::FLOATS
+    };
+    ( ($_token:tt $($rest:tt)*) ) => {
+        float_features!(($($rest)*))
+    };
+}
+
+/// Evaluates to the [`WasmFeatures`] required by an operator of the Wasm `proposal`.
+macro_rules! proposal_features {
+    ( @mvp $ann:tt ) => {
+        float_features!($ann)
+    };
+    ( @sign_extension $ann:tt ) => {
+        WasmFeatures::SIGN_EXTENSION
+    };
+    ( @saturating_float_to_int $ann:tt ) => {
+        WasmFeatures::SATURATING_FLOAT_TO_INT.union(WasmFeatures::FLOATS)
+    };
+    ( @bulk_memory $ann:tt ) => {
+        WasmFeatures::BULK_MEMORY
+    };
+    ( @reference_types $ann:tt ) => {
+        WasmFeatures::REFERENCE_TYPES
+    };
+    ( @tail_call $ann:tt ) => {
+        WasmFeatures::TAIL_CALL
+    };
+    ( @$proposal:ident $ann:tt ) => {
+        // Operators of unsupported Wasm proposals are rejected by Wasm validation.
+        WasmFeatures::empty()
+    };
+}
+
+macro_rules! define_operator_features {
+    ( $( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $ann:tt )* ) => {
+        /// Returns the [`WasmFeatures`] required by the Wasm `operator` itself.
+        fn operator_features(operator: &Operator) -> WasmFeatures {
+            match operator {
+                $(
+                    Operator::$op { .. } => proposal_features!(@$proposal $ann),
+                )*
+                _ => WasmFeatures::empty(),
+            }
+        }
+    };
+}
+wasmparser::for_each_operator!(define_operator_features);
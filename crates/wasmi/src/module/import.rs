@@ -1,3 +1,4 @@
+use super::ModuleAdapter;
 use crate::{GlobalType, MemoryType, TableType};
 use alloc::boxed::Box;
 use core::fmt::{self, Display};
@@ -83,6 +84,15 @@ impl Import {
         }
     }
 
+    /// Returns the [`Import`] renamed according to the [`ModuleAdapter`].
+    pub fn adapt(self, adapter: &ModuleAdapter) -> Self {
+        let name = adapter.adapt_import(self.name.module(), self.name.name());
+        Self {
+            name,
+            kind: self.kind,
+        }
+    }
+
     /// Splits the [`Import`] into its raw parts.
     ///
     /// # Note
@@ -331,6 +331,11 @@ impl ConstExpr {
         None
     }
 
+    /// Returns `true` if the [`ConstExpr`] uses operators of the `extended-const` Wasm proposal.
+    pub fn is_extended(&self) -> bool {
+        matches!(self.op, Op::Expr(_))
+    }
+
     /// Evaluates the [`ConstExpr`] in a constant evaluation context.
     ///
     /// # Note
@@ -55,10 +55,45 @@ pub enum InstantiationError {
         index: u32,
     },
     TooManyInstances,
+    /// Caused when a memory image refers to a non-existing linear memory.
+    InvalidMemoryImage {
+        /// The index of the non-existing linear memory.
+        memory_index: u32,
+    },
+    /// Caused when a table image refers to a non-existing table or function.
+    InvalidTableImage {
+        /// The index of the table of the invalid table image.
+        table_index: u32,
+    },
+    /// Caused when a hot-swapped [`Module`] requires other entities than the [`Instance`] provides.
+    ///
+    /// [`Module`]: crate::Module
+    /// [`Instance`]: crate::Instance
+    HotSwapMismatch {
+        /// The kind of the mismatching entities.
+        kind: &'static str,
+        /// The number of entities provided by the [`Instance`].
+        ///
+        /// [`Instance`]: crate::Instance
+        expected: usize,
+        /// The number of entities required by the [`Module`].
+        ///
+        /// [`Module`]: crate::Module
+        found: usize,
+    },
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for InstantiationError {}
+impl std::error::Error for InstantiationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Table(error) => Some(error),
+            Self::Memory(error) => Some(error),
+            Self::Global(error) => Some(error),
+            _ => None,
+        }
+    }
+}
 
 impl Display for InstantiationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -88,10 +123,29 @@ impl Display for InstantiationError {
             Self::FoundStartFn { index } => {
                 write!(f, "found an unexpected start function with index {index}")
             }
-            Self::Table(error) => Display::fmt(error, f),
-            Self::Memory(error) => Display::fmt(error, f),
-            Self::Global(error) => Display::fmt(error, f),
-            Self::TooManyInstances => write!(f, "too many instances")
+            Self::Table(error) => write!(f, "imported table does not satisfy the required table type: {error}"),
+            Self::Memory(error) => write!(f, "imported memory does not satisfy the required memory type: {error}"),
+            Self::Global(error) => write!(f, "imported global variable does not satisfy the required global variable type: {error}"),
+            Self::TooManyInstances => write!(f, "too many instances"),
+            Self::InvalidMemoryImage { memory_index } => {
+                write!(f, "memory image refers to unknown memory {memory_index}")
+            }
+            Self::InvalidTableImage { table_index } => {
+                write!(
+                    f,
+                    "table image for table {table_index} refers to unknown table or function"
+                )
+            }
+            Self::HotSwapMismatch {
+                kind,
+                expected,
+                found,
+            } => {
+                write!(
+                    f,
+                    "cannot hot-swap module requiring {found} {kind} into instance with {expected} {kind}"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,237 @@
+use super::InstantiationError;
+use crate::{
+    engine::EngineFunc,
+    func::FuncEntity,
+    memory::DataSegment,
+    module::{element::ElementSegmentKind, InitDataSegment, Module},
+    AsContextMut,
+    ElementSegment,
+    Error,
+    Extern,
+    ExternType,
+    Func,
+    FuncRef,
+    Instance,
+    InstanceEntity,
+    Table,
+    Val,
+};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+impl Module {
+    /// Instantiates the [`Module`] re-using the imports and the state of `instance`.
+    ///
+    /// Read more about hot-swapping in [`Instance::hot_swap`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Module`] is incompatible with the imports or the state of `instance`.
+    pub(crate) fn hot_swap(
+        &self,
+        mut context: impl AsContextMut,
+        instance: &Instance,
+    ) -> Result<Instance, Error> {
+        let entity = context.as_context().store.inner.resolve_instance(instance);
+        let funcs: Box<[Func]> = entity.funcs().into();
+        let len_imported_funcs = entity.len_imported_funcs();
+        let tables: Box<[Table]> = entity.tables().into();
+        let memories: Box<[_]> = entity.memories().into();
+        let globals: Box<[_]> = entity.globals().into();
+        let exports: Vec<(Box<str>, Extern)> = entity
+            .exports()
+            .map(|export| (export.name().into(), export.into_extern()))
+            .collect();
+        check_len(
+            "imported functions",
+            len_imported_funcs,
+            self.module_header().imports.len_funcs(),
+        )?;
+        check_len("tables", tables.len(), self.len_tables())?;
+        check_len("memories", memories.len(), self.len_memories())?;
+        check_len("globals", globals.len(), self.len_globals())?;
+        let mut builder = InstanceEntity::build(self);
+        for import in self.imports() {
+            let ExternType::Func(expected) = import.ty() else {
+                continue;
+            };
+            let func = funcs[builder.len_funcs()];
+            let actual = func.ty(&context);
+            if &actual != expected {
+                return Err(Error::from(InstantiationError::SignatureMismatch {
+                    expected: expected.clone(),
+                    actual,
+                }));
+            }
+            builder.push_func(func);
+        }
+        let imports: Vec<ExternType> = self.imports().map(|import| import.ty().clone()).collect();
+        let relaxed_limits = context
+            .as_context()
+            .engine()
+            .config()
+            .get_relaxed_import_limits();
+        let table_types = imports.iter().filter_map(|ty| ty.table().copied());
+        for (table, ty) in tables
+            .iter()
+            .zip(table_types.chain(self.internal_tables().copied()))
+        {
+            table
+                .dynamic_ty(&context)
+                .is_subtype_or_err(&ty, relaxed_limits)
+                .map_err(InstantiationError::from)?;
+            builder.push_table(*table);
+        }
+        let memory_types = imports.iter().filter_map(|ty| ty.memory().copied());
+        let memory_types = memory_types.chain(self.internal_memories().copied());
+        for (memory, ty) in memories.iter().zip(memory_types) {
+            memory
+                .dynamic_ty(&context)
+                .is_subtype_or_err(&ty, relaxed_limits)
+                .map_err(InstantiationError::from)?;
+            builder.push_memory(*memory);
+        }
+        let global_types = imports.iter().filter_map(|ty| ty.global().copied());
+        let global_types = global_types.chain(self.internal_globals().map(|(ty, _)| *ty));
+        for (global, ty) in globals.iter().zip(global_types) {
+            global
+                .ty(&context)
+                .satisfies(&ty)
+                .map_err(InstantiationError::from)?;
+            builder.push_global(*global);
+        }
+        // Note: all checks are done before allocating any entities so that a rejected
+        //       hot-swap leaves the store unchanged.
+        let header = self.module_header();
+        let func_type = |index: usize| {
+            header
+                .funcs
+                .get(index)
+                .map(|dedup| self.engine().resolve_func_type(dedup, Clone::clone))
+        };
+        for (name, old) in &exports {
+            let (Extern::Func(old), Some(ExternType::Func(actual))) = (old, self.get_export(name))
+            else {
+                continue;
+            };
+            let expected = old.ty(&context);
+            if expected != actual {
+                return Err(Error::from(InstantiationError::SignatureMismatch {
+                    expected,
+                    actual,
+                }));
+            }
+        }
+        // Redirect table entries that refer to internal functions of `instance` to their new versions.
+        //
+        // Note: imported functions are never redirected since they are not owned by `instance`.
+        let bodies: BTreeMap<EngineFunc, usize> = funcs
+            .iter()
+            .enumerate()
+            .skip(len_imported_funcs)
+            .filter_map(
+                |(index, func)| match context.as_context().store.inner.resolve_func(func) {
+                    FuncEntity::Wasm(entity) if entity.instance() == instance => {
+                        Some((entity.func_body(), index))
+                    }
+                    _ => None,
+                },
+            )
+            .collect();
+        let mut redirects = Vec::new();
+        for table in &tables {
+            for index in 0..table.size(&context) {
+                let Some(Val::FuncRef(funcref)) = table.get(&context, index) else {
+                    continue;
+                };
+                let Some(func) = funcref.func() else {
+                    continue;
+                };
+                let FuncEntity::Wasm(entity) = context.as_context().store.inner.resolve_func(func)
+                else {
+                    continue;
+                };
+                if entity.instance() != instance {
+                    continue;
+                }
+                let Some(&func_index) = bodies.get(&entity.func_body()) else {
+                    continue;
+                };
+                let expected = func.ty(&context);
+                let Some(actual) = func_type(func_index) else {
+                    return Err(Error::from(InstantiationError::HotSwapMismatch {
+                        kind: "functions",
+                        expected: func_index + 1,
+                        found: header.funcs.len(),
+                    }));
+                };
+                if expected != actual {
+                    return Err(Error::from(InstantiationError::SignatureMismatch {
+                        expected,
+                        actual,
+                    }));
+                }
+                redirects.push((*table, index, func_index as u32));
+            }
+        }
+        context
+            .as_context_mut()
+            .store
+            .check_new_instances_limit(1)?;
+        let handle = context.as_context_mut().store.inner.alloc_instance();
+        self.extract_functions(&mut context, &mut builder, handle);
+        self.extract_exports(&mut builder);
+        for (table, index, func_index) in redirects {
+            let func = builder.get_func(func_index);
+            table
+                .set(&mut context, index, Val::FuncRef(FuncRef::new(func)))
+                .map_err(InstantiationError::from)?;
+        }
+        // Note: segments are re-created without re-initializing the preserved state.
+        for segment in &self.module_header().element_segments[..] {
+            let get_global = |index| builder.get_global(index);
+            let get_func = |index| builder.get_func(index);
+            let element =
+                ElementSegment::new(context.as_context_mut(), segment, get_func, get_global);
+            if let ElementSegmentKind::Active(_) = segment.kind() {
+                context
+                    .as_context_mut()
+                    .store
+                    .inner
+                    .resolve_element_segment_mut(&element)
+                    .drop_items();
+            }
+            builder.push_element_segment(element);
+        }
+        for segment in &self.inner.data_segments {
+            let segment = match segment {
+                InitDataSegment::Active { .. } => DataSegment::new_active(context.as_context_mut()),
+                InitDataSegment::Passive { bytes } => {
+                    DataSegment::new_passive(context.as_context_mut(), bytes)
+                }
+            };
+            builder.push_data_segment(segment);
+        }
+        context
+            .as_context_mut()
+            .store
+            .inner
+            .initialize_instance(handle, builder.finish());
+        Ok(handle)
+    }
+}
+
+/// Checks that the hot-swapped [`Module`] requires as many entities of `kind` as the [`Instance`] provides.
+///
+/// # Errors
+///
+/// If `expected` and `found` differ.
+fn check_len(kind: &'static str, expected: usize, found: usize) -> Result<(), InstantiationError> {
+    if expected != found {
+        return Err(InstantiationError::HotSwapMismatch {
+            kind,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
@@ -1,10 +1,16 @@
 mod error;
+mod hot_swap;
+mod options;
 mod pre;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::{error::InstantiationError, pre::InstancePre};
+pub use self::{
+    error::InstantiationError,
+    options::{InstantiateOptions, MemoryImage, TableImage},
+    pre::InstancePre,
+};
 use super::{element::ElementSegmentKind, export, ConstExpr, InitDataSegment, Module};
 use crate::{
     core::UntypedVal,
@@ -46,9 +52,29 @@ impl Module {
     /// [`Linker`]: struct.Linker.html
     /// [`Func`]: [`crate::Func`]
     pub(crate) fn instantiate<I>(
+        &self,
+        context: impl AsContextMut,
+        externals: I,
+    ) -> Result<InstancePre, Error>
+    where
+        I: IntoIterator<Item = Extern, IntoIter: ExactSizeIterator>,
+    {
+        self.instantiate_with_options(context, externals, &InstantiateOptions::new())
+    }
+
+    /// Instantiates a new [`Instance`] from the given compiled [`Module`] using `options`.
+    ///
+    /// Read more about instantiation in [`Module::instantiate`].
+    ///
+    /// # Errors
+    ///
+    /// - If the given `externals` do not satisfy the required imports.
+    /// - If the memory or table images of `options` are invalid or do not fit.
+    pub(crate) fn instantiate_with_options<I>(
         &self,
         mut context: impl AsContextMut,
         externals: I,
+        options: &InstantiateOptions,
     ) -> Result<InstancePre, Error>
     where
         I: IntoIterator<Item = Extern, IntoIter: ExactSizeIterator>,
@@ -70,6 +96,8 @@ impl Module {
 
         self.initialize_table_elements(&mut context, &mut builder)?;
         self.initialize_memory_data(&mut context, &mut builder)?;
+        self.apply_table_images(&mut context, &builder, options)?;
+        self.apply_memory_images(&mut context, &builder, options)?;
 
         // At this point the module instantiation is nearly done.
         // The only thing that is missing is to run the `start` function.
@@ -101,6 +129,11 @@ impl Module {
     {
         let imports = self.imports();
         let externals = externals.into_iter();
+        let relaxed_limits = store
+            .as_context()
+            .engine()
+            .config()
+            .get_relaxed_import_limits();
         if imports.len() != externals.len() {
             return Err(InstantiationError::InvalidNumberOfImports {
                 required: imports.len(),
@@ -121,12 +154,12 @@ impl Module {
                 }
                 (ExternType::Table(required), Extern::Table(table)) => {
                     let imported = table.dynamic_ty(&store);
-                    imported.is_subtype_or_err(required)?;
+                    imported.is_subtype_or_err(required, relaxed_limits)?;
                     builder.push_table(table);
                 }
                 (ExternType::Memory(required), Extern::Memory(memory)) => {
                     let imported = memory.dynamic_ty(&store);
-                    imported.is_subtype_or_err(required)?;
+                    imported.is_subtype_or_err(required, relaxed_limits)?;
                     builder.push_memory(memory);
                 }
                 (ExternType::Global(required), Extern::Global(global)) => {
@@ -353,4 +386,64 @@ impl Module {
         }
         Ok(())
     }
+
+    /// Applies the table images of the [`InstantiateOptions`] to the [`Instance`] tables.
+    fn apply_table_images(
+        &self,
+        mut context: impl AsContextMut,
+        builder: &InstanceEntityBuilder,
+        options: &InstantiateOptions,
+    ) -> Result<(), Error> {
+        let len_funcs = self.len_funcs();
+        for image in options.table_images() {
+            let table_index = image.table_index();
+            if table_index as usize >= self.len_tables() {
+                return Err(Error::from(InstantiationError::InvalidTableImage {
+                    table_index,
+                }));
+            }
+            let table = builder.get_table(table_index);
+            for (n, func_index) in image.funcs().iter().copied().enumerate() {
+                let func = match func_index {
+                    Some(func_index) if func_index as usize >= len_funcs => {
+                        return Err(Error::from(InstantiationError::InvalidTableImage {
+                            table_index,
+                        }))
+                    }
+                    Some(func_index) => FuncRef::new(builder.get_func(func_index)),
+                    None => FuncRef::null(),
+                };
+                let index = u32::try_from(n)
+                    .ok()
+                    .and_then(|n| image.offset().checked_add(n))
+                    .ok_or(InstantiationError::InvalidTableImage { table_index })?;
+                table
+                    .set(context.as_context_mut(), index, Val::FuncRef(func))
+                    .map_err(InstantiationError::from)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the memory images of the [`InstantiateOptions`] to the [`Instance`] linear memories.
+    fn apply_memory_images(
+        &self,
+        mut context: impl AsContextMut,
+        builder: &InstanceEntityBuilder,
+        options: &InstantiateOptions,
+    ) -> Result<(), Error> {
+        for image in options.memory_images() {
+            let memory_index = image.memory_index();
+            if memory_index as usize >= self.len_memories() {
+                return Err(Error::from(InstantiationError::InvalidMemoryImage {
+                    memory_index,
+                }));
+            }
+            let memory = builder.get_memory(memory_index);
+            memory
+                .write(context.as_context_mut(), image.offset(), image.bytes())
+                .map_err(InstantiationError::from)?;
+        }
+        Ok(())
+    }
 }
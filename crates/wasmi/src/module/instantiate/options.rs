@@ -0,0 +1,183 @@
+use crate::{module::ImportName, Capabilities};
+use alloc::{boxed::Box, vec::Vec};
+
+/// Options to customize the instantiation of a [`Module`].
+///
+/// # Note
+///
+/// Memory and table images are applied after the data and element segments of the
+/// [`Module`] have been initialized and before the `start` function runs.
+/// Therefore they override or supplement the contents of the segments.
+/// This allows snapshot-based cold starts without running initialization
+/// code in the guest.
+///
+/// Use [`Linker::instantiate_with_options`] to apply [`InstantiateOptions`].
+///
+/// [`Module`]: crate::Module
+/// [`Linker::instantiate_with_options`]: crate::Linker::instantiate_with_options
+#[derive(Debug, Default, Clone)]
+pub struct InstantiateOptions {
+    /// The memory images applied in order upon instantiation.
+    memory_images: Vec<MemoryImage>,
+    /// The table images applied in order upon instantiation.
+    table_images: Vec<TableImage>,
+    /// The capabilities granted to the instantiation.
+    capabilities: Capabilities,
+    /// The function imports that are resolved upon their first call.
+    late_bound: Vec<ImportName>,
+}
+
+/// Initial contents written to a linear memory upon instantiation.
+#[derive(Debug, Clone)]
+pub struct MemoryImage {
+    /// The index of the linear memory within the instantiated module.
+    memory_index: u32,
+    /// The byte offset at which `bytes` are written.
+    offset: usize,
+    /// The bytes written to the linear memory.
+    bytes: Box<[u8]>,
+}
+
+impl MemoryImage {
+    /// Returns the index of the linear memory within the instantiated module.
+    pub fn memory_index(&self) -> u32 {
+        self.memory_index
+    }
+
+    /// Returns the byte offset at which the bytes are written.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the bytes written to the linear memory.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Initial function references written to a table upon instantiation.
+#[derive(Debug, Clone)]
+pub struct TableImage {
+    /// The index of the table within the instantiated module.
+    table_index: u32,
+    /// The table index at which `funcs` are written.
+    offset: u32,
+    /// The function indices written to the table.
+    ///
+    /// `None` entries write `null` function references.
+    funcs: Box<[Option<u32>]>,
+}
+
+impl TableImage {
+    /// Returns the index of the table within the instantiated module.
+    pub fn table_index(&self) -> u32 {
+        self.table_index
+    }
+
+    /// Returns the table index at which the function references are written.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the function indices written to the table.
+    ///
+    /// `None` entries represent `null` function references.
+    pub fn funcs(&self) -> &[Option<u32>] {
+        &self.funcs
+    }
+}
+
+impl InstantiateOptions {
+    /// Creates new [`InstantiateOptions`] that do not alter instantiation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes `bytes` at `offset` into the linear memory at `memory_index` upon instantiation.
+    ///
+    /// The `memory_index` refers to the module's memory index space including imported memories.
+    pub fn memory_image(
+        mut self,
+        memory_index: u32,
+        offset: usize,
+        bytes: impl Into<Box<[u8]>>,
+    ) -> Self {
+        self.memory_images.push(MemoryImage {
+            memory_index,
+            offset,
+            bytes: bytes.into(),
+        });
+        self
+    }
+
+    /// Writes function references at `offset` into the table at `table_index` upon instantiation.
+    ///
+    /// - The `table_index` refers to the module's table index space including imported tables.
+    /// - The `funcs` refer to the module's function index space including imported functions.
+    ///   `None` entries write `null` function references.
+    pub fn table_image(
+        mut self,
+        table_index: u32,
+        offset: u32,
+        funcs: impl IntoIterator<Item = Option<u32>>,
+    ) -> Self {
+        self.table_images.push(TableImage {
+            table_index,
+            offset,
+            funcs: funcs.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Grants the `capabilities` to the instantiation.
+    ///
+    /// Read more about capabilities in [`Linker::capability`].
+    ///
+    /// [`Linker::capability`]: crate::Linker::capability
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Declares the function import `name` of `module` as late-bound.
+    ///
+    /// Late-bound function imports do not require a definition in the [`Linker`] upon
+    /// instantiation. Instead they are resolved upon their first call by the resolver
+    /// registered via [`Linker::late_binding_resolver`]. Calls to late-bound function
+    /// imports that cannot be resolved trap.
+    ///
+    /// # Note
+    ///
+    /// - The [`ImportPolicy`] and capabilities still apply to late-bound imports.
+    /// - Late-bound imports of items other than functions fail to instantiate.
+    ///
+    /// [`Linker`]: crate::Linker
+    /// [`Linker::late_binding_resolver`]: crate::Linker::late_binding_resolver
+    /// [`ImportPolicy`]: crate::ImportPolicy
+    pub fn late_bound(mut self, module: &str, name: &str) -> Self {
+        self.late_bound.push(ImportName::new(module, name));
+        self
+    }
+
+    /// Returns `true` if the import `name` of `module` is declared as late-bound.
+    pub fn is_late_bound(&self, module: &str, name: &str) -> bool {
+        self.late_bound
+            .iter()
+            .any(|import| import.module() == module && import.name() == name)
+    }
+
+    /// Returns the memory images in the order in which they are applied.
+    pub fn memory_images(&self) -> &[MemoryImage] {
+        &self.memory_images
+    }
+
+    /// Returns the table images in the order in which they are applied.
+    pub fn table_images(&self) -> &[TableImage] {
+        &self.table_images
+    }
+
+    /// Returns the [`Capabilities`] granted to the instantiation.
+    pub fn get_capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+}
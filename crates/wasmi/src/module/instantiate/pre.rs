@@ -59,6 +59,36 @@ impl InstancePre {
         Ok(self.handle)
     }
 
+    /// Finishes instantiation without running the `start` function of the [`Instance`].
+    ///
+    /// # Note
+    ///
+    /// - The `start` function is deferred and must be executed later using
+    ///   [`Instance::run_start`] or [`Instance::run_start_with_fuel`] for
+    ///   conformant module instantiation.
+    /// - This allows hosts to set up memory contents before the `start` function executes.
+    /// - Use [`Instance::has_pending_start`] to query whether the `start` function already ran.
+    ///
+    /// # Panics
+    ///
+    /// If the `start` function is invalid albeit successful validation.
+    pub fn defer_start(self, mut context: impl AsContextMut) -> Instance {
+        let opt_start_index = self.start_fn();
+        let mut entity = self.builder.finish();
+        if let Some(start_index) = opt_start_index {
+            let start_func = entity.get_func(start_index).unwrap_or_else(|| {
+                panic!("encountered invalid start function after validation: {start_index}")
+            });
+            entity.set_pending_start(start_func);
+        }
+        context
+            .as_context_mut()
+            .store
+            .inner
+            .initialize_instance(self.handle, entity);
+        self.handle
+    }
+
     /// Finishes instantiation ensuring that no `start` function exists.
     ///
     /// # Errors
@@ -1,17 +1,56 @@
+mod adapter;
+mod branch_hints;
 mod builder;
+mod call_graph;
+mod code_size;
+mod compat;
+mod constant_time;
+mod content_hash;
 mod custom_section;
 mod data;
 mod element;
 mod export;
+mod features;
 mod global;
 mod import;
 mod init_expr;
 mod instantiate;
 mod parser;
 mod read;
+mod safepoints;
+mod scan;
+mod signature;
+mod stack_usage;
+mod streaming_control;
 pub(crate) mod utils;
 
+pub use self::{
+    adapter::ModuleAdapter,
+    branch_hints::BranchHint,
+    call_graph::{CallEdge, CallGraph, CallTarget, InstrHistogram},
+    compat::{CompatIssue, CompatReport},
+    constant_time::{
+        ConstantTimeAudit,
+        ConstantTimeReport,
+        ConstantTimeViolation,
+        ConstantTimeViolationKind,
+        CONSTANT_TIME_SECTION,
+    },
+    custom_section::{CustomSection, CustomSectionsIter},
+    export::{ExportType, FuncIdx, MemoryIdx, ModuleExportsIter, TableIdx},
+    features::ModuleFeatures,
+    global::GlobalIdx,
+    import::{FuncTypeIdx, ImportName},
+    instantiate::{InstancePre, InstantiateOptions, InstantiationError, MemoryImage, TableImage},
+    read::{Read, ReadError},
+    safepoints::{BackEdge, Safepoint, SafepointKind, Safepoints},
+    scan::ScannedImport,
+    signature::{ModuleSignature, ModuleVerifier},
+    stack_usage::{StackAnalysis, StackUsage},
+    streaming_control::{CancellationToken, StreamingControl, StreamingProgress},
+};
 use self::{
+    branch_hints::BranchHints,
     builder::ModuleBuilder,
     custom_section::{CustomSections, CustomSectionsBuilder},
     export::ExternIdx,
@@ -19,14 +58,6 @@ use self::{
     import::{ExternTypeIdx, Import},
     parser::ModuleParser,
 };
-pub use self::{
-    custom_section::{CustomSection, CustomSectionsIter},
-    export::{ExportType, FuncIdx, MemoryIdx, ModuleExportsIter, TableIdx},
-    global::GlobalIdx,
-    import::{FuncTypeIdx, ImportName},
-    instantiate::{InstancePre, InstantiationError},
-    read::{Read, ReadError},
-};
 pub(crate) use self::{
     data::{DataSegment, DataSegments, InitDataSegment, PassiveDataSegmentBytes},
     element::{ElementSegment, ElementSegmentKind},
@@ -35,7 +66,15 @@ pub(crate) use self::{
 };
 use crate::{
     collections::Map,
-    engine::{DedupFuncType, EngineFunc, EngineFuncSpan, EngineFuncSpanIter, EngineWeak},
+    engine::{
+        DedupFuncType,
+        EngineFunc,
+        EngineFuncSpan,
+        EngineFuncSpanIter,
+        EngineWeak,
+        ExtensionOp,
+        ExtensionOps,
+    },
     Engine,
     Error,
     ExternType,
@@ -44,7 +83,7 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, format, sync::Arc};
 use core::{iter, slice::Iter as SliceIter};
 use wasmparser::{FuncValidatorAllocations, Parser, ValidPayload, Validator};
 
@@ -58,9 +97,14 @@ pub struct Module {
 #[derive(Debug)]
 struct ModuleInner {
     engine: Engine,
+    /// The stable hash of the Wasm bytecode of the [`Module`].
+    hash: u64,
+    /// The signature attached via [`Module::new_signed`] if any.
+    signature: Option<ModuleSignature>,
     header: ModuleHeader,
     data_segments: DataSegments,
     custom_sections: CustomSections,
+    features: ModuleFeatures,
 }
 
 /// A parsed and validated WebAssembly module header.
@@ -73,6 +117,8 @@ pub struct ModuleHeader {
 struct ModuleHeaderInner {
     engine: EngineWeak,
     func_types: Arc<[DedupFuncType]>,
+    /// The extension instructions of imported functions if any.
+    extension_ops: ExtensionOps,
     imports: ModuleImports,
     funcs: Box<[DedupFuncType]>,
     tables: Box<[TableType]>,
@@ -83,6 +129,8 @@ struct ModuleHeaderInner {
     start: Option<FuncIdx>,
     engine_funcs: EngineFuncSpan,
     element_segments: Box<[ElementSegment]>,
+    /// The branch hints of the Wasm functions.
+    branch_hints: BranchHints,
 }
 
 impl ModuleHeader {
@@ -106,6 +154,15 @@ impl ModuleHeader {
         &self.inner.globals[global_idx.into_u32() as usize]
     }
 
+    /// Returns the [`ExtensionOp`] for the given [`FuncIdx`].
+    ///
+    /// Returns `None` if [`FuncIdx`] does not refer to an imported function
+    /// that is resolved to an extension instruction.
+    pub fn get_extension_op(&self, func_idx: FuncIdx) -> Option<ExtensionOp> {
+        let index = func_idx.into_u32() as usize;
+        self.inner.extension_ops.get(index).copied().flatten()
+    }
+
     /// Returns the [`EngineFunc`] for the given [`FuncIdx`].
     ///
     /// Returns `None` if [`FuncIdx`] refers to an imported function.
@@ -220,6 +277,46 @@ impl Module {
         ModuleParser::new(engine).parse_buffered(wasm)
     }
 
+    /// Creates a new Wasm [`Module`] from Wasm bytecode that is built into Wasmi.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Module::new`] this neither accepts the Wasm text format nor requires
+    /// a [`ModuleSignature`] if the `engine` requires signed modules.
+    pub(crate) fn new_builtin(engine: &Engine, wasm: &[u8]) -> Result<Self, Error> {
+        ModuleParser::new(engine).builtin().parse_buffered(wasm)
+    }
+
+    /// Creates a new Wasm [`Module`] from the given Wasm bytecode buffer
+    /// and renames its imports and exports according to `adapter`.
+    ///
+    /// # Note
+    ///
+    /// This behaves like [`Module::new`] apart from the renaming.
+    /// Read more about renaming in [`ModuleAdapter`].
+    ///
+    /// # Errors
+    ///
+    /// - If the Wasm bytecode is malformed or fails to validate.
+    /// - If the Wasm bytecode violates restrictions
+    ///   set in the [`Config`] used by the `engine`.
+    /// - If Wasmi cannot translate the Wasm bytecode.
+    /// - If renaming results in duplicate export names.
+    ///
+    /// [`Config`]: crate::Config
+    pub fn new_with_adapter(
+        engine: &Engine,
+        wasm: impl AsRef<[u8]>,
+        adapter: &ModuleAdapter,
+    ) -> Result<Self, Error> {
+        let wasm = wasm.as_ref();
+        #[cfg(feature = "wat")]
+        let wasm = &wat::parse_bytes(wasm)?[..];
+        ModuleParser::new(engine)
+            .with_adapter(adapter)
+            .parse_buffered(wasm)
+    }
+
     /// Creates a new Wasm [`Module`] from the given Wasm bytecode stream.
     ///
     /// # Note
@@ -238,6 +335,29 @@ impl Module {
         ModuleParser::new(engine).parse_streaming(stream)
     }
 
+    /// Creates a new Wasm [`Module`] from the given Wasm bytecode stream under the supervision of `control`.
+    ///
+    /// This behaves like [`Module::new_streaming`] but reports the progress of the compilation
+    /// and aborts it once the [`CancellationToken`] of `control` has been cancelled.
+    /// Read more in [`StreamingControl`].
+    ///
+    /// # Errors
+    ///
+    /// - If the Wasm bytecode is malformed or fails to validate.
+    /// - If the Wasm bytecode violates restrictions
+    ///   set in the [`Config`] used by the `engine`.
+    /// - If Wasmi cannot translate the Wasm bytecode.
+    /// - If the compilation has been cancelled, see [`Error::is_cancelled`].
+    ///
+    /// [`Config`]: crate::Config
+    pub fn new_streaming_with_control(
+        engine: &Engine,
+        stream: impl Read,
+        mut control: StreamingControl,
+    ) -> Result<Self, Error> {
+        ModuleParser::new(engine).parse_streaming_with_control(stream, &mut control)
+    }
+
     /// Creates a new Wasm [`Module`] from the given Wasm bytecode buffer.
     ///
     /// # Note
@@ -296,6 +416,20 @@ impl Module {
         &self.inner.engine
     }
 
+    /// Returns the stable 64-bit FNV-1a hash of the Wasm bytecode of the [`Module`].
+    ///
+    /// Modules created from the same Wasm bytecode have the same hash across
+    /// engines, platforms and Wasmi versions. Symbols registered via
+    /// [`Engine::register_symbols`] apply to all modules with the same hash.
+    ///
+    /// # Note
+    ///
+    /// For modules created from the WebAssembly text format this is the hash
+    /// of the resulting WebAssembly binary.
+    pub fn hash(&self) -> u64 {
+        self.inner.hash
+    }
+
     /// Returns a shared reference to the [`ModuleHeaderInner`].
     fn module_header(&self) -> &ModuleHeaderInner {
         &self.inner.header.inner
@@ -487,6 +621,80 @@ impl Module {
     pub fn custom_sections(&self) -> CustomSectionsIter {
         self.inner.custom_sections.iter()
     }
+
+    /// Returns the Wasm proposals that are actually used by the [`Module`].
+    ///
+    /// This allows hosts to enforce policies per [`Module`], e.g. rejecting modules
+    /// that use floating point operations or the `bulk-memory` Wasm proposal,
+    /// even if the [`Engine`] has those Wasm proposals enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{Engine, Module};
+    /// let engine = Engine::default();
+    /// let wasm = r#"(module (func (param f32) (result f32) (local.get 0)))"#;
+    /// let module = Module::new(&engine, wasm).unwrap();
+    /// assert!(module.required_features().contains("floats"));
+    /// assert!(!module.required_features().contains("bulk-memory"));
+    /// ```
+    pub fn required_features(&self) -> &ModuleFeatures {
+        &self.inner.features
+    }
+
+    /// Translates the function at `func_idx` of the [`Module`] ahead of time.
+    ///
+    /// This allows to translate functions on the hot path early with lazy
+    /// compilation modes while keeping the rest of the functions lazy.
+    ///
+    /// # Note
+    ///
+    /// - The `func_idx` is the Wasm function index including imported functions.
+    /// - This does nothing if the function has already been translated, e.g. because
+    ///   the [`Module`] was compiled with [`CompilationMode::Eager`].
+    /// - Translating functions ahead of time does not consume fuel.
+    ///
+    /// # Errors
+    ///
+    /// - If `func_idx` is out of bounds or refers to an imported function.
+    /// - If translation or Wasm validation of the function fails.
+    ///
+    /// [`CompilationMode::Eager`]: crate::CompilationMode::Eager
+    pub fn translate_function(&self, func_idx: u32) -> Result<(), Error> {
+        let header = &self.inner.header;
+        let len_funcs = header.inner.funcs.len() as u32;
+        if func_idx >= len_funcs {
+            return Err(Error::new(format!(
+                "function index {func_idx} is out of bounds for {len_funcs} functions"
+            )));
+        }
+        let Some(engine_func) = header.get_engine_func(FuncIdx::from(func_idx)) else {
+            return Err(Error::new(format!(
+                "cannot translate imported function at index {func_idx}"
+            )));
+        };
+        self.engine().ensure_compiled(engine_func)
+    }
+
+    /// Translates all functions of the [`Module`] ahead of time.
+    ///
+    /// This surfaces translation and Wasm validation errors of lazy compilation modes
+    /// at a deterministic point in time instead of upon the first call of a function.
+    ///
+    /// # Note
+    ///
+    /// Translating functions ahead of time does not consume fuel.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of any function fails.
+    pub fn translate_all(&self) -> Result<(), Error> {
+        let engine = self.engine();
+        for engine_func in self.module_header().engine_funcs.iter() {
+            engine.ensure_compiled(engine_func)?;
+        }
+        Ok(())
+    }
 }
 
 /// An iterator over the imports of a [`Module`].
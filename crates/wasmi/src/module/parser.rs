@@ -1,4 +1,5 @@
 use super::{
+    branch_hints::BranchHints,
     builder::ModuleHeaderBuilder,
     export::ExternIdx,
     global::Global,
@@ -6,11 +7,16 @@ use super::{
     CustomSectionsBuilder,
     ElementSegment,
     FuncIdx,
+    ModuleAdapter,
     ModuleBuilder,
+    ModuleFeatures,
     ModuleHeader,
+    ModuleSignature,
+    StreamingControl,
+    StreamingProgress,
 };
 use crate::{
-    engine::{EnforcedLimitsError, EngineFunc},
+    engine::{EnforcedLimits, EnforcedLimitsError, EngineFunc},
     Engine,
     Error,
     FuncType,
@@ -29,7 +35,9 @@ use wasmparser::{
     FunctionSectionReader,
     GlobalSectionReader,
     ImportSectionReader,
+    KnownCustom,
     MemorySectionReader,
+    Operator,
     Parser as WasmParser,
     Payload,
     TableSectionReader,
@@ -55,6 +63,16 @@ pub struct ModuleParser {
     engine_funcs: u32,
     /// Flag, `true` when `stream` is at the end.
     eof: bool,
+    /// The optional adapter renaming imports and exports.
+    adapter: Option<ModuleAdapter>,
+    /// The remaining compilation fuel if compilation fuel is metered.
+    compilation_fuel: Option<u64>,
+    /// The Wasm proposals used by the parsed function types and function bodies.
+    features: ModuleFeatures,
+    /// The verified signature of the parsed Wasm bytecode if any.
+    signature: Option<ModuleSignature>,
+    /// Is `true` if the parsed Wasm bytecode is built into Wasmi and thus needs no signature.
+    builtin: bool,
 }
 
 impl ModuleParser {
@@ -67,9 +85,49 @@ impl ModuleParser {
             parser,
             engine_funcs: 0,
             eof: false,
+            adapter: None,
+            compilation_fuel: engine.config().get_compilation_fuel(),
+            features: ModuleFeatures::default(),
+            signature: None,
+            builtin: false,
         }
     }
 
+    /// Renames imports and exports of the parsed [`Module`] according to `adapter`.
+    pub fn with_adapter(mut self, adapter: &ModuleAdapter) -> Self {
+        if !adapter.is_empty() {
+            self.adapter = Some(adapter.clone());
+        }
+        self
+    }
+
+    /// Attaches the verified `signature` to the parsed [`Module`].
+    pub fn with_signature(mut self, signature: ModuleSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// Marks the parsed Wasm bytecode as built into Wasmi which exempts it from signature checks.
+    pub fn builtin(mut self) -> Self {
+        self.builtin = true;
+        self
+    }
+
+    /// Checks that the parsed Wasm bytecode is signed if the [`Engine`] requires signed modules.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Engine`] requires signed modules and no signature has been attached.
+    fn check_signed(&self) -> Result<(), Error> {
+        if self.builtin || self.signature.is_some() {
+            return Ok(());
+        }
+        if self.engine.config().get_module_verifier().is_some() {
+            return Err(Error::new("engine requires signed Wasm modules"));
+        }
+        Ok(())
+    }
+
     /// Processes the end of the Wasm binary.
     fn process_end(&mut self, offset: usize) -> Result<(), Error> {
         if let Some(validator) = &mut self.validator {
@@ -108,13 +166,21 @@ impl ModuleParser {
         section: TypeSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        let limits = self.engine.config().get_enforced_limits();
+        Self::enforce_section_items(limits, section.count())?;
+        if let Some(limit) = limits.max_types {
+            if section.count() > limit {
+                return Err(Error::from(EnforcedLimitsError::TooManyTypes { limit }));
+            }
+        }
         if let Some(validator) = &mut self.validator {
             validator.type_section(&section)?;
         }
-        let limits = self.engine.config().get_enforced_limits();
+        let features = &mut self.features;
         let func_types = section.into_iter().map(|result| {
             let ty = result?.into_types().next().unwrap();
             let func_ty = ty.unwrap_func();
+            features.visit_func_type(func_ty);
             if let Some(limit) = limits.max_params {
                 if func_ty.params().len() > limit {
                     return Err(Error::from(EnforcedLimitsError::TooManyParameters {
@@ -148,12 +214,24 @@ impl ModuleParser {
         section: ImportSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        let limits = self.engine.config().get_enforced_limits();
+        Self::enforce_section_items(limits, section.count())?;
+        if let Some(limit) = limits.max_imports {
+            if section.count() > limit {
+                return Err(Error::from(EnforcedLimitsError::TooManyImports { limit }));
+            }
+        }
         if let Some(validator) = &mut self.validator {
             validator.import_section(&section)?;
         }
-        let imports = section
-            .into_iter()
-            .map(|import| import.map(Import::from).map_err(Error::from));
+        let adapter = self.adapter.as_ref();
+        let imports = section.into_iter().map(|import| {
+            let import = Import::from(import?);
+            match adapter {
+                Some(adapter) => Ok(import.adapt(adapter)),
+                None => Ok(import),
+            }
+        });
         header.push_imports(imports)?;
         Ok(())
     }
@@ -172,6 +250,7 @@ impl ModuleParser {
         section: FunctionSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        Self::enforce_section_items(self.engine.config().get_enforced_limits(), section.count())?;
         if let Some(limit) = self.engine.config().get_enforced_limits().max_functions {
             if section.count() > limit {
                 return Err(Error::from(EnforcedLimitsError::TooManyFunctions { limit }));
@@ -201,6 +280,7 @@ impl ModuleParser {
         section: TableSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        Self::enforce_section_items(self.engine.config().get_enforced_limits(), section.count())?;
         if let Some(limit) = self.engine.config().get_enforced_limits().max_tables {
             if section.count() > limit {
                 return Err(Error::from(EnforcedLimitsError::TooManyTables { limit }));
@@ -234,6 +314,7 @@ impl ModuleParser {
         section: MemorySectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        Self::enforce_section_items(self.engine.config().get_enforced_limits(), section.count())?;
         if let Some(limit) = self.engine.config().get_enforced_limits().max_memories {
             if section.count() > limit {
                 return Err(Error::from(EnforcedLimitsError::TooManyMemories { limit }));
@@ -263,6 +344,7 @@ impl ModuleParser {
         section: GlobalSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        Self::enforce_section_items(self.engine.config().get_enforced_limits(), section.count())?;
         if let Some(limit) = self.engine.config().get_enforced_limits().max_globals {
             if section.count() > limit {
                 return Err(Error::from(EnforcedLimitsError::TooManyGlobals { limit }));
@@ -292,12 +374,16 @@ impl ModuleParser {
         section: ExportSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        Self::enforce_section_items(self.engine.config().get_enforced_limits(), section.count())?;
         if let Some(validator) = &mut self.validator {
             validator.export_section(&section)?;
         }
         let exports = section.into_iter().map(|export| {
             let export = export?;
-            let field: Box<str> = export.name.into();
+            let field: Box<str> = match &self.adapter {
+                Some(adapter) => adapter.adapt_export(export.name).into(),
+                None => export.name.into(),
+            };
             let idx = ExternIdx::new(export.kind, export.index)?;
             Ok((field, idx))
         });
@@ -341,6 +427,7 @@ impl ModuleParser {
         section: ElementSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        Self::enforce_section_items(self.engine.config().get_enforced_limits(), section.count())?;
         if let Some(limit) = self
             .engine
             .config()
@@ -397,6 +484,7 @@ impl ModuleParser {
         section: DataSectionReader,
         builder: &mut ModuleBuilder,
     ) -> Result<(), Error> {
+        Self::enforce_section_items(self.engine.config().get_enforced_limits(), section.count())?;
         if let Some(limit) = self.engine.config().get_enforced_limits().max_data_segments {
             if section.count() > limit {
                 return Err(Error::from(EnforcedLimitsError::TooManyDataSegments {
@@ -435,6 +523,7 @@ impl ModuleParser {
         size: u32,
     ) -> Result<(), Error> {
         let enforced_limits = self.engine.config().get_enforced_limits();
+        Self::enforce_section_items(enforced_limits, count)?;
         if let Some(limit) = enforced_limits.max_functions {
             if count > limit {
                 return Err(Error::from(EnforcedLimitsError::TooManyFunctions { limit }));
@@ -488,6 +577,9 @@ impl ModuleParser {
         bytes: &[u8],
         header: &ModuleHeader,
     ) -> Result<(), Error> {
+        self.enforce_func_body_limits(&func_body)?;
+        self.consume_compilation_fuel(&func_body, bytes)?;
+        self.features.visit_func_body(&func_body)?;
         let (func, engine_func) = self.next_func(header);
         let module = header.clone();
         let offset = func_body.get_binary_reader().original_position();
@@ -500,6 +592,123 @@ impl ModuleParser {
         Ok(())
     }
 
+    /// Enforces the section items limit of `limits` for a section with `count` items.
+    ///
+    /// # Errors
+    ///
+    /// If `count` exceeds the section items limit.
+    fn enforce_section_items(limits: &EnforcedLimits, count: u32) -> Result<(), Error> {
+        if let Some(limit) = limits.max_section_items {
+            if count > limit {
+                return Err(Error::from(EnforcedLimitsError::TooManySectionItems {
+                    limit,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Enforces the `br_table` and control flow nesting limits for `func_body`.
+    ///
+    /// # Note
+    ///
+    /// This is checked while parsing the code section regardless of the [`CompilationMode`]
+    /// in use so that lazily compiled Wasm modules are also subject to the limits.
+    ///
+    /// # Errors
+    ///
+    /// - If the operators of `func_body` are malformed.
+    /// - If `func_body` exceeds the `br_table` or control flow nesting limits.
+    ///
+    /// [`CompilationMode`]: crate::CompilationMode
+    fn enforce_func_body_limits(&self, func_body: &FunctionBody) -> Result<(), Error> {
+        let limits = self.engine.config().get_enforced_limits();
+        let (max_targets, max_depth) = (limits.max_br_table_targets, limits.max_nesting_depth);
+        if max_targets.is_none() && max_depth.is_none() {
+            return Ok(());
+        }
+        let mut depth = 0_u32;
+        let mut operators = func_body.get_operators_reader()?;
+        while !operators.eof() {
+            match operators.read()? {
+                Operator::Block { .. } | Operator::Loop { .. } | Operator::If { .. } => {
+                    depth += 1;
+                    if let Some(limit) = max_depth.filter(|&limit| depth > limit) {
+                        return Err(Error::from(EnforcedLimitsError::TooDeeplyNested { limit }));
+                    }
+                }
+                Operator::End => {
+                    depth = depth.saturating_sub(1);
+                }
+                Operator::BrTable { targets } => {
+                    if let Some(limit) = max_targets.filter(|&limit| targets.len() > limit) {
+                        return Err(Error::from(EnforcedLimitsError::TooManyBrTableTargets {
+                            limit,
+                        }));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes the compilation fuel required to translate the function body `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// - If the local variable declarations of `func_body` are malformed.
+    /// - If the remaining compilation fuel does not suffice.
+    fn consume_compilation_fuel(
+        &mut self,
+        func_body: &FunctionBody,
+        bytes: &[u8],
+    ) -> Result<(), Error> {
+        let Some(remaining) = self.compilation_fuel else {
+            return Ok(());
+        };
+        let mut required = bytes.len() as u64;
+        let mut locals = func_body.get_locals_reader()?;
+        for _ in 0..locals.get_count() {
+            let (count, _) = locals.read()?;
+            required = required.saturating_add(u64::from(count));
+        }
+        match remaining.checked_sub(required) {
+            Some(remaining) => {
+                self.compilation_fuel = Some(remaining);
+                Ok(())
+            }
+            None => {
+                let limit = self
+                    .engine
+                    .config()
+                    .get_compilation_fuel()
+                    .unwrap_or_default();
+                Err(Error::from(EnforcedLimitsError::OutOfCompilationFuel {
+                    limit,
+                }))
+            }
+        }
+    }
+
+    /// Process the branch hints of the Wasm custom section if it is a branch hinting section.
+    ///
+    /// # Note
+    ///
+    /// Malformed branch hinting sections are ignored since they do not affect semantics.
+    fn process_branch_hints(
+        &mut self,
+        reader: &CustomSectionReader,
+        header: &mut ModuleHeaderBuilder,
+    ) {
+        if self.engine.config().get_ignore_custom_sections() {
+            return;
+        }
+        if let KnownCustom::BranchHints(section) = reader.as_known() {
+            header.branch_hints = BranchHints::from_section(section).unwrap_or_default();
+        }
+    }
+
     /// Process a single Wasm custom section.
     fn process_custom_section(
         &mut self,
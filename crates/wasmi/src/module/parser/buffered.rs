@@ -5,7 +5,7 @@ use super::{
     ModuleHeaderBuilder,
     ModuleParser,
 };
-use crate::{Error, Module};
+use crate::{engine::StableHasher, Error, Module};
 use wasmparser::{Chunk, Payload, Validator};
 
 impl ModuleParser {
@@ -57,10 +57,13 @@ impl ModuleParser {
     ///
     /// If the Wasm bytecode stream fails to validate.
     unsafe fn parse_buffered_impl(mut self, mut buffer: &[u8]) -> Result<Module, Error> {
+        self.check_signed()?;
+        let mut hasher = StableHasher::default();
+        hasher.write_bytes(buffer);
         let mut custom_sections = CustomSectionsBuilder::default();
         let header = Self::parse_buffered_header(&mut self, &mut buffer, &mut custom_sections)?;
         let builder = Self::parse_buffered_code(&mut self, &mut buffer, header, custom_sections)?;
-        let module = Self::parse_buffered_data(&mut self, &mut buffer, builder)?;
+        let module = Self::parse_buffered_data(&mut self, &mut buffer, builder, hasher.finish())?;
         Ok(module)
     }
 
@@ -130,6 +133,7 @@ impl ModuleParser {
                 Payload::DataSection(_) => break,
                 Payload::End(_) => break,
                 Payload::CustomSection(reader) => {
+                    self.process_branch_hints(&reader, &mut header);
                     self.process_custom_section(custom_sections, reader)
                 }
                 unexpected => self.process_invalid_payload(unexpected),
@@ -178,6 +182,8 @@ impl ModuleParser {
     /// section that comes after the Wasm code section that we have to separate
     /// out for technical reasons.
     ///
+    /// The `hash` is the [`Module::hash`] of the whole Wasm bytecode.
+    ///
     /// # Errors
     ///
     /// If the Wasm bytecode stream fails to parse or validate.
@@ -185,6 +191,7 @@ impl ModuleParser {
         &mut self,
         buffer: &mut &[u8],
         mut builder: ModuleBuilder,
+        hash: u64,
     ) -> Result<Module, Error> {
         loop {
             let (consumed, payload) = self.next_payload(buffer)?;
@@ -203,6 +210,6 @@ impl ModuleParser {
             }
             Self::consume_buffer(consumed, buffer);
         }
-        Ok(builder.finish(&self.engine))
+        Ok(builder.finish(&self.engine, self.features, hash, self.signature.take()))
     }
 }
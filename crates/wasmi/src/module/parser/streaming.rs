@@ -4,8 +4,10 @@ use super::{
     ModuleHeader,
     ModuleHeaderBuilder,
     ModuleParser,
+    StreamingControl,
+    StreamingProgress,
 };
-use crate::{Error, Module, Read};
+use crate::{engine::StableHasher, Error, Module, Read};
 use alloc::vec::Vec;
 use core::ops::{Deref, DerefMut};
 use wasmparser::{Chunk, Payload, Validator};
@@ -14,13 +16,19 @@ use wasmparser::{Chunk, Payload, Validator};
 #[derive(Debug, Default, Clone)]
 struct ParseBuffer {
     buffer: Vec<u8>,
+    /// The number of bytes consumed so far.
+    consumed: usize,
+    /// Hashes the consumed bytes in order to compute the [`Module::hash`].
+    hasher: StableHasher,
 }
 
 impl ParseBuffer {
     /// Drops the first `amount` bytes from the [`ParseBuffer`] as they have been consumed.
     #[inline]
     fn consume(buffer: &mut Self, amount: usize) {
+        buffer.hasher.write_bytes(&buffer.buffer[..amount]);
         buffer.drain(..amount);
+        buffer.consumed += amount;
     }
 
     /// Pulls more bytes from the `stream` in order to produce Wasm payload.
@@ -71,12 +79,28 @@ impl ModuleParser {
     /// # Errors
     ///
     /// If the Wasm bytecode stream fails to validate.
-    pub fn parse_streaming(mut self, stream: impl Read) -> Result<Module, Error> {
+    pub fn parse_streaming(self, stream: impl Read) -> Result<Module, Error> {
+        self.parse_streaming_with_control(stream, &mut StreamingControl::new())
+    }
+
+    /// Parses and validates the Wasm bytecode `stream` under the supervision of `control`.
+    ///
+    /// Returns the compiled and validated Wasm [`Module`] upon success.
+    ///
+    /// # Errors
+    ///
+    /// - If the Wasm bytecode stream fails to validate.
+    /// - If the streaming compilation has been cancelled via `control`.
+    pub fn parse_streaming_with_control(
+        mut self,
+        stream: impl Read,
+        control: &mut StreamingControl,
+    ) -> Result<Module, Error> {
         let features = self.engine.config().wasm_features();
         self.validator = Some(Validator::new_with_features(features));
         // SAFETY: we just pre-populated the Wasm module parser with a validator
         //         thus calling this method is safe.
-        unsafe { self.parse_streaming_impl(stream) }
+        unsafe { self.parse_streaming_impl(stream, control) }
     }
 
     /// Parses the Wasm bytecode `stream` without Wasm validation.
@@ -92,7 +116,7 @@ impl ModuleParser {
     ///
     /// If the Wasm bytecode stream fails to validate.
     pub unsafe fn parse_streaming_unchecked(self, stream: impl Read) -> Result<Module, Error> {
-        unsafe { self.parse_streaming_impl(stream) }
+        unsafe { self.parse_streaming_impl(stream, &mut StreamingControl::new()) }
     }
 
     /// Starts parsing and validating the Wasm bytecode stream.
@@ -110,8 +134,14 @@ impl ModuleParser {
     ///
     /// # Errors
     ///
-    /// If the Wasm bytecode stream fails to validate.
-    unsafe fn parse_streaming_impl(mut self, mut stream: impl Read) -> Result<Module, Error> {
+    /// - If the Wasm bytecode stream fails to validate.
+    /// - If the streaming compilation has been cancelled via `control`.
+    unsafe fn parse_streaming_impl(
+        mut self,
+        mut stream: impl Read,
+        control: &mut StreamingControl,
+    ) -> Result<Module, Error> {
+        self.check_signed()?;
         let mut custom_sections = CustomSectionsBuilder::default();
         let mut buffer = ParseBuffer::default();
         let header = Self::parse_streaming_header(
@@ -119,6 +149,7 @@ impl ModuleParser {
             &mut stream,
             &mut buffer,
             &mut custom_sections,
+            control,
         )?;
         let builder = Self::parse_streaming_code(
             &mut self,
@@ -126,8 +157,10 @@ impl ModuleParser {
             &mut buffer,
             header,
             custom_sections,
+            control,
         )?;
-        let module = Self::parse_streaming_data(&mut self, &mut stream, &mut buffer, builder)?;
+        let module =
+            Self::parse_streaming_data(&mut self, &mut stream, &mut buffer, builder, control)?;
         Ok(module)
     }
 
@@ -146,11 +179,13 @@ impl ModuleParser {
         stream: &mut impl Read,
         buffer: &mut ParseBuffer,
         custom_sections: &mut CustomSectionsBuilder,
+        control: &mut StreamingControl,
     ) -> Result<ModuleHeader, Error> {
         let mut header = ModuleHeaderBuilder::new(&self.engine);
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
+                    control.check_cancelled()?;
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
                     if self.eof {
                         break;
@@ -192,17 +227,20 @@ impl ModuleParser {
                         Payload::CodeSectionStart { count, range, size } => {
                             self.process_code_start(count, range, size)?;
                             ParseBuffer::consume(buffer, consumed);
+                            control.report(self.streaming_progress(buffer))?;
                             break;
                         }
                         Payload::DataSection(_) => break,
                         Payload::End(_) => break,
                         Payload::CustomSection(reader) => {
+                            self.process_branch_hints(&reader, &mut header);
                             self.process_custom_section(custom_sections, reader)
                         }
                         unexpected => self.process_invalid_payload(unexpected),
                     }?;
                     // Cut away the parts from the intermediate buffer that have already been parsed.
                     ParseBuffer::consume(buffer, consumed);
+                    control.report(self.streaming_progress(buffer))?;
                 }
             }
         }
@@ -224,10 +262,12 @@ impl ModuleParser {
         buffer: &mut ParseBuffer,
         header: ModuleHeader,
         custom_sections: CustomSectionsBuilder,
+        control: &mut StreamingControl,
     ) -> Result<ModuleBuilder, Error> {
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
+                    control.check_cancelled()?;
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
@@ -244,6 +284,7 @@ impl ModuleParser {
                     }
                     // Cut away the parts from the intermediate buffer that have already been parsed.
                     ParseBuffer::consume(buffer, consumed);
+                    control.report(self.streaming_progress(buffer))?;
                 }
             }
         }
@@ -264,10 +305,12 @@ impl ModuleParser {
         stream: &mut impl Read,
         buffer: &mut ParseBuffer,
         mut builder: ModuleBuilder,
+        control: &mut StreamingControl,
     ) -> Result<Module, Error> {
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
+                    control.check_cancelled()?;
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
@@ -278,6 +321,7 @@ impl ModuleParser {
                         Payload::End(offset) => {
                             self.process_end(offset)?;
                             ParseBuffer::consume(buffer, consumed);
+                            control.report(self.streaming_progress(buffer))?;
                             break;
                         }
                         Payload::CustomSection(reader) => {
@@ -287,9 +331,16 @@ impl ModuleParser {
                     }
                     // Cut away the parts from the intermediate buffer that have already been parsed.
                     ParseBuffer::consume(buffer, consumed);
+                    control.report(self.streaming_progress(buffer))?;
                 }
             }
         }
-        Ok(builder.finish(&self.engine))
+        let hash = buffer.hasher.finish();
+        Ok(builder.finish(&self.engine, self.features, hash, self.signature.take()))
+    }
+
+    /// Returns the [`StreamingProgress`] of the streaming compilation consuming `buffer`.
+    fn streaming_progress(&self, buffer: &ParseBuffer) -> StreamingProgress {
+        StreamingProgress::new(buffer.consumed, self.engine_funcs)
     }
 }
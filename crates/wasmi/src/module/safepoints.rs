@@ -0,0 +1,298 @@
+use super::{FuncIdx, Module};
+use crate::{
+    core::UntypedVal,
+    ir::{ComparatorAndOffset, Instruction, Reg},
+    Error,
+};
+use alloc::{format, vec::Vec};
+
+impl Module {
+    /// Returns the [`Safepoints`] of the function at `func_idx` in its translated Wasmi bytecode.
+    ///
+    /// Safepoints are the positions at which an execution may be preempted or interrupted,
+    /// e.g. by running out of fuel, by a [`Store::call_hook`] or by an injected trap.
+    /// Auditors of timeslicing fairness can use [`Safepoints::unchecked_back_edges`] to verify
+    /// that no loop of the function can run unbounded between two safepoints.
+    ///
+    /// # Note
+    ///
+    /// - The `func_idx` is the Wasm function index including imported functions.
+    /// - Fuel checks are only inserted if [`Config::consume_fuel`] is enabled.
+    /// - Wasmi has no epoch based interruption, thus there are no epoch checks.
+    /// - The function is compiled if it has not been compiled yet.
+    ///
+    /// # Errors
+    ///
+    /// - If `func_idx` is out of bounds or refers to an imported function.
+    /// - If translation or Wasm validation of the function fails.
+    ///
+    /// [`Store::call_hook`]: crate::Store::call_hook
+    /// [`Config::consume_fuel`]: crate::Config::consume_fuel
+    pub fn safepoints(&self, func_idx: u32) -> Result<Safepoints, Error> {
+        let header = &self.inner.header;
+        let len_funcs = header.inner.funcs.len() as u32;
+        if func_idx >= len_funcs {
+            return Err(Error::new(format!(
+                "function index {func_idx} is out of bounds for {len_funcs} functions"
+            )));
+        }
+        let Some(engine_func) = header.get_engine_func(FuncIdx::from(func_idx)) else {
+            return Err(Error::new(format!(
+                "imported function at index {func_idx} has no safepoints"
+            )));
+        };
+        self.engine()
+            .resolve_compiled(engine_func, |instrs, consts, _| {
+                Safepoints::analyze(instrs, consts)
+            })
+    }
+}
+
+/// The safepoints and loop back edges of a function in its translated Wasmi bytecode.
+///
+/// Returned by [`Module::safepoints`].
+#[derive(Debug, Clone)]
+pub struct Safepoints {
+    /// The number of instruction words of the function.
+    len_instrs: u32,
+    /// The safepoints sorted by their position.
+    safepoints: Vec<Safepoint>,
+    /// The backward branches sorted by their position.
+    back_edges: Vec<BackEdge>,
+}
+
+impl Safepoints {
+    /// Analyzes the Wasmi bytecode `instrs` with function local constant values `consts`.
+    fn analyze(instrs: &[Instruction], consts: &[UntypedVal]) -> Self {
+        let mut safepoints = Vec::new();
+        let mut back_edges = Vec::new();
+        for (pos, instr) in instrs.iter().enumerate() {
+            let pos = pos as u32;
+            if let Some(kind) = SafepointKind::of(instr) {
+                safepoints.push(Safepoint { pos, kind });
+            }
+            let Some(offset) = branch_offset(instr, consts) else {
+                continue;
+            };
+            let target = i64::from(pos) + i64::from(offset);
+            if target <= i64::from(pos) {
+                back_edges.push(BackEdge {
+                    branch: pos,
+                    target: target as u32,
+                });
+            }
+        }
+        Self {
+            len_instrs: instrs.len() as u32,
+            safepoints,
+            back_edges,
+        }
+    }
+
+    /// Returns the number of instruction words of the function.
+    pub fn len_instrs(&self) -> u32 {
+        self.len_instrs
+    }
+
+    /// Returns all safepoints of the function sorted by their position.
+    pub fn safepoints(&self) -> &[Safepoint] {
+        &self.safepoints
+    }
+
+    /// Returns all backward branches of the function sorted by their position.
+    ///
+    /// Backward branches are the only way to execute an instruction more than once
+    /// within the same call frame, i.e. each loop has at least one back edge.
+    pub fn back_edges(&self) -> &[BackEdge] {
+        &self.back_edges
+    }
+
+    /// Returns an iterator over the backward branches that do not pass a safepoint.
+    ///
+    /// The loop of such a back edge may run unbounded without being preempted.
+    pub fn unchecked_back_edges(&self) -> impl Iterator<Item = &BackEdge> + '_ {
+        self.back_edges.iter().filter(|edge| {
+            !self
+                .safepoints
+                .iter()
+                .any(|safepoint| (edge.target..=edge.branch).contains(&safepoint.pos))
+        })
+    }
+
+    /// Returns `true` if every loop of the function passes a safepoint on each iteration.
+    pub fn is_preemptible(&self) -> bool {
+        self.unchecked_back_edges().next().is_none()
+    }
+}
+
+/// A position at which an execution may be preempted or interrupted.
+///
+/// Returned by [`Safepoints::safepoints`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Safepoint {
+    /// The position of the instruction word within the function.
+    pos: u32,
+    /// The kind of the safepoint.
+    kind: SafepointKind,
+}
+
+impl Safepoint {
+    /// Returns the position of the safepoint's instruction word within the function.
+    pub fn pos(&self) -> u32 {
+        self.pos
+    }
+
+    /// Returns the [`SafepointKind`] of the safepoint.
+    pub fn kind(&self) -> SafepointKind {
+        self.kind
+    }
+}
+
+/// The kind of a [`Safepoint`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SafepointKind {
+    /// A fuel check that traps if the fuel of the [`Store`](crate::Store) is exhausted.
+    FuelCheck,
+    /// A call to a Wasm or host function.
+    Call,
+    /// A tail call to a Wasm or host function.
+    TailCall,
+}
+
+impl SafepointKind {
+    /// Returns the [`SafepointKind`] of `instr` if it is a safepoint.
+    fn of(instr: &Instruction) -> Option<Self> {
+        use Instruction as I;
+        let kind = match instr {
+            I::ConsumeFuel { .. } => Self::FuelCheck,
+            I::CallInternal0 { .. }
+            | I::CallInternal { .. }
+            | I::CallImported0 { .. }
+            | I::CallImported { .. }
+            | I::CallIndirect0 { .. }
+            | I::CallIndirect0Imm16 { .. }
+            | I::CallIndirect { .. }
+            | I::CallIndirectImm16 { .. } => Self::Call,
+            I::ReturnCallInternal0 { .. }
+            | I::ReturnCallInternal { .. }
+            | I::ReturnCallImported0 { .. }
+            | I::ReturnCallImported { .. }
+            | I::ReturnCallIndirect0 { .. }
+            | I::ReturnCallIndirect0Imm16 { .. }
+            | I::ReturnCallIndirect { .. }
+            | I::ReturnCallIndirectImm16 { .. } => Self::TailCall,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// A backward branch of a function.
+///
+/// Returned by [`Safepoints::back_edges`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BackEdge {
+    /// The position of the branch instruction word.
+    branch: u32,
+    /// The position of the branch target instruction word.
+    target: u32,
+}
+
+impl BackEdge {
+    /// Returns the position of the branch instruction word within the function.
+    pub fn branch(&self) -> u32 {
+        self.branch
+    }
+
+    /// Returns the position of the branch target instruction word within the function.
+    ///
+    /// This is never greater than [`BackEdge::branch`].
+    pub fn target(&self) -> u32 {
+        self.target
+    }
+}
+
+/// Returns the relative branch offset of `instr` if it is a branch instruction.
+///
+/// The `consts` are required to decode [`Instruction::BranchCmpFallback`].
+#[rustfmt::skip]
+fn branch_offset(instr: &Instruction, consts: &[UntypedVal]) -> Option<i32> {
+    use Instruction as I;
+    let offset = match *instr {
+        I::Branch { offset } |
+        I::BranchTableTarget { offset, .. } |
+        I::BranchTableTargetNonOverlapping { offset, .. } => offset.to_i32(),
+        I::BranchCmpFallback { params, .. } => {
+            let params = const_value(params, consts)?;
+            ComparatorAndOffset::from_untyped(params)?.offset.to_i32()
+        }
+        I::BranchI32And { offset, .. } |
+        I::BranchI32Or { offset, .. } |
+        I::BranchI32Xor { offset, .. } |
+        I::BranchI32AndEqz { offset, .. } |
+        I::BranchI32OrEqz { offset, .. } |
+        I::BranchI32XorEqz { offset, .. } |
+        I::BranchI32Eq { offset, .. } |
+        I::BranchI32Ne { offset, .. } |
+        I::BranchI32LtS { offset, .. } |
+        I::BranchI32LtU { offset, .. } |
+        I::BranchI32LeS { offset, .. } |
+        I::BranchI32LeU { offset, .. } |
+        I::BranchI64Eq { offset, .. } |
+        I::BranchI64Ne { offset, .. } |
+        I::BranchI64LtS { offset, .. } |
+        I::BranchI64LtU { offset, .. } |
+        I::BranchI64LeS { offset, .. } |
+        I::BranchI64LeU { offset, .. } |
+        I::BranchF32Eq { offset, .. } |
+        I::BranchF32Ne { offset, .. } |
+        I::BranchF32Lt { offset, .. } |
+        I::BranchF32Le { offset, .. } |
+        I::BranchF64Eq { offset, .. } |
+        I::BranchF64Ne { offset, .. } |
+        I::BranchF64Lt { offset, .. } |
+        I::BranchF64Le { offset, .. } => offset.to_i16().into(),
+        I::BranchI32AndImm16 { offset, .. } |
+        I::BranchI32OrImm16 { offset, .. } |
+        I::BranchI32XorImm16 { offset, .. } |
+        I::BranchI32AndEqzImm16 { offset, .. } |
+        I::BranchI32OrEqzImm16 { offset, .. } |
+        I::BranchI32XorEqzImm16 { offset, .. } |
+        I::BranchI32EqImm16 { offset, .. } |
+        I::BranchI32NeImm16 { offset, .. } |
+        I::BranchI32LtSImm16Lhs { offset, .. } |
+        I::BranchI32LtSImm16Rhs { offset, .. } |
+        I::BranchI32LeSImm16Lhs { offset, .. } |
+        I::BranchI32LeSImm16Rhs { offset, .. } |
+        I::BranchI32LtUImm16Lhs { offset, .. } |
+        I::BranchI32LtUImm16Rhs { offset, .. } |
+        I::BranchI32LeUImm16Lhs { offset, .. } |
+        I::BranchI32LeUImm16Rhs { offset, .. } => offset.to_i16().into(),
+        I::BranchI64EqImm16 { offset, .. } |
+        I::BranchI64NeImm16 { offset, .. } |
+        I::BranchI64LtSImm16Lhs { offset, .. } |
+        I::BranchI64LtSImm16Rhs { offset, .. } |
+        I::BranchI64LeSImm16Lhs { offset, .. } |
+        I::BranchI64LeSImm16Rhs { offset, .. } |
+        I::BranchI64LtUImm16Lhs { offset, .. } |
+        I::BranchI64LtUImm16Rhs { offset, .. } |
+        I::BranchI64LeUImm16Lhs { offset, .. } |
+        I::BranchI64LeUImm16Rhs { offset, .. } => offset.to_i16().into(),
+        _ => return None,
+    };
+    Some(offset)
+}
+
+/// Returns the function local constant value of `reg` in `consts` if any.
+///
+/// Function local constant values are referenced by negative register indices.
+fn const_value(reg: Reg, consts: &[UntypedVal]) -> Option<UntypedVal> {
+    let index = i16::from(reg);
+    if index >= 0 {
+        return None;
+    }
+    let index = consts
+        .len()
+        .checked_sub(usize::from(index.unsigned_abs()))?;
+    consts.get(index).copied()
+}
@@ -0,0 +1,129 @@
+use super::Module;
+use crate::{Error, ExternType, FuncType, GlobalType, MemoryType, TableType};
+use alloc::{boxed::Box, format, vec::Vec};
+use wasmparser::{CompositeInnerType, Parser, Payload, TypeRef};
+
+impl Module {
+    /// Scans the imports of the Wasm binary `wasm` without compiling it.
+    ///
+    /// Returns the imports of `wasm` in the order of their declaration.
+    ///
+    /// Only the type and import sections of `wasm` are parsed which is much cheaper
+    /// than [`Module::new`]. This allows package managers to resolve the dependency
+    /// graph of many Wasm modules before committing to compile any of them.
+    ///
+    /// # Note
+    ///
+    /// - The input `wasm` must be in binary form, the text format is not accepted by this function.
+    /// - Parsing stops after the import section, so `wasm` is neither validated nor translated.
+    ///   Therefore [`Module::new`] might still fail for `wasm` even if this succeeds.
+    ///
+    /// # Errors
+    ///
+    /// - If the type or import sections of `wasm` are malformed.
+    /// - If an import refers to a function type that does not exist.
+    /// - If an import is of a kind that Wasmi does not support.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{ExternType, Module};
+    /// let wasm = wat::parse_str(r#"
+    ///     (module
+    ///         (import "math" "add" (func (param i32 i32) (result i32)))
+    ///         (import "env" "memory" (memory 1))
+    ///     )
+    /// "#).unwrap();
+    /// let imports = Module::scan_imports(&wasm).unwrap();
+    /// assert_eq!(imports.len(), 2);
+    /// assert_eq!(imports[0].module(), "math");
+    /// assert_eq!(imports[1].name(), "memory");
+    /// assert!(matches!(imports[1].ty(), ExternType::Memory(_)));
+    /// ```
+    pub fn scan_imports(wasm: &[u8]) -> Result<Vec<ScannedImport>, Error> {
+        let mut func_types = Vec::new();
+        let mut imports = Vec::new();
+        for payload in Parser::new(0).parse_all(wasm) {
+            match payload? {
+                Payload::Version { .. } | Payload::CustomSection(_) => {}
+                Payload::TypeSection(section) => {
+                    for rec_group in section {
+                        for sub_type in rec_group?.into_types() {
+                            match sub_type.composite_type.inner {
+                                CompositeInnerType::Func(func_type) => {
+                                    func_types.push(FuncType::from_wasmparser(&func_type))
+                                }
+                                unsupported => {
+                                    return Err(Error::new(format!(
+                                        "unsupported Wasm type: {unsupported:?}"
+                                    )))
+                                }
+                            }
+                        }
+                    }
+                }
+                Payload::ImportSection(section) => {
+                    for import in section {
+                        let import = import?;
+                        let ty = match import.ty {
+                            TypeRef::Func(index) => {
+                                let Some(func_type) = func_types.get(index as usize) else {
+                                    return Err(Error::new(format!(
+                                        "missing function type at index {index} for import {}::{}",
+                                        import.module, import.name
+                                    )));
+                                };
+                                ExternType::Func(func_type.clone())
+                            }
+                            TypeRef::Table(ty) => TableType::from_wasmparser(ty).into(),
+                            TypeRef::Memory(ty) => MemoryType::from_wasmparser(ty).into(),
+                            TypeRef::Global(ty) => GlobalType::from_wasmparser(ty).into(),
+                            TypeRef::Tag(_) => {
+                                return Err(Error::new(format!(
+                                    "unsupported tag import: {}::{}",
+                                    import.module, import.name
+                                )))
+                            }
+                        };
+                        imports.push(ScannedImport {
+                            module: import.module.into(),
+                            name: import.name.into(),
+                            ty,
+                        });
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+        Ok(imports)
+    }
+}
+
+/// An import of a Wasm module found by [`Module::scan_imports`].
+#[derive(Debug, Clone)]
+pub struct ScannedImport {
+    /// The name of the module that defines the imported item.
+    module: Box<str>,
+    /// The name of the imported item within its module namespace.
+    name: Box<str>,
+    /// The type of the imported item.
+    ty: ExternType,
+}
+
+impl ScannedImport {
+    /// Returns the name of the module that defines the imported item.
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// Returns the name of the imported item within its module namespace.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the imported item.
+    pub fn ty(&self) -> &ExternType {
+        &self.ty
+    }
+}
@@ -0,0 +1,89 @@
+use super::{parser::ModuleParser, Module};
+use crate::{Engine, Error};
+use alloc::sync::Arc;
+
+/// Verifies the [`ModuleSignature`] of a Wasm binary.
+///
+/// Returns `true` if the `signature` is valid for the `wasm` bytes.
+///
+/// Set via [`Config::require_signed_modules`](crate::Config::require_signed_modules).
+pub type ModuleVerifier = fn(wasm: &[u8], signature: &ModuleSignature) -> bool;
+
+/// Signed provenance metadata of a Wasm binary.
+///
+/// Attached to a [`Module`] via [`Module::new_signed`] and verified by the
+/// [`ModuleVerifier`] of the [`Engine`] if any.
+///
+/// Wasmi does not interpret the signature or metadata bytes. Their format, e.g. an
+/// Ed25519 signature over the Wasm binary and a publisher manifest, is defined by
+/// the [`ModuleVerifier`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleSignature {
+    /// The signature bytes.
+    signature: Arc<[u8]>,
+    /// The signed metadata bytes, e.g. the publisher and version of the Wasm binary.
+    metadata: Arc<[u8]>,
+}
+
+impl ModuleSignature {
+    /// Creates a new [`ModuleSignature`] from the `signature` bytes without metadata.
+    pub fn new(signature: impl Into<Arc<[u8]>>) -> Self {
+        Self {
+            signature: signature.into(),
+            metadata: Arc::from([]),
+        }
+    }
+
+    /// Attaches the signed `metadata` bytes to the [`ModuleSignature`].
+    pub fn with_metadata(mut self, metadata: impl Into<Arc<[u8]>>) -> Self {
+        self.metadata = metadata.into();
+        self
+    }
+
+    /// Returns the signature bytes.
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Returns the signed metadata bytes.
+    pub fn metadata(&self) -> &[u8] {
+        &self.metadata
+    }
+}
+
+impl Module {
+    /// Creates a new Wasm [`Module`] from the signed Wasm bytecode buffer.
+    ///
+    /// The `signature` is verified over the `wasm` bytes by the [`ModuleVerifier`] of the
+    /// `engine` if any and attached to the [`Module`] for later inspection via
+    /// [`Module::signature`].
+    ///
+    /// # Errors
+    ///
+    /// - If the [`ModuleVerifier`] of the `engine` rejects the `signature`.
+    /// - For all the reasons [`Module::new`] fails.
+    pub fn new_signed(
+        engine: &Engine,
+        wasm: impl AsRef<[u8]>,
+        signature: ModuleSignature,
+    ) -> Result<Self, Error> {
+        let wasm = wasm.as_ref();
+        if let Some(verify) = engine.config().get_module_verifier() {
+            if !verify(wasm, &signature) {
+                return Err(Error::new("invalid signature of Wasm module"));
+            }
+        }
+        #[cfg(feature = "wat")]
+        let wasm = &wat::parse_bytes(wasm)?[..];
+        ModuleParser::new(engine)
+            .with_signature(signature)
+            .parse_buffered(wasm)
+    }
+
+    /// Returns the [`ModuleSignature`] attached to the [`Module`] if any.
+    ///
+    /// Only modules created via [`Module::new_signed`] are signed.
+    pub fn signature(&self) -> Option<&ModuleSignature> {
+        self.inner.signature.as_ref()
+    }
+}
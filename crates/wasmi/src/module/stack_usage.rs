@@ -0,0 +1,220 @@
+use super::{export::ExternIdx, CallGraph, CallTarget, Module};
+use crate::{Error, FuncType};
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+
+impl Module {
+    /// Returns the static [`StackAnalysis`] of all exported functions of the [`Module`].
+    ///
+    /// The analysis bounds the maximum value stack height and call depth that a call
+    /// to an exported function can reach in the units used by [`StackLimits`].
+    ///
+    /// # Note
+    ///
+    /// - The bounds are not statically determinable if an exported function may
+    ///   reach a recursive call or an indirect call, both of which are flagged.
+    /// - Calls to host functions are accounted for their parameters and results only.
+    /// - Lazily compiled functions of the [`Module`] are compiled if they have not been compiled yet.
+    ///
+    /// # Errors
+    ///
+    /// If translation or Wasm validation of a lazily compiled function fails.
+    ///
+    /// [`StackLimits`]: crate::StackLimits
+    pub fn stack_usage(&self) -> Result<StackAnalysis, Error> {
+        let header = &self.inner.header;
+        let engine = self.engine();
+        let call_graph = self.call_graph()?;
+        let len_registers = header
+            .inner
+            .engine_funcs
+            .iter()
+            .map(|func| engine.resolve_len_registers(func))
+            .collect::<Result<Vec<_>, _>>()?;
+        let host_costs = self
+            .imports()
+            .filter_map(|import| import.ty().func().map(host_cost))
+            .collect::<Vec<_>>();
+        let mut analyzer = StackAnalyzer {
+            call_graph: &call_graph,
+            len_registers: &len_registers,
+            host_costs: &host_costs,
+            states: vec![State::Unvisited; len_registers.len()],
+        };
+        let exports = header
+            .inner
+            .exports
+            .iter()
+            .filter_map(|(name, idx)| match idx {
+                ExternIdx::Func(func) => Some((name.clone(), analyzer.visit(func.into_u32()))),
+                _ => None,
+            })
+            .collect();
+        Ok(StackAnalysis { exports })
+    }
+}
+
+/// Returns the value stack cells required to call a host function of type `func_type`.
+fn host_cost(func_type: &FuncType) -> usize {
+    func_type.params().len().max(func_type.results().len())
+}
+
+/// The analysis state of an internal function.
+#[derive(Debug, Copy, Clone)]
+enum State {
+    /// The function has not been visited, yet.
+    Unvisited,
+    /// The function is currently being visited.
+    InProgress,
+    /// The function has been visited.
+    Done(StackUsage),
+}
+
+/// Computes the [`StackUsage`] of internal functions via depth-first search.
+struct StackAnalyzer<'a> {
+    /// The call graph of the analyzed [`Module`].
+    call_graph: &'a CallGraph,
+    /// The number of registers of all internal functions.
+    len_registers: &'a [u16],
+    /// The value stack cells required to call each imported function.
+    host_costs: &'a [usize],
+    /// The analysis states of all internal functions.
+    states: Vec<State>,
+}
+
+impl StackAnalyzer<'_> {
+    /// Returns the [`StackUsage`] of a call to the function at `func`.
+    fn visit(&mut self, func: u32) -> StackUsage {
+        let len_imported = self.host_costs.len();
+        let Some(index) = (func as usize).checked_sub(len_imported) else {
+            // Calling a host function directly does not use the Wasm stack.
+            return StackUsage::default();
+        };
+        match self.states[index] {
+            State::Done(usage) => return usage,
+            State::InProgress => {
+                return StackUsage {
+                    is_recursive: true,
+                    ..StackUsage::default()
+                }
+            }
+            State::Unvisited => {}
+        }
+        self.states[index] = State::InProgress;
+        let frame = usize::from(self.len_registers[index]);
+        let mut usage = StackUsage {
+            max_value_stack: frame,
+            max_call_depth: 1,
+            ..StackUsage::default()
+        };
+        let edges = self
+            .call_graph
+            .edges()
+            .iter()
+            .filter(|edge| edge.caller() == func)
+            .map(|edge| (edge.target().clone(), edge.is_tail_call()))
+            .collect::<Box<[_]>>();
+        for (target, is_tail_call) in edges.iter() {
+            let callee = match target {
+                CallTarget::Indirect(_) => {
+                    usage.has_indirect_calls = true;
+                    continue;
+                }
+                CallTarget::Func(callee) => match self.host_costs.get(*callee as usize) {
+                    Some(&cost) => StackUsage {
+                        max_value_stack: cost,
+                        ..StackUsage::default()
+                    },
+                    None => self.visit(*callee),
+                },
+            };
+            let (stack, depth) = match is_tail_call {
+                // Tail calls replace the call frame of the caller.
+                true => (callee.max_value_stack, callee.max_call_depth),
+                false => (frame + callee.max_value_stack, 1 + callee.max_call_depth),
+            };
+            usage.max_value_stack = usage.max_value_stack.max(stack);
+            usage.max_call_depth = usage.max_call_depth.max(depth);
+            usage.is_recursive |= callee.is_recursive;
+            usage.has_indirect_calls |= callee.has_indirect_calls;
+        }
+        self.states[index] = State::Done(usage);
+        usage
+    }
+}
+
+/// The static stack usage of all exported functions of a [`Module`].
+///
+/// Returned by [`Module::stack_usage`].
+#[derive(Debug, Clone)]
+pub struct StackAnalysis {
+    /// The [`StackUsage`] of all exported functions by their export name.
+    exports: BTreeMap<Box<str>, StackUsage>,
+}
+
+impl StackAnalysis {
+    /// Returns the [`StackUsage`] of the exported function `name` if any.
+    pub fn get(&self, name: &str) -> Option<&StackUsage> {
+        self.exports.get(name)
+    }
+
+    /// Returns an iterator over the export names and [`StackUsage`] of all exported functions.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &StackUsage)> + '_ {
+        self.exports.iter().map(|(name, usage)| (&**name, usage))
+    }
+}
+
+/// The static stack usage of a call to an exported function.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StackUsage {
+    /// The maximum value stack height of all statically known calls.
+    max_value_stack: usize,
+    /// The maximum call depth of all statically known calls.
+    max_call_depth: usize,
+    /// Is `true` if the call may reach a recursive call.
+    is_recursive: bool,
+    /// Is `true` if the call may reach an indirect call.
+    has_indirect_calls: bool,
+}
+
+impl StackUsage {
+    /// Returns `true` if the stack usage is statically bounded.
+    ///
+    /// This is the case if the call neither reaches a recursive nor an indirect call.
+    pub fn is_bounded(&self) -> bool {
+        !self.is_recursive && !self.has_indirect_calls
+    }
+
+    /// Returns the maximum value stack height if the stack usage is statically bounded.
+    pub fn max_value_stack(&self) -> Option<usize> {
+        self.is_bounded().then_some(self.max_value_stack)
+    }
+
+    /// Returns the maximum call depth if the stack usage is statically bounded.
+    pub fn max_call_depth(&self) -> Option<usize> {
+        self.is_bounded().then_some(self.max_call_depth)
+    }
+
+    /// Returns the maximum value stack height of all statically known calls.
+    ///
+    /// This is a lower bound if the stack usage is not statically bounded.
+    pub fn known_value_stack(&self) -> usize {
+        self.max_value_stack
+    }
+
+    /// Returns the maximum call depth of all statically known calls.
+    ///
+    /// This is a lower bound if the stack usage is not statically bounded.
+    pub fn known_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+
+    /// Returns `true` if the call may reach a recursive call.
+    pub fn is_recursive(&self) -> bool {
+        self.is_recursive
+    }
+
+    /// Returns `true` if the call may reach an indirect call.
+    pub fn has_indirect_calls(&self) -> bool {
+        self.has_indirect_calls
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{engine::TranslationError, Error};
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A token to cancel the streaming compilation of a [`Module`], e.g. from another thread.
+///
+/// All clones of a [`CancellationToken`] share the same cancellation state.
+///
+/// # Note
+///
+/// Cancellation is checked whenever the streaming compilation consumed a Wasm payload
+/// or needs to pull more bytes from its stream. Upon cancellation the compilation
+/// returns an [`Error`] for which [`Error::is_cancelled`] returns `true` and releases
+/// its buffered bytes, its Wasm validator and its partially built [`Module`].
+///
+/// [`Module`]: crate::Module
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    /// Is `true` once the [`CancellationToken`] has been cancelled.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new [`CancellationToken`] that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels all streaming compilations observing the [`CancellationToken`].
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the [`CancellationToken`] has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// The progress of the streaming compilation of a [`Module`].
+///
+/// [`Module`]: crate::Module
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StreamingProgress {
+    /// The number of bytes of the Wasm stream consumed so far.
+    bytes_consumed: usize,
+    /// The number of function bodies translated so far.
+    funcs_translated: u32,
+}
+
+impl StreamingProgress {
+    /// Creates a new [`StreamingProgress`].
+    pub(crate) fn new(bytes_consumed: usize, funcs_translated: u32) -> Self {
+        Self {
+            bytes_consumed,
+            funcs_translated,
+        }
+    }
+
+    /// Returns the number of bytes of the Wasm stream consumed so far.
+    pub fn bytes_consumed(&self) -> usize {
+        self.bytes_consumed
+    }
+
+    /// Returns the number of function bodies translated so far.
+    ///
+    /// # Note
+    ///
+    /// With lazy [`CompilationMode`]s this is the number of function bodies that
+    /// have been prepared for lazy translation.
+    ///
+    /// [`CompilationMode`]: crate::CompilationMode
+    pub fn funcs_translated(&self) -> u32 {
+        self.funcs_translated
+    }
+}
+
+/// Controls the streaming compilation of a [`Module`] via [`Module::new_streaming_with_control`].
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{CancellationToken, StreamingControl};
+/// let token = CancellationToken::new();
+/// let control = StreamingControl::new()
+///     .with_cancellation(token.clone())
+///     .on_progress(|progress| {
+///         println!("consumed {} bytes", progress.bytes_consumed());
+///     });
+/// ```
+///
+/// [`Module`]: crate::Module
+/// [`Module::new_streaming_with_control`]: crate::Module::new_streaming_with_control
+#[derive(Default)]
+pub struct StreamingControl<'a> {
+    /// The token that cancels the streaming compilation if any.
+    cancellation: Option<CancellationToken>,
+    /// The callback that is informed about the progress of the streaming compilation if any.
+    progress: Option<Box<dyn FnMut(StreamingProgress) + 'a>>,
+}
+
+impl fmt::Debug for StreamingControl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamingControl")
+            .field("cancellation", &self.cancellation)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl<'a> StreamingControl<'a> {
+    /// Creates a new [`StreamingControl`] that neither cancels nor reports progress.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the streaming compilation once `token` has been cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Calls `f` with the [`StreamingProgress`] whenever the streaming compilation consumed a Wasm payload.
+    pub fn on_progress(mut self, f: impl FnMut(StreamingProgress) + 'a) -> Self {
+        self.progress = Some(Box::new(f));
+        self
+    }
+
+    /// Returns an error if the streaming compilation has been cancelled.
+    ///
+    /// # Errors
+    ///
+    /// If the [`CancellationToken`] of the [`StreamingControl`] has been cancelled.
+    pub(crate) fn check_cancelled(&self) -> Result<(), Error> {
+        match &self.cancellation {
+            Some(token) if token.is_cancelled() => Err(Error::from(TranslationError::Cancelled)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reports `progress` and checks for cancellation afterwards.
+    ///
+    /// # Errors
+    ///
+    /// If the [`CancellationToken`] of the [`StreamingControl`] has been cancelled.
+    pub(crate) fn report(&mut self, progress: StreamingProgress) -> Result<(), Error> {
+        if let Some(f) = &mut self.progress {
+            f(progress);
+        }
+        self.check_cancelled()
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{Engine, Error, Func, Store, Val};
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::Write as _,
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{
+    panic,
+    sync::{Mutex, OnceLock, PoisonError},
+    thread,
+};
+
+/// The threads that execute [`ParallelCall`]s.
+///
+/// Every [`Engine::call_parallel_in`] call using the [`ParallelPool`] spawns scoped
+/// worker threads that are joined before it returns. The thread calling
+/// [`Engine::call_parallel_in`] takes part in executing the calls.
+#[derive(Debug)]
+pub struct ParallelPool {
+    /// The maximum number of threads executing the calls, including the calling thread.
+    threads: usize,
+}
+
+impl ParallelPool {
+    /// Creates a new [`ParallelPool`] that executes calls on up to `threads` threads.
+    ///
+    /// The thread calling [`Engine::call_parallel_in`] takes part in executing the
+    /// calls, therefore each call spawns up to `threads - 1` worker threads.
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+        }
+    }
+
+    /// Returns the number of threads that execute the calls of the [`ParallelPool`].
+    ///
+    /// This includes the thread calling [`Engine::call_parallel_in`].
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Returns the [`ParallelPool`] used by [`Engine::call_parallel`].
+    ///
+    /// It is created upon first use with one thread per available CPU core.
+    fn global() -> &'static Self {
+        static GLOBAL: OnceLock<ParallelPool> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| Self::new(thread::available_parallelism().map_or(1, NonZeroUsize::get)))
+    }
+
+    /// Executes `work` on the calling thread and on up to `helpers` worker threads.
+    ///
+    /// Returns once `work` returned on all threads that started it.
+    ///
+    /// # Panics
+    ///
+    /// - If `work` panicked on any thread.
+    /// - If the operating system fails to spawn a worker thread.
+    fn scope(&self, helpers: usize, work: &(dyn Fn() + Sync)) {
+        let helpers = helpers.min(self.threads - 1);
+        let panic = thread::scope(|scope| {
+            let workers = (0..helpers).map(|_| scope.spawn(work)).collect::<Vec<_>>();
+            work();
+            // Note: all workers are joined explicitly so that the payload of the
+            //       first panicking worker is propagated instead of a generic one.
+            workers
+                .into_iter()
+                .fold(None, |panic, worker| panic.or(worker.join().err()))
+        });
+        if let Some(payload) = panic {
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+/// An independent guest call executed by [`Engine::call_parallel`].
+///
+/// Owns the [`Store`] it is executed in so that it can be moved to a worker thread.
+pub struct ParallelCall<T> {
+    /// The store in which `func` is called.
+    store: Store<T>,
+    /// The called function.
+    func: Func,
+    /// The parameters of the call.
+    params: Vec<Val>,
+    /// The optional fuel limit of the call.
+    fuel: Option<u64>,
+}
+
+impl<T> ParallelCall<T> {
+    /// Creates a new [`ParallelCall`] that calls `func` in `store` with `params`.
+    pub fn new(store: Store<T>, func: Func, params: impl Into<Vec<Val>>) -> Self {
+        Self {
+            store,
+            func,
+            params: params.into(),
+            fuel: None,
+        }
+    }
+
+    /// Sets the fuel of the [`Store`] to `fuel` before the call.
+    ///
+    /// The call fails if fuel metering is disabled for the [`Engine`].
+    pub fn fuel(mut self, fuel: u64) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    /// Executes the [`ParallelCall`] and returns its [`ParallelOutcome`].
+    fn run(mut self, engine: &Engine) -> ParallelOutcome<T> {
+        let result = self.execute(engine);
+        ParallelOutcome {
+            store: self.store,
+            result,
+        }
+    }
+
+    /// Executes the [`ParallelCall`] and returns its results.
+    fn execute(&mut self, engine: &Engine) -> Result<Vec<Val>, Error> {
+        if !Engine::same(self.store.engine(), engine) {
+            return Err(Error::new(
+                "the store of a parallel call does not belong to the calling engine",
+            ));
+        }
+        if let Some(fuel) = self.fuel {
+            self.store.set_fuel(fuel)?;
+        }
+        let ty = self.func.ty(&self.store);
+        let mut results = ty
+            .results()
+            .iter()
+            .copied()
+            .map(Val::default)
+            .collect::<Vec<_>>();
+        self.func
+            .call(&mut self.store, &self.params, &mut results)?;
+        Ok(results)
+    }
+}
+
+/// The outcome of a [`ParallelCall`].
+pub struct ParallelOutcome<T> {
+    /// The store in which the call was executed.
+    store: Store<T>,
+    /// The results of the call or its error.
+    result: Result<Vec<Val>, Error>,
+}
+
+impl<T> ParallelOutcome<T> {
+    /// Returns a shared reference to the [`Store`] of the call.
+    pub fn store(&self) -> &Store<T> {
+        &self.store
+    }
+
+    /// Returns an exclusive reference to the [`Store`] of the call.
+    pub fn store_mut(&mut self) -> &mut Store<T> {
+        &mut self.store
+    }
+
+    /// Returns the results of the call or its error.
+    ///
+    /// # Errors
+    ///
+    /// If the call failed.
+    pub fn result(&self) -> Result<&[Val], &Error> {
+        self.result.as_deref()
+    }
+
+    /// Returns the [`Store`] and the results of the call.
+    pub fn into_parts(self) -> (Store<T>, Result<Vec<Val>, Error>) {
+        (self.store, self.result)
+    }
+}
+
+/// The [`ParallelOutcome`]s of [`Engine::call_parallel`] in the order of their calls.
+pub struct ParallelResults<T> {
+    /// The outcomes of all calls.
+    outcomes: Vec<ParallelOutcome<T>>,
+}
+
+impl<T> ParallelResults<T> {
+    /// Returns the number of executed calls.
+    pub fn len(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    /// Returns `true` if no calls have been executed.
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    /// Returns `true` if all calls succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.result.is_ok())
+    }
+
+    /// Returns the [`ParallelOutcome`]s in the order of their calls.
+    pub fn outcomes(&self) -> &[ParallelOutcome<T>] {
+        &self.outcomes
+    }
+
+    /// Returns an iterator over the indices and errors of all failed calls.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &Error)> + '_ {
+        self.outcomes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, outcome)| Some((index, outcome.result.as_ref().err()?)))
+    }
+
+    /// Consumes `self` and returns the [`ParallelOutcome`]s in the order of their calls.
+    pub fn into_outcomes(self) -> Vec<ParallelOutcome<T>> {
+        self.outcomes
+    }
+
+    /// Consumes `self` and returns the results of all calls in the order of their calls.
+    ///
+    /// # Errors
+    ///
+    /// If any call failed. The returned [`Error`] reports the errors of all failed calls.
+    pub fn into_results(self) -> Result<Vec<Vec<Val>>, Error> {
+        let len_failures = self.failures().count();
+        if len_failures == 0 {
+            return Ok(self
+                .outcomes
+                .into_iter()
+                .filter_map(|outcome| outcome.result.ok())
+                .collect());
+        }
+        let mut message = String::new();
+        _ = write!(
+            message,
+            "{len_failures} of {} parallel calls failed:",
+            self.len()
+        );
+        for (index, error) in self.failures() {
+            _ = write!(message, "\n  call {index}: {error}");
+        }
+        Err(Error::new(message))
+    }
+}
+
+impl Engine {
+    /// Executes the independent `calls` in parallel on all available threads.
+    ///
+    /// The calls are executed by a global [`ParallelPool`] with one thread per available CPU core.
+    ///
+    /// Read more in [`Engine::call_parallel_in`].
+    pub fn call_parallel<T: Send>(
+        &self,
+        calls: impl IntoIterator<Item = ParallelCall<T>>,
+    ) -> ParallelResults<T> {
+        self.call_parallel_in(ParallelPool::global(), calls)
+    }
+
+    /// Executes the independent `calls` in parallel on the threads of `pool`.
+    ///
+    /// Every [`ParallelCall`] owns its [`Store`] which is returned together with the
+    /// results of the call in the [`ParallelOutcome`]. Idle threads take the next
+    /// pending call so that calls of different lengths are balanced across all threads.
+    /// The calling thread takes part in executing the calls.
+    ///
+    /// Failed calls do not affect other calls. Their errors are reported via
+    /// [`ParallelResults::failures`] or aggregated by [`ParallelResults::into_results`].
+    ///
+    /// # Panics
+    ///
+    /// If a call panics, e.g. because its function does not belong to its [`Store`].
+    pub fn call_parallel_in<T: Send>(
+        &self,
+        pool: &ParallelPool,
+        calls: impl IntoIterator<Item = ParallelCall<T>>,
+    ) -> ParallelResults<T> {
+        let pending = calls
+            .into_iter()
+            .map(|call| Mutex::new(Some(call)))
+            .collect::<Vec<_>>();
+        let finished = pending.iter().map(|_| Mutex::new(None)).collect::<Vec<_>>();
+        let next = AtomicUsize::new(0);
+        let worker = || loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(call) = pending.get(index) else {
+                break;
+            };
+            let Some(call) = call.lock().unwrap_or_else(PoisonError::into_inner).take() else {
+                continue;
+            };
+            let outcome = call.run(self);
+            *finished[index]
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(outcome);
+        };
+        pool.scope(pending.len().saturating_sub(1), &worker);
+        let outcomes = finished
+            .into_iter()
+            .filter_map(|outcome| outcome.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .collect();
+        ParallelResults { outcomes }
+    }
+}
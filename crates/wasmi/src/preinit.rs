@@ -0,0 +1,530 @@
+use crate::{
+    core::ValType,
+    module::WasmiValueType,
+    AsContext,
+    Error,
+    ExternType,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Val,
+};
+use alloc::{boxed::Box, format, vec::Vec};
+use wasm_encoder as enc;
+use wasmparser::{DataKind, ExternalKind, Parser, Payload};
+
+/// The minimum amount of consecutive zero bytes that split two snapshot data segments.
+///
+/// Smaller gaps of zero bytes are included in the data segments since every
+/// data segment comes with some encoding overhead.
+const MIN_ZERO_GAP: usize = 8;
+
+/// Pre-initializes Wasm modules by running their initialization function once
+/// and snapshotting the resulting state into a new Wasm module.
+///
+/// The resulting Wasm module starts in the state that the original Wasm module had
+/// after its initialization function returned. This drastically cuts startup time of
+/// guests that perform expensive initialization, e.g. language runtimes.
+///
+/// # Note
+///
+/// - The contents of all non-imported linear memories are captured as data segments.
+/// - The values of all non-imported global variables are captured as their initializers.
+/// - The `start` function is removed from the resulting Wasm module since it already ran.
+/// - Changes to tables and to imported entities are _not_ captured.
+/// - Global variables that refer to non-null `externref` objects cannot be captured.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, Linker, Module, PreInitializer, Store};
+/// # fn main() -> Result<(), wasmi::Error> {
+/// let wasm = r#"
+///     (module
+///         (global $g (export "g") (mut i32) (i32.const 0))
+///         (func (export "init") (global.set $g (i32.const 42)))
+///     )
+/// "#;
+/// let engine = Engine::default();
+/// let mut store = Store::new(&engine, ());
+/// let linker = <Linker<()>>::new(&engine);
+/// let snapshot = PreInitializer::new()
+///     .init_func("init")
+///     .run(&mut store, &linker, wasm)?;
+/// let module = Module::new(&engine, &snapshot)?;
+/// let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+/// let g = instance.get_global(&store, "g").unwrap();
+/// assert_eq!(g.get(&store).i32(), Some(42));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreInitializer {
+    /// The name of the exported initialization function.
+    init_func: Box<str>,
+    /// If `true` the export of the initialization function is kept.
+    keep_init_func: bool,
+}
+
+impl Default for PreInitializer {
+    fn default() -> Self {
+        Self {
+            init_func: "wizer.initialize".into(),
+            keep_init_func: false,
+        }
+    }
+}
+
+impl PreInitializer {
+    /// Creates a new [`PreInitializer`].
+    ///
+    /// By default the initialization function is exported as `wizer.initialize`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the export name of the initialization function.
+    ///
+    /// The initialization function must have type `[] -> []`.
+    pub fn init_func(mut self, name: &str) -> Self {
+        self.init_func = name.into();
+        self
+    }
+
+    /// Configures whether the export of the initialization function is kept.
+    ///
+    /// Disabled by default since running the initialization function again
+    /// usually is not intended.
+    pub fn keep_init_func(mut self, keep: bool) -> Self {
+        self.keep_init_func = keep;
+        self
+    }
+
+    /// Instantiates `wasm`, runs its initialization function and returns the snapshot Wasm binary.
+    ///
+    /// Imports of `wasm` are resolved via `linker` and the instance lives in `store`.
+    ///
+    /// # Errors
+    ///
+    /// - If `wasm` fails to compile or instantiate.
+    /// - If the initialization function is missing, has the wrong type or traps.
+    /// - If the resulting state cannot be captured.
+    pub fn run<T>(
+        &self,
+        store: &mut Store<T>,
+        linker: &Linker<T>,
+        wasm: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        let wasm = wasm.as_ref();
+        #[cfg(feature = "wat")]
+        let wasm = &wat::parse_bytes(wasm)?[..];
+        let module = Module::new(store.engine(), wasm)?;
+        let instance = linker
+            .instantiate(&mut *store, &module)?
+            .start(&mut *store)?;
+        instance
+            .get_typed_func::<(), ()>(&*store, &self.init_func)?
+            .call(&mut *store, ())?;
+        let snapshot = Snapshot::capture(&*store, &module, instance)?;
+        self.rewrite(wasm, &snapshot)
+    }
+
+    /// Like [`PreInitializer::run`] but compiles the snapshot into a new [`Module`].
+    ///
+    /// # Errors
+    ///
+    /// If [`PreInitializer::run`] fails or if the snapshot fails to compile.
+    pub fn run_to_module<T>(
+        &self,
+        store: &mut Store<T>,
+        linker: &Linker<T>,
+        wasm: impl AsRef<[u8]>,
+    ) -> Result<Module, Error> {
+        let snapshot = self.run(store, linker, wasm)?;
+        Module::new(store.engine(), snapshot)
+    }
+
+    /// Rewrites `wasm` so that it starts in the captured `snapshot` state.
+    fn rewrite(&self, wasm: &[u8], snapshot: &Snapshot) -> Result<Vec<u8>, Error> {
+        let mut output = enc::Module::new();
+        let mut has_data = false;
+        // Note: tells for every non-imported linear memory if it uses 64-bit indices.
+        let mut memory64 = Vec::new();
+        let len_snapshot_segments = snapshot.len_segments();
+        for payload in Parser::new(0).parse_all(wasm) {
+            let payload = payload?;
+            match payload {
+                Payload::MemorySection(section) => {
+                    let mut encoded = enc::MemorySection::new();
+                    for (memory, captured) in section.into_iter().zip(&snapshot.memories) {
+                        let memory = memory?;
+                        memory64.push(memory.memory64);
+                        encoded.memory(enc::MemoryType {
+                            minimum: captured.pages,
+                            maximum: memory.maximum,
+                            memory64: memory.memory64,
+                            shared: memory.shared,
+                            page_size_log2: memory.page_size_log2,
+                        });
+                    }
+                    output.section(&encoded);
+                }
+                Payload::GlobalSection(section) => {
+                    let mut encoded = enc::GlobalSection::new();
+                    for (global, init) in section.into_iter().zip(&snapshot.globals) {
+                        let ty = global?.ty;
+                        let global_type = enc::GlobalType {
+                            val_type: encode_val_type(ty.content_type),
+                            mutable: ty.mutable,
+                            shared: ty.shared,
+                        };
+                        encoded.global(global_type, init);
+                    }
+                    output.section(&encoded);
+                }
+                Payload::ExportSection(section) if !self.keep_init_func => {
+                    let mut encoded = enc::ExportSection::new();
+                    for export in section {
+                        let export = export?;
+                        if export.name == &*self.init_func {
+                            continue;
+                        }
+                        encoded.export(export.name, encode_export_kind(export.kind), export.index);
+                    }
+                    output.section(&encoded);
+                }
+                Payload::StartSection { .. } => {
+                    // Note: the `start` function already ran before the snapshot was taken.
+                }
+                Payload::DataCountSection { count, .. } => {
+                    output.section(&enc::DataCountSection {
+                        count: count + len_snapshot_segments,
+                    });
+                }
+                Payload::DataSection(section) => {
+                    let mut encoded = enc::DataSection::new();
+                    for data in section {
+                        let data = data?;
+                        match data.kind {
+                            DataKind::Passive => {
+                                encoded.passive(data.data.iter().copied());
+                            }
+                            DataKind::Active {
+                                memory_index,
+                                offset_expr,
+                            } => {
+                                // Note: data segments of imported memories are kept since
+                                //       their memories are not captured. Data segments of
+                                //       captured memories are emptied but kept to preserve
+                                //       the data segment index space.
+                                let bytes = match snapshot.is_captured_memory(memory_index) {
+                                    true => &[][..],
+                                    false => data.data,
+                                };
+                                let mut reader = offset_expr.get_binary_reader();
+                                let expr = reader.read_bytes(reader.bytes_remaining())?;
+                                // Note: strip the `end` operator since it is added by the encoder.
+                                let expr = &expr[..expr.len().saturating_sub(1)];
+                                let offset = enc::ConstExpr::raw(expr.iter().copied());
+                                encoded.active(memory_index, &offset, bytes.iter().copied());
+                            }
+                        }
+                    }
+                    snapshot.encode_segments(&mut encoded, &memory64);
+                    output.section(&encoded);
+                    has_data = true;
+                }
+                payload => {
+                    if let Some((id, range)) = payload.as_section() {
+                        output.section(&enc::RawSection {
+                            id,
+                            data: &wasm[range],
+                        });
+                    }
+                }
+            }
+        }
+        if !has_data && len_snapshot_segments != 0 {
+            let mut encoded = enc::DataSection::new();
+            snapshot.encode_segments(&mut encoded, &memory64);
+            output.section(&encoded);
+        }
+        Ok(output.finish())
+    }
+}
+
+/// The captured state of a Wasm instance.
+#[derive(Debug)]
+struct Snapshot {
+    /// The number of imported linear memories.
+    len_imported_memories: u32,
+    /// The captured non-imported linear memories.
+    memories: Vec<MemorySnapshot>,
+    /// The initializers of all non-imported global variables.
+    globals: Vec<enc::ConstExpr>,
+}
+
+/// A captured linear memory.
+#[derive(Debug)]
+struct MemorySnapshot {
+    /// The size of the linear memory in pages.
+    pages: u64,
+    /// The non-zero regions of the linear memory given their offsets.
+    segments: Vec<(usize, Box<[u8]>)>,
+}
+
+impl Snapshot {
+    /// Captures the state of the `instance` of `module`.
+    fn capture<T>(store: &Store<T>, module: &Module, instance: Instance) -> Result<Self, Error> {
+        let mut len_imported_memories = 0;
+        let mut len_imported_globals = 0;
+        for import in module.imports() {
+            match import.ty() {
+                ExternType::Memory(_) => len_imported_memories += 1,
+                ExternType::Global(_) => len_imported_globals += 1,
+                _ => {}
+            }
+        }
+        let entity = store.inner.resolve_instance(&instance);
+        let memories = (len_imported_memories..module.len_memories())
+            .map(|index| {
+                let memory = entity
+                    .get_memory(index as u32)
+                    .unwrap_or_else(|| panic!("missing linear memory at index {index}"));
+                MemorySnapshot {
+                    pages: u64::from(memory.size(store)),
+                    segments: data_segments(memory.data(store)),
+                }
+            })
+            .collect();
+        let globals = (len_imported_globals..module.len_globals())
+            .map(|index| {
+                let global = entity
+                    .get_global(index as u32)
+                    .unwrap_or_else(|| panic!("missing global variable at index {index}"));
+                encode_val(store, instance, global.get(store))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Self {
+            len_imported_memories: len_imported_memories as u32,
+            memories,
+            globals,
+        })
+    }
+
+    /// Returns `true` if the linear memory at `memory_index` has been captured.
+    fn is_captured_memory(&self, memory_index: u32) -> bool {
+        memory_index >= self.len_imported_memories
+    }
+
+    /// Returns the number of data segments of the captured linear memories.
+    fn len_segments(&self) -> u32 {
+        let len = self
+            .memories
+            .iter()
+            .map(|memory| memory.segments.len())
+            .sum::<usize>();
+        u32::try_from(len).unwrap_or_else(|_| panic!("too many data segments: {len}"))
+    }
+
+    /// Encodes the captured linear memories as active data segments into `section`.
+    ///
+    /// The offsets of the data segments of captured linear memories whose entry in
+    /// `memory64` is `true` are encoded as `i64` since they use 64-bit indices.
+    fn encode_segments(&self, section: &mut enc::DataSection, memory64: &[bool]) {
+        for (n, memory) in self.memories.iter().enumerate() {
+            let memory_index = self.len_imported_memories + n as u32;
+            let is_64 = memory64.get(n).copied().unwrap_or(false);
+            for (offset, bytes) in &memory.segments {
+                let offset = match is_64 {
+                    true => enc::ConstExpr::i64_const(*offset as u64 as i64),
+                    false => enc::ConstExpr::i32_const(*offset as u32 as i32),
+                };
+                section.active(memory_index, &offset, bytes.iter().copied());
+            }
+        }
+    }
+}
+
+/// Returns the regions of `data` that are not zero with their offsets.
+fn data_segments(data: &[u8]) -> Vec<(usize, Box<[u8]>)> {
+    let mut segments = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = data[pos..].iter().position(|byte| *byte != 0) {
+        let start = pos + start;
+        let mut end = start;
+        let mut zeros = 0;
+        for (offset, byte) in data[start..].iter().enumerate() {
+            if *byte != 0 {
+                end = start + offset + 1;
+                zeros = 0;
+                continue;
+            }
+            zeros += 1;
+            if zeros >= MIN_ZERO_GAP {
+                break;
+            }
+        }
+        segments.push((start, data[start..end].into()));
+        pos = end;
+    }
+    segments
+}
+
+/// Encodes `value` as constant initializer expression.
+///
+/// # Errors
+///
+/// If `value` refers to a non-null `externref` or to a [`Func`] not owned by `instance`.
+fn encode_val<T>(
+    store: &Store<T>,
+    instance: Instance,
+    value: Val,
+) -> Result<enc::ConstExpr, Error> {
+    let expr = match value {
+        Val::I32(value) => enc::ConstExpr::i32_const(value),
+        Val::I64(value) => enc::ConstExpr::i64_const(value),
+        Val::F32(value) => enc::ConstExpr::f32_const(f32::from(value)),
+        Val::F64(value) => enc::ConstExpr::f64_const(f64::from(value)),
+        Val::FuncRef(value) => match value.func() {
+            None => enc::ConstExpr::ref_null(enc::HeapType::FUNC),
+            Some(func) => enc::ConstExpr::ref_func(func_index(store, instance, func)?),
+        },
+        Val::ExternRef(value) if value.is_null() => enc::ConstExpr::ref_null(enc::HeapType::EXTERN),
+        Val::ExternRef(_) => {
+            return Err(Error::new(
+                "cannot capture global variable referring to a non-null `externref`",
+            ))
+        }
+    };
+    Ok(expr)
+}
+
+/// Returns the function index of `func` within `instance`.
+///
+/// # Errors
+///
+/// If `func` is not owned by `instance`.
+fn func_index<T>(store: &Store<T>, instance: Instance, func: &Func) -> Result<u32, Error> {
+    let entity = store.as_context().store.inner.resolve_instance(&instance);
+    (0..)
+        .map_while(|index| entity.get_func(index).map(|f| (index, f)))
+        .find_map(|(index, f)| (f.as_inner() == func.as_inner()).then_some(index))
+        .ok_or_else(|| {
+            Error::new(format!(
+                "cannot capture reference to function not owned by the instance: {func:?}"
+            ))
+        })
+}
+
+/// Encodes the Wasm value type `ty`.
+///
+/// # Panics
+///
+/// If `ty` is not supported by Wasmi which cannot happen for compiled Wasm modules.
+fn encode_val_type(ty: wasmparser::ValType) -> enc::ValType {
+    match WasmiValueType::from(ty).into_inner() {
+        ValType::I32 => enc::ValType::I32,
+        ValType::I64 => enc::ValType::I64,
+        ValType::F32 => enc::ValType::F32,
+        ValType::F64 => enc::ValType::F64,
+        ValType::FuncRef => enc::ValType::FUNCREF,
+        ValType::ExternRef => enc::ValType::EXTERNREF,
+    }
+}
+
+/// Encodes the export kind `kind`.
+fn encode_export_kind(kind: ExternalKind) -> enc::ExportKind {
+    match kind {
+        ExternalKind::Func => enc::ExportKind::Func,
+        ExternalKind::Table => enc::ExportKind::Table,
+        ExternalKind::Memory => enc::ExportKind::Memory,
+        ExternalKind::Global => enc::ExportKind::Global,
+        ExternalKind::Tag => enc::ExportKind::Tag,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use wasmparser::{Operator, Validator, WasmFeatures};
+
+    #[test]
+    fn encode_memory64_segments() {
+        let mut wasm = enc::Module::new();
+        let mut memories = enc::MemorySection::new();
+        memories.memory(enc::MemoryType {
+            minimum: 1,
+            maximum: None,
+            memory64: true,
+            shared: false,
+            page_size_log2: None,
+        });
+        wasm.section(&memories);
+        let snapshot = Snapshot {
+            len_imported_memories: 0,
+            memories: vec![MemorySnapshot {
+                pages: 2,
+                segments: vec![(16, Box::from(&[1, 2, 3][..]))],
+            }],
+            globals: Vec::new(),
+        };
+        let output = PreInitializer::new()
+            .rewrite(&wasm.finish(), &snapshot)
+            .unwrap();
+        Validator::new_with_features(WasmFeatures::default() | WasmFeatures::MEMORY64)
+            .validate_all(&output)
+            .unwrap();
+        let mut segments = Vec::new();
+        for payload in Parser::new(0).parse_all(&output) {
+            match payload.unwrap() {
+                Payload::MemorySection(section) => {
+                    let memory = section.into_iter().next().unwrap().unwrap();
+                    assert!(memory.memory64);
+                    assert_eq!(memory.initial, 2);
+                }
+                Payload::DataSection(section) => {
+                    for data in section {
+                        let data = data.unwrap();
+                        let DataKind::Active { offset_expr, .. } = data.kind else {
+                            panic!("expected active data segment: {data:?}")
+                        };
+                        let offset = offset_expr.get_operators_reader().read().unwrap();
+                        segments.push((format!("{offset:?}"), data.data.to_vec()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(
+            segments,
+            [(
+                format!("{:?}", Operator::I64Const { value: 16 }),
+                vec![1, 2, 3]
+            )]
+        );
+    }
+
+    #[test]
+    fn data_segments_works() {
+        assert!(data_segments(&[0; 16]).is_empty());
+        assert_eq!(
+            data_segments(&[0, 1, 0, 2, 0, 0, 0]),
+            [(1, Box::from(&[1, 0, 2][..]))]
+        );
+        let mut data = [0_u8; 32];
+        data[2] = 1;
+        data[20] = 2;
+        data[27] = 3;
+        assert_eq!(
+            data_segments(&data),
+            [
+                (2, Box::from(&[1][..])),
+                (20, Box::from(&[2, 0, 0, 0, 0, 0, 0, 3][..])),
+            ]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{core::TrapCode, Caller, Error, Extern, Linker};
+
+/// The name of the host module that provides the pseudo-random number functions.
+const PRNG_MODULE: &str = "wasmi:prng";
+
+/// The increment of the internal state per drawn number.
+const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// A deterministic pseudo-random number generator for Wasm guests.
+///
+/// A [`Prng`] is usually stored in the host state of a [`Store`] and its
+/// functions are made available to Wasm guests via [`Prng::add_to_linker`].
+///
+/// The generated sequence only depends on the seed which makes executions that
+/// use randomness reproducible, e.g. for simulations or consensus workloads.
+/// Record [`Prng::seed`] and [`Prng::draws`] in order to replay an execution
+/// from its start or to resume it via [`Prng::resume`].
+///
+/// # Note
+///
+/// The generator is _not_ cryptographically secure.
+///
+/// # Guest Interface
+///
+/// All functions are imported from the `wasmi:prng` module:
+///
+/// - `next_u32() -> i32`: Returns the next pseudo-random 32-bit number.
+/// - `next_u64() -> i64`: Returns the next pseudo-random 64-bit number.
+/// - `fill(ptr: i32, len: i32)`: Fills the guest memory at `ptr` with `len` pseudo-random bytes.
+/// - `seed() -> i64`: Returns the seed of the generator.
+///
+/// The guest must export its linear memory as `memory` in order to use `fill`.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Prng {
+    /// The seed of the generator.
+    seed: u64,
+    /// The amount of 64-bit numbers drawn since seeding.
+    draws: u64,
+}
+
+impl Prng {
+    /// Creates a new [`Prng`] seeded with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self::resume(seed, 0)
+    }
+
+    /// Creates a new [`Prng`] seeded with `seed` that already drew `draws` numbers.
+    ///
+    /// The resulting [`Prng`] continues the sequence exactly where a [`Prng`]
+    /// with the same `seed` and `draws` stopped.
+    pub fn resume(seed: u64, draws: u64) -> Self {
+        Self { seed, draws }
+    }
+
+    /// Returns the seed of the [`Prng`].
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the amount of 64-bit numbers drawn from the [`Prng`] since seeding.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Returns the next pseudo-random 64-bit number.
+    pub fn next_u64(&mut self) -> u64 {
+        // Note: this is the SplitMix64 algorithm which allows to jump to any position in O(1).
+        self.draws = self.draws.wrapping_add(1);
+        let mut z = self.seed.wrapping_add(self.draws.wrapping_mul(GAMMA));
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next pseudo-random 32-bit number.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Fills `buffer` with pseudo-random bytes.
+    pub fn fill(&mut self, buffer: &mut [u8]) {
+        let mut chunks = buffer.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        let remainder = chunks.into_remainder();
+        if !remainder.is_empty() {
+            let bytes = self.next_u64().to_le_bytes();
+            remainder.copy_from_slice(&bytes[..remainder.len()]);
+        }
+    }
+
+    /// Defines the functions of the `wasmi:prng` host module in `linker`.
+    ///
+    /// The [`Prng`] of the host state is accessed via `get`.
+    ///
+    /// # Errors
+    ///
+    /// If any of the functions is already defined in `linker`.
+    pub fn add_to_linker<T: 'static>(
+        linker: &mut Linker<T>,
+        get: fn(&mut T) -> &mut Prng,
+    ) -> Result<(), Error> {
+        linker.func_wrap(PRNG_MODULE, "next_u32", move |mut caller: Caller<'_, T>| {
+            get(caller.data_mut()).next_u32()
+        })?;
+        linker.func_wrap(PRNG_MODULE, "next_u64", move |mut caller: Caller<'_, T>| {
+            get(caller.data_mut()).next_u64()
+        })?;
+        linker.func_wrap(
+            PRNG_MODULE,
+            "fill",
+            move |mut caller: Caller<'_, T>, ptr: u32, len: u32| -> Result<(), Error> {
+                let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+                    return Err(Error::new(
+                        "missing `memory` export for `wasmi:prng` functions",
+                    ));
+                };
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                let buffer = data
+                    .get_mut(ptr as usize..)
+                    .and_then(|data| data.get_mut(..len as usize))
+                    .ok_or(TrapCode::MemoryOutOfBounds)?;
+                get(state).fill(buffer);
+                Ok(())
+            },
+        )?;
+        linker.func_wrap(PRNG_MODULE, "seed", move |mut caller: Caller<'_, T>| {
+            get(caller.data_mut()).seed()
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    core::{UntypedVal, ValType},
+    ExternRef,
+    FuncRef,
+    Store,
+};
+use alloc::{collections::BTreeSet, vec::Vec};
+#[cfg(feature = "std")]
+use std::backtrace::Backtrace;
+
+/// Statistics about the references held by a [`Store`].
+///
+/// Queried via [`Store::ref_stats`].
+///
+/// # Note
+///
+/// A [`Store`] never deallocates the objects of its [`ExternRef`]s. Extern objects that are
+/// no longer reachable from any table or global variable are therefore only kept alive by
+/// the host, which is a common cause of growing memory usage in long running stores.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RefStats {
+    /// The number of extern objects allocated by the [`Store`].
+    pub extern_objects: usize,
+    /// The number of distinct extern objects referenced by a table element or global variable.
+    pub reachable_extern_objects: usize,
+    /// The number of non-null `externref` table elements and global variables.
+    pub live_externrefs: usize,
+    /// The number of functions allocated by the [`Store`].
+    pub funcs: usize,
+    /// The number of non-null `funcref` table elements and global variables.
+    pub live_funcrefs: usize,
+}
+
+impl RefStats {
+    /// Returns the number of extern objects that no table element or global variable references.
+    pub fn unreachable_extern_objects(&self) -> usize {
+        self.extern_objects - self.reachable_extern_objects
+    }
+}
+
+/// An extern object that no table element or global variable of its [`Store`] references.
+///
+/// Returned by [`Store::extern_ref_leaks`].
+#[derive(Debug)]
+pub struct ExternRefLeak<'a> {
+    /// The [`ExternRef`] to the unreachable extern object.
+    externref: ExternRef,
+    /// The backtrace of the creation of the extern object if tracked.
+    #[cfg(feature = "std")]
+    backtrace: Option<&'a Backtrace>,
+    #[cfg(not(feature = "std"))]
+    marker: core::marker::PhantomData<&'a ()>,
+}
+
+impl ExternRefLeak<'_> {
+    /// Returns the [`ExternRef`] to the unreachable extern object.
+    pub fn externref(&self) -> ExternRef {
+        self.externref
+    }
+
+    /// Returns the backtrace of the creation of the extern object if any.
+    ///
+    /// Backtraces are only recorded for extern objects created while
+    /// [`Store::track_extern_refs`] is enabled.
+    #[cfg(feature = "std")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace
+    }
+}
+
+impl<T> Store<T> {
+    /// Returns statistics about the [`ExternRef`] and [`FuncRef`] references held by the [`Store`].
+    ///
+    /// This scans all tables and global variables of the [`Store`] and thus is meant
+    /// for diagnostics rather than for use on hot paths.
+    pub fn ref_stats(&self) -> RefStats {
+        let mut stats = RefStats {
+            extern_objects: self.inner.len_extern_objects(),
+            funcs: self.inner.len_funcs(),
+            ..RefStats::default()
+        };
+        stats.reachable_extern_objects = self
+            .reachable_extern_objects(|ty, value| match ty {
+                ValType::ExternRef if !ExternRef::from(value).is_null() => {
+                    stats.live_externrefs += 1
+                }
+                ValType::FuncRef if !FuncRef::from(value).is_null() => stats.live_funcrefs += 1,
+                _ => {}
+            })
+            .len();
+        stats
+    }
+
+    /// Enables or disables tracking the creation of [`ExternRef`]s of the [`Store`].
+    ///
+    /// While enabled every new extern object records the backtrace of its creation which
+    /// is reported by [`Store::extern_ref_leaks`] in order to find the host code that leaks it.
+    ///
+    /// # Note
+    ///
+    /// - Capturing backtraces is expensive and should only be enabled for diagnostics.
+    /// - Disabling tracking keeps the already recorded backtraces.
+    #[cfg(feature = "std")]
+    pub fn track_extern_refs(&mut self, enable: bool) {
+        self.inner.track_extern_refs(enable);
+    }
+
+    /// Returns all extern objects of the [`Store`] that no table element or global variable references.
+    ///
+    /// The returned leaks are ordered by the creation of their extern objects.
+    pub fn extern_ref_leaks(&self) -> Vec<ExternRefLeak<'_>> {
+        let reachable = self.reachable_extern_objects(|_, _| {});
+        self.inner
+            .extern_objects()
+            .filter(|(index, _)| !reachable.contains(index))
+            .map(|(index, externref)| {
+                #[cfg(not(feature = "std"))]
+                let _ = index;
+                ExternRefLeak {
+                    externref,
+                    #[cfg(feature = "std")]
+                    backtrace: self.inner.extern_ref_origin(index),
+                    #[cfg(not(feature = "std"))]
+                    marker: core::marker::PhantomData,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the indices of all extern objects referenced by a table element or global variable.
+    ///
+    /// Calls `f` for every reference typed table element and global variable.
+    fn reachable_extern_objects(&self, mut f: impl FnMut(ValType, UntypedVal)) -> BTreeSet<u32> {
+        let mut reachable = BTreeSet::new();
+        for (ty, value) in self.inner.ref_values() {
+            f(ty, value);
+            if ty != ValType::ExternRef {
+                continue;
+            }
+            if let Some(index) = self.inner.extern_object_index(&ExternRef::from(value)) {
+                reachable.insert(index);
+            }
+        }
+        reachable
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    core::{TrapCode, UntypedVal},
+    engine::FuncParams,
+    func::TrampolineEntity,
+    store::StoreInner,
+    Error,
+    Instance,
+    Store,
+};
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+
+/// A log of host function calls made by Wasm guests of a [`Store`].
+///
+/// Created via [`Store::record_host_calls`] and served back to the guests
+/// via [`Store::replay_host_calls`] which allows to deterministically reproduce
+/// an execution without invoking any of the real host functions.
+///
+/// Every recorded host call consists of:
+///
+/// - The parameters the host function was called with.
+/// - The results or the error returned by the host function.
+/// - The fuel consumed by the host function.
+/// - The bytes the host function wrote to linear memories of the [`Store`].
+///
+/// # Note
+///
+/// - Host calls made while a recorded host function is executing are not recorded
+///   since replaying the outer host call already reproduces their side effects.
+/// - Side effects on tables and globals as well as the growth of linear memories
+///   or linear memories created by host functions are not recorded.
+/// - Recording copies all linear memories of the [`Store`] around every host call
+///   and therefore is intended for debugging purposes.
+/// - Host errors are replayed as errors with the same message and thus can no
+///   longer be downcast to their original type.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HostCallRecording {
+    /// The recorded host calls in the order they were made.
+    calls: Vec<HostCallRecord>,
+}
+
+impl HostCallRecording {
+    /// Returns the number of recorded host calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns `true` if no host calls have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+}
+
+/// A single recorded host function call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HostCallRecord {
+    /// The parameters of the host call.
+    params: Box<[UntypedVal]>,
+    /// The outcome of the host call.
+    outcome: HostCallOutcome,
+    /// The fuel consumed by the host call.
+    fuel_consumed: u64,
+    /// The writes of the host call to linear memories.
+    memory_writes: Box<[MemoryWrite]>,
+}
+
+/// The outcome of a recorded host function call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostCallOutcome {
+    /// The host call returned the results.
+    Return(Box<[UntypedVal]>),
+    /// The host call trapped.
+    Trap(TrapCode),
+    /// The host call exited with the status.
+    Exit(i32),
+    /// The host call returned an error with the message.
+    Error(Box<str>),
+}
+
+impl HostCallOutcome {
+    /// Creates a [`HostCallOutcome`] from the `error` returned by a host call.
+    fn from_error(error: &Error) -> Self {
+        if let Some(trap_code) = error.as_trap_code() {
+            return Self::Trap(trap_code);
+        }
+        if let Some(status) = error.i32_exit_status() {
+            return Self::Exit(status);
+        }
+        Self::Error(error.to_string().into())
+    }
+}
+
+/// A recorded write of a host function call to a linear memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MemoryWrite {
+    /// The index of the linear memory within the [`Store`].
+    memory: usize,
+    /// The offset of the first written byte.
+    offset: usize,
+    /// The written bytes.
+    bytes: Box<[u8]>,
+}
+
+/// The host call log of a [`Store`] that is either recording or replaying.
+#[derive(Debug)]
+pub(crate) enum HostCallLog {
+    /// New host calls are appended to the recording.
+    Recording(HostCallRecording),
+    /// Host calls are served from the recording.
+    Replaying {
+        /// The recording that is replayed.
+        recording: HostCallRecording,
+        /// The index of the next host call to replay.
+        next: usize,
+    },
+}
+
+impl HostCallLog {
+    /// Consumes `self` and returns the underlying [`HostCallRecording`].
+    pub fn into_recording(self) -> HostCallRecording {
+        match self {
+            Self::Recording(recording) | Self::Replaying { recording, .. } => recording,
+        }
+    }
+
+    /// Returns the number of host calls recorded or replayed so far.
+    #[cfg(feature = "time-travel")]
+    pub fn position(&self) -> usize {
+        match self {
+            Self::Recording(recording) => recording.len(),
+            Self::Replaying { next, .. } => *next,
+        }
+    }
+
+    /// Records or replays the call of the host function `trampoline`.
+    ///
+    /// The parameters are read from and the results are written to `params_results`.
+    pub fn call<T>(
+        &mut self,
+        store: &mut Store<T>,
+        trampoline: &TrampolineEntity<T>,
+        instance: Option<&Instance>,
+        params_results: &mut [UntypedVal],
+        len_params: usize,
+        len_results: usize,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Recording(recording) => {
+                let params = params_results[..len_params].into();
+                let memories: Vec<Box<[u8]>> = store.inner.memories_data().map(Box::from).collect();
+                let fuel_before = store.get_fuel().ok();
+                let result = trampoline.call(
+                    &mut *store,
+                    instance,
+                    FuncParams::new(params_results, len_params, len_results),
+                );
+                let outcome = match &result {
+                    Ok(_) => HostCallOutcome::Return(params_results[..len_results].into()),
+                    Err(error) => HostCallOutcome::from_error(error),
+                };
+                let fuel_consumed = match (fuel_before, store.get_fuel().ok()) {
+                    (Some(before), Some(after)) => before.saturating_sub(after),
+                    _ => 0,
+                };
+                recording.calls.push(HostCallRecord {
+                    params,
+                    outcome,
+                    fuel_consumed,
+                    memory_writes: diff_memories(&memories, &store.inner),
+                });
+                result.map(|_| ())
+            }
+            Self::Replaying { recording, next } => {
+                let index = *next;
+                let Some(record) = recording.calls.get(index) else {
+                    return Err(Error::new(format!(
+                        "host call replay exhausted after {index} host calls"
+                    )));
+                };
+                if *record.params != params_results[..len_params] {
+                    return Err(Error::new(format!(
+                        "host call replay diverged at host call {index}: \
+                        expected parameters {:?} but found {:?}",
+                        record.params,
+                        &params_results[..len_params],
+                    )));
+                }
+                *next += 1;
+                store
+                    .inner
+                    .fuel_mut()
+                    .consume_fuel_if(|_| record.fuel_consumed)?;
+                for write in &record.memory_writes {
+                    let Some(memory) = store
+                        .inner
+                        .memory_data_mut(write.memory)
+                        .and_then(|data| data.get_mut(write.offset..))
+                        .and_then(|data| data.get_mut(..write.bytes.len()))
+                    else {
+                        return Err(Error::new(format!(
+                            "host call replay diverged at host call {index}: \
+                            recorded write to linear memory {} is out of bounds",
+                            write.memory,
+                        )));
+                    };
+                    memory.copy_from_slice(&write.bytes);
+                }
+                match &record.outcome {
+                    HostCallOutcome::Return(results) => {
+                        if results.len() != len_results {
+                            return Err(Error::new(format!(
+                                "host call replay diverged at host call {index}: \
+                                expected {} results but found {len_results}",
+                                results.len(),
+                            )));
+                        }
+                        params_results[..len_results].copy_from_slice(results);
+                        Ok(())
+                    }
+                    HostCallOutcome::Trap(trap_code) => Err(Error::from(*trap_code)),
+                    HostCallOutcome::Exit(status) => Err(Error::i32_exit(*status)),
+                    HostCallOutcome::Error(message) => Err(Error::new(message.clone())),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the writes to the linear memories of `store` since `snapshot` was taken.
+///
+/// Linear memories that did not exist when `snapshot` was taken are ignored.
+fn diff_memories(snapshot: &[Box<[u8]>], store: &StoreInner) -> Box<[MemoryWrite]> {
+    let mut writes = Vec::new();
+    for (memory, (before, after)) in snapshot.iter().zip(store.memories_data()).enumerate() {
+        let mut offset = 0;
+        while offset < after.len() {
+            let is_changed = |at: usize| before.get(at).copied().unwrap_or(0) != after[at];
+            if !is_changed(offset) {
+                offset += 1;
+                continue;
+            }
+            let start = offset;
+            while offset < after.len() && is_changed(offset) {
+                offset += 1;
+            }
+            writes.push(MemoryWrite {
+                memory,
+                offset: start,
+                bytes: after[start..offset].into(),
+            });
+        }
+    }
+    writes.into_boxed_slice()
+}
@@ -0,0 +1,287 @@
+use crate::{
+    engine::{PreemptibleCall, Stack},
+    AsContextMut,
+    Error,
+    Func,
+    Store,
+    Val,
+};
+use alloc::{boxed::Box, collections::VecDeque};
+use core::mem;
+
+/// Identifies a task spawned on a [`Scheduler`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(u64);
+
+/// Runs many Wasm executions concurrently on a single thread.
+///
+/// Every task owns its [`Store`] and executes a Wasm function called from the host.
+/// The [`Scheduler`] runs the tasks in round-robin order and preempts them once they
+/// consumed their slice of fuel which makes even guests that never yield share time fairly.
+///
+/// # Note
+///
+/// - The [`Store`] of every task must have fuel metering enabled via [`Config::consume_fuel`].
+/// - The [`Scheduler`] takes control of the fuel of the [`Store`] of every task and
+///   refuels it with the fuel slice of the task whenever the task is run.
+/// - A task with priority `n` receives `n` times the fuel slice of the [`Scheduler`]
+///   every time it is run.
+/// - Host functions that return errors complete their task with the error.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Config, Engine, Linker, Module, Scheduler, Store, Val};
+/// # fn main() -> Result<(), wasmi::Error> {
+/// let mut config = Config::default();
+/// config.consume_fuel(true);
+/// let engine = Engine::new(&config);
+/// let module = Module::new(&engine, r#"
+///     (module
+///         (func (export "count") (param $n i32) (result i32)
+///             (local $i i32)
+///             (loop $continue
+///                 (local.set $i (i32.add (local.get $i) (i32.const 1)))
+///                 (br_if $continue (i32.lt_u (local.get $i) (local.get $n)))
+///             )
+///             (local.get $i)
+///         )
+///     )
+/// "#)?;
+/// let linker = <Linker<()>>::new(&engine);
+/// let mut scheduler = Scheduler::new(100);
+/// for n in [1_000, 10] {
+///     let mut store = Store::new(&engine, ());
+///     let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+///     let count = instance.get_func(&store, "count").unwrap();
+///     scheduler.spawn(store, count, &[Val::I32(n)])?;
+/// }
+/// let mut finished = Vec::new();
+/// scheduler.run(|completion| finished.push(completion.result().unwrap()[0].i32()));
+/// assert_eq!(finished, [Some(10), Some(1_000)]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Config::consume_fuel`]: crate::Config::consume_fuel
+#[derive(Debug)]
+pub struct Scheduler<T> {
+    /// The fuel a task with priority 1 receives every time it is run.
+    fuel_slice: u64,
+    /// The tasks in the order in which they are run next.
+    tasks: VecDeque<Task<T>>,
+    /// The identifier of the next spawned task.
+    next_id: u64,
+}
+
+/// A task of the [`Scheduler`].
+#[derive(Debug)]
+struct Task<T> {
+    /// The identifier of the task.
+    id: TaskId,
+    /// The priority of the task.
+    priority: u32,
+    /// The [`Store`] owned by the task.
+    store: Store<T>,
+    /// The Wasm function executed by the task.
+    func: Func,
+    /// The execution state of the task.
+    state: TaskState,
+}
+
+/// The execution state of a [`Task`].
+#[derive(Debug)]
+enum TaskState {
+    /// The task has not been run yet and starts with the parameters.
+    Ready(Box<[Val]>),
+    /// The task ran out of fuel and continues with the stack.
+    Preempted(Stack),
+}
+
+/// A task of a [`Scheduler`] that finished its execution.
+#[derive(Debug)]
+pub struct Completion<T> {
+    /// The identifier of the completed task.
+    id: TaskId,
+    /// The [`Store`] owned by the completed task.
+    store: Store<T>,
+    /// The results or the error of the execution of the completed task.
+    result: Result<Box<[Val]>, Error>,
+}
+
+impl<T> Completion<T> {
+    /// Returns the [`TaskId`] of the completed task.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Returns the results of the execution of the completed task.
+    ///
+    /// # Errors
+    ///
+    /// If the execution of the completed task failed.
+    pub fn result(&self) -> Result<&[Val], &Error> {
+        self.result.as_deref()
+    }
+
+    /// Returns a shared reference to the [`Store`] of the completed task.
+    pub fn store(&self) -> &Store<T> {
+        &self.store
+    }
+
+    /// Returns an exclusive reference to the [`Store`] of the completed task.
+    pub fn store_mut(&mut self) -> &mut Store<T> {
+        &mut self.store
+    }
+
+    /// Consumes `self` and returns the [`Store`] and the result of the completed task.
+    pub fn into_parts(self) -> (Store<T>, Result<Box<[Val]>, Error>) {
+        (self.store, self.result)
+    }
+}
+
+impl<T> Scheduler<T> {
+    /// Creates a new [`Scheduler`] that runs tasks with priority 1 for `fuel_slice` units of fuel.
+    ///
+    /// A `fuel_slice` of zero is treated as one.
+    pub fn new(fuel_slice: u64) -> Self {
+        Self {
+            fuel_slice: fuel_slice.max(1),
+            tasks: VecDeque::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Spawns a task with priority 1 that calls `func` with `params` using `store`.
+    ///
+    /// The task is run after all previously spawned tasks.
+    ///
+    /// # Errors
+    ///
+    /// - If fuel metering is disabled for `store`.
+    /// - If `params` do not match the parameters of `func`.
+    pub fn spawn(&mut self, store: Store<T>, func: Func, params: &[Val]) -> Result<TaskId, Error> {
+        store.get_fuel()?;
+        func.ty(&store).match_params(params)?;
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+        self.tasks.push_back(Task {
+            id,
+            priority: 1,
+            store,
+            func,
+            state: TaskState::Ready(params.into()),
+        });
+        Ok(id)
+    }
+
+    /// Returns the number of tasks that have not completed yet.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if all tasks have completed.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Returns the priority of the task with `id` if it has not completed yet.
+    pub fn priority(&self, id: TaskId) -> Option<u32> {
+        self.task(id).map(|task| task.priority)
+    }
+
+    /// Sets the priority of the task with `id` to `priority`.
+    ///
+    /// A task with priority `n` receives `n` times the fuel slice of the [`Scheduler`]
+    /// every time it is run. A `priority` of zero is treated as one.
+    ///
+    /// Returns `false` if the task has already completed.
+    pub fn set_priority(&mut self, id: TaskId, priority: u32) -> bool {
+        match self.tasks.iter_mut().find(|task| task.id == id) {
+            Some(task) => {
+                task.priority = priority.max(1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns a shared reference to the [`Store`] of the task with `id` if it has not completed yet.
+    pub fn store(&self, id: TaskId) -> Option<&Store<T>> {
+        self.task(id).map(|task| &task.store)
+    }
+
+    /// Returns the task with `id` if it has not completed yet.
+    fn task(&self, id: TaskId) -> Option<&Task<T>> {
+        self.tasks.iter().find(|task| task.id == id)
+    }
+
+    /// Runs the next task for its slice of fuel.
+    ///
+    /// Returns the [`Completion`] of the task if it finished its execution.
+    /// Otherwise the task is run again after all other tasks have been run.
+    pub fn step(&mut self) -> Option<Completion<T>> {
+        let mut task = self.tasks.pop_front()?;
+        let fuel = self.fuel_slice.saturating_mul(u64::from(task.priority));
+        let result = task.store.set_fuel(fuel).and_then(|()| task.run());
+        match result {
+            Ok(None) => {
+                self.tasks.push_back(task);
+                None
+            }
+            Ok(Some(results)) => Some(task.complete(Ok(results))),
+            Err(error) => Some(task.complete(Err(error))),
+        }
+    }
+
+    /// Runs all tasks until they have completed and calls `on_complete` for every completed task.
+    pub fn run(&mut self, mut on_complete: impl FnMut(Completion<T>)) {
+        while !self.is_empty() {
+            if let Some(completion) = self.step() {
+                on_complete(completion);
+            }
+        }
+    }
+}
+
+impl<T> Task<T> {
+    /// Runs the task until it finishes or runs out of fuel.
+    ///
+    /// Returns the results if the task finished and `None` if it ran out of fuel.
+    fn run(&mut self) -> Result<Option<Box<[Val]>>, Error> {
+        let func_type = self.func.ty(&self.store);
+        let mut results = func_type
+            .results()
+            .iter()
+            .copied()
+            .map(Val::default)
+            .collect::<Box<[Val]>>();
+        let engine = self.store.engine().clone();
+        let ctx = self.store.as_context_mut();
+        let call = match &mut self.state {
+            TaskState::Ready(params) => {
+                engine.execute_func_preemptible(ctx, &self.func, &params[..], &mut results[..])?
+            }
+            TaskState::Preempted(stack) => {
+                let stack = mem::replace(stack, Stack::empty());
+                engine.resume_func_preempted(ctx, stack, &mut results[..])?
+            }
+        };
+        match call {
+            PreemptibleCall::Finished(()) => Ok(Some(results)),
+            PreemptibleCall::OutOfFuel(stack) => {
+                self.state = TaskState::Preempted(stack);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Consumes the task and returns its [`Completion`] with `result`.
+    fn complete(self, result: Result<Box<[Val]>, Error>) -> Completion<T> {
+        Completion {
+            id: self.id,
+            store: self.store,
+            result,
+        }
+    }
+}
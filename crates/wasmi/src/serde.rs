@@ -0,0 +1,173 @@
+//! Exchanges [`serde`] serializable values with Wasm guests.
+//!
+//! Values are encoded in a configurable [`Format`] and passed via the `(ptr, len)`
+//! convention of [`GuestAbi`] which makes plugin RPC between host and guest a matter
+//! of a single [`GuestCodec::call`].
+//!
+//! # Example
+//!
+//! ```
+//! # use wasmi::{Engine, GuestAbi, Linker, Module, Store};
+//! use wasmi::serde::{Format, GuestCodec};
+//! # let wasm = wat::parse_str(r#"
+//! #     (module
+//! #         (memory (export "memory") 1)
+//! #         (global $next (mut i32) (i32.const 1024))
+//! #         (func (export "malloc") (param $len i32) (result i32)
+//! #             (global.get $next)
+//! #             (global.set $next (i32.add (global.get $next) (local.get $len)))
+//! #         )
+//! #         (func (export "echo") (param i32 i32) (result i32 i32)
+//! #             (local.get 0) (local.get 1)
+//! #         )
+//! #     )
+//! # "#).unwrap();
+//! let engine = Engine::default();
+//! let module = Module::new(&engine, &wasm[..]).unwrap();
+//! let mut store = Store::new(&engine, ());
+//! let instance = Linker::new(&engine)
+//!     .instantiate(&mut store, &module)
+//!     .unwrap()
+//!     .start(&mut store)
+//!     .unwrap();
+//! let abi = GuestAbi::new(&store, &instance).unwrap();
+//! let codec = GuestCodec::new(abi, Format::Json);
+//! let echo = instance.get_func(&store, "echo").unwrap();
+//! let response: (u32, String) = codec.call(&mut store, &echo, &(42, "wasmi")).unwrap();
+//! assert_eq!(response, (42, String::from("wasmi")));
+//! ```
+//!
+//! [`serde`]: https://crates.io/crates/serde
+
+use crate::{AsContext, AsContextMut, Error, Func, GuestAbi, GuestSlice};
+use alloc::{format, vec::Vec};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The encoding of values exchanged via a [`GuestCodec`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// The compact binary [`postcard`] format.
+    ///
+    /// [`postcard`]: https://crates.io/crates/postcard
+    #[default]
+    Postcard,
+    /// The human readable JSON format.
+    Json,
+}
+
+impl Format {
+    /// Encodes `value` in this [`Format`].
+    ///
+    /// # Errors
+    ///
+    /// If `value` cannot be serialized.
+    pub fn encode<V>(self, value: &V) -> Result<Vec<u8>, Error>
+    where
+        V: Serialize + ?Sized,
+    {
+        match self {
+            Self::Postcard => postcard::to_allocvec(value)
+                .map_err(|error| Error::new(format!("failed to encode postcard: {error}"))),
+            Self::Json => serde_json::to_vec(value)
+                .map_err(|error| Error::new(format!("failed to encode JSON: {error}"))),
+        }
+    }
+
+    /// Decodes a value encoded in this [`Format`] from `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// If `bytes` cannot be deserialized into a `V`.
+    pub fn decode<V>(self, bytes: &[u8]) -> Result<V, Error>
+    where
+        V: DeserializeOwned,
+    {
+        match self {
+            Self::Postcard => postcard::from_bytes(bytes)
+                .map_err(|error| Error::new(format!("failed to decode postcard: {error}"))),
+            Self::Json => serde_json::from_slice(bytes)
+                .map_err(|error| Error::new(format!("failed to decode JSON: {error}"))),
+        }
+    }
+}
+
+/// Serializes values into and deserializes values from the memory of a Wasm guest.
+///
+/// Guest memory is allocated and freed according to the conventions of [`GuestAbi`].
+#[derive(Debug, Copy, Clone)]
+pub struct GuestCodec {
+    /// The ABI used to access the guest memory.
+    abi: GuestAbi,
+    /// The encoding of the exchanged values.
+    format: Format,
+}
+
+impl GuestCodec {
+    /// Creates a new [`GuestCodec`] that exchanges values encoded in `format` via `abi`.
+    pub fn new(abi: GuestAbi, format: Format) -> Self {
+        Self { abi, format }
+    }
+
+    /// Returns the [`GuestAbi`] of the [`GuestCodec`].
+    pub fn abi(&self) -> &GuestAbi {
+        &self.abi
+    }
+
+    /// Returns the [`Format`] of the [`GuestCodec`].
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Serializes `value` into newly allocated guest memory.
+    ///
+    /// # Errors
+    ///
+    /// - If `value` cannot be serialized.
+    /// - If the guest memory cannot be allocated or written.
+    pub fn write<V>(&self, ctx: impl AsContextMut, value: &V) -> Result<GuestSlice, Error>
+    where
+        V: Serialize + ?Sized,
+    {
+        let bytes = self.format.encode(value)?;
+        self.abi.write_bytes(ctx, &bytes)
+    }
+
+    /// Deserializes a value from the guest memory referred to by `slice`.
+    ///
+    /// # Errors
+    ///
+    /// - If `slice` is out of bounds of the guest memory.
+    /// - If the guest memory cannot be deserialized into a `V`.
+    pub fn read<V>(&self, ctx: impl AsContext, slice: GuestSlice) -> Result<V, Error>
+    where
+        V: DeserializeOwned,
+    {
+        let bytes = self.abi.read_bytes(ctx, slice)?;
+        self.format.decode(&bytes)
+    }
+
+    /// Calls `func` with the serialized `request` and returns its deserialized response.
+    ///
+    /// The guest function must be of type `(ptr: i32, len: i32) -> (ptr: i32, len: i32)`.
+    /// See [`GuestAbi::call`] for the ownership of the exchanged guest memory.
+    ///
+    /// # Errors
+    ///
+    /// - If `request` cannot be serialized or the response cannot be deserialized.
+    /// - If `func` is not of the expected type.
+    /// - If the execution of `func` traps.
+    pub fn call<Request, Response>(
+        &self,
+        ctx: impl AsContextMut,
+        func: &Func,
+        request: &Request,
+    ) -> Result<Response, Error>
+    where
+        Request: Serialize + ?Sized,
+        Response: DeserializeOwned,
+    {
+        let request = self.format.encode(request)?;
+        let response: Vec<u8> = self.abi.call(ctx, func, &request[..])?;
+        self.format.decode(&response)
+    }
+}
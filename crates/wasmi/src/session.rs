@@ -0,0 +1,152 @@
+use crate::{AsContext, AsContextMut, Error, Extern, Func, Instance, Linker, Module};
+use alloc::boxed::Box;
+
+/// An environment to incrementally define and call Wasm functions.
+///
+/// A [`Session`] is useful for REPL or notebook front-ends that evaluate Wasm snippets
+/// one after another where later snippets use the definitions of earlier snippets.
+///
+/// # Note
+///
+/// - Every snippet is a Wasm module that is compiled and instantiated on its own.
+/// - Snippets can import all definitions of the [`Session`] from its namespace.
+/// - All exports of a snippet are added to the namespace of the [`Session`] for later snippets.
+/// - Definitions of later snippets shadow earlier definitions of the same name.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, Linker, Session, Store};
+/// # fn main() -> Result<(), wasmi::Error> {
+/// let engine = Engine::default();
+/// let mut store = Store::new(&engine, ());
+/// let mut session = Session::new(<Linker<()>>::new(&engine), "repl");
+/// session.eval(
+///     &mut store,
+///     r#"
+///     (module
+///         (func (export "double") (param i32) (result i32)
+///             (i32.add (local.get 0) (local.get 0))
+///         )
+///     )
+///     "#,
+/// )?;
+/// session.eval(
+///     &mut store,
+///     r#"
+///     (module
+///         (import "repl" "double" (func $double (param i32) (result i32)))
+///         (func (export "quad") (param i32) (result i32)
+///             (call $double (call $double (local.get 0)))
+///         )
+///     )
+///     "#,
+/// )?;
+/// let quad = session
+///     .get_func(&store, "quad")
+///     .unwrap()
+///     .typed::<i32, i32>(&store)?;
+/// assert_eq!(quad.call(&mut store, 5)?, 20);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Session<T> {
+    /// The [`Linker`] that holds all definitions of the [`Session`].
+    linker: Linker<T>,
+    /// The module name under which all definitions of the [`Session`] are found.
+    namespace: Box<str>,
+}
+
+impl<T> Session<T> {
+    /// Creates a new [`Session`] with the definitions of `linker` and `namespace`.
+    ///
+    /// Snippets evaluated in the [`Session`] can import all definitions of `linker`
+    /// in addition to the definitions of the [`Session`] under the `namespace` module name.
+    ///
+    /// # Note
+    ///
+    /// Enables [`Linker::allow_shadowing`] for `linker` so that snippets can redefine items.
+    pub fn new(mut linker: Linker<T>, namespace: &str) -> Self {
+        linker.allow_shadowing(true);
+        Self {
+            linker,
+            namespace: namespace.into(),
+        }
+    }
+
+    /// Returns the module name under which all definitions of the [`Session`] are found.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Returns a shared reference to the [`Linker`] of the [`Session`].
+    pub fn linker(&self) -> &Linker<T> {
+        &self.linker
+    }
+
+    /// Adds all exports of `instance` to the namespace of the [`Session`].
+    ///
+    /// This allows to evaluate snippets against an existing [`Instance`].
+    ///
+    /// # Errors
+    ///
+    /// If `instance` comes from a different [`Store`](crate::Store) than the [`Session`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`](crate::Engine) of the [`Session`] and of `store` are not the same.
+    pub fn define_instance(
+        &mut self,
+        store: impl AsContextMut<Data = T>,
+        instance: Instance,
+    ) -> Result<&mut Self, Error> {
+        self.linker.instance(store, &self.namespace, instance)?;
+        Ok(self)
+    }
+
+    /// Compiles, instantiates and starts the `wasm` snippet.
+    ///
+    /// Returns the [`Instance`] of the snippet. All its exports are added to the namespace
+    /// of the [`Session`] and shadow earlier definitions of the same name.
+    ///
+    /// # Errors
+    ///
+    /// - If `wasm` fails to compile or if its imports cannot be resolved.
+    /// - If the `start` function of the snippet traps.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`](crate::Engine) of the [`Session`] and of `store` are not the same.
+    pub fn eval(
+        &mut self,
+        mut store: impl AsContextMut<Data = T>,
+        wasm: impl AsRef<[u8]>,
+    ) -> Result<Instance, Error> {
+        let module = Module::new(self.linker.engine(), wasm)?;
+        let instance = self
+            .linker
+            .instantiate(&mut store, &module)?
+            .start(&mut store)?;
+        self.define_instance(&mut store, instance)?;
+        Ok(instance)
+    }
+
+    /// Returns the [`Extern`] defined under `name` in the namespace of the [`Session`] if any.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`](crate::Engine) of the [`Session`] and of `store` are not the same.
+    pub fn get(&self, store: impl AsContext<Data = T>, name: &str) -> Option<Extern> {
+        self.linker.get(store, &self.namespace, name)
+    }
+
+    /// Returns the [`Func`] defined under `name` in the namespace of the [`Session`] if any.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`](crate::Engine) of the [`Session`] and of `store` are not the same.
+    pub fn get_func(&self, store: impl AsContext<Data = T>, name: &str) -> Option<Func> {
+        self.get(store, name)?.into_func()
+    }
+}
@@ -0,0 +1,358 @@
+use crate::{
+    core::{HostError, TrapCode},
+    AsContextMut,
+    Caller,
+    Error,
+    Extern,
+    ExternType,
+    Func,
+    Linker,
+    Memory,
+    Module,
+    Val,
+};
+use alloc::format;
+use core::fmt;
+
+/// The namespace of the imports resolved by [`EmscriptenSjlj::add_to_linker`].
+const ENV_MODULE: &str = "env";
+
+/// The name prefix of the `invoke_*` trampoline imports.
+const INVOKE_PREFIX: &str = "invoke_";
+
+/// The names of the guest exports saving the stack pointer in order of preference.
+const STACK_SAVE: &[&str] = &["stackSave", "emscripten_stack_get_current"];
+
+/// The names of the guest exports restoring the stack pointer in order of preference.
+const STACK_RESTORE: &[&str] = &["stackRestore", "_emscripten_stack_restore"];
+
+/// The size of an entry of a setjmp table in bytes: a setjmp id followed by its label.
+const SETJMP_ENTRY_SIZE: u32 = 8;
+
+/// The [`HostError`] unwinding the Wasm stack upon a `longjmp` until it is caught by an `invoke_*` trampoline.
+#[derive(Debug)]
+struct Longjmp;
+
+impl fmt::Display for Longjmp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "longjmp without a matching setjmp")
+    }
+}
+
+impl HostError for Longjmp {}
+
+/// Host-side emulation of `setjmp`/`longjmp` for Emscripten compiled Wasm guests.
+///
+/// Emscripten compiles C code using `setjmp` and `longjmp` without the Wasm exception
+/// handling proposal by routing calls that might `longjmp` through `invoke_*` imports
+/// provided by its JavaScript runtime. A [`EmscriptenSjlj`] is usually stored in the host
+/// state of a [`Store`] and provides these imports via [`EmscriptenSjlj::add_to_linker`].
+///
+/// # Guest Interface
+///
+/// The following functions are resolved if they are imported from the `env` module:
+///
+/// - `invoke_<sig>(index: i32, args...) -> results`: Calls the function at `index` of the
+///   exported `__indirect_function_table` with `args`. Upon a `longjmp` the stack pointer
+///   is restored, `setThrew(1, 0)` is called and all results are zero.
+/// - `_emscripten_throw_longjmp()`: Unwinds the Wasm stack to the innermost `invoke_*` call.
+/// - `emscripten_longjmp(env: i32, value: i32)`: Calls `setThrew(env, value)` with a
+///   `value` of 1 instead of 0 and unwinds like `_emscripten_throw_longjmp`.
+/// - `saveSetjmp(env: i32, label: i32, table: i32, size: i32) -> i32`: Registers a new
+///   setjmp id at `env` in the setjmp `table`, growing it via the exported `realloc`.
+/// - `testSetjmp(id: i32, table: i32, size: i32) -> i32`: Returns the label of `id` or 0.
+/// - `getTempRet0() -> i32` and `setTempRet0(value: i32)`: Access the temporary return value.
+///
+/// The guest must export `setThrew` and the stack pointer functions `stackSave` and
+/// `stackRestore` or their `emscripten_stack_get_current` and `_emscripten_stack_restore`
+/// counterparts. `saveSetjmp` and `testSetjmp` require an exported linear memory named `memory`.
+///
+/// # Note
+///
+/// - Wasmi does not support the Wasm exception handling proposal, therefore guests must be
+///   compiled with `-sSUPPORT_LONGJMP=emscripten` instead of `-sSUPPORT_LONGJMP=wasm`.
+/// - C++ exceptions thrown via the `invoke_*` trampolines are not supported.
+/// - A `longjmp` that is not caught by an `invoke_*` trampoline returns an [`Error`] to the host.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EmscriptenSjlj {
+    /// The temporary return value set via `setTempRet0`.
+    temp_ret0: i32,
+    /// The last setjmp id registered via `saveSetjmp`.
+    setjmp_id: i32,
+}
+
+impl EmscriptenSjlj {
+    /// Creates a new [`EmscriptenSjlj`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the temporary return value set via `setTempRet0`.
+    pub fn temp_ret0(&self) -> i32 {
+        self.temp_ret0
+    }
+
+    /// Defines the `setjmp`/`longjmp` functions imported by `module` in `linker`.
+    ///
+    /// The [`EmscriptenSjlj`] of the host state is accessed via `get`.
+    ///
+    /// # Errors
+    ///
+    /// If any of the functions is already defined in `linker`.
+    pub fn add_to_linker<T: 'static>(
+        linker: &mut Linker<T>,
+        module: &Module,
+        get: fn(&mut T) -> &mut EmscriptenSjlj,
+    ) -> Result<(), Error> {
+        for import in module.imports() {
+            if import.module() != ENV_MODULE {
+                continue;
+            }
+            let ExternType::Func(ty) = import.ty() else {
+                continue;
+            };
+            let name = import.name();
+            match name {
+                _ if name.starts_with(INVOKE_PREFIX) => {
+                    linker.func_new(ENV_MODULE, name, ty.clone(), invoke)?;
+                }
+                "_emscripten_throw_longjmp" => {
+                    linker.func_wrap(
+                        ENV_MODULE,
+                        name,
+                        |_: Caller<'_, T>| -> Result<(), Error> { Err(Error::host(Longjmp)) },
+                    )?;
+                }
+                "emscripten_longjmp" => {
+                    linker.func_wrap(
+                        ENV_MODULE,
+                        name,
+                        |mut caller: Caller<'_, T>, env: i32, value: i32| -> Result<(), Error> {
+                            let value = if value == 0 { 1 } else { value };
+                            call_export(
+                                &mut caller,
+                                "setThrew",
+                                &[Val::I32(env), Val::I32(value)],
+                            )?;
+                            Err(Error::host(Longjmp))
+                        },
+                    )?;
+                }
+                "saveSetjmp" => {
+                    linker.func_wrap(
+                        ENV_MODULE,
+                        name,
+                        move |mut caller: Caller<'_, T>,
+                              env: u32,
+                              label: i32,
+                              table: u32,
+                              size: u32|
+                              -> Result<u32, Error> {
+                            save_setjmp(&mut caller, get, env, label, table, size)
+                        },
+                    )?;
+                }
+                "testSetjmp" => {
+                    linker.func_wrap(
+                        ENV_MODULE,
+                        name,
+                        |caller: Caller<'_, T>, id: i32, table: u32, size: u32| {
+                            test_setjmp(&caller, id, table, size)
+                        },
+                    )?;
+                }
+                "getTempRet0" => {
+                    linker.func_wrap(ENV_MODULE, name, move |mut caller: Caller<'_, T>| {
+                        get(caller.data_mut()).temp_ret0
+                    })?;
+                }
+                "setTempRet0" => {
+                    linker.func_wrap(
+                        ENV_MODULE,
+                        name,
+                        move |mut caller: Caller<'_, T>, value: i32| {
+                            get(caller.data_mut()).temp_ret0 = value;
+                        },
+                    )?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Calls the function at `params[0]` of the exported `__indirect_function_table` with the remaining `params`.
+///
+/// Catches a `longjmp` that unwinds the called function.
+fn invoke<T>(mut caller: Caller<'_, T>, params: &[Val], results: &mut [Val]) -> Result<(), Error> {
+    let Some((Val::I32(index), params)) = params.split_first() else {
+        return Err(Error::new(
+            "`invoke_*` functions require an `i32` table index as first parameter",
+        ));
+    };
+    let Some(Extern::Table(table)) = caller.get_export("__indirect_function_table") else {
+        return Err(Error::new(
+            "missing `__indirect_function_table` export for `invoke_*` functions",
+        ));
+    };
+    let func = match table.get(&caller, *index as u32) {
+        Some(Val::FuncRef(funcref)) => funcref.func().copied(),
+        _ => None,
+    }
+    .ok_or(TrapCode::IndirectCallToNull)?;
+    let stack_pointer = stack_save(&mut caller)?;
+    let Err(error) = func.call(&mut caller, params, results) else {
+        return Ok(());
+    };
+    if error.downcast_ref::<Longjmp>().is_none() {
+        return Err(error);
+    }
+    stack_restore(&mut caller, stack_pointer)?;
+    call_export(&mut caller, "setThrew", &[Val::I32(1), Val::I32(0)])?;
+    for result in results {
+        *result = Val::default(result.ty());
+    }
+    Ok(())
+}
+
+/// Registers a new setjmp id at `env` together with `label` in the setjmp `table` of `size` entries.
+///
+/// Returns the possibly reallocated `table` and sets the temporary return value to its size.
+fn save_setjmp<T>(
+    caller: &mut Caller<'_, T>,
+    get: fn(&mut T) -> &mut EmscriptenSjlj,
+    env: u32,
+    label: i32,
+    mut table: u32,
+    mut size: u32,
+) -> Result<u32, Error> {
+    let state = get(caller.data_mut());
+    state.setjmp_id = state.setjmp_id.wrapping_add(1);
+    let id = state.setjmp_id;
+    store_i32(caller, env, id)?;
+    let mut index = 0;
+    loop {
+        while index < size {
+            let entry = entry_address(table, index)?;
+            if load_i32(caller, entry)? == 0 {
+                store_i32(caller, entry, id)?;
+                store_i32(caller, entry + 4, label)?;
+                // Terminates the table with an empty entry.
+                store_i32(caller, entry + SETJMP_ENTRY_SIZE, 0)?;
+                get(caller.data_mut()).temp_ret0 = size as i32;
+                return Ok(table);
+            }
+            index += 1;
+        }
+        size = size.checked_mul(2).ok_or(TrapCode::MemoryOutOfBounds)?;
+        let new_size = entry_address(0, size + 1)?;
+        let mut new_table = [Val::I32(0)];
+        call_export_with_results(
+            caller,
+            "realloc",
+            &[Val::I32(table as i32), Val::I32(new_size as i32)],
+            &mut new_table,
+        )?;
+        table = match new_table {
+            [Val::I32(new_table)] if new_table != 0 => new_table as u32,
+            _ => return Err(Error::new("failed to grow the setjmp table via `realloc`")),
+        };
+    }
+}
+
+/// Returns the label of the setjmp `id` in the setjmp `table` of `size` entries or 0 if none.
+fn test_setjmp<T>(caller: &Caller<'_, T>, id: i32, table: u32, size: u32) -> Result<i32, Error> {
+    for index in 0..size {
+        let entry = entry_address(table, index)?;
+        match load_i32(caller, entry)? {
+            0 => break,
+            current if current == id => return load_i32(caller, entry + 4),
+            _ => {}
+        }
+    }
+    Ok(0)
+}
+
+/// Returns the address of the entry at `index` of the setjmp `table`.
+fn entry_address(table: u32, index: u32) -> Result<u32, Error> {
+    index
+        .checked_mul(SETJMP_ENTRY_SIZE)
+        .and_then(|offset| table.checked_add(offset))
+        .ok_or_else(|| Error::from(TrapCode::MemoryOutOfBounds))
+}
+
+/// Loads the `i32` at `address` of the exported `memory`.
+fn load_i32<T>(caller: &Caller<'_, T>, address: u32) -> Result<i32, Error> {
+    let mut bytes = [0x00_u8; 4];
+    exported_memory(caller)?
+        .read(caller, address as usize, &mut bytes)
+        .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+    Ok(i32::from_le_bytes(bytes))
+}
+
+/// Stores `value` at `address` of the exported `memory`.
+fn store_i32<T>(caller: &mut Caller<'_, T>, address: u32, value: i32) -> Result<(), Error> {
+    exported_memory(caller)?
+        .write(caller, address as usize, &value.to_le_bytes())
+        .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+    Ok(())
+}
+
+/// Returns the exported `memory` of the calling instance.
+fn exported_memory<T>(caller: &Caller<'_, T>) -> Result<Memory, Error> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(Error::new(
+            "missing `memory` export for `saveSetjmp` and `testSetjmp`",
+        )),
+    }
+}
+
+/// Returns the current stack pointer of the guest.
+fn stack_save<T>(caller: &mut Caller<'_, T>) -> Result<i32, Error> {
+    let func = find_export(caller, STACK_SAVE)?;
+    let mut results = [Val::I32(0)];
+    func.call(&mut *caller, &[], &mut results)?;
+    match results {
+        [Val::I32(stack_pointer)] => Ok(stack_pointer),
+        _ => Err(Error::new("`stackSave` must return an `i32` stack pointer")),
+    }
+}
+
+/// Restores the stack pointer of the guest to `stack_pointer`.
+fn stack_restore<T>(caller: &mut Caller<'_, T>, stack_pointer: i32) -> Result<(), Error> {
+    let func = find_export(caller, STACK_RESTORE)?;
+    func.call(&mut *caller, &[Val::I32(stack_pointer)], &mut [])
+}
+
+/// Returns the first exported function of the calling instance found in `names`.
+fn find_export<T>(caller: &Caller<'_, T>, names: &[&str]) -> Result<Func, Error> {
+    names
+        .iter()
+        .find_map(|name| caller.get_export(name)?.into_func())
+        .ok_or_else(|| {
+            Error::new(format!(
+                "missing `{}` export for `invoke_*` functions",
+                names[0]
+            ))
+        })
+}
+
+/// Calls the exported function `name` of the calling instance with `params`.
+fn call_export<T>(caller: &mut Caller<'_, T>, name: &str, params: &[Val]) -> Result<(), Error> {
+    call_export_with_results(caller, name, params, &mut [])
+}
+
+/// Calls the exported function `name` of the calling instance with `params` and writes its `results`.
+fn call_export_with_results<T>(
+    caller: &mut Caller<'_, T>,
+    name: &str,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let func = find_export(caller, &[name])?;
+    func.call(caller.as_context_mut(), params, results)
+}
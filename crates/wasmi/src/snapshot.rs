@@ -0,0 +1,724 @@
+use crate::{
+    core::{UntypedVal, ValType},
+    error::EntityGrowError,
+    memory::MemoryError,
+    module::PassiveDataSegmentBytes,
+    store::StoreInner,
+    Error,
+    ExternRef,
+    FuncRef,
+    Store,
+};
+use alloc::{boxed::Box, format, vec::Vec};
+
+/// A snapshot of the state of all linear memories, tables, global variables and segments of a [`Store`].
+///
+/// Taken via [`Store::snapshot`] and restored via [`Store::restore`].
+///
+/// Two snapshots of the same [`Store`] can be compared via [`StoreSnapshot::diff`] which
+/// yields a [`StoreDelta`] of their differences. A [`StoreDelta`] is compact since it only
+/// contains the changed pages, table elements and global variables and can be applied
+/// to a replica of the [`Store`] via [`Store::apply_delta`] in order to replicate guest
+/// state with minimal bandwidth.
+///
+/// # Note
+///
+/// - Entities are identified by their position within the [`Store`]. Therefore a replica
+///   must have allocated the same entities in the same order, e.g. by instantiating the
+///   same modules in the same order.
+/// - References are captured as the position of their function or extern object within
+///   the [`Store`]. The host state and the contents of extern objects are not captured.
+/// - The values of computed global variables are not captured since they are provided by the host.
+/// - Data segments are captured as shared handles to their bytes and element segments as
+///   their portable items so that [`Store::restore`] can undrop them. Segments are not part
+///   of a [`StoreDelta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreSnapshot {
+    /// The state of all linear memories in allocation order.
+    memories: Box<[MemorySnapshot]>,
+    /// The portable values of all global variables in allocation order.
+    globals: Box<[u64]>,
+    /// The portable elements of all tables in allocation order.
+    tables: Box<[Vec<u64>]>,
+    /// The bytes of all passive data segments in allocation order.
+    ///
+    /// This is `None` for active and dropped data segments.
+    datas: Box<[Option<PassiveDataSegmentBytes>]>,
+    /// The portable items of all element segments in allocation order.
+    elems: Box<[Box<[u64]>]>,
+}
+
+/// The state of a linear memory captured by a [`StoreSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MemorySnapshot {
+    /// The page size of the linear memory in bytes.
+    page_size: u32,
+    /// The contents of the linear memory.
+    data: Vec<u8>,
+}
+
+/// The differences between two [`StoreSnapshot`]s.
+///
+/// Computed via [`StoreSnapshot::diff`] and applied via [`StoreSnapshot::apply_delta`]
+/// or [`Store::apply_delta`]. Use [`StoreDelta::to_bytes`] and [`StoreDelta::from_bytes`]
+/// to transfer a [`StoreDelta`] to replicas.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StoreDelta {
+    /// The changed linear memories.
+    memories: Vec<MemoryDelta>,
+    /// The changed global variables as pairs of position and portable value.
+    globals: Vec<(u32, u64)>,
+    /// The changed tables.
+    tables: Vec<TableDelta>,
+}
+
+/// The changes of a linear memory within a [`StoreDelta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MemoryDelta {
+    /// The position of the linear memory within its store.
+    index: u32,
+    /// The page size of the linear memory in bytes.
+    page_size: u32,
+    /// The new size of the linear memory in pages.
+    pages: u32,
+    /// The changed pages as pairs of page index and contents.
+    changed: Vec<(u32, Box<[u8]>)>,
+}
+
+/// The changes of a table within a [`StoreDelta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TableDelta {
+    /// The position of the table within its store.
+    index: u32,
+    /// The new size of the table in elements.
+    size: u32,
+    /// The changed elements as pairs of element index and portable value.
+    edits: Vec<(u32, u64)>,
+}
+
+impl StoreSnapshot {
+    /// Captures a [`StoreSnapshot`] of `store`.
+    fn capture(store: &StoreInner) -> Self {
+        let memories = store
+            .memory_entities()
+            .map(|memory| MemorySnapshot {
+                page_size: memory.ty().page_size(),
+                data: memory.data().into(),
+            })
+            .collect();
+        let globals = store
+            .global_entities()
+            .map(|global| match global.is_computed() {
+                true => 0,
+                false => portable(store, global.ty().content(), global.get_untyped()),
+            })
+            .collect();
+        let tables = store
+            .table_entities()
+            .map(|table| {
+                let element = table.ty().element();
+                (0..table.size())
+                    .filter_map(|index| table.get_untyped(index))
+                    .map(|value| portable(store, element, value))
+                    .collect()
+            })
+            .collect();
+        let datas = store
+            .data_segment_entities()
+            .map(|segment| segment.passive_bytes().cloned())
+            .collect();
+        let elems = store
+            .element_segment_entities()
+            .map(|segment| {
+                segment
+                    .items()
+                    .iter()
+                    .map(|&value| portable(store, segment.ty(), value))
+                    .collect()
+            })
+            .collect();
+        Self {
+            memories,
+            globals,
+            tables,
+            datas,
+            elems,
+        }
+    }
+
+    /// Returns the [`StoreDelta`] that turns the `older` [`StoreSnapshot`] into `self`.
+    ///
+    /// # Errors
+    ///
+    /// If `older` does not have the same number of linear memories, tables and global variables
+    /// or if the page sizes of its linear memories differ. This happens if `older` has been
+    /// taken from another [`Store`] or before new entities have been allocated.
+    pub fn diff(&self, older: &StoreSnapshot) -> Result<StoreDelta, Error> {
+        ensure_len("linear memories", older.memories.len(), self.memories.len())?;
+        ensure_len("tables", older.tables.len(), self.tables.len())?;
+        ensure_len("global variables", older.globals.len(), self.globals.len())?;
+        let mut delta = StoreDelta::default();
+        for (index, (newer, older)) in self.memories.iter().zip(&older.memories).enumerate() {
+            if newer.page_size != older.page_size {
+                return Err(Error::new(format!(
+                    "page size of linear memory {index} differs between snapshots"
+                )));
+            }
+            if newer == older {
+                continue;
+            }
+            let page_size = newer.page_size as usize;
+            let changed = newer
+                .data
+                .chunks(page_size)
+                .enumerate()
+                .filter(|(page, contents)| {
+                    let start = page * page_size;
+                    older.data.get(start..start + contents.len()) != Some(contents)
+                })
+                .map(|(page, contents)| (page as u32, Box::from(contents)))
+                .collect();
+            delta.memories.push(MemoryDelta {
+                index: index as u32,
+                page_size: newer.page_size,
+                pages: (newer.data.len() / page_size) as u32,
+                changed,
+            });
+        }
+        delta.globals = self
+            .globals
+            .iter()
+            .zip(&older.globals)
+            .enumerate()
+            .filter(|(_, (newer, older))| newer != older)
+            .map(|(index, (newer, _))| (index as u32, *newer))
+            .collect();
+        for (index, (newer, older)) in self.tables.iter().zip(&older.tables).enumerate() {
+            if newer == older {
+                continue;
+            }
+            let edits = newer
+                .iter()
+                .enumerate()
+                .filter(|(element, value)| older.get(*element) != Some(value))
+                .map(|(element, value)| (element as u32, *value))
+                .collect();
+            delta.tables.push(TableDelta {
+                index: index as u32,
+                size: newer.len() as u32,
+                edits,
+            });
+        }
+        Ok(delta)
+    }
+
+    /// Applies `delta` to the [`StoreSnapshot`].
+    ///
+    /// Applying the result of `newer.diff(&older)` to `older` turns it into `newer`.
+    ///
+    /// # Errors
+    ///
+    /// If `delta` refers to linear memories, tables or global variables
+    /// that do not exist or whose page sizes differ.
+    pub fn apply_delta(&mut self, delta: &StoreDelta) -> Result<(), Error> {
+        for memory in &delta.memories {
+            let Some(snapshot) = self.memories.get_mut(memory.index as usize) else {
+                return Err(missing_entity("linear memory", memory.index));
+            };
+            ensure_page_size(memory, snapshot.page_size)?;
+            let page_size = memory.page_size as usize;
+            snapshot.data.resize(memory.pages as usize * page_size, 0);
+            for (page, contents) in &memory.changed {
+                let start = *page as usize * page_size;
+                let Some(dst) = snapshot.data.get_mut(start..start + contents.len()) else {
+                    return Err(Error::from(MemoryError::OutOfBoundsAccess));
+                };
+                dst.copy_from_slice(contents);
+            }
+        }
+        for table in &delta.tables {
+            let Some(elements) = self.tables.get_mut(table.index as usize) else {
+                return Err(missing_entity("table", table.index));
+            };
+            elements.resize(table.size as usize, 0);
+            for &(element, value) in &table.edits {
+                let Some(dst) = elements.get_mut(element as usize) else {
+                    return Err(Error::new(format!(
+                        "out of bounds element {element} of table {}",
+                        table.index
+                    )));
+                };
+                *dst = value;
+            }
+        }
+        for &(index, value) in &delta.globals {
+            let Some(dst) = self.globals.get_mut(index as usize) else {
+                return Err(missing_entity("global variable", index));
+            };
+            *dst = value;
+        }
+        Ok(())
+    }
+}
+
+impl StoreDelta {
+    /// Returns `true` if the [`StoreDelta`] contains no changes.
+    pub fn is_empty(&self) -> bool {
+        self.memories.is_empty() && self.globals.is_empty() && self.tables.is_empty()
+    }
+
+    /// Returns the indices of the changed pages of the linear memory at `index` in ascending order.
+    ///
+    /// The `index` is the position of the linear memory within its [`Store`].
+    pub fn changed_pages(&self, index: u32) -> impl Iterator<Item = u32> + '_ {
+        self.memories
+            .iter()
+            .filter(move |memory| memory.index == index)
+            .flat_map(|memory| memory.changed.iter().map(|(page, _)| *page))
+    }
+
+    /// Encodes the [`StoreDelta`] into a compact binary representation.
+    ///
+    /// Decode the result via [`StoreDelta::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(Self::MAGIC);
+        put_u32(&mut bytes, self.memories.len() as u32);
+        for memory in &self.memories {
+            put_u32(&mut bytes, memory.index);
+            put_u32(&mut bytes, memory.page_size);
+            put_u32(&mut bytes, memory.pages);
+            put_u32(&mut bytes, memory.changed.len() as u32);
+            for (page, contents) in &memory.changed {
+                put_u32(&mut bytes, *page);
+                bytes.extend_from_slice(contents);
+            }
+        }
+        put_u32(&mut bytes, self.globals.len() as u32);
+        for &(index, value) in &self.globals {
+            put_u32(&mut bytes, index);
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        put_u32(&mut bytes, self.tables.len() as u32);
+        for table in &self.tables {
+            put_u32(&mut bytes, table.index);
+            put_u32(&mut bytes, table.size);
+            put_u32(&mut bytes, table.edits.len() as u32);
+            for &(element, value) in &table.edits {
+                put_u32(&mut bytes, element);
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decodes a [`StoreDelta`] encoded by [`StoreDelta::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// If `bytes` is not a valid encoding of a [`StoreDelta`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader { bytes };
+        if reader.take(Self::MAGIC.len())? != Self::MAGIC {
+            return Err(Reader::malformed());
+        }
+        let mut delta = StoreDelta::default();
+        for _ in 0..reader.u32()? {
+            let index = reader.u32()?;
+            let page_size = reader.u32()?;
+            let pages = reader.u32()?;
+            let changed = (0..reader.u32()?)
+                .map(|_| Ok((reader.u32()?, Box::from(reader.take(page_size as usize)?))))
+                .collect::<Result<_, Error>>()?;
+            delta.memories.push(MemoryDelta {
+                index,
+                page_size,
+                pages,
+                changed,
+            });
+        }
+        delta.globals = (0..reader.u32()?)
+            .map(|_| Ok((reader.u32()?, reader.u64()?)))
+            .collect::<Result<_, Error>>()?;
+        for _ in 0..reader.u32()? {
+            let index = reader.u32()?;
+            let size = reader.u32()?;
+            let edits = (0..reader.u32()?)
+                .map(|_| Ok((reader.u32()?, reader.u64()?)))
+                .collect::<Result<_, Error>>()?;
+            delta.tables.push(TableDelta { index, size, edits });
+        }
+        if !reader.bytes.is_empty() {
+            return Err(Reader::malformed());
+        }
+        Ok(delta)
+    }
+
+    /// The magic bytes that start the binary representation of a [`StoreDelta`].
+    const MAGIC: &'static [u8] = b"\0wsd\x01";
+}
+
+impl<T> Store<T> {
+    /// Takes a [`StoreSnapshot`] of the linear memories, tables, global variables and segments of the [`Store`].
+    pub fn snapshot(&self) -> StoreSnapshot {
+        StoreSnapshot::capture(&self.inner)
+    }
+
+    /// Restores the linear memories, tables, global variables and segments of the [`Store`] to `snapshot`.
+    ///
+    /// Unlike [`Store::apply_delta`] this also shrinks linear memories and tables that grew
+    /// after the `snapshot` was taken. Since all entities of the [`Store`] are kept in place,
+    /// handles such as [`Instance`], [`Func`] and [`TypedFunc`] remain valid and do not have
+    /// to be looked up again after restoring a sandbox to a clean state.
+    ///
+    /// # Note
+    ///
+    /// - This bypasses the limits of linear memories and tables and the [`ResourceLimiter`]
+    ///   since the `snapshot` is expected to be a previous state of the [`Store`].
+    /// - Computed global variables are not restored since their values are provided by the host.
+    ///
+    /// # Errors
+    ///
+    /// - If `snapshot` was taken from another [`Store`] or before new linear memories,
+    ///   tables, global variables or segments have been allocated.
+    /// - If the linear memories cannot be resized to their size in `snapshot`.
+    ///
+    /// In these cases the [`Store`] is unchanged.
+    ///
+    /// [`Instance`]: crate::Instance
+    /// [`Func`]: crate::Func
+    /// [`TypedFunc`]: crate::TypedFunc
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
+    pub fn restore(&mut self, snapshot: &StoreSnapshot) -> Result<(), Error> {
+        let inner = &mut self.inner;
+        ensure_len(
+            "linear memories",
+            snapshot.memories.len(),
+            inner.memory_entities().count(),
+        )?;
+        ensure_len(
+            "tables",
+            snapshot.tables.len(),
+            inner.table_entities().count(),
+        )?;
+        ensure_len(
+            "global variables",
+            snapshot.globals.len(),
+            inner.global_entities().count(),
+        )?;
+        ensure_len(
+            "data segments",
+            snapshot.datas.len(),
+            inner.data_segment_entities().count(),
+        )?;
+        ensure_len(
+            "element segments",
+            snapshot.elems.len(),
+            inner.element_segment_entities().count(),
+        )?;
+        for (memory, entity) in snapshot.memories.iter().zip(inner.memory_entities()) {
+            if memory.page_size != entity.ty().page_size() {
+                return Err(Error::new(format!(
+                    "page size of linear memory differs: {} != {}",
+                    memory.page_size,
+                    entity.ty().page_size()
+                )));
+            }
+        }
+        let tables = snapshot
+            .tables
+            .iter()
+            .zip(inner.table_entities())
+            .map(|(elements, entity)| {
+                let element = entity.ty().element();
+                elements
+                    .iter()
+                    .map(|&value| resolve(inner, element, value))
+                    .collect::<Result<Vec<_>, Error>>()
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let globals = snapshot
+            .globals
+            .iter()
+            .zip(inner.global_entities())
+            .map(|(&value, entity)| match entity.is_computed() {
+                true => Ok(None),
+                false => resolve(inner, entity.ty().content(), value).map(Some),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let elems = snapshot
+            .elems
+            .iter()
+            .zip(inner.element_segment_entities())
+            .map(|(items, entity)| {
+                items
+                    .iter()
+                    .map(|&value| resolve(inner, entity.ty(), value))
+                    .collect::<Result<Box<[_]>, Error>>()
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        for (index, memory) in snapshot.memories.iter().enumerate() {
+            if let Some(entity) = inner.memory_entity_mut(index as u32) {
+                entity.reserve_restore(memory.data.len())?;
+            }
+        }
+        // Note: from here on restoring cannot fail so that the store is never restored partially.
+        for (index, memory) in snapshot.memories.iter().enumerate() {
+            if let Some(entity) = inner.memory_entity_mut(index as u32) {
+                entity.restore(&memory.data);
+            }
+        }
+        for (index, elements) in tables.into_iter().enumerate() {
+            if let Some(entity) = inner.table_entity_mut(index as u32) {
+                entity.restore(elements);
+            }
+        }
+        for (index, value) in globals.into_iter().enumerate() {
+            if let (Some(global), Some(value)) = (inner.global_entity_mut(index as u32), value) {
+                global.set_untyped(value);
+            }
+        }
+        for (entity, bytes) in inner.data_segment_entities_mut().zip(&snapshot.datas[..]) {
+            entity.set_bytes(bytes.clone());
+        }
+        for (entity, items) in inner.element_segment_entities_mut().zip(elems) {
+            entity.set_items(items);
+        }
+        Ok(())
+    }
+
+    /// Takes a [`StoreSnapshot`] of the [`Store`] that is restored by [`Store::reset`].
+    ///
+    /// This is usually done right after instantiating the Wasm modules of a sandbox.
+    pub fn set_reset_point(&mut self) {
+        let snapshot = self.snapshot();
+        *self.inner.reset_point_mut() = Some(Box::new(snapshot));
+    }
+
+    /// Restores the [`Store`] to the [`StoreSnapshot`] taken by [`Store::set_reset_point`].
+    ///
+    /// Read more in [`Store::restore`].
+    ///
+    /// # Errors
+    ///
+    /// - If no reset point has been set.
+    /// - If new linear memories, tables, global variables or segments have been allocated since.
+    pub fn reset(&mut self) -> Result<(), Error> {
+        let Some(reset_point) = self.inner.reset_point_mut().take() else {
+            return Err(Error::new("the store has no reset point"));
+        };
+        let result = self.restore(&reset_point);
+        *self.inner.reset_point_mut() = Some(reset_point);
+        result
+    }
+
+    /// Applies `delta` to the linear memories, tables and global variables of the [`Store`].
+    ///
+    /// This replicates the changes between two [`StoreSnapshot`]s of another [`Store`]
+    /// whose entities have been allocated in the same order.
+    /// Read more in the docs of [`StoreSnapshot`].
+    ///
+    /// # Note
+    ///
+    /// Global variables are updated regardless of their mutability
+    /// except for computed global variables which are never updated.
+    ///
+    /// # Errors
+    ///
+    /// - If `delta` refers to linear memories, tables, global variables, functions
+    ///   or extern objects that do not exist in the [`Store`].
+    /// - If `delta` shrinks a linear memory or table or its page sizes differ.
+    /// - If growing a linear memory or table fails.
+    ///   In this case the `delta` might have been applied partially.
+    pub fn apply_delta(&mut self, delta: &StoreDelta) -> Result<(), Error> {
+        let (inner, mut limiter) = self.store_inner_and_resource_limiter_ref();
+        let globals = delta
+            .globals
+            .iter()
+            .map(|&(index, value)| {
+                let Some(global) = inner.global_entities().nth(index as usize) else {
+                    return Err(missing_entity("global variable", index));
+                };
+                Ok((index, resolve(inner, global.ty().content(), value)?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let tables = delta
+            .tables
+            .iter()
+            .map(|table| {
+                let Some(entity) = inner.table_entities().nth(table.index as usize) else {
+                    return Err(missing_entity("table", table.index));
+                };
+                if table.size < entity.size() {
+                    return Err(Error::new(format!("cannot shrink table {}", table.index)));
+                }
+                let element = entity.ty().element();
+                let edits = table
+                    .edits
+                    .iter()
+                    .map(|&(index, value)| Ok((index, resolve(inner, element, value)?)))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                Ok((table, edits))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        for memory in &delta.memories {
+            let Some(entity) = inner.memory_entities().nth(memory.index as usize) else {
+                return Err(missing_entity("linear memory", memory.index));
+            };
+            ensure_page_size(memory, entity.ty().page_size())?;
+            if memory.pages < entity.size() {
+                return Err(Error::new(format!(
+                    "cannot shrink linear memory {}",
+                    memory.index
+                )));
+            }
+        }
+        for memory in &delta.memories {
+            let Some(entity) = inner.memory_entity_mut(memory.index) else {
+                return Err(missing_entity("linear memory", memory.index));
+            };
+            entity
+                .grow(memory.pages - entity.size(), None, &mut limiter)
+                .map_err(grow_error)?;
+            for (page, contents) in &memory.changed {
+                entity.write(*page as usize * memory.page_size as usize, contents)?;
+            }
+        }
+        for (table, edits) in tables {
+            let Some(entity) = inner.table_entity_mut(table.index) else {
+                return Err(missing_entity("table", table.index));
+            };
+            let null = match entity.ty().element() {
+                ValType::FuncRef => UntypedVal::from(FuncRef::null()),
+                _ => UntypedVal::from(ExternRef::null()),
+            };
+            entity
+                .grow_untyped(table.size - entity.size(), null, None, &mut limiter)
+                .map_err(grow_error)?;
+            for (index, value) in edits {
+                entity.set_untyped(index, value)?;
+            }
+        }
+        for (index, value) in globals {
+            if let Some(global) = inner.global_entity_mut(index) {
+                if !global.is_computed() {
+                    global.set_untyped(value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the portable representation of `value` of type `ty` within `store`.
+///
+/// References are represented by the position of their entity plus one and `null` by zero.
+fn portable(store: &StoreInner, ty: ValType, value: UntypedVal) -> u64 {
+    match ty {
+        ValType::FuncRef => FuncRef::from(value)
+            .func()
+            .map_or(0, |func| u64::from(store.entity_id(func.as_inner())) + 1),
+        ValType::ExternRef => store
+            .extern_object_index(&ExternRef::from(value))
+            .map_or(0, |index| u64::from(index) + 1),
+        _ => u64::from(value),
+    }
+}
+
+/// Resolves the portable `value` of type `ty` to its [`UntypedVal`] within `store`.
+///
+/// # Errors
+///
+/// If `value` refers to a function or extern object that does not exist in `store`.
+fn resolve(store: &StoreInner, ty: ValType, value: u64) -> Result<UntypedVal, Error> {
+    let position = value.checked_sub(1).map(|position| position as u32);
+    let resolved = match ty {
+        ValType::FuncRef => match position {
+            None => Some(UntypedVal::from(FuncRef::null())),
+            Some(position) => store
+                .func_at(position)
+                .map(|func| UntypedVal::from(FuncRef::new(func))),
+        },
+        ValType::ExternRef => match position {
+            None => Some(UntypedVal::from(ExternRef::null())),
+            Some(position) => store.extern_object_at(position).map(UntypedVal::from),
+        },
+        _ => Some(UntypedVal::from(value)),
+    };
+    resolved.ok_or_else(|| Error::new(format!("missing referenced {ty:?} entity: {value}")))
+}
+
+/// Returns an error if `expected` does not match `found` entities of `kind`.
+fn ensure_len(kind: &str, expected: usize, found: usize) -> Result<(), Error> {
+    if expected != found {
+        return Err(Error::new(format!(
+            "snapshots have different numbers of {kind}: {expected} != {found}"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns an error if the page size of `memory` does not match `page_size`.
+fn ensure_page_size(memory: &MemoryDelta, page_size: u32) -> Result<(), Error> {
+    if memory.page_size != page_size {
+        return Err(Error::new(format!(
+            "page size of linear memory {} differs: {} != {page_size}",
+            memory.index, memory.page_size
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the error for a missing entity of `kind` at `index`.
+fn missing_entity(kind: &str, index: u32) -> Error {
+    Error::new(format!("missing {kind} at position {index}"))
+}
+
+/// Converts an [`EntityGrowError`] into an [`Error`].
+fn grow_error(error: EntityGrowError) -> Error {
+    match error {
+        EntityGrowError::InvalidGrow => Error::new("failed to grow entity for snapshot delta"),
+        EntityGrowError::TrapCode(trap_code) => Error::from(trap_code),
+    }
+}
+
+/// Appends `value` to `bytes` in little-endian byte order.
+fn put_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Decodes the binary representation of a [`StoreDelta`].
+struct Reader<'a> {
+    /// The bytes that have not yet been decoded.
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Returns the error for malformed binary representations.
+    fn malformed() -> Error {
+        Error::new("malformed store delta")
+    }
+
+    /// Takes the next `len` bytes.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < len {
+            return Err(Self::malformed());
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    /// Takes the next little-endian `u32`.
+    fn u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Takes the next little-endian `u64`.
+    fn u64(&mut self) -> Result<u64, Error> {
+        let mut value = [0; 8];
+        value.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(value))
+    }
+}
@@ -1,11 +1,17 @@
+#[cfg(feature = "fuel-profile")]
+use crate::fuel_profile::{FuelProfile, FuelProfileCounters};
+#[cfg(feature = "trace")]
+use crate::trace::{Trace, Tracer};
 use crate::{
     collections::arena::{Arena, ArenaIndex, GuardedEntity},
-    core::TrapCode,
-    engine::{DedupFuncType, FuelCosts},
+    core::{TrapCode, UntypedVal, ValType},
+    engine::{CachedCall, DedupFuncType, FuelCosts, FuelUsage, Stack},
     externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
-    func::{Trampoline, TrampolineEntity, TrampolineIdx},
-    memory::{DataSegment, MemoryError},
+    func::{FuncError, Trampoline, TrampolineEntity, TrampolineIdx},
+    loop_counters::LoopCounters,
+    memory::{DataSegment, MemoryError, MemoryRegionWrite, MemoryTags},
     module::InstantiationError,
+    replay::{HostCallLog, HostCallRecording},
     table::TableError,
     Config,
     DataSegmentEntity,
@@ -15,6 +21,7 @@ use crate::{
     ElementSegmentIdx,
     Engine,
     Error,
+    ExternRef,
     Func,
     FuncEntity,
     FuncIdx,
@@ -29,15 +36,31 @@ use crate::{
     MemoryEntity,
     MemoryIdx,
     ResourceLimiter,
+    StoreSnapshot,
     Table,
     TableEntity,
     TableIdx,
+    TrapHandler,
 };
-use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use crate::{interrupt::InterruptState, InterruptHandle, SignalHandle};
+#[cfg(feature = "time-travel")]
+use crate::{
+    time_travel::{Checkpoint, TimeTravel, TraceStep},
+    Val,
+};
+#[cfg(feature = "time-travel")]
+use alloc::format;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
     fmt::{self, Debug},
+    mem,
+    num::NonZeroU32,
+    ops::Range,
     sync::atomic::{AtomicU32, Ordering},
 };
+#[cfg(feature = "std")]
+use {alloc::collections::BTreeMap, std::backtrace::Backtrace};
 
 /// A unique store index.
 ///
@@ -116,6 +139,36 @@ impl<T> Debug for CallHookWrapper<T> {
     }
 }
 
+/// A wrapper used to store hooks added with [`Store::memory_watermark_hook`], containing a
+/// boxed `FnMut(&mut T, MemoryWatermark) -> Result<WatermarkAction, Error>`.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works for [`Store`].
+#[allow(clippy::type_complexity)]
+struct WatermarkHookWrapper<T>(
+    Box<dyn FnMut(&mut T, MemoryWatermark) -> Result<WatermarkAction, Error> + Send + Sync>,
+);
+impl<T> Debug for WatermarkHookWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WatermarkHook(...)")
+    }
+}
+
+/// A wrapper used to store hooks added with [`Store::memory_write_hook`], containing a
+/// boxed `FnMut(&mut T, MemoryRegionWrite) -> Result<(), Error>`.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works for [`Store`].
+#[allow(clippy::type_complexity)]
+struct WriteHookWrapper<T>(
+    Box<dyn FnMut(&mut T, MemoryRegionWrite) -> Result<(), Error> + Send + Sync>,
+);
+impl<T> Debug for WriteHookWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WriteHook(...)")
+    }
+}
+
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
 pub struct Store<T> {
@@ -136,6 +189,10 @@ pub struct Store<T> {
     /// or a WebAssembly function calls a host function, or these functions
     /// return.
     call_hook: Option<CallHookWrapper<T>>,
+    /// User provided callback called when a linear memory grows beyond its watermark.
+    watermark_hook: Option<WatermarkHookWrapper<T>>,
+    /// User provided callback called when a tagged region of a linear memory was written.
+    write_hook: Option<WriteHookWrapper<T>>,
 }
 
 /// The inner store that owns all data not associated to the host state.
@@ -169,6 +226,96 @@ pub struct StoreInner {
     engine: Engine,
     /// The fuel of the [`Store`].
     fuel: Fuel,
+    /// The remaining fuel at the last accounting if the [`Store`] has a proportional share of fuel.
+    ///
+    /// Read more in [`Store::set_fuel_share`].
+    fuel_share: Option<u64>,
+    /// The fuel consumed by each executed Wasm function.
+    #[cfg(feature = "fuel-profile")]
+    fuel_profile: FuelProfileCounters,
+    /// The iteration counts of the Wasm `loop`s of each executed Wasm function.
+    loop_counters: LoopCounters,
+    /// The log of host function calls if recording or replaying.
+    host_calls: Option<HostCallLog>,
+    /// The checkpoints of Wasm executions if enabled.
+    #[cfg(feature = "time-travel")]
+    time_travel: Option<TimeTravel>,
+    /// The tracer of Wasm and host function calls if enabled.
+    #[cfg(feature = "trace")]
+    tracer: Option<Tracer>,
+    /// The interruption state of interruptible host calls.
+    #[cfg(feature = "std")]
+    interrupt: InterruptState,
+    /// The policy of the [`Store`].
+    policy: StorePolicy,
+    /// The calling instances of the currently active host function calls.
+    ///
+    /// Only tracked if the [`StorePolicy`] denies reentrant guest calls.
+    host_callers: Vec<Instance>,
+    /// The trap scheduled via [`Store::inject_trap`] if any.
+    injected_trap: Option<InjectedTrap>,
+    /// The [`TrapHandler`] set via [`Store::set_trap_handler`] if any.
+    trap_handler: Option<TrapHandler>,
+    /// The regions of linear memories tagged via [`Store::tag_memory_region`].
+    memory_tags: MemoryTags,
+    /// The creation backtraces of extern objects if tracked.
+    #[cfg(feature = "std")]
+    extern_ref_origins: ExternRefOrigins,
+    /// The [`Stack`] and root function of the last call from the host for reuse by the next one.
+    ///
+    /// This saves frequent host to Wasm calls from acquiring a [`Stack`] of the [`Engine`]
+    /// and from resolving the called function and its instance.
+    cached_stack: CachedStack,
+    /// The snapshot restored by [`Store::reset`] if any.
+    reset_point: Option<Box<StoreSnapshot>>,
+}
+
+/// The [`Stack`] and root function cached by a [`Store`] for reuse by its next call from the host.
+///
+/// # Note
+///
+/// [`Stack`] is not [`Sync`] but the [`CachedStack`] only ever accesses it via exclusive
+/// references. Therefore it does not require locking in order to be [`Sync`].
+#[derive(Default)]
+struct CachedStack {
+    /// The [`Stack`] of the last call from the host if any.
+    stack: Option<Stack>,
+    /// The root Wasm function of the last call from the host if any.
+    call: Option<CachedCall>,
+}
+
+// # Safety
+//
+// `CachedStack` does not provide any access to its `Stack` via shared references.
+unsafe impl Sync for CachedStack {}
+
+impl Debug for CachedStack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Note: the `Stack` must not be accessed via shared references.
+        f.debug_struct("CachedStack")
+            .field("has_stack", &self.stack.is_some())
+            .field("call", &self.call)
+            .finish()
+    }
+}
+
+/// A trap scheduled via [`Store::inject_trap_after_calls`].
+#[derive(Debug, Copy, Clone)]
+struct InjectedTrap {
+    /// The [`TrapCode`] of the injected trap.
+    code: TrapCode,
+    /// The number of function calls that still pass before the trap triggers.
+    remaining_calls: u64,
+}
+
+/// The creation backtraces of the extern objects of a [`Store`].
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct ExternRefOrigins {
+    /// Is `true` if the creation of new extern objects is tracked.
+    enabled: bool,
+    /// The creation backtraces of the tracked extern objects.
+    backtraces: BTreeMap<ExternObjectIdx, Backtrace>,
 }
 
 #[test]
@@ -183,6 +330,46 @@ fn test_store_is_send_sync() {
     };
 }
 
+#[test]
+fn test_store_caches_stacks_within_engine_limits() {
+    let wasm = "(module (func (export \"f\") (param i32) (result i32) (local.get 0)))";
+    for (cached_stacks, is_cached) in [(0, false), (2, true)] {
+        let mut config = Config::default();
+        config.set_cached_stacks(cached_stacks);
+        let engine = Engine::new(&config);
+        let module = crate::Module::new(&engine, wasm).unwrap();
+        let mut store = <Store<()>>::new(&engine, ());
+        let instance = crate::Linker::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let func = instance.get_typed_func::<i32, i32>(&store, "f").unwrap();
+        assert_eq!(func.call(&mut store, 42).unwrap(), 42);
+        assert_eq!(store.inner.take_cached_stack().is_some(), is_cached);
+    }
+}
+
+#[test]
+fn test_store_releases_cached_stack() {
+    let wasm = "(module (func (export \"f\") (param i32) (result i32) (local.get 0)))";
+    let engine = Engine::default();
+    let module = crate::Module::new(&engine, wasm).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let instance = crate::Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<i32, i32>(&store, "f").unwrap();
+    assert_eq!(func.call(&mut store, 42).unwrap(), 42);
+    assert!(store.inner.cached_call(func.func()).is_some());
+    store.release_cached_stack();
+    assert!(store.inner.take_cached_stack().is_none());
+    assert_eq!(func.call(&mut store, 7).unwrap(), 7);
+    assert!(store.inner.take_cached_stack().is_some());
+}
+
 /// Argument to the callback set by [`Store::call_hook`] to indicate why the
 /// callback was invoked.
 #[derive(Debug)]
@@ -197,6 +384,71 @@ pub enum CallHook {
     ReturningFromHost,
 }
 
+/// The policy of a [`Store`] set via [`Store::set_policy`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum StorePolicy {
+    /// Host functions may call back into the exports of their calling instance.
+    ///
+    /// This is the default.
+    #[default]
+    AllowReentrantGuestCalls,
+    /// Host functions calling back into the exports of their calling instance fail.
+    ///
+    /// Such calls return [`FuncError::ReentrantCall`] to the host function instead of
+    /// executing which prevents accidental unbounded recursion across the host boundary.
+    /// Calls into other instances of the same [`Store`] are still allowed.
+    ///
+    /// [`FuncError::ReentrantCall`]: crate::errors::FuncError::ReentrantCall
+    DenyReentrantGuestCalls,
+}
+
+/// Argument to the callback set by [`Store::memory_watermark_hook`] describing
+/// a `memory.grow` operation beyond the watermark of a [`Memory`].
+///
+/// Read more about watermarks in [`Memory::set_watermark`].
+#[derive(Debug, Copy, Clone)]
+pub struct MemoryWatermark {
+    /// The grown [`Memory`].
+    memory: Memory,
+    /// The watermark of the [`Memory`] in pages.
+    watermark: u32,
+    /// The size of the [`Memory`] in pages before the growth.
+    current: u32,
+    /// The size of the [`Memory`] in pages after the growth.
+    desired: u32,
+}
+
+impl MemoryWatermark {
+    /// Returns the grown [`Memory`].
+    pub fn memory(&self) -> Memory {
+        self.memory
+    }
+
+    /// Returns the watermark of the grown [`Memory`] in pages.
+    pub fn watermark(&self) -> u32 {
+        self.watermark
+    }
+
+    /// Returns the size of the grown [`Memory`] in pages before the growth.
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Returns the size of the grown [`Memory`] in pages after the growth.
+    pub fn desired(&self) -> u32 {
+        self.desired
+    }
+}
+
+/// The decision of the callback set by [`Store::memory_watermark_hook`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WatermarkAction {
+    /// Continues with the `memory.grow` operation.
+    Allow,
+    /// Denies the `memory.grow` operation which then returns `-1`.
+    Deny,
+}
+
 /// An error that may be encountered when operating on the [`Store`].
 #[derive(Debug, Clone)]
 pub enum FuelError {
@@ -204,6 +456,10 @@ pub enum FuelError {
     FuelMeteringDisabled,
     /// Raised when trying to consume more fuel than is available in the [`Store`].
     OutOfFuel,
+    /// Raised when calling into a [`Store`] that exceeds its proportional share of fuel.
+    ///
+    /// Read more in [`Engine::set_fuel_share_slack`].
+    OverQuota,
 }
 
 #[cfg(feature = "std")]
@@ -214,6 +470,7 @@ impl fmt::Display for FuelError {
         match self {
             Self::FuelMeteringDisabled => write!(f, "fuel metering is disabled"),
             Self::OutOfFuel => write!(f, "all fuel consumed"),
+            Self::OverQuota => write!(f, "store exceeded its share of fuel"),
         }
     }
 }
@@ -238,6 +495,16 @@ impl FuelError {
     pub fn out_of_fuel() -> Self {
         Self::OutOfFuel
     }
+
+    /// Returns an error indicating that the [`Store`] exceeds its share of fuel.
+    ///
+    /// # Note
+    ///
+    /// This method exists to indicate that this execution path is cold.
+    #[cold]
+    pub fn over_quota() -> Self {
+        Self::OverQuota
+    }
 }
 
 /// The remaining and consumed fuel counters.
@@ -245,6 +512,9 @@ impl FuelError {
 pub struct Fuel {
     /// The remaining fuel.
     remaining: u64,
+    /// The total fuel consumed since the creation of the [`Fuel`].
+    #[cfg(any(feature = "time-travel", feature = "clock"))]
+    consumed: u64,
     /// This is `true` if fuel metering is enabled for the [`Engine`].
     enabled: bool,
     /// The fuel costs provided by the [`Engine`]'s [`Config`].
@@ -257,9 +527,11 @@ impl Fuel {
     /// Creates a new [`Fuel`] for the [`Engine`].
     pub fn new(config: &Config) -> Self {
         let enabled = config.get_consume_fuel();
-        let costs = *config.fuel_costs();
+        let costs = *config.get_fuel_costs();
         Self {
             remaining: 0,
+            #[cfg(any(feature = "time-travel", feature = "clock"))]
+            consumed: 0,
             enabled,
             costs,
         }
@@ -325,9 +597,19 @@ impl Fuel {
             .remaining
             .checked_sub(delta)
             .ok_or(TrapCode::OutOfFuel)?;
+        #[cfg(any(feature = "time-travel", feature = "clock"))]
+        {
+            self.consumed = self.consumed.wrapping_add(delta);
+        }
         Ok(self.remaining)
     }
 
+    /// Returns the total fuel consumed since the creation of the [`Fuel`].
+    #[cfg(any(feature = "time-travel", feature = "clock"))]
+    pub(crate) fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
     /// Synthetically consumes an amount of [`Fuel`] for the [`Store`].
     ///
     /// Returns the remaining amount of [`Fuel`] after this operation.
@@ -360,7 +642,15 @@ impl Fuel {
     ) -> Result<(), TrapCode> {
         match self.consume_fuel(f) {
             Err(FuelError::OutOfFuel) => Err(TrapCode::OutOfFuel),
-            Err(FuelError::FuelMeteringDisabled) | Ok(_) => Ok(()),
+            Err(FuelError::FuelMeteringDisabled | FuelError::OverQuota) | Ok(_) => Ok(()),
+        }
+    }
+}
+
+impl Drop for StoreInner {
+    fn drop(&mut self) {
+        if self.fuel_share.is_some() {
+            self.engine.fuel_ledger().unregister(self.store_idx);
         }
     }
 }
@@ -381,6 +671,26 @@ impl StoreInner {
             elems: Arena::new(),
             extern_objects: Arena::new(),
             fuel,
+            fuel_share: None,
+            #[cfg(feature = "fuel-profile")]
+            fuel_profile: FuelProfileCounters::default(),
+            loop_counters: LoopCounters::default(),
+            host_calls: None,
+            #[cfg(feature = "time-travel")]
+            time_travel: None,
+            #[cfg(feature = "trace")]
+            tracer: None,
+            #[cfg(feature = "std")]
+            interrupt: InterruptState::default(),
+            policy: StorePolicy::default(),
+            host_callers: Vec::new(),
+            injected_trap: None,
+            trap_handler: None,
+            memory_tags: MemoryTags::default(),
+            #[cfg(feature = "std")]
+            extern_ref_origins: ExternRefOrigins::default(),
+            cached_stack: CachedStack::default(),
+            reset_point: None,
         }
     }
 
@@ -389,11 +699,313 @@ impl StoreInner {
         &self.engine
     }
 
+    /// Returns an exclusive reference to the reset point of the [`Store`].
+    pub(crate) fn reset_point_mut(&mut self) -> &mut Option<Box<StoreSnapshot>> {
+        &mut self.reset_point
+    }
+
+    /// Takes the cached [`Stack`] of the last call from the host if any.
+    pub(crate) fn take_cached_stack(&mut self) -> Option<Stack> {
+        self.cached_stack.stack.take()
+    }
+
+    /// Caches the `stack` of a call from the host for reuse by the next one.
+    ///
+    /// Returns the previously cached [`Stack`] if any.
+    pub(crate) fn cache_stack(&mut self, stack: Stack) -> Option<Stack> {
+        self.cached_stack.stack.replace(stack)
+    }
+
+    /// Returns the cached root Wasm function of the last call from the host if it is `func`.
+    pub(crate) fn cached_call(&self, func: &Func) -> Option<CachedCall> {
+        self.cached_stack
+            .call
+            .filter(|call| call.func.as_inner() == func.as_inner())
+    }
+
+    /// Caches the root Wasm function `call` of a call from the host for reuse by the next one.
+    pub(crate) fn cache_call(&mut self, call: CachedCall) {
+        self.cached_stack.call = Some(call);
+    }
+
     /// Returns an exclusive reference to the [`Fuel`] counters.
     pub fn fuel_mut(&mut self) -> &mut Fuel {
         &mut self.fuel
     }
 
+    /// Accounts the fuel consumed since the last accounting to the proportional share of the [`StoreInner`].
+    ///
+    /// Does nothing if the [`StoreInner`] has no proportional share of fuel.
+    pub fn sync_fuel_share(&mut self) {
+        let Some(last) = self.fuel_share else {
+            return;
+        };
+        let Ok(remaining) = self.fuel.get_fuel() else {
+            return;
+        };
+        // Refueling in between accountings is not accounted as consumption.
+        let consumed = last.saturating_sub(remaining);
+        if consumed != 0 {
+            self.engine.fuel_ledger().charge(self.store_idx, consumed);
+        }
+        self.fuel_share = Some(remaining);
+    }
+
+    /// Checks whether a call from the host into the [`StoreInner`] is within its share of fuel.
+    ///
+    /// # Errors
+    ///
+    /// If the [`StoreInner`] exceeds its proportional share of fuel.
+    #[inline]
+    pub fn check_fuel_share(&mut self) -> Result<(), FuelError> {
+        if self.fuel_share.is_none() {
+            return Ok(());
+        }
+        self.sync_fuel_share();
+        if self
+            .engine
+            .fuel_ledger()
+            .is_store_over_quota(self.store_idx)
+        {
+            return Err(FuelError::over_quota());
+        }
+        Ok(())
+    }
+
+    /// Checks the trap scheduled via [`Store::inject_trap`] at a function call.
+    ///
+    /// # Errors
+    ///
+    /// If the scheduled trap is due at this function call.
+    #[inline]
+    pub fn check_injected_trap(&mut self) -> Result<(), TrapCode> {
+        match &mut self.injected_trap {
+            None => Ok(()),
+            Some(injected) if injected.remaining_calls == 0 => {
+                let code = injected.code;
+                self.injected_trap = None;
+                Err(code)
+            }
+            Some(injected) => {
+                injected.remaining_calls -= 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the [`TrapHandler`] of the [`StoreInner`] if any.
+    pub fn trap_handler(&self) -> Option<&TrapHandler> {
+        self.trap_handler.as_ref()
+    }
+
+    /// Registers the start of a host function call from the `caller` instance.
+    ///
+    /// Returns `true` if the host function call has been registered and
+    /// must be unregistered via [`StoreInner::leave_host_call`].
+    pub fn enter_host_call(&mut self, caller: Option<&Instance>) -> bool {
+        match (self.policy, caller) {
+            (StorePolicy::DenyReentrantGuestCalls, Some(caller)) => {
+                self.host_callers.push(*caller);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Unregisters the most recent host function call registered via [`StoreInner::enter_host_call`].
+    pub fn leave_host_call(&mut self, registered: bool) {
+        if registered {
+            self.host_callers.pop();
+        }
+    }
+
+    /// Checks whether a call into a Wasm function of `instance` from the host is allowed.
+    ///
+    /// # Errors
+    ///
+    /// If the [`StorePolicy`] denies reentrant guest calls and `instance` is
+    /// the caller of a currently active host function call.
+    pub fn check_reentrancy(&self, instance: &Instance) -> Result<(), FuncError> {
+        if self.policy == StorePolicy::DenyReentrantGuestCalls
+            && self.host_callers.contains(instance)
+        {
+            return Err(FuncError::ReentrantCall);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the [`Func`] belongs to the [`StoreInner`].
+    #[cfg(feature = "std")]
+    pub fn owns_func(&self, func: &Func) -> bool {
+        func.as_inner().entity_index(self.store_idx).is_some()
+    }
+
+    /// Returns a shared reference to the [`InterruptState`] of interruptible host calls.
+    #[cfg(feature = "std")]
+    pub fn interrupt_state(&self) -> &InterruptState {
+        &self.interrupt
+    }
+
+    /// Returns a shared reference to the [`Fuel`] counters.
+    #[cfg(any(feature = "time-travel", feature = "clock"))]
+    pub fn fuel(&self) -> &Fuel {
+        &self.fuel
+    }
+
+    /// Returns an iterator over the untyped values of all global variables of the [`StoreInner`].
+    ///
+    /// The global variables are yielded in the order of their allocation.
+    #[cfg(feature = "time-travel")]
+    pub fn globals_untyped(&self) -> impl Iterator<Item = UntypedVal> + '_ {
+        self.globals.iter().map(|(_, global)| global.get_untyped())
+    }
+
+    /// Restores the `index`-th allocated linear memory of the [`StoreInner`] to `data`.
+    #[cfg(feature = "time-travel")]
+    pub fn restore_memory(&mut self, index: usize, data: &[u8]) -> Result<(), MemoryError> {
+        match self.memories.get_mut(MemoryIdx::from_usize(index)) {
+            Some(memory) => {
+                memory.reserve_restore(data.len())?;
+                memory.restore(data);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Restores the `index`-th allocated global variable of the [`StoreInner`] to `value`.
+    #[cfg(feature = "time-travel")]
+    pub fn restore_global(&mut self, index: usize, value: UntypedVal) {
+        if let Some(global) = self.globals.get_mut(GlobalIdx::from_usize(index)) {
+            global.set_untyped(value);
+        }
+    }
+
+    /// Returns the number of host calls recorded or replayed so far.
+    #[cfg(feature = "time-travel")]
+    pub fn host_call_position(&self) -> usize {
+        self.host_calls
+            .as_ref()
+            .map(HostCallLog::position)
+            .unwrap_or(0)
+    }
+
+    /// Starts replaying the host call recording of the [`StoreInner`] at `position`.
+    #[cfg(feature = "time-travel")]
+    pub fn replay_host_calls_from(&mut self, position: usize) {
+        let recording = self
+            .host_calls
+            .take()
+            .map(HostCallLog::into_recording)
+            .unwrap_or_default();
+        self.host_calls = Some(HostCallLog::Replaying {
+            recording,
+            next: position,
+        });
+    }
+
+    /// Returns an exclusive reference to the function call tracer if enabled.
+    #[cfg(feature = "trace")]
+    #[inline]
+    pub fn tracer_mut(&mut self) -> Option<&mut Tracer> {
+        self.tracer.as_mut()
+    }
+
+    /// Records the call of the host function `func` if tracing is enabled.
+    #[cfg(feature = "trace")]
+    pub fn trace_host_call(&mut self, func: &Func) {
+        if self.tracer.is_none() {
+            return;
+        }
+        let id = self.entity_id(func.as_inner());
+        if let Some(tracer) = &mut self.tracer {
+            tracer.host_call(id);
+        }
+    }
+
+    /// Returns an exclusive reference to the checkpointing state if enabled.
+    #[cfg(feature = "time-travel")]
+    pub fn time_travel_mut(&mut self) -> Option<&mut TimeTravel> {
+        self.time_travel.as_mut()
+    }
+
+    /// Returns `true` if the current Wasm execution shall be paused.
+    #[cfg(feature = "time-travel")]
+    #[inline]
+    pub fn is_pause_due(&self) -> bool {
+        match &self.time_travel {
+            Some(time_travel) => time_travel.is_pause_due(self.fuel.consumed()),
+            None => false,
+        }
+    }
+
+    /// Takes a [`Checkpoint`] of the paused Wasm execution with the given `stack`.
+    #[cfg(feature = "time-travel")]
+    pub fn take_checkpoint(&mut self, stack: &Stack) {
+        let consumed = self.fuel.consumed();
+        let Some(time_travel) = &mut self.time_travel else {
+            return;
+        };
+        time_travel.schedule_checkpoint(consumed);
+        let Some(func) = time_travel.root() else {
+            return;
+        };
+        let checkpoint = Checkpoint::capture(self, func, stack);
+        if let Some(time_travel) = &mut self.time_travel {
+            time_travel.push_checkpoint(checkpoint);
+        }
+    }
+
+    /// Takes the [`HostCallLog`] of the [`StoreInner`] for the duration of a host call.
+    pub fn take_host_call_log(&mut self) -> Option<HostCallLog> {
+        self.host_calls.take()
+    }
+
+    /// Restores the [`HostCallLog`] taken via [`StoreInner::take_host_call_log`].
+    pub fn restore_host_call_log(&mut self, log: HostCallLog) {
+        self.host_calls = Some(log);
+    }
+
+    /// Returns an iterator over the data of all linear memories of the [`StoreInner`].
+    ///
+    /// The linear memories are yielded in the order of their allocation.
+    pub fn memories_data(&self) -> impl Iterator<Item = &[u8]> {
+        self.memories.iter().map(|(_, memory)| memory.data())
+    }
+
+    /// Returns the data of the `index`-th allocated linear memory of the [`StoreInner`] if any.
+    pub fn memory_data_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+        if index >= self.memories.len() {
+            return None;
+        }
+        self.memories
+            .get_mut(MemoryIdx::from_usize(index))
+            .map(MemoryEntity::data_mut)
+    }
+
+    /// Returns an exclusive reference to the per-function fuel counters.
+    #[cfg(feature = "fuel-profile")]
+    pub fn fuel_profile_mut(&mut self) -> &mut FuelProfileCounters {
+        &mut self.fuel_profile
+    }
+
+    /// Returns an exclusive reference to the per-function loop iteration counters.
+    pub fn loop_counters_mut(&mut self) -> &mut LoopCounters {
+        &mut self.loop_counters
+    }
+
+    /// Returns an iterator over all Wasm or host functions of the [`StoreInner`].
+    pub fn funcs(&self) -> impl Iterator<Item = (Func, &FuncEntity)> {
+        self.funcs
+            .iter()
+            .map(|(idx, entity)| (Func::from_inner(self.wrap_stored(idx)), entity))
+    }
+
+    /// Returns an iterator over all instances of the [`StoreInner`].
+    pub fn instances(&self) -> impl Iterator<Item = &InstanceEntity> {
+        self.instances.iter().map(|(_, entity)| entity)
+    }
+
     /// Wraps an entity `Idx` (index type) as a [`Stored<Idx>`] type.
     ///
     /// # Note
@@ -421,6 +1033,23 @@ impl StoreInner {
         })
     }
 
+    /// Returns the stable ID of the entity referenced by `stored`.
+    ///
+    /// # Note
+    ///
+    /// IDs are assigned per entity kind in the order of allocation and never reused.
+    ///
+    /// # Panics
+    ///
+    /// If `stored` does not belong to the [`StoreInner`].
+    pub fn entity_id<Idx>(&self, stored: &Stored<Idx>) -> u32
+    where
+        Idx: ArenaIndex + Debug,
+    {
+        let index = self.unwrap_stored(stored).into_usize();
+        u32::try_from(index).unwrap_or_else(|_| panic!("entity index out of bounds: {index}"))
+    }
+
     /// Allocates a new [`GlobalEntity`] and returns a [`Global`] reference to it.
     pub fn alloc_global(&mut self, global: GlobalEntity) -> Global {
         let global = self.globals.alloc(global);
@@ -434,7 +1063,10 @@ impl StoreInner {
     }
 
     /// Allocates a new [`MemoryEntity`] and returns a [`Memory`] reference to it.
-    pub fn alloc_memory(&mut self, memory: MemoryEntity) -> Memory {
+    pub fn alloc_memory(&mut self, mut memory: MemoryEntity) -> Memory {
+        if self.engine.config().get_zero_on_free() {
+            memory.set_zero_on_free();
+        }
         let memory = self.memories.alloc(memory);
         Memory::from_inner(self.wrap_stored(memory))
     }
@@ -457,9 +1089,139 @@ impl StoreInner {
     /// Allocates a new [`ExternObjectEntity`] and returns a [`ExternObject`] reference to it.
     pub(super) fn alloc_extern_object(&mut self, object: ExternObjectEntity) -> ExternObject {
         let object = self.extern_objects.alloc(object);
+        #[cfg(feature = "std")]
+        if self.extern_ref_origins.enabled {
+            self.extern_ref_origins
+                .backtraces
+                .insert(object, Backtrace::force_capture());
+        }
         ExternObject::from_inner(self.wrap_stored(object))
     }
 
+    /// Returns the number of allocated extern objects.
+    pub fn len_extern_objects(&self) -> usize {
+        self.extern_objects.len()
+    }
+
+    /// Returns the number of allocated functions.
+    pub fn len_funcs(&self) -> usize {
+        self.funcs.len()
+    }
+
+    /// Returns an iterator over all extern objects and their indices.
+    pub fn extern_objects(&self) -> impl Iterator<Item = (u32, ExternRef)> + '_ {
+        self.extern_objects.iter().map(|(idx, _)| {
+            let object = ExternObject::from_inner(self.wrap_stored(idx));
+            (
+                self.entity_id(object.as_inner()),
+                ExternRef::from_object(object),
+            )
+        })
+    }
+
+    /// Returns the index of the extern object referenced by `externref` unless it is `null`.
+    pub fn extern_object_index(&self, externref: &ExternRef) -> Option<u32> {
+        externref
+            .object()
+            .map(|object| self.entity_id(object.as_inner()))
+    }
+
+    /// Returns an iterator over the values of all reference typed table elements and global variables.
+    pub fn ref_values(&self) -> impl Iterator<Item = (ValType, UntypedVal)> + '_ {
+        let tables = self.tables.iter().flat_map(|(_, table)| {
+            let element = table.ty().element();
+            (0..table.size())
+                .filter_map(move |index| table.get_untyped(index))
+                .map(move |value| (element, value))
+        });
+        let globals = self
+            .globals
+            .iter()
+            .map(|(_, global)| (global.ty().content(), global.get_untyped()));
+        tables.chain(globals).filter(|(ty, _)| ty.is_ref())
+    }
+
+    /// Returns the [`Func`] allocated at `index` if any.
+    pub fn func_at(&self, index: u32) -> Option<Func> {
+        let idx = FuncIdx::from_usize(index as usize);
+        self.funcs.get(idx)?;
+        Some(Func::from_inner(self.wrap_stored(idx)))
+    }
+
+    /// Returns the [`ExternRef`] to the extern object allocated at `index` if any.
+    pub fn extern_object_at(&self, index: u32) -> Option<ExternRef> {
+        let idx = ExternObjectIdx::from_usize(index as usize);
+        self.extern_objects.get(idx)?;
+        let object = ExternObject::from_inner(self.wrap_stored(idx));
+        Some(ExternRef::from_object(object))
+    }
+
+    /// Returns an iterator over all linear memories of the [`StoreInner`] in allocation order.
+    pub fn memory_entities(&self) -> impl Iterator<Item = &MemoryEntity> {
+        self.memories.iter().map(|(_, entity)| entity)
+    }
+
+    /// Returns an iterator over all tables of the [`StoreInner`] in allocation order.
+    pub fn table_entities(&self) -> impl Iterator<Item = &TableEntity> {
+        self.tables.iter().map(|(_, entity)| entity)
+    }
+
+    /// Returns an iterator over all global variables of the [`StoreInner`] in allocation order.
+    pub fn global_entities(&self) -> impl Iterator<Item = &GlobalEntity> {
+        self.globals.iter().map(|(_, entity)| entity)
+    }
+
+    /// Returns an iterator over all data segments of the [`StoreInner`] in allocation order.
+    pub fn data_segment_entities(&self) -> impl Iterator<Item = &DataSegmentEntity> {
+        self.datas.iter().map(|(_, entity)| entity)
+    }
+
+    /// Returns an iterator over all element segments of the [`StoreInner`] in allocation order.
+    pub fn element_segment_entities(&self) -> impl Iterator<Item = &ElementSegmentEntity> {
+        self.elems.iter().map(|(_, entity)| entity)
+    }
+
+    /// Returns an iterator over all data segments of the [`StoreInner`] in allocation order.
+    pub fn data_segment_entities_mut(&mut self) -> impl Iterator<Item = &mut DataSegmentEntity> {
+        self.datas.iter_mut().map(|(_, entity)| entity)
+    }
+
+    /// Returns an iterator over all element segments of the [`StoreInner`] in allocation order.
+    pub fn element_segment_entities_mut(
+        &mut self,
+    ) -> impl Iterator<Item = &mut ElementSegmentEntity> {
+        self.elems.iter_mut().map(|(_, entity)| entity)
+    }
+
+    /// Returns an exclusive reference to the linear memory allocated at `index` if any.
+    pub fn memory_entity_mut(&mut self, index: u32) -> Option<&mut MemoryEntity> {
+        self.memories.get_mut(MemoryIdx::from_usize(index as usize))
+    }
+
+    /// Returns an exclusive reference to the table allocated at `index` if any.
+    pub fn table_entity_mut(&mut self, index: u32) -> Option<&mut TableEntity> {
+        self.tables.get_mut(TableIdx::from_usize(index as usize))
+    }
+
+    /// Returns an exclusive reference to the global variable allocated at `index` if any.
+    pub fn global_entity_mut(&mut self, index: u32) -> Option<&mut GlobalEntity> {
+        self.globals.get_mut(GlobalIdx::from_usize(index as usize))
+    }
+
+    /// Enables or disables tracking the creation backtraces of new extern objects.
+    #[cfg(feature = "std")]
+    pub fn track_extern_refs(&mut self, enable: bool) {
+        self.extern_ref_origins.enabled = enable;
+    }
+
+    /// Returns the creation backtrace of the extern object at `index` if tracked.
+    #[cfg(feature = "std")]
+    pub fn extern_ref_origin(&self, index: u32) -> Option<&Backtrace> {
+        self.extern_ref_origins
+            .backtraces
+            .get(&ExternObjectIdx::from_usize(index as usize))
+    }
+
     /// Allocates a new uninitialized [`InstanceEntity`] and returns an [`Instance`] reference to it.
     ///
     /// # Note
@@ -486,11 +1248,12 @@ impl StoreInner {
     /// - If the [`Instance`] is unknown to the [`Store`].
     /// - If the [`Instance`] has already been initialized.
     /// - If the given [`InstanceEntity`] is itself not initialized, yet.
-    pub fn initialize_instance(&mut self, instance: Instance, init: InstanceEntity) {
+    pub fn initialize_instance(&mut self, instance: Instance, mut init: InstanceEntity) {
         assert!(
             init.is_initialized(),
             "encountered an uninitialized new instance entity: {init:?}",
         );
+        init.set_flat_globals(self.flat_globals(init.globals()));
         let idx = self.unwrap_stored(instance.as_inner());
         let uninit = self
             .instances
@@ -503,6 +1266,32 @@ impl StoreInner {
         *uninit = init;
     }
 
+    /// Returns the index of the first of the flat trailing global variables of `globals`.
+    ///
+    /// Flat global variables are stored in order and without gaps in the [`StoreInner`]
+    /// and are not computed. Returns `globals.len()` if there are no flat global variables.
+    ///
+    /// # Note
+    ///
+    /// Usually the flat global variables are the global variables defined by the
+    /// Wasm module since they are allocated in order upon instantiation.
+    fn flat_globals(&self, globals: &[Global]) -> u32 {
+        let mut start = globals.len();
+        let mut next: Option<usize> = None;
+        for global in globals.iter().rev() {
+            let idx = self.unwrap_stored(global.as_inner());
+            let index = idx.into_usize();
+            if self.resolve_global(global).is_computed()
+                || next.is_some_and(|next| index + 1 != next)
+            {
+                break;
+            }
+            next = Some(index);
+            start -= 1;
+        }
+        u32::try_from(start).unwrap_or_else(|_| panic!("too many global variables: {start}"))
+    }
+
     /// Returns a shared reference to the entity indexed by the given `idx`.
     ///
     /// # Panics
@@ -587,6 +1376,19 @@ impl StoreInner {
         Self::resolve_mut(idx, &mut self.globals)
     }
 
+    /// Returns an exclusive reference to the `len` [`GlobalEntity`] stored in order starting at `first`.
+    ///
+    /// # Panics
+    ///
+    /// - If `first` does not originate from this [`Store`].
+    /// - If there are less than `len` global variables stored starting at `first`.
+    pub fn resolve_globals_mut(&mut self, first: &Global, len: usize) -> &mut [GlobalEntity] {
+        let idx = self.unwrap_stored(first.as_inner());
+        self.globals
+            .get_many_mut(idx, len)
+            .unwrap_or_else(|| panic!("failed to resolve {len} stored globals at: {idx:?}"))
+    }
+
     /// Returns a shared reference to the [`TableEntity`] associated to the given [`Table`].
     ///
     /// # Panics
@@ -829,6 +1631,17 @@ impl StoreInner {
         self.resolve(instance.as_inner(), &self.instances)
     }
 
+    /// Returns an exclusive reference to the [`InstanceEntity`] associated to the given [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Instance`] does not originate from this [`Store`].
+    /// - If the [`Instance`] cannot be resolved to its entity.
+    pub fn resolve_instance_mut(&mut self, instance: &Instance) -> &mut InstanceEntity {
+        let idx = self.unwrap_stored(instance.as_inner());
+        Self::resolve_mut(idx, &mut self.instances)
+    }
+
     /// Returns a shared reference to the [`ExternObjectEntity`] associated to the given [`ExternObject`].
     ///
     /// # Panics
@@ -871,6 +1684,8 @@ where
             data: T::default(),
             limiter: None,
             call_hook: None,
+            watermark_hook: None,
+            write_hook: None,
         }
     }
 }
@@ -884,6 +1699,8 @@ impl<T> Store<T> {
             data,
             limiter: None,
             call_hook: None,
+            watermark_hook: None,
+            write_hook: None,
         }
     }
 
@@ -907,6 +1724,66 @@ impl<T> Store<T> {
         self.data
     }
 
+    /// Releases the execution stack that the [`Store`] caches for its next call from the host.
+    ///
+    /// # Note
+    ///
+    /// The [`Store`] keeps the execution stack of its last call from the host in order
+    /// to speed up frequent calls. Releasing it returns its memory to the [`Engine`]
+    /// which is useful for long-lived [`Store`]s that are only rarely called.
+    ///
+    /// The cached execution stack is acquired again by the next call from the host.
+    pub fn release_cached_stack(&mut self) {
+        if let Some(stack) = self.inner.take_cached_stack() {
+            self.engine().recycle_stack(stack);
+        }
+    }
+
+    /// Returns an iterator over all initialized [`Instance`]s of the [`Store`].
+    ///
+    /// The [`Instance`]s are yielded in the order of their allocation which
+    /// is also the ascending order of their [`Instance::id`].
+    pub fn instances(&self) -> impl Iterator<Item = Instance> + '_ {
+        self.inner
+            .instances
+            .iter()
+            .filter(|(_, entity)| entity.is_initialized())
+            .map(|(idx, _)| Instance::from_inner(self.inner.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Memory`]s of the [`Store`].
+    ///
+    /// The [`Memory`]s are yielded in the order of their allocation which
+    /// is also the ascending order of their [`Memory::id`].
+    pub fn memories(&self) -> impl Iterator<Item = Memory> + '_ {
+        self.inner
+            .memories
+            .iter()
+            .map(|(idx, _)| Memory::from_inner(self.inner.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Table`]s of the [`Store`].
+    ///
+    /// The [`Table`]s are yielded in the order of their allocation which
+    /// is also the ascending order of their [`Table::id`].
+    pub fn tables(&self) -> impl Iterator<Item = Table> + '_ {
+        self.inner
+            .tables
+            .iter()
+            .map(|(idx, _)| Table::from_inner(self.inner.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Global`]s of the [`Store`].
+    ///
+    /// The [`Global`]s are yielded in the order of their allocation which
+    /// is also the ascending order of their [`Global::id`].
+    pub fn globals(&self) -> impl Iterator<Item = Global> + '_ {
+        self.inner
+            .globals
+            .iter()
+            .map(|(idx, _)| Global::from_inner(self.inner.wrap_stored(idx)))
+    }
+
     /// Installs a function into the [`Store`] that will be called with the user
     /// data type `T` to retrieve a [`ResourceLimiter`] any time a limited,
     /// growable resource such as a linear memory or table is grown.
@@ -989,7 +1866,302 @@ impl<T> Store<T> {
     ///
     /// If fuel metering is disabled.
     pub fn set_fuel(&mut self, fuel: u64) -> Result<(), Error> {
-        self.inner.fuel.set_fuel(fuel).map_err(Into::into)
+        self.inner.sync_fuel_share();
+        self.inner.fuel.set_fuel(fuel)?;
+        if self.inner.fuel_share.is_some() {
+            self.inner.fuel_share = Some(fuel);
+        }
+        Ok(())
+    }
+
+    /// Sets the proportional `share` of the fuel of the [`Engine`] for the [`Store`].
+    ///
+    /// The [`Engine`] accounts the fuel consumed by all of its stores with a share and
+    /// denies calls from the host into stores that exceed their share by more than the
+    /// [`Engine::set_fuel_share_slack`] until the other stores caught up. This allows
+    /// for fair multi-tenant execution of many stores on shared threads.
+    ///
+    /// A [`Store`] that newly joins the accounting starts at the consumption of the least
+    /// served [`Store`] scaled by its `share`. Use `None` to leave the accounting.
+    ///
+    /// # Note
+    ///
+    /// Fuel is accounted whenever a call from the host returns and when setting the fuel
+    /// via [`Store::set_fuel`]. Refueling is not accounted as consumption.
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    pub fn set_fuel_share(&mut self, share: Option<NonZeroU32>) -> Result<(), Error> {
+        let remaining = self.inner.fuel.get_fuel()?;
+        self.inner.sync_fuel_share();
+        let store = self.inner.store_idx;
+        let mut ledger = self.inner.engine.fuel_ledger();
+        match share {
+            Some(share) => {
+                ledger.register(store, share);
+                self.inner.fuel_share = Some(remaining);
+            }
+            None => {
+                ledger.unregister(store);
+                self.inner.fuel_share = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the proportional share of the fuel of the [`Engine`] for the [`Store`] if any.
+    ///
+    /// Read more in [`Store::set_fuel_share`].
+    pub fn fuel_share(&self) -> Option<NonZeroU32> {
+        self.fuel_usage().map(|usage| usage.share())
+    }
+
+    /// Returns the [`FuelUsage`] of the [`Store`] if it has a proportional share of fuel.
+    ///
+    /// # Note
+    ///
+    /// Fuel consumed by a Wasm execution that is currently suspended is not yet accounted.
+    ///
+    /// Read more in [`Store::set_fuel_share`].
+    pub fn fuel_usage(&self) -> Option<FuelUsage> {
+        self.inner.fuel_share?;
+        self.inner.engine.fuel_ledger().usage(self.inner.store_idx)
+    }
+
+    /// Returns the [`InterruptHandle`] of the [`Store`].
+    ///
+    /// The returned [`InterruptHandle`] may be sent to other threads in order to interrupt the
+    /// host calls of the [`Store`] that run via [`Caller::block_on_interruptible`] or
+    /// [`Caller::block_on_future_interruptible`].
+    ///
+    /// [`Caller::block_on_interruptible`]: crate::Caller::block_on_interruptible
+    /// [`Caller::block_on_future_interruptible`]: crate::Caller::block_on_future_interruptible
+    #[cfg(feature = "std")]
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.inner.interrupt.handle().clone()
+    }
+
+    /// Returns the [`SignalHandle`] of the [`Store`].
+    ///
+    /// The returned [`SignalHandle`] may be sent to other threads in order to schedule
+    /// guest functions at the next safepoint of a running Wasm execution of the [`Store`].
+    #[cfg(feature = "std")]
+    pub fn signal_handle(&self) -> SignalHandle {
+        self.inner.interrupt.signals().clone()
+    }
+
+    /// Returns the deadline for interruptible host calls of the [`Store`] if any.
+    #[cfg(feature = "std")]
+    pub fn deadline(&self) -> Option<std::time::Instant> {
+        self.inner.interrupt.deadline()
+    }
+
+    /// Sets the `deadline` after which interruptible host calls of the [`Store`] trap.
+    ///
+    /// Host calls that are still running at the `deadline` trap with [`TrapCode::Interrupted`].
+    /// A `deadline` of `None` removes the current deadline.
+    #[cfg(feature = "std")]
+    pub fn set_deadline(&mut self, deadline: Option<std::time::Instant>) {
+        self.inner.interrupt.set_deadline(deadline);
+    }
+
+    /// Returns the breakdown of fuel consumed by each executed Wasm function of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - Enable fuel metering via [`Config::consume_fuel`](crate::Config::consume_fuel).
+    /// - The returned [`FuelProfile`] is a snapshot and is not updated by later executions.
+    #[cfg(feature = "fuel-profile")]
+    pub fn fuel_profile(&self) -> FuelProfile {
+        FuelProfile::new(&self.inner, &self.inner.fuel_profile)
+    }
+
+    /// Resets the per-function fuel counters of the [`Store`].
+    ///
+    /// This does not alter the remaining fuel of the [`Store`].
+    #[cfg(feature = "fuel-profile")]
+    pub fn reset_fuel_profile(&mut self) {
+        self.inner.fuel_profile.reset();
+    }
+
+    /// Returns the iteration counts of the Wasm `loop`s of the Wasm function `func`.
+    ///
+    /// The `loop`s are indexed in order of their appearance in the body of `func`.
+    /// Every entry and every branch back to the start of a `loop` counts as an iteration.
+    ///
+    /// # Note
+    ///
+    /// - Enable loop iteration counting via [`Config::count_loop_iterations`].
+    /// - Iteration counts are attributed to Wasm function bodies. Therefore multiple
+    ///   instances of the same [`Module`] share their loop iteration counts.
+    /// - The returned slice ends with the last `loop` of `func` that has been iterated.
+    ///   It is empty for host functions and Wasm functions without iterated `loop`s.
+    ///
+    /// # Panics
+    ///
+    /// If `func` does not originate from this [`Store`].
+    ///
+    /// [`Module`]: crate::Module
+    pub fn loop_iterations(&self, func: &Func) -> &[u64] {
+        match self.inner.resolve_func(func) {
+            FuncEntity::Wasm(entity) => self.inner.loop_counters.get(entity.func_body()),
+            _ => &[],
+        }
+    }
+
+    /// Resets the loop iteration counts of all Wasm functions of the [`Store`].
+    pub fn reset_loop_iterations(&mut self) {
+        self.inner.loop_counters.reset();
+    }
+
+    /// Starts tracing the Wasm and host function calls of the [`Store`].
+    ///
+    /// Discards all previously traced function calls. The traced function calls
+    /// are queried via [`Store::take_trace`] and [`Store::disable_tracing`].
+    ///
+    /// # Note
+    ///
+    /// Timestamps of the [`Trace`] are relative to the call of this method.
+    #[cfg(feature = "trace")]
+    pub fn enable_tracing(&mut self) {
+        self.inner.tracer = Some(Tracer::default());
+    }
+
+    /// Takes the function calls traced since the last call to this method.
+    ///
+    /// Returns an empty [`Trace`] if tracing is disabled.
+    #[cfg(feature = "trace")]
+    pub fn take_trace(&mut self) -> Trace {
+        let Some(mut tracer) = self.inner.tracer.take() else {
+            return Trace::default();
+        };
+        let trace = tracer.take(&self.inner);
+        self.inner.tracer = Some(tracer);
+        trace
+    }
+
+    /// Stops tracing function calls and returns the function calls traced since the last [`Store::take_trace`].
+    #[cfg(feature = "trace")]
+    pub fn disable_tracing(&mut self) -> Trace {
+        let trace = self.take_trace();
+        self.inner.tracer = None;
+        trace
+    }
+
+    /// Starts taking a [`Checkpoint`] of Wasm executions every `interval` units of consumed fuel.
+    ///
+    /// Also starts recording host calls via [`Store::record_host_calls`] since rewound
+    /// executions replay the host calls instead of invoking the real host functions.
+    /// Discards all previously taken checkpoints.
+    ///
+    /// # Note
+    ///
+    /// - Only executions of Wasm functions called from the host are checkpointed.
+    /// - Checkpoints are taken at the start of basic blocks and thus might be taken
+    ///   a few units of fuel after an `interval` has been reached.
+    /// - Checkpoints do not capture the host state, tables, dropped data or element
+    ///   segments and entities that have been created after they were taken.
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    #[cfg(feature = "time-travel")]
+    pub fn enable_checkpoints(&mut self, interval: u64) -> Result<(), Error> {
+        self.inner.fuel.get_fuel()?;
+        let consumed = self.inner.fuel.consumed();
+        self.inner.time_travel = Some(TimeTravel::new(interval, consumed));
+        self.record_host_calls();
+        Ok(())
+    }
+
+    /// Stops taking checkpoints and discards all previously taken checkpoints.
+    ///
+    /// This does not stop recording or replaying host calls.
+    #[cfg(feature = "time-travel")]
+    pub fn disable_checkpoints(&mut self) {
+        self.inner.time_travel = None;
+    }
+
+    /// Returns all checkpoints taken since [`Store::enable_checkpoints`] in the order they were taken.
+    #[cfg(feature = "time-travel")]
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        match &self.inner.time_travel {
+            Some(time_travel) => time_travel.checkpoints(),
+            None => &[],
+        }
+    }
+
+    /// Rewinds the [`Store`] to the `index`-th [`Checkpoint`] and re-runs the execution from there.
+    ///
+    /// The `tracer` is invoked before every basic block executed by the re-run and
+    /// the results of the re-run execution are written into `outputs`.
+    ///
+    /// # Note
+    ///
+    /// - All host calls of the re-run are replayed from the host call recording
+    ///   which is left in replay mode afterwards.
+    /// - The host state `T` is not rewound.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no [`Checkpoint`] at `index`.
+    /// - If `outputs` do not match the results of [`Checkpoint::func`].
+    /// - If the [`Store`] cannot be restored to the state of the [`Checkpoint`].
+    /// - If the re-run execution traps.
+    #[cfg(feature = "time-travel")]
+    pub fn rewind(
+        &mut self,
+        index: usize,
+        outputs: &mut [Val],
+        mut tracer: impl FnMut(&TraceStep),
+    ) -> Result<(), Error> {
+        let Some(checkpoint) = self.checkpoints().get(index).cloned() else {
+            return Err(Error::new(format!("missing checkpoint at index {index}")));
+        };
+        let func_type = checkpoint.func().ty(&*self);
+        func_type.match_results(outputs, false)?;
+        func_type.prepare_outputs(outputs);
+        checkpoint.restore(&mut self.inner)?;
+        if let Some(time_travel) = self.inner.time_travel_mut() {
+            time_travel.set_root(checkpoint.func());
+        }
+        self.engine()
+            .clone()
+            .rewind(self, checkpoint.stack().clone(), &mut tracer, outputs)?;
+        Ok(())
+    }
+
+    /// Starts recording all host function calls of Wasm guests of the [`Store`].
+    ///
+    /// Ends a previously started recording or replay.
+    /// Use [`Store::take_host_call_recording`] to retrieve the recorded host calls.
+    pub fn record_host_calls(&mut self) {
+        self.inner.host_calls = Some(HostCallLog::Recording(HostCallRecording::default()));
+    }
+
+    /// Starts serving all host function calls of Wasm guests of the [`Store`] from `recording`.
+    ///
+    /// While replaying, the real host functions are not invoked. Instead the recorded
+    /// results, errors, fuel consumption and linear memory writes are applied in order.
+    /// Ends a previously started recording or replay.
+    ///
+    /// # Note
+    ///
+    /// A host call fails with an error if its parameters differ from the recorded
+    /// host call or if all recorded host calls have already been replayed.
+    pub fn replay_host_calls(&mut self, recording: HostCallRecording) {
+        self.inner.host_calls = Some(HostCallLog::Replaying { recording, next: 0 });
+    }
+
+    /// Ends recording or replaying host calls and returns the [`HostCallRecording`].
+    ///
+    /// Returns `None` if the [`Store`] neither records nor replays host calls.
+    pub fn take_host_call_recording(&mut self) -> Option<HostCallRecording> {
+        self.inner
+            .host_calls
+            .take()
+            .map(HostCallLog::into_recording)
     }
 
     /// Allocates a new [`TrampolineEntity`] and returns a [`Trampoline`] reference to it.
@@ -1050,6 +2222,212 @@ impl<T> Store<T> {
         self.call_hook = Some(CallHookWrapper(Box::new(hook)));
     }
 
+    /// Injects a trap with `code` that triggers at the next function call.
+    ///
+    /// This is equivalent to [`Store::inject_trap_after_calls`] with zero calls.
+    pub fn inject_trap(&mut self, code: TrapCode) {
+        self.inject_trap_after_calls(0, code);
+    }
+
+    /// Injects a trap with `code` that triggers after `calls` function calls.
+    ///
+    /// Every call of a Wasm or host function, from the host or from Wasm, is
+    /// counted. The first `calls` calls execute normally and the call after
+    /// them traps with `code` before its callee executes. This allows test
+    /// harnesses to deterministically exercise the error paths of guests and
+    /// hosts, e.g. by simulating [`TrapCode::OutOfFuel`] at a specific call.
+    ///
+    /// # Note
+    ///
+    /// - The trap triggers only once and replaces any previously injected trap.
+    /// - Nothing is consumed from the fuel of the [`Store`] by an injected trap.
+    pub fn inject_trap_after_calls(&mut self, calls: u64, code: TrapCode) {
+        self.inner.injected_trap = Some(InjectedTrap {
+            code,
+            remaining_calls: calls,
+        });
+    }
+
+    /// Removes the trap injected via [`Store::inject_trap`] if it did not trigger yet.
+    ///
+    /// Returns the [`TrapCode`] of the removed trap if any.
+    pub fn cancel_injected_trap(&mut self) -> Option<TrapCode> {
+        self.inner
+            .injected_trap
+            .take()
+            .map(|injected| injected.code)
+    }
+
+    /// Sets the [`TrapHandler`] of the [`Store`].
+    ///
+    /// The [`TrapHandler`] converts traps of its designated functions into results
+    /// returned to their host callers. Replaces the previous [`TrapHandler`] if any.
+    pub fn set_trap_handler(&mut self, handler: TrapHandler) {
+        self.inner.trap_handler = Some(handler);
+    }
+
+    /// Removes the [`TrapHandler`] of the [`Store`] and returns it if any.
+    pub fn take_trap_handler(&mut self) -> Option<TrapHandler> {
+        self.inner.trap_handler.take()
+    }
+
+    /// Returns the [`StorePolicy`] of the [`Store`].
+    pub fn policy(&self) -> StorePolicy {
+        self.inner.policy
+    }
+
+    /// Sets the [`StorePolicy`] of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// The `policy` applies to host function calls that start after this call.
+    pub fn set_policy(&mut self, policy: StorePolicy) {
+        self.inner.policy = policy;
+    }
+
+    /// Sets a callback function that is executed whenever a Wasm `memory.grow`
+    /// operation grows a [`Memory`] beyond its watermark.
+    ///
+    /// The function is passed a `&mut T` to the underlying store, and a
+    /// [`MemoryWatermark`] describing the `memory.grow` operation.
+    ///
+    /// The callback decides via [`WatermarkAction`] whether the `memory.grow` operation
+    /// continues or fails gracefully by returning `-1`. If an [`Error`] is returned the
+    /// execution traps with it and the error is returned to the host caller.
+    ///
+    /// # Note
+    ///
+    /// - The callback is invoked before the [`ResourceLimiter`] is queried.
+    /// - Growth of a [`Memory`] beyond its watermark is allowed if no callback is set.
+    /// - Growth via [`Memory::grow`] from the host does not invoke the callback.
+    ///
+    /// Read more about watermarks in [`Memory::set_watermark`].
+    pub fn memory_watermark_hook(
+        &mut self,
+        hook: impl FnMut(&mut T, MemoryWatermark) -> Result<WatermarkAction, Error>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.watermark_hook = Some(WatermarkHookWrapper(Box::new(hook)));
+    }
+
+    /// Tags the `range` of `memory` with `tag` to report its writes.
+    ///
+    /// Writes to tagged regions are reported to the callback set by
+    /// [`Store::memory_write_hook`], e.g. to observe the metadata of a guest allocator.
+    /// Together with `Memory::walk_dlmalloc` of the `heap-walk` crate feature this allows
+    /// to attribute guest heap usage.
+    ///
+    /// # Note
+    ///
+    /// - Multiple regions may share the same `tag` and regions may overlap.
+    /// - `range` may exceed the current size of `memory` whose missing bytes count as zero.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from this [`Store`].
+    pub fn tag_memory_region(&mut self, memory: &Memory, range: Range<usize>, tag: &str) {
+        let mut tags = mem::take(&mut self.inner.memory_tags);
+        tags.insert(&self.inner, *memory, range, tag);
+        self.inner.memory_tags = tags;
+    }
+
+    /// Removes the tag from all memory regions tagged with `tag`.
+    ///
+    /// Returns `true` if any region was tagged with `tag`.
+    pub fn untag_memory_region(&mut self, tag: &str) -> bool {
+        self.inner.memory_tags.remove(tag)
+    }
+
+    /// Returns an iterator over the memory regions tagged via [`Store::tag_memory_region`].
+    ///
+    /// Yields the [`Memory`], the address range and the tag of each region in tagging order.
+    pub fn memory_tags(&self) -> impl Iterator<Item = (Memory, Range<usize>, &str)> + '_ {
+        self.inner.memory_tags.iter()
+    }
+
+    /// Sets a callback function that is executed whenever a region tagged via
+    /// [`Store::tag_memory_region`] has been written.
+    ///
+    /// The function is passed a `&mut T` to the underlying store, and a
+    /// [`MemoryRegionWrite`] describing the written region.
+    ///
+    /// Writes are detected by comparing tagged regions with a copy of their contents
+    /// whenever control passes between the host and Wasm, i.e. at the same points at
+    /// which the [`Store::call_hook`] is invoked. Therefore Wasm execution is not slowed
+    /// down by tagging regions and multiple writes in between are reported at once.
+    /// If an [`Error`] is returned the execution traps with it.
+    ///
+    /// # Note
+    ///
+    /// To be notified about `memory.grow` operations use [`Store::memory_watermark_hook`]
+    /// together with a watermark of zero set via [`Memory::set_watermark`].
+    pub fn memory_write_hook(
+        &mut self,
+        hook: impl FnMut(&mut T, MemoryRegionWrite) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        self.write_hook = Some(WriteHookWrapper(Box::new(hook)));
+    }
+
+    /// Reports the writes to tagged memory regions to the [`Store::memory_write_hook`].
+    ///
+    /// # Errors
+    ///
+    /// If the callback set by [`Store::memory_write_hook`] returned an error.
+    #[cold]
+    fn check_memory_tags(&mut self) -> Result<(), Error> {
+        let mut tags = mem::take(&mut self.inner.memory_tags);
+        let writes = tags.take_writes(&self.inner);
+        self.inner.memory_tags = tags;
+        let Some(hook) = self.write_hook.as_mut() else {
+            return Ok(());
+        };
+        for write in writes {
+            hook.0(&mut self.data, write)?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether growing `memory` by `delta` pages passes its watermark.
+    ///
+    /// Executes the callback set by [`Store::memory_watermark_hook`] if the growth
+    /// goes beyond the watermark of `memory`.
+    ///
+    /// # Note
+    ///
+    /// Returns `Ok(true)` if the growth may continue.
+    ///
+    /// # Errors
+    ///
+    /// If the callback set by [`Store::memory_watermark_hook`] returned an error.
+    pub(crate) fn check_memory_watermark(
+        &mut self,
+        memory: &Memory,
+        delta: u32,
+    ) -> Result<bool, Error> {
+        let Some(hook) = self.watermark_hook.as_mut() else {
+            return Ok(true);
+        };
+        let entity = self.inner.resolve_memory(memory);
+        let Some(watermark) = entity.watermark() else {
+            return Ok(true);
+        };
+        let current = entity.size();
+        let desired = current.saturating_add(delta);
+        if desired <= watermark {
+            return Ok(true);
+        }
+        let event = MemoryWatermark {
+            memory: *memory,
+            watermark,
+            current,
+            desired,
+        };
+        let action = hook.0(&mut self.data, event)?;
+        Ok(action == WatermarkAction::Allow)
+    }
+
     /// Executes the callback set by [`Store::call_hook`] if any has been set.
     ///
     /// # Note
@@ -1058,6 +2436,9 @@ impl<T> Store<T> {
     /// - Returns `Ok(())` if no call hook exists.
     #[inline]
     pub(crate) fn invoke_call_hook(&mut self, call_type: CallHook) -> Result<(), Error> {
+        if !self.inner.memory_tags.is_empty() {
+            self.check_memory_tags()?;
+        }
         match self.call_hook.as_mut() {
             None => Ok(()),
             Some(call_hook) => Self::invoke_call_hook_impl(&mut self.data, call_type, call_hook),
@@ -0,0 +1,330 @@
+use crate::{core::TrapCode, Caller, Error, Extern, Linker, Memory};
+use alloc::{boxed::Box, vec::Vec};
+use std::{
+    io::{self, Read, Write},
+    sync::mpsc::{Receiver, SyncSender, TryRecvError, TrySendError},
+};
+
+/// The name of the host module that provides the stream functions.
+const STREAMS_MODULE: &str = "streams";
+
+/// Guest stream handles backed by embedder provided readers, writers and channels.
+///
+/// A [`Streams`] instance is usually stored in the host state of a [`Store`] and its
+/// functions are made available to Wasm guests via [`Streams::add_to_linker`].
+///
+/// # Guest Interface
+///
+/// All functions are imported from the `streams` module:
+///
+/// - `read(handle: i32, ptr: i32, len: i32) -> i32`:
+///   Reads up to `len` bytes from the stream into the guest memory at `ptr`.
+///   Returns the number of bytes read where `0` signals the end of the stream.
+/// - `write(handle: i32, ptr: i32, len: i32) -> i32`:
+///   Writes up to `len` bytes from the guest memory at `ptr` to the stream.
+///   Returns the number of bytes written.
+/// - `flush(handle: i32) -> i32`:
+///   Flushes the stream and returns `0` upon success.
+/// - `close(handle: i32) -> i32`:
+///   Closes the stream and returns `0` upon success.
+///
+/// All functions return one of the negative status codes defined on [`Streams`]
+/// upon failure. The guest must export its linear memory as `memory`.
+///
+/// # Backpressure
+///
+/// - A stream signals [`Streams::WOULD_BLOCK`] if it currently cannot make progress,
+///   for example if a channel is empty or a bounded channel is full. The guest is
+///   expected to yield and retry later.
+/// - If fuel metering is enabled every transferred byte consumes fuel according to
+///   [`FuelCosts::fuel_for_bytes`]. Transfers are shortened to what the remaining
+///   fuel can pay for and trap with [`TrapCode::OutOfFuel`] if no byte can be paid for.
+///
+/// [`Store`]: crate::Store
+/// [`FuelCosts::fuel_for_bytes`]: crate::FuelCosts::fuel_for_bytes
+#[derive(Debug, Default)]
+pub struct Streams {
+    /// The streams indexed by their handles.
+    ///
+    /// `None` for closed streams.
+    streams: Vec<Option<Stream>>,
+}
+
+/// A single guest stream.
+enum Stream {
+    /// A stream backed by a [`Read`] implementation.
+    Reader(Box<dyn Read + Send>),
+    /// A stream backed by a [`Write`] implementation.
+    Writer(Box<dyn Write + Send>),
+    /// A stream backed by the receiving end of a channel.
+    Receiver {
+        /// The channel that provides chunks of bytes.
+        receiver: Receiver<Vec<u8>>,
+        /// The remaining bytes of the last received chunk.
+        pending: Vec<u8>,
+        /// The position of the first unread byte in `pending`.
+        pos: usize,
+    },
+    /// A stream backed by the sending end of a bounded channel.
+    Sender(SyncSender<Vec<u8>>),
+}
+
+impl core::fmt::Debug for Stream {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Reader(_) => f.debug_struct("Reader").finish_non_exhaustive(),
+            Self::Writer(_) => f.debug_struct("Writer").finish_non_exhaustive(),
+            Self::Receiver { .. } => f.debug_struct("Receiver").finish_non_exhaustive(),
+            Self::Sender(_) => f.debug_struct("Sender").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl Streams {
+    /// Status code returned if a stream currently cannot make progress.
+    pub const WOULD_BLOCK: i32 = -1;
+    /// Status code returned for unknown or closed handles and for unsupported operations.
+    pub const INVALID_HANDLE: i32 = -2;
+    /// Status code returned if the underlying reader, writer or channel failed.
+    pub const IO_ERROR: i32 = -3;
+
+    /// Creates a new [`Streams`] without any streams.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a readable stream backed by `reader` and returns its handle.
+    pub fn add_reader(&mut self, reader: impl Read + Send + 'static) -> u32 {
+        self.push(Stream::Reader(Box::new(reader)))
+    }
+
+    /// Adds a writable stream backed by `writer` and returns its handle.
+    pub fn add_writer(&mut self, writer: impl Write + Send + 'static) -> u32 {
+        self.push(Stream::Writer(Box::new(writer)))
+    }
+
+    /// Adds a readable stream backed by `receiver` and returns its handle.
+    ///
+    /// Reading from the stream signals [`Streams::WOULD_BLOCK`] while the channel is empty
+    /// and signals the end of the stream once the channel is disconnected.
+    pub fn add_receiver(&mut self, receiver: Receiver<Vec<u8>>) -> u32 {
+        self.push(Stream::Receiver {
+            receiver,
+            pending: Vec::new(),
+            pos: 0,
+        })
+    }
+
+    /// Adds a writable stream backed by `sender` and returns its handle.
+    ///
+    /// Every write sends a single chunk of bytes. Writing to the stream signals
+    /// [`Streams::WOULD_BLOCK`] while the bounded channel is full.
+    pub fn add_sender(&mut self, sender: SyncSender<Vec<u8>>) -> u32 {
+        self.push(Stream::Sender(sender))
+    }
+
+    /// Closes the stream at `handle`.
+    ///
+    /// Returns `false` if there is no open stream at `handle`.
+    pub fn close(&mut self, handle: u32) -> bool {
+        self.streams
+            .get_mut(handle as usize)
+            .and_then(Option::take)
+            .is_some()
+    }
+
+    /// Returns `true` if there is an open stream at `handle`.
+    pub fn is_open(&self, handle: u32) -> bool {
+        matches!(self.streams.get(handle as usize), Some(Some(_)))
+    }
+
+    /// Defines the functions of the `streams` host module in `linker`.
+    ///
+    /// The [`Streams`] of the host state are accessed via `get`.
+    ///
+    /// # Errors
+    ///
+    /// If any of the functions is already defined in `linker`.
+    pub fn add_to_linker<T: 'static>(
+        linker: &mut Linker<T>,
+        get: fn(&mut T) -> &mut Streams,
+    ) -> Result<(), Error> {
+        linker.func_wrap(
+            STREAMS_MODULE,
+            "read",
+            move |mut caller: Caller<'_, T>, handle: u32, ptr: u32, len: u32| {
+                Self::transfer(&mut caller, get, handle, ptr, len, Stream::read)
+            },
+        )?;
+        linker.func_wrap(
+            STREAMS_MODULE,
+            "write",
+            move |mut caller: Caller<'_, T>, handle: u32, ptr: u32, len: u32| {
+                Self::transfer(&mut caller, get, handle, ptr, len, Stream::write)
+            },
+        )?;
+        linker.func_wrap(
+            STREAMS_MODULE,
+            "flush",
+            move |mut caller: Caller<'_, T>, handle: u32| -> i32 {
+                match get(caller.data_mut()).get_mut(handle) {
+                    Some(stream) => stream.flush(),
+                    None => Self::INVALID_HANDLE,
+                }
+            },
+        )?;
+        linker.func_wrap(
+            STREAMS_MODULE,
+            "close",
+            move |mut caller: Caller<'_, T>, handle: u32| -> i32 {
+                match get(caller.data_mut()).close(handle) {
+                    true => 0,
+                    false => Self::INVALID_HANDLE,
+                }
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Pushes `stream` and returns its handle.
+    fn push(&mut self, stream: Stream) -> u32 {
+        let handle = u32::try_from(self.streams.len())
+            .unwrap_or_else(|_| panic!("out of bounds stream handle"));
+        self.streams.push(Some(stream));
+        handle
+    }
+
+    /// Returns an exclusive reference to the open stream at `handle` if any.
+    fn get_mut(&mut self, handle: u32) -> Option<&mut Stream> {
+        self.streams.get_mut(handle as usize)?.as_mut()
+    }
+
+    /// Transfers up to `len` bytes between the stream at `handle` and the guest memory at `ptr`.
+    ///
+    /// Consumes fuel for all transferred bytes if fuel metering is enabled.
+    fn transfer<T>(
+        caller: &mut Caller<'_, T>,
+        get: fn(&mut T) -> &mut Streams,
+        handle: u32,
+        ptr: u32,
+        len: u32,
+        op: fn(&mut Stream, &mut [u8]) -> i32,
+    ) -> Result<i32, Error> {
+        let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+            return Err(Error::new(
+                "missing `memory` export for `streams` functions",
+            ));
+        };
+        let fuel = caller.get_fuel().ok();
+        let bytes_per_fuel = caller.engine().config().get_fuel_costs().bytes_per_fuel();
+        // Note: transfers are limited so that their lengths fit into the `i32` status code.
+        let mut len = len.min(i32::MAX as u32) as usize;
+        if let Some(fuel) = fuel {
+            // Backpressure: only transfer as many bytes as the remaining fuel pays for.
+            let max_len = fuel
+                .saturating_mul(bytes_per_fuel.get())
+                .saturating_add(bytes_per_fuel.get() - 1);
+            len = len.min(usize::try_from(max_len).unwrap_or(usize::MAX));
+            if len == 0 && max_len == 0 {
+                return Err(Error::from(TrapCode::OutOfFuel));
+            }
+        }
+        let status = Self::transfer_impl(caller, memory, get, handle, ptr, len, op)?;
+        if let Some(fuel) = fuel {
+            if let Ok(transferred) = u64::try_from(status) {
+                let costs = caller
+                    .engine()
+                    .config()
+                    .get_fuel_costs()
+                    .fuel_for_bytes(transferred);
+                caller.set_fuel(fuel - costs)?;
+            }
+        }
+        Ok(status)
+    }
+
+    /// Applies `op` to the stream at `handle` and the guest memory at `ptr` of length `len`.
+    fn transfer_impl<T>(
+        caller: &mut Caller<'_, T>,
+        memory: Memory,
+        get: fn(&mut T) -> &mut Streams,
+        handle: u32,
+        ptr: u32,
+        len: usize,
+        op: fn(&mut Stream, &mut [u8]) -> i32,
+    ) -> Result<i32, Error> {
+        let (data, state) = memory.data_and_store_mut(caller);
+        let buffer = data
+            .get_mut(ptr as usize..)
+            .and_then(|data| data.get_mut(..len))
+            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        let status = match get(state).get_mut(handle) {
+            Some(stream) => op(stream, buffer),
+            None => Self::INVALID_HANDLE,
+        };
+        Ok(status)
+    }
+}
+
+impl Stream {
+    /// Reads bytes from the [`Stream`] into `buffer`.
+    fn read(&mut self, buffer: &mut [u8]) -> i32 {
+        match self {
+            Self::Reader(reader) => Self::status(reader.read(buffer)),
+            Self::Receiver {
+                receiver,
+                pending,
+                pos,
+            } => {
+                if *pos == pending.len() {
+                    match receiver.try_recv() {
+                        Ok(chunk) => {
+                            *pending = chunk;
+                            *pos = 0;
+                        }
+                        Err(TryRecvError::Empty) => return Streams::WOULD_BLOCK,
+                        Err(TryRecvError::Disconnected) => return 0,
+                    }
+                }
+                let len = buffer.len().min(pending.len() - *pos);
+                buffer[..len].copy_from_slice(&pending[*pos..][..len]);
+                *pos += len;
+                Self::status(Ok(len))
+            }
+            Self::Writer(_) | Self::Sender(_) => Streams::INVALID_HANDLE,
+        }
+    }
+
+    /// Writes the bytes of `buffer` to the [`Stream`].
+    fn write(&mut self, buffer: &mut [u8]) -> i32 {
+        match self {
+            Self::Writer(writer) => Self::status(writer.write(buffer)),
+            Self::Sender(sender) => match sender.try_send(buffer.to_vec()) {
+                Ok(()) => Self::status(Ok(buffer.len())),
+                Err(TrySendError::Full(_)) => Streams::WOULD_BLOCK,
+                Err(TrySendError::Disconnected(_)) => Streams::IO_ERROR,
+            },
+            Self::Reader(_) | Self::Receiver { .. } => Streams::INVALID_HANDLE,
+        }
+    }
+
+    /// Flushes the [`Stream`].
+    fn flush(&mut self) -> i32 {
+        match self {
+            Self::Writer(writer) => Self::status(writer.flush().map(|()| 0)),
+            Self::Sender(_) => 0,
+            Self::Reader(_) | Self::Receiver { .. } => Streams::INVALID_HANDLE,
+        }
+    }
+
+    /// Converts the result of an I/O operation into a guest status code.
+    fn status(result: io::Result<usize>) -> i32 {
+        match result {
+            Ok(len) => i32::try_from(len).unwrap_or(Streams::IO_ERROR),
+            Err(error) => match error.kind() {
+                io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => Streams::WOULD_BLOCK,
+                _ => Streams::IO_ERROR,
+            },
+        }
+    }
+}
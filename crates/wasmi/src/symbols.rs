@@ -0,0 +1,197 @@
+use crate::{
+    engine::{EngineFunc, EngineFuncSpan},
+    Module,
+};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::fmt::{self, Display};
+use wasmparser::{BinaryReader, Name, NameSectionReader};
+
+/// Human-readable names of a Wasm module and its functions.
+///
+/// Register the [`ModuleSymbols`] of a [`Module`] via [`Engine::register_symbols`] in
+/// order to symbolicate traps, profiles and traces of all modules with the same
+/// [`Module::hash`] consistently, regardless of the [`Store`] they originate from.
+///
+/// [`Engine::register_symbols`]: crate::Engine::register_symbols
+/// [`Store`]: crate::Store
+#[derive(Debug, Clone)]
+pub struct ModuleSymbols {
+    /// The name of the module.
+    name: Box<str>,
+    /// The names of the functions by their index in the Wasm function index space.
+    funcs: BTreeMap<u32, Box<str>>,
+}
+
+impl ModuleSymbols {
+    /// Creates new [`ModuleSymbols`] for the module `name` without function names.
+    pub fn new(name: impl Into<Box<str>>) -> Self {
+        Self {
+            name: name.into(),
+            funcs: BTreeMap::new(),
+        }
+    }
+
+    /// Creates new [`ModuleSymbols`] for the module `name` with the function names of the
+    /// `name` custom section of the `module`.
+    ///
+    /// # Note
+    ///
+    /// Malformed subsections are skipped. No function names are found if the custom
+    /// sections of the `module` are ignored via [`Config::ignore_custom_sections`].
+    ///
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    pub fn from_name_section(name: impl Into<Box<str>>, module: &Module) -> Self {
+        let mut symbols = Self::new(name);
+        for section in module.custom_sections() {
+            if section.name() != "name" {
+                continue;
+            }
+            let reader = NameSectionReader::new(BinaryReader::new(section.data(), 0));
+            for subsection in reader.into_iter().flatten() {
+                let Name::Function(names) = subsection else {
+                    continue;
+                };
+                for naming in names.into_iter().flatten() {
+                    symbols.set_func_name(naming.index, naming.name);
+                }
+            }
+        }
+        symbols
+    }
+
+    /// Names the function at `index` of the Wasm function index space `name`.
+    ///
+    /// The Wasm function index space starts with the imported functions.
+    /// Replaces the previous name of the function if any.
+    pub fn set_func_name(&mut self, index: u32, name: impl Into<Box<str>>) -> &mut Self {
+        self.funcs.insert(index, name.into());
+        self
+    }
+
+    /// Returns the name of the module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the function at `index` of the Wasm function index space if any.
+    pub fn func_name(&self, index: u32) -> Option<&str> {
+        self.funcs.get(&index).map(Box::as_ref)
+    }
+}
+
+/// The symbol of a Wasm function resolved via the [`ModuleSymbols`] registered at its [`Engine`].
+///
+/// Resolved via [`Func::symbol`] or attached to [`FuelTrapFrame`]s.
+///
+/// [`Engine`]: crate::Engine
+/// [`Func::symbol`]: crate::Func::symbol
+/// [`FuelTrapFrame`]: crate::errors::FuelTrapFrame
+#[derive(Debug, Clone)]
+pub struct FuncSymbol {
+    /// The [`Module::hash`] of the module defining the function.
+    module_hash: u64,
+    /// The symbols of the module defining the function.
+    module: Arc<ModuleSymbols>,
+    /// The index of the function in the Wasm function index space.
+    index: u32,
+}
+
+impl FuncSymbol {
+    /// Returns the [`Module::hash`] of the module defining the function.
+    pub fn module_hash(&self) -> u64 {
+        self.module_hash
+    }
+
+    /// Returns the name of the module defining the function.
+    pub fn module_name(&self) -> &str {
+        self.module.name()
+    }
+
+    /// Returns the index of the function in the Wasm function index space.
+    pub fn func_index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the name of the function if any.
+    pub fn func_name(&self) -> Option<&str> {
+        self.module.func_name(self.index)
+    }
+}
+
+impl Display for FuncSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.func_name() {
+            Some(name) => write!(f, "{}!{name}", self.module_name()),
+            None => write!(f, "{}!func[{}]", self.module_name(), self.index),
+        }
+    }
+}
+
+/// A Wasm module compiled by an [`Engine`](crate::Engine).
+#[derive(Debug, Copy, Clone)]
+struct CompiledModule {
+    /// The function bodies of the module.
+    funcs: EngineFuncSpan,
+    /// The number of functions imported by the module.
+    len_imported_funcs: u32,
+    /// The [`Module::hash`] of the module.
+    hash: u64,
+}
+
+/// Maps the [`Module::hash`] of compiled modules to their registered [`ModuleSymbols`].
+#[derive(Debug, Default)]
+pub struct SymbolRegistry {
+    /// The compiled modules with function bodies.
+    modules: Vec<CompiledModule>,
+    /// The registered symbols by [`Module::hash`].
+    symbols: BTreeMap<u64, Arc<ModuleSymbols>>,
+}
+
+impl SymbolRegistry {
+    /// Records that the function bodies `funcs` belong to a module with `hash`.
+    pub fn add_module(&mut self, funcs: EngineFuncSpan, len_imported_funcs: u32, hash: u64) {
+        if funcs.is_empty() {
+            return;
+        }
+        self.modules.push(CompiledModule {
+            funcs,
+            len_imported_funcs,
+            hash,
+        });
+    }
+
+    /// Registers the `symbols` for all modules with `hash`.
+    ///
+    /// Replaces the previously registered [`ModuleSymbols`] if any.
+    pub fn register(&mut self, hash: u64, symbols: ModuleSymbols) {
+        self.symbols.insert(hash, Arc::new(symbols));
+    }
+
+    /// Unregisters the symbols of the modules with `hash`.
+    ///
+    /// Returns `true` if symbols were registered for `hash`.
+    pub fn unregister(&mut self, hash: u64) -> bool {
+        self.symbols.remove(&hash).is_some()
+    }
+
+    /// Returns the [`ModuleSymbols`] registered for the modules with `hash` if any.
+    pub fn get(&self, hash: u64) -> Option<&ModuleSymbols> {
+        self.symbols.get(&hash).map(Arc::as_ref)
+    }
+
+    /// Resolves the [`FuncSymbol`] of the function body `func` if its module has registered symbols.
+    pub fn resolve(&self, func: EngineFunc) -> Option<FuncSymbol> {
+        if self.symbols.is_empty() {
+            return None;
+        }
+        self.modules.iter().find_map(|module| {
+            let position = module.funcs.position(func)?;
+            let symbols = self.symbols.get(&module.hash)?;
+            Some(FuncSymbol {
+                module_hash: module.hash,
+                module: symbols.clone(),
+                index: module.len_imported_funcs + position,
+            })
+        })
+    }
+}
@@ -140,4 +140,13 @@ impl ElementSegmentEntity {
     pub fn drop_items(&mut self) {
         self.items = [].into();
     }
+
+    /// Replaces the items of the [`ElementSegmentEntity`] with `items`.
+    ///
+    /// This is used to undrop element segments when restoring a [`StoreSnapshot`].
+    ///
+    /// [`StoreSnapshot`]: crate::StoreSnapshot
+    pub fn set_items(&mut self, items: Box<[UntypedVal]>) {
+        self.items = items;
+    }
 }
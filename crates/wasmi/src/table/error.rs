@@ -1,5 +1,5 @@
 use super::TableType;
-use crate::core::ValType;
+use crate::{core::ValType, FuncType};
 use core::{fmt, fmt::Display};
 
 /// Errors that may occur upon operating with table entities.
@@ -39,6 +39,15 @@ pub enum TableError {
         other: TableType,
     },
     TooManyTables,
+    /// Occurs when installing a function that does not match the type of the function it replaces.
+    FuncTypeMismatch {
+        /// The index of the table element.
+        index: u32,
+        /// The [`FuncType`] of the replaced function.
+        expected: FuncType,
+        /// The [`FuncType`] of the installed function.
+        actual: FuncType,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -72,11 +81,23 @@ impl Display for TableError {
                 write!(f, "out of bounds access of table elements while copying")
             }
             Self::InvalidSubtype { ty, other } => {
-                write!(f, "table type {ty:?} is not a subtype of {other:?}",)
+                write!(f, "incompatible table type: ")?;
+                ty.fmt_subtype_mismatch(other, f)
             }
             Self::TooManyTables => {
                 write!(f, "too many tables")
             }
+            Self::FuncTypeMismatch {
+                index,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "cannot replace function of type {expected:?} at table element {index} \
+                    with function of type {actual:?}",
+                )
+            }
         }
     }
 }
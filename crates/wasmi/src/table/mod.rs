@@ -9,10 +9,13 @@ use crate::{
     error::EntityGrowError,
     store::{Fuel, FuelError, ResourceLimiterRef},
     value::WithType,
+    Func,
+    FuncRef,
+    FuncType,
     Val,
 };
 use alloc::{vec, vec::Vec};
-use core::cmp::max;
+use core::{cmp::max, fmt, ops::Range};
 
 mod element;
 mod error;
@@ -103,9 +106,14 @@ impl TableType {
     ///
     /// - If the `element` type of `self` does not match the `element` type of `other`.
     /// - If the `minimum` size of `self` is less than or equal to the `minimum` size of `other`.
-    /// - If the `maximum` size of `self` is greater than the `maximum` size of `other`.
-    pub(crate) fn is_subtype_or_err(&self, other: &TableType) -> Result<(), TableError> {
-        match self.is_subtype_of(other) {
+    /// - If the `maximum` size of `self` is greater than the `maximum` size of `other`
+    ///   and `relaxed_limits` is `false`.
+    pub(crate) fn is_subtype_or_err(
+        &self,
+        other: &TableType,
+        relaxed_limits: bool,
+    ) -> Result<(), TableError> {
+        match self.is_subtype_with(other, relaxed_limits) {
             true => Ok(()),
             false => Err(TableError::InvalidSubtype {
                 ty: *self,
@@ -123,18 +131,61 @@ impl TableType {
     /// [import subtyping]:
     /// https://webassembly.github.io/spec/core/valid/types.html#import-subtyping
     pub(crate) fn is_subtype_of(&self, other: &Self) -> bool {
+        self.is_subtype_with(other, false)
+    }
+
+    /// Returns `true` if the [`TableType`] is a subtype of the `other` [`TableType`].
+    ///
+    /// The `maximum` sizes are ignored if `relaxed_limits` is `true`.
+    fn is_subtype_with(&self, other: &Self, relaxed_limits: bool) -> bool {
         if self.matches_element_type(other.element()).is_err() {
             return false;
         }
         if self.minimum() < other.minimum() {
             return false;
         }
+        if relaxed_limits {
+            return true;
+        }
         match (self.maximum(), other.maximum()) {
             (_, None) => true,
             (Some(max), Some(other_max)) => max <= other_max,
             _ => false,
         }
     }
+
+    /// Writes the reason why the [`TableType`] is not a subtype of `other` to `f`.
+    pub(crate) fn fmt_subtype_mismatch(&self, other: &Self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.element() != other.element() {
+            return write!(
+                f,
+                "element type {:?} does not match the required element type {:?}",
+                self.element(),
+                other.element(),
+            );
+        }
+        if self.minimum() < other.minimum() {
+            return write!(
+                f,
+                "minimum of {} elements is less than the required minimum of {} elements",
+                self.minimum(),
+                other.minimum(),
+            );
+        }
+        match (self.maximum(), other.maximum()) {
+            (None, Some(other_max)) => write!(
+                f,
+                "unbounded maximum exceeds the declared maximum of {other_max} elements \
+                (allowed via `Config::relaxed_import_limits`)",
+            ),
+            (Some(max), Some(other_max)) if max > other_max => write!(
+                f,
+                "maximum of {max} elements exceeds the declared maximum of {other_max} elements \
+                (allowed via `Config::relaxed_import_limits`)",
+            ),
+            _ => write!(f, "{self:?} is not a subtype of {other:?}"),
+        }
+    }
 }
 
 /// A Wasm table entity.
@@ -271,8 +322,8 @@ impl TableEntity {
             return notify_limiter(limiter);
         }
         if let Some(fuel) = fuel {
-            match fuel.consume_fuel(|costs| costs.fuel_for_copies(u64::from(delta))) {
-                Ok(_) | Err(FuelError::FuelMeteringDisabled) => {}
+            match fuel.consume_fuel(|costs| costs.fuel_for_table_grow(u64::from(delta))) {
+                Ok(_) | Err(FuelError::FuelMeteringDisabled | FuelError::OverQuota) => {}
                 Err(FuelError::OutOfFuel) => return notify_limiter(limiter),
             }
         }
@@ -316,6 +367,16 @@ impl TableEntity {
         self.set_untyped(index, value.into())
     }
 
+    /// Restores the size and elements of the table to `elements`.
+    ///
+    /// # Note
+    ///
+    /// This bypasses the table limits and the resource limiter since
+    /// `elements` are expected to be a previous state of the table.
+    pub fn restore(&mut self, elements: Vec<UntypedVal>) {
+        self.elements = elements;
+    }
+
     /// Returns the [`UntypedVal`] of the [`Table`] at `index`.
     ///
     /// # Errors
@@ -519,6 +580,34 @@ impl TableEntity {
         dst.fill(val);
         Ok(())
     }
+
+    /// Sets `table[range]` to the values returned by `f` for each index of `range`.
+    ///
+    /// # Note
+    ///
+    /// This is an API for internal use only and exists for efficiency reasons.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds for the [`Table`].
+    pub fn fill_with_untyped(
+        &mut self,
+        range: Range<u32>,
+        mut f: impl FnMut(u32) -> UntypedVal,
+    ) -> Result<(), TableError> {
+        let current = self.size();
+        let elements = self
+            .elements
+            .get_mut(range.start as usize..range.end as usize)
+            .ok_or(TableError::AccessOutOfBounds {
+                current,
+                offset: range.end,
+            })?;
+        for (index, element) in range.zip(elements) {
+            *element = f(index);
+        }
+        Ok(())
+    }
 }
 
 /// A Wasm table reference.
@@ -552,6 +641,20 @@ impl Table {
         Ok(table)
     }
 
+    /// Returns the stable ID of the [`Table`] within its [`Store`](crate::Store).
+    ///
+    /// # Note
+    ///
+    /// IDs are assigned in the order of allocation, starting at 0, and are never reused.
+    /// They are deterministic for the same sequence of operations on a [`Store`](crate::Store).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn id(&self, ctx: impl AsContext) -> u32 {
+        ctx.as_context().store.inner.entity_id(self.as_inner())
+    }
+
     /// Returns the type and limits of the table.
     ///
     /// # Panics
@@ -743,4 +846,173 @@ impl Table {
             .resolve_table_mut(self)
             .fill(dst, val, len, None)
     }
+
+    /// Creates a new `funcref` [`Table`] to the store initialized with `funcs`.
+    ///
+    /// The first elements of the [`Table`] are initialized with `funcs` in order
+    /// and all remaining elements are initialized with `null`.
+    ///
+    /// # Errors
+    ///
+    /// - If the element type of `ty` is not `funcref`.
+    /// - If `funcs` yields more elements than the minimum size of `ty`.
+    pub fn new_with_funcs(
+        mut ctx: impl AsContextMut,
+        ty: TableType,
+        funcs: impl IntoIterator<Item = FuncRef>,
+    ) -> Result<Self, TableError> {
+        let (inner, mut resource_limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        let mut entity =
+            TableEntity::new(ty, Val::FuncRef(FuncRef::null()), &mut resource_limiter)?;
+        let current = entity.size();
+        for (index, func) in funcs.into_iter().enumerate() {
+            let Some(element) = entity.elements.get_mut(index) else {
+                return Err(TableError::AccessOutOfBounds {
+                    current,
+                    offset: current,
+                });
+            };
+            *element = func.into();
+        }
+        Ok(inner.alloc_table(entity))
+    }
+
+    /// Sets `table[range]` to the [`FuncRef`]s returned by `f` for each index of `range`.
+    ///
+    /// This is more efficient than calling [`Table::set`] for each index of `range`
+    /// which makes it suitable to initialize large `funcref` tables.
+    ///
+    /// # Errors
+    ///
+    /// - If the element type of the [`Table`] is not `funcref`.
+    /// - If `range` is out of bounds for the [`Table`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn fill_with(
+        &self,
+        mut ctx: impl AsContextMut,
+        range: Range<u32>,
+        mut f: impl FnMut(u32) -> FuncRef,
+    ) -> Result<(), TableError> {
+        let table = ctx.as_context_mut().store.inner.resolve_table_mut(self);
+        let element = table.ty().element();
+        if element != ValType::FuncRef {
+            return Err(TableError::ElementTypeMismatch {
+                expected: element,
+                actual: ValType::FuncRef,
+            });
+        }
+        table.fill_with_untyped(range, |index| f(index).into())
+    }
+
+    /// Installs `func` at `index` of this `funcref` [`Table`].
+    ///
+    /// Returns the [`Func`] previously installed at `index` if any.
+    ///
+    /// Unlike [`Table::set`] this requires `func` to have the same [`FuncType`] as
+    /// the [`Func`] it replaces. This allows to safely hot-swap functions of a [`Table`]
+    /// that is shared by many instances, e.g. while their executions are suspended,
+    /// since indirect calls through the [`Table`] keep observing the same signatures.
+    ///
+    /// # Errors
+    ///
+    /// - If the element type of the [`Table`] is not `funcref`.
+    /// - If `index` is out of bounds.
+    /// - If `func` does not match the [`FuncType`] of the [`Func`] it replaces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`] or `func`.
+    pub fn install(
+        &self,
+        mut ctx: impl AsContextMut,
+        index: u32,
+        func: Func,
+    ) -> Result<Option<Func>, TableError> {
+        let previous = self.check_install(&ctx, index, &func.ty(&ctx), &[])?;
+        self.set(&mut ctx, index, Val::FuncRef(FuncRef::new(func)))?;
+        Ok(previous)
+    }
+
+    /// Installs all functions of `entries` at their indices of this `funcref` [`Table`].
+    ///
+    /// Either all or none of the `entries` are installed. Entries are installed in
+    /// order and later entries at the same index replace earlier ones.
+    ///
+    /// Read more about the requirements of installed functions in [`Table::install`].
+    ///
+    /// # Errors
+    ///
+    /// If any of the `entries` cannot be installed via [`Table::install`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`] or any of the functions of `entries`.
+    pub fn install_all(
+        &self,
+        mut ctx: impl AsContextMut,
+        entries: impl IntoIterator<Item = (u32, Func)>,
+    ) -> Result<(), TableError> {
+        let mut installed = Vec::new();
+        for (index, func) in entries {
+            let func_type = func.ty(&ctx);
+            self.check_install(&ctx, index, &func_type, &installed)?;
+            installed.push((index, func, func_type));
+        }
+        for (index, func, _) in installed {
+            self.set(&mut ctx, index, Val::FuncRef(FuncRef::new(func)))?;
+        }
+        Ok(())
+    }
+
+    /// Checks if a function of `func_type` can be installed at `index` of this [`Table`].
+    ///
+    /// The functions of `pending` are treated as if they were already installed.
+    ///
+    /// Returns the [`Func`] currently installed at `index` if any.
+    ///
+    /// # Errors
+    ///
+    /// If a function of `func_type` cannot be installed via [`Table::install`].
+    fn check_install(
+        &self,
+        ctx: impl AsContext,
+        index: u32,
+        func_type: &FuncType,
+        pending: &[(u32, Func, FuncType)],
+    ) -> Result<Option<Func>, TableError> {
+        let element = self.ty(&ctx).element();
+        if element != ValType::FuncRef {
+            return Err(TableError::ElementTypeMismatch {
+                expected: element,
+                actual: ValType::FuncRef,
+            });
+        }
+        let current = self
+            .get(&ctx, index)
+            .ok_or_else(|| TableError::AccessOutOfBounds {
+                current: self.size(&ctx),
+                offset: index,
+            })?
+            .funcref()
+            .and_then(FuncRef::func)
+            .copied();
+        let expected = match pending.iter().rev().find(|(i, _, _)| *i == index) {
+            Some((_, _, func_type)) => Some(func_type.clone()),
+            None => current.map(|func| func.ty(&ctx)),
+        };
+        match expected {
+            Some(expected) if expected != *func_type => Err(TableError::FuncTypeMismatch {
+                index,
+                expected,
+                actual: func_type.clone(),
+            }),
+            _ => Ok(current),
+        }
+    }
 }
@@ -0,0 +1,232 @@
+use crate::{
+    core::UntypedVal,
+    engine::Stack,
+    memory::MemoryError,
+    store::{Fuel, StoreInner},
+    Func,
+};
+use alloc::{boxed::Box, vec::Vec};
+
+/// A snapshot of a Wasm execution taken while it was paused.
+///
+/// Checkpoints are taken automatically for [`Store`]s that enabled them via
+/// [`Store::enable_checkpoints`] and allow to rewind the execution to the
+/// point at which they were taken via [`Store::rewind`].
+///
+/// A [`Checkpoint`] captures:
+///
+/// - The value and call stacks of the paused execution.
+/// - The contents of all linear memories of the [`Store`].
+/// - The values of all global variables of the [`Store`].
+/// - The fuel of the [`Store`].
+/// - The number of host calls made so far.
+///
+/// [`Store`]: crate::Store
+/// [`Store::enable_checkpoints`]: crate::Store::enable_checkpoints
+/// [`Store::rewind`]: crate::Store::rewind
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    /// The function that was called from the host to start the execution.
+    func: Func,
+    /// The stacks of the paused execution.
+    stack: Stack,
+    /// The fuel at the time of the checkpoint.
+    fuel: Fuel,
+    /// The data of all linear memories in allocation order.
+    memories: Box<[Box<[u8]>]>,
+    /// The values of all global variables in allocation order.
+    globals: Box<[UntypedVal]>,
+    /// The number of host calls made before the checkpoint.
+    host_calls: usize,
+}
+
+// # Safety
+//
+// `Checkpoint` is not `Sync` because its `Stack` is not `Sync` due to the `InstructionPtr`
+// of its call frames which are raw pointers to `Instruction` buffers owned by the `Engine`.
+//
+// The `Instruction` buffers pointed to are immutable and a `Checkpoint` never offsets the
+// `InstructionPtr` of its `Stack` since it only hands out clones of it for execution.
+//
+// Therefore `Checkpoint` can safely be assumed to be `Sync`.
+unsafe impl Sync for Checkpoint {}
+
+impl Checkpoint {
+    /// Captures a [`Checkpoint`] of the execution of `func` paused with `stack`.
+    pub(crate) fn capture(store: &StoreInner, func: Func, stack: &Stack) -> Self {
+        Self {
+            func,
+            stack: stack.clone(),
+            fuel: *store.fuel(),
+            memories: store.memories_data().map(Box::from).collect(),
+            globals: store.globals_untyped().collect(),
+            host_calls: store.host_call_position(),
+        }
+    }
+
+    /// Restores the state of `store` captured by the [`Checkpoint`].
+    ///
+    /// # Errors
+    ///
+    /// If a linear memory cannot be restored to its checkpointed size.
+    pub(crate) fn restore(&self, store: &mut StoreInner) -> Result<(), MemoryError> {
+        for (index, data) in self.memories.iter().enumerate() {
+            store.restore_memory(index, data)?;
+        }
+        for (index, value) in self.globals.iter().enumerate() {
+            store.restore_global(index, *value);
+        }
+        *store.fuel_mut() = self.fuel;
+        store.replay_host_calls_from(self.host_calls);
+        Ok(())
+    }
+
+    /// Returns the function called from the host that started the checkpointed execution.
+    pub fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the total fuel consumed by the [`Store`] when the [`Checkpoint`] was taken.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn fuel_consumed(&self) -> u64 {
+        self.fuel.consumed()
+    }
+
+    /// Returns the number of host calls made before the [`Checkpoint`] was taken.
+    pub fn host_calls(&self) -> usize {
+        self.host_calls
+    }
+
+    /// Returns the stacks of the paused execution.
+    pub(crate) fn stack(&self) -> &Stack {
+        &self.stack
+    }
+}
+
+/// A step of a rewound Wasm execution observed by the tracer of [`Store::rewind`].
+///
+/// A step is observed whenever the execution enters a new basic block.
+///
+/// [`Store::rewind`]: crate::Store::rewind
+#[derive(Debug)]
+pub struct TraceStep<'a> {
+    /// The store of the traced execution.
+    store: &'a StoreInner,
+    /// The number of call frames of the traced execution.
+    call_depth: usize,
+}
+
+impl<'a> TraceStep<'a> {
+    /// Creates a new [`TraceStep`].
+    pub(crate) fn new(store: &'a StoreInner, call_depth: usize) -> Self {
+        Self { store, call_depth }
+    }
+
+    /// Returns the total fuel consumed by the [`Store`] before the step.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn fuel_consumed(&self) -> u64 {
+        self.store.fuel().consumed()
+    }
+
+    /// Returns the number of nested Wasm calls of the execution at the step.
+    pub fn call_depth(&self) -> usize {
+        self.call_depth
+    }
+
+    /// Returns the data of the `index`-th linear memory of the [`Store`] if any.
+    ///
+    /// Linear memories are indexed in the order of their creation.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn memory_data(&self, index: usize) -> Option<&'a [u8]> {
+        self.store.memories_data().nth(index)
+    }
+}
+
+/// The checkpointing state of a [`Store`].
+///
+/// [`Store`]: crate::Store
+#[derive(Debug)]
+pub(crate) struct TimeTravel {
+    /// The fuel consumed between two checkpoints.
+    interval: u64,
+    /// The total consumed fuel at which the execution is paused next.
+    next_pause: u64,
+    /// The number of nested executions.
+    ///
+    /// Only the outermost execution is paused.
+    depth: usize,
+    /// The function called from the host that started the outermost execution.
+    root: Option<Func>,
+    /// The checkpoints taken so far.
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl TimeTravel {
+    /// Creates a new [`TimeTravel`] that takes a checkpoint every `interval` units of fuel.
+    pub fn new(interval: u64, consumed: u64) -> Self {
+        let interval = interval.max(1);
+        Self {
+            interval,
+            next_pause: consumed.saturating_add(interval),
+            depth: 0,
+            root: None,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Returns the checkpoints taken so far.
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Adds `checkpoint` to the checkpoints taken so far.
+    pub fn push_checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.checkpoints.push(checkpoint);
+    }
+
+    /// Returns the function called from the host that started the outermost execution.
+    pub fn root(&self) -> Option<Func> {
+        self.root
+    }
+
+    /// Sets the function called from the host that started the outermost execution.
+    pub fn set_root(&mut self, func: Func) {
+        self.root = Some(func);
+    }
+
+    /// Returns `true` if the execution shall be paused after consuming `consumed` fuel in total.
+    #[inline]
+    pub fn is_pause_due(&self, consumed: u64) -> bool {
+        self.depth == 1 && consumed >= self.next_pause
+    }
+
+    /// Schedules the next pause of the execution `distance` units of fuel after `consumed`.
+    pub fn schedule_pause(&mut self, consumed: u64, distance: u64) {
+        self.next_pause = consumed.saturating_add(distance);
+    }
+
+    /// Schedules the next checkpoint after `consumed` fuel has been consumed in total.
+    pub fn schedule_checkpoint(&mut self, consumed: u64) {
+        self.schedule_pause(consumed, self.interval);
+    }
+
+    /// Signals that `func` is called from the host.
+    pub fn enter_root_func(&mut self, func: Func) {
+        if self.depth == 0 {
+            self.root = Some(func);
+        }
+    }
+
+    /// Signals that an execution started.
+    pub fn enter_execution(&mut self) {
+        self.depth += 1;
+    }
+
+    /// Signals that an execution stopped.
+    pub fn exit_execution(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
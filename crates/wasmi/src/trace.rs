@@ -0,0 +1,338 @@
+use crate::{engine::EngineFunc, func::FuncEntity, store::StoreInner, Extern};
+use alloc::{collections::BTreeMap, format, string::String, sync::Arc, vec::Vec};
+use core::{fmt::Write as _, time::Duration};
+use std::{
+    io::{self, Write},
+    time::Instant,
+};
+
+/// A function observed by a [`Tracer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum TracedFunc {
+    /// A Wasm function body.
+    Wasm(EngineFunc),
+    /// A host function identified by its position within the store.
+    Host(u32),
+}
+
+/// An event recorded by a [`Tracer`] before its function has been named.
+#[derive(Debug, Copy, Clone)]
+struct RawEvent {
+    /// The kind of the event.
+    kind: TraceEventKind,
+    /// The function of the event.
+    func: TracedFunc,
+    /// The time of the event since the start of the tracing.
+    timestamp: Duration,
+    /// The number of enclosing function calls.
+    depth: usize,
+}
+
+/// Records the function calls of the executions of a [`Store`](crate::Store).
+#[derive(Debug)]
+pub struct Tracer {
+    /// The start of the tracing.
+    start: Instant,
+    /// The recorded events.
+    events: Vec<RawEvent>,
+    /// The currently executing functions.
+    stack: Vec<TracedFunc>,
+}
+
+impl Default for Tracer {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            events: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl Tracer {
+    /// Records an event of `kind` for `func` at `depth`.
+    fn record(&mut self, kind: TraceEventKind, func: TracedFunc, depth: usize) {
+        self.events.push(RawEvent {
+            kind,
+            func,
+            timestamp: self.start.elapsed(),
+            depth,
+        });
+    }
+
+    /// Returns the number of currently executing functions.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Records the entry of the Wasm function body `func`.
+    pub fn enter(&mut self, func: EngineFunc) {
+        let func = TracedFunc::Wasm(func);
+        self.record(TraceEventKind::Enter, func, self.stack.len());
+        self.stack.push(func);
+    }
+
+    /// Records the call of the host function at position `func` within the store.
+    pub fn host_call(&mut self, func: u32) {
+        let func = TracedFunc::Host(func);
+        self.record(TraceEventKind::HostCall, func, self.stack.len());
+        self.stack.push(func);
+    }
+
+    /// Records the exit of the most recently entered Wasm or host function.
+    ///
+    /// Does nothing if no function has been entered since the tracing started.
+    pub fn exit(&mut self) {
+        let Some(func) = self.stack.pop() else {
+            return;
+        };
+        let kind = match func {
+            TracedFunc::Wasm(_) => TraceEventKind::Exit,
+            TracedFunc::Host(_) => TraceEventKind::HostReturn,
+        };
+        self.record(kind, func, self.stack.len());
+    }
+
+    /// Records the exit of all functions entered at or above `depth`.
+    ///
+    /// This is used to close the functions of executions that trapped.
+    pub fn unwind(&mut self, depth: usize) {
+        while self.stack.len() > depth {
+            self.exit();
+        }
+    }
+
+    /// Takes the recorded events and names their functions using the `store`.
+    pub fn take(&mut self, store: &StoreInner) -> Trace {
+        let mut names = BTreeMap::new();
+        for (func, entity) in store.funcs() {
+            let id = store.entity_id(func.as_inner());
+            let (traced, name) = match entity {
+                FuncEntity::Wasm(entity) => (
+                    TracedFunc::Wasm(entity.func_body()),
+                    format!("wasm-func[{id}]"),
+                ),
+                FuncEntity::Host(_) => (TracedFunc::Host(id), format!("host-func[{id}]")),
+            };
+            names.entry(traced).or_insert(name);
+        }
+        for instance in store.instances() {
+            for export in instance.exports() {
+                let name = export.name();
+                let Extern::Func(func) = export.into_extern() else {
+                    continue;
+                };
+                let traced = match store.resolve_func(&func) {
+                    FuncEntity::Wasm(entity) => TracedFunc::Wasm(entity.func_body()),
+                    FuncEntity::Host(_) => TracedFunc::Host(store.entity_id(func.as_inner())),
+                };
+                names.insert(traced, String::from(name));
+            }
+        }
+        for (traced, name) in &mut names {
+            let TracedFunc::Wasm(body) = traced else {
+                continue;
+            };
+            if let Some(symbol) = store.engine().resolve_symbol(*body) {
+                *name = format!("{symbol}");
+            }
+        }
+        let names: BTreeMap<TracedFunc, Arc<str>> = names
+            .into_iter()
+            .map(|(func, name)| (func, Arc::from(name)))
+            .collect();
+        let events = self
+            .events
+            .drain(..)
+            .map(|event| TraceEvent {
+                kind: event.kind,
+                name: names
+                    .get(&event.func)
+                    .cloned()
+                    .unwrap_or_else(|| Arc::from("<unknown>")),
+                timestamp: event.timestamp,
+                depth: event.depth,
+            })
+            .collect();
+        Trace { events }
+    }
+}
+
+/// The kind of a [`TraceEvent`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TraceEventKind {
+    /// A Wasm function has been entered.
+    Enter,
+    /// A Wasm function has been exited, either by returning or by trapping.
+    Exit,
+    /// A host function has been called.
+    HostCall,
+    /// A host function has returned, either successfully or with an error.
+    HostReturn,
+}
+
+impl TraceEventKind {
+    /// Returns `true` if the event starts a function span.
+    fn is_begin(self) -> bool {
+        matches!(self, Self::Enter | Self::HostCall)
+    }
+
+    /// Returns `true` if the event concerns a host function.
+    fn is_host(self) -> bool {
+        matches!(self, Self::HostCall | Self::HostReturn)
+    }
+
+    /// Returns the name of the event in the JSONL format.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Enter => "enter",
+            Self::Exit => "exit",
+            Self::HostCall => "host_call",
+            Self::HostReturn => "host_return",
+        }
+    }
+}
+
+/// An event of a [`Trace`].
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    /// The kind of the event.
+    kind: TraceEventKind,
+    /// The name of the function of the event.
+    name: Arc<str>,
+    /// The time of the event since the start of the tracing.
+    timestamp: Duration,
+    /// The number of enclosing function calls.
+    depth: usize,
+}
+
+impl TraceEvent {
+    /// Returns the [`TraceEventKind`] of the [`TraceEvent`].
+    pub fn kind(&self) -> TraceEventKind {
+        self.kind
+    }
+
+    /// Returns the name of the function of the [`TraceEvent`].
+    ///
+    /// This is the [`FuncSymbol`](crate::FuncSymbol) of Wasm functions of modules with registered
+    /// symbols as in `app!main`, or else the export name of the function if it has been exported
+    /// by any instance. Otherwise the function is named after its position within the
+    /// [`Store`](crate::Store) as in `wasm-func[3]` or `host-func[0]`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the time of the [`TraceEvent`] since the tracing started.
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    /// Returns the number of function calls that enclose the [`TraceEvent`].
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+/// The function calls recorded by [`Store::enable_tracing`].
+///
+/// A [`Trace`] can be exported in the Chrome trace-event format via [`Trace::write_chrome_trace`]
+/// to be opened with viewers such as Perfetto or speedscope, or as JSON lines via
+/// [`Trace::write_jsonl`] for custom processing.
+///
+/// [`Store::enable_tracing`]: crate::Store::enable_tracing
+#[derive(Debug, Default, Clone)]
+pub struct Trace {
+    /// The recorded events in chronological order.
+    events: Vec<TraceEvent>,
+}
+
+impl Trace {
+    /// Returns the [`TraceEvent`]s of the [`Trace`] in chronological order.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Returns the number of [`TraceEvent`]s of the [`Trace`].
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if the [`Trace`] has no [`TraceEvent`]s.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Writes the [`Trace`] to `writer` in the Chrome trace-event JSON format.
+    ///
+    /// Function calls are written as duration events with the `wasm` or `host` category.
+    ///
+    /// # Errors
+    ///
+    /// If writing to `writer` fails.
+    pub fn write_chrome_trace(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(b"{\"traceEvents\":[")?;
+        for (n, event) in self.events.iter().enumerate() {
+            if n != 0 {
+                writer.write_all(b",")?;
+            }
+            let phase = if event.kind.is_begin() { "B" } else { "E" };
+            let category = if event.kind.is_host() { "host" } else { "wasm" };
+            write!(
+                writer,
+                "{{\"name\":{},\"cat\":\"{category}\",\"ph\":\"{phase}\",\"ts\":{},\"pid\":1,\"tid\":1}}",
+                json_string(&event.name),
+                micros(event.timestamp),
+            )?;
+        }
+        writer.write_all(b"],\"displayTimeUnit\":\"ns\"}")
+    }
+
+    /// Writes the [`Trace`] to `writer` as JSON lines with one [`TraceEvent`] per line.
+    ///
+    /// Every line is a JSON object with the fields `ts_ns`, `event`, `name` and `depth`
+    /// where `event` is one of `enter`, `exit`, `host_call` or `host_return`.
+    ///
+    /// # Errors
+    ///
+    /// If writing to `writer` fails.
+    pub fn write_jsonl(&self, mut writer: impl Write) -> io::Result<()> {
+        for event in &self.events {
+            writeln!(
+                writer,
+                "{{\"ts_ns\":{},\"event\":\"{}\",\"name\":{},\"depth\":{}}}",
+                event.timestamp.as_nanos(),
+                event.kind.as_str(),
+                json_string(&event.name),
+                event.depth,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats `duration` as fractional microseconds.
+fn micros(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    format!("{}.{:03}", nanos / 1_000, nanos % 1_000)
+}
+
+/// Returns `value` as a quoted and escaped JSON string.
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
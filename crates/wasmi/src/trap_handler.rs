@@ -0,0 +1,118 @@
+use crate::{core::TrapCode, AsContext, Error, Func, Val};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+/// Converts a [`TrapCode`] into the results of a handled function or `None` to propagate the trap.
+type TrapConverter = Box<dyn Fn(TrapCode) -> Option<Vec<Val>> + Send + Sync>;
+
+/// Converts traps of designated functions into results returned to their callers.
+///
+/// Functions designated via [`TrapHandler::handle`] use a result-code ABI: instead of
+/// unwinding to the host, selected traps of their calls from the host are converted into
+/// results, e.g. an error code, that are returned as if the function returned normally.
+/// This eases porting C code that expects `setjmp`/`longjmp` like recovery from faults.
+///
+/// Set the [`TrapHandler`] of a [`Store`] via [`Store::set_trap_handler`].
+///
+/// # Note
+///
+/// - Only traps of Wasm executions are handled, not errors returned by host functions.
+/// - Side effects of the trapped execution, e.g. writes to linear memory, are not reverted.
+/// - The results are returned to the host caller of the designated function. Traps of
+///   designated functions called from Wasm propagate to their host caller as usual.
+///
+/// [`Store`]: crate::Store
+/// [`Store::set_trap_handler`]: crate::Store::set_trap_handler
+#[derive(Default)]
+pub struct TrapHandler {
+    /// The designated functions and their trap converters.
+    funcs: Vec<(Func, TrapConverter)>,
+}
+
+impl fmt::Debug for TrapHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrapHandler")
+            .field(
+                "funcs",
+                &self.funcs.iter().map(|(func, _)| func).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl TrapHandler {
+    /// Creates a new [`TrapHandler`] without designated functions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Designates `func` to convert its traps via `convert`.
+    ///
+    /// Upon a trap of a call to `func` from the host `convert` is called with the
+    /// [`TrapCode`] and either returns the results of the call or `None` in order
+    /// to propagate the trap.
+    ///
+    /// Replaces the previous converter of `func` if any.
+    ///
+    /// # Note
+    ///
+    /// The call fails with an [`Error`] if the returned results do not match the
+    /// result types of `func`.
+    pub fn handle(
+        &mut self,
+        func: Func,
+        convert: impl Fn(TrapCode) -> Option<Vec<Val>> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.remove(&func);
+        self.funcs.push((func, Box::new(convert)));
+        self
+    }
+
+    /// Removes the designation of `func`.
+    ///
+    /// Returns `true` if `func` was designated.
+    pub fn remove(&mut self, func: &Func) -> bool {
+        let len_funcs = self.funcs.len();
+        self.funcs
+            .retain(|(designated, _)| designated.as_inner() != func.as_inner());
+        self.funcs.len() != len_funcs
+    }
+
+    /// Returns `true` if no functions are designated.
+    pub fn is_empty(&self) -> bool {
+        self.funcs.is_empty()
+    }
+
+    /// Converts the `error` of a call to `func` into its results if possible.
+    ///
+    /// Returns `None` if `func` is not designated, `error` is not a trap or the trap is propagated.
+    ///
+    /// # Errors
+    ///
+    /// If the converted results do not match the result types of `func`.
+    pub(crate) fn convert(
+        &self,
+        ctx: impl AsContext,
+        func: &Func,
+        error: &Error,
+    ) -> Option<Result<Vec<Val>, Error>> {
+        let (_, convert) = self
+            .funcs
+            .iter()
+            .find(|(designated, _)| designated.as_inner() == func.as_inner())?;
+        let results = convert(error.as_trap_code()?)?;
+        let ty = func.ty(ctx);
+        let matches = ty.results().len() == results.len()
+            && ty
+                .results()
+                .iter()
+                .zip(&results)
+                .all(|(ty, result)| *ty == result.ty());
+        if !matches {
+            return Some(Err(Error::new(
+                "results of the trap handler do not match the result types of the function",
+            )));
+        }
+        Some(Ok(results))
+    }
+}
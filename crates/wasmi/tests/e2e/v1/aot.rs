@@ -0,0 +1,213 @@
+//! Tests for the ahead-of-time emission of C code via [`AotEmitter`].
+#![cfg(feature = "aot")]
+
+use std::{fs, process::Command};
+use wasmi::{
+    core::{TrapCode, ValType},
+    AotEmitter,
+    Engine,
+    Func,
+    FuncType,
+    Instance,
+    Module,
+    Store,
+};
+
+const WASM: &str = r#"
+    (module
+        (memory 1)
+        (func (export "gcd") (param i64 i64) (result i64)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i64.eqz (local.get 1)))
+                    (i64.rem_u (local.get 0) (local.get 1))
+                    (local.set 0 (local.get 1))
+                    (local.set 1)
+                    (br $continue)
+                )
+            )
+            (local.get 0)
+        )
+        (func (export "factorial") (param i32) (result i32)
+            (local $acc i32)
+            (local.set $acc (i32.const 1))
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.le_s (local.get 0) (i32.const 1)))
+                    (local.set $acc (i32.mul (local.get $acc) (local.get 0)))
+                    (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func (export "div_s") (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func (export "load") (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] and returns its store and instance.
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[]).unwrap();
+    (store, instance)
+}
+
+/// Emits the C code of the `gcd`, `factorial` and `div_s` exports of [`WASM`].
+fn emit() -> String {
+    let (store, instance) = setup();
+    let mut emitter = AotEmitter::new();
+    for name in ["gcd", "factorial", "div_s"] {
+        let func = instance.get_func(&store, name).unwrap();
+        emitter.emit_func(&store, &func, name).unwrap();
+    }
+    emitter.finish()
+}
+
+#[test]
+fn emits_functions() {
+    let source = emit();
+    assert!(source.contains("#include <stdint.h>"));
+    for name in ["gcd", "factorial", "div_s"] {
+        assert!(source.contains(&format!("int32_t {name}(uint64_t *values) {{")));
+    }
+    assert!(source.contains("goto L"));
+}
+
+#[test]
+fn rejects_unsupported_functions() {
+    let (mut store, instance) = setup();
+    let mut emitter = AotEmitter::new();
+    let load = instance.get_func(&store, "load").unwrap();
+    assert!(emitter.emit_func(&store, &load, "load").is_err());
+    let gcd = instance.get_func(&store, "gcd").unwrap();
+    assert!(emitter
+        .emit_func(&store, &gcd, "not-an-identifier")
+        .is_err());
+    emitter.emit_func(&store, &gcd, "gcd").unwrap();
+    assert!(emitter.emit_func(&store, &gcd, "gcd").is_err());
+    let host = Func::wrap(&mut store, || {});
+    assert!(emitter.emit_func(&store, &host, "host").is_err());
+}
+
+#[test]
+fn trap_codes() {
+    assert_eq!(AotEmitter::trap_code(0), None);
+    assert_eq!(AotEmitter::trap_code(-1), None);
+    assert_eq!(
+        AotEmitter::trap_code(1),
+        Some(TrapCode::UnreachableCodeReached)
+    );
+    assert_eq!(
+        AotEmitter::trap_code(5),
+        Some(TrapCode::IntegerDivisionByZero)
+    );
+    assert_eq!(AotEmitter::trap_code(1000), None);
+}
+
+/// Stands in for a compiled emitted `div_s` function.
+unsafe extern "C" fn div_s(values: *mut u64) -> i32 {
+    let values = unsafe { core::slice::from_raw_parts_mut(values, 2) };
+    let (lhs, rhs) = (values[0] as i32, values[1] as i32);
+    if rhs == 0 {
+        return 5;
+    }
+    values[0] = u64::from(lhs.wrapping_div(rhs) as u32);
+    0
+}
+
+#[test]
+fn host_func() {
+    let (mut store, _) = setup();
+    let ty = FuncType::new([ValType::I32, ValType::I32], [ValType::I32]);
+    let func = unsafe { AotEmitter::host_func(&mut store, ty, div_s) };
+    let func = func.typed::<(i32, i32), i32>(&store).unwrap();
+    assert_eq!(func.call(&mut store, (-9, 2)).unwrap(), -4);
+    let error = func.call(&mut store, (1, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+}
+
+/// The C harness that prints the results of the emitted functions.
+const HARNESS: &str = r#"
+#include <stdio.h>
+int main(void) {
+    uint64_t v[2];
+    int32_t s;
+    v[0] = 1071; v[1] = 462; s = gcd(v);
+    printf("%d %llu\n", s, (unsigned long long)v[0]);
+    v[0] = 10; s = factorial(v);
+    printf("%d %d\n", s, (int32_t)v[0]);
+    v[0] = (uint32_t)-7; v[1] = 2; s = div_s(v);
+    printf("%d %d\n", s, (int32_t)v[0]);
+    v[0] = 1; v[1] = 0; s = div_s(v);
+    printf("%d\n", s);
+    v[0] = 0x80000000u; v[1] = 0xffffffffu; s = div_s(v);
+    printf("%d\n", s);
+    return 0;
+}
+"#;
+
+#[test]
+fn compiled_matches_interpreter() {
+    let dir = std::env::temp_dir().join(format!("wasmi-aot-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("aot.c");
+    let binary = dir.join("aot");
+    fs::write(&source, format!("{}{HARNESS}", emit())).unwrap();
+    let Ok(status) = Command::new("cc")
+        .arg("-std=c99")
+        .arg("-o")
+        .arg(&binary)
+        .arg(&source)
+        .status()
+    else {
+        // Note: skip if there is no C compiler available.
+        return;
+    };
+    assert!(status.success());
+    let output = Command::new(&binary).output().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let (mut store, instance) = setup();
+    let gcd = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "gcd")
+        .unwrap();
+    let factorial = instance
+        .get_typed_func::<i32, i32>(&store, "factorial")
+        .unwrap();
+    let div_s = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "div_s")
+        .unwrap();
+    let status = |trap_code| {
+        (1..)
+            .find(|status| AotEmitter::trap_code(*status) == Some(trap_code))
+            .unwrap()
+    };
+    let expected = format!(
+        "0 {}\n0 {}\n0 {}\n{}\n{}\n",
+        gcd.call(&mut store, (1071, 462)).unwrap(),
+        factorial.call(&mut store, 10).unwrap(),
+        div_s.call(&mut store, (-7, 2)).unwrap(),
+        status(
+            div_s
+                .call(&mut store, (1, 0))
+                .unwrap_err()
+                .as_trap_code()
+                .unwrap()
+        ),
+        status(
+            div_s
+                .call(&mut store, (i32::MIN, -1))
+                .unwrap_err()
+                .as_trap_code()
+                .unwrap()
+        ),
+    );
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
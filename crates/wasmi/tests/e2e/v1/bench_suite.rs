@@ -0,0 +1,60 @@
+//! Tests for the public benchmark suite of [`wasmi::bench`].
+#![cfg(feature = "bench")]
+
+use wasmi::{
+    bench::{self, BenchReport, BenchResult},
+    Config,
+    Engine,
+};
+
+#[test]
+fn benchmarks_run() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    for engine in [Engine::default(), Engine::new(&config)] {
+        let report = bench::run_suite(&engine, 1).unwrap();
+        assert_eq!(report.results().len(), bench::benchmarks().len());
+        for benchmark in bench::benchmarks() {
+            assert!(report.get(benchmark.name()).is_some());
+        }
+    }
+}
+
+#[test]
+fn reports_round_trip() {
+    let report = [
+        BenchResult::new("call/typed/0", 1_000.0),
+        BenchResult::new("dispatch/loop", 25_000.5),
+    ]
+    .into_iter()
+    .collect::<BenchReport>();
+    let text = report.to_text();
+    assert_eq!(BenchReport::from_text(&text).unwrap(), report);
+    let commented = format!("# baseline\n\n{text}");
+    assert_eq!(BenchReport::from_text(&commented).unwrap(), report);
+    assert!(BenchReport::from_text("call/typed/0 fast").is_err());
+}
+
+#[test]
+fn regressions_are_detected() {
+    let baseline = [
+        BenchResult::new("call/typed/0", 1_000.0),
+        BenchResult::new("dispatch/loop", 20_000.0),
+    ]
+    .into_iter()
+    .collect::<BenchReport>();
+    let current = [
+        BenchResult::new("call/typed/0", 1_050.0),
+        BenchResult::new("dispatch/loop", 30_000.0),
+        BenchResult::new("memory/fill", 1.0),
+    ]
+    .into_iter()
+    .collect::<BenchReport>();
+    assert!(current.check_regressions(&baseline, 0.6).is_ok());
+    let error = current
+        .check_regressions(&baseline, 0.1)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("dispatch/loop"));
+    assert!(!error.contains("call/typed/0"));
+}
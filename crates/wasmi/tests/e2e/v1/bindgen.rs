@@ -0,0 +1,188 @@
+//! Tests for the typed bindings generated from WIT via [`wasmi::bindgen!`].
+#![cfg(feature = "bindgen")]
+
+use wasmi::{Engine, Error, Linker, Module, Store};
+
+wasmi::bindgen!({
+    inline: "
+        package example:plugin;
+
+        interface counter {
+            next: func(step: u32) -> u64;
+        }
+
+        interface math {
+            double: func(x: s64) -> s64;
+        }
+
+        world plugin {
+            import counter;
+            import log: func(message: string);
+            import get-name: func() -> string;
+            import is-even: func(n: s32) -> bool;
+
+            export math;
+            /// Greets the host by its name.
+            export greet: func(greeting: string) -> string;
+            export add: func(a: u32, b: u32) -> u32;
+            export checksum: func(data: list<u8>) -> u8;
+            export count: func(times: u32) -> u64;
+            export parity: func(n: s32) -> char;
+        }
+    ",
+});
+
+/// A core Wasm module implementing the `plugin` world via the canonical ABI.
+const WASM: &str = r#"
+    (module
+        (import "example:plugin/counter" "next" (func $next (param i32) (result i64)))
+        (import "$root" "log" (func $log (param i32 i32)))
+        (import "$root" "get-name" (func $get_name (param i32)))
+        (import "$root" "is-even" (func $is_even (param i32) (result i32)))
+        (memory (export "memory") 1)
+        (global $next_alloc (mut i32) (i32.const 1024))
+        (global $posts (export "posts") (mut i32) (i32.const 0))
+        (func $cabi_realloc (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+            (global.get $next_alloc)
+            (global.set $next_alloc (i32.add (global.get $next_alloc) (local.get 3)))
+        )
+        (func (export "greet") (param $ptr i32) (param $len i32) (result i32)
+            (local $name_ptr i32)
+            (local $name_len i32)
+            (local $dst i32)
+            (call $log (local.get $ptr) (local.get $len))
+            (call $get_name (i32.const 16))
+            (local.set $name_ptr (i32.load (i32.const 16)))
+            (local.set $name_len (i32.load (i32.const 20)))
+            (local.set $dst
+                (call $cabi_realloc
+                    (i32.const 0) (i32.const 0) (i32.const 1)
+                    (i32.add (local.get $len) (local.get $name_len))
+                )
+            )
+            (memory.copy (local.get $dst) (local.get $ptr) (local.get $len))
+            (memory.copy
+                (i32.add (local.get $dst) (local.get $len))
+                (local.get $name_ptr)
+                (local.get $name_len)
+            )
+            (i32.store (i32.const 32) (local.get $dst))
+            (i32.store (i32.const 36) (i32.add (local.get $len) (local.get $name_len)))
+            (i32.const 32)
+        )
+        (func (export "cabi_post_greet") (param i32)
+            (global.set $posts (i32.add (global.get $posts) (i32.const 1)))
+        )
+        (func (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func (export "checksum") (param $ptr i32) (param $len i32) (result i32)
+            (local $sum i32)
+            (block $done
+                (loop $continue
+                    (br_if $done (i32.eqz (local.get $len)))
+                    (local.set $sum
+                        (i32.add (local.get $sum) (i32.load8_u (local.get $ptr)))
+                    )
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+                    (local.set $len (i32.sub (local.get $len) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "count") (param $times i32) (result i64)
+            (local $last i64)
+            (block $done
+                (loop $continue
+                    (br_if $done (i32.eqz (local.get $times)))
+                    (local.set $last (call $next (i32.const 2)))
+                    (local.set $times (i32.sub (local.get $times) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $last)
+        )
+        (func (export "parity") (param i32) (result i32)
+            (select (i32.const 0x65) (i32.const 0x6F) (call $is_even (local.get 0)))
+        )
+        (func (export "example:plugin/math#double") (param i64) (result i64)
+            (i64.add (local.get 0) (local.get 0))
+        )
+    )
+"#;
+
+/// The host state implementing the imports of the `plugin` world.
+#[derive(Default)]
+struct Host {
+    logs: Vec<String>,
+    counter: u64,
+}
+
+impl PluginImports for Host {
+    fn counter_next(&mut self, step: u32) -> Result<u64, Error> {
+        self.counter += u64::from(step);
+        if self.counter > 100 {
+            return Err(Error::new("counter overflow"));
+        }
+        Ok(self.counter)
+    }
+
+    fn log(&mut self, message: String) -> Result<(), Error> {
+        self.logs.push(message);
+        Ok(())
+    }
+
+    fn get_name(&mut self) -> Result<String, Error> {
+        Ok(String::from("Wasmi"))
+    }
+
+    fn is_even(&mut self, n: i32) -> Result<bool, Error> {
+        Ok(n % 2 == 0)
+    }
+}
+
+/// Instantiates [`WASM`] with the host imports of the `plugin` world.
+fn instantiate() -> (Store<Host>, Plugin, wasmi::Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, Host::default());
+    let mut linker = <Linker<Host>>::new(&engine);
+    Plugin::add_to_linker(&mut linker, |host| host).unwrap();
+    let (plugin, instance) = Plugin::instantiate(&mut store, &linker, &module).unwrap();
+    (store, plugin, instance)
+}
+
+#[test]
+fn exports() {
+    let (mut store, plugin, instance) = instantiate();
+    assert_eq!(plugin.call_add(&mut store, 1, u32::MAX).unwrap(), 0);
+    assert_eq!(plugin.call_checksum(&mut store, &[1, 2, 255]).unwrap(), 2);
+    assert_eq!(plugin.call_math_double(&mut store, -21).unwrap(), -42);
+    let greeting = plugin.call_greet(&mut store, "Hello, ").unwrap();
+    assert_eq!(greeting, "Hello, Wasmi");
+    let posts = instance.get_global(&store, "posts").unwrap().get(&store);
+    assert_eq!(posts.i32(), Some(1));
+}
+
+#[test]
+fn imports() {
+    let (mut store, plugin, _instance) = instantiate();
+    plugin.call_greet(&mut store, "Hi ").unwrap();
+    assert_eq!(store.data().logs, ["Hi "]);
+    assert_eq!(plugin.call_count(&mut store, 3).unwrap(), 6);
+    assert_eq!(plugin.call_parity(&mut store, 4).unwrap(), 'e');
+    assert_eq!(plugin.call_parity(&mut store, 7).unwrap(), 'o');
+    // Errors of host imports are propagated as traps.
+    let error = plugin.call_count(&mut store, 100).unwrap_err();
+    assert!(error.to_string().contains("counter overflow"));
+}
+
+#[test]
+fn missing_exports() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, r#"(module (memory (export "memory") 1))"#).unwrap();
+    let mut store = Store::new(&engine, Host::default());
+    let linker = <Linker<Host>>::new(&engine);
+    assert!(Plugin::instantiate(&mut store, &linker, &module).is_err());
+}
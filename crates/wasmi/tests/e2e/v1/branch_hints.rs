@@ -0,0 +1,104 @@
+//! Tests for the Wasm `branch-hinting` proposal.
+
+use wasmi::{CompilationMode, Config, Engine, Linker, Module, Store};
+
+/// A Wasm module with branch hints for conditional branches carrying values.
+///
+/// - `$unlikely` has an unlikely taken `br_if` and a likely taken `if`.
+/// - `$likely` has a likely taken `br_if`.
+const WAT: &str = r#"
+    (module
+        (func $unlikely (export "unlikely") (param $x i32) (param $y i32) (result i32)
+            (block $exit (result i32)
+                (local.get $y)
+                (local.get $x)
+                (@metadata.code.branch_hint "\00")
+                (br_if $exit)
+                (drop)
+                (@metadata.code.branch_hint "\01")
+                (if (result i32) (local.get $x)
+                    (then (i32.const 10))
+                    (else (i32.const 20))
+                )
+            )
+        )
+        (func $likely (export "likely") (param $x i32) (param $y i32) (result i32)
+            (block $exit (result i32)
+                (local.get $y)
+                (local.get $x)
+                (@metadata.code.branch_hint "\01")
+                (br_if $exit)
+                (drop)
+                (i32.const 30)
+            )
+        )
+    )
+"#;
+
+/// Calls the exported `unlikely` and `likely` functions with all relevant inputs.
+fn assert_results(config: &Config) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WAT).unwrap();
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(1_000).unwrap_or_default();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let unlikely = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "unlikely")
+        .unwrap();
+    let likely = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "likely")
+        .unwrap();
+    assert_eq!(unlikely.call(&mut store, (0, 5)).unwrap(), 20);
+    assert_eq!(unlikely.call(&mut store, (1, 5)).unwrap(), 5);
+    assert_eq!(likely.call(&mut store, (0, 5)).unwrap(), 30);
+    assert_eq!(likely.call(&mut store, (1, 5)).unwrap(), 5);
+}
+
+#[test]
+fn branch_hints_are_parsed() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WAT).unwrap();
+    let unlikely = module.branch_hints(0);
+    assert_eq!(unlikely.len(), 2);
+    assert!(unlikely[0].offset() < unlikely[1].offset());
+    assert!(!unlikely[0].is_likely_taken());
+    assert!(unlikely[1].is_likely_taken());
+    let likely = module.branch_hints(1);
+    assert_eq!(likely.len(), 1);
+    assert!(likely[0].is_likely_taken());
+    assert!(module.branch_hints(2).is_empty());
+}
+
+#[test]
+fn branch_hints_are_ignored() {
+    let mut config = Config::default();
+    config.ignore_custom_sections(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WAT).unwrap();
+    assert!(module.branch_hints(0).is_empty());
+    assert!(module.branch_hints(1).is_empty());
+    assert_results(&config);
+}
+
+#[test]
+fn branch_hints_execute() {
+    assert_results(&Config::default());
+}
+
+#[test]
+fn branch_hints_execute_with_fuel() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    assert_results(&config);
+}
+
+#[test]
+fn branch_hints_execute_lazy() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    assert_results(&config);
+}
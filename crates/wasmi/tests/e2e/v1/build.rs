@@ -0,0 +1,105 @@
+//! Tests for the programmatic construction of Wasm binaries via [`wasmi::build`].
+#![cfg(feature = "build")]
+
+use wasmi::{
+    build::{Code, ModuleBuilder},
+    core::ValType,
+    Caller,
+    Engine,
+    Linker,
+    Module,
+    Store,
+};
+
+#[test]
+fn empty_module() {
+    let wasm = ModuleBuilder::new().finish();
+    assert_eq!(wasm, b"\0asm\x01\0\0\0");
+    Module::new(&Engine::default(), &wasm[..]).unwrap();
+}
+
+#[test]
+fn deduplicates_types() {
+    let mut builder = ModuleBuilder::new();
+    let a = builder.ty(&[ValType::I32], &[]);
+    let b = builder.ty(&[], &[ValType::I32]);
+    assert_eq!(builder.ty(&[ValType::I32], &[]), a);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn imports_locals_and_memory() {
+    let mut builder = ModuleBuilder::new();
+    let log_ty = builder.ty(&[ValType::I64], &[]);
+    let run_ty = builder.ty(&[ValType::I32], &[ValType::I64]);
+    let log = builder.import_func("env", "log", log_ty);
+    let memory = builder.memory(1, Some(2));
+    let run = builder.func(
+        run_ty,
+        &[ValType::I64, ValType::I64, ValType::F64],
+        Code::new()
+            // local.set 1 (i64.const -1_000_000)
+            .op(0x42)
+            .i64(-1_000_000)
+            .op(0x21)
+            .u32(1)
+            // i64.store (local.get 0) (local.get 1)
+            .op(0x20)
+            .u32(0)
+            .op(0x20)
+            .u32(1)
+            .op(0x37)
+            .u32(3)
+            .u32(0)
+            // call $log (i64.load (local.get 0))
+            .op(0x20)
+            .u32(0)
+            .op(0x29)
+            .u32(3)
+            .u32(0)
+            .op(0x10)
+            .u32(log)
+            // f64.const 1.5 drop
+            .op(0x44)
+            .f64(1.5)
+            .op(0x1A)
+            // i64.extend_i32_s (i32.const 300)
+            .op(0x41)
+            .i32(300)
+            .op(0xAC),
+    );
+    assert_eq!(run, 1);
+    builder
+        .export_func("run", run)
+        .export_memory("memory", memory);
+    let wasm = builder.finish();
+
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, Vec::new());
+    let mut linker = <Linker<Vec<i64>>>::new(&engine);
+    linker
+        .func_wrap("env", "log", |mut caller: Caller<Vec<i64>>, value: i64| {
+            caller.data_mut().push(value);
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i64>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, 8).unwrap(), 300);
+    assert_eq!(store.data(), &[-1_000_000]);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(memory.ty(&store).maximum(), Some(2));
+}
+
+#[test]
+#[should_panic]
+fn import_after_func() {
+    let mut builder = ModuleBuilder::new();
+    let ty = builder.ty(&[], &[]);
+    builder.func(ty, &[], &Code::new());
+    builder.import_func("env", "late", ty);
+}
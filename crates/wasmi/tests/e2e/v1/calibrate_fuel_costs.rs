@@ -0,0 +1,49 @@
+//! Tests for the fuel cost calibration via [`Engine::calibrate_fuel_costs`].
+#![cfg(feature = "calibrate")]
+
+use wasmi::{Config, Engine};
+
+#[test]
+fn calibrate_fuel_costs() {
+    let engine = Engine::default();
+    let calibration = engine.calibrate_fuel_costs().unwrap();
+    let names = calibration
+        .costs()
+        .iter()
+        .map(|cost| cost.name())
+        .collect::<Vec<_>>();
+    for name in ["i32.add", "i64.div_s", "call", "f64.sqrt", "memory.fill"] {
+        assert!(names.contains(&name), "missing {name}");
+    }
+    assert_eq!(calibration.get("i32.add").unwrap().fuel(), 1);
+    assert!(calibration.costs().iter().all(|cost| cost.fuel() >= 1));
+    assert!(calibration.costs().iter().all(|cost| cost.nanos() >= 0.0));
+    assert!(calibration.get("i32.sub").is_none());
+    let table = calibration.to_string();
+    assert!(table.starts_with("operation"));
+    assert_eq!(table.lines().count(), names.len() + 1);
+
+    let fuel_costs = calibration.suggested_fuel_costs();
+    assert_eq!(fuel_costs.base(), 1);
+    assert_eq!(
+        fuel_costs.bytes_per_fuel().get(),
+        calibration.get("memory.fill").unwrap().fuel()
+    );
+    let mut config = Config::default();
+    config.set_fuel_costs(fuel_costs);
+    assert_eq!(config.get_fuel_costs(), &fuel_costs);
+}
+
+#[test]
+fn calibrate_with_config() {
+    let mut config = Config::default();
+    config
+        .floats(false)
+        .wasm_bulk_memory(false)
+        .consume_fuel(true);
+    let engine = Engine::new(&config);
+    let calibration = engine.calibrate_fuel_costs().unwrap();
+    assert!(calibration.get("i32.add").is_some());
+    assert!(calibration.get("f64.add").is_none());
+    assert!(calibration.get("memory.fill").is_none());
+}
@@ -0,0 +1,78 @@
+//! Tests for the static analysis of modules via [`Module::call_graph`].
+
+use wasmi::{core::ValType, CallTarget, CompilationMode, Config, Engine, FuncType, Module};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "log" (func $log (param i32)))
+        (table 2 funcref)
+        (type $unary (func (param i32) (result i32)))
+        (type $nullary (func))
+        (func $double (param i32) (result i32)
+            (i32.add (local.get 0) (local.get 0))
+        )
+        (func $run (param i32) (result i32)
+            (call $log (local.get 0))
+            (call $log (local.get 0))
+            (call_indirect (type $nullary) (i32.const 1))
+            (call_indirect (type $unary) (call $double (local.get 0)) (i32.const 0))
+        )
+        (func $loop (param i32) (result i32)
+            (return_call $run (local.get 0))
+        )
+    )
+"#;
+
+fn call_graph(mode: CompilationMode) {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let graph = module.call_graph().unwrap();
+    let unary = FuncType::new([ValType::I32], [ValType::I32]);
+    let nullary = FuncType::new([], []);
+    // Function indices: 0 = $log, 1 = $double, 2 = $run, 3 = $loop
+    assert_eq!(graph.callees(1).count(), 0);
+    let callees = graph.callees(2).cloned().collect::<Vec<_>>();
+    assert_eq!(callees.len(), 4);
+    for expected in [
+        CallTarget::Func(0),
+        CallTarget::Func(1),
+        CallTarget::Indirect(nullary),
+        CallTarget::Indirect(unary),
+    ] {
+        assert!(callees.contains(&expected), "missing callee: {expected:?}");
+    }
+    assert_eq!(graph.indirect_call_types(2).count(), 2);
+    assert_eq!(graph.indirect_call_types(3).count(), 0);
+    let tail_calls = graph
+        .edges()
+        .iter()
+        .filter(|edge| edge.is_tail_call())
+        .collect::<Vec<_>>();
+    assert_eq!(tail_calls.len(), 1);
+    assert_eq!(tail_calls[0].caller(), 3);
+    assert_eq!(tail_calls[0].target(), &CallTarget::Func(2));
+    // Histograms are only available for internal functions.
+    assert!(graph.histogram(0).is_none());
+    assert!(graph.histogram(4).is_none());
+    let double = graph.histogram(1).unwrap();
+    assert_eq!(double.get("I32Add"), 1);
+    assert_eq!(double.get("I32Sub"), 0);
+    assert_eq!(
+        double.total(),
+        double.iter().map(|(_, count)| count).sum::<u32>()
+    );
+    let run = graph.histogram(2).unwrap();
+    assert_eq!(run.get("CallImported"), 2);
+}
+
+#[test]
+fn call_graph_eager() {
+    call_graph(CompilationMode::Eager);
+}
+
+#[test]
+fn call_graph_lazy() {
+    call_graph(CompilationMode::Lazy);
+}
@@ -0,0 +1,138 @@
+//! Tests for executing independent guest calls in parallel via [`Engine::call_parallel`].
+
+use wasmi::{
+    core::TrapCode,
+    Config,
+    Engine,
+    Linker,
+    Module,
+    ParallelCall,
+    ParallelPool,
+    Store,
+    Val,
+};
+
+const WASM: &str = r#"
+    (module
+        (func (export "sum") (param $n i64) (result i64)
+            (local $sum i64)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i64.eqz (local.get $n)))
+                    (local.set $sum (i64.add (local.get $sum) (local.get $n)))
+                    (local.set $n (i64.sub (local.get $n) (i64.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "div") (param i32 i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+fn call(engine: &Engine, module: &Module, name: &str, params: &[Val]) -> ParallelCall<usize> {
+    let mut store = Store::new(engine, params.len());
+    let instance = Linker::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, name).unwrap();
+    ParallelCall::new(store, func, params)
+}
+
+#[test]
+fn call_parallel_works() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let calls = (0..32_i64)
+        .map(|n| call(&engine, &module, "sum", &[Val::I64(n * 100)]))
+        .collect::<Vec<_>>();
+    let results = engine.call_parallel(calls);
+    assert_eq!(results.len(), 32);
+    assert!(results.is_ok());
+    let results = results.into_results().unwrap();
+    for (n, results) in results.iter().enumerate() {
+        let n = n as i64 * 100;
+        assert_eq!(results[0].i64(), Some(n * (n + 1) / 2));
+    }
+}
+
+#[test]
+fn call_parallel_in_reuses_pool() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let pool = ParallelPool::new(4);
+    assert_eq!(pool.threads(), 4);
+    for round in 0..8_i64 {
+        let calls = (0..16_i64)
+            .map(|n| call(&engine, &module, "sum", &[Val::I64(round + n)]))
+            .collect::<Vec<_>>();
+        let results = engine
+            .call_parallel_in(&pool, calls)
+            .into_results()
+            .unwrap();
+        for (n, results) in results.iter().enumerate() {
+            let n = round + n as i64;
+            assert_eq!(results[0].i64(), Some(n * (n + 1) / 2));
+        }
+    }
+    // A pool with a single thread executes all calls on the calling thread.
+    let pool = ParallelPool::new(1);
+    let calls = [call(&engine, &module, "sum", &[Val::I64(10)])];
+    let results = engine
+        .call_parallel_in(&pool, calls)
+        .into_results()
+        .unwrap();
+    assert_eq!(results[0][0].i64(), Some(55));
+}
+
+#[test]
+fn call_parallel_reports_failures() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let calls = vec![
+        call(&engine, &module, "div", &[Val::I32(9), Val::I32(3)]).fuel(1_000),
+        call(&engine, &module, "div", &[Val::I32(9), Val::I32(0)]).fuel(1_000),
+        call(&engine, &module, "sum", &[Val::I64(1_000)]).fuel(10),
+        call(&engine, &module, "sum", &[Val::I64(10)]).fuel(1_000),
+    ];
+    let results = engine.call_parallel(calls);
+    assert!(!results.is_ok());
+    let failures = results
+        .failures()
+        .map(|(index, error)| (index, error.as_trap_code()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        failures,
+        [
+            (1, Some(TrapCode::IntegerDivisionByZero)),
+            (2, Some(TrapCode::OutOfFuel)),
+        ]
+    );
+    // The stores of all calls are returned.
+    let outcomes = results.outcomes();
+    assert_eq!(outcomes[0].result().unwrap()[0].i32(), Some(3));
+    assert_eq!(*outcomes[0].store().data(), 2);
+    assert!(outcomes[3].store().get_fuel().unwrap() < 1_000);
+    let error = results.into_results().unwrap_err().to_string();
+    assert!(error.starts_with("2 of 4 parallel calls failed:"));
+}
+
+#[test]
+fn call_parallel_rejects_foreign_stores() {
+    let engine = Engine::default();
+    let other = Engine::default();
+    let module = Module::new(&other, WASM).unwrap();
+    let calls = [call(&other, &module, "sum", &[Val::I64(1)])];
+    let results = engine.call_parallel(calls);
+    assert_eq!(results.failures().count(), 1);
+    // Calls with fuel limits fail if fuel metering is disabled.
+    let module = Module::new(&engine, WASM).unwrap();
+    let calls = [call(&engine, &module, "sum", &[Val::I64(1)]).fuel(10)];
+    assert!(engine.call_parallel(calls).into_results().is_err());
+}
@@ -0,0 +1,55 @@
+//! Tests for the `compact-dispatch` crate feature.
+#![cfg(feature = "compact-dispatch")]
+
+use wasmi::{Engine, Linker, Module, Store};
+
+#[test]
+fn compact_dispatch_executes_general_forms() {
+    let engine = Engine::default();
+    let wasm = r#"
+        (module
+            (memory 1)
+            (func (export "f") (param i32 i64) (result i64)
+                ;; store and load via constant addresses
+                (i32.store8 (i32.const 10) (i32.const 7))
+                (i64.store offset=16 (i32.const 0) (local.get 1))
+                ;; store and load via small offsets
+                (i32.store16 offset=40 (local.get 0) (i32.const -1))
+                (i64.add
+                    (i64.add
+                        (i64.extend_i32_u (i32.load8_u (i32.const 10)))
+                        (i64.load offset=16 (i32.const 0))
+                    )
+                    (i64.extend_i32_s
+                        (i32.add
+                            (i32.load16_s offset=40 (local.get 0))
+                            ;; binary and comparison with small immediates
+                            (i32.add
+                                (i32.mul (local.get 0) (i32.const 3))
+                                (i32.lt_s (local.get 0) (i32.const 100))
+                            )
+                        )
+                    )
+                )
+            )
+            (func (export "div") (param i32) (result i32)
+                (i32.div_s (i32.const 100) (local.get 0))
+            )
+        )
+    "#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let f = instance
+        .get_typed_func::<(i32, i64), i64>(&store, "f")
+        .unwrap();
+    // 7 + 1000 + (-1 + 20 * 3 + 1)
+    assert_eq!(f.call(&mut store, (20, 1000)).unwrap(), 1067);
+    let div = instance.get_typed_func::<i32, i32>(&store, "div").unwrap();
+    assert_eq!(div.call(&mut store, 7).unwrap(), 14);
+    assert!(div.call(&mut store, 0).is_err());
+}
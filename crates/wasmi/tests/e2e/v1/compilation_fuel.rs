@@ -0,0 +1,83 @@
+//! Tests for metering Wasm module translation via [`Config::compilation_fuel`].
+
+use wasmi::{
+    errors::{EnforcedLimitsError, ErrorKind},
+    CompilationMode,
+    Config,
+    Engine,
+    Module,
+};
+
+/// Parses `wat` into a Wasm module using an [`Engine`] with `fuel` compilation fuel.
+fn compile(wat: &str, fuel: Option<u64>, mode: CompilationMode) -> Result<Module, wasmi::Error> {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    if let Some(fuel) = fuel {
+        config.compilation_fuel(fuel);
+    }
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(&engine, &wasm[..])
+}
+
+fn assert_out_of_compilation_fuel(error: wasmi::Error, expected: u64) {
+    match error.kind() {
+        ErrorKind::Limits(EnforcedLimitsError::OutOfCompilationFuel { limit }) => {
+            assert_eq!(*limit, expected)
+        }
+        _ => panic!("expected out of compilation fuel error but found: {error}"),
+    }
+}
+
+#[test]
+fn compilation_fuel_limits_function_bodies() {
+    let wat = r#"
+        (module
+            (func (export "add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+    "#;
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        compile(wat, None, mode).unwrap();
+        compile(wat, Some(1_000), mode).unwrap();
+        assert_out_of_compilation_fuel(compile(wat, Some(2), mode).unwrap_err(), 2);
+    }
+}
+
+#[test]
+fn compilation_fuel_is_shared_by_all_functions() {
+    // Each function body consists of 3 bytes: the local declarations count
+    // as well as the `nop` and `end` instructions.
+    let wat = r#"
+        (module
+            (func (nop))
+            (func (nop))
+            (func (nop))
+        )
+    "#;
+    compile(wat, Some(9), CompilationMode::Eager).unwrap();
+    assert_out_of_compilation_fuel(
+        compile(wat, Some(8), CompilationMode::Eager).unwrap_err(),
+        8,
+    );
+}
+
+#[test]
+fn compilation_fuel_counts_local_variables() {
+    // Local variables consume compilation fuel in addition to the bytes of their declarations.
+    let wat = r#"
+        (module
+            (func (local i64 i64 i64 i64 i64 i64 i64 i64))
+        )
+    "#;
+    compile(wat, Some(100), CompilationMode::Eager).unwrap();
+    assert_out_of_compilation_fuel(
+        compile(wat, Some(8), CompilationMode::Eager).unwrap_err(),
+        8,
+    );
+}
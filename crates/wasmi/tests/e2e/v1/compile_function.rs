@@ -0,0 +1,137 @@
+//! Tests for compiling standalone functions via [`Engine::compile_function`].
+
+use wasmi::{
+    core::ValType,
+    Engine,
+    ExternType,
+    FuncType,
+    Global,
+    GlobalType,
+    Linker,
+    Memory,
+    MemoryType,
+    Mutability,
+    Store,
+    Val,
+};
+
+/// Wasm opcodes used by the function bodies of the tests.
+const LOCAL_GET: u8 = 0x20;
+const GLOBAL_GET: u8 = 0x23;
+const CALL: u8 = 0x10;
+const I32_LOAD: u8 = 0x28;
+const I32_ADD: u8 = 0x6A;
+const I32_SUB: u8 = 0x6B;
+const I32_EQZ: u8 = 0x45;
+const IF_I32: [u8; 2] = [0x04, 0x7F];
+const ELSE: u8 = 0x05;
+const END: u8 = 0x0B;
+const I32_CONST: u8 = 0x41;
+
+fn i32_type(len_params: usize) -> FuncType {
+    FuncType::new(vec![ValType::I32; len_params], [ValType::I32])
+}
+
+#[test]
+fn compile_function_without_imports() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    // No locals: `local.get 0; local.get 1; i32.add; end`
+    let body = [0x00, LOCAL_GET, 0, LOCAL_GET, 1, I32_ADD, END];
+    let add = engine
+        .compile_function(&mut store, &linker, &[], &i32_type(2), &body)
+        .unwrap();
+    let add = add.typed::<(i32, i32), i32>(&store).unwrap();
+    assert_eq!(add.call(&mut store, (20, 22)).unwrap(), 42);
+}
+
+#[test]
+fn compile_function_with_imports() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    memory.write(&mut store, 8, &100_i32.to_le_bytes()).unwrap();
+    let global = Global::new(&mut store, Val::I32(1000), Mutability::Const);
+    linker.define("env", "memory", memory).unwrap();
+    linker.define("env", "offset", global).unwrap();
+    linker
+        .func_wrap("env", "double", |value: i32| 2 * value)
+        .unwrap();
+    let imports = [
+        (
+            "env",
+            "memory",
+            ExternType::from(MemoryType::new(1, None).unwrap()),
+        ),
+        ("env", "double", ExternType::from(i32_type(1))),
+        (
+            "env",
+            "offset",
+            ExternType::from(GlobalType::new(ValType::I32, Mutability::Const)),
+        ),
+    ];
+    // `double(local.get 0) + i32.load(8) + global.get 0`
+    #[rustfmt::skip]
+    let body = [
+        0x00,
+        LOCAL_GET, 0, CALL, 0,
+        I32_CONST, 8, I32_LOAD, 2, 0,
+        I32_ADD,
+        GLOBAL_GET, 0,
+        I32_ADD,
+        END,
+    ];
+    let func = engine
+        .compile_function(&mut store, &linker, &imports, &i32_type(1), &body)
+        .unwrap();
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(func.call(&mut store, 5).unwrap(), 1110);
+}
+
+#[test]
+fn compile_recursive_function() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "inc", |value: i32| value + 1)
+        .unwrap();
+    let imports = [("env", "inc", ExternType::from(i32_type(1)))];
+    // `if (local.get 0 == 0) { 0 } else { inc(self(local.get 0 - 1)) }`
+    #[rustfmt::skip]
+    let body = [
+        0x00,
+        LOCAL_GET, 0, I32_EQZ,
+        IF_I32[0], IF_I32[1],
+            I32_CONST, 0,
+        ELSE,
+            LOCAL_GET, 0, I32_CONST, 1, I32_SUB, CALL, 1, CALL, 0,
+        END,
+        END,
+    ];
+    let count = engine
+        .compile_function(&mut store, &linker, &imports, &i32_type(1), &body)
+        .unwrap();
+    let count = count.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(count.call(&mut store, 10).unwrap(), 10);
+}
+
+#[test]
+fn compile_function_errors() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    // Invalid: the function returns nothing.
+    let body = [0x00, END];
+    assert!(engine
+        .compile_function(&mut store, &linker, &[], &i32_type(0), &body)
+        .is_err());
+    // Invalid: the import is not defined by the linker.
+    let imports = [("env", "missing", ExternType::from(i32_type(1)))];
+    let body = [0x00, LOCAL_GET, 0, END];
+    assert!(engine
+        .compile_function(&mut store, &linker, &imports, &i32_type(1), &body)
+        .is_err());
+}
@@ -0,0 +1,120 @@
+//! Tests for computed global variables via [`Linker::global_computed`] and [`Global::new_computed`].
+
+use std::sync::{
+    atomic::{AtomicI32, AtomicI64, Ordering},
+    Arc,
+};
+use wasmi::{
+    core::ValType,
+    errors::GlobalError,
+    Engine,
+    Extern,
+    Global,
+    Instance,
+    Linker,
+    Module,
+    Mutability,
+    Store,
+    Val,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "epoch" (global $epoch i64))
+        (import "env" "quota" (global $quota i32))
+        (func (export "epoch") (result i64)
+            (global.get $epoch)
+        )
+        (func (export "quota") (result i32)
+            (global.get $quota)
+        )
+    )
+"#;
+
+#[test]
+fn linker_global_computed() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let epoch = Arc::new(AtomicI64::new(1));
+    let quota = Arc::new(AtomicI32::new(10));
+    let mut linker = <Linker<()>>::new(&engine);
+    let epoch2 = epoch.clone();
+    let quota2 = quota.clone();
+    linker
+        .global_computed("env", "epoch", move || epoch2.load(Ordering::SeqCst))
+        .unwrap()
+        .global_computed("env", "quota", move || quota2.load(Ordering::SeqCst))
+        .unwrap();
+    let mut store = Store::new(&engine, ());
+    assert!(linker.get(&store, "env", "epoch").is_none());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let get_epoch = instance.get_typed_func::<(), i64>(&store, "epoch").unwrap();
+    let get_quota = instance.get_typed_func::<(), i32>(&store, "quota").unwrap();
+    assert_eq!(get_epoch.call(&mut store, ()).unwrap(), 1);
+    assert_eq!(get_quota.call(&mut store, ()).unwrap(), 10);
+    epoch.store(42, Ordering::SeqCst);
+    quota.fetch_sub(3, Ordering::SeqCst);
+    assert_eq!(get_epoch.call(&mut store, ()).unwrap(), 42);
+    assert_eq!(get_quota.call(&mut store, ()).unwrap(), 7);
+
+    // Note: the same definition can be used to instantiate in multiple stores.
+    let mut store2 = Store::new(&engine, ());
+    let instance2 = linker
+        .instantiate(&mut store2, &module)
+        .unwrap()
+        .start(&mut store2)
+        .unwrap();
+    let get_epoch2 = instance2
+        .get_typed_func::<(), i64>(&store2, "epoch")
+        .unwrap();
+    assert_eq!(get_epoch2.call(&mut store2, ()).unwrap(), 42);
+}
+
+#[test]
+fn global_new_computed() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let epoch = Arc::new(AtomicI64::new(5));
+    let epoch2 = epoch.clone();
+    let global = Global::new_computed(&mut store, move || epoch2.fetch_add(1, Ordering::SeqCst));
+    let ty = global.ty(&store);
+    assert_eq!(ty.content(), ValType::I64);
+    assert_eq!(ty.mutability(), Mutability::Const);
+    assert_eq!(global.get(&store).i64(), Some(5));
+    assert_eq!(global.get(&store).i64(), Some(6));
+    assert!(matches!(
+        global.set(&mut store, Val::I64(0)),
+        Err(GlobalError::ImmutableWrite)
+    ));
+    let quota = Global::new_computed(&mut store, || 0_i32);
+    let instance = Instance::new(
+        &mut store,
+        &module,
+        &[Extern::Global(global), Extern::Global(quota)],
+    )
+    .unwrap();
+    let get_epoch = instance.get_typed_func::<(), i64>(&store, "epoch").unwrap();
+    assert_eq!(get_epoch.call(&mut store, ()).unwrap(), 7);
+    assert_eq!(get_epoch.call(&mut store, ()).unwrap(), 8);
+}
+
+#[test]
+fn computed_global_type_mismatch() {
+    let engine = Engine::default();
+    let wasm = r#"
+        (module
+            (import "env" "epoch" (global (mut i64)))
+        )
+    "#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.global_computed("env", "epoch", || 0_i64).unwrap();
+    assert!(linker.global_computed("env", "epoch", || 0_i64).is_err());
+    let mut store = Store::new(&engine, ());
+    assert!(linker.instantiate(&mut store, &module).is_err());
+}
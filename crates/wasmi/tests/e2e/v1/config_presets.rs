@@ -0,0 +1,230 @@
+//! Tests for the [`Config::deterministic`], [`Config::embedded`] and [`Config::fast`] presets.
+
+use wasmi::{core::TrapCode, CompilationMode, Config, Engine, Instance, Linker, Module, Store};
+
+/// Compiles `wat` with an [`Engine`] using `config`.
+fn compile(config: &Config, wat: &str) -> Result<Module, wasmi::Error> {
+    let engine = Engine::new_with_features(config)?;
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(&engine, &wasm[..])
+}
+
+/// Instantiates `module` within a new [`Store`] with `fuel` if fuel metering is enabled.
+fn instantiate(module: &Module, fuel: u64) -> (Store<()>, Instance) {
+    let mut store = Store::new(module.engine(), ());
+    if store.engine().config().get_consume_fuel() {
+        store.set_fuel(fuel).unwrap();
+    }
+    let instance = Linker::<()>::new(module.engine())
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// A Wasm module with a `count` function that loops `n` times and returns `n`.
+const COUNT: &str = r#"
+    (module
+        (func (export "count") (param $n i32) (result i32)
+            (local $i i32)
+            (loop $continue
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br_if $continue (i32.lt_u (local.get $i) (local.get $n)))
+            )
+            (local.get $i)
+        )
+    )
+"#;
+
+/// A Wasm module with a `depth` function that recurses `n` times.
+const RECURSE: &str = r#"
+    (module
+        (func $depth (export "depth") (param $n i32)
+            (if (local.get $n)
+                (then (call $depth (i32.sub (local.get $n) (i32.const 1))))
+            )
+        )
+    )
+"#;
+
+/// A Wasm module with an unused function that fails validation.
+const INVALID_UNUSED: &str = r#"
+    (module
+        (func (export "ok") (result i32)
+            (i32.const 1)
+        )
+        (func (result i32)
+            (i64.const 1)
+        )
+    )
+"#;
+
+mod deterministic {
+    use super::*;
+
+    #[test]
+    fn settings() {
+        let config = Config::deterministic();
+        assert_eq!(config.validate(), Ok(()));
+        assert!(config.get_consume_fuel());
+        assert!(!config.get_floats());
+        assert_eq!(config.get_compilation_mode(), CompilationMode::Eager);
+        assert!(config.get_enforced_limits().max_functions().is_some());
+    }
+
+    #[test]
+    fn fuel_consumption_is_reproducible() {
+        let config = Config::deterministic();
+        let consumed = || {
+            let module = compile(&config, COUNT).unwrap();
+            let (mut store, instance) = instantiate(&module, 1_000_000);
+            let count = instance
+                .get_typed_func::<i32, i32>(&store, "count")
+                .unwrap();
+            assert_eq!(count.call(&mut store, 100).unwrap(), 100);
+            1_000_000 - store.get_fuel().unwrap()
+        };
+        let first = consumed();
+        assert_ne!(first, 0);
+        assert_eq!(first, consumed());
+    }
+
+    #[test]
+    fn runs_out_of_fuel() {
+        let module = compile(&Config::deterministic(), COUNT).unwrap();
+        let (mut store, instance) = instantiate(&module, 100);
+        let count = instance
+            .get_typed_func::<i32, i32>(&store, "count")
+            .unwrap();
+        let error = count.call(&mut store, 1_000_000).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    }
+
+    #[test]
+    fn rejects_floats() {
+        let wat = r#"
+            (module
+                (func (param f32) (result f32)
+                    (f32.add (local.get 0) (local.get 0))
+                )
+            )
+        "#;
+        assert_eq!(
+            compile(&Config::default(), wat).is_ok(),
+            !cfg!(feature = "no-floats"),
+        );
+        assert!(compile(&Config::deterministic(), wat).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_unused_functions() {
+        assert!(compile(&Config::deterministic(), INVALID_UNUSED).is_err());
+    }
+
+    #[test]
+    fn enforces_strict_limits() {
+        let wat = r#"
+            (module
+                (memory 1)
+                (memory 1)
+            )
+        "#;
+        assert!(compile(&Config::default(), wat).is_ok());
+        assert!(compile(&Config::deterministic(), wat).is_err());
+    }
+}
+
+mod embedded {
+    use super::*;
+
+    #[test]
+    fn settings() {
+        let config = Config::embedded();
+        assert_eq!(config.validate(), Ok(()));
+        assert!(!config.get_consume_fuel());
+        assert!(config.get_ignore_custom_sections());
+        assert_eq!(config.get_cached_stacks(), 1);
+        assert_eq!(
+            config.get_compilation_mode(),
+            CompilationMode::LazyTranslation
+        );
+        let limits = config.get_stack_limits();
+        let default = Config::default().get_stack_limits();
+        assert!(limits.maximum_value_stack_height < default.maximum_value_stack_height);
+        assert!(limits.maximum_recursion_depth < default.maximum_recursion_depth);
+    }
+
+    #[test]
+    fn limits_recursion_depth() {
+        let depth = |config: &Config| {
+            let module = compile(config, RECURSE).unwrap();
+            let (mut store, instance) = instantiate(&module, 0);
+            let depth = instance.get_typed_func::<i32, ()>(&store, "depth").unwrap();
+            depth.call(&mut store, 500)
+        };
+        assert!(depth(&Config::default()).is_ok());
+        let error = depth(&Config::embedded()).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    }
+
+    #[test]
+    fn rejects_invalid_unused_functions() {
+        assert!(compile(&Config::embedded(), INVALID_UNUSED).is_err());
+    }
+
+    #[test]
+    fn ignores_custom_sections() {
+        let mut wasm = wat::parse_str("(module)").unwrap();
+        // Custom section with ID 0, size 5 and name `hint` of length 4.
+        wasm.extend_from_slice(b"\x00\x05\x04hint");
+        let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+        assert_eq!(module.custom_sections().count(), 1);
+        let engine = Engine::new_with_features(&Config::embedded()).unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        assert_eq!(module.custom_sections().count(), 0);
+    }
+
+    #[test]
+    fn executes() {
+        let module = compile(&Config::embedded(), COUNT).unwrap();
+        let (mut store, instance) = instantiate(&module, 0);
+        let count = instance
+            .get_typed_func::<i32, i32>(&store, "count")
+            .unwrap();
+        assert_eq!(count.call(&mut store, 10).unwrap(), 10);
+    }
+}
+
+mod fast {
+    use super::*;
+
+    #[test]
+    fn settings() {
+        let config = Config::fast();
+        assert_eq!(config.validate(), Ok(()));
+        assert!(!config.get_consume_fuel());
+        assert!(config.get_ignore_custom_sections());
+        assert_eq!(config.get_compilation_mode(), CompilationMode::Lazy);
+        assert!(config.get_cached_stacks() > Config::default().get_cached_stacks());
+    }
+
+    #[test]
+    fn executes_without_fuel() {
+        let module = compile(&Config::fast(), COUNT).unwrap();
+        let (mut store, instance) = instantiate(&module, 0);
+        assert!(store.get_fuel().is_err());
+        let count = instance
+            .get_typed_func::<i32, i32>(&store, "count")
+            .unwrap();
+        assert_eq!(count.call(&mut store, 10_000).unwrap(), 10_000);
+    }
+
+    #[test]
+    fn validates_lazily() {
+        let module = compile(&Config::fast(), INVALID_UNUSED).unwrap();
+        let (mut store, instance) = instantiate(&module, 0);
+        let ok = instance.get_typed_func::<(), i32>(&store, "ok").unwrap();
+        assert_eq!(ok.call(&mut store, ()).unwrap(), 1);
+    }
+}
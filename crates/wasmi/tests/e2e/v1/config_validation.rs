@@ -0,0 +1,75 @@
+//! Tests for validating the interplay of [`Config`] settings via [`Engine::new_with_features`].
+
+use wasmi::{
+    errors::{ConfigError, ErrorKind},
+    Config,
+    Engine,
+    Module,
+    UnsupportedProposals,
+};
+
+/// Returns the [`ConfigError`] of [`Engine::new_with_features`] for `config` if any.
+fn config_error(config: &Config) -> Option<ConfigError> {
+    match Engine::new_with_features(config) {
+        Ok(_) => None,
+        Err(error) => match error.kind() {
+            ErrorKind::Config(error) => Some(*error),
+            error => panic!("unexpected error: {error}"),
+        },
+    }
+}
+
+#[test]
+fn default_config_is_valid() {
+    assert_eq!(Config::default().validate(), Ok(()));
+    assert!(config_error(&Config::default()).is_none());
+}
+
+#[test]
+fn reference_types_require_bulk_memory() {
+    let mut config = Config::default();
+    config.wasm_bulk_memory(false);
+    assert_eq!(
+        config_error(&config),
+        Some(ConfigError::MissingRequiredProposal {
+            proposal: "reference-types",
+            requires: "bulk-memory",
+        })
+    );
+    // Without validation the misconfiguration only surfaces upon translation.
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func $f)
+            (func (export "f") (result funcref)
+                (ref.func $f)
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    assert!(Module::new(&Engine::new(&config), &wasm[..]).is_err());
+    config.wasm_reference_types(false);
+    assert!(config_error(&config).is_none());
+}
+
+#[test]
+fn trapping_bulk_memory_satisfies_reference_types() {
+    let mut config = Config::default();
+    config
+        .wasm_bulk_memory(false)
+        .unsupported_proposals(UnsupportedProposals::TrapAtUse);
+    assert!(config_error(&config).is_none());
+}
+
+#[test]
+fn fuel_costs_require_fuel_metering() {
+    let mut config = Config::default();
+    config.fuel_per_memory_page(10);
+    assert_eq!(
+        config_error(&config),
+        Some(ConfigError::FuelCostsWithoutFuelMetering)
+    );
+    config.consume_fuel(true);
+    assert!(config_error(&config).is_none());
+}
@@ -0,0 +1,212 @@
+//! Tests for constant-time audits of Wasm functions via [`Module::audit_constant_time`].
+
+use wasmi::{ConstantTimeAudit, ConstantTimeViolationKind, Module};
+
+/// Audits `wat` with `audit` and returns the violations as `(func_name, kind)` pairs.
+fn audit(wat: &str, audit: &ConstantTimeAudit) -> Vec<(String, ConstantTimeViolationKind)> {
+    let wasm = wat::parse_str(wat).unwrap();
+    let report = Module::audit_constant_time(&wasm, audit).unwrap();
+    report
+        .violations()
+        .iter()
+        .map(|violation| (violation.func_name().to_string(), violation.kind()))
+        .collect()
+}
+
+#[test]
+fn branchless_code_is_constant_time() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "ct_eq") (param $a i32) (param $b i32) (result i32)
+                (local $diff i32)
+                (local.set $diff (i32.xor (local.get $a) (local.get $b)))
+                (i32.shr_u
+                    (i32.sub (i32.const 0) (i32.or (local.get $diff) (i32.sub (i32.const 0) (local.get $diff))))
+                    (i32.const 31)
+                )
+            )
+            (func (export "ct_select") (param $c i32) (param $a i64) (param $b i64) (result i64)
+                (select (local.get $a) (local.get $b) (local.get $c))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let mut audit = ConstantTimeAudit::new();
+    audit.function("ct_eq").function("ct_select");
+    let report = Module::audit_constant_time(&wasm, &audit).unwrap();
+    assert!(report.is_constant_time());
+    assert_eq!(report.audited().collect::<Vec<_>>(), ["ct_eq", "ct_select"]);
+    assert_eq!(
+        report.to_string(),
+        "ct_eq: constant-time\nct_select: constant-time\n"
+    );
+}
+
+#[test]
+fn secret_branches_are_flagged() {
+    let wat = r#"
+        (module
+            (func (export "early_exit") (param $a i32) (param $b i32) (result i32)
+                (local $tmp i32)
+                (block $exit
+                    (local.set $tmp (i32.eq (local.get $a) (local.get $b)))
+                    (br_if $exit (local.get $tmp))
+                    (return (i32.const 0))
+                )
+                (i32.const 1)
+            )
+            (func (export "dispatch") (param $a i32)
+                (block (block (br_table 0 1 (local.get $a))))
+            )
+            (func (export "public") (param $a i32) (result i32)
+                (if (result i32) (i32.const 1)
+                    (then (local.get $a))
+                    (else (i32.const 0))
+                )
+            )
+        )
+    "#;
+    let mut ct = ConstantTimeAudit::new();
+    ct.function("early_exit")
+        .function("dispatch")
+        .function("public");
+    assert_eq!(
+        audit(wat, &ct),
+        [
+            ("dispatch".into(), ConstantTimeViolationKind::SecretBranch),
+            ("early_exit".into(), ConstantTimeViolationKind::SecretBranch),
+        ]
+    );
+}
+
+#[test]
+fn secret_indices_are_flagged() {
+    let wat = r#"
+        (module
+            (memory 1)
+            (table 4 funcref)
+            (type $f (func (result i32)))
+            (func (export "sbox") (param $x i32) (result i32)
+                (i32.load8_u (local.get $x))
+            )
+            (func (export "indirect") (param $x i32) (result i32)
+                (call_indirect (type $f) (local.get $x))
+            )
+            (func (export "store") (param $x i32)
+                (i32.store (i32.const 0) (local.get $x))
+            )
+        )
+    "#;
+    let mut ct = ConstantTimeAudit::new();
+    ct.function("sbox").function("indirect").function("store");
+    assert_eq!(
+        audit(wat, &ct),
+        [
+            (
+                "indirect".into(),
+                ConstantTimeViolationKind::SecretTableIndex
+            ),
+            (
+                "sbox".into(),
+                ConstantTimeViolationKind::SecretMemoryAddress
+            ),
+        ]
+    );
+}
+
+#[test]
+fn secret_memory_taints_loaded_values() {
+    let wat = r#"
+        (module
+            (memory 1)
+            (func (export "check") (param $ptr i32) (result i32)
+                (if (result i32) (i32.load (local.get $ptr))
+                    (then (i32.const 1))
+                    (else (i32.const 0))
+                )
+            )
+        )
+    "#;
+    let mut ct = ConstantTimeAudit::new();
+    ct.function("check").secret_params(false);
+    assert!(audit(wat, &ct).is_empty());
+    ct.secret_memory(true);
+    assert_eq!(
+        audit(wat, &ct),
+        [("check".into(), ConstantTimeViolationKind::SecretBranch)]
+    );
+}
+
+#[test]
+fn locals_propagate_secrets_across_loops() {
+    let wat = r#"
+        (module
+            (func (export "loop") (param $secret i32) (result i32)
+                (local $a i32) (local $b i32)
+                (loop $continue
+                    (if (local.get $a) (then (nop)))
+                    (local.set $a (local.get $b))
+                    (local.set $b (local.get $secret))
+                    (br_if $continue (i32.const 0))
+                )
+                (local.get $a)
+            )
+        )
+    "#;
+    let mut ct = ConstantTimeAudit::new();
+    ct.function("loop");
+    assert_eq!(
+        audit(wat, &ct),
+        [("loop".into(), ConstantTimeViolationKind::SecretBranch)]
+    );
+}
+
+#[test]
+fn custom_section_marks_functions() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func $leaky (param $a i32) (result i32)
+                (if (result i32) (local.get $a)
+                    (then (i32.const 1))
+                    (else (i32.const 0))
+                )
+            )
+            (func (export "run") (param i32) (result i32)
+                (call $leaky (local.get 0))
+            )
+            (@custom "wasmi.constant_time" "leaky")
+        )
+        "#,
+    )
+    .unwrap();
+    let report = Module::audit_constant_time(&wasm, &ConstantTimeAudit::new()).unwrap();
+    assert_eq!(report.audited().collect::<Vec<_>>(), ["leaky"]);
+    let [violation] = report.violations() else {
+        panic!("expected a single violation: {report}")
+    };
+    assert_eq!(violation.func_index(), 0);
+    assert_eq!(violation.kind(), ConstantTimeViolationKind::SecretBranch);
+    assert!(report.to_string().starts_with("leaky:\n  0x"));
+}
+
+#[test]
+fn unknown_functions_are_rejected() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host" (func (param i32)))
+            (export "host" (func 0))
+        )
+        "#,
+    )
+    .unwrap();
+    let mut audit = ConstantTimeAudit::new();
+    audit.function("missing");
+    assert!(Module::audit_constant_time(&wasm, &audit).is_err());
+    let mut audit = ConstantTimeAudit::new();
+    audit.function("host");
+    assert!(Module::audit_constant_time(&wasm, &audit).is_err());
+}
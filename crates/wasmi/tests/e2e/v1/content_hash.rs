@@ -0,0 +1,78 @@
+//! Tests for the stable hashes of the translated Wasmi bytecode of modules and functions.
+
+use wasmi::{CompilationMode, Config, Engine, Module};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "log" (func $log (param i32)))
+        (func $square (param i32) (result i32)
+            (i32.mul (local.get 0) (local.get 0))
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $log (local.get 0))
+            (call $square (i32.add (local.get 0) (i32.const 100_000)))
+        )
+    )
+"#;
+
+fn module(config: &Config, wat: &str) -> Module {
+    Module::new(&Engine::new(config), wat).unwrap()
+}
+
+#[test]
+fn stable_across_engines() {
+    let config = Config::default();
+    let engine = Engine::new(&config);
+    // Occupy some function bodies so that the compiled functions use different engine indices.
+    Module::new(&engine, "(module (func) (func))").unwrap();
+    let a = Module::new(&engine, WAT).unwrap();
+    let b = module(&config, WAT);
+    assert_eq!(a.content_hash().unwrap(), b.content_hash().unwrap());
+    for index in 0..3 {
+        assert_eq!(a.func_hash(index).unwrap(), b.func_hash(index).unwrap());
+    }
+}
+
+#[test]
+fn imported_and_out_of_bounds_funcs_have_no_hash() {
+    let module = module(&Config::default(), WAT);
+    assert_eq!(module.func_hash(0).unwrap(), None);
+    assert!(module.func_hash(1).unwrap().is_some());
+    assert!(module.func_hash(2).unwrap().is_some());
+    assert_eq!(module.func_hash(3).unwrap(), None);
+    assert_ne!(module.func_hash(1).unwrap(), module.func_hash(2).unwrap());
+}
+
+#[test]
+fn lazy_compilation_does_not_change_hashes() {
+    let eager = module(&Config::default(), WAT);
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let lazy = module(&config, WAT);
+    assert_eq!(eager.content_hash().unwrap(), lazy.content_hash().unwrap());
+}
+
+#[test]
+fn differs_with_translated_bytecode() {
+    let plain = module(&Config::default(), WAT);
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let metered = module(&config, WAT);
+    assert_eq!(plain.hash(), metered.hash());
+    assert_ne!(
+        plain.content_hash().unwrap(),
+        metered.content_hash().unwrap()
+    );
+    assert_ne!(plain.func_hash(1).unwrap(), metered.func_hash(1).unwrap());
+}
+
+#[test]
+fn differs_with_function_body() {
+    let changed = WAT.replace("100_000", "100_001");
+    let a = module(&Config::default(), WAT);
+    let b = module(&Config::default(), &changed);
+    assert_ne!(a.content_hash().unwrap(), b.content_hash().unwrap());
+    // Only the changed function has a different hash.
+    assert_eq!(a.func_hash(1).unwrap(), b.func_hash(1).unwrap());
+    assert_ne!(a.func_hash(2).unwrap(), b.func_hash(2).unwrap());
+}
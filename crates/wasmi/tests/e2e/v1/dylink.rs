@@ -0,0 +1,161 @@
+//! Tests for Emscripten-style dynamic linking via [`DynamicLinker`].
+#![cfg(feature = "dylink")]
+
+use wasmi::{
+    core::ValType,
+    errors::{ErrorKind, LinkerError},
+    DylinkSymbol,
+    DynamicLinker,
+    Engine,
+    FuncRef,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+    Table,
+    TableType,
+    Val,
+};
+
+/// The main module which references symbols of the side module via its `GOT`.
+const MAIN: &str = r#"
+    (module
+        (@custom "dylink.0" "\01\04\10\02\00\00")
+        (import "env" "memory" (memory 1))
+        (import "env" "__indirect_function_table" (table 0 funcref))
+        (import "env" "__memory_base" (global $memory_base i32))
+        (import "GOT.mem" "counter" (global $counter (mut i32)))
+        (import "GOT.func" "side_double" (global $side_double (mut i32)))
+        (type $unary (func (param i32) (result i32)))
+        (global (export "main_data") i32 (i32.const 4))
+        (data (global.get $memory_base) "\2A\00\00\00\07\00\00\00")
+        (func (export "main_add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func (export "bump") (result i32)
+            (i32.store
+                (global.get $counter)
+                (i32.add (i32.load (global.get $counter)) (i32.const 1))
+            )
+            (i32.load (global.get $counter))
+        )
+        (func (export "call_double") (param i32) (result i32)
+            (call_indirect (type $unary) (local.get 0) (global.get $side_double))
+        )
+    )
+"#;
+
+/// The side module which is loaded after the main module.
+const SIDE: &str = r#"
+    (module
+        (@custom "dylink.0" "\01\04\08\03\01\00")
+        (import "env" "memory" (memory 1))
+        (import "env" "__indirect_function_table" (table 0 funcref))
+        (import "env" "__memory_base" (global $memory_base i32))
+        (import "env" "__table_base" (global $table_base i32))
+        (import "env" "main_add" (func $main_add (param i32 i32) (result i32)))
+        (import "GOT.mem" "main_data" (global $main_data (mut i32)))
+        (type $unary (func (param i32) (result i32)))
+        (global (export "counter") i32 (i32.const 0))
+        (elem (global.get $table_base) func $side_double)
+        (func $side_double (export "side_double") (param i32) (result i32)
+            (call $main_add (local.get 0) (local.get 0))
+        )
+        (func (export "read_main_data") (result i32)
+            (i32.load (global.get $main_data))
+        )
+        (func (export "call_own_table") (param i32) (result i32)
+            (call_indirect (type $unary) (local.get 0) (global.get $table_base))
+        )
+        (func (export "__wasm_call_ctors")
+            (i32.store (global.get $memory_base) (i32.const 100))
+        )
+    )
+"#;
+
+/// Creates a [`Store`] and a [`DynamicLinker`] with a fresh memory and table.
+fn setup() -> (Store<()>, DynamicLinker, Linker<()>) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValType::FuncRef, 0, None),
+        Val::FuncRef(FuncRef::null()),
+    )
+    .unwrap();
+    let dylink = DynamicLinker::new(&mut store, memory, table);
+    let linker = <Linker<()>>::new(&engine);
+    (store, dylink, linker)
+}
+
+fn module(store: &Store<()>, wat: &str) -> Module {
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(store.engine(), &wasm[..]).unwrap()
+}
+
+#[test]
+fn dylink_main_and_side_module() {
+    let (mut store, mut dylink, linker) = setup();
+    let stack_top = dylink.allocate_stack(&mut store, 4096).unwrap();
+    assert_eq!(
+        dylink.stack_pointer().get(&store).i32(),
+        Some(stack_top as i32)
+    );
+    let main = module(&store, MAIN);
+    let main = dylink.load(&mut store, &linker, &main).unwrap();
+    assert_eq!(
+        dylink.unresolved_symbols().collect::<Vec<_>>(),
+        ["counter", "side_double"]
+    );
+    let side = module(&store, SIDE);
+    let side = dylink.load(&mut store, &linker, &side).unwrap();
+    assert_eq!(dylink.unresolved_symbols().count(), 0);
+    // The side module calls back into the main module via the shared table.
+    let call_double = main
+        .get_typed_func::<i32, i32>(&store, "call_double")
+        .unwrap();
+    assert_eq!(call_double.call(&mut store, 21).unwrap(), 42);
+    // The constructor of the side module initialized its counter.
+    let bump = main.get_typed_func::<(), i32>(&store, "bump").unwrap();
+    assert_eq!(bump.call(&mut store, ()).unwrap(), 101);
+    assert_eq!(bump.call(&mut store, ()).unwrap(), 102);
+    // The side module reads the data of the main module.
+    let read_main_data = side
+        .get_typed_func::<(), i32>(&store, "read_main_data")
+        .unwrap();
+    assert_eq!(read_main_data.call(&mut store, ()).unwrap(), 7);
+    // The side module uses its own table region.
+    let call_own_table = side
+        .get_typed_func::<i32, i32>(&store, "call_own_table")
+        .unwrap();
+    assert_eq!(call_own_table.call(&mut store, 5).unwrap(), 10);
+    // Symbols are looked up by name.
+    let Some(DylinkSymbol::Data(counter)) = dylink.symbol("counter") else {
+        panic!("expected `counter` data symbol")
+    };
+    let mut bytes = [0x00; 4];
+    dylink
+        .memory()
+        .read(&store, counter as usize, &mut bytes)
+        .unwrap();
+    assert_eq!(i32::from_le_bytes(bytes), 102);
+    assert!(matches!(
+        dylink.symbol("main_add"),
+        Some(DylinkSymbol::Func(_))
+    ));
+    assert!(dylink.symbol("__wasm_call_ctors").is_none());
+    assert!(dylink.symbol("missing").is_none());
+}
+
+#[test]
+fn dylink_missing_symbol() {
+    let (mut store, mut dylink, linker) = setup();
+    let side = module(&store, SIDE);
+    let error = dylink.load(&mut store, &linker, &side).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::MissingDefinition { .. })
+    ));
+}
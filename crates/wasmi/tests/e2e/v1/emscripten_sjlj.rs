@@ -0,0 +1,159 @@
+//! Tests for the Emscripten `setjmp`/`longjmp` emulation via [`EmscriptenSjlj`].
+#![cfg(feature = "emscripten-sjlj")]
+
+use wasmi::{core::TrapCode, EmscriptenSjlj, Engine, Instance, Linker, Module, Store};
+
+/// Mimics the `setjmp`/`longjmp` lowering of Emscripten compiled C code.
+const WASM: &str = r#"
+    (module
+        (import "env" "invoke_vi" (func $invoke_vi (param i32 i32)))
+        (import "env" "invoke_iii" (func $invoke_iii (param i32 i32 i32) (result i32)))
+        (import "env" "emscripten_longjmp" (func $longjmp (param i32 i32)))
+        (import "env" "saveSetjmp" (func $saveSetjmp (param i32 i32 i32 i32) (result i32)))
+        (import "env" "testSetjmp" (func $testSetjmp (param i32 i32 i32) (result i32)))
+        (import "env" "getTempRet0" (func $getTempRet0 (result i32)))
+        (memory (export "memory") 1)
+        (table (export "__indirect_function_table") 4 funcref)
+        (elem (i32.const 1) $jump $add $trap)
+        (global $threw (mut i32) (i32.const 0))
+        (global $threw_value (mut i32) (i32.const 0))
+        (global $sp (mut i32) (i32.const 4096))
+        (global $heap (mut i32) (i32.const 8192))
+        (func $setThrew (export "setThrew") (param i32 i32)
+            (if (i32.eqz (global.get $threw))
+                (then
+                    (global.set $threw (local.get 0))
+                    (global.set $threw_value (local.get 1))
+                )
+            )
+        )
+        (func (export "stackSave") (result i32)
+            (global.get $sp)
+        )
+        (func (export "stackRestore") (param i32)
+            (global.set $sp (local.get 0))
+        )
+        ;; A bump allocator that copies the reallocated region.
+        (func (export "realloc") (param $ptr i32) (param $size i32) (result i32)
+            (local $new i32)
+            (local.set $new (global.get $heap))
+            (global.set $heap (i32.add (global.get $heap) (local.get $size)))
+            (memory.copy (local.get $new) (local.get $ptr) (local.get $size))
+            (local.get $new)
+        )
+        ;; Allocates stack space and longjmps to `env` with `value`.
+        (func $jump (param $env i32)
+            (global.set $sp (i32.sub (global.get $sp) (i32.const 64)))
+            (call $longjmp (local.get $env) (i32.const 0))
+        )
+        (func $add (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func $trap (param i32)
+            (unreachable)
+        )
+        ;; `if (setjmp(env) == 0) { jump(env); return -1; } else { return value; }`
+        (func (export "run") (result i32)
+            (local $table i32)
+            (local $size i32)
+            (local.set $table (call $saveSetjmp (i32.const 16) (i32.const 1) (i32.const 64) (i32.const 1)))
+            (local.set $size (call $getTempRet0))
+            (global.set $threw (i32.const 0))
+            (call $invoke_vi (i32.const 1) (i32.const 16))
+            (if (i32.eqz (global.get $threw))
+                (then (return (i32.const -1)))
+            )
+            ;; Resolves the label of the setjmp id at `env`.
+            (if (i32.eqz
+                    (call $testSetjmp
+                        (i32.load (global.get $threw))
+                        (local.get $table)
+                        (local.get $size)
+                    )
+                )
+                (then (return (i32.const -2)))
+            )
+            (global.get $threw_value)
+        )
+        (func (export "add") (param i32 i32) (result i32)
+            (call $invoke_iii (i32.const 2) (local.get 0) (local.get 1))
+        )
+        (func (export "trap")
+            (call $invoke_vi (i32.const 3) (i32.const 0))
+        )
+        (func (export "sp") (result i32)
+            (global.get $sp)
+        )
+        (func (export "save") (param i32 i32 i32 i32) (result i32)
+            (call $saveSetjmp (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "test") (param i32 i32 i32) (result i32)
+            (call $testSetjmp (local.get 0) (local.get 1) (local.get 2))
+        )
+    )
+"#;
+
+fn setup() -> (Store<EmscriptenSjlj>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, EmscriptenSjlj::new());
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut linker = Linker::new(&engine);
+    EmscriptenSjlj::add_to_linker(&mut linker, &module, |state| state).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn longjmp_returns_to_invoke() {
+    let (mut store, instance) = setup();
+    let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+    // `longjmp` with a value of 0 yields a value of 1 for `setjmp`.
+    assert_eq!(run.call(&mut store, ()).unwrap(), 1);
+    // The stack pointer is restored by the `invoke_*` trampoline.
+    let sp = instance.get_typed_func::<(), i32>(&store, "sp").unwrap();
+    assert_eq!(sp.call(&mut store, ()).unwrap(), 4096);
+}
+
+#[test]
+fn invoke_without_longjmp() {
+    let (mut store, instance) = setup();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (2, 3)).unwrap(), 5);
+}
+
+#[test]
+fn invoke_propagates_traps() {
+    let (mut store, instance) = setup();
+    let trap = instance.get_typed_func::<(), ()>(&store, "trap").unwrap();
+    let error = trap.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+}
+
+#[test]
+fn setjmp_table_grows() {
+    let (mut store, instance) = setup();
+    let save = instance
+        .get_typed_func::<(i32, i32, i32, i32), i32>(&store, "save")
+        .unwrap();
+    let test = instance
+        .get_typed_func::<(i32, i32, i32), i32>(&store, "test")
+        .unwrap();
+    // The initial table at 64 has a single entry.
+    let table = save.call(&mut store, (16, 10, 64, 1)).unwrap();
+    assert_eq!((table, store.data().temp_ret0()), (64, 1));
+    // Registering a second setjmp reallocates the table with twice the size.
+    let table = save.call(&mut store, (20, 20, table, 1)).unwrap();
+    assert_eq!((table, store.data().temp_ret0()), (8192, 2));
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let id = |env: usize| i32::from_le_bytes(memory.data(&store)[env..env + 4].try_into().unwrap());
+    let (first, second) = (id(16), id(20));
+    assert_eq!(test.call(&mut store, (first, table, 2)).unwrap(), 10);
+    assert_eq!(test.call(&mut store, (second, table, 2)).unwrap(), 20);
+    assert_eq!(test.call(&mut store, (42, table, 2)).unwrap(), 0);
+}
@@ -0,0 +1,92 @@
+//! Tests for the deterministic iteration order and stable IDs of [`Store`] entities.
+
+use wasmi::{
+    core::ValType,
+    Engine,
+    Global,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    Val,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "memory" (memory 1))
+        (memory 1)
+        (table 1 funcref)
+        (table 2 funcref)
+        (global (export "g") (mut i32) (i32.const 0))
+    )
+"#;
+
+/// Instantiates [`WASM`] twice within a new [`Store`] with an imported host [`Memory`].
+fn setup(engine: &Engine) -> Store<()> {
+    let module = Module::new(engine, WASM).unwrap();
+    let mut store = Store::new(engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let mut linker = <Linker<()>>::new(engine);
+    linker.define("env", "memory", memory).unwrap();
+    for _ in 0..2 {
+        linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+    }
+    Global::new(&mut store, Val::I64(0), Mutability::Const);
+    store
+}
+
+#[test]
+fn entity_ids_are_allocation_ordered() {
+    let engine = Engine::default();
+    let store = setup(&engine);
+    assert_eq!(
+        store.instances().map(|i| i.id(&store)).collect::<Vec<_>>(),
+        [0, 1]
+    );
+    // The imported memory is allocated before the memories of both instances.
+    assert_eq!(
+        store.memories().map(|m| m.id(&store)).collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    assert_eq!(
+        store.tables().map(|t| t.id(&store)).collect::<Vec<_>>(),
+        [0, 1, 2, 3]
+    );
+    let globals = store.globals().collect::<Vec<_>>();
+    assert_eq!(globals.len(), 3);
+    assert_eq!(globals[2].id(&store), 2);
+    assert_eq!(globals[2].ty(&store).content(), ValType::I64);
+    // Table sizes reveal the per instance allocation order.
+    let sizes = store.tables().map(|t| t.size(&store)).collect::<Vec<_>>();
+    assert_eq!(sizes, [1, 2, 1, 2]);
+}
+
+#[test]
+fn entity_ids_are_deterministic() {
+    let engine = Engine::default();
+    let a = setup(&engine);
+    let b = setup(&engine);
+    let instance = |store: &Store<()>| {
+        let instance = store.instances().last().unwrap();
+        let global = instance.get_global(store, "g").unwrap();
+        (instance.id(store), global.id(store))
+    };
+    assert_eq!(instance(&a), instance(&b));
+    assert_eq!(instance(&a), (1, 1));
+}
+
+#[test]
+#[should_panic]
+fn entity_id_of_foreign_store_panics() {
+    let engine = Engine::default();
+    let a = setup(&engine);
+    let b = setup(&engine);
+    let memory = a.memories().next().unwrap();
+    memory.id(&b);
+}
@@ -0,0 +1,223 @@
+//! Tests for handling errors via [`Error::category`] and their [`std::error::Error::source`] chains.
+
+use std::error::Error as _;
+use wasmi::{
+    core::{HostError, TrapCode},
+    errors::{ErrorCategory, ErrorKind, InstantiationError, MemoryError},
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Extern,
+    Func,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+};
+
+#[derive(Debug)]
+struct MyError(u32);
+
+impl core::fmt::Display for MyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "my error: {}", self.0)
+    }
+}
+
+impl HostError for MyError {}
+
+/// Returns an [`Engine`] that captures the Wasm call frames of traps.
+fn backtrace_engine() -> Engine {
+    let mut config = Config::default();
+    config.wasm_backtrace(true);
+    Engine::new(&config)
+}
+
+fn run(wasm: &str, store: &mut Store<()>, linker: &Linker<()>) -> Result<(), Error> {
+    let module = Module::new(store.engine(), wasm)?;
+    let instance = linker
+        .instantiate(&mut *store, &module)?
+        .start(&mut *store)?;
+    instance
+        .get_typed_func::<(), ()>(&*store, "run")?
+        .call(&mut *store, ())
+}
+
+#[test]
+fn trap() {
+    let engine = backtrace_engine();
+    let mut store = Store::new(&engine, ());
+    let wasm = r#"
+        (module
+            (func $trap (export "trap") unreachable)
+            (func (export "run") (call $trap))
+        )
+    "#;
+    let error = run(wasm, &mut store, &Linker::new(&engine)).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::TrapCode(TrapCode::UnreachableCodeReached)
+    ));
+    let ErrorCategory::Trap { code, backtrace } = error.category() else {
+        panic!("unexpected error: {error:?}")
+    };
+    assert_eq!(code, TrapCode::UnreachableCodeReached);
+    let names = backtrace
+        .iter()
+        .map(|frame| frame.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, [Some("trap"), Some("run")]);
+    assert!(backtrace[0].instr_offset().is_some());
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn trap_of_host_func_has_backtrace() {
+    let engine = backtrace_engine();
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    let fail = Func::wrap(&mut store, |_: Caller<()>| -> Result<(), Error> {
+        Err(Error::from(TrapCode::BadSignature))
+    });
+    linker.define("env", "fail", fail).unwrap();
+    let wasm = r#"
+        (module
+            (import "env" "fail" (func $fail))
+            (func (export "run") (call $fail))
+        )
+    "#;
+    let error = run(wasm, &mut store, &linker).unwrap_err();
+    let ErrorCategory::Trap { code, backtrace } = error.category() else {
+        panic!("unexpected error: {error:?}")
+    };
+    assert_eq!(code, TrapCode::BadSignature);
+    assert_eq!(backtrace.len(), 1);
+    assert_eq!(backtrace[0].name(), Some("run"));
+}
+
+#[test]
+fn trap_out_of_fuel_has_backtrace() {
+    let mut config = Config::default();
+    config.consume_fuel(true).wasm_backtrace(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(1_000).unwrap();
+    let wasm = r#"(module (func (export "run") (loop (br 0))))"#;
+    let error = run(wasm, &mut store, &Linker::new(&engine)).unwrap_err();
+    let ErrorCategory::Trap { code, backtrace } = error.category() else {
+        panic!("unexpected error: {error:?}")
+    };
+    assert_eq!(code, TrapCode::OutOfFuel);
+    assert_eq!(backtrace.len(), 1);
+    assert_eq!(backtrace[0].name(), Some("run"));
+}
+
+#[test]
+fn trap_without_backtrace_by_default() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = r#"(module (func (export "run") unreachable))"#;
+    let error = run(wasm, &mut store, &Linker::new(&engine)).unwrap_err();
+    let ErrorCategory::Trap { code, backtrace } = error.category() else {
+        panic!("unexpected error: {error:?}")
+    };
+    assert_eq!(code, TrapCode::UnreachableCodeReached);
+    assert!(backtrace.is_empty());
+}
+
+#[test]
+fn validation() {
+    let engine = Engine::default();
+    let wasm = b"\0asm\x01\0\0\0\x01\x04\x01\x60\x00";
+    let error = Module::new(&engine, &wasm[..]).unwrap_err();
+    let ErrorCategory::Validation { offset } = error.category() else {
+        panic!("unexpected error: {error:?}")
+    };
+    assert!(offset.is_some());
+}
+
+#[test]
+fn instantiation_missing_import() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = r#"
+        (module
+            (import "env" "missing" (func))
+            (func (export "run"))
+        )
+    "#;
+    let error = run(wasm, &mut store, &Linker::new(&engine)).unwrap_err();
+    let ErrorCategory::Instantiation { import } = error.category() else {
+        panic!("unexpected error: {error:?}")
+    };
+    assert_eq!(import, Some(("env", "missing")));
+}
+
+#[test]
+fn instantiation_source_chain() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = r#"(module (import "env" "memory" (memory 2)))"#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let error = Instance::new(&mut store, &module, &[Extern::from(memory)]).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::Memory(_))
+    ));
+    assert!(matches!(
+        error.category(),
+        ErrorCategory::Instantiation { import: None }
+    ));
+    let source = error
+        .source()
+        .expect("missing source of instantiation error");
+    assert!(matches!(
+        source.downcast_ref::<MemoryError>(),
+        Some(MemoryError::InvalidSubtype { .. })
+    ));
+    assert_ne!(error.to_string(), source.to_string());
+}
+
+#[test]
+fn host() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    let fail = Func::wrap(&mut store, |_: Caller<()>| -> Result<(), Error> {
+        Err(Error::host(MyError(42)))
+    });
+    linker.define("env", "fail", fail).unwrap();
+    let wasm = r#"
+        (module
+            (import "env" "fail" (func $fail))
+            (func (export "run") (call $fail))
+        )
+    "#;
+    let error = run(wasm, &mut store, &linker).unwrap_err();
+    let ErrorCategory::Host { payload } = error.category() else {
+        panic!("unexpected error: {error:?}")
+    };
+    assert!(matches!(
+        payload.downcast_ref::<MyError>(),
+        Some(MyError(42))
+    ));
+    let source = error.source().expect("missing source of host error");
+    assert_eq!(source.to_string(), "my error: 42");
+    assert!(source.source().is_none());
+}
+
+#[test]
+fn exit_and_other() {
+    assert!(matches!(
+        Error::i32_exit(3).category(),
+        ErrorCategory::Exit { status: 3 }
+    ));
+    assert!(matches!(
+        Error::new("custom").category(),
+        ErrorCategory::Other
+    ));
+}
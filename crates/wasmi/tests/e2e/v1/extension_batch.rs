@@ -0,0 +1,135 @@
+//! Tests for batched dispatch of consecutive [`InstructionExtension`] instructions.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    core::{TrapCode, UntypedVal, ValType},
+    Engine,
+    Error,
+    FuncType,
+    Instance,
+    InstructionExtension,
+    InstructionExtensions,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A toy cipher extension that records the sizes of its dispatched batches.
+#[derive(Clone, Default)]
+struct Cipher {
+    batches: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Cipher {
+    const ENCRYPT: u16 = 0;
+}
+
+impl InstructionExtension for Cipher {
+    fn module(&self) -> &str {
+        "cipher"
+    }
+
+    fn resolve(&self, name: &str, ty: &FuncType) -> Option<u16> {
+        let expected = FuncType::new([ValType::I64; 2], [ValType::I64]);
+        (name == "encrypt" && ty == &expected).then_some(Self::ENCRYPT)
+    }
+
+    fn execute(
+        &self,
+        _opcode: u16,
+        params: &[UntypedVal],
+        results: &mut [UntypedVal],
+    ) -> Result<(), Error> {
+        self.batches.lock().unwrap().push(1);
+        let [value, key] = [0, 1].map(|n| u64::from(params[n]));
+        if key == 0 {
+            return Err(Error::from(TrapCode::BadSignature));
+        }
+        results[0] = (value ^ key).into();
+        Ok(())
+    }
+
+    fn supports_batch(&self, _opcode: u16) -> bool {
+        true
+    }
+
+    fn execute_batch(
+        &self,
+        _opcode: u16,
+        len: usize,
+        params: &[UntypedVal],
+        results: &mut [UntypedVal],
+    ) -> Result<(), Error> {
+        self.batches.lock().unwrap().push(len);
+        for (params, result) in params.chunks(2).zip(results) {
+            let [value, key] = [0, 1].map(|n| u64::from(params[n]));
+            if key == 0 {
+                return Err(Error::from(TrapCode::BadSignature));
+            }
+            *result = (value ^ key).into();
+        }
+        Ok(())
+    }
+}
+
+const WASM: &str = r#"
+    (module
+        (import "cipher" "encrypt" (func $encrypt (param i64 i64) (result i64)))
+        (func (export "encrypt4") (param $key i64) (result i64)
+            (local $a i64) (local $b i64) (local $c i64) (local $d i64)
+            (local.set $a (call $encrypt (i64.const 1) (local.get $key)))
+            (local.set $b (call $encrypt (i64.const 2) (local.get $key)))
+            (local.set $c (call $encrypt (i64.const 4) (local.get $key)))
+            (local.set $d (call $encrypt (i64.const 8) (local.get $key)))
+            (i64.add
+                (i64.add (local.get $a) (local.get $b))
+                (i64.add (local.get $c) (local.get $d))
+            )
+        )
+        (func (export "encrypt_twice") (param $value i64) (param $key i64) (result i64)
+            (call $encrypt
+                (call $encrypt (local.get $value) (local.get $key))
+                (local.get $key)
+            )
+        )
+    )
+"#;
+
+fn setup() -> (Store<()>, Instance, Arc<Mutex<Vec<usize>>>) {
+    let cipher = Cipher::default();
+    let batches = cipher.batches.clone();
+    let mut extensions = InstructionExtensions::new();
+    extensions.register(cipher).unwrap();
+    let engine = Engine::with_extensions(&Default::default(), extensions);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, batches)
+}
+
+#[test]
+fn consecutive_calls_are_batched() {
+    let (mut store, instance, batches) = setup();
+    let encrypt4 = instance
+        .get_typed_func::<i64, i64>(&store, "encrypt4")
+        .unwrap();
+    assert_eq!(encrypt4.call(&mut store, 0x10).unwrap(), 0x4F);
+    assert_eq!(*batches.lock().unwrap(), [4]);
+    // Traps in batches are propagated as usual.
+    let error = encrypt4.call(&mut store, 0).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+}
+
+#[test]
+fn dependent_calls_are_not_batched() {
+    let (mut store, instance, batches) = setup();
+    let encrypt_twice = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "encrypt_twice")
+        .unwrap();
+    assert_eq!(encrypt_twice.call(&mut store, (42, 7)).unwrap(), 42);
+    assert_eq!(*batches.lock().unwrap(), [1, 1]);
+}
@@ -79,3 +79,50 @@ fn check_fuel_consumption(given_fuel: u64, consumed_fuel: u64) {
 fn fuel_consumption_01() {
     check_fuel_consumption(3, 3);
 }
+
+/// Returns the fuel consumed by growing a memory and a table by `delta` each.
+fn grow_fuel(fuel_per_page: u64, fuel_per_element: u64, delta: i32) -> u64 {
+    let mut config = Config::default();
+    config
+        .consume_fuel(true)
+        .fuel_per_memory_page(fuel_per_page)
+        .fuel_per_table_element(fuel_per_element);
+    let engine = Engine::new(&config);
+    let wasm = r#"
+        (module
+            (memory 0)
+            (table 0 funcref)
+            (func (export "grow") (param i32)
+                (drop (memory.grow (local.get 0)))
+                (drop (table.grow (ref.null func) (local.get 0)))
+            )
+        )
+    "#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let grow = instance.get_typed_func::<i32, ()>(&store, "grow").unwrap();
+    store.set_fuel(1_000_000).unwrap();
+    grow.call(&mut store, delta).unwrap();
+    1_000_000 - store.get_fuel().unwrap()
+}
+
+#[test]
+fn grow_fuel_is_proportional_to_delta() {
+    let bytes_per_page = 1 << 16;
+    let bytes_per_fuel = 64;
+    let base = grow_fuel(0, 0, 0);
+    // By default only the added bytes and element copies are charged.
+    let default_costs = grow_fuel(0, 0, 2) - base;
+    assert!(default_costs >= 2 * bytes_per_page / bytes_per_fuel);
+    assert_eq!(grow_fuel(100, 0, 2) - base, default_costs + 2 * 100);
+    assert_eq!(grow_fuel(0, 7, 2) - base, default_costs + 2 * 7);
+    assert_eq!(
+        grow_fuel(100, 7, 4) - base,
+        2 * default_costs + 4 * (100 + 7)
+    );
+}
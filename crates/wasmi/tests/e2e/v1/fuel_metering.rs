@@ -6,7 +6,7 @@ use wasmi::{core::TrapCode, Config, Engine, Error, Func, Linker, Module, Store};
 /// Setup [`Engine`] and [`Store`] for fuel metering.
 fn test_setup() -> (Store<()>, Linker<()>) {
     let mut config = Config::default();
-    config.consume_fuel(true);
+    config.consume_fuel(true).wasm_backtrace(true);
     let engine = Engine::new(&config);
     let store = Store::new(&engine, ());
     let linker = Linker::new(&engine);
@@ -85,3 +85,48 @@ fn metered_i32_add() {
     assert_success(func.call(&mut store, (1, 2)));
     assert_eq!(store.get_fuel().ok(), Some(7));
 }
+
+#[test]
+fn out_of_fuel_diagnostics() {
+    use wasmi::{core::ValType, errors::ErrorKind};
+    let wasm = r#"
+        (module
+            (func $inner (export "inner") (param $n i32)
+                (loop $continue
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br_if $continue (local.get $n))
+                )
+            )
+            (func $middle (param i32)
+                (call $inner (local.get 0))
+            )
+            (func (export "test") (param i32)
+                (call $middle (local.get 0))
+            )
+        )
+    "#;
+    let (mut store, func) = default_test_setup(wasm.as_bytes());
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    store.set_fuel(100).unwrap();
+    let error = func.call(&mut store, 1_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    let ErrorKind::FuelTrap(trap) = error.kind() else {
+        panic!("expected fuel trap but found: {error}")
+    };
+    assert_eq!(trap.call_depth(), 3);
+    let names = trap
+        .frames()
+        .iter()
+        .map(|frame| frame.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, [Some("inner"), None, Some("test")]);
+    assert!(trap
+        .frames()
+        .iter()
+        .all(|frame| frame.instr_offset().is_some()));
+    let current = trap.current().unwrap();
+    assert_eq!(current.func().ty(&store).params(), [ValType::I32]);
+    let message = error.to_string();
+    assert!(message.contains("#0 inner at instruction"), "{message}");
+    assert!(message.contains("#1 <unnamed>"), "{message}");
+}
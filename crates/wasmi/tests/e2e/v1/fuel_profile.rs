@@ -0,0 +1,73 @@
+//! Tests for the per-function fuel accounting of Wasmi.
+
+#![cfg(feature = "fuel-profile")]
+
+use wasmi::{Config, Engine, Linker, Module, Store};
+
+/// Setup [`Store`] and [`Linker`] for fuel profiling.
+fn test_setup() -> (Store<()>, Linker<()>) {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let store = Store::new(&engine, ());
+    let linker = Linker::new(&engine);
+    (store, linker)
+}
+
+#[test]
+fn fuel_profile_attributes_per_function() {
+    let wasm = r#"
+        (module
+            (func $cheap (result i32)
+                (i32.const 1)
+            )
+            (func $expensive (param $n i32) (result i32)
+                (local $sum i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (local.set $sum (i32.add (local.get $sum) (local.get $n)))
+                        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+                (local.get $sum)
+            )
+            (func (export "cheap") (result i32)
+                (call $cheap)
+            )
+            (func (export "expensive") (result i32)
+                (call $expensive (i32.const 100))
+            )
+        )
+    "#;
+    let (mut store, linker) = test_setup();
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert!(store.fuel_profile().is_empty());
+    store.set_fuel(100_000).unwrap();
+    let cheap = instance.get_typed_func::<(), i32>(&store, "cheap").unwrap();
+    let expensive = instance
+        .get_typed_func::<(), i32>(&store, "expensive")
+        .unwrap();
+    cheap.call(&mut store, ()).unwrap();
+    expensive.call(&mut store, ()).unwrap();
+    let consumed = 100_000 - store.get_fuel().unwrap();
+    let profile = store.fuel_profile();
+    // All four functions have been executed.
+    assert_eq!(profile.len(), 4);
+    // All consumed fuel is attributed since no bulk operations have been used.
+    assert_eq!(profile.total(), consumed);
+    // The looping function dominates the fuel consumption and is reported first.
+    let entries = profile.iter().collect::<Vec<_>>();
+    assert!(entries[0].consumed() > entries[1].consumed());
+    assert!(entries
+        .windows(2)
+        .all(|w| w[0].consumed() >= w[1].consumed()));
+    store.reset_fuel_profile();
+    assert!(store.fuel_profile().is_empty());
+}
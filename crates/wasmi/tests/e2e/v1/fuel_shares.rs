@@ -0,0 +1,159 @@
+//! Tests for proportional-share fuel accounting across the stores of an [`Engine`].
+
+use core::num::NonZeroU32;
+use wasmi::{
+    errors::{ErrorKind, FuelError},
+    Config,
+    Engine,
+    Error,
+    Store,
+    TypedFunc,
+};
+
+const WASM: &str = r#"
+    (module
+        ;; Loops `n` times in order to consume fuel.
+        (func (export "work") (param $n i32)
+            (loop $continue
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $continue (local.get $n))
+            )
+        )
+    )
+"#;
+
+fn engine() -> Engine {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config)
+}
+
+fn setup(engine: &Engine, share: u32) -> (Store<()>, TypedFunc<i32, ()>) {
+    let mut store = Store::new(engine, ());
+    store.set_fuel(u64::MAX).unwrap();
+    store.set_fuel_share(NonZeroU32::new(share)).unwrap();
+    let module = wasmi::Module::new(engine, WASM).unwrap();
+    let instance = wasmi::Linker::new(engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let work = instance.get_typed_func::<i32, ()>(&store, "work").unwrap();
+    (store, work)
+}
+
+fn consumed(store: &Store<()>) -> u64 {
+    store.fuel_usage().unwrap().consumed()
+}
+
+fn assert_over_quota(error: Error) {
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Fuel(FuelError::OverQuota)
+    ));
+}
+
+#[test]
+fn accounts_consumed_fuel() {
+    let engine = engine();
+    let (mut store, work) = setup(&engine, 1);
+    let before = consumed(&store);
+    let remaining = store.get_fuel().unwrap();
+    work.call(&mut store, 100).unwrap();
+    let spent = remaining - store.get_fuel().unwrap();
+    assert!(spent > 0);
+    assert_eq!(consumed(&store), before + spent);
+    // Refueling is not accounted as consumption.
+    store.set_fuel(1_000).unwrap();
+    assert_eq!(consumed(&store), before + spent);
+    let usage = store.fuel_usage().unwrap();
+    assert_eq!(usage.share().get(), 1);
+    assert!(!usage.is_over_quota());
+}
+
+#[test]
+fn accounting_only_without_slack() {
+    let engine = engine();
+    assert_eq!(engine.fuel_share_slack(), None);
+    let (mut a, work_a) = setup(&engine, 1);
+    let (_b, _) = setup(&engine, 1);
+    for _ in 0..10 {
+        work_a.call(&mut a, 1_000).unwrap();
+    }
+    assert!(!a.fuel_usage().unwrap().is_over_quota());
+}
+
+#[test]
+fn denies_store_over_quota_until_others_catch_up() {
+    let engine = engine();
+    engine.set_fuel_share_slack(Some(100));
+    let (mut a, work_a) = setup(&engine, 1);
+    let (mut b, work_b) = setup(&engine, 1);
+    work_a.call(&mut a, 1_000).unwrap();
+    assert!(a.fuel_usage().unwrap().is_over_quota());
+    assert_over_quota(work_a.call(&mut a, 1).unwrap_err());
+    // The other store may run and eventually catches up.
+    while consumed(&b) < consumed(&a) {
+        work_b.call(&mut b, 100).unwrap();
+    }
+    assert!(!a.fuel_usage().unwrap().is_over_quota());
+    work_a.call(&mut a, 1).unwrap();
+}
+
+#[test]
+fn shares_are_proportional() {
+    let engine = engine();
+    engine.set_fuel_share_slack(Some(0));
+    let (mut a, work_a) = setup(&engine, 1);
+    let (mut b, work_b) = setup(&engine, 2);
+    let (mut runs_a, mut runs_b) = (0_u32, 0_u32);
+    // Round-robin scheduling that skips stores exceeding their share.
+    for _ in 0..300 {
+        if work_a.call(&mut a, 10).is_ok() {
+            runs_a += 1;
+        }
+        if work_b.call(&mut b, 10).is_ok() {
+            runs_b += 1;
+        }
+    }
+    let ratio = f64::from(runs_b) / f64::from(runs_a);
+    assert!((1.8..=2.2).contains(&ratio), "unexpected ratio: {ratio}");
+}
+
+#[test]
+fn late_stores_start_at_least_served() {
+    let engine = engine();
+    let (mut a, work_a) = setup(&engine, 1);
+    work_a.call(&mut a, 1_000).unwrap();
+    let (b, _) = setup(&engine, 2);
+    assert_eq!(consumed(&b), 2 * consumed(&a));
+}
+
+#[test]
+fn leaving_and_dropping_unregisters() {
+    let engine = engine();
+    engine.set_fuel_share_slack(Some(0));
+    let (mut a, work_a) = setup(&engine, 1);
+    {
+        let (_b, _) = setup(&engine, 1);
+        work_a.call(&mut a, 1_000).unwrap();
+        assert_over_quota(work_a.call(&mut a, 1).unwrap_err());
+    }
+    // The dropped store no longer holds back the remaining store.
+    work_a.call(&mut a, 1).unwrap();
+    let (mut c, _) = setup(&engine, 1);
+    work_a.call(&mut a, 1_000).unwrap();
+    assert_over_quota(work_a.call(&mut a, 1).unwrap_err());
+    c.set_fuel_share(None).unwrap();
+    assert_eq!(c.fuel_share(), None);
+    assert_eq!(c.fuel_usage(), None);
+    work_a.call(&mut a, 1).unwrap();
+}
+
+#[test]
+fn requires_fuel_metering() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    assert!(store.set_fuel_share(NonZeroU32::new(1)).is_err());
+    assert_eq!(store.fuel_share(), None);
+}
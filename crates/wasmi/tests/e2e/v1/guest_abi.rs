@@ -0,0 +1,224 @@
+//! Tests for marshalling strings and byte slices via [`GuestAbi`].
+#![cfg(feature = "guest-abi")]
+
+use wasmi::{Caller, Engine, Extern, GuestAbi, GuestSlice, Instance, Linker, Module, Store};
+
+/// A guest with a bump `malloc` that counts its `free` calls in the `frees` global.
+const MALLOC_WASM: &str = r#"
+    (module
+        (import "host" "greet" (func $greet (param i32 i32) (result i32 i32)))
+        (import "host" "scratch" (func $scratch (param i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (global $next (mut i32) (i32.const 1024))
+        (global $frees (export "frees") (mut i32) (i32.const 0))
+        (func $malloc (export "malloc") (param $len i32) (result i32)
+            (global.get $next)
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+        )
+        (func (export "free") (param i32)
+            (global.set $frees (i32.add (global.get $frees) (i32.const 1)))
+        )
+        ;; Returns a copy of the bytes at `ptr` with every byte incremented by `delta`.
+        (func (export "shift") (param $ptr i32) (param $len i32) (param $delta i32) (result i32 i32)
+            (local $dst i32)
+            (local $i i32)
+            (local.set $dst (call $malloc (local.get $len)))
+            (block $done
+                (loop $continue
+                    (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                    (i32.store8
+                        (i32.add (local.get $dst) (local.get $i))
+                        (i32.add
+                            (i32.load8_u (i32.add (local.get $ptr) (local.get $i)))
+                            (local.get $delta)
+                        )
+                    )
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $dst)
+            (local.get $len)
+        )
+        (func (export "greet") (param i32 i32) (result i32 i32)
+            (call $greet (local.get 0) (local.get 1))
+        )
+        ;; Returns the first byte at `ptr` or 0 if `len` is 0.
+        (func (export "first") (param $ptr i32) (param $len i32) (result i32)
+            (if (result i32) (local.get $len)
+                (then (i32.load8_u (local.get $ptr)))
+                (else (i32.const 0))
+            )
+        )
+        (func (export "scratch") (param i32 i32) (result i32)
+            (call $scratch (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// A guest with a bump `cabi_realloc` that never frees.
+const CABI_WASM: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (global $next (mut i32) (i32.const 2048))
+        (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+            (global.get $next)
+            (global.set $next (i32.add (global.get $next) (local.get 3)))
+        )
+        (func (export "concat") (param i32 i32 i32 i32) (result i32 i32)
+            (local.get 0)
+            (i32.add (local.get 1) (local.get 3))
+        )
+    )
+"#;
+
+/// Instantiates `wasm` with the `host` functions.
+///
+/// - `host.greet` greets its string parameter.
+/// - `host.scratch` passes `n` scratch buffers to the guest `first` callback and sums up its results.
+///   The scratch buffers are freed explicitly if `explicit` is non-zero and upon drop otherwise.
+fn instantiate(wasm: &str) -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap(
+            "host",
+            "greet",
+            |mut caller: Caller<()>, ptr: i32, len: i32| -> Result<(i32, i32), wasmi::Error> {
+                let abi = GuestAbi::from_caller(&caller)?;
+                let name = abi.read_string(&caller, GuestSlice::new(ptr, len))?;
+                let greeting = abi.write_str(&mut caller, &format!("Hello, {name}!"))?;
+                Ok((greeting.ptr as i32, greeting.len as i32))
+            },
+        )
+        .unwrap()
+        .func_wrap(
+            "host",
+            "scratch",
+            |mut caller: Caller<()>, n: i32, explicit: i32| -> Result<i32, wasmi::Error> {
+                let first = caller
+                    .get_export("first")
+                    .and_then(Extern::into_func)
+                    .unwrap()
+                    .typed::<(i32, i32), i32>(&caller)?;
+                let mut scratch = caller.scratch()?;
+                let mut sum = 0;
+                for i in 0..n {
+                    let slice = scratch.write_bytes(&[b'a' + i as u8])?;
+                    sum += first.call(&mut scratch, (slice.ptr as i32, slice.len as i32))?;
+                }
+                // Empty scratch buffers are neither allocated nor freed.
+                let empty = scratch.alloc(0)?;
+                sum += first.call(&mut scratch, (empty.ptr as i32, empty.len as i32))?;
+                if explicit != 0 {
+                    scratch.free()?;
+                }
+                Ok(sum)
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the number of `free` calls of the `MALLOC_WASM` guest.
+fn frees(store: &Store<()>, instance: &Instance) -> i32 {
+    instance
+        .get_global(store, "frees")
+        .unwrap()
+        .get(store)
+        .i32()
+        .unwrap()
+}
+
+#[test]
+fn malloc_and_free() {
+    let (mut store, instance) = instantiate(MALLOC_WASM);
+    let abi = GuestAbi::new(&store, &instance).unwrap();
+    let shift = instance.get_func(&store, "shift").unwrap();
+    let result: String = abi.call(&mut store, &shift, ("HAL", 1)).unwrap();
+    assert_eq!(result, "IBM");
+    // Both the parameter and the result buffers are freed.
+    assert_eq!(frees(&store, &instance), 2);
+    let result: Vec<u8> = abi
+        .call(&mut store, &shift, (vec![1_u8, 2, 3], -1))
+        .unwrap();
+    assert_eq!(result, [0, 1, 2]);
+    assert_eq!(frees(&store, &instance), 4);
+    // Empty slices are neither allocated nor freed.
+    let result: String = abi.call(&mut store, &shift, ("", 1)).unwrap();
+    assert_eq!(result, "");
+    assert_eq!(frees(&store, &instance), 4);
+}
+
+#[test]
+fn host_functions() {
+    let (mut store, instance) = instantiate(MALLOC_WASM);
+    let abi = GuestAbi::new(&store, &instance).unwrap();
+    let greet = instance.get_func(&store, "greet").unwrap();
+    let result: String = abi.call(&mut store, &greet, String::from("Wasmi")).unwrap();
+    assert_eq!(result, "Hello, Wasmi!");
+}
+
+#[test]
+fn scratch_buffers() {
+    let (mut store, instance) = instantiate(MALLOC_WASM);
+    let scratch = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "scratch")
+        .unwrap();
+    assert_eq!(scratch.call(&mut store, (3, 0)).unwrap(), 97 + 98 + 99);
+    assert_eq!(frees(&store, &instance), 3);
+    assert_eq!(scratch.call(&mut store, (2, 1)).unwrap(), 97 + 98);
+    assert_eq!(frees(&store, &instance), 5);
+    assert_eq!(scratch.call(&mut store, (0, 1)).unwrap(), 0);
+    assert_eq!(frees(&store, &instance), 5);
+}
+
+#[test]
+fn cabi_realloc() {
+    let (mut store, instance) = instantiate(CABI_WASM);
+    let abi = GuestAbi::new(&store, &instance).unwrap();
+    let concat = instance.get_func(&store, "concat").unwrap();
+    // Consecutive allocations of the bump allocator are adjacent.
+    let result: String = abi.call(&mut store, &concat, ("foo", "bar")).unwrap();
+    assert_eq!(result, "foobar");
+    let slice = abi.write_bytes(&mut store, b"wasm").unwrap();
+    assert_eq!(abi.read_bytes(&store, slice).unwrap(), b"wasm");
+}
+
+#[test]
+fn errors() {
+    let (mut store, instance) = instantiate(MALLOC_WASM);
+    let abi = GuestAbi::new(&store, &instance).unwrap();
+    let shift = instance.get_func(&store, "shift").unwrap();
+    // Parameters do not match the function type.
+    assert!(abi.call::<_, _, String>(&mut store, &shift, "HAL").is_err());
+    // Results do not match the function type.
+    assert!(abi
+        .call::<_, _, i32>(&mut store, &shift, ("HAL", 1))
+        .is_err());
+    // Invalid UTF-8 results.
+    assert!(abi
+        .call::<_, _, String>(&mut store, &shift, (&[0xFF_u8][..], 0))
+        .is_err());
+    // Out of bounds slices.
+    assert!(abi
+        .read_bytes(&store, GuestSlice::new(0x1_0000, 1))
+        .is_err());
+    // Missing exports.
+    let engine = Engine::default();
+    let module = Module::new(&engine, r#"(module (memory (export "memory") 1))"#).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert!(GuestAbi::new(&store, &instance).is_err());
+}
@@ -0,0 +1,136 @@
+//! Tests for the `hardened-dispatch` crate feature.
+#![cfg(feature = "hardened-dispatch")]
+
+use wasmi::{Engine, Instance, Linker, Module, Store};
+
+fn instantiate(wasm: &str) -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn hardened_select_works() {
+    let (mut store, instance) = instantiate(
+        r#"
+        (module
+            (func (export "select_i64") (param i32 i64 i64) (result i64)
+                (select (local.get 1) (local.get 2) (local.get 0))
+            )
+            (func (export "select_imm") (param i32) (result i32)
+                (select (i32.const -1) (i32.const 42) (local.get 0))
+            )
+        )
+        "#,
+    );
+    let select_i64 = instance
+        .get_typed_func::<(i32, i64, i64), i64>(&store, "select_i64")
+        .unwrap();
+    let select_imm = instance
+        .get_typed_func::<i32, i32>(&store, "select_imm")
+        .unwrap();
+    for condition in [1, -1, i32::MIN, 0x100] {
+        assert_eq!(
+            select_i64
+                .call(&mut store, (condition, i64::MIN, i64::MAX))
+                .unwrap(),
+            i64::MIN
+        );
+        assert_eq!(select_imm.call(&mut store, condition).unwrap(), -1);
+    }
+    assert_eq!(
+        select_i64
+            .call(&mut store, (0, i64::MIN, i64::MAX))
+            .unwrap(),
+        i64::MAX
+    );
+    assert_eq!(select_imm.call(&mut store, 0).unwrap(), 42);
+}
+
+#[test]
+fn hardened_comparisons_work() {
+    let (mut store, instance) = instantiate(
+        r#"
+        (module
+            (func (export "cmp_i32") (param $a i32) (param $b i32) (result i32)
+                (i32.or
+                    (i32.or
+                        (i32.or (i32.eq (local.get $a) (local.get $b))
+                                (i32.shl (i32.ne (local.get $a) (local.get $b)) (i32.const 1)))
+                        (i32.or (i32.shl (i32.lt_s (local.get $a) (local.get $b)) (i32.const 2))
+                                (i32.shl (i32.lt_u (local.get $a) (local.get $b)) (i32.const 3)))
+                    )
+                    (i32.or
+                        (i32.or (i32.shl (i32.ge_s (local.get $a) (local.get $b)) (i32.const 4))
+                                (i32.shl (i32.ge_u (local.get $a) (local.get $b)) (i32.const 5)))
+                        (i32.shl (i32.eqz (local.get $a)) (i32.const 6))
+                    )
+                )
+            )
+            (func (export "cmp_i64") (param $a i64) (param $b i64) (result i32)
+                (i32.or
+                    (i32.or
+                        (i32.or (i64.eq (local.get $a) (local.get $b))
+                                (i32.shl (i64.ne (local.get $a) (local.get $b)) (i32.const 1)))
+                        (i32.or (i32.shl (i64.lt_s (local.get $a) (local.get $b)) (i32.const 2))
+                                (i32.shl (i64.lt_u (local.get $a) (local.get $b)) (i32.const 3)))
+                    )
+                    (i32.or
+                        (i32.or (i32.shl (i64.gt_s (local.get $a) (local.get $b)) (i32.const 4))
+                                (i32.shl (i64.le_u (local.get $a) (local.get $b)) (i32.const 5)))
+                        (i32.shl (i64.eqz (local.get $a)) (i32.const 6))
+                    )
+                )
+            )
+        )
+        "#,
+    );
+    let cmp_i32 = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "cmp_i32")
+        .unwrap();
+    let cmp_i64 = instance
+        .get_typed_func::<(i64, i64), i32>(&store, "cmp_i64")
+        .unwrap();
+    let bits = |flags: [bool; 7]| {
+        flags
+            .iter()
+            .enumerate()
+            .fold(0, |bits, (n, flag)| bits | (i32::from(*flag) << n))
+    };
+    let values = [0, 1, -1, i64::MIN, i64::MAX, i64::from(i32::MIN), 42];
+    for &a in &values {
+        for &b in &values {
+            let (a32, b32) = (a as i32, b as i32);
+            assert_eq!(
+                cmp_i32.call(&mut store, (a32, b32)).unwrap(),
+                bits([
+                    a32 == b32,
+                    a32 != b32,
+                    a32 < b32,
+                    (a32 as u32) < (b32 as u32),
+                    a32 >= b32,
+                    (a32 as u32) >= (b32 as u32),
+                    a32 == 0,
+                ]),
+            );
+            assert_eq!(
+                cmp_i64.call(&mut store, (a, b)).unwrap(),
+                bits([
+                    a == b,
+                    a != b,
+                    a < b,
+                    (a as u64) < (b as u64),
+                    a > b,
+                    (a as u64) <= (b as u64),
+                    a == 0,
+                ]),
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Tests for the host API definitions derived via [`wasmi::host_api`].
+#![cfg(feature = "bindgen")]
+
+use wasmi::{Engine, Error, Linker, Module, Store};
+
+/// The host API provided to the guests of the tests.
+#[wasmi::host_api(module = "host")]
+pub trait Host {
+    /// Logs `value`.
+    fn log(&mut self, value: i32);
+    fn add(&mut self, a: i64, b: i64) -> i64;
+    #[host_api(name = "checked-div")]
+    fn div(&mut self, a: u32, b: u32) -> Result<u32, Error>;
+    fn swap(&mut self, a: f32, b: i32) -> (i32, f32);
+}
+
+/// The host state implementing [`Host`].
+#[derive(Debug, Default)]
+struct State {
+    logged: Vec<i32>,
+}
+
+impl Host for State {
+    fn log(&mut self, value: i32) {
+        self.logged.push(value);
+    }
+
+    fn add(&mut self, a: i64, b: i64) -> i64 {
+        a + b
+    }
+
+    fn div(&mut self, a: u32, b: u32) -> Result<u32, Error> {
+        a.checked_div(b)
+            .ok_or_else(|| Error::new("division by zero"))
+    }
+
+    fn swap(&mut self, a: f32, b: i32) -> (i32, f32) {
+        (b, a)
+    }
+}
+
+const WASM: &str = r#"
+    (module
+        (import "host" "log" (func $log (param i32)))
+        (import "host" "add" (func $add (param i64 i64) (result i64)))
+        (import "host" "checked-div" (func $div (param i32 i32) (result i32)))
+        (import "host" "swap" (func $swap (param f32 i32) (result i32 f32)))
+        (func (export "run") (param i32 i32) (result i32)
+            (call $log (local.get 0))
+            (drop (call $add (i64.const 1) (i64.const 2)))
+            (call $swap (f32.const 1) (i32.const 2))
+            (drop)
+            (drop)
+            (call $div (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+#[test]
+fn add_to_linker() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, State::default());
+    let mut linker = <Linker<State>>::new(&engine);
+    HostHostApi::add_to_linker(&mut linker, |state| state).unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(i32, i32), i32>(&store, "run")
+        .unwrap();
+    assert_eq!(run.call(&mut store, (10, 3)).unwrap(), 3);
+    let error = run.call(&mut store, (7, 0)).unwrap_err();
+    assert!(error.to_string().contains("division by zero"));
+    assert_eq!(store.data().logged, [10, 7]);
+    // Defining the host API twice fails.
+    assert!(HostHostApi::add_to_linker(&mut linker, |state| state).is_err());
+}
+
+#[test]
+fn names_and_guest_externs() {
+    assert_eq!(HostHostApi::MODULE, "host");
+    assert_eq!(HostHostApi::NAMES, ["log", "add", "checked-div", "swap"]);
+    let externs = HostHostApi::GUEST_EXTERNS;
+    assert!(externs.starts_with("#[link(wasm_import_module = \"host\")]\nextern \"C\" {\n"));
+    assert!(externs.contains("    pub fn log(value: i32);\n"));
+    assert!(externs.contains("    pub fn add(a: i64, b: i64) -> i64;\n"));
+    assert!(externs
+        .contains("    #[link_name = \"checked-div\"]\n    pub fn div(a: u32, b: u32) -> u32;\n"));
+}
@@ -0,0 +1,124 @@
+//! Tests for batched dispatch of consecutive calls to batched host functions.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    core::{TrapCode, ValType},
+    Engine,
+    Error,
+    Func,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Val,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "cipher" "encrypt" (func $encrypt (param i64 i64) (result i64)))
+        (func (export "encrypt4") (param $key i64) (result i64)
+            (local $a i64) (local $b i64) (local $c i64) (local $d i64)
+            (local.set $a (call $encrypt (i64.const 1) (local.get $key)))
+            (local.set $b (call $encrypt (i64.const 2) (local.get $key)))
+            (local.set $c (call $encrypt (i64.const 4) (local.get $key)))
+            (local.set $d (call $encrypt (i64.const 8) (local.get $key)))
+            (i64.add
+                (i64.add (local.get $a) (local.get $b))
+                (i64.add (local.get $c) (local.get $d))
+            )
+        )
+        (func (export "encrypt_twice") (param $value i64) (param $key i64) (result i64)
+            (call $encrypt
+                (call $encrypt (local.get $value) (local.get $key))
+                (local.get $key)
+            )
+        )
+    )
+"#;
+
+/// Returns the type of the toy `encrypt` host function.
+fn encrypt_type() -> FuncType {
+    FuncType::new([ValType::I64; 2], [ValType::I64])
+}
+
+/// A toy cipher host function that records the sizes of its dispatched batches.
+fn encrypt(
+    batches: &Mutex<Vec<usize>>,
+    len: usize,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    batches.lock().unwrap().push(len);
+    for (params, result) in params.chunks(2).zip(results) {
+        let [value, key] = [0, 1].map(|n| params[n].i64().unwrap());
+        if key == 0 {
+            return Err(Error::from(TrapCode::BadSignature));
+        }
+        *result = Val::I64(value ^ key);
+    }
+    Ok(())
+}
+
+fn setup() -> (Store<()>, Instance, Arc<Mutex<Vec<usize>>>) {
+    let batches = <Arc<Mutex<Vec<usize>>>>::default();
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let recorded = batches.clone();
+    linker
+        .func_new_batched(
+            "cipher",
+            "encrypt",
+            encrypt_type(),
+            move |_caller, len, params, results| encrypt(&recorded, len, params, results),
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, batches)
+}
+
+#[test]
+fn consecutive_calls_are_batched() {
+    let (mut store, instance, batches) = setup();
+    let encrypt4 = instance
+        .get_typed_func::<i64, i64>(&store, "encrypt4")
+        .unwrap();
+    assert_eq!(encrypt4.call(&mut store, 0x10).unwrap(), 0x4F);
+    assert_eq!(*batches.lock().unwrap(), [4]);
+    // Traps in batches are propagated as usual.
+    let error = encrypt4.call(&mut store, 0).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+}
+
+#[test]
+fn dependent_calls_are_not_batched() {
+    let (mut store, instance, batches) = setup();
+    let encrypt_twice = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "encrypt_twice")
+        .unwrap();
+    assert_eq!(encrypt_twice.call(&mut store, (42, 7)).unwrap(), 42);
+    assert_eq!(*batches.lock().unwrap(), [1, 1]);
+}
+
+#[test]
+fn host_calls_are_batches_of_one() {
+    let batches = <Arc<Mutex<Vec<usize>>>>::default();
+    let mut store = Store::new(&Engine::default(), ());
+    let recorded = batches.clone();
+    let func = Func::new_batched(
+        &mut store,
+        encrypt_type(),
+        move |_caller, len, params, results| encrypt(&recorded, len, params, results),
+    );
+    let mut results = [Val::I64(0)];
+    func.call(&mut store, &[Val::I64(42), Val::I64(7)], &mut results)
+        .unwrap();
+    assert_eq!(results[0].i64(), Some(42 ^ 7));
+    assert_eq!(*batches.lock().unwrap(), [1]);
+}
@@ -0,0 +1,111 @@
+//! Tests for recording and replaying host function calls.
+
+use wasmi::{core::TrapCode, Caller, Config, Engine, Error, Extern, Linker, Module, Store};
+
+const GUEST: &str = r#"
+    (module
+        (import "env" "next" (func $next (param i32) (result i32)))
+        (import "env" "fill" (func $fill (param i32)))
+        (memory (export "memory") 1)
+        (func (export "run") (param i32) (result i32)
+            (call $fill (i32.const 8))
+            (i32.add
+                (call $next (local.get 0))
+                (i32.load (i32.const 8))
+            )
+        )
+    )
+"#;
+
+/// Creates a [`Store`] with a `GUEST` instance whose host functions are based on `seed`.
+///
+/// - `next(x)` returns `x` plus a host counter that starts at `seed`.
+/// - `fill(ptr)` writes the host counter to the guest memory at `ptr`.
+/// - Both functions consume `seed` fuel and trap if the counter exceeds `seed + 2`.
+fn setup(seed: i32) -> (Store<i32>, wasmi::TypedFunc<i32, i32>) {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, seed);
+    store.set_fuel(1_000).unwrap();
+    let mut linker = <Linker<i32>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "next",
+            move |mut caller: Caller<'_, i32>, x: i32| -> Result<i32, Error> {
+                let fuel = caller.get_fuel()?;
+                caller.set_fuel(fuel - seed as u64)?;
+                *caller.data_mut() += 1;
+                if *caller.data() > seed + 2 {
+                    return Err(Error::from(TrapCode::UnreachableCodeReached));
+                }
+                Ok(x + *caller.data())
+            },
+        )
+        .unwrap();
+    linker
+        .func_wrap("env", "fill", |mut caller: Caller<'_, i32>, ptr: i32| {
+            let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+                panic!("missing `memory` export")
+            };
+            let value = *caller.data();
+            memory
+                .write(&mut caller, ptr as usize, &value.to_le_bytes())
+                .unwrap();
+        })
+        .unwrap();
+    let module = Module::new(&engine, GUEST).unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<i32, i32>(&store, "run")
+        .unwrap();
+    (store, run)
+}
+
+#[test]
+fn host_call_replay_reproduces_execution() {
+    let (mut store, run) = setup(10);
+    store.record_host_calls();
+    let results = [
+        run.call(&mut store, 1).unwrap(),
+        run.call(&mut store, 2).unwrap(),
+    ];
+    let error = run.call(&mut store, 3).unwrap_err();
+    assert_eq!(results, [1 + 11 + 10, 2 + 12 + 11]);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    let fuel = store.get_fuel().unwrap();
+    let recording = store.take_host_call_recording().unwrap();
+    assert_eq!(recording.len(), 6);
+    // Replaying with different host functions reproduces the recorded execution.
+    let (mut store, run) = setup(100);
+    store.replay_host_calls(recording.clone());
+    assert_eq!(run.call(&mut store, 1).unwrap(), results[0]);
+    assert_eq!(run.call(&mut store, 2).unwrap(), results[1]);
+    let error = run.call(&mut store, 3).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert_eq!(store.get_fuel().unwrap(), fuel);
+    // The real host functions have not been invoked.
+    assert_eq!(*store.data(), 100);
+    assert_eq!(store.take_host_call_recording(), Some(recording));
+    assert_eq!(store.take_host_call_recording(), None);
+}
+
+#[test]
+fn host_call_replay_detects_divergence() {
+    let (mut store, run) = setup(0);
+    store.record_host_calls();
+    run.call(&mut store, 1).unwrap();
+    let recording = store.take_host_call_recording().unwrap();
+    let (mut store, run) = setup(0);
+    store.replay_host_calls(recording.clone());
+    let error = run.call(&mut store, 2).unwrap_err();
+    assert!(error.to_string().contains("diverged"));
+    store.replay_host_calls(recording);
+    run.call(&mut store, 1).unwrap();
+    let error = run.call(&mut store, 1).unwrap_err();
+    assert!(error.to_string().contains("exhausted"));
+}
@@ -0,0 +1,168 @@
+//! Tests that [`HostError`]s returned by host functions can be downcast after crossing guest frames.
+
+use core::fmt;
+use wasmi::{
+    core::HostError,
+    Caller,
+    Engine,
+    Error,
+    Func,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+    TypedResumableCall,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+struct MyError(i32);
+
+impl fmt::Display for MyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "my error: {}", self.0)
+    }
+}
+
+impl HostError for MyError {}
+
+const WASM: &str = r#"
+    (module
+        (import "env" "fail" (func $fail (param i32)))
+        (import "env" "reenter" (func $reenter (param i32)))
+        (type $t (func (param i32)))
+        (table funcref (elem $fail $deep $reenter))
+        (func (export "direct") (param i32)
+            (call $fail (local.get 0))
+        )
+        (func (export "indirect") (param i32)
+            (call_indirect (type $t) (local.get 0) (i32.const 0))
+        )
+        (func (export "tail") (param i32)
+            (return_call $fail (local.get 0))
+        )
+        (func (export "tail_indirect") (param i32)
+            (return_call_indirect (type $t) (local.get 0) (i32.const 0))
+        )
+        (func $deep (export "deep") (param i32)
+            (call $tail_chain (local.get 0) (i32.const 10))
+        )
+        (func $tail_chain (param i32 i32)
+            (if (i32.eqz (local.get 1))
+                (then (return_call_indirect (type $t) (local.get 0) (i32.const 0)))
+            )
+            (return_call $tail_chain (local.get 0) (i32.sub (local.get 1) (i32.const 1)))
+        )
+        (func (export "nested") (param i32)
+            (call_indirect (type $t) (local.get 0) (i32.const 2))
+        )
+    )
+"#;
+
+fn setup() -> (Store<()>, wasmi::Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "fail",
+            |_: Caller<()>, code: i32| -> Result<(), Error> { Err(Error::host(MyError(code))) },
+        )
+        .unwrap();
+    linker
+        .func_wrap(
+            "env",
+            "reenter",
+            |mut caller: Caller<()>, code: i32| -> Result<(), Error> {
+                // Re-enters Wasm which fails in a nested host function call.
+                let deep = caller.get_export("deep").unwrap().into_func().unwrap();
+                deep.typed::<i32, ()>(&caller)?.call(&mut caller, code)
+            },
+        )
+        .unwrap();
+    let module = Module::new(&engine, WASM).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+fn typed(store: &Store<()>, instance: wasmi::Instance, name: &str) -> TypedFunc<i32, ()> {
+    instance.get_typed_func::<i32, ()>(store, name).unwrap()
+}
+
+const EXPORTS: &[&str] = &[
+    "direct",
+    "indirect",
+    "tail",
+    "tail_indirect",
+    "deep",
+    "nested",
+];
+
+#[test]
+fn downcast_typed() {
+    let (mut store, instance) = setup();
+    for (code, name) in EXPORTS.iter().enumerate() {
+        let code = code as i32;
+        let mut error = typed(&store, instance, name)
+            .call(&mut store, code)
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<MyError>(),
+            Some(&MyError(code)),
+            "{name}"
+        );
+        assert_eq!(error.downcast_mut::<MyError>(), Some(&mut MyError(code)));
+        assert_eq!(error.downcast::<MyError>(), Some(MyError(code)));
+    }
+}
+
+#[test]
+fn downcast_untyped() {
+    let (mut store, instance) = setup();
+    for (code, name) in EXPORTS.iter().enumerate() {
+        let code = code as i32;
+        let func: Func = instance.get_func(&store, name).unwrap();
+        let error = func.call(&mut store, &[code.into()], &mut []).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<MyError>(),
+            Some(&MyError(code)),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn downcast_resumable() {
+    let (mut store, instance) = setup();
+    for (code, name) in EXPORTS.iter().enumerate() {
+        let code = code as i32;
+        // Note: host functions tail called by the root Wasm frame cannot be resumed
+        //       since there is no Wasm caller left and thus return their error directly.
+        match typed(&store, instance, name).call_resumable(&mut store, code) {
+            Ok(TypedResumableCall::Resumable(invocation)) => {
+                let error = invocation.host_error();
+                assert_eq!(
+                    error.downcast_ref::<MyError>(),
+                    Some(&MyError(code)),
+                    "{name}"
+                );
+            }
+            Ok(TypedResumableCall::Finished(())) => panic!("{name}: expected the call to fail"),
+            Err(error) => {
+                assert!(
+                    name.starts_with("tail"),
+                    "{name}: expected the call to be resumable"
+                );
+                assert_eq!(
+                    error.downcast_ref::<MyError>(),
+                    Some(&MyError(code)),
+                    "{name}"
+                );
+            }
+        }
+    }
+}
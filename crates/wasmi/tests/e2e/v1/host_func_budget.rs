@@ -0,0 +1,92 @@
+//! Tests for per-host-function time budgets via [`Linker::func_budget`].
+
+use std::{thread, time::Duration};
+use wasmi::{
+    core::TrapCode,
+    errors::LinkerError,
+    BudgetExceeded,
+    Engine,
+    HostFuncBudget,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+    TypedResumableCall,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "sleep" (func $sleep (param i32) (result i32)))
+        (func (export "sleep") (param i32) (result i32)
+            (i32.add (call $sleep (local.get 0)) (i32.const 1))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] with a `host::sleep` function that has the given `budget`.
+///
+/// `sleep(ms)` sleeps for `ms` milliseconds and returns `ms + 1`.
+fn setup(budget: HostFuncBudget) -> (Store<()>, TypedFunc<i32, i32>) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("host", "sleep", |ms: i32| -> i32 {
+            thread::sleep(Duration::from_millis(ms as u64));
+            ms
+        })
+        .unwrap()
+        .func_budget("host", "sleep", budget)
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let sleep = instance.get_typed_func(&store, "sleep").unwrap();
+    (store, sleep)
+}
+
+#[test]
+fn within_budget() {
+    let (mut store, sleep) = setup(HostFuncBudget::trap(Duration::from_secs(10)));
+    assert_eq!(sleep.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+fn overrun_traps() {
+    let (mut store, sleep) = setup(HostFuncBudget::trap(Duration::from_millis(1)));
+    let error = sleep.call(&mut store, 20).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::HostBudgetExceeded));
+    // The store remains usable after the trap.
+    assert_eq!(sleep.call(&mut store, 0).unwrap(), 1);
+}
+
+#[test]
+fn overrun_suspends() {
+    let limit = Duration::from_millis(1);
+    let (mut store, sleep) = setup(HostFuncBudget::suspend(limit));
+    let mut invocation = match sleep.call_resumable(&mut store, 20).unwrap() {
+        TypedResumableCall::Resumable(invocation) => invocation,
+        TypedResumableCall::Finished(_) => panic!("expected TypedResumableCall::Resumable"),
+    };
+    let exceeded = *invocation.payload_mut::<BudgetExceeded>().unwrap();
+    assert_eq!(exceeded.limit, limit);
+    assert!(exceeded.elapsed >= Duration::from_millis(20));
+    match invocation.resume_typed(&mut store, 41_i32).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 42),
+        TypedResumableCall::Resumable(_) => panic!("expected TypedResumableCall::Finished"),
+    }
+}
+
+#[test]
+fn missing_host_func() {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    let budget = HostFuncBudget::trap(Duration::from_millis(1));
+    assert!(matches!(
+        linker.func_budget("host", "missing", budget),
+        Err(LinkerError::MissingHostFunc { .. })
+    ));
+}
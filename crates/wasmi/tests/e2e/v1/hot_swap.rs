@@ -0,0 +1,233 @@
+//! Tests for live-updating the code of an instance via [`Instance::hot_swap`].
+
+use wasmi::{
+    errors::{ErrorKind, InstantiationError},
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Creates a version of a counter module whose step applies `op` to the counter.
+///
+/// The `extra` items are appended to the module.
+fn counter(op: &str, extra: &str) -> String {
+    format!(
+        r#"
+        (module
+            (import "env" "log" (func $log (param i32)))
+            (memory (export "memory") 1)
+            (global $counter (export "counter") (mut i32) (i32.const 0))
+            (table 1 funcref)
+            (type $unary (func (param i32) (result i32)))
+            (elem (i32.const 0) $op)
+            (data (i32.const 0) "{op}")
+            (func $op (param i32) (result i32)
+                ({op} (local.get 0) (i32.const 2))
+            )
+            (func (export "step") (result i32)
+                (global.set $counter
+                    (call_indirect (type $unary) (global.get $counter) (i32.const 0))
+                )
+                (call $log (global.get $counter))
+                (global.get $counter)
+            )
+            (func $start
+                (global.set $counter (i32.const 1000))
+            )
+            (start $start)
+            {extra}
+        )
+        "#
+    )
+}
+
+/// Instantiates the first version of the counter module.
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "log", |_: i32| {}).unwrap();
+    let module = Module::new(&engine, counter("i32.add", "")).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+fn step(store: &mut Store<()>, instance: Instance) -> i32 {
+    instance
+        .get_typed_func::<(), i32>(&*store, "step")
+        .unwrap()
+        .call(store, ())
+        .unwrap()
+}
+
+#[test]
+fn hot_swap_preserves_state() {
+    let (mut store, old) = setup();
+    assert_eq!(step(&mut store, old), 1002);
+    assert_eq!(step(&mut store, old), 1004);
+    let module = Module::new(store.engine(), counter("i32.mul", "")).unwrap();
+    let new = old.hot_swap(&mut store, &module).unwrap();
+    // The global state is preserved and the `start` function did not run.
+    assert_eq!(step(&mut store, new), 2008);
+    // The linear memory is shared and its active data segment was not applied.
+    let memory = new.get_memory(&store, "memory").unwrap();
+    assert_eq!(&memory.data(&store)[..7], b"i32.add");
+    assert!(old.get_memory(&store, "memory").is_some());
+    // The table entries of the old instance are redirected to the new code.
+    assert_eq!(step(&mut store, old), 4016);
+}
+
+/// Asserts that rejected hot-swaps did not allocate an [`Instance`] after `last` in the `store`.
+fn assert_no_instance_allocated(store: &mut Store<()>, old: Instance, last: Instance) {
+    let module = Module::new(store.engine(), counter("i32.mul", "")).unwrap();
+    let new = old.hot_swap(&mut *store, &module).unwrap();
+    assert_eq!(new.id(&*store), last.id(&*store) + 1);
+}
+
+fn assert_hot_swap_error(extra: &str, op: &str, check: impl FnOnce(&InstantiationError) -> bool) {
+    let (mut store, old) = setup();
+    let module = Module::new(store.engine(), counter(op, extra)).unwrap();
+    let error = old.hot_swap(&mut store, &module).unwrap_err();
+    match error.kind() {
+        ErrorKind::Instantiation(error) => assert!(check(error), "unexpected error: {error}"),
+        _ => panic!("unexpected error: {error}"),
+    }
+    // The old instance is still usable.
+    assert_eq!(step(&mut store, old), 1002);
+    assert_no_instance_allocated(&mut store, old, old);
+}
+
+#[test]
+fn hot_swap_rejects_new_globals() {
+    assert_hot_swap_error("(global i32 (i32.const 0))", "i32.mul", |error| {
+        matches!(
+            error,
+            InstantiationError::HotSwapMismatch {
+                kind: "globals",
+                expected: 1,
+                found: 2
+            }
+        )
+    });
+}
+
+#[test]
+fn hot_swap_rejects_changed_export_signatures() {
+    let extra = r#"(func (export "reset") (param i32))"#;
+    let (mut store, old) = setup();
+    let module = Module::new(store.engine(), counter("i32.mul", extra)).unwrap();
+    let new = old.hot_swap(&mut store, &module).unwrap();
+    assert!(new.get_func(&store, "reset").is_some());
+    let renamed = r#"
+        (module
+            (import "env" "log" (func $log (param i32)))
+            (memory 1)
+            (global (mut i32) (i32.const 0))
+            (table 1 funcref)
+            (func (export "step") (param i32))
+        )
+    "#;
+    let module = Module::new(store.engine(), renamed).unwrap();
+    let error = old.hot_swap(&mut store, &module).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::SignatureMismatch { .. })
+    ));
+    assert_no_instance_allocated(&mut store, old, new);
+}
+
+#[test]
+fn hot_swap_rejects_mismatching_imports() {
+    let (mut store, old) = setup();
+    let module = r#"
+        (module
+            (import "env" "log" (func $log (param i64)))
+            (memory 1)
+            (global (mut i32) (i32.const 0))
+            (table 1 funcref)
+        )
+    "#;
+    let module = Module::new(store.engine(), module).unwrap();
+    let error = old.hot_swap(&mut store, &module).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::SignatureMismatch { .. })
+    ));
+}
+
+#[test]
+fn hot_swap_rejects_more_imported_funcs() {
+    // Note: the new import must not be bound to an internal function of the old instance.
+    let module = r#"
+        (module
+            (import "env" "log" (func $log (param i32)))
+            (import "env" "secret" (func $secret (param i32) (result i32)))
+            (memory 1)
+            (global (mut i32) (i32.const 0))
+            (table 1 funcref)
+            (func (export "leak") (result i32) (call $secret (i32.const 0)))
+        )
+    "#;
+    assert_imported_funcs_mismatch(module, 2);
+}
+
+#[test]
+fn hot_swap_rejects_fewer_imported_funcs() {
+    let module = r#"
+        (module
+            (memory 1)
+            (global (mut i32) (i32.const 0))
+            (table 1 funcref)
+            (func $log (param i32))
+        )
+    "#;
+    assert_imported_funcs_mismatch(module, 0);
+}
+
+/// Asserts that hot-swapping `module` is rejected since it imports `found` functions.
+fn assert_imported_funcs_mismatch(module: &str, found: usize) {
+    let (mut store, old) = setup();
+    let module = Module::new(store.engine(), module).unwrap();
+    let error = old.hot_swap(&mut store, &module).unwrap_err();
+    assert!(
+        matches!(
+            error.kind(),
+            ErrorKind::Instantiation(InstantiationError::HotSwapMismatch {
+                kind: "imported functions",
+                expected: 1,
+                found: actual,
+            }) if *actual == found
+        ),
+        "unexpected error: {error}"
+    );
+    assert_eq!(step(&mut store, old), 1002);
+    assert_no_instance_allocated(&mut store, old, old);
+}
+
+#[test]
+fn hot_swap_rejects_changed_table_signatures() {
+    let (mut store, old) = setup();
+    let module = r#"
+        (module
+            (import "env" "log" (func $log (param i32)))
+            (memory 1)
+            (global (mut i32) (i32.const 0))
+            (table 1 funcref)
+            (func $op (param i64) (result i64) (local.get 0))
+        )
+    "#;
+    let module = Module::new(store.engine(), module).unwrap();
+    let error = old.hot_swap(&mut store, &module).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::SignatureMismatch { .. })
+    ));
+    assert_eq!(step(&mut store, old), 1002);
+    assert_no_instance_allocated(&mut store, old, old);
+}
@@ -0,0 +1,173 @@
+//! Tests for the `wasmi:i128` instruction extension of the `i128` crate feature.
+#![cfg(feature = "i128")]
+
+use wasmi::{
+    core::TrapCode,
+    Engine,
+    I128Ops,
+    Instance,
+    InstructionExtensions,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "wasmi:i128" "add" (func $add (param i64 i64 i64 i64) (result i64 i64)))
+        (import "wasmi:i128" "mul" (func $mul (param i64 i64 i64 i64) (result i64 i64)))
+        (import "wasmi:i128" "add_sat_s" (func $add_sat_s (param i64 i64 i64 i64) (result i64 i64)))
+        (import "wasmi:i128" "sub_sat_u" (func $sub_sat_u (param i64 i64 i64 i64) (result i64 i64)))
+        (import "wasmi:i128" "mul_checked_u" (func $mul_checked_u (param i64 i64 i64 i64) (result i64 i64)))
+        (import "wasmi:i128" "div_s" (func $div_s (param i64 i64 i64 i64) (result i64 i64)))
+        (import "wasmi:i128" "div_rem_u" (func $div_rem_u (param i64 i64 i64 i64) (result i64 i64 i64 i64)))
+        (import "wasmi:i128" "mul_wide_s" (func $mul_wide_s (param i64 i64) (result i64 i64)))
+        (import "wasmi:i128" "mul_shr_s" (func $mul_shr_s (param i64 i64 i32) (result i64)))
+        (func (export "add") (param i64 i64 i64 i64) (result i64 i64)
+            (call $add (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "mul") (param i64 i64 i64 i64) (result i64 i64)
+            (call $mul (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "add_sat_s") (param i64 i64 i64 i64) (result i64 i64)
+            (call $add_sat_s (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "sub_sat_u") (param i64 i64 i64 i64) (result i64 i64)
+            (call $sub_sat_u (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "mul_checked_u") (param i64 i64 i64 i64) (result i64 i64)
+            (call $mul_checked_u (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "div_s") (param i64 i64 i64 i64) (result i64 i64)
+            (call $div_s (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "div_rem_u") (param i64 i64 i64 i64) (result i64 i64 i64 i64)
+            (call $div_rem_u (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "mul_wide_s") (param i64 i64) (result i64 i64)
+            (call $mul_wide_s (local.get 0) (local.get 1))
+        )
+        ;; Multiplies two Q32.32 fixed-point numbers.
+        (func (export "mul_q32") (param i64 i64) (result i64)
+            (call $mul_shr_s (local.get 0) (local.get 1) (i32.const 32))
+        )
+    )
+"#;
+
+/// A 128-bit integer passed as `(lo, hi)` pair.
+type I128 = (i64, i64);
+
+fn split(value: i128) -> I128 {
+    (value as i64, (value >> 64) as i64)
+}
+
+fn join((lo, hi): I128) -> i128 {
+    (i128::from(hi) << 64) | i128::from(lo as u64)
+}
+
+fn setup() -> (Store<()>, Instance) {
+    let mut extensions = InstructionExtensions::new();
+    extensions.register(I128Ops).unwrap();
+    let engine = Engine::with_extensions(&Default::default(), extensions);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+fn binary(
+    store: &Store<()>,
+    instance: Instance,
+    name: &str,
+) -> TypedFunc<(i64, i64, i64, i64), I128> {
+    instance.get_typed_func(store, name).unwrap()
+}
+
+fn call(
+    store: &mut Store<()>,
+    func: TypedFunc<(i64, i64, i64, i64), I128>,
+    lhs: i128,
+    rhs: i128,
+) -> Result<i128, wasmi::Error> {
+    let ((a, b), (c, d)) = (split(lhs), split(rhs));
+    func.call(store, (a, b, c, d)).map(join)
+}
+
+#[test]
+fn wrapping_arithmetic_works() {
+    let (mut store, instance) = setup();
+    let add = binary(&store, instance, "add");
+    let mul = binary(&store, instance, "mul");
+    let big = i128::from(u64::MAX) + 1;
+    assert_eq!(call(&mut store, add, big - 1, 1).unwrap(), big);
+    assert_eq!(call(&mut store, add, i128::MAX, 1).unwrap(), i128::MIN);
+    assert_eq!(call(&mut store, mul, big, -3).unwrap(), -3 * big);
+    assert_eq!(call(&mut store, mul, big, big).unwrap(), 0);
+}
+
+#[test]
+fn saturating_and_checked_arithmetic_works() {
+    let (mut store, instance) = setup();
+    let add_sat_s = binary(&store, instance, "add_sat_s");
+    let sub_sat_u = binary(&store, instance, "sub_sat_u");
+    let mul_checked_u = binary(&store, instance, "mul_checked_u");
+    assert_eq!(
+        call(&mut store, add_sat_s, i128::MAX, 1).unwrap(),
+        i128::MAX
+    );
+    assert_eq!(
+        call(&mut store, add_sat_s, i128::MIN, -1).unwrap(),
+        i128::MIN
+    );
+    assert_eq!(call(&mut store, sub_sat_u, 1, 2).unwrap(), 0);
+    assert_eq!(
+        call(&mut store, mul_checked_u, 1 << 63, 1 << 63).unwrap(),
+        1 << 126
+    );
+    let error = call(&mut store, mul_checked_u, 1 << 64, 1 << 64).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerOverflow));
+}
+
+#[test]
+fn division_works() {
+    let (mut store, instance) = setup();
+    let div_s = binary(&store, instance, "div_s");
+    assert_eq!(call(&mut store, div_s, -7, 2).unwrap(), -3);
+    let error = call(&mut store, div_s, 1, 0).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    let error = call(&mut store, div_s, i128::MIN, -1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerOverflow));
+    let div_rem_u = instance
+        .get_typed_func::<(i64, i64, i64, i64), (i64, i64, i64, i64)>(&store, "div_rem_u")
+        .unwrap();
+    let lhs = split(u128::MAX as i128);
+    let rhs = split(10);
+    let (q_lo, q_hi, r_lo, r_hi) = div_rem_u
+        .call(&mut store, (lhs.0, lhs.1, rhs.0, rhs.1))
+        .unwrap();
+    assert_eq!(join((q_lo, q_hi)) as u128, u128::MAX / 10);
+    assert_eq!(join((r_lo, r_hi)), 5);
+}
+
+#[test]
+fn wide_and_fixed_point_multiplication_works() {
+    let (mut store, instance) = setup();
+    let mul_wide_s = instance
+        .get_typed_func::<(i64, i64), I128>(&store, "mul_wide_s")
+        .unwrap();
+    let product = mul_wide_s.call(&mut store, (i64::MIN, i64::MAX)).unwrap();
+    assert_eq!(join(product), i128::from(i64::MIN) * i128::from(i64::MAX));
+    let mul_q32 = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "mul_q32")
+        .unwrap();
+    let q32 = |value: f64| (value * (1_u64 << 32) as f64) as i64;
+    assert_eq!(
+        mul_q32.call(&mut store, (q32(1.5), q32(-2.25))).unwrap(),
+        q32(-3.375)
+    );
+}
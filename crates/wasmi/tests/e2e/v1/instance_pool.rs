@@ -0,0 +1,105 @@
+//! Tests for sharing instances of a module across threads via [`InstancePool`].
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+use wasmi::{core::TrapCode, Caller, Engine, InstancePool, Linker, Module};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "id" (func $id (result i32)))
+        (global $calls (mut i32) (i32.const 0))
+        (func (export "count") (result i32)
+            (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+            (global.get $calls)
+        )
+        (func (export "id") (result i32)
+            (call $id)
+        )
+        (func (export "trap")
+            (unreachable)
+        )
+    )
+"#;
+
+/// Creates an [`InstancePool`] and a counter of its created instances.
+fn pool() -> (InstancePool<i32>, Arc<AtomicUsize>) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let created = Arc::new(AtomicUsize::new(0));
+    let counter = created.clone();
+    let pool = InstancePool::new(module, move |engine| {
+        let id = counter.fetch_add(1, Ordering::Relaxed) as i32;
+        let mut linker = Linker::new(engine);
+        linker.func_wrap("host", "id", |caller: Caller<i32>| *caller.data())?;
+        Ok((linker, id))
+    });
+    (pool, created)
+}
+
+#[test]
+fn instances_are_reused() {
+    let (pool, created) = pool();
+    for expected in 1..=3 {
+        let count = pool
+            .with_instance(|pooled| {
+                let count = pooled
+                    .instance()
+                    .get_typed_func::<(), i32>(pooled.store(), "count")?;
+                count.call(pooled.store_mut(), ())
+            })
+            .unwrap();
+        assert_eq!(count, expected);
+    }
+    assert_eq!(created.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.len_idle(), 1);
+    // Discarded instances and instances of failed calls are dropped.
+    pool.with_instance(|pooled| {
+        pooled.discard();
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(pool.len_idle(), 0);
+    pool.prewarm().unwrap();
+    let error = pool
+        .with_instance(|pooled| {
+            let trap = pooled
+                .instance()
+                .get_typed_func::<(), ()>(pooled.store(), "trap")?;
+            trap.call(pooled.store_mut(), ())
+        })
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert_eq!(pool.len_idle(), 0);
+    assert_eq!(created.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn instances_are_shared_across_threads() {
+    let (pool, created) = pool();
+    let pool = pool.max_idle(2);
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..16 {
+                    pool.with_instance(|pooled| {
+                        let id = pooled
+                            .instance()
+                            .get_typed_func::<(), i32>(pooled.store(), "id")?;
+                        assert_eq!(id.call(pooled.store_mut(), ())?, *pooled.store().data());
+                        Ok(())
+                    })
+                    .unwrap();
+                }
+            });
+        }
+    });
+    assert!(pool.len_idle() <= 2);
+    assert!(created.load(Ordering::Relaxed) >= 1);
+    pool.clear();
+    assert_eq!(pool.len_idle(), 0);
+}
@@ -0,0 +1,211 @@
+//! Tests for extending the Wasmi engine with instructions via [`InstructionExtension`].
+
+use wasmi::{
+    core::{TrapCode, UntypedVal, ValType},
+    Engine,
+    Error,
+    FuncType,
+    InstructionExtension,
+    InstructionExtensions,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A modular arithmetic extension as used for homomorphic encryption schemes.
+struct ModArith;
+
+impl ModArith {
+    const MOD_ADD: u16 = 0;
+    const DIV_REM: u16 = 1;
+    const MODULUS: u16 = 2;
+}
+
+impl InstructionExtension for ModArith {
+    fn module(&self) -> &str {
+        "modarith"
+    }
+
+    fn resolve(&self, name: &str, ty: &FuncType) -> Option<u16> {
+        let (opcode, expected) = match name {
+            "mod_add" => (
+                Self::MOD_ADD,
+                FuncType::new([ValType::I64; 3], [ValType::I64]),
+            ),
+            "div_rem" => (
+                Self::DIV_REM,
+                FuncType::new([ValType::I32; 2], [ValType::I32; 2]),
+            ),
+            "modulus" => (Self::MODULUS, FuncType::new([], [ValType::I64])),
+            _ => return None,
+        };
+        (ty == &expected).then_some(opcode)
+    }
+
+    fn execute(
+        &self,
+        opcode: u16,
+        params: &[UntypedVal],
+        results: &mut [UntypedVal],
+    ) -> Result<(), Error> {
+        match opcode {
+            Self::MOD_ADD => {
+                let [a, b, m] = [0, 1, 2].map(|n| u64::from(params[n]));
+                results[0] = ((a + b) % m).into();
+            }
+            Self::DIV_REM => {
+                let [lhs, rhs] = [0, 1].map(|n| u32::from(params[n]));
+                if rhs == 0 {
+                    return Err(Error::from(TrapCode::IntegerDivisionByZero));
+                }
+                results[0] = (lhs / rhs).into();
+                results[1] = (lhs % rhs).into();
+            }
+            Self::MODULUS => results[0] = 97_u64.into(),
+            _ => unreachable!("unknown opcode: {opcode}"),
+        }
+        Ok(())
+    }
+}
+
+const WASM: &str = r#"
+    (module
+        (import "modarith" "mod_add" (func $mod_add (param i64 i64 i64) (result i64)))
+        (import "modarith" "div_rem" (func $div_rem (param i32 i32) (result i32 i32)))
+        (import "modarith" "modulus" (func $modulus (result i64)))
+        (export "div_rem" (func $div_rem))
+        (func (export "sum") (param i64 i64) (result i64)
+            (call $mod_add (local.get 0) (local.get 1) (call $modulus))
+        )
+        (func (export "digits") (param i32) (result i32)
+            (call $div_rem (local.get 0) (i32.const 10))
+            (i32.add)
+        )
+    )
+"#;
+
+fn engine() -> Engine {
+    let mut extensions = InstructionExtensions::new();
+    extensions.register(ModArith).unwrap();
+    Engine::with_extensions(&Default::default(), extensions)
+}
+
+#[test]
+fn extension_instructions_work() {
+    let engine = engine();
+    let module = Module::new(&engine, WASM).unwrap();
+    let histogram = module.call_graph().unwrap();
+    let sum = histogram.histogram(3).unwrap();
+    assert_eq!(sum.get("Extension0"), 1);
+    assert_eq!(sum.get("Extension"), 1);
+    assert_eq!(sum.get("CallImported"), 0);
+    let mut store = Store::new(&engine, ());
+    // Note: the linker defines imports that are resolved to extension instructions.
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let sum = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "sum")
+        .unwrap();
+    assert_eq!(sum.call(&mut store, (90, 10)).unwrap(), 3);
+    let digits = instance
+        .get_typed_func::<i32, i32>(&store, "digits")
+        .unwrap();
+    assert_eq!(digits.call(&mut store, 123).unwrap(), 15);
+    // Host calls to imports use the linker-defined host functions.
+    let div_rem = instance
+        .get_typed_func::<(i32, i32), (i32, i32)>(&store, "div_rem")
+        .unwrap();
+    assert_eq!(div_rem.call(&mut store, (17, 5)).unwrap(), (3, 2));
+    let error = div_rem.call(&mut store, (1, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+}
+
+#[test]
+fn extension_instructions_trap() {
+    let engine = engine();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let wasm = r#"
+        (module
+            (import "modarith" "div_rem" (func $div_rem (param i32 i32) (result i32 i32)))
+            (func (export "div") (param i32 i32) (result i32)
+                (call $div_rem (local.get 0) (local.get 1))
+                (drop)
+            )
+        )
+    "#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let div = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(i32, i32), i32>(&store, "div")
+        .unwrap();
+    assert_eq!(div.call(&mut store, (9, 2)).unwrap(), 4);
+    let error = div.call(&mut store, (9, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    // Other instances are unaffected by the trap.
+    let sum = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "sum")
+        .unwrap();
+    assert_eq!(sum.call(&mut store, (1, 2)).unwrap(), 3);
+}
+
+#[test]
+fn unresolved_imports_are_host_calls() {
+    let engine = engine();
+    let wasm = r#"
+        (module
+            (import "modarith" "mod_sub" (func $mod_sub (param i64 i64) (result i64)))
+            (import "modarith" "modulus" (func $modulus (result i32)))
+            (func (export "run") (result i64)
+                (call $mod_sub (i64.const 5) (i64.extend_i32_u (call $modulus)))
+            )
+        )
+    "#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let histogram = module.call_graph().unwrap();
+    assert_eq!(histogram.histogram(2).unwrap().get("Extension"), 0);
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    // Note: unknown names and mismatching types are not resolved by the extension.
+    assert!(linker.instantiate(&mut store, &module).is_err());
+    linker
+        .func_wrap("modarith", "mod_sub", |a: i64, b: i64| a - b)
+        .unwrap()
+        .func_wrap("modarith", "modulus", || 3_i32)
+        .unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(), i64>(&store, "run")
+        .unwrap();
+    assert_eq!(run.call(&mut store, ()).unwrap(), 2);
+}
+
+#[test]
+fn register_rejects_duplicate_modules() {
+    let mut extensions = InstructionExtensions::new();
+    extensions.register(ModArith).unwrap();
+    assert!(extensions.register(ModArith).is_err());
+    let engine = Engine::with_extensions(&Default::default(), extensions);
+    assert!(!engine.extensions().is_empty());
+    // Engines without extensions translate calls to imports as usual.
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    assert!(Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .is_err());
+}
@@ -0,0 +1,136 @@
+//! Tests for interruptible host calls via [`Caller::block_on_interruptible`].
+
+use std::{
+    future,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+use wasmi::{core::TrapCode, Caller, Engine, Error, Linker, Module, Store, TypedFunc};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "blocking" (func $blocking (param i32) (result i32)))
+        (import "host" "pending" (func $pending (result i32)))
+        (func (export "blocking") (param i32) (result i32)
+            (call $blocking (local.get 0))
+        )
+        (func (export "pending") (result i32)
+            (call $pending)
+        )
+    )
+"#;
+
+/// The host state that receives a notification once a blocking operation has been cancelled.
+type Host = Option<mpsc::Sender<()>>;
+
+/// Instantiates [`WASM`] and returns its `blocking` and `pending` exports.
+///
+/// - `blocking(ms)` sleeps for `ms` milliseconds on another thread unless cancelled.
+/// - `pending()` awaits a future that is ready on its second poll if `ready` is `true`.
+fn setup(ready: bool) -> (Store<Host>, TypedFunc<i32, i32>, TypedFunc<(), i32>) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, None);
+    let mut linker = <Linker<Host>>::new(&engine);
+    linker
+        .func_wrap(
+            "host",
+            "blocking",
+            |caller: Caller<Host>, ms: i32| -> Result<i32, Error> {
+                let cancelled = caller.data().clone();
+                caller.block_on_interruptible(move |cancel| {
+                    let start = Instant::now();
+                    while start.elapsed() < Duration::from_millis(ms as u64) {
+                        if cancel.is_interrupted() {
+                            if let Some(cancelled) = cancelled {
+                                cancelled.send(()).unwrap();
+                            }
+                            return -1;
+                        }
+                        thread::sleep(Duration::from_millis(1));
+                    }
+                    ms
+                })
+            },
+        )
+        .unwrap();
+    linker
+        .func_wrap(
+            "host",
+            "pending",
+            move |caller: Caller<Host>| -> Result<i32, Error> {
+                if !ready {
+                    return caller.block_on_future_interruptible(future::pending());
+                }
+                let mut polled = false;
+                caller.block_on_future_interruptible(future::poll_fn(|cx| {
+                    if polled {
+                        return std::task::Poll::Ready(42);
+                    }
+                    polled = true;
+                    cx.waker().wake_by_ref();
+                    std::task::Poll::Pending
+                }))
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let blocking = instance.get_typed_func(&store, "blocking").unwrap();
+    let pending = instance.get_typed_func(&store, "pending").unwrap();
+    (store, blocking, pending)
+}
+
+#[test]
+fn completes() {
+    let (mut store, blocking, pending) = setup(true);
+    assert_eq!(blocking.call(&mut store, 5).unwrap(), 5);
+    assert_eq!(pending.call(&mut store, ()).unwrap(), 42);
+}
+
+#[test]
+fn interrupt_handle() {
+    let (mut store, blocking, _pending) = setup(true);
+    let (sender, receiver) = mpsc::channel();
+    *store.data_mut() = Some(sender);
+    let handle = store.interrupt_handle();
+    let interrupter = thread::spawn({
+        let handle = handle.clone();
+        move || {
+            thread::sleep(Duration::from_millis(20));
+            handle.interrupt();
+        }
+    });
+    let start = Instant::now();
+    let error = blocking.call(&mut store, 60_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    assert!(start.elapsed() < Duration::from_secs(30));
+    interrupter.join().unwrap();
+    // The cancelled operation is notified so that it can stop early.
+    receiver.recv_timeout(Duration::from_secs(30)).unwrap();
+    // The handle stays interrupted until it is reset.
+    assert!(handle.is_interrupted());
+    assert!(blocking.call(&mut store, 1).is_err());
+    handle.reset();
+    assert_eq!(blocking.call(&mut store, 1).unwrap(), 1);
+}
+
+#[test]
+fn deadline() {
+    let (mut store, blocking, pending) = setup(false);
+    assert_eq!(store.deadline(), None);
+    let deadline = Instant::now() + Duration::from_millis(20);
+    store.set_deadline(Some(deadline));
+    assert_eq!(store.deadline(), Some(deadline));
+    let error = blocking.call(&mut store, 60_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    assert!(Instant::now() >= deadline);
+    let error = pending.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    store.set_deadline(None);
+    assert_eq!(blocking.call(&mut store, 1).unwrap(), 1);
+}
@@ -0,0 +1,141 @@
+//! Tests for building functions directly from Wasmi IR via [`IrFuncBuilder`].
+#![cfg(feature = "ir-builder")]
+
+use wasmi::{
+    core::ValType,
+    ir::{BranchOffset, BranchOffset16, Instruction, Reg},
+    Engine,
+    Error,
+    FuncType,
+    Instance,
+    IrFuncBuilder,
+    Linker,
+    Module,
+    Store,
+    IR_VERSION,
+};
+
+/// Creates a [`Store`] and an [`Instance`] of an empty Wasm module.
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, "(module)").unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+fn i32_type(len_params: usize) -> FuncType {
+    FuncType::new(vec![ValType::I32; len_params], [ValType::I32])
+}
+
+#[test]
+fn build_with_consts() -> Result<(), Error> {
+    let (mut store, instance) = setup();
+    let mut builder = IrFuncBuilder::new(IR_VERSION, i32_type(1))?;
+    builder.len_registers(2);
+    let c0 = builder.alloc_const(100_i32)?;
+    let c1 = builder.alloc_const(20_i32)?;
+    builder.push_instr(Instruction::i32_add(Reg::from(1), Reg::from(0), c0));
+    builder.push_instr(Instruction::i32_sub(Reg::from(1), Reg::from(1), c1));
+    builder.push_instr(Instruction::return_reg(Reg::from(1)));
+    let func = unsafe { builder.finish(&mut store, instance)? };
+    let func = func.typed::<i32, i32>(&store)?;
+    assert_eq!(func.call(&mut store, 2)?, 82);
+    Ok(())
+}
+
+#[test]
+fn build_with_branches() -> Result<(), Error> {
+    let (mut store, instance) = setup();
+    // Computes the sum of `1..=n` in a loop.
+    let mut builder = IrFuncBuilder::new(IR_VERSION, i32_type(1))?;
+    builder.len_registers(2);
+    let zero = builder.alloc_const(0_i32)?;
+    let one = builder.alloc_const(1_i32)?;
+    let n = Reg::from(0);
+    let sum = Reg::from(1);
+    builder.push_instr(Instruction::copy(sum, zero));
+    let head = builder.push_instr(Instruction::branch_i32_eq(n, zero, BranchOffset16::from(4)));
+    builder.push_instr(Instruction::i32_add(sum, sum, n));
+    builder.push_instr(Instruction::i32_sub(n, n, one));
+    let back = builder.len_instrs();
+    builder.push_instr(Instruction::branch(
+        BranchOffset::from_src_to_dst(back, head).unwrap(),
+    ));
+    builder.push_instr(Instruction::return_reg(sum));
+    let func = unsafe { builder.finish(&mut store, instance)? };
+    let func = func.typed::<i32, i32>(&store)?;
+    assert_eq!(func.call(&mut store, 10)?, 55);
+    Ok(())
+}
+
+#[test]
+fn validation_errors() -> Result<(), Error> {
+    let ty = i32_type(1);
+    assert!(IrFuncBuilder::new(IR_VERSION + 1, ty.clone()).is_err());
+    // No instructions.
+    assert!(IrFuncBuilder::new(IR_VERSION, ty.clone())?
+        .validate()
+        .is_err());
+    // Out of bounds register.
+    let mut builder = IrFuncBuilder::new(IR_VERSION, ty.clone())?;
+    builder.push_instr(Instruction::return_reg(Reg::from(1)));
+    assert!(builder.validate().is_err());
+    builder.len_registers(2);
+    assert!(builder.validate().is_ok());
+    // Out of bounds function local constant.
+    let mut builder = IrFuncBuilder::new(IR_VERSION, ty.clone())?;
+    builder.push_instr(Instruction::return_reg(Reg::from(-1)));
+    assert!(builder.validate().is_err());
+    builder.alloc_const(0_i32)?;
+    assert!(builder.validate().is_ok());
+    // Results written to function local constants.
+    let mut builder = IrFuncBuilder::new(IR_VERSION, ty.clone())?;
+    let c = builder.alloc_const(0_i32)?;
+    builder.push_instr(Instruction::i32_add(c, Reg::from(0), Reg::from(0)));
+    builder.push_instr(Instruction::return_reg(Reg::from(0)));
+    assert!(builder.validate().is_err());
+    // Fewer registers than parameters.
+    let mut builder = IrFuncBuilder::new(IR_VERSION, ty.clone())?;
+    builder.len_registers(0);
+    builder.push_instr(Instruction::trap(
+        wasmi::core::TrapCode::UnreachableCodeReached,
+    ));
+    assert!(builder.validate().is_err());
+    // Out of bounds branch target.
+    let mut builder = IrFuncBuilder::new(IR_VERSION, ty)?;
+    builder.push_instr(Instruction::branch(
+        BranchOffset::from_src_to_dst(0, 2).unwrap(),
+    ));
+    builder.push_instr(Instruction::return_reg(Reg::from(0)));
+    assert!(builder.validate().is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(any(feature = "no-floats", feature = "compact-dispatch"))]
+fn unsupported_instructions_trap() -> Result<(), Error> {
+    let (mut store, instance) = setup();
+    let mut builder = IrFuncBuilder::new(IR_VERSION, i32_type(1))?;
+    #[cfg(feature = "no-floats")]
+    builder.push_instr(Instruction::f32_add(
+        Reg::from(0),
+        Reg::from(0),
+        Reg::from(0),
+    ));
+    #[cfg(feature = "compact-dispatch")]
+    builder.push_instr(Instruction::i32_add_imm16(
+        Reg::from(0),
+        Reg::from(0),
+        wasmi::ir::Const16::<i32>::from(1_i16),
+    ));
+    builder.push_instr(Instruction::return_reg(Reg::from(0)));
+    let func = unsafe { builder.finish(&mut store, instance)? };
+    let func = func.typed::<i32, i32>(&store)?;
+    assert!(func.call(&mut store, 1).is_err());
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//! Tests for late-bound function imports via [`InstantiateOptions::late_bound`].
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use wasmi::{
+    errors::{ErrorKind, LinkerError},
+    Engine,
+    Func,
+    InstantiateOptions,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A guest that calls the imported `plugin::add` function.
+const WAT: &str = r#"
+    (module
+        (import "plugin" "add" (func $add (param i32 i32) (result i32)))
+        (func (export "run") (param i32 i32) (result i32)
+            (call $add (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] with `plugin::add` being late-bound using `linker`.
+///
+/// Returns the `store` and the exported `run` function.
+fn instantiate(engine: &Engine, linker: &Linker<()>) -> (Store<()>, Func) {
+    let mut store = Store::new(engine, ());
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let options = InstantiateOptions::new().late_bound("plugin", "add");
+    let instance = linker
+        .instantiate_with_options(&mut store, &module, &options)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_func(&store, "run").unwrap();
+    (store, run)
+}
+
+#[test]
+fn late_bound_import_requires_no_definition() {
+    let engine = Engine::default();
+    let linker = <Linker<()>>::new(&engine);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    assert!(linker.instantiate(&mut store, &module).is_err());
+    let (mut store, run) = instantiate(&engine, &linker);
+    let run = run.typed::<(i32, i32), i32>(&store).unwrap();
+    let error = run.call(&mut store, (1, 2)).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::UnresolvedLateBinding { .. })
+    ));
+}
+
+#[test]
+fn late_bound_import_resolves_once() {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    let resolved = Arc::new(AtomicUsize::new(0));
+    let counter = resolved.clone();
+    linker.late_binding_resolver(move |caller, module, name| {
+        assert_eq!((module, name), ("plugin", "add"));
+        counter.fetch_add(1, Ordering::SeqCst);
+        Some(Func::wrap(caller, |a: i32, b: i32| a.wrapping_add(b)))
+    });
+    let (mut store, run) = instantiate(&engine, &linker);
+    let run = run.typed::<(i32, i32), i32>(&store).unwrap();
+    assert_eq!(run.call(&mut store, (1, 2)).unwrap(), 3);
+    assert_eq!(run.call(&mut store, (40, 2)).unwrap(), 42);
+    assert_eq!(resolved.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn late_bound_import_retries_unresolved() {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+    linker.late_binding_resolver(move |caller, _module, _name| {
+        if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+            return None;
+        }
+        Some(Func::wrap(caller, |a: i32, b: i32| a.wrapping_mul(b)))
+    });
+    let (mut store, run) = instantiate(&engine, &linker);
+    let run = run.typed::<(i32, i32), i32>(&store).unwrap();
+    assert!(run.call(&mut store, (2, 3)).is_err());
+    assert_eq!(run.call(&mut store, (2, 3)).unwrap(), 6);
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn late_bound_import_type_mismatch() {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.late_binding_resolver(|caller, _module, _name| Some(Func::wrap(caller, |a: i64| a)));
+    let (mut store, run) = instantiate(&engine, &linker);
+    let run = run.typed::<(i32, i32), i32>(&store).unwrap();
+    let error = run.call(&mut store, (1, 2)).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::FuncTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn late_bound_import_must_be_func() {
+    let engine = Engine::default();
+    let linker = <Linker<()>>::new(&engine);
+    let wasm = wat::parse_str(r#"(module (import "plugin" "memory" (memory 1)))"#).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let options = InstantiateOptions::new().late_bound("plugin", "memory");
+    let error = linker
+        .instantiate_with_options(&mut store, &module, &options)
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::InvalidLateBinding { .. })
+    ));
+}
@@ -0,0 +1,112 @@
+//! Tests for counting the iterations of Wasm `loop`s via `Config::count_loop_iterations`.
+
+use wasmi::{CompilationMode, Config, Engine, Func, Linker, Module, Store};
+
+/// A Wasm module with functions containing Wasm `loop`s.
+///
+/// - `$nested` iterates its outer `loop` `$n` times and its inner `loop` `$n * $n` times.
+/// - `$dead` has an unreachable `loop` followed by a reachable `loop` iterating `$n` times.
+const WAT: &str = r#"
+    (module
+        (func (export "nested") (param $n i32) (result i32)
+            (local $i i32) (local $j i32) (local $sum i32)
+            (loop $outer
+                (local.set $j (i32.const 0))
+                (loop $inner
+                    (local.set $sum (i32.add (local.get $sum) (i32.const 1)))
+                    (local.set $j (i32.add (local.get $j) (i32.const 1)))
+                    (br_if $inner (i32.lt_u (local.get $j) (local.get $n)))
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br_if $outer (i32.lt_u (local.get $i) (local.get $n)))
+            )
+            (local.get $sum)
+        )
+        (func (export "dead") (param $n i32)
+            (block
+                (br 0)
+                (loop (br 0))
+            )
+            (loop $l
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $l (local.get $n))
+            )
+        )
+    )
+"#;
+
+/// Instantiates the [`WAT`] module and returns the [`Store`] and the `nested` and `dead` functions.
+fn setup(config: &Config) -> (Store<()>, Func, Func) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WAT).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let nested = instance.get_func(&store, "nested").unwrap();
+    let dead = instance.get_func(&store, "dead").unwrap();
+    (store, nested, dead)
+}
+
+/// Asserts the loop iteration counts of the [`WAT`] module functions for the `config`.
+fn assert_loop_iterations(config: &Config) {
+    let (mut store, nested, dead) = setup(config);
+    let typed_nested = nested.typed::<i32, i32>(&store).unwrap();
+    let typed_dead = dead.typed::<i32, ()>(&store).unwrap();
+    assert!(store.loop_iterations(&nested).is_empty());
+    assert_eq!(typed_nested.call(&mut store, 3).unwrap(), 9);
+    assert_eq!(store.loop_iterations(&nested), [3, 9]);
+    assert_eq!(typed_nested.call(&mut store, 2).unwrap(), 4);
+    assert_eq!(store.loop_iterations(&nested), [5, 13]);
+    typed_dead.call(&mut store, 4).unwrap();
+    assert_eq!(store.loop_iterations(&dead), [0, 4]);
+    store.reset_loop_iterations();
+    assert!(store.loop_iterations(&nested).is_empty());
+    assert!(store.loop_iterations(&dead).is_empty());
+}
+
+#[test]
+fn loop_iterations_are_counted() {
+    let mut config = Config::default();
+    config.count_loop_iterations(true);
+    assert_loop_iterations(&config);
+}
+
+#[test]
+fn loop_iterations_are_counted_with_fuel() {
+    let mut config = Config::default();
+    config.count_loop_iterations(true).consume_fuel(true);
+    let (mut store, nested, _dead) = setup(&config);
+    store.set_fuel(10_000).unwrap();
+    let typed_nested = nested.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(typed_nested.call(&mut store, 4).unwrap(), 16);
+    assert_eq!(store.loop_iterations(&nested), [4, 16]);
+}
+
+#[test]
+fn loop_iterations_are_counted_lazy() {
+    let mut config = Config::default();
+    config
+        .count_loop_iterations(true)
+        .compilation_mode(CompilationMode::Lazy);
+    assert_loop_iterations(&config);
+}
+
+#[test]
+fn loop_iterations_are_not_counted_by_default() {
+    let (mut store, nested, _dead) = setup(&Config::default());
+    let typed_nested = nested.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(typed_nested.call(&mut store, 3).unwrap(), 9);
+    assert!(store.loop_iterations(&nested).is_empty());
+}
+
+#[test]
+fn loop_iterations_of_host_funcs() {
+    let mut config = Config::default();
+    config.count_loop_iterations(true);
+    let (mut store, _nested, _dead) = setup(&config);
+    let host = Func::wrap(&mut store, || {});
+    assert!(store.loop_iterations(&host).is_empty());
+}
@@ -0,0 +1,138 @@
+//! Tests for accessing global variables with indices other than 0 which are cached separately.
+
+use std::sync::{
+    atomic::{AtomicI32, Ordering},
+    Arc,
+};
+use wasmi::{Caller, Engine, Func, Global, Instance, Linker, Module, Mutability, Store, Val};
+
+/// Returns a Wasm module with 42 global variables where global `$gN` has index `N`.
+///
+/// - Global variables `$g18` and `$g34` share a slot of the global variable cache
+///   whereas `$g5` stays cached across calls of the imported `$callback`.
+/// - The imported `$epoch` global variable is computed.
+fn wat() -> String {
+    let globals = (2..42)
+        .map(|n| format!("(global $g{n} (mut i32) (i32.const {n}))"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        r#"
+        (module
+            (import "env" "g0" (global $g0 (mut i32)))
+            (import "env" "epoch" (global $epoch i32))
+            (import "env" "callback" (func $callback))
+            {globals}
+            (export "g5" (global $g5))
+            (export "g18" (global $g18))
+            (export "g34" (global $g34))
+            (func (export "sum") (result i32)
+                (i32.add
+                    (i32.add (global.get $g2) (global.get $g18))
+                    (i32.add (global.get $g34) (global.get $epoch))
+                )
+            )
+            (func $bump (export "bump") (result i32)
+                (global.set $g5 (i32.add (global.get $g5) (i32.const 1)))
+                (global.set $g18 (i32.add (global.get $g18) (i32.const 1)))
+                (global.set $g34 (i32.add (global.get $g34) (i32.const 1)))
+                (call $callback)
+                (global.set $g5 (i32.add (global.get $g5) (i32.const 1)))
+                (global.set $g18 (i32.add (global.get $g18) (i32.const 1)))
+                (global.set $g34 (i32.add (global.get $g34) (i32.const 1)))
+                (i32.add
+                    (global.get $g5)
+                    (i32.add (global.get $g18) (global.get $g34))
+                )
+            )
+            (func (export "touch")
+                (drop (call $bump))
+            )
+        )
+    "#
+    )
+}
+
+/// Creates many global variables in the store so that the storage of global variables is reallocated.
+fn create_globals(caller: &mut Caller<()>) {
+    for n in 0..1000 {
+        Global::new(&mut *caller, Val::I32(n), Mutability::Var);
+    }
+}
+
+/// Instantiates the module of [`wat`] with `callback` and the computed `epoch` global.
+fn instantiate(
+    store: &mut Store<()>,
+    module: &Module,
+    epoch: &Arc<AtomicI32>,
+    callback: Func,
+) -> Instance {
+    let mut linker = <Linker<()>>::new(store.engine());
+    let epoch = epoch.clone();
+    let g0 = Global::new(&mut *store, Val::I32(0), Mutability::Var);
+    linker
+        .define("env", "g0", g0)
+        .unwrap()
+        .global_computed("env", "epoch", move || epoch.load(Ordering::SeqCst))
+        .unwrap()
+        .define("env", "callback", callback)
+        .unwrap();
+    linker
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+fn get_i32(store: &Store<()>, instance: &Instance, name: &str) -> i32 {
+    instance
+        .get_global(store, name)
+        .unwrap()
+        .get(store)
+        .i32()
+        .unwrap()
+}
+
+#[test]
+fn many_globals_survive_reallocation() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wat()).unwrap();
+    let mut store = Store::new(&engine, ());
+    let epoch = Arc::new(AtomicI32::new(100));
+    let callback = Func::wrap(&mut store, |mut caller: Caller<()>| {
+        create_globals(&mut caller)
+    });
+    let instance = instantiate(&mut store, &module, &epoch, callback);
+    let sum = instance.get_typed_func::<(), i32>(&store, "sum").unwrap();
+    let bump = instance.get_typed_func::<(), i32>(&store, "bump").unwrap();
+    assert_eq!(sum.call(&mut store, ()).unwrap(), 2 + 18 + 34 + 100);
+    assert_eq!(bump.call(&mut store, ()).unwrap(), 7 + 20 + 36);
+    assert_eq!(bump.call(&mut store, ()).unwrap(), 9 + 22 + 38);
+    assert_eq!(get_i32(&store, &instance, "g5"), 9);
+    assert_eq!(get_i32(&store, &instance, "g18"), 22);
+    assert_eq!(get_i32(&store, &instance, "g34"), 38);
+    epoch.store(200, Ordering::SeqCst);
+    assert_eq!(sum.call(&mut store, ()).unwrap(), 2 + 22 + 38 + 200);
+}
+
+#[test]
+fn many_globals_of_multiple_instances() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wat()).unwrap();
+    let mut store = Store::new(&engine, ());
+    let epoch = Arc::new(AtomicI32::new(0));
+    let callback = Func::wrap(&mut store, |mut caller: Caller<()>| {
+        create_globals(&mut caller)
+    });
+    let inner = instantiate(&mut store, &module, &epoch, callback);
+    // Note: calling a Wasm function of another instance switches the cached instance.
+    let callback = inner.get_func(&store, "touch").unwrap();
+    let outer = instantiate(&mut store, &module, &epoch, callback);
+    let outer_bump = outer.get_typed_func::<(), i32>(&store, "bump").unwrap();
+    assert_eq!(outer_bump.call(&mut store, ()).unwrap(), 7 + 20 + 36);
+    for instance in [&inner, &outer] {
+        assert_eq!(get_i32(&store, instance, "g5"), 7);
+        assert_eq!(get_i32(&store, instance, "g18"), 20);
+        assert_eq!(get_i32(&store, instance, "g34"), 36);
+    }
+}
@@ -0,0 +1,146 @@
+//! Tests for accessing linear memories with indices other than 0 which are cached separately.
+
+use wasmi::{Caller, Engine, Extern, Func, Instance, Linker, Memory, MemoryType, Module, Store};
+
+/// Returns a Wasm module with 6 linear memories where memory `$mN` has index `N`.
+///
+/// - Linear memories `$m1` and `$m5` share a slot of the linear memory cache.
+/// - The imported `$m1` linear memory may be shared with other instances.
+const WAT: &str = r#"
+    (module
+        (import "env" "m0" (memory $m0 1))
+        (import "env" "m1" (memory $m1 1))
+        (import "env" "callback" (func $callback))
+        (memory $m2 1)
+        (memory $m3 1)
+        (memory $m4 1)
+        (memory $m5 1)
+        (export "m1" (memory $m1))
+        (export "m5" (memory $m5))
+        (func (export "sum") (param $addr i32) (result i32)
+            (i32.add
+                (i32.load $m1 (local.get $addr))
+                (i32.load $m5 (local.get $addr))
+            )
+        )
+        (func (export "grow_m1")
+            (drop (memory.grow $m1 (i32.const 1)))
+        )
+        (func (export "grow_m5") (result i32)
+            (i32.store $m1 (i32.const 0) (i32.const 1))
+            (i32.store $m5 (i32.const 0) (i32.const 5))
+            (i32.store $m5
+                (i32.mul (memory.grow $m5 (i32.const 1)) (i32.const 65536))
+                (i32.const 50)
+            )
+            (i32.load $m1 (i32.const 0))
+        )
+        (func (export "callback_m1") (result i32)
+            (i32.store $m1 (i32.const 0) (i32.const 10))
+            (i32.store $m5 (i32.const 0) (i32.const 20))
+            (call $callback)
+            (i32.store $m1
+                (i32.mul (i32.sub (memory.size $m1) (i32.const 1)) (i32.const 65536))
+                (i32.const 100)
+            )
+            (i32.add
+                (i32.load $m1 (i32.const 0))
+                (i32.load $m5 (i32.const 0))
+            )
+        )
+    )
+"#;
+
+/// Instantiates the module of [`WAT`] with the linear memory `m1` and `callback`.
+fn instantiate(store: &mut Store<()>, module: &Module, m1: Memory, callback: Func) -> Instance {
+    let mut linker = <Linker<()>>::new(store.engine());
+    let m0 = Memory::new(&mut *store, MemoryType::new(1, None).unwrap()).unwrap();
+    linker
+        .define("env", "m0", m0)
+        .unwrap()
+        .define("env", "m1", m1)
+        .unwrap()
+        .define("env", "callback", callback)
+        .unwrap();
+    linker
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Returns the `i32` value stored at `addr` of `memory`.
+fn load_i32(store: &Store<()>, memory: Memory, addr: usize) -> i32 {
+    let mut bytes = [0x00_u8; 4];
+    memory.read(store, addr, &mut bytes).unwrap();
+    i32::from_le_bytes(bytes)
+}
+
+#[test]
+fn many_memories_survive_grow() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WAT).unwrap();
+    let mut store = Store::new(&engine, ());
+    let m1 = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let callback = Func::wrap(&mut store, || {});
+    let instance = instantiate(&mut store, &module, m1, callback);
+    let grow_m5 = instance
+        .get_typed_func::<(), i32>(&store, "grow_m5")
+        .unwrap();
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    for pages in 2..10 {
+        assert_eq!(grow_m5.call(&mut store, ()).unwrap(), 1);
+        let m5 = instance.get_memory(&store, "m5").unwrap();
+        assert_eq!(m5.size(&store), pages);
+        assert_eq!(load_i32(&store, m5, (pages as usize - 1) * 65536), 50);
+    }
+    assert_eq!(sum.call(&mut store, 0).unwrap(), 1 + 5);
+}
+
+#[test]
+fn many_memories_survive_host_grow() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WAT).unwrap();
+    let mut store = Store::new(&engine, ());
+    let m1 = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let callback = Func::wrap(&mut store, |mut caller: Caller<()>| {
+        let Some(Extern::Memory(m1)) = caller.get_export("m1") else {
+            panic!("missing exported linear memory `m1`")
+        };
+        m1.grow(&mut caller, 1).unwrap();
+    });
+    let instance = instantiate(&mut store, &module, m1, callback);
+    let callback_m1 = instance
+        .get_typed_func::<(), i32>(&store, "callback_m1")
+        .unwrap();
+    for pages in 2..10 {
+        assert_eq!(callback_m1.call(&mut store, ()).unwrap(), 10 + 20);
+        assert_eq!(m1.size(&store), pages);
+        assert_eq!(load_i32(&store, m1, (pages as usize - 1) * 65536), 100);
+    }
+}
+
+#[test]
+fn many_memories_of_multiple_instances() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WAT).unwrap();
+    let mut store = Store::new(&engine, ());
+    let m1 = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let callback = Func::wrap(&mut store, || {});
+    let inner = instantiate(&mut store, &module, m1, callback);
+    // Note: calling a Wasm function of another instance switches the cached instance
+    //       while the shared linear memory `m1` is grown by the other instance.
+    let callback = inner.get_func(&store, "grow_m1").unwrap();
+    let outer = instantiate(&mut store, &module, m1, callback);
+    let callback_m1 = outer
+        .get_typed_func::<(), i32>(&store, "callback_m1")
+        .unwrap();
+    let inner_sum = inner.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    for pages in 2..10 {
+        assert_eq!(callback_m1.call(&mut store, ()).unwrap(), 10 + 20);
+        assert_eq!(m1.size(&store), pages);
+        assert_eq!(load_i32(&store, m1, (pages as usize - 1) * 65536), 100);
+    }
+    // Note: the linear memory `m5` is not shared between the instances.
+    assert_eq!(inner_sum.call(&mut store, 0).unwrap(), 10);
+}
@@ -0,0 +1,132 @@
+//! Tests for [`Memory::dirty_pages`], [`Memory::dump`] and [`Memory::hexdump`].
+
+use wasmi::{errors::MemoryError, Engine, Linker, Memory, MemoryType, Module, Store};
+
+/// Creates a [`Store`] with a [`Memory`] of `pages` Wasm pages.
+fn setup(pages: u32) -> (Store<()>, Memory) {
+    let mut store = Store::new(&Engine::default(), ());
+    let memory = Memory::new(&mut store, MemoryType::new(pages, None).unwrap()).unwrap();
+    (store, memory)
+}
+
+#[test]
+fn dirty_pages() {
+    let (mut store, memory) = setup(3);
+    assert_eq!(memory.dirty_pages(&store), [0, 1, 2]);
+    memory.clear_dirty_pages(&mut store);
+    assert!(memory.dirty_pages(&store).is_empty());
+    memory.write(&mut store, 0x2_0010, &[1, 2, 3]).unwrap();
+    memory.write(&mut store, 0xFFFF, &[4, 5]).unwrap();
+    assert_eq!(memory.dirty_pages(&store), [0, 1, 2]);
+    memory.clear_dirty_pages(&mut store);
+    memory.write(&mut store, 0x2_0010, &[1]).unwrap();
+    assert_eq!(memory.dirty_pages(&store), [2]);
+    // Writes that restore the previous contents of a page still make it dirty.
+    memory.clear_dirty_pages(&mut store);
+    memory.write(&mut store, 0x1_0000, &[0]).unwrap();
+    assert_eq!(memory.dirty_pages(&store), [1]);
+    // Untracked exclusive access makes all pages dirty.
+    memory.clear_dirty_pages(&mut store);
+    memory.data_mut(&mut store)[0x1_0000] = 5;
+    assert_eq!(memory.dirty_pages(&store), [0, 1, 2]);
+    // Grown pages are dirty.
+    memory.clear_dirty_pages(&mut store);
+    memory.grow(&mut store, 2).unwrap();
+    assert_eq!(memory.dirty_pages(&store), [3, 4]);
+    memory.clear_dirty_pages(&mut store);
+    assert!(memory.dirty_pages(&store).is_empty());
+}
+
+#[test]
+fn dirty_pages_wasm() {
+    let wat = r#"
+        (module
+            (memory (export "m0") 4)
+            (memory (export "m1") 4)
+            (func (export "store") (param i32)
+                (i32.store (local.get 0) (i32.const -1))
+            )
+            (func (export "store_m1") (param i32)
+                (i64.store8 1 (local.get 0) (i64.const -1))
+            )
+            (func (export "fill") (param i32 i32)
+                (memory.fill (local.get 0) (i32.const 7) (local.get 1))
+            )
+            (func (export "grow") (param i32) (result i32)
+                (memory.grow (local.get 0))
+            )
+        )
+    "#;
+    let mut store = Store::new(&Engine::default(), ());
+    let module = Module::new(store.engine(), wat).unwrap();
+    let instance = <Linker<()>>::new(store.engine())
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let m0 = instance.get_memory(&store, "m0").unwrap();
+    let m1 = instance.get_memory(&store, "m1").unwrap();
+    let store_m0 = instance.get_typed_func::<i32, ()>(&store, "store").unwrap();
+    let store_m1 = instance
+        .get_typed_func::<i32, ()>(&store, "store_m1")
+        .unwrap();
+    let fill = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "fill")
+        .unwrap();
+    let grow = instance.get_typed_func::<i32, i32>(&store, "grow").unwrap();
+    m0.clear_dirty_pages(&mut store);
+    m1.clear_dirty_pages(&mut store);
+    store_m0.call(&mut store, 0x1_0010).unwrap();
+    assert_eq!(m0.dirty_pages(&store), [1]);
+    // A store crossing a page boundary makes both pages dirty.
+    store_m0.call(&mut store, 0x2_FFFE).unwrap();
+    assert_eq!(m0.dirty_pages(&store), [1, 2, 3]);
+    // A store that ends right before a page boundary only makes its page dirty.
+    m0.clear_dirty_pages(&mut store);
+    store_m0.call(&mut store, 0x2_FFFC).unwrap();
+    assert_eq!(m0.dirty_pages(&store), [2]);
+    assert!(m1.dirty_pages(&store).is_empty());
+    store_m1.call(&mut store, 0x3_FFFF).unwrap();
+    assert_eq!(m1.dirty_pages(&store), [3]);
+    // Out of bounds stores do not make any page dirty.
+    m0.clear_dirty_pages(&mut store);
+    store_m0.call(&mut store, 0x3_FFFE).unwrap_err();
+    assert!(m0.dirty_pages(&store).is_empty());
+    fill.call(&mut store, (0xFFFF, 0x1_0002)).unwrap();
+    assert_eq!(m0.dirty_pages(&store), [0, 1, 2]);
+    m0.clear_dirty_pages(&mut store);
+    assert_eq!(grow.call(&mut store, 1).unwrap(), 4);
+    store_m0.call(&mut store, 0x4_0000).unwrap();
+    assert_eq!(m0.dirty_pages(&store), [4]);
+}
+
+#[test]
+fn dump() {
+    let (mut store, memory) = setup(1);
+    memory.write(&mut store, 8, b"wasmi").unwrap();
+    let mut buffer = Vec::new();
+    memory.dump(&store, 6..15, &mut buffer).unwrap();
+    assert_eq!(buffer, b"\0\0wasmi\0\0");
+    let error = memory
+        .dump(&store, 0xFFF0..0x1_0001, &mut buffer)
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn hexdump() {
+    let (mut store, memory) = setup(1);
+    memory.write(&mut store, 0x13, b"Hello, World!\n").unwrap();
+    assert_eq!(
+        memory.hexdump(&store, 0x10..0x28).unwrap(),
+        "\
+00000010  00 00 00 48 65 6c 6c 6f  2c 20 57 6f 72 6c 64 21  |...Hello, World!|
+00000020  0a 00 00 00 00 00 00 00                           |........|
+"
+    );
+    assert_eq!(memory.hexdump(&store, 0..0).unwrap(), "");
+    assert!(matches!(
+        memory.hexdump(&store, 0x1_0000..0x1_0001),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+}
@@ -0,0 +1,140 @@
+//! Tests for memory region tagging via [`Store::tag_memory_region`] and [`Memory::walk_dlmalloc`].
+
+use wasmi::{core::TrapCode, Caller, Engine, Error, Linker, Memory, Module, Store, TypedFunc};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "yield" (func $yield))
+        (memory (export "memory") 1)
+        (func (export "store") (param i32 i32)
+            (i32.store (local.get 0) (local.get 1))
+            (call $yield)
+        )
+        (func (export "load") (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+    )
+"#;
+
+/// The host state recording all reported writes as `(tag, start, end)`.
+type Writes = Vec<(String, usize, usize)>;
+
+/// The exported `store` function of [`WASM`].
+type StoreFn = TypedFunc<(i32, i32), ()>;
+
+/// Instantiates [`WASM`] and returns its exported memory, `store` and `load` functions.
+fn setup() -> (Store<Writes>, Memory, StoreFn, TypedFunc<i32, i32>) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, Writes::new());
+    let mut linker = <Linker<Writes>>::new(&engine);
+    linker
+        .func_wrap("host", "yield", |_: Caller<Writes>| {})
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let store_fn = instance.get_typed_func(&store, "store").unwrap();
+    let load = instance.get_typed_func(&store, "load").unwrap();
+    store.memory_write_hook(|writes, write| {
+        let range = write.range();
+        writes.push((write.tag().into(), range.start, range.end));
+        Ok(())
+    });
+    (store, memory, store_fn, load)
+}
+
+#[test]
+fn reports_guest_and_host_writes() {
+    let (mut store, memory, store_fn, load) = setup();
+    store.tag_memory_region(&memory, 16..32, "metadata");
+    store.tag_memory_region(&memory, 1000..1004, "other");
+    // Guest writes are reported before the guest calls the host.
+    store_fn.call(&mut store, (20, 0x0102)).unwrap();
+    assert_eq!(store.data(), &[("metadata".into(), 20, 22)]);
+    // Writes outside of tagged regions are not reported.
+    store_fn.call(&mut store, (100, 1)).unwrap();
+    assert_eq!(store.data().len(), 1);
+    // Host writes are reported upon the next call into the guest.
+    memory.write(&mut store, 1002, &[0xFF; 4]).unwrap();
+    assert_eq!(load.call(&mut store, 1002).unwrap(), -1);
+    assert_eq!(store.data()[1], ("other".into(), 1002, 1004));
+    assert!(store.untag_memory_region("metadata"));
+    assert!(!store.untag_memory_region("metadata"));
+    store_fn.call(&mut store, (16, -1)).unwrap();
+    assert_eq!(store.data().len(), 2);
+    let tags = store
+        .memory_tags()
+        .map(|(_, range, tag)| (range, tag))
+        .collect::<Vec<_>>();
+    assert_eq!(tags, [(1000..1004, "other")]);
+}
+
+#[test]
+fn hook_error_traps() {
+    let (mut store, memory, store_fn, _) = setup();
+    store.tag_memory_region(&memory, 0..8, "guarded");
+    store.memory_write_hook(|_, write| {
+        Err(Error::new(format!("unexpected write to {}", write.tag())))
+    });
+    let error = store_fn.call(&mut store, (4, 1)).unwrap_err();
+    assert!(error.to_string().contains("unexpected write to guarded"));
+    store_fn.call(&mut store, (64, 1)).unwrap();
+}
+
+#[test]
+fn tags_beyond_memory_size() {
+    let (mut store, memory, store_fn, _) = setup();
+    store.tag_memory_region(&memory, 65532..65540, "edge");
+    memory.grow(&mut store, 1).unwrap();
+    store_fn.call(&mut store, (200, 1)).unwrap();
+    assert!(store.data().is_empty());
+    store_fn.call(&mut store, (65536, 7)).unwrap();
+    assert_eq!(store.data(), &[("edge".into(), 65536, 65537)]);
+    let error = store_fn.call(&mut store, (2 * 65536, 1)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+}
+
+/// Writes a dlmalloc chunk header with `head` at `addr` to `memory`.
+#[cfg(feature = "heap-walk")]
+fn write_chunk(store: &mut Store<Writes>, memory: Memory, addr: usize, head: u32) {
+    memory.write(store, addr + 4, &head.to_le_bytes()).unwrap();
+}
+
+#[test]
+#[cfg(feature = "heap-walk")]
+fn walk_dlmalloc() {
+    let (mut store, memory, _, _) = setup();
+    let base = 1024;
+    // An allocated chunk of 32 bytes, a free chunk of 48 bytes,
+    // an allocated chunk of 16 bytes and a fencepost.
+    write_chunk(&mut store, memory, base, 32 | 0b11);
+    write_chunk(&mut store, memory, base + 32, 48 | 0b01);
+    write_chunk(&mut store, memory, base + 80, 16 | 0b11);
+    write_chunk(&mut store, memory, base + 96, 0b111 | 4);
+    let walk = memory.walk_dlmalloc(&store, base..4096).unwrap();
+    let chunks = walk
+        .chunks()
+        .iter()
+        .map(|chunk| (chunk.addr(), chunk.size(), chunk.is_in_use()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        chunks,
+        [
+            (base, 32, true),
+            (base + 32, 48, false),
+            (base + 80, 16, true)
+        ]
+    );
+    assert_eq!(walk.chunks()[0].payload(), base + 8);
+    assert_eq!(walk.in_use(), 48);
+    assert_eq!(walk.free(), 48);
+    assert_eq!(walk.in_use_within(base + 16..base + 88), 16 + 8);
+    // The walk stops at chunks exceeding the segment.
+    let walk = memory.walk_dlmalloc(&store, base..base + 64).unwrap();
+    assert_eq!(walk.chunks().len(), 1);
+    assert!(memory.walk_dlmalloc(&store, base..65537).is_err());
+}
@@ -0,0 +1,115 @@
+//! Tests for memory watermarks via [`Memory::set_watermark`] and [`Store::memory_watermark_hook`].
+
+use wasmi::{
+    Engine,
+    Error,
+    Linker,
+    Memory,
+    MemoryWatermark,
+    Module,
+    Store,
+    TypedFunc,
+    WatermarkAction,
+};
+
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 1 100)
+        (func (export "grow") (param i32) (result i32)
+            (memory.grow (local.get 0))
+        )
+        (func (export "grow_by_2") (result i32)
+            (memory.grow (i32.const 2))
+        )
+    )
+"#;
+
+/// The host state recording all watermark events.
+type Events = Vec<(u32, u32, u32)>;
+
+/// Instantiates [`WASM`] and returns its exported memory and `grow` functions.
+fn setup() -> (
+    Store<Events>,
+    Memory,
+    TypedFunc<i32, i32>,
+    TypedFunc<(), i32>,
+) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, Events::new());
+    let instance = <Linker<Events>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let grow = instance.get_typed_func::<i32, i32>(&store, "grow").unwrap();
+    let grow_by_2 = instance
+        .get_typed_func::<(), i32>(&store, "grow_by_2")
+        .unwrap();
+    (store, memory, grow, grow_by_2)
+}
+
+/// A watermark policy that allows growth up to 8 pages, denies growth up to 16 pages and traps beyond.
+fn policy(events: &mut Events, event: MemoryWatermark) -> Result<WatermarkAction, Error> {
+    events.push((event.watermark(), event.current(), event.desired()));
+    match event.desired() {
+        0..=8 => Ok(WatermarkAction::Allow),
+        9..=16 => Ok(WatermarkAction::Deny),
+        _ => Err(Error::new("memory exceeds hard policy limit")),
+    }
+}
+
+#[test]
+fn watermark_without_hook_allows_growth() {
+    let (mut store, memory, grow, _) = setup();
+    assert_eq!(memory.watermark(&store), None);
+    memory.set_watermark(&mut store, Some(2));
+    assert_eq!(memory.watermark(&store), Some(2));
+    assert_eq!(grow.call(&mut store, 5).unwrap(), 1);
+    assert_eq!(memory.size(&store), 6);
+}
+
+#[test]
+fn watermark_hook_decides_growth() {
+    let (mut store, memory, grow, grow_by_2) = setup();
+    store.memory_watermark_hook(policy);
+    memory.set_watermark(&mut store, Some(4));
+    // Growth up to the watermark does not invoke the hook.
+    assert_eq!(grow.call(&mut store, 3).unwrap(), 1);
+    assert!(store.data().is_empty());
+    // Growth beyond the watermark is allowed by the hook.
+    assert_eq!(grow_by_2.call(&mut store, ()).unwrap(), 4);
+    assert_eq!(store.data(), &[(4, 4, 6)]);
+    // Growth beyond the watermark is denied by the hook.
+    assert_eq!(grow.call(&mut store, 4).unwrap(), -1);
+    assert_eq!(memory.size(&store), 6);
+    // Growth beyond the watermark traps due to the hook.
+    let error = grow.call(&mut store, 20).unwrap_err();
+    assert!(error.to_string().contains("hard policy limit"));
+    assert_eq!(memory.size(&store), 6);
+    assert_eq!(store.data(), &[(4, 4, 6), (4, 6, 10), (4, 6, 26)]);
+    // Growth by zero pages never invokes the hook.
+    assert_eq!(grow.call(&mut store, 0).unwrap(), 6);
+    assert_eq!(store.data().len(), 3);
+}
+
+#[test]
+fn watermark_hook_ignores_memories_without_watermark() {
+    let (mut store, memory, grow, _) = setup();
+    store.memory_watermark_hook(policy);
+    assert_eq!(grow.call(&mut store, 20).unwrap(), 1);
+    memory.set_watermark(&mut store, Some(21));
+    memory.set_watermark(&mut store, None);
+    assert_eq!(grow.call(&mut store, 20).unwrap(), 21);
+    assert!(store.data().is_empty());
+}
+
+#[test]
+fn watermark_hook_ignores_host_growth() {
+    let (mut store, memory, _, _) = setup();
+    store.memory_watermark_hook(policy);
+    memory.set_watermark(&mut store, Some(1));
+    assert_eq!(memory.grow(&mut store, 20).unwrap(), 1);
+    assert!(store.data().is_empty());
+}
@@ -1,9 +1,88 @@
+mod aot;
+mod bench_suite;
+mod bindgen;
+mod branch_hints;
+mod build;
+mod calibrate_fuel_costs;
+mod call_graph;
 mod call_hook;
+mod call_parallel;
+mod compact_dispatch;
+mod compilation_fuel;
+mod compile_function;
+mod computed_globals;
+mod config_presets;
+mod config_validation;
+mod constant_time;
+mod content_hash;
+mod dylink;
+mod emscripten_sjlj;
+mod entity_ids;
+mod error_category;
+mod extension_batch;
 mod fuel_consumption;
 mod fuel_metering;
+mod fuel_profile;
+mod fuel_shares;
 mod func;
+mod guest_abi;
+mod hardened_dispatch;
+mod host_api;
+mod host_call_batch;
 mod host_call_compilation;
 mod host_call_instantiation;
+mod host_call_replay;
 mod host_calls_wasm;
+mod host_error_downcast;
+mod host_func_budget;
+mod hot_swap;
+mod i128_ops;
+mod instance_pool;
+mod instruction_extensions;
+mod interruptible_host_call;
+mod ir_builder;
+mod late_binding;
+mod loop_iterations;
+mod many_globals;
+mod many_memories;
+mod memory_dump;
+mod memory_tags;
+mod memory_watermark;
+mod module_adapter;
+mod no_floats;
+mod partial_writes;
+mod preinit;
+mod prng;
+mod reentrancy_policy;
+mod ref_stats;
+mod relaxed_import_limits;
+mod required_features;
 mod resource_limiter;
 mod resumable_call;
+mod return_values;
+mod safepoints;
+mod saturating_div_rem;
+mod scan_imports;
+mod scheduler;
+mod self_check;
+mod serde;
+mod session;
+mod shared_table;
+mod signals;
+mod signed_modules;
+mod stack_usage;
+mod store_snapshot;
+mod streaming_control;
+mod streams;
+mod symbols;
+mod table_fill_with;
+mod time_travel;
+mod trace;
+mod translate_ahead;
+mod translated_code_size;
+mod trap_handler;
+mod trap_injection;
+mod unsupported_proposals;
+mod upgrade_compat;
+mod virtual_clock;
+mod zero_on_free;
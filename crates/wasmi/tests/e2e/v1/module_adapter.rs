@@ -0,0 +1,88 @@
+//! Tests for renaming imports and exports via [`ModuleAdapter`].
+
+use wasmi::{Engine, Linker, Module, ModuleAdapter, Store};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "foo" (func $foo (result i32)))
+        (import "env" "bar" (func $bar (result i32)))
+        (import "other" "baz" (func $baz (result i32)))
+        (func (export "run") (result i32)
+            (i32.add
+                (i32.add (call $foo) (call $bar))
+                (call $baz)
+            )
+        )
+        (func (export "start") (result i32)
+            (i32.const 0)
+        )
+    )
+"#;
+
+fn import_names(module: &Module) -> Vec<(String, String)> {
+    module
+        .imports()
+        .map(|import| (import.module().into(), import.name().into()))
+        .collect()
+}
+
+#[test]
+fn empty_adapter_renames_nothing() {
+    let engine = Engine::default();
+    let module = Module::new_with_adapter(&engine, WASM, &ModuleAdapter::new()).unwrap();
+    assert_eq!(
+        import_names(&module),
+        [
+            ("env".into(), "foo".into()),
+            ("env".into(), "bar".into()),
+            ("other".into(), "baz".into()),
+        ]
+    );
+}
+
+#[test]
+fn rename_imports_and_exports() {
+    let engine = Engine::default();
+    let adapter = ModuleAdapter::new()
+        .rename_import("env", "foo", "host", "foo_v2")
+        .rename_namespace("env", "host")
+        .rename_export("run", "_start");
+    let module = Module::new_with_adapter(&engine, WASM, &adapter).unwrap();
+    assert_eq!(
+        import_names(&module),
+        [
+            ("host".into(), "foo_v2".into()),
+            ("host".into(), "bar".into()),
+            ("other".into(), "baz".into()),
+        ]
+    );
+    assert!(module.get_export("run").is_none());
+    assert!(module.get_export("_start").is_some());
+    let mut store = <Store<()>>::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("host", "foo_v2", || 1_i32)
+        .unwrap()
+        .func_wrap("host", "bar", || 2_i32)
+        .unwrap()
+        .func_wrap("other", "baz", || 3_i32)
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let result = instance
+        .get_typed_func::<(), i32>(&store, "_start")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(result, 6);
+}
+
+#[test]
+fn duplicate_export_names_fail() {
+    let engine = Engine::default();
+    let adapter = ModuleAdapter::new().rename_export("run", "start");
+    assert!(Module::new_with_adapter(&engine, WASM, &adapter).is_err());
+}
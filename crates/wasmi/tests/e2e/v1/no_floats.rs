@@ -0,0 +1,43 @@
+//! Tests for the `no-floats` crate feature.
+#![cfg(feature = "no-floats")]
+
+use wasmi::{Config, Engine, Linker, Module, Store};
+
+#[test]
+fn no_floats_rejects_float_modules() {
+    let mut config = Config::default();
+    config.floats(true);
+    assert!(!config.get_floats());
+    let engine = Engine::new(&config);
+    let wasm = r#"
+        (module
+            (func (export "f") (param f32 f32) (result f32)
+                (f32.add (local.get 0) (local.get 1))
+            )
+        )
+    "#;
+    assert!(Module::new(&engine, wasm).is_err());
+}
+
+#[test]
+fn no_floats_executes_integer_modules() {
+    let engine = Engine::default();
+    let wasm = r#"
+        (module
+            (func (export "f") (param i32 i64) (result i64)
+                (i64.add (i64.extend_i32_s (local.get 0)) (local.get 1))
+            )
+        )
+    "#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let f = instance
+        .get_typed_func::<(i32, i64), i64>(&store, "f")
+        .unwrap();
+    assert_eq!(f.call(&mut store, (-1, 10)).unwrap(), 9);
+}
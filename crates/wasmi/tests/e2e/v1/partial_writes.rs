@@ -0,0 +1,228 @@
+//! Tests asserting that trapping Wasm writes never leave partial writes behind.
+
+use wasmi::{
+    core::TrapCode,
+    Config,
+    Engine,
+    FuncRef,
+    Instance,
+    Memory,
+    Module,
+    Store,
+    Table,
+    TypedFunc,
+    Val,
+};
+
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 1 1)
+        (table (export "table") 4 4 funcref)
+        (data $data "\01\02\03\04\05\06\07\08")
+        (elem $elem func $f $f $f $f)
+        (func $f)
+        (func (export "memory.copy") (param i32 i32 i32)
+            (memory.copy (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "memory.fill") (param i32 i32 i32)
+            (memory.fill (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "memory.init") (param i32 i32 i32)
+            (memory.init $data (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "i64.store") (param i32 i64)
+            (i64.store (local.get 0) (local.get 1))
+        )
+        (func (export "table.fill") (param i32 i32)
+            (table.fill (local.get 0) (ref.func $f) (local.get 1))
+        )
+        (func (export "table.init") (param i32 i32 i32)
+            (table.init $elem (local.get 0) (local.get 1) (local.get 2))
+        )
+    )
+"#;
+
+/// The size of the linear memory in bytes.
+const MEMORY_SIZE: i32 = 0x1_0000;
+
+/// Instantiates [`WASM`] with `fuel` if any and returns its store and instance.
+fn setup(fuel: Option<u64>) -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.consume_fuel(fuel.is_some());
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    if let Some(fuel) = fuel {
+        store.set_fuel(fuel).unwrap();
+    }
+    let instance = Instance::new(&mut store, &module, &[]).unwrap();
+    (store, instance)
+}
+
+/// Returns the linear memory of `instance` after filling it with a recognizable pattern.
+fn memory(store: &mut Store<()>, instance: &Instance) -> Memory {
+    let memory = instance.get_memory(&*store, "memory").unwrap();
+    for (n, byte) in memory.data_mut(&mut *store).iter_mut().enumerate() {
+        *byte = n as u8;
+    }
+    memory
+}
+
+/// Asserts that calling `func` with `params` traps with `trap_code` without altering `memory`.
+fn assert_no_partial_write<Params: wasmi::WasmParams>(
+    store: &mut Store<()>,
+    memory: Memory,
+    func: TypedFunc<Params, ()>,
+    params: Params,
+    trap_code: TrapCode,
+) {
+    let before = memory.data(&*store).to_vec();
+    let error = func.call(&mut *store, params).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(trap_code));
+    assert!(memory.data(&*store) == before, "partial write detected");
+}
+
+#[test]
+fn memory_copy_straddling_end() {
+    let (mut store, instance) = setup(None);
+    let memory = memory(&mut store, &instance);
+    let copy = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "memory.copy")
+        .unwrap();
+    let oob = TrapCode::MemoryOutOfBounds;
+    // Destination straddles the end of the linear memory.
+    assert_no_partial_write(&mut store, memory, copy, (MEMORY_SIZE - 4, 0, 8), oob);
+    // Source straddles the end of the linear memory.
+    assert_no_partial_write(&mut store, memory, copy, (0, MEMORY_SIZE - 4, 8), oob);
+    // Overlapping regions where only the destination straddles.
+    assert_no_partial_write(
+        &mut store,
+        memory,
+        copy,
+        (MEMORY_SIZE - 4, MEMORY_SIZE - 8, 8),
+        oob,
+    );
+    // Lengths that overflow the address space.
+    assert_no_partial_write(&mut store, memory, copy, (16, 0, -1), oob);
+    // In bounds copies up to the very end still succeed.
+    copy.call(&mut store, (MEMORY_SIZE - 8, 0, 8)).unwrap();
+    assert_eq!(
+        &memory.data(&store)[MEMORY_SIZE as usize - 8..],
+        &[0, 1, 2, 3, 4, 5, 6, 7]
+    );
+}
+
+#[test]
+fn memory_fill_straddling_end() {
+    let (mut store, instance) = setup(None);
+    let memory = memory(&mut store, &instance);
+    let fill = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "memory.fill")
+        .unwrap();
+    let oob = TrapCode::MemoryOutOfBounds;
+    assert_no_partial_write(&mut store, memory, fill, (MEMORY_SIZE - 1, 0xFF, 2), oob);
+    assert_no_partial_write(&mut store, memory, fill, (0, 0xFF, MEMORY_SIZE + 1), oob);
+    assert_no_partial_write(&mut store, memory, fill, (MEMORY_SIZE + 1, 0xFF, 0), oob);
+    fill.call(&mut store, (MEMORY_SIZE, 0xFF, 0)).unwrap();
+}
+
+#[test]
+fn memory_init_straddling_end() {
+    let (mut store, instance) = setup(None);
+    let memory = memory(&mut store, &instance);
+    let init = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "memory.init")
+        .unwrap();
+    let oob = TrapCode::MemoryOutOfBounds;
+    // Destination straddles the end of the linear memory.
+    assert_no_partial_write(&mut store, memory, init, (MEMORY_SIZE - 4, 0, 8), oob);
+    // Source straddles the end of the data segment.
+    assert_no_partial_write(&mut store, memory, init, (0, 4, 8), oob);
+    init.call(&mut store, (MEMORY_SIZE - 8, 0, 8)).unwrap();
+    assert_eq!(
+        &memory.data(&store)[MEMORY_SIZE as usize - 8..],
+        &[1, 2, 3, 4, 5, 6, 7, 8]
+    );
+}
+
+#[test]
+fn store_straddling_end() {
+    let (mut store, instance) = setup(None);
+    let memory = memory(&mut store, &instance);
+    let i64_store = instance
+        .get_typed_func::<(i32, i64), ()>(&store, "i64.store")
+        .unwrap();
+    for address in MEMORY_SIZE - 7..MEMORY_SIZE {
+        assert_no_partial_write(
+            &mut store,
+            memory,
+            i64_store,
+            (address, -1),
+            TrapCode::MemoryOutOfBounds,
+        );
+    }
+    i64_store.call(&mut store, (MEMORY_SIZE - 8, -1)).unwrap();
+}
+
+#[test]
+fn out_of_fuel_bulk_write() {
+    let (mut store, instance) = setup(Some(100));
+    let memory = memory(&mut store, &instance);
+    let fill = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "memory.fill")
+        .unwrap();
+    assert_no_partial_write(
+        &mut store,
+        memory,
+        fill,
+        (0, 0xFF, MEMORY_SIZE),
+        TrapCode::OutOfFuel,
+    );
+}
+
+#[test]
+fn table_writes_straddling_end() {
+    let (mut store, instance) = setup(None);
+    let table: Table = instance.get_table(&store, "table").unwrap();
+    table
+        .fill(&mut store, 0, Val::FuncRef(FuncRef::null()), 4)
+        .unwrap();
+    let is_null = |store: &Store<()>| {
+        (0..4)
+            .map(|index| {
+                table
+                    .get(store, index)
+                    .unwrap()
+                    .funcref()
+                    .unwrap()
+                    .is_null()
+            })
+            .collect::<Vec<_>>()
+    };
+    let fill = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "table.fill")
+        .unwrap();
+    let init = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "table.init")
+        .unwrap();
+    let oob = Some(TrapCode::TableOutOfBounds);
+    let error = fill.call(&mut store, (2, 3)).unwrap_err();
+    assert_eq!(error.as_trap_code(), oob);
+    let error = init.call(&mut store, (3, 0, 2)).unwrap_err();
+    assert_eq!(error.as_trap_code(), oob);
+    assert_eq!(is_null(&store), [true; 4]);
+    fill.call(&mut store, (2, 2)).unwrap();
+    assert_eq!(is_null(&store), [true, true, false, false]);
+}
+
+#[test]
+fn host_write_straddling_end() {
+    let (mut store, instance) = setup(None);
+    let memory = memory(&mut store, &instance);
+    let before = memory.data(&store).to_vec();
+    let end = MEMORY_SIZE as usize;
+    assert!(memory.write(&mut store, end - 2, &[0xFF; 4]).is_err());
+    assert!(memory.write(&mut store, usize::MAX, &[0xFF; 4]).is_err());
+    assert!(memory.read(&store, usize::MAX, &mut [0; 4]).is_err());
+    assert!(memory.data(&store) == before);
+}
@@ -0,0 +1,108 @@
+//! Tests for Wasm module pre-initialization via [`PreInitializer`].
+#![cfg(feature = "preinit")]
+
+use wasmi::{Caller, Engine, Linker, PreInitializer, Store};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "count" (func $count))
+        (memory (export "memory") 1 4)
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (global $callback (mut funcref) (ref.null func))
+        (data (i32.const 0) "\01\02\03\04")
+        (func $start
+            (call $count)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+        )
+        (func $answer (result i32) (i32.const 42))
+        (func (export "init")
+            (drop (memory.grow (i32.const 1)))
+            (i32.store (i32.const 2) (i32.const 0xAABBCCDD))
+            (i32.store8 (i32.const 70000) (i32.const 0xFF))
+            (global.set $counter (i32.add (global.get $counter) (i32.const 10)))
+            (global.set $callback (ref.func $answer))
+        )
+        (func (export "call_callback") (result i32)
+            (call_indirect (result i32) (i32.const 0))
+        )
+        (func (export "has_callback") (result i32)
+            (i32.eqz (ref.is_null (global.get $callback)))
+        )
+        (table 1 funcref)
+        (elem (i32.const 0) $answer)
+        (start $start)
+    )
+"#;
+
+fn linker(engine: &Engine) -> Linker<u32> {
+    let mut linker = <Linker<u32>>::new(engine);
+    linker
+        .func_wrap("host", "count", |mut caller: Caller<u32>| {
+            *caller.data_mut() += 1;
+        })
+        .unwrap();
+    linker
+}
+
+#[test]
+fn preinit_captures_memory_and_globals() {
+    let engine = Engine::default();
+    let linker = linker(&engine);
+    let mut store = Store::new(&engine, 0_u32);
+    let module = PreInitializer::new()
+        .init_func("init")
+        .run_to_module(&mut store, &linker, WASM)
+        .unwrap();
+    // The `start` function ran once during pre-initialization.
+    assert_eq!(*store.data(), 1);
+    assert!(module.get_export("init").is_none());
+
+    let mut store = Store::new(&engine, 0_u32);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    // The `start` function does not run again for the snapshot.
+    assert_eq!(*store.data(), 0);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(memory.size(&store), 2);
+    assert_eq!(
+        &memory.data(&store)[..6],
+        &[0x01, 0x02, 0xDD, 0xCC, 0xBB, 0xAA]
+    );
+    assert_eq!(memory.data(&store)[70000], 0xFF);
+    let counter = instance.get_global(&store, "counter").unwrap();
+    assert_eq!(counter.get(&store).i32(), Some(11));
+    let has_callback = instance
+        .get_typed_func::<(), i32>(&store, "has_callback")
+        .unwrap();
+    assert_eq!(has_callback.call(&mut store, ()).unwrap(), 1);
+    let call_callback = instance
+        .get_typed_func::<(), i32>(&store, "call_callback")
+        .unwrap();
+    assert_eq!(call_callback.call(&mut store, ()).unwrap(), 42);
+}
+
+#[test]
+fn preinit_keeps_init_func() {
+    let engine = Engine::default();
+    let linker = linker(&engine);
+    let mut store = Store::new(&engine, 0_u32);
+    let module = PreInitializer::new()
+        .init_func("init")
+        .keep_init_func(true)
+        .run_to_module(&mut store, &linker, WASM)
+        .unwrap();
+    assert!(module.get_export("init").is_some());
+}
+
+#[test]
+fn preinit_missing_init_func() {
+    let engine = Engine::default();
+    let linker = linker(&engine);
+    let mut store = Store::new(&engine, 0_u32);
+    assert!(PreInitializer::new()
+        .run(&mut store, &linker, WASM)
+        .is_err());
+}
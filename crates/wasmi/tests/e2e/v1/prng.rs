@@ -0,0 +1,99 @@
+//! Tests for the built-in `wasmi:prng` host module.
+#![cfg(feature = "prng")]
+
+use wasmi::{Engine, Linker, Module, Prng, Store};
+
+const GUEST: &str = r#"
+    (module
+        (import "wasmi:prng" "next_u64" (func $next_u64 (result i64)))
+        (import "wasmi:prng" "fill" (func $fill (param i32 i32)))
+        (import "wasmi:prng" "seed" (func $seed (result i64)))
+        (memory (export "memory") 1)
+        (func (export "next_u64") (result i64)
+            (call $next_u64)
+        )
+        (func (export "fill_and_load") (result i64)
+            (call $fill (i32.const 3) (i32.const 13))
+            (i64.load (i32.const 8))
+        )
+        (func (export "fill_oob")
+            (call $fill (i32.const 65530) (i32.const 7))
+        )
+        (func (export "seed") (result i64)
+            (call $seed)
+        )
+    )
+"#;
+
+fn run(prng: Prng, func: &str) -> (i64, Prng) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, prng);
+    let mut linker = <Linker<Prng>>::new(&engine);
+    Prng::add_to_linker(&mut linker, |prng| prng).unwrap();
+    let module = Module::new(&engine, GUEST).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let result = instance
+        .get_typed_func::<(), i64>(&store, func)
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    (result, *store.data())
+}
+
+#[test]
+fn prng_is_deterministic() {
+    let (a, _) = run(Prng::new(42), "next_u64");
+    let (b, _) = run(Prng::new(42), "next_u64");
+    let (c, _) = run(Prng::new(43), "next_u64");
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a as u64, Prng::new(42).next_u64());
+    let (seed, _) = run(Prng::new(42), "seed");
+    assert_eq!(seed, 42);
+}
+
+#[test]
+fn prng_resumes_sequence() {
+    let mut prng = Prng::new(7);
+    let numbers = [prng.next_u64(), prng.next_u64(), prng.next_u64()];
+    let (next, after) = run(Prng::resume(7, 2), "next_u64");
+    assert_eq!(next as u64, numbers[2]);
+    assert_eq!(after, prng);
+    assert_eq!(after.draws(), 3);
+}
+
+#[test]
+fn prng_fills_guest_memory() {
+    let (loaded, after) = run(Prng::new(1), "fill_and_load");
+    let mut expected = [0x00_u8; 16];
+    Prng::new(1).fill(&mut expected[3..]);
+    assert_eq!(
+        loaded,
+        i64::from_le_bytes(expected[8..].try_into().unwrap())
+    );
+    // 13 bytes require two draws.
+    assert_eq!(after.draws(), 2);
+}
+
+#[test]
+fn prng_fill_out_of_bounds_traps() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Prng::new(0));
+    let mut linker = <Linker<Prng>>::new(&engine);
+    Prng::add_to_linker(&mut linker, |prng| prng).unwrap();
+    let module = Module::new(&engine, GUEST).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let fill_oob = instance
+        .get_typed_func::<(), ()>(&store, "fill_oob")
+        .unwrap();
+    assert!(fill_oob.call(&mut store, ()).is_err());
+    assert_eq!(store.data().draws(), 0);
+}
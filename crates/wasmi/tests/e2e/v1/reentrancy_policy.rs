@@ -0,0 +1,106 @@
+//! Tests for denying reentrant guest calls via [`StorePolicy::DenyReentrantGuestCalls`].
+
+use std::panic::{self, AssertUnwindSafe};
+use wasmi::{
+    errors::{ErrorKind, FuncError},
+    Caller,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    StorePolicy,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "call_back" (func $call_back (param i32) (result i32)))
+        (func (export "run") (param i32) (result i32)
+            (call $call_back (local.get 0))
+        )
+        (func (export "inc") (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] twice with the given `policy`.
+///
+/// The `host::call_back` function calls the `inc` export of the [`Instance`] stored in
+/// the host state which is the first instance unless replaced by the caller.
+/// It panics for negative inputs.
+fn setup(policy: StorePolicy) -> (Store<Option<Instance>>, Instance, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, None);
+    store.set_policy(policy);
+    let mut linker = <Linker<Option<Instance>>>::new(&engine);
+    linker
+        .func_wrap(
+            "host",
+            "call_back",
+            |mut caller: Caller<Option<Instance>>, input: i32| -> Result<i32, Error> {
+                assert!(input >= 0, "negative input: {input}");
+                let instance = caller.data().unwrap();
+                let inc = instance.get_typed_func::<i32, i32>(&caller, "inc")?;
+                inc.call(&mut caller, input)
+            },
+        )
+        .unwrap();
+    let instantiate = |store: &mut Store<Option<Instance>>| {
+        linker
+            .instantiate(&mut *store, &module)
+            .unwrap()
+            .start(&mut *store)
+            .unwrap()
+    };
+    let first = instantiate(&mut store);
+    let second = instantiate(&mut store);
+    *store.data_mut() = Some(first);
+    (store, first, second)
+}
+
+#[test]
+fn allow_by_default() {
+    let (mut store, first, _second) = setup(StorePolicy::default());
+    assert_eq!(store.policy(), StorePolicy::AllowReentrantGuestCalls);
+    let run = first.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, 41).unwrap(), 42);
+}
+
+#[test]
+fn deny_reentrant_call() {
+    let (mut store, first, _second) = setup(StorePolicy::DenyReentrantGuestCalls);
+    let run = first.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let error = run.call(&mut store, 41).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::ReentrantCall)
+    ));
+    // Calls from the host into the instance are unaffected.
+    let inc = first.get_typed_func::<i32, i32>(&store, "inc").unwrap();
+    assert_eq!(inc.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+fn deny_allows_other_instances() {
+    let (mut store, first, second) = setup(StorePolicy::DenyReentrantGuestCalls);
+    let run_first = first.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let run_second = second.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(run_second.call(&mut store, 41).unwrap(), 42);
+    assert!(run_first.call(&mut store, 41).is_err());
+    // The guard is released once the denied host function call returned.
+    assert_eq!(run_second.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+fn deny_recovers_from_host_panics() {
+    let (mut store, first, second) = setup(StorePolicy::DenyReentrantGuestCalls);
+    let run_first = first.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let run_second = second.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| run_first.call(&mut store, -1)));
+    assert!(result.is_err());
+    // The host function call of the first instance ended with the panic.
+    assert_eq!(run_second.call(&mut store, 41).unwrap(), 42);
+}
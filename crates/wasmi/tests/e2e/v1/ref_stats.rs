@@ -0,0 +1,95 @@
+//! Tests for reference statistics and leak detection via [`Store::ref_stats`].
+
+use wasmi::{
+    core::ValType,
+    Engine,
+    ExternRef,
+    Func,
+    FuncRef,
+    Global,
+    Mutability,
+    RefStats,
+    Store,
+    Table,
+    TableType,
+    Val,
+};
+
+#[test]
+fn empty_store() {
+    let store = <Store<()>>::default();
+    assert_eq!(store.ref_stats(), RefStats::default());
+    assert!(store.extern_ref_leaks().is_empty());
+}
+
+#[test]
+fn counts_live_references() {
+    let mut store = Store::new(&Engine::default(), ());
+    let func = Func::wrap(&mut store, || {});
+    let funcs = Table::new(
+        &mut store,
+        TableType::new(ValType::FuncRef, 3, None),
+        Val::FuncRef(FuncRef::new(func)),
+    )
+    .unwrap();
+    funcs
+        .set(&mut store, 0, Val::FuncRef(FuncRef::null()))
+        .unwrap();
+    let object = ExternRef::new(&mut store, 42_i32);
+    Table::new(
+        &mut store,
+        TableType::new(ValType::ExternRef, 2, None),
+        Val::ExternRef(object),
+    )
+    .unwrap();
+    Global::new(&mut store, Val::ExternRef(object), Mutability::Const);
+    let leaked = ExternRef::new(&mut store, 7_i32);
+    let stats = store.ref_stats();
+    assert_eq!(
+        stats,
+        RefStats {
+            extern_objects: 2,
+            reachable_extern_objects: 1,
+            live_externrefs: 3,
+            funcs: 1,
+            live_funcrefs: 2,
+        }
+    );
+    assert_eq!(stats.unreachable_extern_objects(), 1);
+    let leaks = store.extern_ref_leaks();
+    assert_eq!(leaks.len(), 1);
+    assert_eq!(
+        leaks[0]
+            .externref()
+            .data(&store)
+            .unwrap()
+            .downcast_ref::<i32>(),
+        leaked.data(&store).unwrap().downcast_ref::<i32>(),
+    );
+    assert!(leaks[0].backtrace().is_none());
+}
+
+#[test]
+fn tracks_creation_backtraces() {
+    let mut store = Store::new(&Engine::default(), ());
+    let untracked = ExternRef::new(&mut store, 1_i32);
+    store.track_extern_refs(true);
+    let tracked = ExternRef::new(&mut store, 2_i32);
+    let global = Global::new(&mut store, Val::ExternRef(untracked), Mutability::Var);
+    let leaks = store.extern_ref_leaks();
+    assert_eq!(leaks.len(), 1);
+    assert!(leaks[0].backtrace().is_some());
+    assert_eq!(
+        leaks[0]
+            .externref()
+            .data(&store)
+            .unwrap()
+            .downcast_ref::<i32>(),
+        tracked.data(&store).unwrap().downcast_ref::<i32>(),
+    );
+    // Replacing the only reference to an extern object leaks it.
+    global.set(&mut store, Val::ExternRef(tracked)).unwrap();
+    let leaks = store.extern_ref_leaks();
+    assert_eq!(leaks.len(), 1);
+    assert!(leaks[0].backtrace().is_none());
+}
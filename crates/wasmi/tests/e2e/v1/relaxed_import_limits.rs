@@ -0,0 +1,117 @@
+//! Tests for relaxed import limits via [`Config::relaxed_import_limits`].
+
+use wasmi::{
+    core::ValType,
+    Config,
+    Engine,
+    Extern,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+    Table,
+    TableType,
+    Val,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "memory" (memory 1 2))
+        (import "env" "table" (table 1 2 funcref))
+        (func (export "grow") (param i32) (result i32)
+            (memory.grow (local.get 0))
+        )
+    )
+"#;
+
+/// Returns a store with `relaxed` import limits and a linker defining
+/// a linear memory and table with the given limits.
+fn setup(
+    relaxed: bool,
+    memory: (u32, Option<u32>),
+    table: (u32, Option<u32>),
+) -> (Store<()>, Linker<()>, Module) {
+    let mut config = Config::default();
+    config.relaxed_import_limits(relaxed);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(memory.0, memory.1).unwrap()).unwrap();
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValType::FuncRef, table.0, table.1),
+        Val::default(ValType::FuncRef),
+    )
+    .unwrap();
+    let mut linker = Linker::new(&engine);
+    linker.define("env", "memory", memory).unwrap();
+    linker.define("env", "table", table).unwrap();
+    (store, linker, module)
+}
+
+/// Returns the error message of instantiating `module` via `linker` and via [`Instance::new`].
+fn instantiate_errors(
+    store: &mut Store<()>,
+    linker: &Linker<()>,
+    module: &Module,
+) -> (String, String) {
+    let linker_error = linker.instantiate(&mut *store, module).unwrap_err();
+    let externs: Vec<Extern> = module
+        .imports()
+        .map(|import| linker.get(&*store, import.module(), import.name()).unwrap())
+        .collect();
+    let instance_error = Instance::new(&mut *store, module, &externs).unwrap_err();
+    (linker_error.to_string(), instance_error.to_string())
+}
+
+#[test]
+fn strict_limits_reject_larger_maximum() {
+    let (mut store, linker, module) = setup(false, (1, Some(4)), (1, Some(2)));
+    let (linker_error, instance_error) = instantiate_errors(&mut store, &linker, &module);
+    for error in [&linker_error, &instance_error] {
+        assert!(
+            error.contains("maximum of 4 pages exceeds the declared maximum of 2 pages"),
+            "{error}"
+        );
+        assert!(error.contains("Config::relaxed_import_limits"), "{error}");
+    }
+    assert!(linker_error.starts_with("import env::memory: incompatible memory type"));
+
+    let (mut store, linker, module) = setup(false, (1, None), (1, Some(2)));
+    let (linker_error, _) = instantiate_errors(&mut store, &linker, &module);
+    assert!(linker_error.contains("unbounded maximum exceeds the declared maximum of 2 pages"));
+
+    let (mut store, linker, module) = setup(false, (1, Some(2)), (1, Some(8)));
+    let (linker_error, instance_error) = instantiate_errors(&mut store, &linker, &module);
+    for error in [&linker_error, &instance_error] {
+        assert!(
+            error.contains("maximum of 8 elements exceeds the declared maximum of 2 elements"),
+            "{error}"
+        );
+    }
+}
+
+#[test]
+fn relaxed_limits_allow_larger_maximum() {
+    let (mut store, linker, module) = setup(true, (1, Some(4)), (1, None));
+    let instance = linker.instantiate(&mut store, &module).unwrap();
+    let instance = instance.start(&mut store).unwrap();
+    // Note: growth is limited by the maximum of the provided memory, not the declared one.
+    let grow = instance.get_typed_func::<i32, i32>(&store, "grow").unwrap();
+    assert_eq!(grow.call(&mut store, 3).unwrap(), 1);
+    assert_eq!(grow.call(&mut store, 1).unwrap(), -1);
+}
+
+#[test]
+fn relaxed_limits_check_minimum() {
+    let (mut store, linker, module) = setup(true, (0, Some(4)), (1, Some(2)));
+    let (linker_error, instance_error) = instantiate_errors(&mut store, &linker, &module);
+    for error in [&linker_error, &instance_error] {
+        assert!(
+            error.contains("minimum of 0 pages is less than the required minimum of 1 pages"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Tests for reporting the Wasm proposals used by a module via [`Module::required_features`].
+
+use wasmi::{Config, Engine, Module};
+
+fn required_features(wat: &str) -> Vec<&'static str> {
+    let mut config = Config::default();
+    config.wasm_custom_page_sizes(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wat).unwrap();
+    module.required_features().iter().collect()
+}
+
+#[test]
+fn mvp_module_requires_nothing() {
+    let features = required_features(
+        r#"
+        (module
+            (memory 1)
+            (table 1 funcref)
+            (global i32 (i32.const 0))
+            (elem (i32.const 0) $f)
+            (data (i32.const 0) "mvp")
+            (func $f (param i32) (result i32)
+                (block (result i32)
+                    (i32.load (local.get 0))
+                )
+            )
+        )
+    "#,
+    );
+    assert!(features.is_empty(), "unexpected features: {features:?}");
+}
+
+#[test]
+#[cfg(not(feature = "no-floats"))]
+fn floats() {
+    for wat in [
+        "(module (func (param f32)))",
+        "(module (func (local f64)))",
+        "(module (global f32 (f32.const 0)))",
+        "(module (func (drop (f64.const 1))))",
+        "(module (memory 1) (func (drop (i64.reinterpret_f64 (f64.load (i32.const 0))))))",
+    ] {
+        assert_eq!(required_features(wat), ["floats"], "{wat}");
+    }
+}
+
+#[test]
+fn operators() {
+    for (wat, expected) in [
+        (
+            "(module (func (param i32) (result i32) (i32.extend8_s (local.get 0))))",
+            &["sign-extension"][..],
+        ),
+        (
+            "(module (memory 1) (func (memory.fill (i32.const 0) (i32.const 0) (i32.const 0))))",
+            &["bulk-memory"][..],
+        ),
+        (
+            "(module (table 1 funcref) (func (result i32) (table.size 0)))",
+            &["reference-types"][..],
+        ),
+        ("(module (func $f (return_call $f)))", &["tail-call"][..]),
+        (
+            "(module (func (result i32 i32) (i32.const 0) (i32.const 1)))",
+            &["multi-value"][..],
+        ),
+    ] {
+        assert_eq!(required_features(wat), expected, "{wat}");
+    }
+}
+
+#[test]
+#[cfg(not(feature = "no-floats"))]
+fn float_operators() {
+    let wat = "(module (func (param f32) (result i32) (i32.trunc_sat_f32_s (local.get 0))))";
+    assert_eq!(
+        required_features(wat),
+        ["saturating-float-to-int", "floats"],
+        "{wat}"
+    );
+}
+
+#[test]
+fn module_entities() {
+    for (wat, expected) in [
+        (
+            r#"(module (import "env" "g" (global (mut i32))))"#,
+            &["mutable-global"][..],
+        ),
+        (
+            r#"(module (global (export "g") (mut i32) (i32.const 0)))"#,
+            &["mutable-global"][..],
+        ),
+        ("(module (memory 1) (memory 1))", &["multi-memory"][..]),
+        (
+            "(module (memory 1) (data \"passive\"))",
+            &["bulk-memory"][..],
+        ),
+        ("(module (table 1 externref))", &["reference-types"][..]),
+        (
+            "(module (global i32 (i32.add (i32.const 1) (i32.const 2))))",
+            &["extended-const"][..],
+        ),
+        (
+            "(module (memory 1 (pagesize 1)))",
+            &["custom-page-sizes"][..],
+        ),
+    ] {
+        assert_eq!(required_features(wat), expected, "{wat}");
+    }
+}
+
+#[test]
+#[cfg(not(feature = "no-floats"))]
+fn required_features_api() {
+    let engine = Engine::default();
+    let wat = "(module (func (param f32) (result i32) (i32.trunc_sat_f32_s (local.get 0))))";
+    let module = Module::new(&engine, wat).unwrap();
+    let features = module.required_features();
+    assert!(!features.is_empty());
+    assert!(features.contains("floats"));
+    assert!(!features.contains("bulk-memory"));
+    assert_eq!(features.to_string(), "saturating-float-to-int, floats");
+}
@@ -130,6 +130,25 @@ fn test_memory_does_not_grow_on_limited_growth() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_memory_with_maximum_address_space_grows_to_limit() -> Result<(), Error> {
+    let limits = StoreLimitsBuilder::new().memory_size(0x30_0000).build();
+    let (mut store, linker) = test_setup(limits);
+    let wasm = r#"
+        (module
+            (memory 0x20 0x1_0000)
+            (func (export "memory_grow") (param $pages i32) (result i32) (memory.grow (local.get $pages)))
+        )
+    "#;
+    let module = create_module(&store, wasm.as_bytes())?;
+    let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+    let memory_grow = instance.get_typed_func::<(i32,), i32>(&store, "memory_grow")?;
+    // The maximum of the memory spans the entire 32-bit address space.
+    assert_eq!(memory_grow.call(&mut store, (0x10,))?, 0x20);
+    assert_eq!(memory_grow.call(&mut store, (0x10,))?, -1);
+    Ok(())
+}
+
 #[test]
 fn test_memory_traps_on_limited_growth() -> Result<(), Error> {
     let limits = StoreLimitsBuilder::new()
@@ -103,6 +103,30 @@ fn resumable_call_smoldot_01() {
     }
 }
 
+#[test]
+fn resumable_call_smoldot_typed_inputs() {
+    let (mut store, wasm_fn) = resumable_call_smoldot_common(
+        r#"
+        (module
+            (import "env" "host_fn" (func $host_fn (result i32)))
+            (func (export "test") (result i32)
+                (i32.add (call $host_fn) (call $host_fn))
+            )
+        )
+        "#,
+    );
+    let invocation = wasm_fn.call_resumable(&mut store, ()).unwrap_resumable();
+    assert!(invocation.resume_typed(&mut store, 1_i64).is_err());
+    let invocation = wasm_fn.call_resumable(&mut store, ()).unwrap_resumable();
+    let invocation = invocation
+        .resume_typed(&mut store, 40_i32)
+        .unwrap_resumable();
+    match invocation.resume_typed(&mut store, (2_i32,)).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 42),
+        TypedResumableCall::Resumable(_) => panic!("expected TypeResumableCall::Finished"),
+    }
+}
+
 #[test]
 fn resumable_call_smoldot_tail_01() {
     let (mut store, wasm_fn) = resumable_call_smoldot_common(
@@ -172,6 +196,59 @@ fn resumable_call_smoldot_02() {
     }
 }
 
+#[derive(Debug, PartialEq)]
+struct Syscall {
+    id: i32,
+}
+
+#[test]
+fn resumable_call_suspend_with_payload() {
+    let (mut store, mut linker) = test_setup(0);
+    linker
+        .func_wrap("env", "syscall", |id: i32| -> Result<i64, Error> {
+            Err(Error::suspend(Syscall { id }))
+        })
+        .unwrap();
+    let wasm = r#"
+        (module
+            (import "env" "syscall" (func $syscall (param i32) (result i64)))
+            (func (export "test") (result i64)
+                (i64.add (call $syscall (i32.const 1)) (call $syscall (i32.const 2)))
+            )
+        )
+    "#;
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let test = instance.get_typed_func::<(), i64>(&store, "test").unwrap();
+    let mut invocation = test.call_resumable(&mut store, ()).unwrap_resumable();
+    assert_eq!(invocation.payload_mut::<i32>(), None);
+    assert_eq!(invocation.payload_mut(), Some(&mut Syscall { id: 1 }));
+    let mut invocation = invocation
+        .resume_typed(&mut store, 10_i64)
+        .unwrap_resumable();
+    let payload = invocation.take_payload().unwrap();
+    assert_eq!(*payload.downcast::<Syscall>().unwrap(), Syscall { id: 2 });
+    assert!(invocation.take_payload().is_none());
+    match invocation.resume_typed(&mut store, 32_i64).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 42),
+        TypedResumableCall::Resumable(_) => panic!("expected TypeResumableCall::Finished"),
+    }
+    // Suspending a host function that is called from the host returns the plain error.
+    let syscall = Func::wrap(&mut store, |id: i32| -> Result<i64, Error> {
+        Err(Error::suspend(Syscall { id }))
+    });
+    let mut error = syscall
+        .typed::<i32, i64>(&store)
+        .unwrap()
+        .call(&mut store, 3)
+        .unwrap_err();
+    assert_eq!(error.payload_mut(), Some(&mut Syscall { id: 3 }));
+}
+
 #[test]
 fn resumable_call_host() {
     let (mut store, _linker) = test_setup(0);
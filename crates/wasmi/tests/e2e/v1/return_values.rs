@@ -0,0 +1,93 @@
+//! Tests for Wasm functions returning many values from non-contiguous registers.
+
+use wasmi::{Engine, Instance, Linker, Module, Store, Val};
+
+/// Returns a Wasm module with functions returning `n` values with `n` in `4..=9`.
+///
+/// - `reversed{n}` returns its `n` parameters in reversed order.
+/// - `call{n}` calls `reversed{n}` and returns its results in reversed order.
+fn wat() -> String {
+    let funcs = (4..=9)
+        .map(|n| {
+            let types = vec!["i32"; n].join(" ");
+            let reversed = (0..n)
+                .rev()
+                .map(|i| format!("(local.get {i})"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let params = (0..n)
+                .map(|i| format!("(local.get {i})"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let locals = (0..n)
+                .rev()
+                .map(|i| format!("(local.set {})", n + i))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let results = (n..2 * n)
+                .rev()
+                .map(|i| format!("(local.get {i})"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(
+                r#"
+                (func $reversed{n} (export "reversed{n}") (param {types}) (result {types})
+                    (return {reversed})
+                )
+                (func (export "call{n}") (param {types}) (result {types})
+                    (local {types})
+                    (call $reversed{n} {params})
+                    {locals}
+                    (return {results})
+                )
+                "#
+            )
+        })
+        .collect::<String>();
+    format!("(module {funcs})")
+}
+
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wat()).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` with the parameters `0..n` and returns its results.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, n: i32) -> Vec<i32> {
+    let func = instance.get_func(&mut *store, name).unwrap();
+    let params = (0..n).map(Val::I32).collect::<Vec<_>>();
+    let mut results = vec![Val::I32(0); n as usize];
+    func.call(&mut *store, &params, &mut results).unwrap();
+    results.iter().map(|result| result.i32().unwrap()).collect()
+}
+
+#[test]
+fn return_many_values_to_host() {
+    let (mut store, instance) = setup();
+    for n in 4..=9 {
+        let expected = (0..n).rev().collect::<Vec<_>>();
+        assert_eq!(
+            call(&mut store, &instance, &format!("reversed{n}"), n),
+            expected
+        );
+    }
+}
+
+#[test]
+fn return_many_values_to_wasm() {
+    let (mut store, instance) = setup();
+    for n in 4..=9 {
+        let expected = (0..n).collect::<Vec<_>>();
+        assert_eq!(
+            call(&mut store, &instance, &format!("call{n}"), n),
+            expected
+        );
+    }
+}
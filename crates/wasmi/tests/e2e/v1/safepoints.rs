@@ -0,0 +1,91 @@
+//! Tests for the enumeration of safepoints via [`Module::safepoints`].
+
+use wasmi::{Config, Engine, Module, SafepointKind};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "tick" (func $tick))
+        (func $spin (param i32) (result i32)
+            (loop $continue
+                (local.set 0 (i32.add (local.get 0) (i32.const 1)))
+                (br_if $continue (i32.ne (local.get 0) (i32.const 100)))
+            )
+            (local.get 0)
+        )
+        (func $ticking (param i32)
+            (loop $continue
+                (call $tick)
+                (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                (br_if $continue (local.get 0))
+            )
+        )
+        (func $straight (param i32) (result i32)
+            (i32.add (local.get 0) (local.get 0))
+        )
+        (func $tail
+            (return_call $tick)
+        )
+    )
+"#;
+
+/// Compiles [`WASM`] with fuel metering enabled if `consume_fuel` is `true`.
+fn module(consume_fuel: bool) -> Module {
+    let mut config = Config::default();
+    config.consume_fuel(consume_fuel);
+    let engine = Engine::new(&config);
+    Module::new(&engine, WASM).unwrap()
+}
+
+#[test]
+fn without_fuel_metering() {
+    let module = module(false);
+    let spin = module.safepoints(1).unwrap();
+    assert!(spin.safepoints().is_empty());
+    assert_eq!(spin.back_edges().len(), 1);
+    assert!(!spin.is_preemptible());
+    let edge = spin.unchecked_back_edges().next().unwrap();
+    assert!(edge.target() <= edge.branch());
+    assert!(edge.branch() < spin.len_instrs());
+
+    let ticking = module.safepoints(2).unwrap();
+    assert_eq!(ticking.back_edges().len(), 1);
+    assert!(ticking.is_preemptible());
+    assert!(ticking
+        .safepoints()
+        .iter()
+        .all(|safepoint| safepoint.kind() == SafepointKind::Call));
+
+    let straight = module.safepoints(3).unwrap();
+    assert!(straight.safepoints().is_empty());
+    assert!(straight.back_edges().is_empty());
+    assert!(straight.is_preemptible());
+
+    let tail = module.safepoints(4).unwrap();
+    assert_eq!(tail.safepoints().len(), 1);
+    assert_eq!(tail.safepoints()[0].kind(), SafepointKind::TailCall);
+}
+
+#[test]
+fn with_fuel_metering() {
+    let module = module(true);
+    for func in 1..=4 {
+        let safepoints = module.safepoints(func).unwrap();
+        assert!(safepoints.is_preemptible());
+        assert_eq!(safepoints.safepoints()[0].kind(), SafepointKind::FuelCheck);
+        assert!(safepoints
+            .safepoints()
+            .windows(2)
+            .all(|pair| pair[0].pos() < pair[1].pos()));
+    }
+    assert_eq!(
+        module.safepoints(1).unwrap().unchecked_back_edges().count(),
+        0
+    );
+}
+
+#[test]
+fn invalid_function_index() {
+    let module = module(false);
+    assert!(module.safepoints(0).is_err());
+    assert!(module.safepoints(5).is_err());
+}
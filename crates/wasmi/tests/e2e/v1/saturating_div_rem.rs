@@ -0,0 +1,115 @@
+//! Tests for non-trapping integer division and remainder via [`Config::saturating_div_rem`].
+
+use wasmi::{Config, Engine, Instance, Linker, Module, Store};
+
+const WASM: &str = r#"
+    (module
+        (func (export "i32.div_s") (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func (export "i32.div_u") (param i32 i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+        (func (export "i32.rem_s") (param i32 i32) (result i32)
+            (i32.rem_s (local.get 0) (local.get 1))
+        )
+        (func (export "i32.rem_u") (param i32 i32) (result i32)
+            (i32.rem_u (local.get 0) (local.get 1))
+        )
+        (func (export "i64.div_s") (param i64 i64) (result i64)
+            (i64.div_s (local.get 0) (local.get 1))
+        )
+        (func (export "i64.rem_u") (param i64 i64) (result i64)
+            (i64.rem_u (local.get 0) (local.get 1))
+        )
+        (func (export "i32.div_s_zero") (param i32) (result i32)
+            (i32.div_s (local.get 0) (i32.const 0))
+        )
+        (func (export "i32.div_s_min") (param i32) (result i32)
+            (i32.div_s (i32.const 0x8000_0000) (local.get 0))
+        )
+        (func (export "i64.div_s_const") (result i64)
+            (i64.div_s (i64.const 0x8000_0000_0000_0000) (i64.const -1))
+        )
+    )
+"#;
+
+fn setup(saturating: bool) -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.saturating_div_rem(saturating);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+fn call_i32(store: &mut Store<()>, instance: Instance, name: &str, lhs: i32, rhs: i32) -> i32 {
+    instance
+        .get_typed_func::<(i32, i32), i32>(&*store, name)
+        .unwrap()
+        .call(store, (lhs, rhs))
+        .unwrap()
+}
+
+fn call_i64(store: &mut Store<()>, instance: Instance, name: &str, lhs: i64, rhs: i64) -> i64 {
+    instance
+        .get_typed_func::<(i64, i64), i64>(&*store, name)
+        .unwrap()
+        .call(store, (lhs, rhs))
+        .unwrap()
+}
+
+#[test]
+fn saturating_div_rem_returns_values() {
+    let (mut store, instance) = setup(true);
+    let store = &mut store;
+    assert_eq!(call_i32(store, instance, "i32.div_s", 7, 2), 3);
+    assert_eq!(call_i32(store, instance, "i32.div_s", 7, 0), 0);
+    assert_eq!(
+        call_i32(store, instance, "i32.div_s", i32::MIN, -1),
+        i32::MAX
+    );
+    assert_eq!(call_i32(store, instance, "i32.div_u", -1, 0), 0);
+    assert_eq!(call_i32(store, instance, "i32.div_u", -1, 2), i32::MAX);
+    assert_eq!(call_i32(store, instance, "i32.rem_s", 7, 0), 0);
+    assert_eq!(call_i32(store, instance, "i32.rem_s", i32::MIN, -1), 0);
+    assert_eq!(call_i32(store, instance, "i32.rem_u", 7, 0), 0);
+    assert_eq!(call_i32(store, instance, "i32.rem_u", 7, 4), 3);
+    assert_eq!(
+        call_i64(store, instance, "i64.div_s", i64::MIN, -1),
+        i64::MAX
+    );
+    assert_eq!(call_i64(store, instance, "i64.div_s", 9, 0), 0);
+    assert_eq!(call_i64(store, instance, "i64.rem_u", 9, 0), 0);
+    let div_s_zero = instance
+        .get_typed_func::<i32, i32>(&*store, "i32.div_s_zero")
+        .unwrap();
+    assert_eq!(div_s_zero.call(&mut *store, 42).unwrap(), 0);
+    let div_s_min = instance
+        .get_typed_func::<i32, i32>(&*store, "i32.div_s_min")
+        .unwrap();
+    assert_eq!(div_s_min.call(&mut *store, -1).unwrap(), i32::MAX);
+    let div_s_const = instance
+        .get_typed_func::<(), i64>(&*store, "i64.div_s_const")
+        .unwrap();
+    assert_eq!(div_s_const.call(&mut *store, ()).unwrap(), i64::MAX);
+}
+
+#[test]
+fn default_div_rem_traps() {
+    let (mut store, instance) = setup(false);
+    let div_s = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "i32.div_s")
+        .unwrap();
+    assert!(div_s.call(&mut store, (7, 0)).is_err());
+    assert!(div_s.call(&mut store, (i32::MIN, -1)).is_err());
+    let div_s_const = instance
+        .get_typed_func::<(), i64>(&store, "i64.div_s_const")
+        .unwrap();
+    assert!(div_s_const.call(&mut store, ()).is_err());
+}
@@ -0,0 +1,79 @@
+//! Tests for scanning the imports of Wasm binaries via [`Module::scan_imports`].
+
+use wasmi::{core::ValType, Engine, ExternType, Module, Mutability};
+
+#[test]
+fn scan_imports_matches_module_imports() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (type $unused (func))
+            (import "env" "log" (func (param i32)))
+            (import "env" "table" (table 2 funcref))
+            (import "deps" "memory" (memory 1 4))
+            (import "deps" "counter" (global (mut i64)))
+            (import "deps" "add" (func (param i32 i32) (result i32)))
+            (func (export "run"))
+        )
+        "#,
+    )
+    .unwrap();
+    let scanned = Module::scan_imports(&wasm).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm).unwrap();
+    assert_eq!(scanned.len(), module.imports().len());
+    for import in module.imports() {
+        let scanned = scanned
+            .iter()
+            .find(|scanned| scanned.module() == import.module() && scanned.name() == import.name())
+            .unwrap();
+        assert_eq!(format!("{:?}", scanned.ty()), format!("{:?}", import.ty()));
+    }
+    // Imports are yielded in the order of their declaration.
+    let names = scanned
+        .iter()
+        .map(|import| import.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["log", "table", "memory", "counter", "add"]);
+    let ExternType::Func(add) = scanned[4].ty() else {
+        panic!(
+            "expected a function import but found: {:?}",
+            scanned[4].ty()
+        );
+    };
+    assert_eq!(add.params(), [ValType::I32, ValType::I32]);
+    let ExternType::Global(counter) = scanned[3].ty() else {
+        panic!("expected a global import but found: {:?}", scanned[3].ty());
+    };
+    assert_eq!(counter.mutability(), Mutability::Var);
+}
+
+#[test]
+fn scan_imports_skips_code() {
+    // Scanning does not validate the code section, so invalid function bodies are not detected.
+    let mut wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "f" (func (result i32)))
+            (func (result i32) (i32.const 0))
+        )
+        "#,
+    )
+    .unwrap();
+    // Turn the `i32.const 0` into an invalid `i64.const 0` within the code section.
+    let pos = wasm.windows(2).rposition(|w| w == [0x41, 0x00]).unwrap();
+    wasm[pos] = 0x42;
+    let engine = Engine::default();
+    assert!(Module::new(&engine, &wasm).is_err());
+    let imports = Module::scan_imports(&wasm).unwrap();
+    assert_eq!(imports.len(), 1);
+    assert_eq!(imports[0].name(), "f");
+}
+
+#[test]
+fn scan_imports_errors() {
+    assert!(Module::scan_imports(b"not a wasm binary").is_err());
+    // Truncated import section.
+    let wasm = wat::parse_str(r#"(module (import "env" "memory" (memory 1)))"#).unwrap();
+    assert!(Module::scan_imports(&wasm[..wasm.len() - 2]).is_err());
+}
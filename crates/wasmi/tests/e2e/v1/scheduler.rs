@@ -0,0 +1,114 @@
+//! Tests for running many Wasm executions via [`Scheduler`].
+
+use wasmi::{core::TrapCode, Caller, Config, Engine, Func, Linker, Module, Scheduler, Store, Val};
+
+const GUEST: &str = r#"
+    (module
+        (import "env" "tick" (func $tick))
+        (func $fib (export "fib") (param $n i32) (result i32)
+            (call $tick)
+            (if (result i32) (i32.lt_u (local.get $n) (i32.const 2))
+                (then (local.get $n))
+                (else
+                    (i32.add
+                        (call $fib (i32.sub (local.get $n) (i32.const 1)))
+                        (call $fib (i32.sub (local.get $n) (i32.const 2)))
+                    )
+                )
+            )
+        )
+        (func (export "trap") (param i32) (result i32)
+            (unreachable)
+        )
+    )
+"#;
+
+fn fib(n: i32) -> i32 {
+    if n < 2 {
+        return n;
+    }
+    fib(n - 1) + fib(n - 2)
+}
+
+/// Spawns a task calling the exported `func` of `GUEST` with `n` on `scheduler`.
+fn spawn(
+    engine: &Engine,
+    scheduler: &mut Scheduler<u32>,
+    func: &str,
+    n: i32,
+) -> Result<wasmi::TaskId, wasmi::Error> {
+    let mut store = Store::new(engine, 0_u32);
+    let mut linker = <Linker<u32>>::new(engine);
+    linker
+        .func_wrap("env", "tick", |mut caller: Caller<'_, u32>| {
+            *caller.data_mut() += 1;
+        })
+        .unwrap();
+    let module = Module::new(engine, GUEST).unwrap();
+    let func: Func = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_func(&store, func)
+        .unwrap();
+    scheduler.spawn(store, func, &[Val::I32(n)])
+}
+
+fn fuel_engine() -> Engine {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config)
+}
+
+#[test]
+fn scheduler_preempts_and_resumes_tasks() {
+    let engine = fuel_engine();
+    let mut scheduler = Scheduler::new(7);
+    let ids = [15, 3, 10].map(|n| spawn(&engine, &mut scheduler, "fib", n).unwrap());
+    let trap = spawn(&engine, &mut scheduler, "trap", 0).unwrap();
+    assert_eq!(scheduler.len(), 4);
+    let mut completed = Vec::new();
+    scheduler.run(|completion| completed.push(completion));
+    assert!(scheduler.is_empty());
+    // Short tasks complete before long tasks.
+    let order = completed.iter().map(|c| c.id()).collect::<Vec<_>>();
+    assert_eq!(order, [trap, ids[1], ids[2], ids[0]]);
+    let error = completed[0].result().unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    for (completion, n) in completed[1..].iter().zip([3, 10, 15]) {
+        assert_eq!(completion.result().unwrap()[0].i32(), Some(fib(n)));
+        // The host function has been called exactly once per recursive call.
+        assert_eq!(*completion.store().data(), 2 * fib(n + 1) as u32 - 1);
+    }
+}
+
+#[test]
+fn scheduler_respects_priorities() {
+    let engine = fuel_engine();
+    let mut scheduler = Scheduler::new(10);
+    let low = spawn(&engine, &mut scheduler, "fib", 12).unwrap();
+    let high = spawn(&engine, &mut scheduler, "fib", 12).unwrap();
+    assert_eq!(scheduler.priority(high), Some(1));
+    assert!(scheduler.set_priority(high, 4));
+    assert_eq!(scheduler.priority(high), Some(4));
+    assert_eq!(scheduler.priority(low), Some(1));
+    let mut order = Vec::new();
+    while !scheduler.is_empty() {
+        if let Some(completion) = scheduler.step() {
+            order.push(completion.id());
+        }
+    }
+    assert_eq!(order, [high, low]);
+    assert!(!scheduler.set_priority(high, 2));
+    assert_eq!(scheduler.priority(high), None);
+}
+
+#[test]
+fn scheduler_rejects_invalid_tasks() {
+    let mut scheduler = Scheduler::new(10);
+    // Fuel metering is disabled.
+    assert!(spawn(&Engine::default(), &mut scheduler, "fib", 1).is_err());
+    assert!(scheduler.is_empty());
+    assert!(scheduler.step().is_none());
+}
@@ -0,0 +1,111 @@
+//! Tests for the sandbox self-tests of the Wasmi engine.
+
+#[cfg(feature = "checked-execution")]
+use wasmi::{Caller, Linker, Store};
+use wasmi::{CompilationMode, Config, Engine, Module, ModuleSignature};
+
+#[test]
+fn default_config_passes() {
+    Engine::default().self_check().unwrap();
+}
+
+#[test]
+fn fuel_metering_passes() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config).self_check().unwrap();
+}
+
+#[test]
+fn compilation_modes_pass() {
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let mut config = Config::default();
+        config.compilation_mode(mode);
+        Engine::new(&config).self_check().unwrap();
+    }
+}
+
+#[test]
+fn signed_modules_pass() {
+    fn reject_all(_wasm: &[u8], _signature: &ModuleSignature) -> bool {
+        false
+    }
+    let mut config = Config::default();
+    config.require_signed_modules(reject_all);
+    let engine = Engine::new(&config);
+    engine.self_check().unwrap();
+    assert!(Module::new(&engine, "(module)").is_err());
+}
+
+#[test]
+fn mvp_features_pass() {
+    let mut config = Config::default();
+    config
+        .wasm_mutable_global(false)
+        .wasm_reference_types(false);
+    config.wasm_bulk_memory(false).wasm_multi_value(false);
+    // The self-test module only uses Wasm MVP features.
+    Engine::new(&config).self_check().unwrap();
+}
+
+#[test]
+#[cfg(feature = "checked-execution")]
+fn checked_execution_across_instances() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    linker
+        .func_wrap("host", "double", |_: Caller<()>, x: i32| x * 2)
+        .unwrap();
+    let callee = Module::new(
+        &engine,
+        r#"
+        (module
+            (import "host" "double" (func $double (param i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "grow_and_double") (param i32) (result i32)
+                (drop (memory.grow (i32.const 1)))
+                (i32.store (i32.const 0) (call $double (local.get 0)))
+                (i32.load (i32.const 0))
+            )
+        )"#,
+    )
+    .unwrap();
+    let callee = linker
+        .instantiate(&mut store, &callee)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    linker.instance(&mut store, "callee", callee).unwrap();
+    let caller = Module::new(
+        &engine,
+        r#"
+        (module
+            (import "callee" "grow_and_double" (func $f (param i32) (result i32)))
+            (memory 1)
+            (func $loop (export "run") (param i32) (result i32)
+                (if (result i32) (i32.eqz (local.get 0))
+                    (then (i32.const 0))
+                    (else
+                        (i32.add
+                            (call $f (local.get 0))
+                            (call $loop (i32.sub (local.get 0) (i32.const 1)))
+                        )
+                    )
+                )
+            )
+        )"#,
+    )
+    .unwrap();
+    let caller = linker
+        .instantiate(&mut store, &caller)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = caller.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, 10).unwrap(), 110);
+}
@@ -0,0 +1,147 @@
+//! Tests for exchanging serializable values with Wasm guests via [`wasmi::serde`].
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use wasmi::{
+    serde::{Format, GuestCodec},
+    Caller,
+    Engine,
+    GuestAbi,
+    GuestSlice,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "rpc" (func $rpc (param i32 i32) (result i32 i32)))
+        (memory (export "memory") 1)
+        (global $next (mut i32) (i32.const 1024))
+        (func (export "malloc") (param $len i32) (result i32)
+            (global.get $next)
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+        )
+        (func (export "free") (param i32))
+        (func (export "echo") (param i32 i32) (result i32 i32)
+            (local.get 0) (local.get 1)
+        )
+        (func (export "forward") (param i32 i32) (result i32 i32)
+            (call $rpc (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Request {
+    name: String,
+    values: Vec<i64>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Response {
+    Sum { name: String, sum: i64 },
+    Empty,
+}
+
+/// Instantiates [`WASM`] with a `host.rpc` function that answers [`Request`]s encoded in `format`.
+fn instantiate(format: Format) -> (Store<()>, Instance, GuestCodec) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap(
+            "host",
+            "rpc",
+            move |mut caller: Caller<()>, ptr: i32, len: i32| -> Result<(i32, i32), wasmi::Error> {
+                let codec = GuestCodec::new(GuestAbi::from_caller(&caller)?, format);
+                let request: Request = codec.read(&caller, GuestSlice::new(ptr, len))?;
+                let response = match request.values.is_empty() {
+                    true => Response::Empty,
+                    false => Response::Sum {
+                        name: request.name,
+                        sum: request.values.iter().sum(),
+                    },
+                };
+                let slice = codec.write(&mut caller, &response)?;
+                Ok((slice.ptr as i32, slice.len as i32))
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let codec = GuestCodec::new(GuestAbi::new(&store, &instance).unwrap(), format);
+    (store, instance, codec)
+}
+
+#[test]
+fn roundtrip() {
+    let request = Request {
+        name: String::from("wasmi"),
+        values: vec![1, -2, 3],
+    };
+    for format in [Format::Postcard, Format::Json] {
+        let (mut store, instance, codec) = instantiate(format);
+        let echo = instance.get_func(&store, "echo").unwrap();
+        let response: Request = codec.call(&mut store, &echo, &request).unwrap();
+        assert_eq!(response, request);
+        let slice = codec.write(&mut store, &request).unwrap();
+        assert_eq!(codec.read::<Request>(&store, slice).unwrap(), request);
+        assert_eq!(
+            codec.abi().read_bytes(&store, slice).unwrap(),
+            format.encode(&request).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn json_is_readable() {
+    let (mut store, _instance, codec) = instantiate(Format::Json);
+    let slice = codec.write(&mut store, &Response::Empty).unwrap();
+    let json = codec.abi().read_string(&store, slice).unwrap();
+    assert_eq!(json, r#""Empty""#);
+}
+
+#[test]
+fn rpc() {
+    for format in [Format::Postcard, Format::Json] {
+        let (mut store, instance, codec) = instantiate(format);
+        let forward = instance.get_func(&store, "forward").unwrap();
+        let request = Request {
+            name: String::from("sum"),
+            values: vec![10, 20, 30],
+        };
+        let response: Response = codec.call(&mut store, &forward, &request).unwrap();
+        assert_eq!(
+            response,
+            Response::Sum {
+                name: String::from("sum"),
+                sum: 60
+            }
+        );
+        let request = Request {
+            name: String::from("empty"),
+            values: vec![],
+        };
+        let response: Response = codec.call(&mut store, &forward, &request).unwrap();
+        assert_eq!(response, Response::Empty);
+    }
+}
+
+#[test]
+fn errors() {
+    let (mut store, instance, codec) = instantiate(Format::Json);
+    let echo = instance.get_func(&store, "echo").unwrap();
+    // The response does not match the request.
+    assert!(codec
+        .call::<_, Response>(&mut store, &echo, &"not a response")
+        .is_err());
+    // The guest memory does not contain valid JSON.
+    let slice = codec.abi().write_bytes(&mut store, b"{").unwrap();
+    assert!(codec.read::<Request>(&store, slice).is_err());
+}
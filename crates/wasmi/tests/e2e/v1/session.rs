@@ -0,0 +1,77 @@
+//! Tests for incrementally defining Wasm functions via [`Session`].
+
+use wasmi::{Engine, Linker, Module, Session, Store};
+
+#[test]
+fn session_evaluates_snippets_against_instance() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let wasm = r#"
+        (module
+            (memory (export "mem") 1)
+            (global (export "counter") (mut i32) (i32.const 0))
+            (data (i32.const 0) "\2A")
+        )
+    "#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let mut session = Session::new(linker, "env");
+    session.define_instance(&mut store, instance).unwrap();
+    session
+        .eval(
+            &mut store,
+            r#"
+            (module
+                (import "env" "mem" (memory 1))
+                (import "env" "counter" (global $counter (mut i32)))
+                (func (export "next") (result i32)
+                    (global.set $counter
+                        (i32.add (global.get $counter) (i32.load8_u (i32.const 0)))
+                    )
+                    (global.get $counter)
+                )
+            )
+            "#,
+        )
+        .unwrap();
+    let next = session
+        .get_func(&store, "next")
+        .unwrap()
+        .typed::<(), i32>(&store)
+        .unwrap();
+    assert_eq!(next.call(&mut store, ()).unwrap(), 42);
+    assert_eq!(next.call(&mut store, ()).unwrap(), 84);
+    let counter = instance.get_global(&store, "counter").unwrap();
+    assert_eq!(counter.get(&store).i32(), Some(84));
+}
+
+#[test]
+fn session_shadows_earlier_definitions() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut session = Session::new(<Linker<()>>::new(&engine), "repl");
+    let answer = |value: i32| {
+        format!(r#"(module (func (export "answer") (result i32) (i32.const {value})))"#)
+    };
+    session.eval(&mut store, answer(1)).unwrap();
+    let first = session.get_func(&store, "answer").unwrap();
+    session.eval(&mut store, answer(2)).unwrap();
+    let second = session.get_func(&store, "answer").unwrap();
+    let call = |func: wasmi::Func, store: &mut Store<()>| {
+        func.typed::<(), i32>(&*store)
+            .unwrap()
+            .call(store, ())
+            .unwrap()
+    };
+    assert_eq!(call(first, &mut store), 1);
+    assert_eq!(call(second, &mut store), 2);
+    assert!(session.get_func(&store, "missing").is_none());
+    assert!(session
+        .eval(&mut store, r#"(module (import "repl" "missing" (func)))"#)
+        .is_err());
+}
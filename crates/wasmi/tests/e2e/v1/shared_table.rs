@@ -0,0 +1,138 @@
+//! Tests for hot-swapping functions of a table shared by many instances via [`Table::install`].
+
+use wasmi::{
+    errors::TableError,
+    Engine,
+    Error,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Table,
+    TypedResumableCall,
+};
+
+/// Exports the shared table.
+const PROVIDER: &str = r#"
+    (module
+        (table (export "plugins") 2 funcref)
+    )
+"#;
+
+/// Calls the plugins of the shared table indirectly.
+const CONSUMER: &str = r#"
+    (module
+        (import "env" "pause" (func $pause))
+        (import "provider" "plugins" (table 2 funcref))
+        (type $plugin (func (param i32) (result i32)))
+        (func (export "call") (param i32) (result i32)
+            (call_indirect (type $plugin) (local.get 0) (i32.const 0))
+        )
+        (func (export "pause_and_call") (param i32) (result i32)
+            (call $pause)
+            (call_indirect (type $plugin) (local.get 0) (i32.const 0))
+        )
+    )
+"#;
+
+/// Instantiates the provider and two consumers sharing its table.
+fn setup() -> (Store<()>, Table, Instance, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "pause", || -> Result<(), Error> {
+            Err(Error::suspend(()))
+        })
+        .unwrap();
+    let provider = Module::new(&engine, PROVIDER).unwrap();
+    let provider = linker
+        .instantiate(&mut store, &provider)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    linker.instance(&mut store, "provider", provider).unwrap();
+    let table = provider.get_table(&store, "plugins").unwrap();
+    let consumer = Module::new(&engine, CONSUMER).unwrap();
+    let mut instantiate = || {
+        linker
+            .instantiate(&mut store, &consumer)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+    };
+    let (a, b) = (instantiate(), instantiate());
+    (store, table, a, b)
+}
+
+fn assert_func_type_mismatch(error: TableError) {
+    assert!(matches!(
+        error,
+        TableError::FuncTypeMismatch { index: 0, .. }
+    ));
+}
+
+#[test]
+fn install_while_suspended() {
+    let (mut store, table, a, b) = setup();
+    let increment = Func::wrap(&mut store, |x: i32| x + 1);
+    let scale = Func::wrap(&mut store, |x: i32| x * 10);
+    assert!(table.install(&mut store, 0, increment).unwrap().is_none());
+    let call_a = a.get_typed_func::<i32, i32>(&store, "call").unwrap();
+    let call_b = b.get_typed_func::<i32, i32>(&store, "call").unwrap();
+    assert_eq!(call_a.call(&mut store, 5).unwrap(), 6);
+    assert_eq!(call_b.call(&mut store, 5).unwrap(), 6);
+    let pause_and_call = a
+        .get_typed_func::<i32, i32>(&store, "pause_and_call")
+        .unwrap();
+    let TypedResumableCall::Resumable(invocation) =
+        pause_and_call.call_resumable(&mut store, 5).unwrap()
+    else {
+        panic!("expected TypedResumableCall::Resumable")
+    };
+    // Hot-swap the plugin while the execution is suspended.
+    assert!(table.install(&mut store, 0, scale).unwrap().is_some());
+    match invocation.resume_typed(&mut store, ()).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 50),
+        TypedResumableCall::Resumable(_) => panic!("expected TypedResumableCall::Finished"),
+    }
+    assert_eq!(call_b.call(&mut store, 5).unwrap(), 50);
+}
+
+#[test]
+fn install_rejects_mismatching_types() {
+    let (mut store, table, a, _b) = setup();
+    let increment = Func::wrap(&mut store, |x: i32| x + 1);
+    let wide = Func::wrap(&mut store, |x: i64| x + 1);
+    table.install(&mut store, 0, increment).unwrap();
+    assert_func_type_mismatch(table.install(&mut store, 0, wide).unwrap_err());
+    assert!(matches!(
+        table.install(&mut store, 2, increment).unwrap_err(),
+        TableError::AccessOutOfBounds { offset: 2, .. }
+    ));
+    // Empty table elements accept functions of any type.
+    assert!(table.install(&mut store, 1, wide).unwrap().is_none());
+    let call = a.get_typed_func::<i32, i32>(&store, "call").unwrap();
+    assert_eq!(call.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+fn install_all_is_atomic() {
+    let (mut store, table, a, _b) = setup();
+    let increment = Func::wrap(&mut store, |x: i32| x + 1);
+    let scale = Func::wrap(&mut store, |x: i32| x * 10);
+    let wide = Func::wrap(&mut store, |x: i64| x + 1);
+    table.install(&mut store, 0, increment).unwrap();
+    let error = table
+        .install_all(&mut store, [(1, wide), (0, scale), (0, wide)])
+        .unwrap_err();
+    assert_func_type_mismatch(error);
+    assert!(table.get(&store, 1).unwrap().funcref().unwrap().is_null());
+    let call = a.get_typed_func::<i32, i32>(&store, "call").unwrap();
+    assert_eq!(call.call(&mut store, 3).unwrap(), 4);
+    table
+        .install_all(&mut store, [(1, wide), (0, scale)])
+        .unwrap();
+    assert_eq!(call.call(&mut store, 3).unwrap(), 30);
+}
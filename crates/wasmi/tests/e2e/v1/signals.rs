@@ -0,0 +1,123 @@
+//! Tests for calling guest functions at safepoints via [`SignalHandle`].
+
+use std::{thread, time::Duration};
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, Store};
+
+const WASM: &str = r#"
+    (module
+        (global $stop (mut i32) (i32.const 0))
+        (global $log (mut i32) (i32.const 0))
+        ;; Spins until a signal handler stops it and returns the number of iterations.
+        (func (export "spin") (result i32)
+            (local $n i32)
+            (loop $continue
+                (local.set $n (i32.add (local.get $n) (i32.const 1)))
+                (br_if $continue (i32.eqz (global.get $stop)))
+            )
+            (local.get $n)
+        )
+        (func (export "stop")
+            (global.set $stop (i32.const 1))
+        )
+        (func (export "cancel")
+            (unreachable)
+        )
+        (func (export "log_1")
+            (global.set $log (i32.add (i32.mul (global.get $log) (i32.const 10)) (i32.const 1)))
+        )
+        (func (export "log_2")
+            (global.set $log (i32.add (i32.mul (global.get $log) (i32.const 10)) (i32.const 2)))
+        )
+        (func (export "log") (result i32)
+            (global.get $log)
+        )
+    )
+"#;
+
+fn setup(engine: &Engine) -> (Store<()>, Instance) {
+    let mut store = Store::new(engine, ());
+    store.set_fuel(u64::MAX).unwrap();
+    let module = Module::new(engine, WASM).unwrap();
+    let instance = Linker::new(engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+fn engine() -> Engine {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config)
+}
+
+#[test]
+fn timer_stops_running_loop() {
+    let engine = engine();
+    let (mut store, instance) = setup(&engine);
+    let stop = instance.get_func(&store, "stop").unwrap();
+    let spin = instance.get_typed_func::<(), i32>(&store, "spin").unwrap();
+    let signals = store.signal_handle();
+    let timer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        signals.raise(stop);
+    });
+    let iterations = spin.call(&mut store, ()).unwrap();
+    assert!(iterations > 1);
+    timer.join().unwrap();
+    assert!(!store.signal_handle().is_pending());
+}
+
+#[test]
+fn handler_cancels_execution() {
+    let engine = engine();
+    let (mut store, instance) = setup(&engine);
+    let cancel = instance.get_func(&store, "cancel").unwrap();
+    let spin = instance.get_typed_func::<(), i32>(&store, "spin").unwrap();
+    store.signal_handle().raise(cancel);
+    let error = spin.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+}
+
+#[test]
+fn handlers_run_in_order() {
+    let engine = engine();
+    let (mut store, instance) = setup(&engine);
+    let signals = store.signal_handle();
+    for name in ["log_2", "log_1", "log_2", "stop"] {
+        signals.raise(instance.get_func(&store, name).unwrap());
+    }
+    assert!(signals.is_pending());
+    let spin = instance.get_typed_func::<(), i32>(&store, "spin").unwrap();
+    spin.call(&mut store, ()).unwrap();
+    let log = instance.get_typed_func::<(), i32>(&store, "log").unwrap();
+    assert_eq!(log.call(&mut store, ()).unwrap(), 212);
+}
+
+#[test]
+fn clear_removes_pending_handlers() {
+    let engine = engine();
+    let (mut store, instance) = setup(&engine);
+    let signals = store.signal_handle();
+    signals.raise(instance.get_func(&store, "log_1").unwrap());
+    signals.clear();
+    assert!(!signals.is_pending());
+    signals.raise(instance.get_func(&store, "stop").unwrap());
+    let spin = instance.get_typed_func::<(), i32>(&store, "spin").unwrap();
+    spin.call(&mut store, ()).unwrap();
+    let log = instance.get_typed_func::<(), i32>(&store, "log").unwrap();
+    assert_eq!(log.call(&mut store, ()).unwrap(), 0);
+}
+
+#[test]
+fn handler_of_other_store() {
+    let engine = engine();
+    let (mut store, instance) = setup(&engine);
+    let (other, other_instance) = setup(&engine);
+    store
+        .signal_handle()
+        .raise(other_instance.get_func(&other, "stop").unwrap());
+    let spin = instance.get_typed_func::<(), i32>(&store, "spin").unwrap();
+    assert!(spin.call(&mut store, ()).is_err());
+}
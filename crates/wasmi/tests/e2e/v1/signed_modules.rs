@@ -0,0 +1,62 @@
+//! Tests for signed Wasm modules and engines requiring signed modules.
+
+use wasmi::{Config, Engine, Module, ModuleSignature};
+
+const WAT: &str = r#"(module (func (export "run")))"#;
+
+/// Returns the checksum signature of `wasm`.
+fn checksum(wasm: &[u8]) -> [u8; 4] {
+    wasm.iter()
+        .fold(0_u32, |sum, byte| sum.wrapping_add(u32::from(*byte)))
+        .to_le_bytes()
+}
+
+/// Accepts signatures that match the [`checksum`] of the `wasm` bytes.
+fn verify(wasm: &[u8], signature: &ModuleSignature) -> bool {
+    signature.signature() == checksum(wasm)
+}
+
+fn signed_engine() -> Engine {
+    let mut config = Config::default();
+    config.require_signed_modules(verify);
+    Engine::new(&config)
+}
+
+#[test]
+fn unsigned_module_is_rejected() {
+    let engine = signed_engine();
+    assert!(Module::new(&engine, WAT).is_err());
+}
+
+#[test]
+fn invalid_signature_is_rejected() {
+    let engine = signed_engine();
+    let signature = ModuleSignature::new(*b"nope");
+    assert!(Module::new_signed(&engine, WAT, signature).is_err());
+}
+
+#[test]
+fn valid_signature_is_accepted() {
+    let engine = signed_engine();
+    let signature =
+        ModuleSignature::new(checksum(WAT.as_bytes())).with_metadata(*b"publisher=acme");
+    let module = Module::new_signed(&engine, WAT, signature.clone()).unwrap();
+    assert_eq!(module.signature(), Some(&signature));
+    assert_eq!(module.signature().unwrap().metadata(), b"publisher=acme");
+}
+
+#[test]
+fn signature_is_attached_without_verifier() {
+    let engine = Engine::default();
+    let signature = ModuleSignature::new(*b"any");
+    let module = Module::new_signed(&engine, WAT, signature.clone()).unwrap();
+    assert_eq!(module.signature(), Some(&signature));
+    assert!(Module::new(&engine, WAT).unwrap().signature().is_none());
+}
+
+#[test]
+fn streaming_module_is_rejected() {
+    let engine = signed_engine();
+    let wasm = wat::parse_str(WAT).unwrap();
+    assert!(Module::new_streaming(&engine, &wasm[..]).is_err());
+}
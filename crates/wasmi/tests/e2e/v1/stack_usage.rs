@@ -0,0 +1,99 @@
+//! Tests for the static stack usage analysis via [`Module::stack_usage`].
+
+use wasmi::{Config, Engine, Linker, Module, StackLimits, Store};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32 i32 i32) (result i32)))
+        (table 1 funcref)
+        (func $leaf (export "leaf") (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func $middle (export "middle") (param i32) (result i32)
+            (call $leaf (call $leaf (local.get 0)))
+        )
+        (func $top (export "top") (param i32) (result i32)
+            (call $middle (call $host (local.get 0) (local.get 0) (local.get 0)))
+        )
+        (func $tail (export "tail") (param i32) (result i32)
+            (return_call $middle (local.get 0))
+        )
+        (func $recursive (export "recursive") (param i32) (result i32)
+            (if (result i32) (local.get 0)
+                (then (call $recursive (i32.sub (local.get 0) (i32.const 1))))
+                (else (i32.const 0))
+            )
+        )
+        (func $indirect (export "indirect") (param i32) (result i32)
+            (call_indirect (param i32) (result i32) (local.get 0) (i32.const 0))
+        )
+        (export "host" (func $host))
+        (memory (export "memory") 1)
+    )
+"#;
+
+#[test]
+fn stack_usage() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let analysis = module.stack_usage().unwrap();
+    assert!(analysis.get("memory").is_none());
+    assert_eq!(analysis.iter().count(), 7);
+    let leaf = analysis.get("leaf").unwrap();
+    let middle = analysis.get("middle").unwrap();
+    let top = analysis.get("top").unwrap();
+    let tail = analysis.get("tail").unwrap();
+    assert_eq!(leaf.max_call_depth(), Some(1));
+    assert_eq!(middle.max_call_depth(), Some(2));
+    assert_eq!(top.max_call_depth(), Some(3));
+    // Tail calls replace the call frame of the caller.
+    assert_eq!(tail.max_call_depth(), Some(2));
+    assert_eq!(tail.max_value_stack(), middle.max_value_stack());
+    let leaf_stack = leaf.max_value_stack().unwrap();
+    let middle_stack = middle.max_value_stack().unwrap();
+    assert!(leaf_stack > 0);
+    assert!(middle_stack > leaf_stack);
+    assert!(top.max_value_stack().unwrap() > middle_stack);
+    let host = analysis.get("host").unwrap();
+    assert_eq!(host.max_call_depth(), Some(0));
+    // Recursion and indirect calls are flagged.
+    let recursive = analysis.get("recursive").unwrap();
+    assert!(recursive.is_recursive());
+    assert!(!recursive.is_bounded());
+    assert_eq!(recursive.max_call_depth(), None);
+    assert_eq!(recursive.known_call_depth(), 1);
+    let indirect = analysis.get("indirect").unwrap();
+    assert!(indirect.has_indirect_calls());
+    assert!(!indirect.is_recursive());
+    assert_eq!(indirect.max_value_stack(), None);
+}
+
+/// Calls `top` with the maximum recursion depth limited to `depth`.
+fn call_top(depth: usize) -> bool {
+    let mut config = Config::default();
+    config.set_stack_limits(StackLimits::new(256, 1024 * 1024, depth).unwrap());
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", |a: i32, b: i32, c: i32| a + b + c)
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let top = instance.get_typed_func::<i32, i32>(&store, "top").unwrap();
+    top.call(&mut store, 1).is_ok()
+}
+
+#[test]
+fn stack_usage_bounds_execution() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let analysis = module.stack_usage().unwrap();
+    let depth = analysis.get("top").unwrap().max_call_depth().unwrap();
+    assert!(call_top(depth));
+    assert!(!call_top(depth - 1));
+}
@@ -0,0 +1,206 @@
+//! Tests for snapshot deltas via [`StoreSnapshot::diff`] and [`Store::apply_delta`]
+//! and for restoring snapshots via [`Store::restore`] and [`Store::reset`].
+
+use core::sync::atomic::{AtomicI32, Ordering};
+use wasmi::{Engine, Global, Instance, Linker, Module, Store, StoreDelta, TypedFunc};
+
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 2)
+        (table (export "table") 2 funcref)
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (func $inc (export "inc")
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+        )
+        (func (export "update") (param $addr i32) (param $value i32)
+            (i32.store (local.get $addr) (local.get $value))
+            (table.set (i32.const 1) (ref.func $inc))
+            (call $inc)
+        )
+        (func (export "grow") (param $pages i32)
+            (drop (memory.grow (local.get $pages)))
+            (drop (table.grow (ref.null func) (i32.const 1)))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] in a new [`Store`].
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the `update` export of `instance`.
+fn update(store: &Store<()>, instance: Instance) -> TypedFunc<(i32, i32), ()> {
+    instance.get_typed_func(store, "update").unwrap()
+}
+
+#[test]
+fn empty_delta() {
+    let (store, _instance) = setup();
+    let snapshot = store.snapshot();
+    let delta = store.snapshot().diff(&snapshot).unwrap();
+    assert!(delta.is_empty());
+    assert_eq!(StoreDelta::from_bytes(&delta.to_bytes()).unwrap(), delta);
+}
+
+#[test]
+fn replicate() {
+    let (mut primary, instance) = setup();
+    let (mut replica, replica_instance) = setup();
+    let older = primary.snapshot();
+    update(&primary, instance)
+        .call(&mut primary, (0x1_0004, 42))
+        .unwrap();
+    let grow = instance
+        .get_typed_func::<i32, ()>(&primary, "grow")
+        .unwrap();
+    grow.call(&mut primary, 1).unwrap();
+    let newer = primary.snapshot();
+    let delta = newer.diff(&older).unwrap();
+    assert_eq!(delta.changed_pages(0).collect::<Vec<_>>(), [1, 2]);
+    let bytes = delta.to_bytes();
+    assert!(bytes.len() < 3 * 0x1_0000);
+    let delta = StoreDelta::from_bytes(&bytes).unwrap();
+    replica.apply_delta(&delta).unwrap();
+    assert_eq!(replica.snapshot(), newer);
+    let memory = replica_instance.get_memory(&replica, "memory").unwrap();
+    assert_eq!(memory.size(&replica), 3);
+    assert_eq!(memory.data(&replica)[0x1_0004], 42);
+    let counter = replica_instance.get_global(&replica, "counter").unwrap();
+    assert_eq!(counter.get(&replica).i32(), Some(1));
+    let table = replica_instance.get_table(&replica, "table").unwrap();
+    assert_eq!(table.size(&replica), 3);
+    // The replicated table element refers to the `inc` function of the replica.
+    let element = table.get(&replica, 1).unwrap();
+    let inc = *element.funcref().unwrap().func().unwrap();
+    inc.call(&mut replica, &[], &mut []).unwrap();
+    assert_eq!(counter.get(&replica).i32(), Some(2));
+    // Applying the delta to the older snapshot yields the newer snapshot.
+    let mut snapshot = older.clone();
+    snapshot.apply_delta(&delta).unwrap();
+    assert_eq!(snapshot, newer);
+}
+
+#[test]
+fn mismatched_stores() {
+    let (mut store, _instance) = setup();
+    let (mut other, _other_instance) = setup();
+    let older = store.snapshot();
+    setup_second_instance(&mut store);
+    assert!(store.snapshot().diff(&older).is_err());
+    // Deltas that shrink entities are rejected.
+    let (mut grown, instance) = setup();
+    let before = grown.snapshot();
+    let grow = instance.get_typed_func::<i32, ()>(&grown, "grow").unwrap();
+    grow.call(&mut grown, 1).unwrap();
+    let shrink = before.diff(&grown.snapshot()).unwrap();
+    let grow = grown.snapshot().diff(&before).unwrap();
+    other.apply_delta(&grow).unwrap();
+    assert!(other.apply_delta(&shrink).is_err());
+    assert!(StoreDelta::from_bytes(&grow.to_bytes()[1..]).is_err());
+}
+
+#[test]
+fn reset_keeps_handles() {
+    let (mut store, instance) = setup();
+    assert!(store.reset().is_err());
+    store.set_reset_point();
+    let update = update(&store, instance);
+    let grow = instance.get_typed_func::<i32, ()>(&store, "grow").unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let table = instance.get_table(&store, "table").unwrap();
+    let counter = instance.get_global(&store, "counter").unwrap();
+    for _ in 0..3 {
+        update.call(&mut store, (0x1_0004, 42)).unwrap();
+        grow.call(&mut store, 1).unwrap();
+        assert_eq!(memory.size(&store), 3);
+        assert_eq!(table.size(&store), 3);
+        assert_eq!(counter.get(&store).i32(), Some(1));
+        store.reset().unwrap();
+        // Memories and tables are shrunk and all handles remain valid.
+        assert_eq!(memory.size(&store), 2);
+        assert_eq!(table.size(&store), 2);
+        assert_eq!(counter.get(&store).i32(), Some(0));
+        assert!(table.get(&store, 1).unwrap().funcref().unwrap().is_null());
+        let mut value = [0x00; 4];
+        memory.read(&store, 0x1_0004, &mut value).unwrap();
+        assert_eq!(value, [0x00; 4]);
+    }
+    // Snapshots are rejected after new entities have been allocated.
+    let snapshot = store.snapshot();
+    setup_second_instance(&mut store);
+    assert!(store.restore(&snapshot).is_err());
+    assert!(store.reset().is_err());
+}
+
+#[test]
+fn reset_undrops_segments() {
+    let wasm = r#"
+        (module
+            (memory 1)
+            (table 1 funcref)
+            (func $f)
+            (data $data "\2A")
+            (elem $elem func $f)
+            (func (export "init") (result i32)
+                (memory.init $data (i32.const 0) (i32.const 0) (i32.const 1))
+                (table.init $elem (i32.const 0) (i32.const 0) (i32.const 1))
+                (i32.load8_u (i32.const 0))
+            )
+            (func (export "drop")
+                (data.drop $data)
+                (elem.drop $elem)
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let init = instance.get_typed_func::<(), i32>(&store, "init").unwrap();
+    let drop = instance.get_typed_func::<(), ()>(&store, "drop").unwrap();
+    store.set_reset_point();
+    assert_eq!(init.call(&mut store, ()).unwrap(), 42);
+    drop.call(&mut store, ()).unwrap();
+    assert!(init.call(&mut store, ()).is_err());
+    let dropped = store.snapshot();
+    store.reset().unwrap();
+    assert_eq!(init.call(&mut store, ()).unwrap(), 42);
+    store.restore(&dropped).unwrap();
+    assert!(init.call(&mut store, ()).is_err());
+}
+
+#[test]
+fn restore_skips_computed_globals() {
+    static VALUE: AtomicI32 = AtomicI32::new(1);
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let global = Global::new_computed(&mut store, || VALUE.load(Ordering::Relaxed));
+    let snapshot = store.snapshot();
+    VALUE.store(2, Ordering::Relaxed);
+    assert!(store.snapshot().diff(&snapshot).unwrap().is_empty());
+    store.restore(&snapshot).unwrap();
+    assert_eq!(global.get(&store).i32(), Some(2));
+}
+
+/// Instantiates [`WASM`] a second time in `store`.
+fn setup_second_instance(store: &mut Store<()>) {
+    let module = Module::new(store.engine(), WASM).unwrap();
+    <Linker<()>>::new(store.engine())
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+}
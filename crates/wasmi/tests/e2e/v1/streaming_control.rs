@@ -0,0 +1,98 @@
+//! Tests for progress reporting and cancellation of [`Module::new_streaming_with_control`].
+
+use std::{cell::RefCell, io};
+use wasmi::{
+    errors::ErrorCategory,
+    CancellationToken,
+    Engine,
+    Module,
+    StreamingControl,
+    StreamingProgress,
+};
+
+/// A Wasm module with a data segment and some functions.
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (func (export "a") (result i32) (i32.const 1))
+        (func (export "b") (result i32) (i32.const 2))
+        (func (export "c") (result i32) (i32.const 3))
+        (data (i32.const 0) "hello")
+    )
+"#;
+
+/// A [`io::Read`] that yields at most one byte per read.
+struct Trickle<'a>(&'a [u8]);
+
+impl io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.0.len().min(buf.len()).min(1);
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn progress_is_reported() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let reports = RefCell::new(Vec::<StreamingProgress>::new());
+    let control = StreamingControl::new().on_progress(|progress| {
+        reports.borrow_mut().push(progress);
+    });
+    let module = Module::new_streaming_with_control(&engine, Trickle(&wasm), control).unwrap();
+    assert_eq!(module.exports().count(), 3);
+    let reports = reports.into_inner();
+    assert!(reports.windows(2).all(|w| {
+        w[0].bytes_consumed() <= w[1].bytes_consumed()
+            && w[0].funcs_translated() <= w[1].funcs_translated()
+    }));
+    let last = reports.last().unwrap();
+    assert_eq!(last.bytes_consumed(), wasm.len());
+    assert_eq!(last.funcs_translated(), 3);
+}
+
+#[test]
+fn cancel_before_start() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let token = CancellationToken::new();
+    token.cancel();
+    let control = StreamingControl::new().with_cancellation(token);
+    let error = Module::new_streaming_with_control(&engine, &wasm[..], control).unwrap_err();
+    assert!(error.is_cancelled());
+    assert!(matches!(error.category(), ErrorCategory::Other));
+}
+
+#[test]
+fn cancel_during_code_section() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let token = CancellationToken::new();
+    let last = RefCell::new(StreamingProgress::default());
+    let control = StreamingControl::new()
+        .with_cancellation(token.clone())
+        .on_progress(|progress| {
+            if progress.funcs_translated() == 1 {
+                token.cancel();
+            }
+            *last.borrow_mut() = progress;
+        });
+    let error = Module::new_streaming_with_control(&engine, Trickle(&wasm), control).unwrap_err();
+    assert!(error.is_cancelled());
+    assert_eq!(last.borrow().funcs_translated(), 1);
+    assert!(last.borrow().bytes_consumed() < wasm.len());
+}
+
+#[test]
+fn uncancelled_token_works() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let token = CancellationToken::new();
+    let control = StreamingControl::new().with_cancellation(token.clone());
+    Module::new_streaming_with_control(&engine, &wasm[..], control).unwrap();
+    assert!(!token.is_cancelled());
+    let error = Module::new(&engine, b"invalid").unwrap_err();
+    assert!(!error.is_cancelled());
+}
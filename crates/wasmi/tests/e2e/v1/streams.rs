@@ -0,0 +1,123 @@
+//! Tests for the built-in `streams` host module.
+#![cfg(feature = "streams")]
+
+use std::{
+    io::Write,
+    sync::{mpsc, Arc, Mutex},
+};
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, Store, Streams};
+
+/// A [`Write`] implementation that collects all written bytes.
+#[derive(Debug, Default, Clone)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Guest that copies stream `0` to stream `1` in chunks of 4 bytes until either blocks.
+const ECHO: &str = r#"
+    (module
+        (import "streams" "read" (func $read (param i32 i32 i32) (result i32)))
+        (import "streams" "write" (func $write (param i32 i32 i32) (result i32)))
+        (import "streams" "flush" (func $flush (param i32) (result i32)))
+        (memory (export "memory") 1)
+        (func (export "echo") (result i32)
+            (local $n i32)
+            (loop $continue
+                (local.set $n (call $read (i32.const 0) (i32.const 0) (i32.const 4)))
+                (if (i32.le_s (local.get $n) (i32.const 0))
+                    (then (return (local.get $n)))
+                )
+                (local.set $n (call $write (i32.const 1) (i32.const 0) (local.get $n)))
+                (if (i32.lt_s (local.get $n) (i32.const 0))
+                    (then (return (local.get $n)))
+                )
+                (br $continue)
+            )
+            (unreachable)
+        )
+        (func (export "flush") (param i32) (result i32)
+            (call $flush (local.get 0))
+        )
+    )
+"#;
+
+fn instantiate(config: &Config, streams: Streams) -> (Store<Streams>, Instance) {
+    let engine = Engine::new(config);
+    let mut store = Store::new(&engine, streams);
+    let mut linker = <Linker<Streams>>::new(&engine);
+    Streams::add_to_linker(&mut linker, |streams| streams).unwrap();
+    let module = Module::new(&engine, ECHO).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn streams_echo_reader_to_writer() {
+    let output = SharedBuffer::default();
+    let mut streams = Streams::new();
+    assert_eq!(streams.add_reader(&b"hello, streams"[..]), 0);
+    assert_eq!(streams.add_writer(output.clone()), 1);
+    let (mut store, instance) = instantiate(&Config::default(), streams);
+    let echo = instance.get_typed_func::<(), i32>(&store, "echo").unwrap();
+    assert_eq!(echo.call(&mut store, ()).unwrap(), 0);
+    assert_eq!(&output.0.lock().unwrap()[..], b"hello, streams");
+    let flush = instance
+        .get_typed_func::<i32, i32>(&store, "flush")
+        .unwrap();
+    assert_eq!(flush.call(&mut store, 1).unwrap(), 0);
+    assert_eq!(flush.call(&mut store, 0).unwrap(), Streams::INVALID_HANDLE);
+    assert_eq!(flush.call(&mut store, 2).unwrap(), Streams::INVALID_HANDLE);
+}
+
+#[test]
+fn streams_channels_apply_backpressure() {
+    let (input_tx, input_rx) = mpsc::channel();
+    let (output_tx, output_rx) = mpsc::sync_channel(1);
+    let mut streams = Streams::new();
+    streams.add_receiver(input_rx);
+    streams.add_sender(output_tx);
+    let (mut store, instance) = instantiate(&Config::default(), streams);
+    let echo = instance.get_typed_func::<(), i32>(&store, "echo").unwrap();
+    // The input channel is empty.
+    assert_eq!(echo.call(&mut store, ()).unwrap(), Streams::WOULD_BLOCK);
+    // The output channel is full after the first chunk.
+    input_tx.send(b"abcdefgh".to_vec()).unwrap();
+    assert_eq!(echo.call(&mut store, ()).unwrap(), Streams::WOULD_BLOCK);
+    assert_eq!(output_rx.try_recv().unwrap(), b"abcd");
+    assert!(output_rx.try_recv().is_err());
+    // The input channel is empty again after resuming.
+    assert_eq!(echo.call(&mut store, ()).unwrap(), Streams::WOULD_BLOCK);
+    // The input channel is disconnected.
+    drop(input_tx);
+    assert_eq!(echo.call(&mut store, ()).unwrap(), 0);
+}
+
+#[test]
+fn streams_consume_fuel() {
+    let output = SharedBuffer::default();
+    let mut streams = Streams::new();
+    streams.add_reader(&[0xFF_u8; 1024][..]);
+    streams.add_writer(output.clone());
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let (mut store, instance) = instantiate(&config, streams);
+    let echo = instance.get_typed_func::<(), i32>(&store, "echo").unwrap();
+    store.set_fuel(100).unwrap();
+    let error = echo.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    let transferred = output.0.lock().unwrap().len();
+    assert!(0 < transferred && transferred < 1024);
+}
@@ -0,0 +1,148 @@
+//! Tests for symbolicating Wasm functions via [`ModuleSymbols`] registered at the [`Engine`].
+
+use wasmi::{
+    core::TrapCode,
+    errors::ErrorKind,
+    Config,
+    Engine,
+    Func,
+    Linker,
+    Module,
+    ModuleSymbols,
+    Store,
+};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "log" (func $log (param i32)))
+        (func $inner (param $n i32)
+            (loop $continue
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $continue (local.get $n))
+            )
+        )
+        (func $outer (export "run") (param i32)
+            (call $inner (local.get 0))
+        )
+    )
+"#;
+
+fn wasm() -> Vec<u8> {
+    wat::parse_str(WAT).unwrap()
+}
+
+fn instantiate(engine: &Engine, module: &Module) -> (Store<()>, Func) {
+    let mut store = Store::new(engine, ());
+    let mut linker = Linker::new(engine);
+    linker.func_wrap("env", "log", |_: i32| {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_func(&store, "run").unwrap();
+    (store, run)
+}
+
+#[test]
+fn module_hash_is_stable() {
+    let engine = Engine::default();
+    let wasm = wasm();
+    let a = Module::new(&engine, &wasm).unwrap();
+    let b = Module::new(&Engine::default(), &wasm).unwrap();
+    let c = Module::new_streaming(&engine, &wasm[..]).unwrap();
+    let other = Module::new(&engine, "(module)").unwrap();
+    assert_eq!(a.hash(), b.hash());
+    assert_eq!(a.hash(), c.hash());
+    assert_ne!(a.hash(), other.hash());
+}
+
+#[test]
+fn name_section_symbols() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm()).unwrap();
+    let symbols = ModuleSymbols::from_name_section("app", &module);
+    assert_eq!(symbols.name(), "app");
+    assert_eq!(symbols.func_name(0), Some("log"));
+    assert_eq!(symbols.func_name(1), Some("inner"));
+    assert_eq!(symbols.func_name(2), Some("outer"));
+    assert_eq!(symbols.func_name(3), None);
+}
+
+#[test]
+fn symbols_apply_across_modules_and_stores() {
+    let engine = Engine::default();
+    let wasm = wasm();
+    let module = Module::new(&engine, &wasm).unwrap();
+    let (store, run) = instantiate(&engine, &module);
+    assert!(run.symbol(&store).is_none());
+    let mut symbols = ModuleSymbols::new("app");
+    symbols.set_func_name(2, "app_run");
+    engine.register_symbols(&module, symbols);
+    assert_eq!(engine.module_symbols(&module).unwrap().name(), "app");
+    // Modules compiled from the same Wasm bytecode share the symbols.
+    let twin = Module::new(&engine, &wasm).unwrap();
+    let (other_store, other_run) = instantiate(&engine, &twin);
+    for symbol in [run.symbol(&store), other_run.symbol(&other_store)] {
+        let symbol = symbol.unwrap();
+        assert_eq!(symbol.module_name(), "app");
+        assert_eq!(symbol.module_hash(), module.hash());
+        assert_eq!(symbol.func_index(), 2);
+        assert_eq!(symbol.func_name(), Some("app_run"));
+        assert_eq!(symbol.to_string(), "app!app_run");
+    }
+    // Host functions have no symbols.
+    let mut host_store = Store::new(&engine, ());
+    let host = Func::wrap(&mut host_store, || {});
+    assert!(host.symbol(&host_store).is_none());
+    assert!(engine.unregister_symbols(&module));
+    assert!(!engine.unregister_symbols(&module));
+    assert!(run.symbol(&store).is_none());
+}
+
+#[test]
+fn symbolicates_fuel_traps() {
+    let mut config = Config::default();
+    config.consume_fuel(true).wasm_backtrace(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm()).unwrap();
+    engine.register_symbols(&module, ModuleSymbols::from_name_section("app", &module));
+    let (mut store, run) = instantiate(&engine, &module);
+    store.set_fuel(100).unwrap();
+    let run = run.typed::<i32, ()>(&store).unwrap();
+    let error = run.call(&mut store, 1_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    let ErrorKind::FuelTrap(trap) = error.kind() else {
+        panic!("expected fuel trap but found: {error}")
+    };
+    let symbols = trap
+        .frames()
+        .iter()
+        .map(|frame| frame.symbol().map(ToString::to_string))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        symbols,
+        [Some("app!inner".to_string()), Some("app!outer".to_string())]
+    );
+    let message = error.to_string();
+    assert!(message.contains("#0 app!inner at instruction"), "{message}");
+}
+
+#[test]
+#[cfg(feature = "trace")]
+fn symbolicates_traces() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm()).unwrap();
+    engine.register_symbols(&module, ModuleSymbols::from_name_section("app", &module));
+    let (mut store, run) = instantiate(&engine, &module);
+    store.enable_tracing();
+    run.call(&mut store, &[wasmi::Val::I32(1)], &mut [])
+        .unwrap();
+    let trace = store.take_trace();
+    let names = trace
+        .events()
+        .iter()
+        .map(|event| event.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["app!outer", "app!inner", "app!inner", "app!outer"]);
+}
@@ -0,0 +1,118 @@
+//! Tests for the bulk initialization of `funcref` tables via [`Table::fill_with`].
+
+use wasmi::{
+    core::ValType,
+    errors::TableError,
+    Engine,
+    Func,
+    FuncRef,
+    Linker,
+    Module,
+    Store,
+    Table,
+    TableType,
+    Val,
+};
+
+const WASM: &str = r#"
+    (module
+        (import "env" "table" (table 0 funcref))
+        (type $unary (func (param i32) (result i32)))
+        (func (export "dispatch") (param i32) (result i32)
+            (call_indirect (type $unary) (local.get 0) (local.get 0))
+        )
+    )
+"#;
+
+const LEN: u32 = 100_000;
+
+/// Returns a host function that returns its parameter plus `delta`.
+fn adder(store: &mut Store<()>, delta: i32) -> Func {
+    Func::wrap(store, move |value: i32| value + delta)
+}
+
+/// Instantiates [`WASM`] with `table` and returns its exported `dispatch` function.
+fn dispatch(store: &mut Store<()>, table: Table) -> wasmi::TypedFunc<i32, i32> {
+    let module = Module::new(store.engine(), WASM).unwrap();
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker.define("env", "table", table).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+        .get_typed_func::<i32, i32>(&*store, "dispatch")
+        .unwrap()
+}
+
+#[test]
+fn fill_with_large_table() {
+    let mut store = Store::new(&Engine::default(), ());
+    let even = adder(&mut store, 1);
+    let odd = adder(&mut store, 2);
+    let ty = TableType::new(ValType::FuncRef, LEN, None);
+    let table = Table::new(&mut store, ty, Val::FuncRef(FuncRef::null())).unwrap();
+    table
+        .fill_with(&mut store, 1..LEN, |index| match index % 2 {
+            0 => FuncRef::new(even),
+            _ => FuncRef::new(odd),
+        })
+        .unwrap();
+    assert!(table.get(&store, 0).unwrap().funcref().unwrap().is_null());
+    let dispatch = dispatch(&mut store, table);
+    assert_eq!(dispatch.call(&mut store, 10).unwrap(), 11);
+    assert_eq!(dispatch.call(&mut store, 11).unwrap(), 13);
+    assert_eq!(
+        dispatch.call(&mut store, LEN as i32 - 1).unwrap(),
+        LEN as i32 + 1
+    );
+    assert!(dispatch.call(&mut store, 0).is_err());
+}
+
+#[test]
+fn new_with_funcs() {
+    let mut store = Store::new(&Engine::default(), ());
+    let funcs = (0..LEN as i32)
+        .map(|delta| FuncRef::new(adder(&mut store, delta)))
+        .collect::<Vec<_>>();
+    let ty = TableType::new(ValType::FuncRef, LEN + 1, None);
+    let table = Table::new_with_funcs(&mut store, ty, funcs).unwrap();
+    assert_eq!(table.size(&store), LEN + 1);
+    assert!(table.get(&store, LEN).unwrap().funcref().unwrap().is_null());
+    let dispatch = dispatch(&mut store, table);
+    assert_eq!(dispatch.call(&mut store, 3).unwrap(), 6);
+    assert_eq!(
+        dispatch.call(&mut store, LEN as i32 - 1).unwrap(),
+        2 * (LEN as i32 - 1)
+    );
+}
+
+#[test]
+fn fill_with_errors() {
+    let mut store = Store::new(&Engine::default(), ());
+    let func = adder(&mut store, 0);
+    let ty = TableType::new(ValType::FuncRef, 10, None);
+    let table = Table::new(&mut store, ty, Val::FuncRef(FuncRef::null())).unwrap();
+    assert!(matches!(
+        table.fill_with(&mut store, 5..11, |_| FuncRef::new(func)),
+        Err(TableError::AccessOutOfBounds { .. })
+    ));
+    // Failed fills leave the table untouched.
+    assert!(table.get(&store, 5).unwrap().funcref().unwrap().is_null());
+    let ty = TableType::new(ValType::ExternRef, 10, None);
+    let externs = Table::new(&mut store, ty, Val::default(ValType::ExternRef)).unwrap();
+    assert!(matches!(
+        externs.fill_with(&mut store, 0..1, |_| FuncRef::new(func)),
+        Err(TableError::ElementTypeMismatch { .. })
+    ));
+    let ty = TableType::new(ValType::FuncRef, 1, None);
+    assert!(matches!(
+        Table::new_with_funcs(&mut store, ty, [FuncRef::new(func), FuncRef::new(func)]),
+        Err(TableError::AccessOutOfBounds { .. })
+    ));
+    let ty = TableType::new(ValType::ExternRef, 1, None);
+    assert!(matches!(
+        Table::new_with_funcs(&mut store, ty, []),
+        Err(TableError::ElementTypeMismatch { .. })
+    ));
+}
@@ -0,0 +1,110 @@
+//! Tests for rewinding Wasm executions to checkpoints.
+#![cfg(feature = "time-travel")]
+
+use wasmi::{Caller, Config, Engine, Func, Linker, Module, Store, Val};
+
+/// Guest that sums `n` host provided numbers, stores the running sum and grows its memory.
+const GUEST: &str = r#"
+    (module
+        (import "env" "next" (func $next (result i32)))
+        (memory (export "memory") 1)
+        (global $sum (export "sum") (mut i32) (i32.const 0))
+        (func (export "run") (param $n i32) (result i32)
+            (block $break
+                (loop $continue
+                    (br_if $break (i32.eqz (local.get $n)))
+                    (global.set $sum (i32.add (global.get $sum) (call $next)))
+                    (i32.store (i32.const 0) (global.get $sum))
+                    (if (i32.eqz (i32.rem_u (local.get $n) (i32.const 10)))
+                        (then (drop (memory.grow (i32.const 1))))
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (global.get $sum)
+        )
+    )
+"#;
+
+fn setup() -> (Store<i32>, Func) {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, 0);
+    store.set_fuel(100_000).unwrap();
+    let mut linker = <Linker<i32>>::new(&engine);
+    linker
+        .func_wrap("env", "next", |mut caller: Caller<'_, i32>| {
+            *caller.data_mut() += 1;
+            *caller.data() * 3
+        })
+        .unwrap();
+    let module = Module::new(&engine, GUEST).unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_func(&store, "run")
+        .unwrap();
+    (store, run)
+}
+
+#[test]
+fn time_travel_rewinds_to_checkpoints() {
+    let (mut store, run) = setup();
+    store.enable_checkpoints(100).unwrap();
+    let mut results = [Val::I32(0)];
+    run.call(&mut store, &[Val::I32(25)], &mut results).unwrap();
+    let expected = (1..=25).map(|n| n * 3).sum::<i32>();
+    assert_eq!(results[0].i32(), Some(expected));
+    assert_eq!(*store.data(), 25);
+    let fuel = store.get_fuel().unwrap();
+    let checkpoints = store.checkpoints().to_vec();
+    assert!(checkpoints.len() > 3);
+    assert!(checkpoints
+        .windows(2)
+        .all(|w| w[0].fuel_consumed() + 100 <= w[1].fuel_consumed()));
+    assert!(checkpoints
+        .windows(2)
+        .all(|w| w[0].host_calls() <= w[1].host_calls()));
+    for index in [2, 0, checkpoints.len() - 1] {
+        let mut trace = Vec::new();
+        let mut results = [Val::I32(0)];
+        store
+            .rewind(index, &mut results, |step| {
+                let memory = step.memory_data(0).unwrap();
+                let stored = i32::from_le_bytes(memory[..4].try_into().unwrap());
+                trace.push((step.fuel_consumed(), stored, memory.len()));
+            })
+            .unwrap();
+        assert_eq!(results[0].i32(), Some(expected));
+        assert_eq!(store.get_fuel().unwrap(), fuel);
+        // The first traced step is the checkpoint itself.
+        assert_eq!(trace[0].0, checkpoints[index].fuel_consumed());
+        assert!(trace.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(trace.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(trace.windows(2).all(|w| w[0].2 <= w[1].2));
+        assert_eq!(trace.last().unwrap().1, expected);
+        assert_eq!(trace.last().unwrap().2, 3 * 0x1_0000);
+        if index == 0 {
+            // Rewinding restores the linear memory to its smaller size.
+            assert!(trace[0].2 < trace.last().unwrap().2);
+        }
+    }
+    // The real host function is not invoked by rewound executions.
+    assert_eq!(*store.data(), 25);
+    assert!(store
+        .rewind(checkpoints.len(), &mut results, |_| {})
+        .is_err());
+    assert!(store.rewind(0, &mut [], |_| {}).is_err());
+}
+
+#[test]
+fn time_travel_requires_fuel_metering() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    assert!(store.enable_checkpoints(100).is_err());
+    assert!(store.checkpoints().is_empty());
+}
@@ -0,0 +1,122 @@
+//! Tests for tracing function calls via [`Store::enable_tracing`].
+#![cfg(feature = "trace")]
+
+use wasmi::{Caller, Engine, Linker, Module, Store, TraceEventKind, TypedFunc};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "log" (func $log (param i32)))
+        (func $square (param i32) (result i32)
+            (call $log (local.get 0))
+            (i32.mul (local.get 0) (local.get 0))
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $square (local.get 0))
+        )
+        (func (export "trap")
+            (call $square (i32.const 1))
+            (drop)
+            (unreachable)
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] and returns its `run` and `trap` exports.
+fn setup() -> (Store<()>, TypedFunc<i32, i32>, TypedFunc<(), ()>) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("host", "log", |_caller: Caller<()>, _value: i32| {})
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func(&store, "run").unwrap();
+    let trap = instance.get_typed_func(&store, "trap").unwrap();
+    (store, run, trap)
+}
+
+/// Returns the kind, name and depth of all events of the trace taken from `store`.
+fn take_events(store: &mut Store<()>) -> Vec<(TraceEventKind, String, usize)> {
+    store
+        .take_trace()
+        .events()
+        .iter()
+        .map(|event| (event.kind(), String::from(event.name()), event.depth()))
+        .collect()
+}
+
+#[test]
+fn disabled_by_default() {
+    let (mut store, run, _trap) = setup();
+    assert_eq!(run.call(&mut store, 3).unwrap(), 9);
+    assert!(store.take_trace().is_empty());
+}
+
+#[test]
+fn traces_calls() {
+    let (mut store, run, _trap) = setup();
+    store.enable_tracing();
+    assert_eq!(run.call(&mut store, 3).unwrap(), 9);
+    let events = take_events(&mut store);
+    let square = &events[1].1;
+    assert!(square.starts_with("wasm-func["));
+    assert!(events[2].1.starts_with("host-func["));
+    assert_eq!(
+        events,
+        [
+            (TraceEventKind::Enter, String::from("run"), 0),
+            (TraceEventKind::Enter, square.clone(), 1),
+            (TraceEventKind::HostCall, events[2].1.clone(), 2),
+            (TraceEventKind::HostReturn, events[2].1.clone(), 2),
+            (TraceEventKind::Exit, square.clone(), 1),
+            (TraceEventKind::Exit, String::from("run"), 0),
+        ]
+    );
+    // Events are only taken once.
+    assert!(store.take_trace().is_empty());
+    assert!(store.disable_tracing().is_empty());
+    run.call(&mut store, 3).unwrap();
+    assert!(store.take_trace().is_empty());
+}
+
+#[test]
+fn closes_trapped_calls() {
+    let (mut store, run, trap) = setup();
+    store.enable_tracing();
+    trap.call(&mut store, ()).unwrap_err();
+    let events = take_events(&mut store);
+    assert_eq!(events.len(), 6);
+    assert_eq!(events[5], (TraceEventKind::Exit, String::from("trap"), 0));
+    // The trace continues properly after a trap.
+    run.call(&mut store, 2).unwrap();
+    let events = take_events(&mut store);
+    assert_eq!(events[0], (TraceEventKind::Enter, String::from("run"), 0));
+    assert_eq!(events[5], (TraceEventKind::Exit, String::from("run"), 0));
+}
+
+#[test]
+fn export_formats() {
+    let (mut store, run, _trap) = setup();
+    store.enable_tracing();
+    run.call(&mut store, 3).unwrap();
+    let trace = store.take_trace();
+    let mut chrome = Vec::new();
+    trace.write_chrome_trace(&mut chrome).unwrap();
+    let chrome = String::from_utf8(chrome).unwrap();
+    assert!(chrome.starts_with(r#"{"traceEvents":[{"name":"run","cat":"wasm","ph":"B","ts":"#));
+    assert!(chrome.ends_with(r#""pid":1,"tid":1}],"displayTimeUnit":"ns"}"#));
+    assert_eq!(chrome.matches(r#""cat":"host""#).count(), 2);
+    let mut jsonl = Vec::new();
+    trace.write_jsonl(&mut jsonl).unwrap();
+    let jsonl = String::from_utf8(jsonl).unwrap();
+    let lines = jsonl.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with(r#"{"ts_ns":"#));
+    assert!(lines[0].ends_with(r#","event":"enter","name":"run","depth":0}"#));
+    assert!(lines[2].contains(r#""event":"host_call""#));
+}
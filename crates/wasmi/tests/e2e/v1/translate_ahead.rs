@@ -0,0 +1,72 @@
+//! Tests for translating functions ahead of time via [`Module::translate_function`]
+//! and [`Module::translate_all`].
+
+use wasmi::{CompilationMode, Config, Engine, Linker, Module, Store};
+
+/// A Wasm module where the function at index 2 fails to validate.
+const WASM: &str = r#"
+    (module
+        (import "env" "host" (func))
+        (func (export "valid") (result i32)
+            (i32.const 42)
+        )
+        (func (export "invalid") (result i32)
+            (i64.const 42)
+        )
+    )
+"#;
+
+fn module(mode: CompilationMode) -> Result<Module, wasmi::Error> {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    let engine = Engine::new(&config);
+    Module::new(&engine, WASM)
+}
+
+#[test]
+fn translate_function_surfaces_errors_early() {
+    // Only lazy Wasm validation accepts the invalid Wasm module upfront.
+    assert!(module(CompilationMode::LazyTranslation).is_err());
+    let module = module(CompilationMode::Lazy).unwrap();
+    module.translate_function(1).unwrap();
+    // Translating a function twice is a no-op.
+    module.translate_function(1).unwrap();
+    assert!(module.translate_function(2).is_err());
+    assert!(module.translate_all().is_err());
+}
+
+#[test]
+fn translate_function_invalid_index() {
+    let module = module(CompilationMode::Lazy).unwrap();
+    // Imported functions cannot be translated.
+    assert!(module.translate_function(0).is_err());
+    // Out of bounds function index.
+    assert!(module.translate_function(3).is_err());
+}
+
+#[test]
+fn translate_all_then_execute() {
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let mut config = Config::default();
+        config.compilation_mode(mode);
+        let engine = Engine::new(&config);
+        let module = Module::new(
+            &engine,
+            r#"(module (func (export "f") (result i32) (i32.const 1)))"#,
+        )
+        .unwrap();
+        module.translate_all().unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let f = instance.get_typed_func::<(), i32>(&store, "f").unwrap();
+        assert_eq!(f.call(&mut store, ()).unwrap(), 1);
+    }
+}
@@ -0,0 +1,107 @@
+//! Tests for [`Module::translated_func_size`] and [`Config::max_translated_func_size`].
+
+use wasmi::{
+    errors::{EnforcedLimitsError, ErrorKind},
+    CompilationMode,
+    Config,
+    Engine,
+    Instance,
+    Module,
+    Store,
+};
+
+/// A Wasm module with an imported, a small and a large function.
+const WAT: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func (export "small") (param i32) (result i32)
+            (local.get 0)
+        )
+        (func (export "large") (param i32) (result i32)
+            (block (block (block (block (block (block (block (block
+                (br_table 0 1 2 3 4 5 6 7 0 1 2 3 4 5 6 7 (local.get 0))
+            ) ) ) ) ) ) ) )
+            (i32.add (local.get 0) (i32.const 100000))
+        )
+    )
+"#;
+
+/// Compiles [`WAT`] using `mode` and the optional `max_translated_func_size` limit.
+fn compile(mode: CompilationMode, limit: Option<usize>) -> Result<Module, wasmi::Error> {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    if let Some(limit) = limit {
+        config.max_translated_func_size(limit);
+    }
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(&engine, &wasm[..])
+}
+
+fn assert_too_large(error: wasmi::Error, expected: usize) {
+    match error.kind() {
+        ErrorKind::Limits(EnforcedLimitsError::TranslatedFuncTooLarge { limit }) => {
+            assert_eq!(*limit, expected)
+        }
+        _ => panic!("expected translated function too large error but found: {error}"),
+    }
+}
+
+#[test]
+fn translated_func_size_works() {
+    let module = compile(CompilationMode::Eager, None).unwrap();
+    assert_eq!(module.translated_func_size(0).unwrap(), None);
+    let small = module.translated_func_size(1).unwrap().unwrap();
+    let large = module.translated_func_size(2).unwrap().unwrap();
+    assert_eq!(module.translated_func_size(3).unwrap(), None);
+    assert!(0 < small && small < large);
+    assert_eq!(module.translated_size().unwrap(), small + large);
+}
+
+#[test]
+fn translated_func_size_is_independent_of_compilation_mode() {
+    let eager = compile(CompilationMode::Eager, None).unwrap();
+    let lazy = compile(CompilationMode::Lazy, None).unwrap();
+    for index in 0..3 {
+        assert_eq!(
+            eager.translated_func_size(index).unwrap(),
+            lazy.translated_func_size(index).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn max_translated_func_size_works() {
+    let module = compile(CompilationMode::Eager, None).unwrap();
+    let small = module.translated_func_size(1).unwrap().unwrap();
+    let large = module.translated_func_size(2).unwrap().unwrap();
+    compile(CompilationMode::Eager, Some(large)).unwrap();
+    assert_too_large(
+        compile(CompilationMode::Eager, Some(large - 1)).unwrap_err(),
+        large - 1,
+    );
+    assert_too_large(
+        compile(CompilationMode::Eager, Some(small - 1)).unwrap_err(),
+        small - 1,
+    );
+}
+
+#[test]
+fn max_translated_func_size_with_lazy_compilation() {
+    let eager = compile(CompilationMode::Eager, None).unwrap();
+    let limit = eager.translated_func_size(2).unwrap().unwrap() - 1;
+    let module = compile(CompilationMode::Lazy, Some(limit)).unwrap();
+    let mut store = Store::new(module.engine(), ());
+    let host = wasmi::Func::wrap(&mut store, || {});
+    let instance = Instance::new(&mut store, &module, &[host.into()]).unwrap();
+    let small = instance
+        .get_typed_func::<i32, i32>(&store, "small")
+        .unwrap();
+    assert_eq!(small.call(&mut store, 5).unwrap(), 5);
+    let large = instance
+        .get_typed_func::<i32, i32>(&store, "large")
+        .unwrap();
+    assert_too_large(large.call(&mut store, 5).unwrap_err(), limit);
+    // Note: Subsequent uses of a function that failed to compile lazily only report the failure.
+    assert!(module.translated_func_size(2).is_err());
+}
@@ -0,0 +1,127 @@
+//! Tests for converting traps of designated functions into results via [`TrapHandler`].
+
+use wasmi::{core::TrapCode, Engine, Func, Instance, Linker, Module, Store, TrapHandler, Val};
+
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (func (export "div") (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func $load (export "load") (param i32) (result i32)
+            (i32.store (i32.const 0) (i32.const 7))
+            (i32.load (local.get 0))
+        )
+        (func (export "nested") (param i32) (result i32)
+            (call $load (local.get 0))
+        )
+        (func (export "abort")
+            (unreachable)
+        )
+    )
+"#;
+
+/// Error codes of the result-code ABI of the test module.
+const ERR_DIV_BY_ZERO: i32 = -1;
+const ERR_OUT_OF_BOUNDS: i32 = -2;
+
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, WASM).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+fn result_code(code: TrapCode) -> Option<Vec<Val>> {
+    let code = match code {
+        TrapCode::IntegerDivisionByZero => ERR_DIV_BY_ZERO,
+        TrapCode::MemoryOutOfBounds => ERR_OUT_OF_BOUNDS,
+        _ => return None,
+    };
+    Some(vec![Val::I32(code)])
+}
+
+fn func(store: &Store<()>, instance: Instance, name: &str) -> Func {
+    instance.get_func(store, name).unwrap()
+}
+
+#[test]
+fn converts_selected_traps() {
+    let (mut store, instance) = setup();
+    let mut handler = TrapHandler::new();
+    handler
+        .handle(func(&store, instance, "div"), result_code)
+        .handle(func(&store, instance, "load"), result_code);
+    store.set_trap_handler(handler);
+    let div = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "div")
+        .unwrap();
+    assert_eq!(div.call(&mut store, (10, 2)).unwrap(), 5);
+    assert_eq!(div.call(&mut store, (10, 0)).unwrap(), ERR_DIV_BY_ZERO);
+    // Traps that are not selected by the handler are propagated.
+    assert_eq!(
+        div.call(&mut store, (i32::MIN, -1))
+            .unwrap_err()
+            .as_trap_code(),
+        Some(TrapCode::IntegerOverflow)
+    );
+    // The untyped API is supported as well.
+    let mut results = [Val::I32(0)];
+    func(&store, instance, "load")
+        .call(&mut store, &[Val::I32(-1)], &mut results)
+        .unwrap();
+    assert_eq!(results[0].i32(), Some(ERR_OUT_OF_BOUNDS));
+    // Side effects of the trapped execution persist.
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(memory.data(&store)[0], 7);
+    // The store can be reused after a handled trap.
+    assert_eq!(div.call(&mut store, (9, 3)).unwrap(), 3);
+}
+
+#[test]
+fn only_designated_funcs() {
+    let (mut store, instance) = setup();
+    let mut handler = TrapHandler::new();
+    handler.handle(func(&store, instance, "load"), result_code);
+    store.set_trap_handler(handler);
+    // The trap of `load` is converted when called from the host.
+    let load = instance.get_typed_func::<i32, i32>(&store, "load").unwrap();
+    assert_eq!(load.call(&mut store, -1).unwrap(), ERR_OUT_OF_BOUNDS);
+    // The trap of `load` propagates when called by the not designated `nested`.
+    let nested = instance
+        .get_typed_func::<i32, i32>(&store, "nested")
+        .unwrap();
+    assert_eq!(
+        nested.call(&mut store, -1).unwrap_err().as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+    // Removing the trap handler restores the default behavior.
+    let handler = store.take_trap_handler().unwrap();
+    assert!(!handler.is_empty());
+    assert_eq!(
+        load.call(&mut store, -1).unwrap_err().as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+}
+
+#[test]
+fn mismatching_results() {
+    let (mut store, instance) = setup();
+    let mut handler = TrapHandler::new();
+    handler
+        .handle(func(&store, instance, "div"), |_| Some(vec![Val::I64(-1)]))
+        .handle(func(&store, instance, "abort"), |_| Some(Vec::new()));
+    store.set_trap_handler(handler);
+    let div = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "div")
+        .unwrap();
+    let error = div.call(&mut store, (1, 0)).unwrap_err();
+    assert!(error.as_trap_code().is_none());
+    let abort = instance.get_typed_func::<(), ()>(&store, "abort").unwrap();
+    abort.call(&mut store, ()).unwrap();
+}
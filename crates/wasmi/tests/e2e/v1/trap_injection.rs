@@ -0,0 +1,95 @@
+//! Tests for injecting traps via [`Store::inject_trap`].
+
+use wasmi::{core::TrapCode, Caller, Engine, Func, Linker, Module, Store, TypedFunc};
+
+const WASM: &str = r#"
+    (module
+        (import "host" "log" (func $log (param i32)))
+        (func $inc (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $log (local.get 0))
+            (call $inc (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] and returns its store and `run` export.
+///
+/// The store data records the values passed to the `log` host function.
+fn setup() -> (Store<Vec<i32>>, TypedFunc<i32, i32>) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, Vec::new());
+    let mut linker = <Linker<Vec<i32>>>::new(&engine);
+    linker
+        .func_wrap("host", "log", |mut caller: Caller<Vec<i32>>, value: i32| {
+            caller.data_mut().push(value);
+        })
+        .unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<i32, i32>(&store, "run")
+        .unwrap();
+    (store, run)
+}
+
+#[test]
+fn inject_trap() {
+    let (mut store, run) = setup();
+    store.inject_trap(TrapCode::OutOfFuel);
+    let error = run.call(&mut store, 1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert!(store.data().is_empty());
+    // Note: injected traps trigger only once.
+    assert_eq!(run.call(&mut store, 2).unwrap(), 3);
+    assert_eq!(store.data(), &[2]);
+}
+
+#[test]
+fn inject_trap_after_calls() {
+    // Each `run` call consists of 3 function calls: `run`, `log` and `inc`.
+    for (calls, logged) in [(1, &[][..]), (2, &[5][..])] {
+        let (mut store, run) = setup();
+        store.inject_trap_after_calls(calls, TrapCode::UnreachableCodeReached);
+        let error = run.call(&mut store, 5).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+        assert_eq!(store.data(), logged);
+    }
+    let (mut store, run) = setup();
+    store.inject_trap_after_calls(4, TrapCode::StackOverflow);
+    assert_eq!(run.call(&mut store, 5).unwrap(), 6);
+    let error = run.call(&mut store, 6).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    assert_eq!(store.data(), &[5]);
+}
+
+#[test]
+fn inject_trap_into_host_func() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let func = Func::wrap(&mut store, || 42_i32)
+        .typed::<(), i32>(&store)
+        .unwrap();
+    store.inject_trap(TrapCode::BadSignature);
+    let error = func.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+    assert_eq!(func.call(&mut store, ()).unwrap(), 42);
+}
+
+#[test]
+fn cancel_injected_trap() {
+    let (mut store, run) = setup();
+    assert_eq!(store.cancel_injected_trap(), None);
+    store.inject_trap(TrapCode::OutOfFuel);
+    store.inject_trap_after_calls(10, TrapCode::MemoryOutOfBounds);
+    assert_eq!(
+        store.cancel_injected_trap(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+    assert_eq!(run.call(&mut store, 1).unwrap(), 2);
+}
@@ -0,0 +1,98 @@
+//! Tests for translating operators of disabled Wasm proposals into traps
+//! via [`UnsupportedProposals::TrapAtUse`].
+
+use wasmi::{
+    core::TrapCode,
+    CompilationMode,
+    Config,
+    Engine,
+    Linker,
+    Module,
+    Store,
+    UnsupportedProposals,
+};
+
+const WASM: &str = r#"
+    (module
+        (memory 1)
+        (func (export "plain") (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func (export "fill") (param i32) (result i32)
+            (if (local.get 0)
+                (then (memory.fill (i32.const 0) (i32.const 1) (i32.const 8)))
+            )
+            (i32.load8_u (i32.const 0))
+        )
+        (func (export "extend") (param i32) (result i32)
+            (i32.extend8_s (local.get 0))
+        )
+        (func (export "tail") (param i32) (result i32)
+            (return_call 0 (local.get 0))
+        )
+    )
+"#;
+
+fn config(mode: UnsupportedProposals, compilation_mode: CompilationMode) -> Config {
+    let mut config = Config::default();
+    config
+        .wasm_bulk_memory(false)
+        .wasm_sign_extension(false)
+        .wasm_tail_call(false)
+        .compilation_mode(compilation_mode)
+        .unsupported_proposals(mode);
+    config
+}
+
+#[test]
+fn reject_by_default() {
+    let engine = Engine::new(&config(
+        UnsupportedProposals::default(),
+        CompilationMode::Eager,
+    ));
+    assert!(Module::new(&engine, WASM).is_err());
+}
+
+#[test]
+fn trap_at_use() {
+    for compilation_mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let engine = Engine::new(&config(UnsupportedProposals::TrapAtUse, compilation_mode));
+        let module = Module::new(&engine, WASM).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let func = |name: &str| instance.get_typed_func::<i32, i32>(&store, name).unwrap();
+        let (plain, fill, extend, tail) =
+            (func("plain"), func("fill"), func("extend"), func("tail"));
+        assert_eq!(plain.call(&mut store, 1).unwrap(), 2);
+        // Functions only trap once they actually execute a disabled operator.
+        assert_eq!(fill.call(&mut store, 0).unwrap(), 0);
+        for (func, input) in [(fill, 1), (extend, 0xFF), (tail, 0)] {
+            let error = func.call(&mut store, input).unwrap_err();
+            assert_eq!(error.as_trap_code(), Some(TrapCode::UnsupportedOperator));
+        }
+    }
+}
+
+#[test]
+fn trap_at_use_still_validates() {
+    let engine = Engine::new(&config(
+        UnsupportedProposals::TrapAtUse,
+        CompilationMode::Eager,
+    ));
+    // The `memory.fill` operator is missing one of its operands.
+    let wasm = r#"
+        (module
+            (memory 1)
+            (func (memory.fill (i32.const 0) (i32.const 1)))
+        )
+    "#;
+    assert!(Module::new(&engine, wasm).is_err());
+}
@@ -0,0 +1,86 @@
+//! Tests for checking the compatibility of module versions via [`Module::check_upgrade_compat`].
+
+use wasmi::{CompatIssue, Engine, Module};
+
+/// The first version of a plugin.
+const V1: &str = r#"
+    (module
+        (import "host" "log" (func (param i32)))
+        (import "host" "config" (global i32))
+        (memory (export "memory") 1 4)
+        (global (mut i32) (i32.const 0))
+        (func (export "run") (param i32) (result i32) (local.get 0))
+        (func (export "reset"))
+    )
+"#;
+
+fn check(new: &str) -> Vec<CompatIssue> {
+    let engine = Engine::default();
+    let old = Module::new(&engine, V1).unwrap();
+    let new = Module::new(&engine, new).unwrap();
+    let report = Module::check_upgrade_compat(&old, &new);
+    assert_eq!(report.is_compatible(), report.issues().len() == 0);
+    report.issues().cloned().collect()
+}
+
+#[test]
+fn compatible_upgrade() {
+    let issues = check(
+        r#"
+        (module
+            (import "host" "log" (func (param i32)))
+            (memory (export "memory") 1 4)
+            (global (mut i32) (i32.const 0))
+            (func (export "run") (param i32) (result i32) (local.get 0))
+            (func (export "reset"))
+            (func (export "extra"))
+        )
+    "#,
+    );
+    assert!(issues.is_empty(), "unexpected issues: {issues:?}");
+}
+
+#[test]
+fn incompatible_upgrade() {
+    let issues = check(
+        r#"
+        (module
+            (import "host" "log" (func (param i64)))
+            (import "host" "time" (func (result i64)))
+            (memory (export "memory") 2 2)
+            (func (export "run") (param i64) (result i32) (i32.const 0))
+        )
+    "#,
+    );
+    let mut issues = issues.iter();
+    assert!(matches!(
+        issues.next(),
+        Some(CompatIssue::ImportTypeChanged { module, name, .. })
+        if &**module == "host" && &**name == "log"
+    ));
+    assert!(matches!(
+        issues.next(),
+        Some(CompatIssue::AddedImport { name, .. }) if &**name == "time"
+    ));
+    assert!(matches!(
+        issues.next(),
+        Some(CompatIssue::RemovedExport { name, .. }) if &**name == "reset"
+    ));
+    assert!(matches!(
+        issues.next(),
+        Some(CompatIssue::ExportTypeChanged { name, .. }) if &**name == "run"
+    ));
+    assert!(matches!(
+        issues.next(),
+        Some(CompatIssue::EntityCountChanged {
+            kind: "globals",
+            old: 1,
+            new: 0
+        })
+    ));
+    assert!(matches!(
+        issues.next(),
+        Some(CompatIssue::MemoryLimitsChanged { index: 0, .. })
+    ));
+    assert!(issues.next().is_none());
+}
@@ -0,0 +1,93 @@
+//! Tests for the `wasmi:clock` host module via [`VirtualClock`].
+#![cfg(feature = "clock")]
+
+use core::time::Duration;
+use wasmi::{Config, Engine, Linker, Module, Store, TypedFunc, VirtualClock};
+
+const WASM: &str = r#"
+    (module
+        (import "wasmi:clock" "monotonic_now" (func $monotonic_now (result i64)))
+        (import "wasmi:clock" "realtime_now" (func $realtime_now (result i64)))
+        (import "wasmi:clock" "sleep" (func $sleep (param i64)))
+        (func (export "monotonic_now") (result i64)
+            (call $monotonic_now)
+        )
+        (func (export "realtime_now") (result i64)
+            (call $realtime_now)
+        )
+        (func (export "sleep") (param i64) (result i64)
+            (call $sleep (local.get 0))
+            (call $monotonic_now)
+        )
+    )
+"#;
+
+/// The store and the `monotonic_now`, `realtime_now` and `sleep` exports of [`WASM`].
+type Setup = (
+    Store<VirtualClock>,
+    TypedFunc<(), i64>,
+    TypedFunc<(), i64>,
+    TypedFunc<i64, i64>,
+);
+
+/// Instantiates [`WASM`] with `clock` and returns its exports.
+fn setup(consume_fuel: bool, clock: VirtualClock) -> Setup {
+    let mut config = Config::default();
+    config.consume_fuel(consume_fuel);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, clock);
+    if consume_fuel {
+        store.set_fuel(1_000).unwrap();
+    }
+    let mut linker = <Linker<VirtualClock>>::new(&engine);
+    VirtualClock::add_to_linker(&mut linker, |clock| clock).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let monotonic_now = instance.get_typed_func(&store, "monotonic_now").unwrap();
+    let realtime_now = instance.get_typed_func(&store, "realtime_now").unwrap();
+    let sleep = instance.get_typed_func(&store, "sleep").unwrap();
+    (store, monotonic_now, realtime_now, sleep)
+}
+
+#[test]
+fn advances_with_fuel() {
+    let clock = VirtualClock::new(Duration::from_secs(1_000)).with_nanos_per_fuel(10);
+    let (mut store, monotonic_now, realtime_now, _sleep) = setup(true, clock);
+    let first = monotonic_now.call(&mut store, ()).unwrap();
+    let second = monotonic_now.call(&mut store, ()).unwrap();
+    assert!(first > 0);
+    assert_eq!(second, 2 * first);
+    let consumed = 1_000 - store.get_fuel().unwrap();
+    assert_eq!(
+        store.data().elapsed(&store),
+        Duration::from_nanos(consumed * 10)
+    );
+    let realtime = realtime_now.call(&mut store, ()).unwrap();
+    assert_eq!(realtime, 1_000_000_000_000 + 3 * first);
+}
+
+#[test]
+fn deterministic_across_runs() {
+    let run = || {
+        let (mut store, monotonic_now, _realtime_now, sleep) = setup(true, VirtualClock::default());
+        (
+            monotonic_now.call(&mut store, ()).unwrap(),
+            sleep.call(&mut store, 5).unwrap(),
+        )
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn advances_with_steps() {
+    let (mut store, monotonic_now, _realtime_now, sleep) = setup(false, VirtualClock::default());
+    assert_eq!(monotonic_now.call(&mut store, ()).unwrap(), 0);
+    store.data_mut().advance(Duration::from_nanos(7));
+    assert_eq!(monotonic_now.call(&mut store, ()).unwrap(), 7);
+    assert_eq!(sleep.call(&mut store, 1_000).unwrap(), 1_007);
+    assert_eq!(store.data().stepped(), Duration::from_nanos(1_007));
+}
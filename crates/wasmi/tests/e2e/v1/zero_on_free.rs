@@ -0,0 +1,91 @@
+//! Tests for zeroing freed Wasm stack values and linear memory bytes via [`Config::zero_on_free`].
+
+use wasmi::{Caller, Config, Engine, Linker, Memory, MemoryType, Module, Store};
+
+/// A Wasm module that recursively computes Fibonacci numbers via a host function.
+const FIB: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i64 i64) (result i64)))
+        (memory (export "memory") 1)
+        (func $fib (export "fib") (param $n i64) (result i64)
+            (if (result i64) (i64.le_u (local.get $n) (i64.const 1))
+                (then (local.get $n))
+                (else
+                    (call $add
+                        (call $fib (i64.sub (local.get $n) (i64.const 1)))
+                        (call $fib (i64.sub (local.get $n) (i64.const 2)))
+                    )
+                )
+            )
+        )
+        (func (export "grow") (param $delta i32) (result i32)
+            (memory.grow (local.get $delta))
+        )
+    )
+"#;
+
+/// Returns a [`Store`] and [`Linker`] for an [`Engine`] with `zero_on_free`.
+fn setup(zero_on_free: bool) -> (Store<()>, Linker<()>) {
+    let mut config = Config::default();
+    config.zero_on_free(zero_on_free);
+    let engine = Engine::new(&config);
+    let mut linker = Linker::new(&engine);
+    linker
+        .func_wrap("env", "add", |_: Caller<()>, a: i64, b: i64| a + b)
+        .unwrap();
+    (Store::new(&engine, ()), linker)
+}
+
+#[test]
+fn execution_is_unaffected() {
+    let fib = |zero_on_free: bool| {
+        let (mut store, linker) = setup(zero_on_free);
+        let wasm = wat::parse_str(FIB).unwrap();
+        let module = Module::new(store.engine(), &wasm[..]).unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let fib = instance.get_typed_func::<i64, i64>(&store, "fib").unwrap();
+        (0..20)
+            .map(|n| fib.call(&mut store, n).unwrap())
+            .collect::<Vec<_>>()
+    };
+    let expected = fib(false);
+    assert_eq!(expected[19], 4181);
+    assert_eq!(fib(true), expected);
+}
+
+#[test]
+fn memory_growth_retains_contents() {
+    let (mut store, linker) = setup(true);
+    let wasm = wat::parse_str(FIB).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    memory.write(&mut store, 100, b"secret").unwrap();
+    let grow = instance.get_typed_func::<i32, i32>(&store, "grow").unwrap();
+    assert_eq!(grow.call(&mut store, 3).unwrap(), 1);
+    let mut buffer = [0x00_u8; 6];
+    memory.read(&store, 100, &mut buffer).unwrap();
+    assert_eq!(&buffer, b"secret");
+}
+
+#[test]
+fn memory_is_zeroed_on_store_drop() {
+    let buffer: &'static mut [u8] = Box::leak(vec![0x00_u8; 1 << 16].into_boxed_slice());
+    let ptr = buffer.as_ptr();
+    let (mut store, _) = setup(true);
+    let memory =
+        Memory::new_static(&mut store, MemoryType::new(1, Some(1)).unwrap(), buffer).unwrap();
+    memory.data_mut(&mut store)[..6].copy_from_slice(b"secret");
+    drop(store);
+    // Safety: the buffer is leaked and thus still valid after dropping the store.
+    let bytes = unsafe { core::slice::from_raw_parts(ptr, 1 << 16) };
+    assert!(bytes.iter().all(|byte| *byte == 0x00));
+}
@@ -0,0 +1,26 @@
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use wasmi_fuzz::{run_module, FuzzConfig, FuzzModule, FuzzSmithConfig};
+
+#[derive(Debug)]
+pub struct FuzzInput {
+    config: FuzzConfig,
+    module: FuzzModule,
+}
+
+impl<'a> Arbitrary<'a> for FuzzInput {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let config = FuzzConfig::arbitrary(u)?;
+        let fuzz_config = FuzzSmithConfig::arbitrary(u)?;
+        let module = FuzzModule::new(fuzz_config, u)?;
+        Ok(Self { config, module })
+    }
+}
+
+fuzz_target!(|input: FuzzInput| {
+    let FuzzInput { config, module } = input;
+    let wasm_source = module.wasm();
+    _ = run_module(wasm_source.as_bytes(), &config);
+});